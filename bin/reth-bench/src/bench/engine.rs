@@ -0,0 +1,61 @@
+//! Runs the `reth bench engine` command, which replays blocks as `newPayload` and
+//! `forkchoiceUpdated` calls and reports the latency percentiles of each phase.
+
+use crate::bench::{
+    new_payload_fcu::{replay_blocks, write_results},
+    output::{LatencyPercentiles, PERCENTILES_OUTPUT_SUFFIX},
+};
+use clap::Parser;
+use csv::Writer;
+use reth_cli_runner::CliContext;
+use reth_node_core::args::BenchmarkArgs;
+use tracing::info;
+
+/// `reth benchmark engine` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The RPC url to use for getting the blocks to replay.
+    #[arg(long = "rpc", alias = "rpc-url", value_name = "RPC_URL", verbatim_doc_comment)]
+    rpc_url: String,
+
+    #[command(flatten)]
+    benchmark: BenchmarkArgs,
+}
+
+impl Command {
+    /// Execute `benchmark engine` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let results = replay_blocks(&self.benchmark, self.rpc_url).await?;
+
+        let (new_payload, (fcu, total)): (Vec<_>, (Vec<_>, Vec<_>)) = results
+            .iter()
+            .map(|(_, result)| {
+                (result.new_payload_result.latency, (result.fcu_latency, result.total_latency))
+            })
+            .unzip();
+        let percentiles = [
+            LatencyPercentiles::new("newPayload", new_payload),
+            LatencyPercentiles::new("forkchoiceUpdated", fcu),
+            LatencyPercentiles::new("total", total),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        for phase in &percentiles {
+            info!(%phase);
+        }
+
+        if let Some(path) = &self.benchmark.output {
+            let output_path = path.join(PERCENTILES_OUTPUT_SUFFIX);
+            info!("Writing latency percentiles to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for phase in percentiles {
+                writer.serialize(phase)?;
+            }
+            writer.flush()?;
+        }
+
+        write_results(&self.benchmark, results)
+    }
+}
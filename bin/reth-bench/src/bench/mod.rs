@@ -6,9 +6,11 @@ use reth_node_core::args::LogArgs;
 use reth_tracing::FileWorkerGuard;
 
 mod context;
+mod engine;
 mod new_payload_fcu;
 mod new_payload_only;
 mod output;
+mod rpc;
 
 /// `reth bench` command
 #[derive(Debug, Parser)]
@@ -28,6 +30,14 @@ pub enum Subcommands {
 
     /// Benchmark which only calls subsequent `newPayload` calls.
     NewPayloadOnly(new_payload_only::Command),
+
+    /// Benchmark which replays blocks as `newPayload` and `forkchoiceUpdated` calls, reporting
+    /// the latency percentiles of each phase.
+    Engine(engine::Command),
+
+    /// Benchmark which replays a recorded or synthetic JSON-RPC workload, reporting the latency
+    /// percentiles of each method.
+    Rpc(rpc::Command),
 }
 
 impl BenchmarkCommand {
@@ -39,6 +49,8 @@ impl BenchmarkCommand {
         match self.command {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
+            Subcommands::Engine(command) => command.execute(ctx).await,
+            Subcommands::Rpc(command) => command.execute(ctx).await,
         }
     }
 
@@ -14,6 +14,12 @@ pub(crate) const COMBINED_OUTPUT_SUFFIX: &str = "combined_latency.csv";
 /// This is the suffix for new payload output csv files.
 pub(crate) const NEW_PAYLOAD_OUTPUT_SUFFIX: &str = "new_payload_latency.csv";
 
+/// This is the suffix for RPC request output csv files.
+pub(crate) const RPC_OUTPUT_SUFFIX: &str = "rpc_latency.csv";
+
+/// This is the suffix for latency percentile output csv files.
+pub(crate) const PERCENTILES_OUTPUT_SUFFIX: &str = "latency_percentiles.csv";
+
 /// This represents the results of a single `newPayload` call in the benchmark, containing the gas
 /// used and the `newPayload` latency.
 #[derive(Debug)]
@@ -177,6 +183,93 @@ impl Serialize for TotalGasRow {
     }
 }
 
+/// This represents the result of a single JSON-RPC request in the `rpc` benchmark.
+#[derive(Debug)]
+pub(crate) struct RpcRequestResult {
+    /// The called method.
+    pub(crate) method: String,
+    /// The latency of the request.
+    pub(crate) latency: Duration,
+    /// Whether the node responded with a result rather than an error.
+    pub(crate) success: bool,
+}
+
+/// This serializes the latency of the [`RpcRequestResult`] as microseconds.
+impl Serialize for RpcRequestResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let mut state = serializer.serialize_struct("RpcRequestResult", 3)?;
+        state.serialize_field("method", &self.method)?;
+        state.serialize_field("latency", &self.latency.as_micros())?;
+        state.serialize_field("success", &self.success)?;
+        state.end()
+    }
+}
+
+/// This represents the latency percentiles of one phase of the benchmark, like the `newPayload`
+/// calls or the requests of one RPC method.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LatencyPercentiles {
+    /// The name of the phase.
+    pub(crate) phase: String,
+    /// The median latency.
+    pub(crate) p50: Duration,
+    /// The 90th percentile latency.
+    pub(crate) p90: Duration,
+    /// The 99th percentile latency.
+    pub(crate) p99: Duration,
+    /// The maximum latency.
+    pub(crate) max: Duration,
+}
+
+impl LatencyPercentiles {
+    /// Computes the nearest-rank percentiles of the given latencies, returns `None` if there are
+    /// no latencies.
+    pub(crate) fn new(phase: impl Into<String>, mut latencies: Vec<Duration>) -> Option<Self> {
+        latencies.sort_unstable();
+        let max = *latencies.last()?;
+        let percentile = |p: usize| {
+            let rank = (latencies.len() * p).div_ceil(100).max(1);
+            latencies[rank - 1]
+        };
+        Some(Self {
+            phase: phase.into(),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max,
+        })
+    }
+}
+
+impl std::fmt::Display for LatencyPercentiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} latency p50: {:?}, p90: {:?}, p99: {:?}, max: {:?}",
+            self.phase, self.p50, self.p90, self.p99, self.max
+        )
+    }
+}
+
+/// This serializes the latencies of the [`LatencyPercentiles`] as microseconds.
+impl Serialize for LatencyPercentiles {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let mut state = serializer.serialize_struct("LatencyPercentiles", 5)?;
+        state.serialize_field("phase", &self.phase)?;
+        state.serialize_field("p50", &self.p50.as_micros())?;
+        state.serialize_field("p90", &self.p90.as_micros())?;
+        state.serialize_field("p99", &self.p99.as_micros())?;
+        state.serialize_field("max", &self.max.as_micros())?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,4 +296,25 @@ mod tests {
         let second_line = result.next().unwrap().unwrap();
         assert_eq!(second_line, expected_second_line);
     }
+
+    #[test]
+    fn test_latency_percentiles() {
+        assert_eq!(LatencyPercentiles::new("newPayload", vec![]), None);
+
+        let latencies = (1..=200).rev().map(Duration::from_millis).collect();
+        let percentiles = LatencyPercentiles::new("newPayload", latencies).unwrap();
+        assert_eq!(percentiles.p50, Duration::from_millis(100));
+        assert_eq!(percentiles.p90, Duration::from_millis(180));
+        assert_eq!(percentiles.p99, Duration::from_millis(198));
+        assert_eq!(percentiles.max, Duration::from_millis(200));
+
+        let single = LatencyPercentiles::new("fcu", vec![Duration::from_micros(7)]).unwrap();
+        assert_eq!(single.p50, Duration::from_micros(7));
+        assert_eq!(single.p99, Duration::from_micros(7));
+
+        let mut writer = Writer::from_writer(vec![]);
+        writer.serialize(single).unwrap();
+        let result = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(result, "phase,p50,p90,p99,max\nfcu,7,7,7,7\n");
+    }
 }
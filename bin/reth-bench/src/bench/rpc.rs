@@ -0,0 +1,484 @@
+//! Runs the `reth bench rpc` command, which replays a JSON-RPC workload against a node and reports
+//! the latency percentiles of each method.
+
+use crate::bench::output::{
+    LatencyPercentiles, RpcRequestResult, PERCENTILES_OUTPUT_SUFFIX, RPC_OUTPUT_SUFFIX,
+};
+use clap::Parser;
+use csv::Writer;
+use eyre::WrapErr;
+use futures::{stream, StreamExt};
+use reqwest::{header::CONTENT_TYPE, Client};
+use reth_cli_runner::CliContext;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    num::NonZeroUsize,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info};
+
+/// The number of requests that are sent for a synthetic mix if `--requests` is not set.
+const DEFAULT_SYNTHETIC_REQUESTS: usize = 10_000;
+
+/// `reth benchmark rpc` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The RPC url of the node to benchmark.
+    #[arg(long = "rpc", value_name = "RPC_URL", default_value = "http://localhost:8545")]
+    rpc_url: String,
+
+    /// A file with a recorded workload to replay, containing one JSON-RPC request per line.
+    ///
+    /// Without a workload, a synthetic mix of requests is generated from the recent blocks of the
+    /// node.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    workload: Option<PathBuf>,
+
+    /// The weights of the request kinds of the synthetic mix, as comma separated `kind=weight`
+    /// pairs. The kinds are `call` (`eth_call`), `logs` (`eth_getLogs`) and `trace`
+    /// (`debug_traceTransaction`).
+    #[arg(
+        long,
+        value_name = "MIX",
+        default_value = "call=60,logs=30,trace=10",
+        conflicts_with = "workload"
+    )]
+    mix: RequestMix,
+
+    /// The number of recent blocks the synthetic requests are generated from.
+    #[arg(long, value_name = "BLOCKS", default_value_t = 64, conflicts_with = "workload")]
+    blocks: u64,
+
+    /// The total number of requests to send.
+    ///
+    /// Defaults to the number of requests in the workload, or 10000 for a synthetic mix. A
+    /// workload is repeated if it contains fewer requests.
+    #[arg(long, value_name = "REQUESTS", verbatim_doc_comment)]
+    requests: Option<usize>,
+
+    /// The maximum number of requests in flight at the same time.
+    #[arg(long, value_name = "CONCURRENCY", default_value = "16")]
+    concurrency: NonZeroUsize,
+
+    /// The path to the output directory for the latency of every request and the percentiles.
+    #[arg(long, short, value_name = "BENCHMARK_OUTPUT")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `benchmark rpc` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let client = Client::new();
+
+        let requests = match &self.workload {
+            Some(path) => {
+                info!("Loading RPC workload from file: {:?}", path);
+                let workload = std::fs::read_to_string(path)
+                    .wrap_err_with(|| format!("failed to read workload {path:?}"))?;
+                let workload = parse_workload(&workload)?;
+                let count = self.requests.unwrap_or(workload.len());
+                workload.into_iter().cycle().take(count).collect()
+            }
+            None => {
+                info!(rpc_url = %self.rpc_url, blocks = self.blocks, mix = %self.mix, "Generating synthetic RPC workload");
+                let pools = RequestPools::fetch(&client, &self.rpc_url, self.blocks).await?;
+                pools.mix(&self.mix, self.requests.unwrap_or(DEFAULT_SYNTHETIC_REQUESTS))?
+            }
+        };
+        if requests.is_empty() {
+            eyre::bail!("the workload contains no requests")
+        }
+
+        info!(
+            requests = requests.len(),
+            concurrency = self.concurrency.get(),
+            "Running RPC benchmark against {}",
+            self.rpc_url
+        );
+        let start = Instant::now();
+        let results = stream::iter(requests.into_iter().enumerate())
+            .map(|(id, request)| {
+                let client = &client;
+                let rpc_url = self.rpc_url.as_str();
+                async move {
+                    let start = Instant::now();
+                    let response = send(client, rpc_url, id, &request).await;
+                    let latency = start.elapsed();
+                    if let Err(err) = &response {
+                        debug!(method = %request.method, %err, "RPC request failed");
+                    }
+                    RpcRequestResult { method: request.method, latency, success: response.is_ok() }
+                }
+            })
+            .buffer_unordered(self.concurrency.get())
+            .collect::<Vec<_>>()
+            .await;
+        let total_duration = start.elapsed();
+
+        let mut latencies = BTreeMap::<_, Vec<Duration>>::new();
+        let mut errors = BTreeMap::<_, usize>::new();
+        for result in &results {
+            latencies.entry(result.method.clone()).or_default().push(result.latency);
+            *errors.entry(result.method.clone()).or_default() += usize::from(!result.success);
+        }
+        let percentiles = latencies
+            .into_iter()
+            .filter_map(|(method, latencies)| {
+                let requests = latencies.len();
+                let percentiles = LatencyPercentiles::new(method, latencies)?;
+                info!(requests, errors = errors[&percentiles.phase], "{percentiles}");
+                Some(percentiles)
+            })
+            .collect::<Vec<_>>();
+
+        info!(
+            ?total_duration,
+            requests = results.len(),
+            errors = results.iter().filter(|result| !result.success).count(),
+            "Requests per second: {:.2}",
+            results.len() as f64 / total_duration.as_secs_f64()
+        );
+
+        if let Some(path) = &self.output {
+            let output_path = path.join(RPC_OUTPUT_SUFFIX);
+            info!("Writing RPC latency output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for result in results {
+                writer.serialize(result)?;
+            }
+            writer.flush()?;
+
+            let output_path = path.join(PERCENTILES_OUTPUT_SUFFIX);
+            info!("Writing latency percentiles to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for method in percentiles {
+                writer.serialize(method)?;
+            }
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A JSON-RPC request of a workload.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct RpcRequest {
+    /// The called method.
+    method: String,
+    /// The parameters of the call.
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+impl RpcRequest {
+    fn new(method: &str, params: Value) -> Self {
+        Self { method: method.to_string(), params: Some(params) }
+    }
+}
+
+/// Parses a workload with one JSON-RPC request per line, skipping empty lines.
+///
+/// Any other fields of the requests, like the `id`, are ignored.
+fn parse_workload(workload: &str) -> eyre::Result<Vec<RpcRequest>> {
+    workload
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| eyre::eyre!("invalid request on line {}: {err}", index + 1))
+        })
+        .collect()
+}
+
+/// Sends the request and returns the result, or an error if the request failed or the node
+/// responded with an error.
+async fn send(
+    client: &Client,
+    rpc_url: &str,
+    id: usize,
+    request: &RpcRequest,
+) -> eyre::Result<Value> {
+    let mut body = json!({ "jsonrpc": "2.0", "id": id, "method": request.method });
+    if let Some(params) = &request.params {
+        body["params"] = params.clone();
+    }
+
+    let response = client
+        .post(rpc_url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&body)?)
+        .send()
+        .await?
+        .error_for_status()?;
+    let mut response: Value = serde_json::from_slice(&response.bytes().await?)?;
+    if let Some(error) = response.get("error") {
+        eyre::bail!("{error}")
+    }
+    Ok(response["result"].take())
+}
+
+/// The kinds of requests of a synthetic mix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RequestKind {
+    /// `eth_call` of a transaction on the state of its parent block.
+    Call,
+    /// `eth_getLogs` of a block.
+    Logs,
+    /// `debug_traceTransaction` with the call tracer.
+    Trace,
+}
+
+impl RequestKind {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::Logs => "logs",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for RequestKind {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "call" => Ok(Self::Call),
+            "logs" => Ok(Self::Logs),
+            "trace" => Ok(Self::Trace),
+            _ => eyre::bail!("unknown request kind {s:?}, expected one of call, logs, trace"),
+        }
+    }
+}
+
+/// The weights of the request kinds of a synthetic mix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RequestMix {
+    weights: Vec<(RequestKind, u32)>,
+}
+
+impl RequestMix {
+    /// Returns the kind of the request at the given index.
+    ///
+    /// The kinds are spread in proportion to their weights, deterministically so runs are
+    /// comparable.
+    fn kind_at(&self, index: usize) -> RequestKind {
+        let total = self.weights.iter().map(|(_, weight)| *weight as usize).sum::<usize>();
+        let mut slot = index % total;
+        for (kind, weight) in &self.weights {
+            if slot < *weight as usize {
+                return *kind
+            }
+            slot -= *weight as usize;
+        }
+        unreachable!("the slot is less than the total weight")
+    }
+}
+
+impl FromStr for RequestMix {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = Vec::new();
+        for pair in s.split(',') {
+            let (kind, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| eyre::eyre!("expected `kind=weight`, got {pair:?}"))?;
+            let kind = kind.trim().parse::<RequestKind>()?;
+            if weights.iter().any(|(existing, _)| *existing == kind) {
+                eyre::bail!("duplicate request kind {:?}", kind.as_str())
+            }
+            weights.push((kind, weight.trim().parse()?));
+        }
+        weights.retain(|(_, weight)| *weight > 0);
+        if weights.is_empty() {
+            eyre::bail!("the mix must have at least one request kind with a positive weight")
+        }
+        Ok(Self { weights })
+    }
+}
+
+impl fmt::Display for RequestMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (kind, weight)) in self.weights.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={weight}", kind.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// The synthetic requests of each kind that were generated from the recent blocks.
+#[derive(Debug, Default)]
+struct RequestPools {
+    pools: BTreeMap<RequestKind, Vec<RpcRequest>>,
+}
+
+impl RequestPools {
+    /// Generates the requests from the transactions of the given number of recent blocks.
+    async fn fetch(client: &Client, rpc_url: &str, blocks: u64) -> eyre::Result<Self> {
+        let latest = send(client, rpc_url, 0, &RpcRequest::new("eth_blockNumber", json!([])))
+            .await?
+            .as_str()
+            .and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| eyre::eyre!("invalid eth_blockNumber response"))?;
+
+        let mut pools = Self::default();
+        for number in latest.saturating_sub(blocks.saturating_sub(1)).max(1)..=latest {
+            let block = send(
+                client,
+                rpc_url,
+                0,
+                &RpcRequest::new("eth_getBlockByNumber", json!([format!("{number:#x}"), true])),
+            )
+            .await?;
+            pools.add_block(number, &block);
+        }
+        Ok(pools)
+    }
+
+    /// Adds the requests of the block, as returned by `eth_getBlockByNumber` with full
+    /// transactions.
+    fn add_block(&mut self, number: u64, block: &Value) {
+        self.push(
+            RequestKind::Logs,
+            RpcRequest::new(
+                "eth_getLogs",
+                json!([{ "fromBlock": format!("{number:#x}"), "toBlock": format!("{number:#x}") }]),
+            ),
+        );
+
+        let transactions = block["transactions"].as_array().map(Vec::as_slice).unwrap_or_default();
+        for tx in transactions {
+            if !tx["to"].is_null() {
+                let call = json!({
+                    "from": tx["from"],
+                    "to": tx["to"],
+                    "gas": tx["gas"],
+                    "value": tx["value"],
+                    "input": tx["input"],
+                });
+                self.push(
+                    RequestKind::Call,
+                    RpcRequest::new("eth_call", json!([call, format!("{:#x}", number - 1)])),
+                );
+            }
+            self.push(
+                RequestKind::Trace,
+                RpcRequest::new(
+                    "debug_traceTransaction",
+                    json!([tx["hash"], { "tracer": "callTracer" }]),
+                ),
+            );
+        }
+    }
+
+    fn push(&mut self, kind: RequestKind, request: RpcRequest) {
+        self.pools.entry(kind).or_default().push(request);
+    }
+
+    /// Returns the given number of requests, with the kinds spread according to the mix.
+    ///
+    /// The requests of each kind are cycled through in order.
+    fn mix(&self, mix: &RequestMix, count: usize) -> eyre::Result<Vec<RpcRequest>> {
+        if let Some((kind, _)) = mix.weights.iter().find(|(kind, _)| !self.pools.contains_key(kind))
+        {
+            eyre::bail!(
+                "the recent blocks contain no transactions for {:?} requests",
+                kind.as_str()
+            )
+        }
+
+        let mut next = BTreeMap::<RequestKind, usize>::new();
+        Ok((0..count)
+            .map(|index| {
+                let kind = mix.kind_at(index);
+                let pool = &self.pools[&kind];
+                let next = next.entry(kind).or_default();
+                let request = pool[*next % pool.len()].clone();
+                *next += 1;
+                request
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request_mix() {
+        let mix = "call=3, logs=1,trace=0".parse::<RequestMix>().unwrap();
+        assert_eq!(mix.to_string(), "call=3,logs=1");
+        let kinds = (0..8).map(|index| mix.kind_at(index)).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [RequestKind::Call, RequestKind::Call, RequestKind::Call, RequestKind::Logs].repeat(2)
+        );
+
+        assert!("call=1,call=2".parse::<RequestMix>().is_err());
+        assert!("call=0".parse::<RequestMix>().is_err());
+        assert!("estimate=1".parse::<RequestMix>().is_err());
+        assert!("call".parse::<RequestMix>().is_err());
+    }
+
+    #[test]
+    fn parse_recorded_workload() {
+        let workload = r#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"}
+
+{"jsonrpc":"2.0","id":2,"method":"eth_getBalance","params":["0x0000000000000000000000000000000000000000","latest"]}
+"#;
+        let requests = parse_workload(workload).unwrap();
+        assert_eq!(
+            requests,
+            vec![
+                RpcRequest { method: "eth_blockNumber".to_string(), params: None },
+                RpcRequest::new(
+                    "eth_getBalance",
+                    json!(["0x0000000000000000000000000000000000000000", "latest"])
+                ),
+            ]
+        );
+
+        let err = parse_workload("{\"method\":\"eth_chainId\"}\nnot json").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn mix_synthetic_requests() {
+        let mut pools = RequestPools::default();
+        let block = json!({
+            "transactions": [
+                { "hash": "0x01", "from": "0x0a", "to": "0x0b", "gas": "0x5208", "value": "0x0", "input": "0x" },
+                { "hash": "0x02", "from": "0x0a", "to": null, "gas": "0x5208", "value": "0x0", "input": "0x" },
+            ]
+        });
+        pools.add_block(10, &block);
+        assert_eq!(pools.pools[&RequestKind::Call].len(), 1);
+        assert_eq!(pools.pools[&RequestKind::Logs].len(), 1);
+        assert_eq!(pools.pools[&RequestKind::Trace].len(), 2);
+
+        let mix = "logs=1,trace=1".parse().unwrap();
+        let methods = pools
+            .mix(&mix, 4)
+            .unwrap()
+            .into_iter()
+            .map(|request| (request.method, request.params.unwrap()[0].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(methods[1], ("debug_traceTransaction".to_string(), json!("0x01")));
+        assert_eq!(methods[3], ("debug_traceTransaction".to_string(), json!("0x02")));
+        assert_eq!(methods[0].0, "eth_getLogs");
+
+        assert!(RequestPools::default().mix(&mix, 1).is_err());
+    }
+}
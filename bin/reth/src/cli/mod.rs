@@ -2,14 +2,15 @@
 
 use crate::{
     args::{
-        utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+        utils::{chain_help, chain_value_parser},
         LogArgs,
     },
     commands::{
-        config_cmd, debug_cmd, dump_genesis, import, init_cmd, init_state,
+        chain, config_cmd, debug_cmd, dump_genesis, import, import_receipts, init_cmd, init_state,
         node::{self, NoArgs},
-        p2p, prune, recover, stage, test_vectors,
+        p2p, prune, recover, stage, stateless_verify, test_vectors, validate_builder_submissions,
     },
+    core::manifest::DEFAULT_CHAIN,
     version::{LONG_VERSION, SHORT_VERSION},
 };
 use clap::{value_parser, Parser, Subcommand};
@@ -46,7 +47,7 @@ pub struct Cli<Ext: clap::Args + fmt::Debug = NoArgs> {
         long,
         value_name = "CHAIN_OR_PATH",
         long_help = chain_help(),
-        default_value = SUPPORTED_CHAINS[0],
+        default_value = DEFAULT_CHAIN,
         value_parser = chain_value_parser,
         global = true,
     )]
@@ -144,13 +145,16 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
         info!(target: "reth::cli", "Initialized tracing, debug log directory: {}", self.logs.log_file_directory);
 
         let runner = CliRunner::default();
-        match self.command {
-            Commands::Node(command) => {
-                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
-            }
+        let result = match self.command {
+            Commands::Node(command) => runner
+                .with_graceful_shutdown_timeout(command.shutdown.timeout())
+                .run_command_until_exit(|ctx| command.execute(ctx, launcher)),
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::InitState(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::ImportReceipts(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute())
+            }
             #[cfg(feature = "optimism")]
             Commands::ImportOp(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
@@ -163,10 +167,23 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Chain(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute()),
-        }
+            Commands::StatelessVerify(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute())
+            }
+            Commands::ValidateBuilderSubmissions(command) => runner
+                .with_graceful_shutdown_timeout(command.shutdown_timeout())
+                .run_command_until_exit(|ctx| command.execute(ctx)),
+        };
+
+        // export the spans that are still buffered
+        #[cfg(feature = "otlp")]
+        reth_tracing::shutdown_otlp();
+
+        result
     }
 
     /// Initializes tracing with the configured options.
@@ -194,6 +211,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// This imports RLP encoded receipts from a file, validating them against the headers.
+    #[command(name = "import-receipts")]
+    ImportReceipts(import_receipts::ImportReceiptsCommand),
     /// This syncs RLP encoded OP blocks below Bedrock from a file, without executing.
     #[cfg(feature = "optimism")]
     #[command(name = "import-op")]
@@ -219,6 +239,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
+    /// Manage the chains of the datadirs
+    #[command(name = "chain")]
+    Chain(chain::Command),
     /// Various debug routines
     #[command(name = "debug")]
     Debug(debug_cmd::Command),
@@ -228,12 +251,18 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand),
+    /// Re-execute a block from a witness without a database
+    #[command(name = "stateless-verify")]
+    StatelessVerify(stateless_verify::StatelessVerifyCommand),
+    /// Run the node as a block submission validation service for relays
+    #[command(name = "validate-builder-submissions")]
+    ValidateBuilderSubmissions(validate_builder_submissions::Command),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::args::ColorMode;
+    use crate::args::{utils::SUPPORTED_CHAINS, ColorMode};
     use clap::CommandFactory;
 
     #[test]
@@ -0,0 +1,101 @@
+//! `reth chain` command.
+
+use crate::{
+    args::utils::{chain_help, chain_value_parser},
+    core::manifest::{ChainManifest, DatadirChain},
+};
+use clap::{Parser, Subcommand};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// `reth chain` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth chain` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// List the datadirs used by this installation and the chains they belong to
+    List,
+    /// Set the chain that is used if no `--chain` is specified
+    Switch(SwitchCommand),
+}
+
+/// `reth chain switch` command
+#[derive(Debug, Parser)]
+pub struct SwitchCommand {
+    /// The chain to use by default.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(value_name = "CHAIN_OR_PATH", long_help = chain_help())]
+    name: String,
+}
+
+impl Command {
+    /// Execute `chain` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::List => list(),
+            Subcommands::Switch(command) => command.execute(),
+        }
+    }
+}
+
+fn list() -> eyre::Result<()> {
+    let manifest = ChainManifest::load()?;
+    if let Some(chain) = &manifest.default_chain {
+        println!("Default chain: {chain}");
+    }
+    if manifest.datadirs.is_empty() {
+        println!("No datadirs recorded yet");
+        return Ok(())
+    }
+
+    for entry in &manifest.datadirs {
+        let last_used =
+            humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(entry.last_used));
+        let missing = match DatadirChain::read(&entry.path) {
+            Ok(Some(chain)) if chain == entry.chain => "",
+            Ok(Some(_)) => " (reinitialized with another chain)",
+            Ok(None) | Err(_) => " (missing)",
+        };
+        println!("{}{missing}", entry.path.display());
+        println!("  chain:     {}", entry.chain.chain());
+        println!("  genesis:   {}", entry.chain.genesis_hash);
+        println!("  last used: {last_used}");
+    }
+    Ok(())
+}
+
+impl SwitchCommand {
+    fn execute(self) -> eyre::Result<()> {
+        // ensure the chain can be loaded before making it the default
+        let chain_spec = chain_value_parser(&self.name)?;
+
+        let mut manifest = ChainManifest::load()?;
+        manifest.default_chain = Some(self.name);
+        manifest.save()?;
+
+        println!(
+            "Default chain set to {} (genesis {})",
+            chain_spec.chain,
+            chain_spec.genesis_hash()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_switch() {
+        let command = Command::parse_from(["reth", "switch", "sepolia"]);
+        assert!(
+            matches!(command.command, Subcommands::Switch(SwitchCommand { name }) if name == "sepolia")
+        );
+    }
+}
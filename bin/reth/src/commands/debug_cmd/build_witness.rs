@@ -0,0 +1,339 @@
+//! Command for building a witness to execute a block statelessly.
+
+use crate::macros::block_executor;
+use alloy_rlp::Encodable;
+use clap::Parser;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_db::DatabaseError;
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_primitives::{
+    keccak256,
+    revm_primitives::{AccountInfo, Bytecode},
+    Address, BlockNumber, Bytes, TransactionVariant, B256, U256,
+};
+use reth_provider::{BlockNumReader, BlockReader, ChainSpecProvider, HeaderProvider};
+use reth_revm::{database::StateProviderDatabase, Database};
+use reth_stateless::BlockWitness;
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    proof::Proof,
+    HashedPostState, Nibbles,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+use tracing::*;
+
+/// `reth debug build-witness` command
+///
+/// Executes a block of the local database on top of its parent state, records which accounts,
+/// storage slots, contracts and block hashes it accesses, and writes a witness to re-execute the
+/// block without a database, e.g. in a zk prover or with `reth stateless-verify`.
+///
+/// The proofs are generated against the state root of the parent block, by reverting the changes
+/// of all later blocks in memory, which is slow for old blocks and requires the state history of
+/// all blocks after the parent. Besides the nodes along the paths of the accessed keys, the witness
+/// contains the sibling nodes that are needed to collapse the tries when the block deletes keys.
+///
+/// The witness is written as JSON, see [`BlockWitness`] for the format.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The number of the block to build the witness for.
+    #[arg(long)]
+    block: BlockNumber,
+
+    /// The path of the file the witness is written to.
+    #[arg(long, value_name = "FILE")]
+    out: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug build-witness` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        if self.block == 0 {
+            eyre::bail!("the genesis block is not executed")
+        }
+        let parent = self.block - 1;
+
+        let provider = provider_factory.provider()?;
+        let block = provider
+            .block_with_senders(self.block.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre::eyre!("block {} not found in the database", self.block))?;
+        let td = provider
+            .header_td_by_number(self.block)?
+            .ok_or_else(|| eyre::eyre!("total difficulty of block {} not found", self.block))?;
+
+        info!(target: "reth::cli", block = self.block, "Executing block");
+        let mut db = RecordingDatabase::new(StateProviderDatabase::new(
+            provider_factory.history_by_block_number(parent)?,
+        ));
+        let output = block_executor!(provider_factory.chain_spec())
+            .executor(&mut db)
+            .execute((&block, td).into())?;
+
+        let tx = provider.tx_ref();
+        let tip = provider.last_block_number()?;
+        let parent_header = provider
+            .header_by_number(parent)?
+            .ok_or_else(|| eyre::eyre!("header of block {parent} not found"))?;
+        info!(target: "reth::cli", from = parent + 1, to = tip, "Reverting the state");
+        let reverts = HashedPostState::from_revert_range(tx, parent + 1..=tip)?;
+        let root = reverts.state_root(tx)?;
+        if root != parent_header.state_root {
+            eyre::bail!(
+                "the reverted state root {root} doesn't match the state root {} of the parent \
+                 block, is the state history pruned?",
+                parent_header.state_root
+            )
+        }
+        let reverts_sorted = reverts.clone().into_sorted();
+        let cursor_factory = HashedPostStateCursorFactory::new(tx, &reverts_sorted);
+
+        let mut targets = db
+            .keys
+            .iter()
+            .map(|(address, slots)| (keccak256(address), slots.iter().map(keccak256).collect()))
+            .collect::<BTreeMap<B256, BTreeSet<B256>>>();
+
+        // a branch node left with a single child by deletions is collapsed into it, which requires
+        // the node of the remaining child
+        let changes = HashedPostState::from_bundle_state(&output.state.state);
+        let deleted_accounts = changes
+            .accounts
+            .iter()
+            .filter_map(|(hashed_address, account)| account.is_none().then_some(*hashed_address))
+            .collect::<BTreeSet<_>>();
+        let mut account_cursor = cursor_factory.hashed_account_cursor()?;
+        for hashed_address in &deleted_accounts {
+            if let Some(sibling) =
+                remaining_sibling(&mut account_cursor, *hashed_address, &deleted_accounts)?
+            {
+                targets.entry(sibling).or_default();
+            }
+        }
+        for (hashed_address, storage) in &changes.storages {
+            // wiped storage tries are rebuilt from scratch
+            if storage.wiped {
+                continue
+            }
+            let deleted_slots = storage
+                .storage
+                .iter()
+                .filter_map(|(hashed_slot, value)| value.is_zero().then_some(*hashed_slot))
+                .collect::<BTreeSet<_>>();
+            let mut storage_cursor = cursor_factory.hashed_storage_cursor(*hashed_address)?;
+            for hashed_slot in &deleted_slots {
+                if let Some(sibling) =
+                    remaining_sibling(&mut storage_cursor, *hashed_slot, &deleted_slots)?
+                {
+                    targets.entry(*hashed_address).or_default().insert(sibling);
+                }
+            }
+        }
+
+        info!(target: "reth::cli", accounts = targets.len(), "Generating proofs");
+        let state = Proof::from_tx(tx)
+            .with_hashed_cursor_factory(cursor_factory)
+            .with_prefix_sets_mut(reverts.construct_prefix_sets())
+            .multiproof_nodes(&targets)?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+
+        let oldest_ancestor = db.block_hashes.first().copied().unwrap_or(parent).min(parent);
+        let headers = provider.headers_range(oldest_ancestor..=parent)?;
+
+        let witness = BlockWitness {
+            chain_id: provider_factory.chain_spec().chain.id(),
+            total_difficulty: td,
+            block: rlp(&block.block.unseal()),
+            headers: headers.iter().map(rlp).collect(),
+            state: state.into_iter().collect(),
+            codes: db.codes.into_values().collect(),
+            keys: db.keys,
+        };
+        reth_fs_util::write(&self.out, serde_json::to_string_pretty(&witness)?)?;
+        info!(
+            target: "reth::cli",
+            path = ?self.out,
+            nodes = witness.state.len(),
+            codes = witness.codes.len(),
+            headers = witness.headers.len(),
+            "Witness written"
+        );
+        Ok(())
+    }
+}
+
+fn rlp(value: &impl Encodable) -> Bytes {
+    let mut buf = Vec::with_capacity(value.length());
+    value.encode(&mut buf);
+    buf.into()
+}
+
+/// Returns a key that remains in the trie after the deletions and shares the longest prefix with
+/// the deleted key, if any.
+///
+/// If deleting the key leaves its deepest branch node with a single child, the remaining key lies
+/// in that child, so the proof of the remaining key contains the node the branch collapses into.
+fn remaining_sibling<C: HashedCursor>(
+    cursor: &mut C,
+    key: B256,
+    deleted: &BTreeSet<B256>,
+) -> Result<Option<B256>, DatabaseError> {
+    // the subtrie of a shorter prefix contains the subtrie of a longer one, so the longest prefix
+    // whose subtrie contains a remaining key can be searched with bisection
+    let Some(mut sibling) = first_remaining(cursor, key, 0, deleted)? else { return Ok(None) };
+    let (mut shared, mut unshared) = (0, 64);
+    while unshared - shared > 1 {
+        let mid = (shared + unshared) / 2;
+        match first_remaining(cursor, key, mid, deleted)? {
+            Some(remaining) => {
+                sibling = remaining;
+                shared = mid;
+            }
+            None => unshared = mid,
+        }
+    }
+    Ok(Some(sibling))
+}
+
+/// Returns the first key that shares the first nibbles with the given key and isn't deleted, other
+/// than the key itself.
+fn first_remaining<C: HashedCursor>(
+    cursor: &mut C,
+    key: B256,
+    nibbles: usize,
+    deleted: &BTreeSet<B256>,
+) -> Result<Option<B256>, DatabaseError> {
+    let prefix = Nibbles::unpack(key).slice(..nibbles);
+    let mut start = B256::ZERO;
+    start[..nibbles / 2].copy_from_slice(&key[..nibbles / 2]);
+    if nibbles % 2 == 1 {
+        start[nibbles / 2] = key[nibbles / 2] & 0xf0;
+    }
+
+    let mut entry = cursor.seek(start)?;
+    while let Some((candidate, _)) = entry {
+        if !Nibbles::unpack(candidate).has_prefix(&prefix) {
+            break
+        }
+        if candidate != key && !deleted.contains(&candidate) {
+            return Ok(Some(candidate))
+        }
+        entry = cursor.next()?;
+    }
+    Ok(None)
+}
+
+/// A [`Database`] that records the accounts, storage slots, contracts and block hashes that are
+/// accessed.
+#[derive(Debug)]
+struct RecordingDatabase<DB> {
+    inner: DB,
+    keys: BTreeMap<Address, BTreeSet<B256>>,
+    codes: BTreeMap<B256, Bytes>,
+    block_hashes: BTreeSet<BlockNumber>,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    const fn new(inner: DB) -> Self {
+        Self { inner, keys: BTreeMap::new(), codes: BTreeMap::new(), block_hashes: BTreeSet::new() }
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.keys.entry(address).or_default();
+        self.inner.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.inner.code_by_hash(code_hash)?;
+        self.codes.insert(code_hash, code.original_bytes());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.keys.entry(address).or_default().insert(B256::from(index));
+        self.inner.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.block_hashes.insert(number.saturating_to());
+        self.inner.block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex_literal::hex;
+    use reth_revm::db::EmptyDB;
+    use std::ops::Bound;
+
+    /// A cursor over a set of keys.
+    struct KeysCursor {
+        keys: BTreeSet<B256>,
+        position: Option<B256>,
+    }
+
+    impl HashedCursor for KeysCursor {
+        type Value = ();
+
+        fn seek(&mut self, key: B256) -> Result<Option<(B256, ())>, DatabaseError> {
+            self.position = self.keys.range(key..).next().copied();
+            Ok(self.position.map(|key| (key, ())))
+        }
+
+        fn next(&mut self) -> Result<Option<(B256, ())>, DatabaseError> {
+            let Some(position) = self.position else { return Ok(None) };
+            self.position =
+                self.keys.range((Bound::Excluded(position), Bound::Unbounded)).next().copied();
+            Ok(self.position.map(|key| (key, ())))
+        }
+    }
+
+    #[test]
+    fn records_accesses() {
+        let mut db = RecordingDatabase::new(EmptyDB::default());
+        let address = Address::with_last_byte(1);
+        db.basic(address).unwrap();
+        db.storage(address, U256::from(7)).unwrap();
+        db.storage(Address::with_last_byte(2), U256::from(1)).unwrap();
+        db.block_hash(U256::from(10)).unwrap();
+        db.block_hash(U256::from(5)).unwrap();
+
+        assert_eq!(
+            db.keys,
+            BTreeMap::from([
+                (address, BTreeSet::from([B256::with_last_byte(7)])),
+                (Address::with_last_byte(2), BTreeSet::from([B256::with_last_byte(1)])),
+            ])
+        );
+        assert_eq!(db.block_hashes.first(), Some(&5));
+    }
+
+    #[test]
+    fn finds_remaining_sibling() {
+        let a = B256::new(hex!("1000000000000000000000000000000000000000000000000000000000000000"));
+        let b = B256::new(hex!("1100000000000000000000000000000000000000000000000000000000000000"));
+        let c = B256::new(hex!("2000000000000000000000000000000000000000000000000000000000000000"));
+        let mut cursor = KeysCursor { keys: BTreeSet::from([a, b, c]), position: None };
+
+        // the branch at `1` is left with `b`
+        assert_eq!(remaining_sibling(&mut cursor, a, &BTreeSet::from([a])).unwrap(), Some(b));
+        // the branch at `1` is removed, the root is left with `c`
+        assert_eq!(remaining_sibling(&mut cursor, a, &BTreeSet::from([a, b])).unwrap(), Some(c));
+        assert_eq!(remaining_sibling(&mut cursor, c, &BTreeSet::from([c])).unwrap(), Some(a));
+        // the trie is empty
+        assert_eq!(remaining_sibling(&mut cursor, a, &BTreeSet::from([a, b, c])).unwrap(), None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Command for comparing the execution outcome of a block with the one of another node.
+
+use crate::macros::block_executor;
+use clap::Parser;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_primitives::{
+    logs_bloom, BlockId, BlockNumber, BlockNumberOrTag, Receipt, TransactionVariant, B256, U256,
+};
+use reth_provider::{BlockReader, ChainSpecProvider, HeaderProvider, StateRootProvider};
+use reth_revm::{database::StateProviderDatabase, db::BundleState};
+use reth_rpc_api::EthApiClient;
+use reth_rpc_types::{serde_helpers::JsonStorageKey, AnyTransactionReceipt};
+use tracing::*;
+
+/// `reth debug compare-block` command
+///
+/// Executes a block of the local database on top of its parent state, and compares the receipts,
+/// logs bloom, gas used and state root with the ones of another node. The block has to be
+/// executed by the local node already, and the other node has to serve the state of the block,
+/// e.g. an archive node.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The number of the block to compare.
+    #[arg(long)]
+    block: BlockNumber,
+
+    /// The HTTP RPC URL of the node to compare with.
+    #[arg(long, value_name = "URL")]
+    against: String,
+
+    /// The maximum number of changed accounts whose post-state is compared with the other node,
+    /// if the state roots differ.
+    #[arg(long, default_value_t = 100)]
+    max_accounts: usize,
+}
+
+impl Command {
+    /// Execute `debug compare-block` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        if self.block == 0 {
+            eyre::bail!("the genesis block is not executed")
+        }
+        let client = HttpClientBuilder::default().build(&self.against)?;
+
+        info!(target: "reth::cli", block = self.block, "Executing block");
+        let provider = provider_factory.provider()?;
+        let block = provider
+            .block_with_senders(self.block.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre::eyre!("block {} not found in the database", self.block))?;
+        let td = provider
+            .header_td_by_number(self.block)?
+            .ok_or_else(|| eyre::eyre!("total difficulty of block {} not found", self.block))?;
+
+        let db =
+            StateProviderDatabase::new(provider_factory.history_by_block_number(self.block - 1)?);
+        let executor = block_executor!(provider_factory.chain_spec()).executor(db);
+        let BlockExecutionOutput { state, receipts, gas_used, .. } =
+            executor.execute((&block, td).into())?;
+        let state_root =
+            provider_factory.history_by_block_number(self.block - 1)?.state_root(&state)?;
+
+        info!(target: "reth::cli", against = %self.against, "Fetching block and receipts");
+        let block_id = BlockId::Number(BlockNumberOrTag::Number(self.block));
+        let remote_block = client
+            .block_by_number(BlockNumberOrTag::Number(self.block), false)
+            .await?
+            .ok_or_else(|| eyre::eyre!("block {} not found on {}", self.block, self.against))?;
+        let remote_receipts = client
+            .block_receipts(block_id)
+            .await?
+            .ok_or_else(|| eyre::eyre!("receipts of block {} not found", self.block))?;
+
+        let remote_hash = remote_block.header.hash.unwrap_or_default();
+        if remote_hash != block.hash() {
+            println!(
+                "Note: the other node has block {remote_hash} at height {}, not {}",
+                self.block,
+                block.hash()
+            );
+        }
+
+        let local_bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+        let mut diverged = false;
+        diverged |= compare("gas used", gas_used as u128, remote_block.header.gas_used);
+        diverged |= compare("logs bloom", local_bloom, remote_block.header.logs_bloom);
+        diverged |= compare("state root", state_root, remote_block.header.state_root);
+
+        if let Some(divergence) = first_divergent_receipt(&receipts, &remote_receipts) {
+            diverged = true;
+            println!("First divergent transaction: {divergence}");
+        }
+
+        print_state_summary(&state);
+        if state_root != remote_block.header.state_root {
+            self.compare_accounts(&client, &state, block_id).await?;
+        }
+
+        if diverged {
+            eyre::bail!(
+                "the execution outcome of block {} diverges from {}",
+                self.block,
+                self.against
+            )
+        }
+        println!("The execution outcome of block {} matches {}", self.block, self.against);
+        Ok(())
+    }
+
+    /// Compares the post-state of the changed accounts with the other node.
+    async fn compare_accounts(
+        &self,
+        client: &HttpClient,
+        state: &BundleState,
+        block_id: BlockId,
+    ) -> eyre::Result<()> {
+        println!("Comparing the post-state of up to {} changed accounts", self.max_accounts);
+        for (address, account) in state.state.iter().take(self.max_accounts) {
+            let info = account.info.clone().unwrap_or_default();
+
+            let balance = client.balance(*address, Some(block_id)).await?;
+            if balance != info.balance {
+                println!(
+                    "  {address}: balance {} locally, {balance} on the other node",
+                    info.balance
+                );
+            }
+            let nonce = client.transaction_count(*address, Some(block_id)).await?;
+            if nonce != U256::from(info.nonce) {
+                println!("  {address}: nonce {} locally, {nonce} on the other node", info.nonce);
+            }
+            for (slot, value) in account.storage.iter().filter(|(_, slot)| slot.is_changed()) {
+                let remote = client
+                    .storage_at(*address, JsonStorageKey(B256::from(*slot)), Some(block_id))
+                    .await?;
+                if U256::from_be_bytes(remote.0) != value.present_value {
+                    println!(
+                        "  {address}: storage slot {slot:#x} is {:#x} locally, {remote} on the other node",
+                        value.present_value
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prints the local and remote value if they differ, and returns `true` if they do.
+fn compare<T: PartialEq + std::fmt::Debug>(name: &str, local: T, remote: T) -> bool {
+    if local == remote {
+        println!("{name}: matches");
+        return false
+    }
+    println!("{name}: {local:?} locally, {remote:?} on the other node");
+    true
+}
+
+/// Returns a description of the first transaction whose receipt differs from the one of the other
+/// node, if any.
+fn first_divergent_receipt(local: &[Receipt], remote: &[AnyTransactionReceipt]) -> Option<String> {
+    if local.len() != remote.len() {
+        return Some(format!("{} receipts locally, {} on the other node", local.len(), remote.len()))
+    }
+    local.iter().zip(remote).enumerate().find_map(|(index, (local, remote))| {
+        let hash = remote.inner.transaction_hash;
+        let envelope = &remote.inner.inner.inner;
+        let receipt = &envelope.receipt;
+
+        let reason = if receipt.status != local.success.into() {
+            format!("success {} locally", local.success)
+        } else if receipt.cumulative_gas_used != local.cumulative_gas_used as u128 {
+            format!(
+                "cumulative gas used {} locally, {} on the other node",
+                local.cumulative_gas_used, receipt.cumulative_gas_used
+            )
+        } else if !receipt.logs.iter().map(|log| &log.inner).eq(local.logs.iter()) {
+            format!(
+                "{} logs locally, {} on the other node, or their contents differ",
+                local.logs.len(),
+                receipt.logs.len()
+            )
+        } else if envelope.logs_bloom != local.bloom_slow() {
+            "logs bloom differs".to_string()
+        } else {
+            return None
+        };
+        Some(format!("#{index} {hash}: {reason}"))
+    })
+}
+
+/// Prints a summary of the state changes of the block.
+fn print_state_summary(state: &BundleState) {
+    let accounts = state.state.len();
+    let created = state.state.values().filter(|account| account.original_info.is_none()).count();
+    let destroyed = state.state.values().filter(|account| account.was_destroyed()).count();
+    let slots = state
+        .state
+        .values()
+        .map(|account| account.storage.values().filter(|slot| slot.is_changed()).count())
+        .sum::<usize>();
+    println!(
+        "State changes: {accounts} accounts ({created} created, {destroyed} destroyed), {slots} storage slots"
+    );
+}
@@ -19,6 +19,7 @@ use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkEvents, NetworkHandle};
 use reth_network_api::NetworkInfo;
 use reth_network_p2p::{bodies::client::BodiesClient, headers::client::HeadersClient};
+use reth_node_core::args::SupplyCheckMode;
 use reth_primitives::{BlockHashOrNumber, BlockNumber, B256};
 use reth_provider::{
     BlockExecutionWriter, ChainSpecProvider, ProviderFactory, StageCheckpointReader,
@@ -51,6 +52,12 @@ pub struct Command {
     /// Defaults to `1000`.
     #[arg(long, default_value = "1000")]
     pub interval: u64,
+
+    /// If provided, every executed block is checked to conserve the ETH supply.
+    ///
+    /// Violations are either logged or fail the execution of the block.
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub supply_check: Option<SupplyCheckMode>,
 }
 
 impl Command {
@@ -81,6 +88,9 @@ impl Command {
 
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
         let executor = block_executor!(provider_factory.chain_spec());
+        #[cfg(not(feature = "optimism"))]
+        let executor =
+            executor.with_supply_check(reth_node_ethereum::evm::supply_check(self.supply_check));
 
         let pipeline = Pipeline::builder()
             .with_tip_sender(tip_tx)
@@ -4,6 +4,8 @@ use clap::{Parser, Subcommand};
 use reth_cli_runner::CliContext;
 
 mod build_block;
+mod build_witness;
+mod compare_block;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
@@ -29,6 +31,10 @@ pub enum Subcommands {
     BuildBlock(build_block::Command),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command),
+    /// Compare the execution outcome of a block with the one of another node.
+    CompareBlock(compare_block::Command),
+    /// Build a witness to execute a block statelessly.
+    BuildWitness(build_witness::Command),
 }
 
 impl Command {
@@ -40,6 +46,8 @@ impl Command {
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute(ctx).await,
+            Subcommands::CompareBlock(command) => command.execute(ctx).await,
+            Subcommands::BuildWitness(command) => command.execute(ctx).await,
         }
     }
 }
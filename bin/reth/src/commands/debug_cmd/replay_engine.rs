@@ -2,7 +2,9 @@ use crate::{args::NetworkArgs, macros::block_executor};
 use clap::Parser;
 use eyre::Context;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
-use reth_beacon_consensus::{hooks::EngineHooks, BeaconConsensusEngine, EthBeaconConsensus};
+use reth_beacon_consensus::{
+    hooks::EngineHooks, BackfillThresholds, BeaconConsensusEngine, EthBeaconConsensus,
+};
 use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
@@ -12,7 +14,9 @@ use reth_cli_util::get_secret_key;
 use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_db::DatabaseEnv;
-use reth_engine_util::engine_store::{EngineMessageStore, StoredEngineApiMessage};
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResult, StoredForkchoiceStatus,
+};
 use reth_fs_util as fs;
 use reth_network::NetworkHandle;
 use reth_network_api::NetworkInfo;
@@ -40,13 +44,25 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
-    /// The path to read engine API messages from.
-    #[arg(long = "engine-api-store", value_name = "PATH")]
-    engine_api_store: PathBuf,
+    /// The directory to read engine API messages from, e.g. recorded with `--engine.record`.
+    ///
+    /// If the responses of the node were recorded as well, the replayed responses are compared
+    /// to them.
+    #[arg(value_name = "DIR", required_unless_present = "engine_api_store")]
+    dir: Option<PathBuf>,
+
+    /// The path to read engine API messages from, same as the positional `DIR`.
+    #[arg(long = "engine-api-store", value_name = "PATH", conflicts_with = "dir")]
+    engine_api_store: Option<PathBuf>,
 
     /// The number of milliseconds between Engine API messages.
     #[arg(long = "interval", default_value_t = 1_000)]
     interval: u64,
+
+    /// Pauses between Engine API messages as long as they were apart when they were recorded,
+    /// instead of `--interval`.
+    #[arg(long = "recorded-timing", conflicts_with = "interval")]
+    recorded_timing: bool,
 }
 
 impl Command {
@@ -154,7 +170,7 @@ impl Command {
             None,
             payload_builder,
             None,
-            u64::MAX,
+            BackfillThresholds::new(u64::MAX),
             EngineHooks::new(),
         )?;
         info!(target: "reth::cli", "Consensus engine initialized");
@@ -167,30 +183,65 @@ impl Command {
             let _ = tx.send(res);
         });
 
-        let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
+        let dir = self.dir.clone().or_else(|| self.engine_api_store.clone()).unwrap_or_default();
+        let engine_api_store = EngineMessageStore::new(dir);
+        let mut previous_received_at = None;
+        let (mut replayed, mut mismatches) = (0, 0);
         for filepath in engine_api_store.engine_messages_iter()? {
+            // Pause before next message
+            let received_at = EngineMessageStore::received_at(&filepath).unwrap_or_default();
+            if let Some(previous) = previous_received_at.replace(received_at) {
+                let pause = if self.recorded_timing {
+                    Duration::from_millis(received_at.saturating_sub(previous))
+                } else {
+                    Duration::from_millis(self.interval)
+                };
+                tokio::time::sleep(pause).await;
+            }
+
             let contents =
                 fs::read(&filepath).wrap_err(format!("failed to read: {}", filepath.display()))?;
             let message = serde_json::from_slice(&contents)
                 .wrap_err(format!("failed to parse: {}", filepath.display()))?;
             debug!(target: "reth::cli", filepath = %filepath.display(), ?message, "Forwarding Engine API message");
-            match message {
+            let result = match message {
                 StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
                     let response =
-                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await?;
+                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await;
                     debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    response
+                        .map(|updated| {
+                            StoredEngineApiResult::ForkchoiceUpdated(
+                                StoredForkchoiceStatus::from_payload_status(
+                                    &updated.payload_status.status,
+                                ),
+                            )
+                        })
+                        .map_err(|err| err.to_string())
                 }
                 StoredEngineApiMessage::NewPayload { payload, cancun_fields } => {
-                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await?;
+                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await;
                     debug!(target: "reth::cli", ?response, "Received for new payload");
+                    response.map(StoredEngineApiResult::NewPayload).map_err(|err| err.to_string())
                 }
             };
+            replayed += 1;
 
-            // Pause before next message
-            tokio::time::sleep(Duration::from_millis(self.interval)).await;
+            match EngineMessageStore::response(&filepath)? {
+                Some(recorded) => {
+                    if !responses_match(&recorded.result, &result) {
+                        mismatches += 1;
+                        warn!(target: "reth::cli", filepath = %filepath.display(), recorded = ?recorded.result, replayed = ?result, "Replayed response differs from the recorded response");
+                    }
+                }
+                // without a recorded response, errors can't be expected
+                None => {
+                    result.map_err(|err| eyre::eyre!(err))?;
+                }
+            }
         }
 
-        info!(target: "reth::cli", "Finished replaying engine API messages");
+        info!(target: "reth::cli", replayed, mismatches, "Finished replaying engine API messages");
 
         match rx.await? {
             Ok(()) => info!("Beacon consensus engine exited successfully"),
@@ -202,3 +253,17 @@ impl Command {
         Ok(())
     }
 }
+
+/// Returns `true` if the replayed result matches the recorded one.
+///
+/// Errors only need to be present in both, since their messages depend on where they were raised.
+fn responses_match(
+    recorded: &Result<StoredEngineApiResult, String>,
+    replayed: &Result<StoredEngineApiResult, String>,
+) -> bool {
+    match (recorded, replayed) {
+        (Ok(recorded), Ok(replayed)) => recorded == replayed,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
@@ -6,8 +6,12 @@ use reth_beacon_consensus::EthBeaconConsensus;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_config::Config;
 use reth_consensus::Consensus;
-use reth_db::tables;
-use reth_db_api::{database::Database, transaction::DbTx};
+use reth_db::{static_file::HeaderMask, tables};
+use reth_db_api::{
+    database::Database,
+    models::CompactU256,
+    transaction::{DbTx, DbTxMut},
+};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     file_client::{ChunkedFileReader, FileClient, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
@@ -17,16 +21,22 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader,
     headers::downloader::{HeaderDownloader, SyncTarget},
 };
+use reth_node_core::static_files_server::{StaticFileEntry, StaticFileListing, STATIC_FILES_PATH};
 use reth_node_events::node::NodeEvent;
-use reth_primitives::B256;
+use reth_primitives::{BlockHash, BlockNumber, Header, StaticFileSegment, B256, U256};
 use reth_provider::{
-    BlockNumReader, ChainSpecProvider, HeaderProvider, ProviderError, ProviderFactory,
-    StageCheckpointReader,
+    providers::StaticFileProvider, BlockHashReader, BlockNumReader, ChainSpecProvider,
+    HeaderProvider, ProviderError, ProviderFactory, StageCheckpointReader, StageCheckpointWriter,
+    StaticFileProviderFactory,
 };
 use reth_prune::PruneModes;
-use reth_stages::{prelude::*, Pipeline, StageId, StageSet};
+use reth_stages::{prelude::*, Pipeline, StageCheckpoint, StageId, StageSet};
 use reth_static_file::StaticFileProducer;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::watch;
 use tracing::{debug, error, info};
 
@@ -44,12 +54,23 @@ pub struct ImportCommand {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// URL of the static files endpoint of another node, e.g. `http://10.0.0.1:8547`.
+    ///
+    /// The headers static files it serves are downloaded and verified before the block file
+    /// is imported. Only supported on a database that contains nothing but the genesis block.
+    #[arg(long = "static-files.url", value_name = "URL", verbatim_doc_comment)]
+    static_files_url: Option<String>,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
-    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
-    path: PathBuf,
+    #[arg(
+        value_name = "IMPORT_PATH",
+        required_unless_present = "static_files_url",
+        verbatim_doc_comment
+    )]
+    path: Option<PathBuf>,
 }
 
 impl ImportCommand {
@@ -68,11 +89,17 @@ impl ImportCommand {
 
         let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
 
-        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        if let Some(url) = &self.static_files_url {
+            import_static_file_headers(url, &provider_factory).await?;
+        }
+
+        let Some(path) = &self.path else { return Ok(()) };
+
+        let consensus = Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
         info!(target: "reth::cli", "Consensus engine initialized");
 
         // open file
-        let mut reader = ChunkedFileReader::new(&self.path, self.chunk_len).await?;
+        let mut reader = ChunkedFileReader::new(path, self.chunk_len).await?;
 
         let mut total_decoded_blocks = 0;
         let mut total_decoded_txns = 0;
@@ -148,6 +175,179 @@ impl ImportCommand {
     }
 }
 
+/// Number of header hashes written to the database per transaction when importing headers static
+/// files.
+const HEADER_NUMBERS_BATCH_SIZE: u64 = 100_000;
+
+/// Number of static files that are downloaded concurrently.
+const STATIC_FILE_DOWNLOADS: usize = 8;
+
+/// Downloads the headers static files served by another node's static files endpoint
+/// concurrently, verifies them and moves them into the static files directory.
+///
+/// Only headers are imported, since the other segments depend on database tables that static
+/// files don't contain, e.g. block body indices.
+async fn import_static_file_headers<DB: Database>(
+    url: &str,
+    provider_factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    let headers_checkpoint =
+        provider_factory.provider()?.get_stage_checkpoint(StageId::Headers)?.unwrap_or_default();
+    if headers_checkpoint.block_number != 0 {
+        eyre::bail!(
+            "static files can only be imported into a database that only contains the genesis block"
+        );
+    }
+
+    let base_url = format!("{}{STATIC_FILES_PATH}", url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+    let listings: Vec<StaticFileListing> = client
+        .get(format!("{base_url}?segment={}", StaticFileSegment::Headers.as_ref()))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    if listings.is_empty() {
+        info!(target: "reth::cli", url, "No headers static files to download");
+        return Ok(())
+    }
+
+    let static_file_provider = provider_factory.static_file_provider();
+    let download_dir = static_file_provider.directory().join("download");
+    if download_dir.exists() {
+        reth_fs_util::remove_dir_all(&download_dir)?;
+    }
+    reth_fs_util::create_dir_all(&download_dir)?;
+
+    let mut downloads = futures::stream::iter(listings.iter().flat_map(|listing| &listing.files))
+        .map(|file| download_static_file(&client, &base_url, file, &download_dir))
+        .buffer_unordered(STATIC_FILE_DOWNLOADS);
+    while let Some(result) = downloads.next().await {
+        result?;
+    }
+    drop(downloads);
+
+    let downloaded = StaticFileProvider::read_only(&download_dir)?;
+    let highest_block =
+        verify_static_file_headers(&downloaded, provider_factory.chain_spec().genesis_hash())?;
+    drop(downloaded);
+
+    static_file_provider.import_segment(StaticFileSegment::Headers, &download_dir)?;
+    reth_fs_util::remove_dir_all(&download_dir)?;
+
+    // The headers stage keeps the hash to number index in the database
+    for start in (0..=highest_block).step_by(HEADER_NUMBERS_BATCH_SIZE as usize) {
+        let end = (start + HEADER_NUMBERS_BATCH_SIZE).min(highest_block + 1);
+        let provider_rw = provider_factory.provider_rw()?;
+        for (number, hash) in
+            (start..end).zip(static_file_provider.canonical_hashes_range(start, end)?)
+        {
+            provider_rw.tx_ref().put::<tables::HeaderNumbers>(hash, number)?;
+        }
+        if end == highest_block + 1 {
+            provider_rw
+                .save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(highest_block))?;
+        }
+        provider_rw.commit()?;
+    }
+
+    info!(target: "reth::cli", highest_block, "Imported headers static files");
+
+    Ok(())
+}
+
+/// Downloads one of the data, offsets or configuration files of a static file into the given
+/// directory.
+async fn download_static_file(
+    client: &reqwest::Client,
+    base_url: &str,
+    file: &StaticFileEntry,
+    download_dir: &Path,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", name = %file.name, size = file.size, "Downloading static file");
+
+    let mut response =
+        client.get(format!("{base_url}/{}", file.name)).send().await?.error_for_status()?;
+    let etag = response.headers().get(reqwest::header::ETAG).and_then(|v| v.to_str().ok());
+    if etag != Some(file.etag.as_str()) {
+        eyre::bail!("static file {} changed while downloading it", file.name);
+    }
+
+    let path = download_dir.join(&file.name);
+    let mut writer = std::io::BufWriter::new(
+        std::fs::File::create(&path)
+            .map_err(|err| reth_fs_util::FsPathError::create_file(err, &path))?,
+    );
+    let mut size = 0;
+    while let Some(chunk) = response.chunk().await? {
+        writer.write_all(&chunk)?;
+        size += chunk.len() as u64;
+    }
+    writer.flush()?;
+
+    if size != file.size {
+        eyre::bail!("static file {} has {size} bytes, expected {} bytes", file.name, file.size);
+    }
+
+    Ok(())
+}
+
+/// Verifies that the headers in the given static files form a chain from the genesis block, and
+/// that their hashes and total difficulties are correct.
+///
+/// Returns the highest block.
+fn verify_static_file_headers(
+    static_file_provider: &StaticFileProvider,
+    genesis_hash: B256,
+) -> eyre::Result<BlockNumber> {
+    let highest_block = static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .ok_or_else(|| eyre::eyre!("no headers static files were downloaded"))?;
+
+    let mut parent_hash = B256::ZERO;
+    let mut total_difficulty = U256::ZERO;
+    let mut number = 0;
+    while number <= highest_block {
+        let jar_provider = static_file_provider.get_segment_provider_from_block(
+            StaticFileSegment::Headers,
+            number,
+            None,
+        )?;
+        let mut cursor = jar_provider.cursor()?;
+        let end = jar_provider.user_header().block_end().unwrap_or(highest_block);
+
+        for number in number..=end {
+            let (header, hash) = cursor
+                .get_two::<HeaderMask<Header, BlockHash>>(number.into())?
+                .ok_or_else(|| eyre::eyre!("header {number} is missing"))?;
+            let td = cursor
+                .get_one::<HeaderMask<CompactU256>>(number.into())?
+                .ok_or_else(|| eyre::eyre!("total difficulty of header {number} is missing"))?;
+
+            if header.number != number || header.hash_slow() != hash {
+                eyre::bail!("header {number} is invalid");
+            }
+            if number == 0 && hash != genesis_hash {
+                eyre::bail!("genesis hash {hash} does not match the local chain {genesis_hash}");
+            }
+            if number > 0 && header.parent_hash != parent_hash {
+                eyre::bail!("header {number} does not extend header {}", number - 1);
+            }
+            total_difficulty += header.difficulty;
+            if td.0 != total_difficulty {
+                eyre::bail!("total difficulty of header {number} is invalid");
+            }
+
+            parent_hash = hash;
+        }
+
+        number = end + 1;
+    }
+
+    Ok(highest_block)
+}
+
 /// Builds import pipeline.
 ///
 /// If configured to execute, all stages will run. Otherwise, only stages that don't require state
@@ -226,6 +426,16 @@ mod tests {
     use super::*;
     use reth_node_core::args::utils::SUPPORTED_CHAINS;
 
+    #[test]
+    fn parse_static_files_url() {
+        let args: ImportCommand =
+            ImportCommand::parse_from(["reth", "--static-files.url", "http://127.0.0.1:8547"]);
+        assert_eq!(args.static_files_url.as_deref(), Some("http://127.0.0.1:8547"));
+        assert!(args.path.is_none());
+
+        assert!(ImportCommand::try_parse_from(["reth"]).is_err());
+    }
+
     #[test]
     fn parse_common_import_command_chain_args() {
         for chain in SUPPORTED_CHAINS {
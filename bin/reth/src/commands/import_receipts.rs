@@ -0,0 +1,87 @@
+//! Command that imports receipts from a file, to complete the receipts of blocks that were synced
+//! without them, e.g. imported without execution or pruned.
+
+use crate::version::SHORT_VERSION;
+use clap::Parser;
+use eyre::bail;
+use reth_cli_commands::{
+    common::{AccessRights, Environment, EnvironmentArgs},
+    import_receipts::import_receipts_from_file,
+};
+use reth_downloaders::{
+    file_client::DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE, file_codec_receipt::ReceiptFileCodec,
+};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+#[cfg(feature = "optimism")]
+use reth_provider::ChainSpecProvider;
+
+/// Imports RLP encoded receipts from a file.
+///
+/// The receipts of every block are validated against the receipts root of its header before they
+/// are written.
+#[derive(Debug, Parser)]
+pub struct ImportReceiptsCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Chunk byte length to read from file.
+    #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
+    chunk_len: Option<u64>,
+
+    /// The path to a receipts file for import.
+    ///
+    /// Every entry of the file is the RLP list `[block_number, receipt]`, where the receipt uses
+    /// its network encoding. Entries must be ordered by block number and transaction index, and
+    /// blocks without transactions can be omitted.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ImportReceiptsCommand {
+    /// Execute `import-receipts` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        debug!(target: "reth::cli",
+            chunk_byte_len=self.chunk_len.unwrap_or(DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE),
+            "Chunking receipts import"
+        );
+
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+        let prune_modes = config.prune.map(|prune| prune.segments).unwrap_or_default();
+
+        #[cfg(feature = "optimism")]
+        let chain_spec = provider_factory.chain_spec();
+
+        import_receipts_from_file::<_, ReceiptFileCodec, _>(
+            provider_factory,
+            self.path,
+            self.chunk_len,
+            &prune_modes,
+            |header, receipts| {
+                let receipts_refs = receipts.iter().collect::<Vec<_>>();
+                #[cfg(not(feature = "optimism"))]
+                let receipts_root =
+                    reth_primitives::proofs::calculate_receipt_root_no_memo(&receipts_refs);
+                #[cfg(feature = "optimism")]
+                let receipts_root =
+                    reth_primitives::proofs::calculate_receipt_root_no_memo_optimism(
+                        &receipts_refs,
+                        &chain_spec,
+                        header.timestamp,
+                    );
+                if receipts_root != header.receipts_root {
+                    bail!(
+                        "receipts root mismatch for block {}: expected {}, got {receipts_root}",
+                        header.number,
+                        header.receipts_root
+                    )
+                }
+                Ok(0)
+            },
+        )
+        .await
+    }
+}
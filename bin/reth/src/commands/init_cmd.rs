@@ -2,7 +2,9 @@
 
 use clap::Parser;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_db_common::init::init_genesis_alloc;
 use reth_provider::BlockHashReader;
+use std::{fs::File, io::BufReader, path::PathBuf};
 use tracing::info;
 
 /// Initializes the database with the genesis block.
@@ -10,6 +12,16 @@ use tracing::info;
 pub struct InitCommand {
     #[command(flatten)]
     env: EnvironmentArgs,
+
+    /// Genesis file to stream the alloc of the genesis block from.
+    ///
+    /// For chains with a very large alloc, the genesis file can carry the pre-computed state root
+    /// as `stateRoot` in its chain config. The alloc is then not loaded into memory with the
+    /// chain spec, and has to be imported with this argument instead, usually from the same
+    /// file as `--chain`. The accounts are streamed and inserted in batches, and the resulting
+    /// state root is checked against the pre-computed one.
+    #[arg(long = "genesis.alloc", value_name = "GENESIS_FILE", verbatim_doc_comment)]
+    alloc: Option<PathBuf>,
 }
 
 impl InitCommand {
@@ -17,7 +29,13 @@ impl InitCommand {
     pub async fn execute(self) -> eyre::Result<()> {
         info!(target: "reth::cli", "reth init starting");
 
-        let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+        let Environment { config, provider_factory, .. } = self.env.init(AccessRights::RW)?;
+
+        if let Some(path) = self.alloc {
+            info!(target: "reth::cli", ?path, "Importing genesis alloc");
+            let reader = BufReader::new(File::open(path)?);
+            init_genesis_alloc(reader, provider_factory.clone(), config.stages.etl)?;
+        }
 
         let hash = provider_factory
             .block_hash(0)?
@@ -30,8 +30,16 @@ pub struct InitStateCommand {
     ///         ..
     ///     },
     ///     "address": "\<address\>",
+    ///     "storageRoot": "\<storage-root\>",
     /// }
     ///
+    /// The storage root is optional. If present, the storage of the account is verified
+    /// against it while the file is read, and the import is rejected before anything is
+    /// written.
+    ///
+    /// Accounts are committed in chunks, so an interrupted import of the same file resumes
+    /// after the last committed account.
+    ///
     /// Allows init at a non-genesis block. Caution! Blocks must be manually imported up until
     /// and including the non-genesis block to init chain at. See 'import' command.
     #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
@@ -1,9 +1,11 @@
 //! This contains all of the `reth` commands
 
+pub mod chain;
 pub mod config_cmd;
 pub mod debug_cmd;
 pub mod dump_genesis;
 pub mod import;
+pub mod import_receipts;
 pub mod init_cmd;
 pub mod init_state;
 pub mod node;
@@ -11,4 +13,6 @@ pub mod p2p;
 pub mod prune;
 pub mod recover;
 pub mod stage;
+pub mod stateless_verify;
 pub mod test_vectors;
+pub mod validate_builder_submissions;
@@ -2,8 +2,8 @@
 
 use crate::args::{
     utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
-    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-    RpcServerArgs, TxPoolArgs,
+    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EvmArgs, HardforkOverrideArgs,
+    NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, ShutdownArgs, TxPoolArgs,
 };
 use clap::{value_parser, Args, Parser};
 use reth_chainspec::ChainSpec;
@@ -11,7 +11,7 @@ use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
 use reth_db::{init_db, DatabaseEnv};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
-use reth_node_core::{node_config::NodeConfig, version};
+use reth_node_core::{manifest::DEFAULT_CHAIN, node_config::NodeConfig, version};
 use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
 
 /// Start the node
@@ -28,7 +28,7 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
         long,
         value_name = "CHAIN_OR_PATH",
         long_help = chain_help(),
-        default_value = SUPPORTED_CHAINS[0],
+        default_value = DEFAULT_CHAIN,
         default_value_if("dev", "true", "dev"),
         value_parser = chain_value_parser,
         required = false,
@@ -41,6 +41,13 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, value_name = "SOCKET", value_parser = parse_socket_address, help_heading = "Metrics")]
     pub metrics: Option<SocketAddr>,
 
+    /// Serve complete static files over HTTP, so that new nodes can download the headers static
+    /// files with `reth import --static-files.url`.
+    ///
+    /// The static files will be served at the given interface and port.
+    #[arg(long = "static-files.http", value_name = "SOCKET", value_parser = parse_socket_address, help_heading = "Static Files")]
+    pub static_files_http: Option<SocketAddr>,
+
     /// Add a new instance of a node.
     ///
     /// Configures the ports of the node to avoid conflicts with the defaults.
@@ -100,6 +107,22 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All hardfork activation override arguments with --override prefix
+    #[command(flatten)]
+    pub hardforks: HardforkOverrideArgs,
+
+    /// All graceful shutdown related arguments with --shutdown prefix
+    #[command(flatten)]
+    pub shutdown: ShutdownArgs,
+
+    /// All EVM related arguments with --evm prefix
+    #[command(flatten)]
+    pub evm: EvmArgs,
+
+    /// All consensus engine related arguments with --engine prefix
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -138,6 +161,7 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             config,
             chain,
             metrics,
+            static_files_http,
             instance,
             with_unused_ports,
             network,
@@ -148,6 +172,10 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            hardforks,
+            shutdown,
+            evm,
+            engine,
             ext,
         } = self;
 
@@ -157,6 +185,7 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             config,
             chain,
             metrics,
+            static_files_http,
             instance,
             network,
             rpc,
@@ -166,6 +195,10 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            hardforks,
+            shutdown,
+            evm,
+            engine,
         };
 
         // Register the prometheus recorder before creating the database,
@@ -354,6 +387,16 @@ mod tests {
         assert_eq!(cmd.network.port, 30305);
     }
 
+    #[test]
+    fn parse_static_files_http() {
+        let cmd =
+            NodeCommand::try_parse_args_from(["reth", "--static-files.http", ":9005"]).unwrap();
+        assert_eq!(
+            cmd.static_files_http,
+            Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9005))
+        );
+    }
+
     #[test]
     fn parse_with_unused_ports() {
         let cmd = NodeCommand::<NoArgs>::parse_from(["reth", "--with-unused-ports"]);
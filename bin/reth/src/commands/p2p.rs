@@ -9,12 +9,12 @@ use crate::{
 };
 use backon::{ConstantBuilder, Retryable};
 use clap::{Parser, Subcommand};
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, HardforkOverrides};
 use reth_cli_util::{get_secret_key, hash_or_num_value_parser};
 use reth_config::Config;
 use reth_network::NetworkConfigBuilder;
 use reth_network_p2p::bodies::client::BodiesClient;
-use reth_node_core::args::DatadirArgs;
+use reth_node_core::{args::DatadirArgs, node_config::chain_with_hardfork_overrides};
 use reth_primitives::BlockHashOrNumber;
 use std::{path::PathBuf, sync::Arc};
 
@@ -88,18 +88,22 @@ impl Command {
 
         config.peers.trusted_nodes_only = self.network.trusted_only;
 
+        // Use the same fork schedule as the node
+        let chain =
+            chain_with_hardfork_overrides(&self.chain, &config, HardforkOverrides::default())?;
+
         let default_secret_key_path = data_dir.p2p_secret();
         let secret_key_path =
             self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
         let p2p_secret_key = get_secret_key(&secret_key_path)?;
         let rlpx_socket = (self.network.addr, self.network.port).into();
-        let boot_nodes = self.chain.bootnodes().unwrap_or_default();
+        let boot_nodes = chain.bootnodes().unwrap_or_default();
 
         let net = NetworkConfigBuilder::new(p2p_secret_key)
             .peer_config(config.peers_config_with_basic_nodes_from_file(None))
             .external_ip_resolver(self.network.nat)
-            .chain_spec(self.chain.clone())
-            .disable_discv4_discovery_if(self.chain.chain.is_optimism())
+            .chain_spec(chain.clone())
+            .disable_discv4_discovery_if(chain.chain.is_optimism())
             .boot_nodes(boot_nodes.clone())
             .apply(|builder| {
                 self.network.discovery.apply_to_builder(builder, rlpx_socket, boot_nodes)
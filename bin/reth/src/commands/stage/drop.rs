@@ -9,7 +9,7 @@ use reth_db_common::{
     init::{insert_genesis_header, insert_genesis_history, insert_genesis_state},
     DbTool,
 };
-use reth_provider::{providers::StaticFileWriter, StaticFileProviderFactory};
+use reth_provider::{providers::StaticFileWriter, ChainSpecProvider, StaticFileProviderFactory};
 use reth_stages::StageId;
 use reth_static_file_types::{find_fixed_range, StaticFileSegment};
 
@@ -28,6 +28,8 @@ impl Command {
         let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
 
         let static_file_provider = provider_factory.static_file_provider();
+        // the chain spec with the hardfork overrides of the config applied
+        let chain = provider_factory.chain_spec();
 
         let tool = DbTool::new(provider_factory)?;
 
@@ -68,7 +70,7 @@ impl Command {
                     StageId::Headers.to_string(),
                     Default::default(),
                 )?;
-                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, self.env.chain)?;
+                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, chain)?;
             }
             StageEnum::Bodies => {
                 tx.clear::<tables::BlockBodyIndices>()?;
@@ -81,7 +83,7 @@ impl Command {
                     StageId::Bodies.to_string(),
                     Default::default(),
                 )?;
-                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, self.env.chain)?;
+                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, chain)?;
             }
             StageEnum::Senders => {
                 tx.clear::<tables::TransactionSenders>()?;
@@ -101,7 +103,7 @@ impl Command {
                     StageId::Execution.to_string(),
                     Default::default(),
                 )?;
-                let alloc = &self.env.chain.genesis().alloc;
+                let alloc = &chain.genesis().alloc;
                 insert_genesis_state::<DatabaseEnv>(tx, alloc.len(), alloc.iter())?;
             }
             StageEnum::AccountHashing => {
@@ -160,7 +162,7 @@ impl Command {
                     StageId::IndexStorageHistory.to_string(),
                     Default::default(),
                 )?;
-                insert_genesis_history(&provider_rw, self.env.chain.genesis.alloc.iter())?;
+                insert_genesis_history(&provider_rw, chain.genesis.alloc.iter())?;
             }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
@@ -168,7 +170,7 @@ impl Command {
                     StageId::TransactionLookup.to_string(),
                     Default::default(),
                 )?;
-                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, self.env.chain)?;
+                insert_genesis_header::<DatabaseEnv>(tx, &static_file_provider, chain)?;
             }
         }
 
@@ -6,6 +6,7 @@ use reth_cli_runner::CliContext;
 pub mod drop;
 pub mod dump;
 pub mod run;
+pub mod scratch;
 pub mod unwind;
 
 /// `reth stage` command
@@ -1,6 +1,7 @@
 //! Main `stage` command
 //!
 //! Stage debugging tool
+use super::scratch::ScratchArgs;
 use crate::{
     args::{NetworkArgs, StageEnum},
     macros::block_executor,
@@ -79,15 +80,25 @@ pub struct Command {
 
     #[command(flatten)]
     network: NetworkArgs,
+
+    #[command(flatten)]
+    scratch: ScratchArgs,
 }
 
 impl Command {
     /// Execute `stage` command
-    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+    pub async fn execute(mut self, ctx: CliContext) -> eyre::Result<()> {
         // Raise the fd limit of the process.
         // Does not do anything on windows.
         let _ = fdlimit::raise_fd_limit();
 
+        // Run against a clone of the datadir, so the datadir itself is never modified
+        let source = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+        if let Some(scratch) = self.scratch.prepare(source.data_dir(), &source.static_files())? {
+            self.env.datadir.datadir = scratch.into();
+            self.env.datadir.static_files_path = None;
+        }
+
         let Environment { provider_factory, config, data_dir } = self.env.init(AccessRights::RW)?;
 
         let mut provider_rw = provider_factory.provider_rw()?;
@@ -158,6 +169,9 @@ impl Command {
                                 config.stages.bodies.downloader_min_concurrent_requests..=
                                     config.stages.bodies.downloader_max_concurrent_requests,
                             )
+                            .with_requests_per_peer(
+                                config.stages.bodies.downloader_requests_per_peer,
+                            )
                             .build(fetch_client, consensus.clone(), provider_factory.clone()),
                     );
                     (Box::new(stage), None)
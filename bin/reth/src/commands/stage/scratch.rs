@@ -0,0 +1,222 @@
+//! Scratch copies of a datadir that stages can be run against without mutating the original.
+
+use clap::Args;
+use eyre::WrapErr;
+use reth_config::config::EtlConfig;
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// The lock file of the database, which is recreated when the database is opened.
+const DB_LOCK_FILE: &str = "mdbx.lck";
+
+/// Parameters for running a stage against a scratch copy of the datadir
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Scratch")]
+pub struct ScratchArgs {
+    /// Runs the stage against a copy-on-write clone of the datadir at the given path instead of
+    /// the datadir itself, which is only read.
+    ///
+    /// The path must not exist or be an empty directory, unless `--scratch.force` or
+    /// `--scratch.keep` is passed. The datadir must not be in use by a node while it's cloned.
+    #[arg(long = "scratch.dir", value_name = "PATH")]
+    pub dir: Option<PathBuf>,
+
+    /// Removes the contents of an existing, non-empty `--scratch.dir` and recreates the clone
+    /// from the datadir, so the stage can be run repeatedly against the same input.
+    #[arg(long = "scratch.force", requires = "dir", conflicts_with = "keep")]
+    pub force: bool,
+
+    /// Reuses an existing clone at `--scratch.dir` instead of recreating it, so the stage
+    /// continues from the state left by the previous run.
+    #[arg(long = "scratch.keep", requires = "dir")]
+    pub keep: bool,
+
+    /// Falls back to a full copy of the files if the filesystem doesn't support copy-on-write
+    /// clones.
+    ///
+    /// Without this, cloning fails on such filesystems rather than copying a large datadir.
+    #[arg(long = "scratch.allow-copy", requires = "dir")]
+    pub allow_copy: bool,
+}
+
+impl ScratchArgs {
+    /// Clones the datadir and the static files directory into the scratch directory, if one is
+    /// configured, and returns the scratch directory.
+    ///
+    /// The static files are placed in the default location of the scratch directory.
+    pub fn prepare(&self, datadir: &Path, static_files: &Path) -> eyre::Result<Option<PathBuf>> {
+        let Some(scratch) = &self.dir else { return Ok(None) };
+        if scratch.starts_with(datadir) || datadir.starts_with(scratch) {
+            eyre::bail!(
+                "the scratch directory {scratch:?} must not overlap with the datadir {datadir:?}"
+            )
+        }
+
+        if scratch.exists() {
+            if self.keep {
+                info!(target: "reth::cli", ?scratch, "Reusing existing scratch datadir");
+                return Ok(Some(scratch.clone()))
+            }
+            if !self.force && !is_empty_dir(scratch)? {
+                eyre::bail!(
+                    "the scratch directory {scratch:?} is not empty, pass --scratch.force to \
+                     replace its contents or --scratch.keep to reuse it"
+                )
+            }
+            reth_fs_util::remove_dir_all(scratch)?;
+        }
+
+        info!(target: "reth::cli", ?datadir, ?scratch, "Cloning datadir into scratch datadir");
+        let mut cloned = CloneStats::default();
+        let skip = [EtlConfig::from_datadir(datadir), static_files.to_path_buf()];
+        clone_dir(datadir, scratch, &skip, self.allow_copy, &mut cloned)?;
+        clone_dir(static_files, &scratch.join("static_files"), &[], self.allow_copy, &mut cloned)?;
+        info!(target: "reth::cli", files = cloned.files, copied = cloned.copied, "Cloned datadir into scratch datadir");
+
+        Ok(Some(scratch.clone()))
+    }
+}
+
+/// Returns whether the path is a directory without any entries.
+fn is_empty_dir(path: &Path) -> eyre::Result<bool> {
+    Ok(path.is_dir() &&
+        fs::read_dir(path)
+            .wrap_err_with(|| format!("failed to read directory {path:?}"))?
+            .next()
+            .is_none())
+}
+
+/// The number of files that were cloned, and how many of them had to be copied.
+#[derive(Debug, Default)]
+struct CloneStats {
+    files: usize,
+    copied: usize,
+}
+
+/// Recursively clones the directory, skipping the given paths and the database lock file.
+fn clone_dir(
+    src: &Path,
+    dst: &Path,
+    skip: &[PathBuf],
+    allow_copy: bool,
+    stats: &mut CloneStats,
+) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(dst)?;
+    if !src.exists() {
+        return Ok(())
+    }
+
+    for entry in reth_fs_util::read_dir(src)? {
+        let entry = entry.wrap_err_with(|| format!("failed to read directory {src:?}"))?;
+        let path = entry.path();
+        if skip.contains(&path) || entry.file_name() == DB_LOCK_FILE {
+            continue
+        }
+
+        let target = dst.join(entry.file_name());
+        if reth_fs_util::metadata(&path)?.is_dir() {
+            clone_dir(&path, &target, skip, allow_copy, stats)?;
+        } else {
+            let copied = clone_file(&path, &target, allow_copy)
+                .wrap_err_with(|| format!("failed to clone {path:?} to {target:?}"))?;
+            stats.files += 1;
+            stats.copied += copied as usize;
+        }
+    }
+    Ok(())
+}
+
+/// Clones the file with a copy-on-write clone, or copies it if that's not supported and copying
+/// is allowed.
+///
+/// Returns true if the file was copied.
+fn clone_file(src: &Path, dst: &Path, allow_copy: bool) -> io::Result<bool> {
+    let source = File::open(src)?;
+    let target = File::create(dst)?;
+    match reflink(&source, &target) {
+        Ok(()) => Ok(false),
+        Err(err) if allow_copy => {
+            drop(target);
+            fs::copy(src, dst).map(|_| true).map_err(|copy_err| {
+                io::Error::new(
+                    copy_err.kind(),
+                    format!("{copy_err}, after copy-on-write clone failed: {err}"),
+                )
+            })
+        }
+        Err(err) => Err(io::Error::new(
+            err.kind(),
+            format!("copy-on-write clone failed, pass --scratch.allow-copy to copy instead: {err}"),
+        )),
+    }
+}
+
+/// Clones the contents of the source file into the target file, sharing the data blocks until
+/// either file is modified.
+#[cfg(target_os = "linux")]
+fn reflink(source: &File, target: &File) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    // SAFETY: both file descriptors are valid for the duration of the call
+    let ret = unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE as _, source.as_raw_fd()) };
+    if ret == -1 {
+        return Err(io::Error::last_os_error())
+    }
+    Ok(())
+}
+
+/// Copy-on-write clones are only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+fn reflink(_source: &File, _target: &File) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "copy-on-write clones require Linux"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_datadir_into_scratch() {
+        let dir = tempfile::tempdir().unwrap();
+        let datadir = dir.path().join("datadir");
+        let static_files = datadir.join("static_files");
+        reth_fs_util::create_dir_all(datadir.join("db")).unwrap();
+        reth_fs_util::create_dir_all(datadir.join("etl-tmp")).unwrap();
+        reth_fs_util::create_dir_all(&static_files).unwrap();
+        reth_fs_util::write(datadir.join("db").join("mdbx.dat"), b"db").unwrap();
+        reth_fs_util::write(datadir.join("db").join(DB_LOCK_FILE), b"lock").unwrap();
+        reth_fs_util::write(datadir.join("etl-tmp").join("collector"), b"etl").unwrap();
+        reth_fs_util::write(static_files.join("headers"), b"headers").unwrap();
+
+        let scratch = dir.path().join("scratch");
+        let args =
+            ScratchArgs { dir: Some(scratch.clone()), keep: false, force: false, allow_copy: true };
+        assert_eq!(args.prepare(&datadir, &static_files).unwrap(), Some(scratch.clone()));
+        assert_eq!(reth_fs_util::read(scratch.join("db").join("mdbx.dat")).unwrap(), b"db");
+        assert_eq!(
+            reth_fs_util::read(scratch.join("static_files").join("headers")).unwrap(),
+            b"headers"
+        );
+        assert!(!scratch.join("db").join(DB_LOCK_FILE).exists());
+        assert!(!scratch.join("etl-tmp").exists());
+
+        // the scratch copy is only replaced if forced
+        reth_fs_util::write(scratch.join("db").join("mdbx.dat"), b"modified").unwrap();
+        let keep = ScratchArgs { keep: true, ..args.clone() };
+        keep.prepare(&datadir, &static_files).unwrap();
+        assert_eq!(reth_fs_util::read(scratch.join("db").join("mdbx.dat")).unwrap(), b"modified");
+        assert!(args.prepare(&datadir, &static_files).is_err());
+        assert_eq!(reth_fs_util::read(scratch.join("db").join("mdbx.dat")).unwrap(), b"modified");
+        let force = ScratchArgs { force: true, ..args.clone() };
+        force.prepare(&datadir, &static_files).unwrap();
+        assert_eq(reth_fs_util::read(scratch.join("db").join("mdbx.dat")).unwrap(), b"db");
+        assert_eq!(reth_fs_util::read(datadir.join("db").join("mdbx.dat")).unwrap(), b"db");
+
+        let overlapping = ScratchArgs { dir: Some(datadir.join("scratch")), ..args };
+        assert!(overlapping.prepare(&datadir, &static_files).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! Command that re-executes a block from a witness without a database.
+use crate::{
+    args::utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+    macros::block_executor,
+};
+use clap::Parser;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_chainspec::ChainSpec;
+use reth_stateless::BlockWitness;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Re-executes a block purely from a witness and validates the resulting state root.
+///
+/// The witness is the JSON file written by `reth debug build-witness`.
+#[derive(Debug, Parser)]
+pub struct StatelessVerifyCommand {
+    /// The chain the block belongs to.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// The path of the witness file.
+    #[arg(long, value_name = "FILE")]
+    witness: PathBuf,
+}
+
+impl StatelessVerifyCommand {
+    /// Execute the `stateless-verify` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let witness: BlockWitness =
+            serde_json::from_str(&reth_fs_util::read_to_string(&self.witness)?)?;
+        let chain_id = self.chain.chain.id();
+        if witness.chain_id != chain_id {
+            eyre::bail!("the witness is for chain {}, expected chain {chain_id}", witness.chain_id)
+        }
+
+        let consensus = EthBeaconConsensus::new(self.chain.clone());
+        let output =
+            reth_stateless::execute_witness(&block_executor!(self.chain), &consensus, &witness)?;
+        info!(
+            target: "reth::cli",
+            gas_used = output.gas_used,
+            receipts = output.receipts.len(),
+            "Block verified"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_stateless_verify_command() {
+        let args: StatelessVerifyCommand =
+            StatelessVerifyCommand::parse_from(["reth", "--witness", "witness.json"]);
+        assert_eq!(args.witness, PathBuf::from("witness.json"));
+        assert_eq!(args.chain.chain, SUPPORTED_CHAINS[0].parse::<reth_chainspec::Chain>().unwrap());
+    }
+}
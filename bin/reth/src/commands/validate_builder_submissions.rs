@@ -0,0 +1,93 @@
+//! Command that runs the node as a relay block submission validation service.
+
+use crate::commands::node::NodeCommand;
+use clap::{Args, Parser};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_cli_runner::CliContext;
+use reth_cli_util::parse_duration_from_ms;
+use reth_node_api::FullNodeComponents;
+use reth_node_ethereum::EthereumNode;
+use reth_rpc::{RethValidationApi, ValidationApi, DEFAULT_BUILDER_VALIDATION_DEADLINE};
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use std::{sync::Arc, time::Duration};
+use tracing::info;
+
+/// Run the node as a block submission validation service for relays.
+///
+/// Serves `flashbots_validateBuilderSubmissionV1`, `flashbots_validateBuilderSubmissionV2` and
+/// `flashbots_validateBuilderSubmissionV3` on the HTTP server of the node, which is always enabled
+/// in this mode.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    node: NodeCommand<BuilderValidationArgs>,
+}
+
+/// Arguments of the block submission validation service.
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+pub struct BuilderValidationArgs {
+    /// The time in milliseconds a submission has to be validated in.
+    ///
+    /// Submissions that take longer are rejected and their validation is cancelled.
+    #[arg(
+        long = "builder-validation.deadline",
+        value_name = "MILLISECONDS",
+        value_parser = parse_duration_from_ms,
+        default_value = "2000",
+    )]
+    pub deadline: Duration,
+}
+
+impl Command {
+    /// Returns the time the graceful shutdown of the node may take.
+    pub fn shutdown_timeout(&self) -> Duration {
+        self.node.shutdown.timeout()
+    }
+
+    /// Execute `validate-builder-submissions` command
+    pub async fn execute(mut self, ctx: CliContext) -> eyre::Result<()> {
+        // submissions are received over http
+        self.node.rpc.http = true;
+
+        self.node
+            .execute(ctx, |builder, args| async move {
+                let handle = builder
+                    .node(EthereumNode::default())
+                    .extend_rpc_modules(move |ctx| {
+                        // the chain spec of the launched node includes the hardfork overrides
+                        let consensus = Arc::new(EthBeaconConsensus::new(ctx.config().chain.clone()));
+                        let validation = RethValidationApi::new(
+                            ctx.provider().clone(),
+                            consensus,
+                            ctx.node().block_executor().clone(),
+                            Box::new(ctx.node().task_executor().clone()),
+                        );
+                        let api = ValidationApi::new(validation).with_deadline(args.deadline);
+                        ctx.modules.merge_configured(api.into_rpc())?;
+
+                        info!(target: "reth::cli", deadline = ?args.deadline, "Builder submission validation enabled");
+                        Ok(())
+                    })
+                    .launch()
+                    .await?;
+
+                handle.wait_for_node_exit().await
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_builder_validation_deadline() {
+        let cmd = Command::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.node.ext.deadline, DEFAULT_BUILDER_VALIDATION_DEADLINE);
+
+        let cmd =
+            Command::try_parse_from(["reth", "--builder-validation.deadline", "500"]).unwrap();
+        assert_eq!(cmd.node.ext.deadline, Duration::from_millis(500));
+    }
+}
@@ -10,9 +10,6 @@ compile_error!("Cannot build the `reth` binary with the `optimism` feature flag
 
 #[cfg(not(feature = "optimism"))]
 fn main() {
-    use reth::cli::Cli;
-    use reth_node_ethereum::EthereumNode;
-
     reth::sigsegv_handler::install();
 
     // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
@@ -20,11 +17,47 @@ fn main() {
         std::env::set_var("RUST_BACKTRACE", "1");
     }
 
-    if let Err(err) = Cli::parse_args().run(|builder, _| async {
-        let handle = builder.launch_node(EthereumNode::default()).await?;
-        handle.node_exit_future.await
-    }) {
+    if let Err(err) = run() {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
 }
+
+#[cfg(all(not(feature = "optimism"), not(feature = "indexer")))]
+fn run() -> eyre::Result<()> {
+    use reth::cli::Cli;
+    use reth_node_ethereum::EthereumNode;
+
+    Cli::parse_args().run(|builder, _| async {
+        let handle = builder.launch_node(EthereumNode::default()).await?;
+        handle.node_exit_future.await
+    })
+}
+
+/// Runs the node with the indexer `ExEx`, which is installed if enabled with `--exex.indexer`.
+#[cfg(all(not(feature = "optimism"), feature = "indexer"))]
+fn run() -> eyre::Result<()> {
+    use clap::Parser;
+    use reth::cli::Cli;
+    use reth_exex_indexer::{indexer_exex, IndexApiServer, IndexRpc, IndexerArgs, IndexerDb};
+    use reth_node_ethereum::EthereumNode;
+
+    Cli::<IndexerArgs>::parse().run(|builder, indexer_args| async move {
+        let db_path = indexer_args.db_path(builder.config().datadir().data_dir());
+        let mut builder = builder.node(EthereumNode::default());
+        if indexer_args.enabled {
+            let db = IndexerDb::open(db_path)?;
+            let exex_db = db.clone();
+            builder = builder
+                .install_exex("indexer", move |ctx| async move {
+                    Ok(indexer_exex(ctx, exex_db, indexer_args.events))
+                })
+                .extend_rpc_modules(move |ctx| {
+                    ctx.modules.merge_configured(IndexRpc::new(db).into_rpc())?;
+                    Ok(())
+                });
+        }
+        let handle = builder.launch().await?;
+        handle.node_exit_future.await
+    })
+}
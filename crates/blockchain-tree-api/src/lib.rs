@@ -113,6 +113,13 @@ pub trait BlockchainTreeEngine: BlockchainTreeViewer + Send + Sync {
     ///
     /// Returns `Ok` if the blocks were canonicalized, or if the blocks were already canonical.
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError>;
+
+    /// Unwinds the canonical chain down to the given block, by reverting the blocks above it from
+    /// the database.
+    ///
+    /// The block, `unwind_to`, stays canonical. The reverted blocks are kept in the tree as a
+    /// side chain, together with their execution outcome.
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError>;
 }
 
 /// Represents the kind of validation that should be performed when inserting a block.
@@ -1,7 +1,9 @@
 //! Implementation of [`BlockchainTree`]
 
 use crate::{
-    metrics::{MakeCanonicalAction, MakeCanonicalDurationsRecorder, TreeMetrics},
+    metrics::{
+        BlockProcessingPhase, MakeCanonicalAction, MakeCanonicalDurationsRecorder, TreeMetrics,
+    },
     state::{BlockchainId, TreeState},
     AppendableChain, BlockIndices, BlockchainTreeConfig, ExecutionData, TreeExternals,
 };
@@ -10,7 +12,7 @@ use reth_blockchain_tree_api::{
     BlockAttachment, BlockStatus, BlockValidationKind, CanonicalOutcome, InsertPayloadOk,
 };
 use reth_consensus::{Consensus, ConsensusError};
-use reth_db_api::database::Database;
+use reth_db_api::{database::Database, models::StoredReorg};
 use reth_evm::execute::BlockExecutorProvider;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_execution_types::{Chain, ExecutionOutcome};
@@ -19,16 +21,19 @@ use reth_primitives::{
     SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, B256, U256,
 };
 use reth_provider::{
-    BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
+    BlockExecutionWriter, BlockNumReader, BlockReader, BlockWriter, CanonStateNotification,
     CanonStateNotificationSender, CanonStateNotifications, ChainSpecProvider, ChainSplit,
     ChainSplitTarget, DisplayBlocksChain, HeaderProvider, ProviderError, StaticFileProviderFactory,
+    TransactionVariant,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
+use reth_trie::trie_cursor::TrieNodeCache;
 use std::{
     collections::{btree_map::Entry, BTreeMap, HashSet},
     sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -118,13 +123,14 @@ where
         prune_modes: PruneModes,
     ) -> ProviderResult<Self> {
         let max_reorg_depth = config.max_reorg_depth() as usize;
-        // The size of the broadcast is twice the maximum reorg depth, because at maximum reorg
-        // depth at least N blocks must be sent at once.
-        let (canon_state_notification_sender, _receiver) =
-            tokio::sync::broadcast::channel(max_reorg_depth * 2);
+        // The capacity of each subscriber is twice the maximum reorg depth, because at maximum
+        // reorg depth at least N blocks must be sent at once.
+        let canon_state_notification_sender =
+            CanonStateNotificationSender::new(max_reorg_depth * 2);
 
         // Set the prune modes argument, on the provider
         externals.provider_factory = externals.provider_factory.with_prune_modes(prune_modes);
+        externals.trie_node_cache = config.trie_node_cache().map(TrieNodeCache::new);
 
         let last_canonical_hashes =
             externals.fetch_latest_canonical_hashes(config.num_of_canonical_hashes() as usize)?;
@@ -672,8 +678,14 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<InsertPayloadOk, InsertBlockError> {
+        let start = Instant::now();
         match block.try_seal_with_senders() {
-            Ok(block) => self.insert_block(block, BlockValidationKind::Exhaustive),
+            Ok(block) => {
+                self.externals
+                    .block_processing_metrics
+                    .record(BlockProcessingPhase::SenderRecovery, start.elapsed());
+                self.insert_block(block, BlockValidationKind::Exhaustive)
+            }
             Err(block) => Err(InsertBlockError::sender_recovery_error(block)),
         }
     }
@@ -840,7 +852,14 @@ where
 
     /// Update all block hashes. iterate over present and new list of canonical hashes and compare
     /// them. Remove all mismatches, disconnect them and removes all chains.
+    ///
+    /// The database may have been changed outside of the tree, e.g. by the pipeline, so the trie
+    /// node cache is cleared.
     pub fn update_block_hashes(&mut self) -> ProviderResult<BTreeMap<BlockNumber, B256>> {
+        if let Some(cache) = &self.externals.trie_node_cache {
+            cache.clear();
+        }
+
         let last_canonical_hashes = self
             .externals
             .fetch_latest_canonical_hashes(self.config.num_of_canonical_hashes() as usize)?;
@@ -1161,6 +1180,7 @@ where
 
             if let Some(old_canon_chain) = old_canon_chain {
                 self.update_reorg_metrics(old_canon_chain.len() as f64);
+                self.journal_reorg(canon_fork, &old_canon_chain, &new_canon_chain);
 
                 // Insert old canonical chain back into tree.
                 self.insert_unwound_chain(AppendableChain::new(old_canon_chain.clone()));
@@ -1202,7 +1222,11 @@ where
 
         // Send notification about new canonical chain and return outcome of canonicalization.
         let outcome = CanonicalOutcome::Committed { head: chain_notification.tip().header.clone() };
+        let start = Instant::now();
         let _ = self.canon_state_notification_sender.send(chain_notification);
+        self.externals
+            .block_processing_metrics
+            .record(BlockProcessingPhase::NotificationFanOut, start.elapsed());
         Ok(outcome)
     }
 
@@ -1251,6 +1275,11 @@ where
         };
         recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
 
+        if let Some(cache) = &self.externals.trie_node_cache {
+            cache.apply_updates(&trie_updates);
+        }
+
+        let start = Instant::now();
         let provider_rw = self.externals.provider_factory.provider_rw()?;
         provider_rw
             .append_blocks_with_state(
@@ -1263,11 +1292,17 @@ where
 
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
+        self.externals
+            .block_processing_metrics
+            .record(BlockProcessingPhase::TrieUpdatesPersist, start.elapsed());
 
         Ok(())
     }
 
     /// Unwind tables and put it inside state
+    ///
+    /// The unwind is sent as a [`CanonStateNotification::Reorg`] from the unwound blocks to the
+    /// new tip. The new tip was already canonical, so its chain has no receipts or state changes.
     pub fn unwind(&mut self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
         // nothing to be done if unwind_to is higher then the tip
         if self.block_indices().canonical_tip().number <= unwind_to {
@@ -1279,8 +1314,26 @@ where
         // check if there is block in chain
         if let Some(old_canon_chain) = old_canon_chain {
             self.state.block_indices.unwind_canonical_chain(unwind_to);
+
+            let new_tip = self
+                .externals
+                .provider_factory
+                .provider()?
+                .sealed_block_with_senders(unwind_to.into(), TransactionVariant::WithHash)?
+                .ok_or(ProviderError::HeaderNotFound(unwind_to.into()))?;
+            let new_canon_chain = Chain::new(
+                [new_tip],
+                ExecutionOutcome { first_block: unwind_to, ..Default::default() },
+                None,
+            );
+            let chain_notification = CanonStateNotification::Reorg {
+                old: Arc::new(old_canon_chain.clone()),
+                new: Arc::new(new_canon_chain),
+            };
+
             // insert old canonical chain to BlockchainTree.
             self.insert_unwound_chain(AppendableChain::new(old_canon_chain));
+            let _ = self.canon_state_notification_sender.send(chain_notification);
         }
 
         Ok(())
@@ -1326,6 +1379,9 @@ where
         let blocks_and_execution = provider_rw
             .take_block_and_execution_range(revert_range)
             .map_err(|e| CanonicalError::CanonicalRevert(e.to_string()))?;
+        if let Some(cache) = &self.externals.trie_node_cache {
+            cache.clear();
+        }
 
         provider_rw.commit()?;
 
@@ -1341,6 +1397,23 @@ where
         self.metrics.latest_reorg_depth.set(reorg_depth);
     }
 
+    /// Appends the reorg to the reorg journal.
+    ///
+    /// The reorg is already committed to the database at this point, so failing to journal it is
+    /// only logged.
+    fn journal_reorg(&self, fork_block: BlockNumHash, old: &Chain, new: &Chain) {
+        let reorg = StoredReorg {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            fork_block_number: fork_block.number,
+            fork_block_hash: fork_block.hash,
+            old_blocks: old.blocks_iter().map(|block| block.hash()).collect(),
+            new_blocks: new.blocks_iter().map(|block| block.hash()).collect(),
+        };
+        if let Err(err) = self.externals.save_reorg(reorg) {
+            error!(target: "blockchain_tree", %err, ?fork_block, "Failed to journal reorg");
+        }
+    }
+
     /// Update blockchain tree chains (canonical and sidechains) and sync metrics.
     ///
     /// NOTE: this method should not be called during the pipeline sync, because otherwise the sync
@@ -1389,7 +1462,7 @@ mod tests {
     };
     use reth_provider::{
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory_with_chain_spec},
-        ProviderFactory,
+        ProviderFactory, ReorgJournalReader,
     };
     use reth_stages_api::StageCheckpoint;
     use reth_trie::{root::state_root_unhashed, StateRoot};
@@ -1642,7 +1715,7 @@ mod tests {
             mock_block(3, Some(sidechain_block_1.hash()), Vec::from([mock_tx(2)]), 3);
 
         let mut tree = BlockchainTree::new(
-            TreeExternals::new(provider_factory, consensus, executor_provider),
+            TreeExternals::new(provider_factory.clone(), consensus, executor_provider),
             BlockchainTreeConfig::default(),
             PruneModes::default(),
         )
@@ -1666,7 +1739,7 @@ mod tests {
         );
 
         assert_eq!(
-            tree.insert_block(canonical_block_2, BlockValidationKind::Exhaustive).unwrap(),
+            tree.insert_block(canonical_block_2.clone(), BlockValidationKind::Exhaustive).unwrap(),
             InsertPayloadOk::Inserted(BlockStatus::Valid(BlockAttachment::Canonical))
         );
 
@@ -1704,6 +1777,19 @@ mod tests {
             tree.make_canonical(canonical_block_3.hash()).unwrap(),
             CanonicalOutcome::Committed { head: canonical_block_3.header.clone() }
         );
+
+        // every reorg is journaled, the latest one reverted the whole sidechain
+        let history = provider_factory.reorg_history(usize::MAX).unwrap();
+        assert_eq!(history.len(), 4);
+        let (id, reorg) = &history[0];
+        assert_eq!(*id, 3);
+        assert_eq!(reorg.fork_block(), fork_block.num_hash());
+        assert_eq!(reorg.old_blocks, vec![sidechain_block_1.hash(), sidechain_block_2.hash()]);
+        assert_eq!(
+            reorg.new_blocks,
+            vec![canonical_block_1.hash(), canonical_block_2.hash(), canonical_block_3.hash()]
+        );
+        assert_eq!(reorg.depth(), 2);
     }
 
     #[test]
@@ -2266,6 +2352,10 @@ mod tests {
 
         // unwind canonical
         assert!(tree.unwind(block1.number).is_ok());
+        assert_matches!(canon_notif.try_recv(),
+            Ok(CanonStateNotification::Reorg{ old, new })
+            if *old.blocks() == BTreeMap::from([(block2.number,block2.clone())]) &&
+                *new.blocks() == BTreeMap::from([(block1.number,block1.clone())]));
         // Trie state:
         //    b2   b2a (pending block)
         //   /    /
@@ -2364,16 +2454,16 @@ mod tests {
         // test pops execution results from vector, so order is from last to first.
         let externals =
             setup_externals(vec![exec3.clone(), exec2.clone(), exec1.clone(), exec3, exec2, exec1]);
-        let cloned_externals_1 = TreeExternals {
-            provider_factory: externals.provider_factory.clone(),
-            executor_factory: externals.executor_factory.clone(),
-            consensus: externals.consensus.clone(),
-        };
-        let cloned_externals_2 = TreeExternals {
-            provider_factory: externals.provider_factory.clone(),
-            executor_factory: externals.executor_factory.clone(),
-            consensus: externals.consensus.clone(),
-        };
+        let cloned_externals_1 = TreeExternals::new(
+            externals.provider_factory.clone(),
+            externals.consensus.clone(),
+            externals.executor_factory.clone(),
+        );
+        let cloned_externals_2 = TreeExternals::new(
+            externals.provider_factory.clone(),
+            externals.consensus.clone(),
+            externals.executor_factory.clone(),
+        );
 
         // last finalized block would be number 9.
         setup_genesis(&externals.provider_factory, genesis);
@@ -4,7 +4,7 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 
 use super::externals::TreeExternals;
-use crate::BundleStateDataRef;
+use crate::{metrics::BlockProcessingPhase, BundleStateDataRef};
 use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, InsertBlockErrorKind},
     BlockAttachment, BlockValidationKind,
@@ -209,11 +209,13 @@ impl AppendableChain {
         let block_hash = block.hash();
         let block = block.unseal();
 
+        let start = Instant::now();
         let state = executor.execute((&block, U256::MAX).into())?;
         let BlockExecutionOutput { state, receipts, requests, .. } = state;
         externals
             .consensus
             .validate_block_post_execution(&block, PostExecutionInput::new(&receipts, &requests))?;
+        externals.block_processing_metrics.record(BlockProcessingPhase::Execution, start.elapsed());
 
         let initial_execution_outcome =
             ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()]);
@@ -228,13 +230,20 @@ impl AppendableChain {
                     provider.block_execution_data_provider.execution_outcome().clone();
                 execution_outcome.extend(initial_execution_outcome.clone());
                 let hashed_state = execution_outcome.hash_state_slow();
-                ParallelStateRoot::new(consistent_view, hashed_state)
+                let mut state_root = ParallelStateRoot::new(consistent_view, hashed_state);
+                if let Some(cache) = &externals.trie_node_cache {
+                    state_root = state_root.with_trie_node_cache(cache.clone());
+                }
+                state_root
                     .incremental_root_with_updates()
                     .map(|(root, updates)| (root, Some(updates)))
                     .map_err(ProviderError::from)?
             } else {
                 (provider.state_root(initial_execution_outcome.state())?, None)
             };
+            externals
+                .block_processing_metrics
+                .record(BlockProcessingPhase::StateRoot, start.elapsed());
             if block.state_root != state_root {
                 return Err(ConsensusError::BodyStateRootDiff(
                     GotExpected { got: state_root, expected: block.state_root }.into(),
@@ -1,5 +1,7 @@
 //! Blockchain tree configuration
 
+use reth_trie::trie_cursor::TrieNodeCacheConfig;
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Copy, Debug)]
 pub struct BlockchainTreeConfig {
@@ -19,6 +21,9 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// The configuration of the trie node cache that is shared between the state root
+    /// computations of blocks extending the canonical chain, disabled if `None`.
+    trie_node_cache: Option<TrieNodeCacheConfig>,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +38,8 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // trie node caching is opt-in.
+            trie_node_cache: None,
         }
     }
 }
@@ -54,9 +61,16 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            trie_node_cache: None,
         }
     }
 
+    /// Enables the trie node cache with the given configuration.
+    pub const fn with_trie_node_cache(mut self, trie_node_cache: TrieNodeCacheConfig) -> Self {
+        self.trie_node_cache = Some(trie_node_cache);
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub const fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -88,4 +102,9 @@ impl BlockchainTreeConfig {
     pub const fn max_unconnected_blocks(&self) -> u32 {
         self.max_unconnected_blocks
     }
+
+    /// Return the configuration of the trie node cache, if enabled.
+    pub const fn trie_node_cache(&self) -> Option<TrieNodeCacheConfig> {
+        self.trie_node_cache
+    }
 }
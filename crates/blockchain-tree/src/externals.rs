@@ -1,14 +1,16 @@
 //! Blockchain tree externals.
 
+use crate::metrics::BlockProcessingMetrics;
 use reth_consensus::Consensus;
 use reth_db::{static_file::HeaderMask, tables};
-use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
+use reth_db_api::{cursor::DbCursorRO, database::Database, models::StoredReorg, transaction::DbTx};
 use reth_primitives::{BlockHash, BlockNumber, StaticFileSegment};
 use reth_provider::{
-    FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory, StaticFileProviderFactory,
-    StatsReader,
+    FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory, ReorgJournalWriter,
+    StaticFileProviderFactory, StatsReader,
 };
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::trie_cursor::TrieNodeCache;
 use std::{collections::BTreeMap, sync::Arc};
 
 /// A container for external components.
@@ -28,6 +30,10 @@ pub struct TreeExternals<DB, E> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: E,
+    /// The durations of the phases of processing a new block.
+    pub(crate) block_processing_metrics: BlockProcessingMetrics,
+    /// The cache of trie nodes shared between state root computations, if enabled.
+    pub(crate) trie_node_cache: Option<TrieNodeCache>,
 }
 
 impl<DB, E> TreeExternals<DB, E> {
@@ -37,7 +43,13 @@ impl<DB, E> TreeExternals<DB, E> {
         consensus: Arc<dyn Consensus>,
         executor_factory: E,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self {
+            provider_factory,
+            consensus,
+            executor_factory,
+            block_processing_metrics: BlockProcessingMetrics::default(),
+            trie_node_cache: None,
+        }
     }
 }
 
@@ -98,4 +110,11 @@ impl<DB: Database, E> TreeExternals<DB, E> {
         provider_rw.commit()?;
         Ok(())
     }
+
+    pub(crate) fn save_reorg(&self, reorg: StoredReorg) -> ProviderResult<u64> {
+        let provider_rw = self.provider_factory.provider_rw()?;
+        let id = provider_rw.append_reorg(reorg)?;
+        provider_rw.commit()?;
+        Ok(id)
+    }
 }
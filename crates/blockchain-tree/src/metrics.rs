@@ -33,6 +33,78 @@ pub struct BlockBufferMetrics {
     pub blocks: Gauge,
 }
 
+/// The phases of processing a new block at the tip, from receiving it to notifying the
+/// subscribers of the new canonical chain.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum BlockProcessingPhase {
+    /// Recovering the senders of the transactions.
+    SenderRecovery,
+    /// Executing the block and validating the execution outcome.
+    Execution,
+    /// Computing and validating the state root.
+    StateRoot,
+    /// Persisting the blocks, their state and trie updates once they became canonical.
+    TrieUpdatesPersist,
+    /// Notifying the subscribers of the new canonical chain.
+    NotificationFanOut,
+}
+
+/// Durations of the phases of processing a new block.
+///
+/// Every phase is recorded in a histogram, and the duration of the most recent block in a gauge.
+#[derive(Metrics)]
+#[metrics(scope = "blockchain_tree.block_processing")]
+pub(crate) struct BlockProcessingMetrics {
+    /// Duration of the sender recovery of a block.
+    sender_recovery_duration_seconds: Histogram,
+    /// Duration of the execution of a block.
+    execution_duration_seconds: Histogram,
+    /// Duration of the state root computation of a block.
+    state_root_duration_seconds: Histogram,
+    /// Duration of persisting the blocks and trie updates of a new canonical chain.
+    trie_updates_persist_duration_seconds: Histogram,
+    /// Duration of notifying the subscribers of a new canonical chain.
+    notification_fan_out_duration_seconds: Histogram,
+    /// Duration of the sender recovery of the most recent block.
+    latest_sender_recovery_duration_seconds: Gauge,
+    /// Duration of the execution of the most recent block.
+    latest_execution_duration_seconds: Gauge,
+    /// Duration of the state root computation of the most recent block.
+    latest_state_root_duration_seconds: Gauge,
+    /// Duration of persisting the most recent canonical chain.
+    latest_trie_updates_persist_duration_seconds: Gauge,
+    /// Duration of notifying the subscribers of the most recent canonical chain.
+    latest_notification_fan_out_duration_seconds: Gauge,
+}
+
+impl BlockProcessingMetrics {
+    /// Records the duration of the given phase.
+    pub(crate) fn record(&self, phase: BlockProcessingPhase, duration: Duration) {
+        let (histogram, latest) = match phase {
+            BlockProcessingPhase::SenderRecovery => (
+                &self.sender_recovery_duration_seconds,
+                &self.latest_sender_recovery_duration_seconds,
+            ),
+            BlockProcessingPhase::Execution => {
+                (&self.execution_duration_seconds, &self.latest_execution_duration_seconds)
+            }
+            BlockProcessingPhase::StateRoot => {
+                (&self.state_root_duration_seconds, &self.latest_state_root_duration_seconds)
+            }
+            BlockProcessingPhase::TrieUpdatesPersist => (
+                &self.trie_updates_persist_duration_seconds,
+                &self.latest_trie_updates_persist_duration_seconds,
+            ),
+            BlockProcessingPhase::NotificationFanOut => (
+                &self.notification_fan_out_duration_seconds,
+                &self.latest_notification_fan_out_duration_seconds,
+            ),
+        };
+        histogram.record(duration);
+        latest.set(duration.as_secs_f64());
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct MakeCanonicalDurationsRecorder {
     start: Instant,
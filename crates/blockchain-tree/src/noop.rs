@@ -69,6 +69,10 @@ impl BlockchainTreeEngine for NoopBlockchainTree {
         Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash }.into())
     }
 
+    fn unwind(&self, _unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        Ok(())
+    }
+
     fn update_block_hashes_and_clear_buffered(
         &self,
     ) -> Result<BTreeMap<BlockNumber, BlockHash>, CanonicalError> {
@@ -104,6 +104,14 @@ where
         tree.update_chains_metrics();
         res
     }
+
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        trace!(target: "blockchain_tree", unwind_to, "Unwinding canonical chain");
+        let mut tree = self.tree.write();
+        let res = tree.unwind(unwind_to);
+        tree.update_chains_metrics();
+        res
+    }
 }
 
 impl<DB, E> BlockchainTreeViewer for ShareableBlockchainTree<DB, E>
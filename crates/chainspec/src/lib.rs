@@ -12,8 +12,8 @@
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
 pub use info::ChainInfo;
 pub use spec::{
-    BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, DepositContract,
-    ForkBaseFeeParams, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
+    BaseFeeParams, BaseFeeParamsKind, BlobParams, BlobSchedule, ChainSpec, ChainSpecBuilder,
+    DepositContract, ForkBaseFeeParams, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
 };
 #[cfg(feature = "optimism")]
 pub use spec::{BASE_MAINNET, BASE_SEPOLIA, OP_MAINNET, OP_SEPOLIA};
@@ -29,6 +29,10 @@ mod info;
 /// The chain spec module.
 mod spec;
 
+/// Hardfork activation overrides.
+mod overrides;
+pub use overrides::{HardforkOverrideError, HardforkOverrides};
+
 /// Chain specific constants
 pub(crate) mod constants;
 
@@ -0,0 +1,222 @@
+use crate::ChainSpec;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String};
+use core::str::FromStr;
+use reth_ethereum_forks::{EthereumHardfork, ForkCondition};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Activation overrides of [`EthereumHardfork`]s that are layered on top of a [`ChainSpec`].
+///
+/// Forks before [`EthereumHardfork::Paris`] are activated at the given block number, forks after
+/// it at the given timestamp. Paris itself is activated by total difficulty and can't be
+/// overridden.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardforkOverrides(BTreeMap<EthereumHardfork, u64>);
+
+impl HardforkOverrides {
+    /// Sets the activation block number or timestamp of the fork and returns the previous
+    /// override, if any.
+    pub fn insert(&mut self, fork: EthereumHardfork, activation: u64) -> Option<u64> {
+        self.0.insert(fork, activation)
+    }
+
+    /// Returns the override of the fork, if any.
+    pub fn get(&self, fork: EthereumHardfork) -> Option<u64> {
+        self.0.get(&fork).copied()
+    }
+
+    /// Adds the overrides of `other`, which take precedence over the existing ones.
+    pub fn extend(&mut self, other: Self) {
+        self.0.extend(other.0)
+    }
+
+    /// Returns `true` if no fork is overridden.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the overridden forks and their activation, in fork order.
+    pub fn iter(&self) -> impl Iterator<Item = (EthereumHardfork, u64)> + '_ {
+        self.0.iter().map(|(fork, activation)| (*fork, *activation))
+    }
+
+    /// Parses the overrides from pairs of fork names and activations, e.g. the entries of the
+    /// `[hardforks]` table of the config file.
+    ///
+    /// Fork names are case-insensitive.
+    pub fn from_named<'a>(
+        forks: impl IntoIterator<Item = (&'a str, u64)>,
+    ) -> Result<Self, HardforkOverrideError> {
+        forks
+            .into_iter()
+            .map(|(name, activation)| {
+                EthereumHardfork::from_str(name)
+                    .map(|fork| (fork, activation))
+                    .map_err(|_| HardforkOverrideError::UnknownFork(name.into()))
+            })
+            .collect()
+    }
+}
+
+impl FromIterator<(EthereumHardfork, u64)> for HardforkOverrides {
+    fn from_iter<T: IntoIterator<Item = (EthereumHardfork, u64)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Error returned when [`HardforkOverrides`] can't be applied to a [`ChainSpec`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum HardforkOverrideError {
+    /// The fork name is not an Ethereum hardfork.
+    #[error("unknown hardfork: {0}")]
+    UnknownFork(String),
+    /// The fork is not activated by block number or timestamp.
+    #[error("{0} is activated by total difficulty and can't be overridden")]
+    UnsupportedFork(EthereumHardfork),
+    /// The fork would activate before a fork that precedes it.
+    #[error("{fork} would activate before {previous}")]
+    OutOfOrder {
+        /// The fork that activates too early.
+        fork: EthereumHardfork,
+        /// The preceding fork that activates after it.
+        previous: EthereumHardfork,
+    },
+}
+
+impl ChainSpec {
+    /// Applies the hardfork overrides to the chain spec.
+    ///
+    /// Overridden forks that are not part of the chain spec yet are scheduled after its existing
+    /// forks. The resulting schedule is validated: every fork has to activate at or after the
+    /// forks preceding it.
+    ///
+    /// If an override changes which forks are active at genesis, the cached genesis hash is
+    /// cleared since the genesis header changes with it. The applied overrides are recorded in
+    /// [`ChainSpec::hardfork_overrides`].
+    pub fn apply_hardfork_overrides(
+        &mut self,
+        overrides: &HardforkOverrides,
+    ) -> Result<(), HardforkOverrideError> {
+        let mut genesis_changed = false;
+        for (fork, activation) in overrides.iter() {
+            let condition = match fork {
+                EthereumHardfork::Paris => return Err(HardforkOverrideError::UnsupportedFork(fork)),
+                fork if fork < EthereumHardfork::Paris => ForkCondition::Block(activation),
+                _ => ForkCondition::Timestamp(activation),
+            };
+            genesis_changed |= self.active_at_genesis(self.hardforks.fork(fork)) !=
+                self.active_at_genesis(condition);
+            self.hardforks.insert(fork, condition);
+        }
+
+        self.validate_hardfork_schedule()?;
+        self.hardfork_overrides.extend(overrides.clone());
+
+        if genesis_changed {
+            self.genesis_hash = None;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the condition is met by the genesis block.
+    const fn active_at_genesis(&self, condition: ForkCondition) -> bool {
+        condition.active_at_block(0) || condition.active_at_timestamp(self.genesis.timestamp)
+    }
+
+    /// Ensures the Ethereum forks of the chain spec are listed in fork order and that none of
+    /// them activates before the forks preceding it.
+    fn validate_hardfork_schedule(&self) -> Result<(), HardforkOverrideError> {
+        let mut previous: Option<(EthereumHardfork, ForkCondition)> = None;
+        for (fork, condition) in self.hardforks.forks_iter() {
+            let Ok(fork) = EthereumHardfork::from_str(fork.name()) else { continue };
+            if let Some((previous, previous_condition)) = previous {
+                let in_order = match (previous_condition, condition) {
+                    (ForkCondition::Block(previous_block), ForkCondition::Block(block)) => {
+                        previous_block <= block
+                    }
+                    (
+                        ForkCondition::Timestamp(previous_timestamp),
+                        ForkCondition::Timestamp(timestamp),
+                    ) => previous_timestamp <= timestamp,
+                    // a fork that never activates can't be followed by a fork that does
+                    (ForkCondition::Never, condition) => condition == ForkCondition::Never,
+                    _ => true,
+                };
+                if previous > fork || !in_order {
+                    return Err(HardforkOverrideError::OutOfOrder { fork, previous })
+                }
+            }
+            previous = Some((fork, condition));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAINNET;
+    use reth_ethereum_forks::{EthereumHardforks, Head};
+
+    #[test]
+    fn override_timestamp_fork() {
+        let mut spec = (**MAINNET).clone();
+        let overrides: HardforkOverrides =
+            [(EthereumHardfork::Prague, 2_000_000_000)].into_iter().collect();
+        spec.apply_hardfork_overrides(&overrides).unwrap();
+
+        assert_eq!(spec.fork(EthereumHardfork::Prague), ForkCondition::Timestamp(2_000_000_000));
+        assert!(spec.is_prague_active_at_timestamp(2_000_000_000));
+        assert!(!spec.is_prague_active_at_timestamp(1_999_999_999));
+        assert_eq!(spec.hardfork_overrides, overrides);
+        // the genesis is unaffected
+        assert_eq!(spec.genesis_hash, MAINNET.genesis_hash);
+
+        let head = Head { number: 20_000_000, timestamp: 1_800_000_000, ..Default::default() };
+        assert_eq!(spec.fork_id(&head).next, 2_000_000_000);
+        assert_ne!(spec.fork_id(&head), MAINNET.fork_id(&head));
+    }
+
+    #[test]
+    fn override_at_genesis_clears_genesis_hash() {
+        let mut spec = (**MAINNET).clone();
+        let overrides: HardforkOverrides = [(EthereumHardfork::Shanghai, 0)].into_iter().collect();
+        spec.apply_hardfork_overrides(&overrides).unwrap();
+
+        assert_eq!(spec.genesis_hash, None);
+        assert!(spec.genesis_header().withdrawals_root.is_some());
+    }
+
+    #[test]
+    fn rejects_inconsistent_overrides() {
+        let mut spec = (**MAINNET).clone();
+        let overrides: HardforkOverrides = [(EthereumHardfork::Cancun, 1)].into_iter().collect();
+        assert_eq!(
+            spec.apply_hardfork_overrides(&overrides),
+            Err(HardforkOverrideError::OutOfOrder {
+                fork: EthereumHardfork::Cancun,
+                previous: EthereumHardfork::Shanghai
+            })
+        );
+
+        let mut spec = (**MAINNET).clone();
+        let overrides: HardforkOverrides = [(EthereumHardfork::Paris, 1)].into_iter().collect();
+        assert_eq!(
+            spec.apply_hardfork_overrides(&overrides),
+            Err(HardforkOverrideError::UnsupportedFork(EthereumHardfork::Paris))
+        );
+    }
+
+    #[test]
+    fn parse_named_overrides() {
+        let overrides = HardforkOverrides::from_named([("prague", 10), ("Cancun", 5)]).unwrap();
+        assert_eq!(overrides.get(EthereumHardfork::Prague), Some(10));
+        assert_eq!(overrides.get(EthereumHardfork::Cancun), Some(5));
+
+        assert_eq!(
+            HardforkOverrides::from_named([("osaka", 10)]),
+            Err(HardforkOverrideError::UnknownFork("osaka".to_string()))
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::constants::MAINNET_DEPOSIT_CONTRACT;
+use crate::{constants::MAINNET_DEPOSIT_CONTRACT, HardforkOverrides};
 #[cfg(not(feature = "std"))]
 use alloc::{
     collections::BTreeMap,
@@ -11,6 +11,7 @@ use alloy_chains::{Chain, ChainKind, NamedChain};
 use alloy_genesis::Genesis;
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
 use alloy_trie::EMPTY_ROOT_HASH;
+use core::str::FromStr;
 use derive_more::From;
 use once_cell::sync::Lazy;
 use reth_ethereum_forks::{
@@ -24,7 +25,7 @@ use reth_primitives_traits::{
 };
 use reth_trie_common::root::state_root_ref_unhashed;
 #[cfg(feature = "std")]
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 #[cfg(feature = "optimism")]
 use crate::constants::optimism::{
@@ -38,6 +39,7 @@ use reth_network_peers::{
     base_nodes, base_testnet_nodes, goerli_nodes, holesky_nodes, mainnet_nodes, op_nodes,
     op_testnet_nodes, sepolia_nodes,
 };
+pub use reth_primitives_traits::BlobParams;
 
 /// The Ethereum mainnet spec
 pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
@@ -62,6 +64,8 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 3500,
+        blob_schedule: BlobSchedule::default(),
+        hardfork_overrides: HardforkOverrides::default(),
     }
     .into()
 });
@@ -86,6 +90,8 @@ pub static GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        blob_schedule: BlobSchedule::default(),
+        hardfork_overrides: HardforkOverrides::default(),
     }
     .into()
 });
@@ -110,6 +116,8 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        blob_schedule: BlobSchedule::default(),
+        hardfork_overrides: HardforkOverrides::default(),
     }
     .into()
 });
@@ -132,6 +140,8 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        blob_schedule: BlobSchedule::default(),
+        hardfork_overrides: HardforkOverrides::default(),
     }
     .into()
 });
@@ -259,6 +269,44 @@ pub static BASE_MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
     .into()
 });
 
+/// The [`BlobParams`] of the forks that change them, sorted by [Hardfork] activation order.
+///
+/// The parameters of a fork apply until the next fork of the schedule is activated.
+#[derive(Clone, Debug, PartialEq, Eq, From)]
+pub struct BlobSchedule(Vec<(Box<dyn Hardfork>, BlobParams)>);
+
+impl BlobSchedule {
+    /// Returns the blob schedule of Ethereum.
+    pub fn ethereum() -> Self {
+        Self(vec![(EthereumHardfork::Cancun.boxed(), BlobParams::cancun())])
+    }
+
+    /// Returns the blob schedule of the `blobSchedule` of a genesis file, which maps lowercase
+    /// Ethereum fork names to their [`BlobParams`].
+    ///
+    /// Cancun uses the [`BlobParams::cancun`] parameters unless they are overridden. Unknown forks
+    /// are ignored.
+    fn from_genesis(schedule: BTreeMap<String, BlobParams>) -> Self {
+        let mut forks = BTreeMap::from([(EthereumHardfork::Cancun, BlobParams::cancun())]);
+        forks.extend(schedule.into_iter().filter_map(|(name, params)| {
+            EthereumHardfork::from_str(&name).ok().map(|fork| (fork, params))
+        }));
+        Self(forks.into_iter().map(|(fork, params)| (fork.boxed(), params)).collect())
+    }
+
+    /// Returns an iterator over the forks of the schedule and their [`BlobParams`], in activation
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&dyn Hardfork, BlobParams)> {
+        self.0.iter().map(|(fork, params)| (&**fork, *params))
+    }
+}
+
+impl Default for BlobSchedule {
+    fn default() -> Self {
+        Self::ethereum()
+    }
+}
+
 /// A wrapper around [`BaseFeeParams`] that allows for specifying constant or dynamic EIP-1559
 /// parameters based on the active [Hardfork].
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -335,10 +383,17 @@ pub struct ChainSpec {
     /// The parameters that configure how a block's base fee is computed
     pub base_fee_params: BaseFeeParamsKind,
 
+    /// The blob parameters of the forks that change them
+    pub blob_schedule: BlobSchedule,
+
     /// The delete limit for pruner, per block. In the actual pruner run it will be multiplied by
     /// the amount of blocks between pruner runs to account for the difference in amount of new
     /// data coming in.
     pub prune_delete_limit: usize,
+
+    /// The hardfork activations that were overridden by the node's configuration, see
+    /// [`ChainSpec::apply_hardfork_overrides`].
+    pub hardfork_overrides: HardforkOverrides,
 }
 
 impl Default for ChainSpec {
@@ -351,7 +406,9 @@ impl Default for ChainSpec {
             hardforks: Default::default(),
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+            blob_schedule: Default::default(),
             prune_delete_limit: MAINNET.prune_delete_limit,
+            hardfork_overrides: Default::default(),
         }
     }
 }
@@ -407,6 +464,14 @@ impl ChainSpec {
         &self.genesis
     }
 
+    /// Returns the state root of the genesis block if it was pre-computed in the genesis file.
+    ///
+    /// Genesis files of chains with a large alloc can carry the root as `stateRoot` in the chain
+    /// config, so the alloc doesn't have to be loaded to compute the genesis header.
+    pub fn precomputed_genesis_state_root(&self) -> Option<B256> {
+        self.genesis.config.extra_fields.get_deserialized("stateRoot").and_then(Result::ok)
+    }
+
     /// Get the header for the genesis block.
     pub fn genesis_header(&self) -> Header {
         // If London is activated at genesis, we set the initial base fee as per EIP-1559.
@@ -444,7 +509,9 @@ impl ChainSpec {
             difficulty: self.genesis.difficulty,
             nonce: self.genesis.nonce,
             extra_data: self.genesis.extra_data.clone(),
-            state_root: state_root_ref_unhashed(&self.genesis.alloc),
+            state_root: self
+                .precomputed_genesis_state_root()
+                .unwrap_or_else(|| state_root_ref_unhashed(&self.genesis.alloc)),
             timestamp: self.genesis.timestamp,
             mix_hash: self.genesis.mix_hash,
             beneficiary: self.genesis.coinbase,
@@ -511,6 +578,21 @@ impl ChainSpec {
         }
     }
 
+    /// Get the [`BlobParams`] for the chain at the given timestamp.
+    ///
+    /// These are the parameters of the last fork of the blob schedule that is active at the
+    /// timestamp, or of the first fork if none is active.
+    pub fn blob_params_at_timestamp(&self, timestamp: u64) -> BlobParams {
+        let BlobSchedule(ref schedule) = self.blob_schedule;
+        schedule
+            .iter()
+            .rev()
+            .find(|(fork, _)| self.hardforks.is_fork_active_at_timestamp(fork.clone(), timestamp))
+            .or_else(|| schedule.first())
+            .map(|(_, params)| *params)
+            .unwrap_or_else(BlobParams::cancun)
+    }
+
     /// Get the hash of the genesis block.
     pub fn genesis_hash(&self) -> B256 {
         self.genesis_hash.unwrap_or_else(|| self.genesis_header().hash_slow())
@@ -820,6 +902,14 @@ impl From<Genesis> for ChainSpec {
             DepositContract { address, block: 0, topic: MAINNET_DEPOSIT_CONTRACT.topic }
         });
 
+        let blob_schedule = genesis
+            .config
+            .extra_fields
+            .get_deserialized("blobSchedule")
+            .and_then(Result::ok)
+            .map(BlobSchedule::from_genesis)
+            .unwrap_or_default();
+
         Self {
             chain: genesis.config.chain_id.into(),
             genesis,
@@ -827,6 +917,7 @@ impl From<Genesis> for ChainSpec {
             hardforks: ChainHardforks::new(hardforks),
             paris_block_and_final_difficulty,
             deposit_contract,
+            blob_schedule,
             #[cfg(feature = "optimism")]
             base_fee_params: optimism_genesis_info.base_fee_params,
             ..Default::default()
@@ -2524,6 +2615,21 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.base_fee_per_gas, Some(0x1337));
     }
 
+    #[test]
+    fn precomputed_genesis_state_root() {
+        let root = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        let s = r#"{"config":{"chainId":1337,"stateRoot":"0x1111111111111111111111111111111111111111111111111111111111111111"},"difficulty":"0x1","gasLimit":"0x4c4b40","alloc":{}}"#;
+        let chainspec: ChainSpec = serde_json::from_str::<Genesis>(s).unwrap().into();
+        assert_eq!(chainspec.precomputed_genesis_state_root(), Some(root));
+        assert_eq!(chainspec.genesis_header().state_root, root);
+
+        let s =
+            r#"{"config":{"chainId":1337},"difficulty":"0x1","gasLimit":"0x4c4b40","alloc":{}}"#;
+        let chainspec: ChainSpec = serde_json::from_str::<Genesis>(s).unwrap().into();
+        assert_eq!(chainspec.precomputed_genesis_state_root(), None);
+        assert_eq!(chainspec.genesis_header().state_root, EMPTY_ROOT_HASH);
+    }
+
     #[test]
     fn test_parse_cancun_genesis_json() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
@@ -2569,6 +2675,32 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.cancun_time, Some(4661));
     }
 
+    #[test]
+    fn blob_schedule_from_genesis() {
+        let s = r#"{"config":{"chainId":1337,"londonBlock":0,"terminalTotalDifficulty":0,"shanghaiTime":0,"cancunTime":10,"pragueTime":20,"blobSchedule":{"prague":{"target":6,"max":9,"baseFeeUpdateFraction":5007716},"unknown":{"target":1,"max":1,"baseFeeUpdateFraction":1}}},"gasLimit":"0x1c9c380","difficulty":"0x0","alloc":{}}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let spec = ChainSpec::from(genesis);
+
+        let prague = BlobParams {
+            target_blobs_per_block: 6,
+            max_blobs_per_block: 9,
+            base_fee_update_fraction: 5_007_716,
+        };
+        assert_eq!(
+            spec.blob_schedule
+                .iter()
+                .map(|(fork, params)| (fork.name(), params))
+                .collect::<Vec<_>>(),
+            vec![("Cancun", BlobParams::cancun()), ("Prague", prague)]
+        );
+        assert_eq!(spec.blob_params_at_timestamp(0), BlobParams::cancun());
+        assert_eq!(spec.blob_params_at_timestamp(19), BlobParams::cancun());
+        assert_eq!(spec.blob_params_at_timestamp(20), prague);
+
+        // without a blob schedule, the Cancun parameters apply to all forks
+        assert_eq!(MAINNET.blob_params_at_timestamp(u64::MAX), BlobParams::cancun());
+    }
+
     #[test]
     fn test_paris_block_and_total_difficulty() {
         let genesis = Genesis { gas_limit: 0x2fefd8u128, ..Default::default() };
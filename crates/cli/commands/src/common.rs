@@ -2,7 +2,7 @@
 
 use clap::Parser;
 use reth_beacon_consensus::EthBeaconConsensus;
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, HardforkOverrides};
 use reth_config::{config::EtlConfig, Config};
 use reth_db::{init_db, open_db_read_only, DatabaseEnv};
 use reth_db_common::init::init_genesis;
@@ -10,10 +10,12 @@ use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHe
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_node_core::{
     args::{
-        utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+        utils::{chain_help, chain_value_parser},
         DatabaseArgs, DatadirArgs,
     },
     dirs::{ChainPath, DataDirPath},
+    manifest::{register_datadir, DatadirChain, DEFAULT_CHAIN},
+    node_config::chain_with_hardfork_overrides,
 };
 use reth_primitives::B256;
 use reth_provider::{providers::StaticFileProvider, ProviderFactory, StaticFileProviderFactory};
@@ -41,7 +43,7 @@ pub struct EnvironmentArgs {
         long,
         value_name = "CHAIN_OR_PATH",
         long_help = chain_help(),
-        default_value = SUPPORTED_CHAINS[0],
+        default_value = DEFAULT_CHAIN,
         value_parser = chain_value_parser
     )]
     pub chain: Arc<ChainSpec>,
@@ -59,11 +61,6 @@ impl EnvironmentArgs {
         let db_path = data_dir.db();
         let sf_path = data_dir.static_files();
 
-        if access.is_read_write() {
-            reth_fs_util::create_dir_all(&db_path)?;
-            reth_fs_util::create_dir_all(&sf_path)?;
-        }
-
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
         let mut config: Config = confy::load_path(config_path)
             .inspect_err(
@@ -71,6 +68,17 @@ impl EnvironmentArgs {
             )
             .unwrap_or_default();
 
+        // Use the same fork schedule as the node
+        let chain =
+            chain_with_hardfork_overrides(&self.chain, &config, HardforkOverrides::default())?;
+
+        DatadirChain::ensure_datadir_matches(data_dir.data_dir(), &chain)?;
+
+        if access.is_read_write() {
+            reth_fs_util::create_dir_all(&db_path)?;
+            reth_fs_util::create_dir_all(&sf_path)?;
+        }
+
         // Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
         if config.stages.etl.dir.is_none() {
             config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
@@ -80,7 +88,9 @@ impl EnvironmentArgs {
         let (db, sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
+                StaticFileProvider::read_write(sf_path)?
+                    .with_compression(config.static_files.compression)
+                    .with_receipts_layout(config.static_files.receipts_layout),
             ),
             AccessRights::RO => (
                 Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
@@ -88,10 +98,11 @@ impl EnvironmentArgs {
             ),
         };
 
-        let provider_factory = self.create_provider_factory(&config, db, sfp)?;
+        let provider_factory = self.create_provider_factory(chain.clone(), &config, db, sfp)?;
         if access.is_read_write() {
-            debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
+            debug!(target: "reth::cli", chain=%chain.chain, genesis=?chain.genesis_hash(), "Initializing genesis");
             init_genesis(provider_factory.clone())?;
+            register_datadir(data_dir.data_dir(), &chain)?;
         }
 
         Ok(Environment { config, provider_factory, data_dir })
@@ -104,6 +115,7 @@ impl EnvironmentArgs {
     /// node to heal.
     fn create_provider_factory(
         &self,
+        chain: Arc<ChainSpec>,
         config: &Config,
         db: Arc<DatabaseEnv>,
         static_file_provider: StaticFileProvider,
@@ -111,7 +123,7 @@ impl EnvironmentArgs {
         let has_receipt_pruning = config.prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
         let prune_modes =
             config.prune.as_ref().map(|prune| prune.segments.clone()).unwrap_or_default();
-        let factory = ProviderFactory::new(db, self.chain.clone(), static_file_provider)
+        let factory = ProviderFactory::new(db, chain.clone(), static_file_provider)
             .with_prune_modes(prune_modes.clone());
 
         info!(target: "reth::cli", "Verifying storage consistency.");
@@ -139,7 +151,7 @@ impl EnvironmentArgs {
                 .add_stages(DefaultStages::new(
                     factory.clone(),
                     tip_rx,
-                    Arc::new(EthBeaconConsensus::new(self.chain.clone())),
+                    Arc::new(EthBeaconConsensus::new(chain)),
                     NoopHeaderDownloader::default(),
                     NoopBodiesDownloader::default(),
                     NoopBlockExecutorProvider::default(),
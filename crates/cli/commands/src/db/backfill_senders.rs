@@ -0,0 +1,60 @@
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_primitives::TransactionSignedNoHash;
+use reth_provider::{ProviderError, ProviderFactory, TransactionsProvider};
+use tracing::info;
+
+/// The arguments for the `reth db backfill-senders` command
+///
+/// Stores the senders of the transactions static files with them, so that they don't have to be
+/// recovered again once they are pruned from the database. Senders are taken from the database
+/// where available, and recovered otherwise.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The number of transactions whose senders are stored at once.
+    #[arg(long, default_value_t = 100_000, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+}
+
+impl Command {
+    /// Execute `db backfill-senders` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+        let missing = static_file_provider.missing_persisted_senders()?;
+        if missing.is_empty() {
+            println!("The senders of all transactions static files are stored");
+            return Ok(())
+        }
+
+        for range in missing {
+            let mut start = range.start;
+            while start < range.end {
+                let end = range.end.min(start + self.batch_size);
+
+                let provider = provider_factory.provider()?;
+                let mut senders = provider.senders_by_tx_range(start..end)?;
+                let recovered = senders.len() as u64 != end - start;
+                if recovered {
+                    let transactions = static_file_provider.transactions_by_tx_range(start..end)?;
+                    senders =
+                        TransactionSignedNoHash::recover_signers(&transactions, transactions.len())
+                            .ok_or(ProviderError::SenderRecoveryError)?;
+                }
+                drop(provider);
+
+                let stored = static_file_provider.persist_senders(start, &senders)?;
+                eyre::ensure!(
+                    stored == senders.len(),
+                    "Stored {stored} of {} senders from transaction {start}",
+                    senders.len()
+                );
+                info!(target: "reth::cli", from = start, to = end - 1, recovered, "Stored senders");
+
+                start = end;
+            }
+        }
+
+        println!("The senders of all transactions static files are stored");
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use clap::Parser;
+use reth_config::StaticFilesConfig;
+use reth_db_api::database::Database;
+use reth_provider::{ProviderFactory, StaticFileProviderFactory};
+use reth_static_file_types::{Compression, ReceiptsLayout, StaticFileSegment};
+use tracing::info;
+
+/// The arguments for the `reth db compact-static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Static file segments to rewrite. Defaults to all segments.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// Compression to rewrite the static files with. Defaults to the compression configured for
+    /// each segment in the `[static_files.compression]` section of the config file.
+    #[arg(long, value_enum)]
+    compression: Option<Compression>,
+
+    /// Layout to rewrite the receipts static files with, which migrates existing receipts between
+    /// layouts. Defaults to the `receipts_layout` of the `[static_files]` section of the config
+    /// file.
+    #[arg(long, value_enum)]
+    receipts_layout: Option<ReceiptsLayout>,
+}
+
+impl Command {
+    /// Execute `db compact-static-files` command
+    pub fn execute<DB: Database>(
+        self,
+        config: &StaticFilesConfig,
+        provider_factory: ProviderFactory<DB>,
+    ) -> eyre::Result<()> {
+        let segments = if self.segments.is_empty() {
+            vec![
+                StaticFileSegment::Headers,
+                StaticFileSegment::Transactions,
+                StaticFileSegment::Receipts,
+            ]
+        } else {
+            self.segments
+        };
+        let static_file_provider = provider_factory.static_file_provider();
+        let receipts_layout = self.receipts_layout.unwrap_or(config.receipts_layout);
+
+        for segment in segments {
+            let compression = self.compression.unwrap_or_else(|| config.compression.get(segment));
+            info!(target: "reth::cli", ?segment, compression = compression.as_ref(), receipts_layout = segment.is_receipts().then(|| receipts_layout.as_ref()), "Compacting static files");
+
+            let (mut uncompressed_size, mut original_size, mut compacted_size) = (0, 0, 0);
+            for compaction in
+                static_file_provider.compact_segment(segment, compression, receipts_layout)?
+            {
+                info!(
+                    target: "reth::cli",
+                    path = ?compaction.path,
+                    rows = compaction.rows,
+                    original_size = compaction.original_size,
+                    compacted_size = compaction.compacted_size,
+                    original_ratio = format!("{:.2}", compaction.original_ratio()),
+                    compacted_ratio = format!("{:.2}", compaction.compacted_ratio()),
+                    "Compacted static file"
+                );
+                uncompressed_size += compaction.uncompressed_size;
+                original_size += compaction.original_size;
+                compacted_size += compaction.compacted_size;
+            }
+
+            info!(
+                target: "reth::cli",
+                ?segment,
+                uncompressed_size,
+                original_size,
+                compacted_size,
+                "Compacted static file segment"
+            );
+        }
+
+        Ok(())
+    }
+}
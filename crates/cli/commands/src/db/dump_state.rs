@@ -0,0 +1,40 @@
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_db_common::state_dump::dump_state_at;
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockNumReader, ProviderFactory};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db dump-state` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The block to dump the state at. Defaults to the latest block.
+    ///
+    /// The account and storage history of the block must not be pruned.
+    #[arg(long, value_name = "BLOCK")]
+    at: Option<BlockNumber>,
+
+    /// The path to write the state dump to.
+    ///
+    /// The dump uses the format of `reth init-state`: the first line is the state root, followed
+    /// by one account per line, ordered by address.
+    #[arg(value_name = "OUTPUT_FILE")]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `db dump-state` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let block_number = match self.at {
+            Some(block_number) => block_number,
+            None => provider_factory.best_block_number()?,
+        };
+
+        let writer = BufWriter::new(File::create(&self.path)?);
+        let summary = dump_state_at(&provider_factory, block_number, writer)?;
+        info!(target: "reth::cli", path = ?self.path, block_number, state_root = %summary.state_root, accounts = summary.accounts, "State dump written");
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use clap::Parser;
 use reth_db::{
-    static_file::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask, TransactionMask},
-    tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
+    static_file::{
+        decode_receipt, ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, TransactionMask,
+    },
+    tables, RawKey, RawTable, TableViewer, Transactions,
 };
 use reth_db_api::{
     database::Database,
@@ -10,7 +12,7 @@ use reth_db_api::{
 use reth_db_common::DbTool;
 use reth_primitives::{BlockHash, Header};
 use reth_provider::StaticFileProviderFactory;
-use reth_static_file_types::StaticFileSegment;
+use reth_static_file_types::{ReceiptsLayout, StaticFileSegment};
 use tracing::error;
 
 /// The arguments for the `reth db get` command
@@ -68,10 +70,10 @@ impl Command {
                         table_key::<tables::Transactions>(&key)?,
                         <TransactionMask<<Transactions as Table>::Value>>::MASK,
                     ),
-                    StaticFileSegment::Receipts => (
-                        table_key::<tables::Receipts>(&key)?,
-                        <ReceiptMask<<Receipts as Table>::Value>>::MASK,
-                    ),
+                    // selects all columns of either layout
+                    StaticFileSegment::Receipts => {
+                        (table_key::<tables::Receipts>(&key)?, ReceiptsLayout::Columns.mask())
+                    }
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -108,8 +110,8 @@ impl Command {
                                     println!("{}", serde_json::to_string_pretty(&transaction)?);
                                 }
                                 StaticFileSegment::Receipts => {
-                                    let receipt = <<Receipts as Table>::Value>::decompress(
-                                        content[0].as_slice(),
+                                    let receipt = decode_receipt(
+                                        &content.iter().map(Vec::as_slice).collect::<Vec<_>>(),
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
@@ -0,0 +1,57 @@
+use clap::Parser;
+use reth_db_api::{
+    database::Database,
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+};
+use reth_primitives::{Address, B256};
+use reth_provider::{HistoryWriter, ProviderFactory};
+use tracing::info;
+
+/// The arguments for the `reth db migrate-history` command
+///
+/// Migrates the account and storage history indices to the current shard layout: last shards
+/// that exceed the size of a last shard are split, and sealed shards that are not full are merged
+/// into full shards. The node does the same incrementally in the background, this migrates the
+/// whole indices at once.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The number of shards of a history table that are walked in one database transaction.
+    #[arg(long, default_value_t = 1_000_000, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+}
+
+impl Command {
+    /// Execute `db migrate-history` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let batch_size = self.batch_size as usize;
+
+        let mut accounts = 0;
+        let mut next = Some(ShardedKey::new(Address::ZERO, 0));
+        while let Some(from) = next {
+            let provider = provider_factory.provider_rw()?;
+            let (merged, next_key) =
+                provider.merge_account_history_shards(from, batch_size, true)?;
+            provider.commit()?;
+            accounts += merged;
+            next = next_key;
+            info!(target: "reth::cli", merged = accounts, next = ?next.as_ref().map(|key| key.key), "Migrated account history shards");
+        }
+
+        let mut storages = 0;
+        let mut next = Some(StorageShardedKey::new(Address::ZERO, B256::ZERO, 0));
+        while let Some(from) = next {
+            let provider = provider_factory.provider_rw()?;
+            let (merged, next_key) =
+                provider.merge_storage_history_shards(from, batch_size, true)?;
+            provider.commit()?;
+            storages += merged;
+            next = next_key;
+            info!(target: "reth::cli", merged = storages, next = ?next.as_ref().map(|key| key.address), "Migrated storage history shards");
+        }
+
+        println!(
+            "Migrated {accounts} account history shards and {storages} storage history shards"
+        );
+        Ok(())
+    }
+}
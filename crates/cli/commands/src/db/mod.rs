@@ -4,12 +4,19 @@ use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
 use reth_db_common::DbTool;
 use std::io::{self, Write};
 
+mod backfill_senders;
 mod checksum;
 mod clear;
+mod compact_static_files;
 mod diff;
+mod dump_state;
 mod get;
 mod list;
+mod migrate_history;
+mod rebuild_tx_lookup;
+mod snapshot;
 mod stats;
+mod stats_history;
 /// DB List TUI
 mod tui;
 
@@ -44,6 +51,18 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Exports the state at a block, in the format of `reth init-state`
+    DumpState(dump_state::Command),
+    /// Exports or imports portable snapshots of the hashed state and the state trie
+    Snapshot(snapshot::Command),
+    /// Rewrites static files with a different compression, and reports compression ratios
+    CompactStaticFiles(compact_static_files::Command),
+    /// Rebuilds the pruned part of the transaction hash index
+    RebuildTxLookup(rebuild_tx_lookup::Command),
+    /// Migrates the history indices to the current shard layout
+    MigrateHistory(migrate_history::Command),
+    /// Stores the senders of the transactions static files with them
+    BackfillSenders(backfill_senders::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -128,6 +147,33 @@ impl Command {
                 let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::DumpState(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Snapshot(command) => {
+                let access =
+                    if command.is_read_write() { AccessRights::RW } else { AccessRights::RO };
+                let Environment { provider_factory, .. } = self.env.init(access)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::CompactStaticFiles(command) => {
+                let Environment { config, provider_factory, .. } =
+                    self.env.init(AccessRights::RW)?;
+                command.execute(&config.static_files, provider_factory)?;
+            }
+            Subcommands::RebuildTxLookup(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::MigrateHistory(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::BackfillSenders(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,91 @@
+use clap::Parser;
+use reth_db::tables;
+use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    BlockReader, ProviderError, ProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    TransactionsProviderExt,
+};
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use tracing::info;
+
+/// The arguments for the `reth db rebuild-tx-lookup` command
+///
+/// Rebuilds the transaction hash index of blocks that were pruned with
+/// `--prune.txlookup.distance`. The node prunes the rebuilt blocks again unless the distance is
+/// raised accordingly.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The lowest block whose transactions are indexed again.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The number of blocks that are indexed in one database transaction.
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+}
+
+impl Command {
+    /// Execute `db rebuild-tx-lookup` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let checkpoint =
+            provider_factory.provider()?.get_prune_checkpoint(PruneSegment::TransactionLookup)?;
+        let Some((mut pruned_to, prune_mode)) = checkpoint
+            .and_then(|checkpoint| checkpoint.block_number.zip(Some(checkpoint.prune_mode)))
+        else {
+            println!("The transaction index is not pruned");
+            return Ok(())
+        };
+        if pruned_to < self.from {
+            println!("The transaction index is complete from block {}", pruned_to + 1);
+            return Ok(())
+        }
+
+        // Blocks are indexed from the newest pruned one down, so the prune checkpoint stays
+        // correct if the command is interrupted.
+        loop {
+            let start = pruned_to.saturating_sub(self.batch_size - 1).max(self.from);
+
+            let provider = provider_factory.provider_rw()?;
+            let first = provider
+                .block_body_indices(start)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(start))?;
+            let last = provider
+                .block_body_indices(pruned_to)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(pruned_to))?;
+
+            let tx_range = first.first_tx_num()..last.next_tx_num();
+            let transactions = tx_range.end - tx_range.start;
+            if !tx_range.is_empty() {
+                for (hash, tx_number) in provider.transaction_hashes_by_range(tx_range)? {
+                    provider.tx_ref().put::<tables::TransactionHashNumbers>(hash, tx_number)?;
+                }
+            }
+
+            if start == 0 {
+                provider
+                    .tx_ref()
+                    .delete::<tables::PruneCheckpoints>(PruneSegment::TransactionLookup, None)?;
+            } else {
+                provider.save_prune_checkpoint(
+                    PruneSegment::TransactionLookup,
+                    PruneCheckpoint {
+                        block_number: Some(start - 1),
+                        tx_number: first.first_tx_num().checked_sub(1),
+                        prune_mode,
+                    },
+                )?;
+            }
+            provider.commit()?;
+            info!(target: "reth::cli", from = start, to = pruned_to, transactions, "Rebuilt transaction index");
+
+            if start == self.from {
+                break
+            }
+            pruned_to = start - 1;
+        }
+
+        println!("The transaction index is complete from block {}", self.from);
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use clap::{Parser, Subcommand};
+use reth_db_api::database::Database;
+use reth_db_common::snapshot::{export_state_snapshot, import_state_snapshot};
+use reth_provider::ProviderFactory;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// The arguments for the `reth db snapshot` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    subcommand: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Exports the hashed state and the state trie at the latest block to a portable snapshot
+    /// file.
+    Export {
+        /// The path to write the snapshot to.
+        #[arg(value_name = "SNAPSHOT_FILE")]
+        path: PathBuf,
+    },
+    /// Imports a state snapshot file, verifying it against the state root of its block.
+    ///
+    /// The block of the snapshot must already be imported, and the hashed state and trie tables
+    /// must be empty.
+    Import {
+        /// The path to read the snapshot from.
+        #[arg(value_name = "SNAPSHOT_FILE")]
+        path: PathBuf,
+    },
+}
+
+impl Command {
+    /// Execute `db snapshot` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        match self.subcommand {
+            Subcommands::Export { path } => {
+                let writer = BufWriter::new(File::create(&path)?);
+                let header = export_state_snapshot(&provider_factory.provider()?, writer)?;
+                info!(target: "reth::cli", ?path, block = header.block_number, state_root = %header.state_root, "State snapshot exported");
+            }
+            Subcommands::Import { path } => {
+                let reader = BufReader::new(File::open(&path)?);
+                let provider_rw = provider_factory.provider_rw()?;
+                let header = import_state_snapshot(&provider_rw, reader)?;
+                provider_rw.commit()?;
+                info!(target: "reth::cli", ?path, block = header.block_number, state_root = %header.state_root, "State snapshot imported");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the command needs write access to the database.
+    pub const fn is_read_write(&self) -> bool {
+        matches!(self.subcommand, Subcommands::Import { .. })
+    }
+}
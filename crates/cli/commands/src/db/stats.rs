@@ -1,4 +1,7 @@
-use crate::db::checksum::ChecksumViewer;
+use crate::db::{
+    checksum::ChecksumViewer,
+    stats_history::{StatsHistory, StatsSnapshot, STATS_HISTORY_FILE},
+};
 use clap::Parser;
 use comfy_table::{Cell, Row, Table as ComfyTable};
 use eyre::WrapErr;
@@ -11,7 +14,11 @@ use reth_fs_util as fs;
 use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_provider::providers::StaticFileProvider;
 use reth_static_file_types::{find_fixed_range, SegmentRangeInclusive};
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db stats` command
@@ -32,6 +39,21 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Record the table sizes in the stats history of the datadir, and show how much every table
+    /// grew since the previous record.
+    ///
+    /// Running this periodically, e.g. from cron, builds up a history to forecast the disk usage
+    /// from.
+    #[arg(long, default_value_t = false)]
+    history: bool,
+
+    /// The minimum number of hours between two records of the stats history.
+    ///
+    /// The table sizes are only recorded if the latest record is older, the growth is shown
+    /// either way.
+    #[arg(long = "history.interval", value_name = "HOURS", default_value_t = 24)]
+    history_interval: u64,
 }
 
 impl Command {
@@ -47,18 +69,45 @@ impl Command {
             println!("\n");
         }
 
+        let history_path = data_dir.data_dir().join(STATS_HISTORY_FILE);
+
         let static_files_stats_table = self.static_files_stats_table(data_dir)?;
         println!("{static_files_stats_table}");
 
         println!("\n");
 
-        let db_stats_table = self.db_stats_table(tool)?;
+        let (db_stats_table, table_sizes) = self.db_stats_table(tool)?;
         println!("{db_stats_table}");
 
+        if self.history {
+            let mut history = StatsHistory::load(&history_path)?;
+            let snapshot = StatsSnapshot {
+                timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+                tables: table_sizes,
+            };
+
+            println!("\n");
+            match history.growth_table(&snapshot) {
+                Some(growth_table) => println!("{growth_table}"),
+                None => println!("No previous record in {}", history_path.display()),
+            }
+
+            if history
+                .record(snapshot, Duration::from_secs(self.history_interval.saturating_mul(3600)))
+            {
+                history.save(&history_path)?;
+                println!("Recorded table sizes in {}", history_path.display());
+            }
+        }
+
         Ok(())
     }
 
-    fn db_stats_table(&self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<ComfyTable> {
+    /// Returns the stats of the database tables, and the size of every table.
+    fn db_stats_table(
+        &self,
+        tool: &DbTool<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<(ComfyTable, BTreeMap<String, u64>)> {
         let mut table = ComfyTable::new();
         table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
         table.set_header([
@@ -70,10 +119,11 @@ impl Command {
             "Total Size",
         ]);
 
-        tool.provider_factory.db_ref().view(|tx| {
+        let table_sizes = tool.provider_factory.db_ref().view(|tx| {
             let mut db_tables = Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
             db_tables.sort();
             let mut total_size = 0;
+            let mut table_sizes = BTreeMap::new();
             for db_table in db_tables {
                 let table_db = tx.inner.open_db(Some(db_table)).wrap_err("Could not open db.")?;
 
@@ -93,6 +143,7 @@ impl Command {
                 let table_size = page_size * num_pages;
 
                 total_size += table_size;
+                table_sizes.insert(db_table.to_string(), table_size as u64);
                 let mut row = Row::new();
                 row.add_cell(Cell::new(db_table))
                     .add_cell(Cell::new(stats.entries()))
@@ -132,10 +183,10 @@ impl Command {
                 .add_cell(Cell::new(human_bytes(freelist_size as f64)));
             table.add_row(row);
 
-            Ok::<(), eyre::Report>(())
+            Ok::<_, eyre::Report>(table_sizes)
         })??;
 
-        Ok(table)
+        Ok((table, table_sizes))
     }
 
     fn static_files_stats_table(
@@ -0,0 +1,196 @@
+//! History of the table sizes recorded by `reth db stats --history`.
+
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_fs_util as fs;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+/// Name of the file in the datadir that holds the history.
+pub(crate) const STATS_HISTORY_FILE: &str = "db-stats-history.json";
+
+/// The maximum number of snapshots kept in the history, the oldest are dropped first.
+const MAX_SNAPSHOTS: usize = 256;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// The sizes of the database tables at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StatsSnapshot {
+    /// The unix timestamp of the snapshot.
+    pub(crate) timestamp: u64,
+    /// The size of every table in bytes.
+    pub(crate) tables: BTreeMap<String, u64>,
+}
+
+/// The recorded snapshots, oldest first.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StatsHistory {
+    snapshots: Vec<StatsSnapshot>,
+}
+
+/// The growth of a table between two snapshots.
+#[derive(Debug, PartialEq)]
+pub(crate) struct TableGrowth {
+    pub(crate) table: String,
+    pub(crate) size: u64,
+    /// The change of the size in bytes, negative if the table shrank.
+    pub(crate) delta: i64,
+    pub(crate) per_day: f64,
+}
+
+impl StatsHistory {
+    /// Loads the history from the file, or returns an empty one if it doesn't exist.
+    pub(crate) fn load(path: &Path) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default())
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Saves the history to the file.
+    pub(crate) fn save(&self, path: &Path) -> eyre::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the latest snapshot that was taken before the given timestamp.
+    fn latest_before(&self, timestamp: u64) -> Option<&StatsSnapshot> {
+        self.snapshots.iter().rev().find(|snapshot| snapshot.timestamp < timestamp)
+    }
+
+    /// Adds the snapshot if the latest one is at least `interval` older.
+    ///
+    /// Returns `true` if the snapshot was added.
+    pub(crate) fn record(&mut self, snapshot: StatsSnapshot, interval: Duration) -> bool {
+        if self.snapshots.last().is_some_and(|latest| {
+            snapshot.timestamp.saturating_sub(latest.timestamp) < interval.as_secs()
+        }) {
+            return false
+        }
+        self.snapshots.push(snapshot);
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.remove(0);
+        }
+        true
+    }
+
+    /// Returns the growth of every table since the latest snapshot before `current`, fastest
+    /// growing first, together with the elapsed time.
+    pub(crate) fn growth(&self, current: &StatsSnapshot) -> Option<(Duration, Vec<TableGrowth>)> {
+        let previous = self.latest_before(current.timestamp)?;
+        let elapsed = current.timestamp - previous.timestamp;
+
+        let mut growth = current
+            .tables
+            .iter()
+            .map(|(table, size)| {
+                let delta = *size as i64 - previous.tables.get(table).copied().unwrap_or(0) as i64;
+                TableGrowth {
+                    table: table.clone(),
+                    size: *size,
+                    delta,
+                    per_day: delta as f64 * SECONDS_PER_DAY / elapsed as f64,
+                }
+            })
+            .collect::<Vec<_>>();
+        growth.sort_by(|a, b| b.per_day.total_cmp(&a.per_day));
+
+        Some((Duration::from_secs(elapsed), growth))
+    }
+
+    /// Returns a table of the growth since the latest snapshot before `current`, if there is one.
+    pub(crate) fn growth_table(&self, current: &StatsSnapshot) -> Option<ComfyTable> {
+        let (elapsed, growth) = self.growth(current)?;
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            "Table Name".to_string(),
+            "Total Size".to_string(),
+            format!("Change ({:.1} hours)", elapsed.as_secs_f64() / 3600.0),
+            "Change per Day".to_string(),
+        ]);
+
+        let (mut total_size, mut total_delta, mut total_per_day) = (0, 0, 0.0);
+        for TableGrowth { table: name, size, delta, per_day } in growth {
+            total_size += size;
+            total_delta += delta;
+            total_per_day += per_day;
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name))
+                .add_cell(Cell::new(human_bytes(size as f64)))
+                .add_cell(Cell::new(signed_bytes(delta as f64)))
+                .add_cell(Cell::new(signed_bytes(per_day)));
+            table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Tables"))
+            .add_cell(Cell::new(human_bytes(total_size as f64)))
+            .add_cell(Cell::new(signed_bytes(total_delta as f64)))
+            .add_cell(Cell::new(signed_bytes(total_per_day)));
+        table.add_row(row);
+
+        Some(table)
+    }
+}
+
+/// Formats a number of bytes that may be negative.
+fn signed_bytes(bytes: f64) -> String {
+    if bytes < 0.0 {
+        format!("-{}", human_bytes(-bytes))
+    } else {
+        format!("+{}", human_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp: u64, tables: &[(&str, u64)]) -> StatsSnapshot {
+        StatsSnapshot {
+            timestamp,
+            tables: tables.iter().map(|(table, size)| (table.to_string(), *size)).collect(),
+        }
+    }
+
+    #[test]
+    fn records_at_interval() {
+        let interval = Duration::from_secs(3600);
+        let mut history = StatsHistory::default();
+        assert!(history.record(snapshot(0, &[]), interval));
+        assert!(!history.record(snapshot(1800, &[]), interval));
+        assert!(history.record(snapshot(3600, &[]), interval));
+        assert_eq!(history.snapshots.len(), 2);
+    }
+
+    #[test]
+    fn growth_since_previous_snapshot() {
+        let mut history = StatsHistory::default();
+        history.record(snapshot(0, &[("Headers", 100), ("Receipts", 1_000)]), Duration::ZERO);
+        let current = snapshot(43_200, &[("Headers", 200), ("Receipts", 800), ("Bodies", 50)]);
+        history.record(current.clone(), Duration::ZERO);
+
+        let (elapsed, growth) = history.growth(&current).unwrap();
+        assert_eq!(elapsed, Duration::from_secs(43_200));
+        let growth =
+            growth.iter().map(|g| (g.table.as_str(), g.delta, g.per_day)).collect::<Vec<_>>();
+        assert_eq!(
+            growth,
+            vec![("Headers", 100, 200.0), ("Bodies", 50, 100.0), ("Receipts", -200, -400.0)]
+        );
+
+        // there is nothing to compare the first snapshot with
+        assert!(history.growth(&history.snapshots[0].clone()).is_none());
+    }
+}
@@ -0,0 +1,300 @@
+//! Imports receipts from a file into the database or the static files.
+//!
+//! Shared by the commands that import receipts, which differ in the file codec and in how the
+//! receipts of a block are validated or filtered.
+
+use eyre::bail;
+use reth_db::tables;
+use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_downloaders::{
+    file_client::{ChunkedFileReader, FileClientError, FromReader},
+    receipt_file_client::ReceiptFileClient,
+};
+use reth_primitives::{BlockNumber, Header, Receipt, StaticFileSegment};
+use reth_provider::{
+    providers::StaticFileProvider, BlockNumReader, BlockReader, DatabaseProviderRW, HeaderProvider,
+    ProviderError, ProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Imports receipts from a file, decoded with the codec `D`.
+///
+/// Before the receipts of a block are written, `on_block` is called with the block header and the
+/// receipts, so it can validate them or filter them out. It returns the number of receipts it
+/// filtered out. The receipts left must match the transactions of the block. Blocks that are
+/// skipped in the file have no receipts.
+///
+/// Nodes that prune receipts keep them in the database, so if receipts were pruned or receipts
+/// pruning is configured, the receipts are written to the database and fill the pruned blocks.
+/// The receipts prune checkpoint is moved below the imported blocks, which must not be pruned
+/// by the configured `prune_modes` again. Otherwise, receipts are appended to the static files
+/// after the highest block with receipts. Receipts in static files are never pruned, so the
+/// blocks up to it already have their receipts and are skipped.
+pub async fn import_receipts_from_file<DB, D, F>(
+    provider_factory: ProviderFactory<DB>,
+    path: impl AsRef<Path>,
+    chunk_len: Option<u64>,
+    prune_modes: &PruneModes,
+    mut on_block: F,
+) -> eyre::Result<()>
+where
+    DB: Database,
+    ReceiptFileClient<D>: FromReader<Error = FileClientError>,
+    F: FnMut(&Header, &mut Vec<Receipt>) -> eyre::Result<usize>,
+{
+    let provider = provider_factory.provider_rw()?;
+    let static_file_provider = provider_factory.static_file_provider();
+    let mut importer = ReceiptsImporter::new(&provider, &static_file_provider, prune_modes)?;
+
+    let mut total_decoded_receipts = 0;
+    // the receipts of the last block of a chunk may continue in the next chunk
+    let mut pending: Option<(BlockNumber, Vec<Receipt>)> = None;
+
+    let mut reader = ChunkedFileReader::new(path, chunk_len).await?;
+    while let Some(file_client) = reader.next_chunk::<ReceiptFileClient<D>>().await? {
+        let ReceiptFileClient {
+            receipts, first_block, total_receipts: total_receipts_chunk, ..
+        } = file_client;
+        total_decoded_receipts += total_receipts_chunk;
+
+        info!(target: "reth::cli",
+            first_receipts_block=?first_block,
+            total_receipts_chunk,
+            "Importing receipt file chunk"
+        );
+
+        for (number, receipts) in (first_block..).zip(receipts) {
+            let receipts = receipts.into_iter().flatten();
+            if let Some((pending_number, pending_receipts)) = &mut pending {
+                if *pending_number == number {
+                    pending_receipts.extend(receipts);
+                    continue
+                }
+            }
+            if let Some((number, receipts)) = pending.replace((number, receipts.collect())) {
+                importer.import(number, receipts, &mut on_block)?;
+            }
+        }
+    }
+    if let Some((number, receipts)) = pending {
+        importer.import(number, receipts, &mut on_block)?;
+    }
+
+    importer.update_prune_checkpoint()?;
+    let ReceiptsImporter {
+        first_block,
+        last_block,
+        imported_receipts,
+        filtered_receipts,
+        skipped_blocks,
+        ..
+    } = importer;
+
+    static_file_provider.commit()?;
+    provider.commit()?;
+
+    if total_decoded_receipts == 0 {
+        warn!(target: "reth::cli", "No receipts were imported, ensure the receipt file is valid and not empty");
+        return Ok(())
+    }
+
+    info!(target: "reth::cli",
+        ?first_block,
+        ?last_block,
+        total_decoded_receipts,
+        imported_receipts,
+        filtered_receipts,
+        skipped_blocks,
+        "Receipt file imported"
+    );
+
+    Ok(())
+}
+
+/// Validates the receipts of consecutive blocks and writes them.
+struct ReceiptsImporter<'a, DB: Database> {
+    provider: &'a DatabaseProviderRW<DB>,
+    static_file_provider: &'a StaticFileProvider,
+    prune_modes: &'a PruneModes,
+    /// The tip, used to check whether the configured prune modes prune an imported block.
+    tip: BlockNumber,
+    /// Whether the receipts are appended to the static files, or written to the database.
+    static_files: bool,
+    /// The highest block with receipts in the static files. Receipts of blocks up to it are
+    /// already present.
+    highest_static_file_block: Option<BlockNumber>,
+    /// The receipts prune checkpoint before the import.
+    prune_checkpoint: Option<PruneCheckpoint>,
+    /// The first block whose receipts were imported.
+    first_block: Option<BlockNumber>,
+    /// The last block whose receipts were imported.
+    last_block: Option<BlockNumber>,
+    imported_receipts: usize,
+    filtered_receipts: usize,
+    skipped_blocks: u64,
+}
+
+impl<'a, DB: Database> ReceiptsImporter<'a, DB> {
+    fn new(
+        provider: &'a DatabaseProviderRW<DB>,
+        static_file_provider: &'a StaticFileProvider,
+        prune_modes: &'a PruneModes,
+    ) -> eyre::Result<Self> {
+        let prune_checkpoint = provider.get_prune_checkpoint(PruneSegment::Receipts)?;
+        let static_files = prune_checkpoint.is_none() &&
+            prune_modes.receipts.is_none() &&
+            prune_modes.receipts_log_filter.is_empty();
+        let highest_static_file_block = static_files
+            .then(|| {
+                static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts)
+            })
+            .flatten();
+        Ok(Self {
+            provider,
+            static_file_provider,
+            prune_modes,
+            tip: provider.best_block_number()?,
+            static_files,
+            highest_static_file_block,
+            prune_checkpoint,
+            first_block: None,
+            last_block: None,
+            imported_receipts: 0,
+            filtered_receipts: 0,
+            skipped_blocks: 0,
+        })
+    }
+
+    /// Imports the receipts of the block, and of the blocks skipped since the last imported block.
+    fn import<F>(
+        &mut self,
+        number: BlockNumber,
+        receipts: Vec<Receipt>,
+        on_block: &mut F,
+    ) -> eyre::Result<()>
+    where
+        F: FnMut(&Header, &mut Vec<Receipt>) -> eyre::Result<usize>,
+    {
+        if self.highest_static_file_block.is_some_and(|highest| number <= highest) {
+            self.skipped_blocks += 1;
+            return Ok(())
+        }
+
+        match self.last_block.or(self.highest_static_file_block) {
+            Some(last_block) if number <= last_block => {
+                bail!("receipts of block {number} are not ordered by block number")
+            }
+            // blocks skipped in the file have no receipts
+            Some(last_block) => {
+                for empty_block in last_block + 1..number {
+                    self.import_block(empty_block, Vec::new(), on_block)?;
+                }
+            }
+            None => {}
+        }
+        self.import_block(number, receipts, on_block)
+    }
+
+    /// Validates the receipts of the block against its header and writes them.
+    fn import_block<F>(
+        &mut self,
+        number: BlockNumber,
+        mut receipts: Vec<Receipt>,
+        on_block: &mut F,
+    ) -> eyre::Result<()>
+    where
+        F: FnMut(&Header, &mut Vec<Receipt>) -> eyre::Result<usize>,
+    {
+        if !self.static_files &&
+            self.prune_modes.receipts.is_some_and(|mode| mode.should_prune(number, self.tip))
+        {
+            bail!(
+                "receipts of block {number} would be pruned again by the configured receipts \
+                 prune mode, remove it before importing"
+            )
+        }
+
+        let header = self
+            .provider
+            .header_by_number(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        let indices = self
+            .provider
+            .block_body_indices(number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+
+        self.filtered_receipts += on_block(&header, &mut receipts)?;
+        if receipts.len() as u64 != indices.tx_count {
+            bail!(
+                "block {number} has {} transactions, but {} receipts",
+                indices.tx_count,
+                receipts.len()
+            )
+        }
+
+        self.imported_receipts += receipts.len();
+        let tx_numbers = indices.first_tx_num()..;
+        if self.static_files {
+            let mut writer =
+                self.static_file_provider.get_writer(number, StaticFileSegment::Receipts)?;
+            writer.increment_block(StaticFileSegment::Receipts, number)?;
+            for (tx_number, receipt) in tx_numbers.zip(receipts) {
+                writer.append_receipt(tx_number, receipt)?;
+            }
+        } else {
+            for (tx_number, receipt) in tx_numbers.zip(receipts) {
+                self.provider.tx_ref().put::<tables::Receipts>(tx_number, receipt)?;
+            }
+        }
+
+        self.first_block.get_or_insert(number);
+        self.last_block = Some(number);
+        Ok(())
+    }
+
+    /// Moves the receipts prune checkpoint below the imported blocks, if they were pruned.
+    ///
+    /// The checkpoint can't describe gaps, so the imported blocks must reach up to it.
+    fn update_prune_checkpoint(&self) -> eyre::Result<()> {
+        let (Some(checkpoint), Some(first_block), Some(last_block)) =
+            (self.prune_checkpoint, self.first_block, self.last_block)
+        else {
+            return Ok(())
+        };
+        let Some(pruned_block) = checkpoint.block_number.filter(|pruned| first_block <= *pruned)
+        else {
+            return Ok(())
+        };
+
+        if last_block < pruned_block {
+            bail!(
+                "receipts of blocks {}..={pruned_block} are pruned, the file must include them",
+                last_block + 1
+            )
+        }
+
+        let first_tx_number = self
+            .provider
+            .block_body_indices(first_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(first_block))?
+            .first_tx_num();
+        self.provider.save_prune_checkpoint(
+            PruneSegment::Receipts,
+            PruneCheckpoint {
+                block_number: first_block.checked_sub(1),
+                tx_number: first_tx_number.checked_sub(1),
+                prune_mode: checkpoint.prune_mode,
+            },
+        )?;
+
+        info!(target: "reth::cli",
+            previous_pruned_block = pruned_block,
+            pruned_block = ?first_block.checked_sub(1),
+            "Updated receipts prune checkpoint"
+        );
+        Ok(())
+    }
+}
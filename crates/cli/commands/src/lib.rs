@@ -10,3 +10,4 @@
 
 pub mod common;
 pub mod db;
+pub mod import_receipts;
@@ -17,13 +17,25 @@ use tracing::{debug, error, trace};
 /// Executes CLI commands.
 ///
 /// Provides utilities for running a cli command to completion.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[non_exhaustive]
-pub struct CliRunner;
+pub struct CliRunner {
+    /// How long tasks spawned with graceful shutdown are given to finish on exit.
+    graceful_shutdown_timeout: Duration,
+}
 
 // === impl CliRunner ===
 
 impl CliRunner {
+    /// Sets how long tasks spawned with graceful shutdown are given to finish after the command
+    /// has finished or an exit signal was received.
+    ///
+    /// Defaults to 5 seconds.
+    pub const fn with_graceful_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.graceful_shutdown_timeout = timeout;
+        self
+    }
+
     /// Executes the given _async_ command on the tokio runtime until the command future resolves or
     /// until the process receives a `SIGINT` or `SIGTERM` signal.
     ///
@@ -52,7 +64,7 @@ impl CliRunner {
             // after the command has finished or exit signal was received we shutdown the task
             // manager which fires the shutdown signal to all tasks spawned via the task
             // executor and awaiting on tasks spawned with graceful shutdown
-            task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
+            task_manager.graceful_shutdown_with_timeout(self.graceful_shutdown_timeout);
         }
 
         // `drop(tokio_runtime)` would block the current thread until its pools
@@ -113,6 +125,12 @@ impl CliRunner {
     }
 }
 
+impl Default for CliRunner {
+    fn default() -> Self {
+        Self { graceful_shutdown_timeout: Duration::from_secs(5) }
+    }
+}
+
 /// [`CliRunner`] configuration when executing commands asynchronously
 struct AsyncCliRunner {
     context: CliContext,
@@ -14,4 +14,7 @@ pub use load_secret_key::get_secret_key;
 
 /// Cli parsers functions.
 pub mod parsers;
-pub use parsers::{hash_or_num_value_parser, parse_duration_from_secs, parse_socket_address};
+pub use parsers::{
+    hash_or_num_value_parser, parse_duration_from_ms, parse_duration_from_secs,
+    parse_socket_address,
+};
@@ -11,6 +11,12 @@ pub fn parse_duration_from_secs(arg: &str) -> eyre::Result<Duration, std::num::P
     Ok(Duration::from_secs(seconds))
 }
 
+/// Helper to parse a [Duration] from milliseconds
+pub fn parse_duration_from_ms(arg: &str) -> eyre::Result<Duration, std::num::ParseIntError> {
+    let millis = arg.parse()?;
+    Ok(Duration::from_millis(millis))
+}
+
 /// Parse [`BlockHashOrNumber`]
 pub fn hash_or_num_value_parser(value: &str) -> eyre::Result<BlockHashOrNumber, eyre::Error> {
     match B256::from_str(value) {
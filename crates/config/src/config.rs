@@ -1,10 +1,12 @@
 //! Configuration files.
 
-use reth_network_types::{PeersConfig, SessionsConfig};
+use reth_network_types::{BandwidthConfig, PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
-use reth_stages_types::ExecutionStageThresholds;
+use reth_stages_types::{AdaptiveBatchSize, ExecutionStageThresholds};
+use reth_static_file_types::{ReceiptsLayout, SegmentCompression};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
+    collections::BTreeMap,
     ffi::OsStr,
     path::{Path, PathBuf},
     time::Duration,
@@ -26,6 +28,16 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for weighting bandwidth between sync, gossip and serving peers.
+    pub bandwidth: BandwidthConfig,
+    /// Configuration for static files.
+    pub static_files: StaticFilesConfig,
+    /// Activation overrides of hardforks, by fork name.
+    ///
+    /// Forks before the merge are activated at the given block number, later forks at the given
+    /// timestamp.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardforks: BTreeMap<String, u64>,
 }
 
 impl Config {
@@ -57,6 +69,37 @@ impl Config {
     pub fn update_prune_config(&mut self, prune_config: PruneConfig) {
         self.prune = Some(prune_config);
     }
+
+    /// Returns the keys of all settings that differ between this config and `other`, as dotted
+    /// paths like `peers.connection_info.max_inbound`, in lexicographic order.
+    pub fn changed_keys(&self, other: &Self) -> Result<Vec<String>, toml::ser::Error> {
+        let mut keys = Vec::new();
+        diff_toml(
+            "",
+            Some(&toml::Value::try_from(self)?),
+            Some(&toml::Value::try_from(other)?),
+            &mut keys,
+        );
+        Ok(keys)
+    }
+}
+
+/// Collects the dotted paths of the values that differ between `a` and `b`, descending into
+/// tables.
+fn diff_toml(path: &str, a: Option<&toml::Value>, b: Option<&toml::Value>, keys: &mut Vec<String>) {
+    match (a, b) {
+        (Some(toml::Value::Table(a)), Some(toml::Value::Table(b))) => {
+            let mut names = a.keys().chain(b.keys()).collect::<Vec<_>>();
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                let path = if path.is_empty() { name.clone() } else { format!("{path}.{name}") };
+                diff_toml(&path, a.get(name), b.get(name), keys);
+            }
+        }
+        (a, b) if a != b => keys.push(path.to_string()),
+        _ => {}
+    }
 }
 
 /// Configuration for each stage in the pipeline.
@@ -153,11 +196,17 @@ pub struct BodiesConfig {
     ///
     /// Default: 5
     pub downloader_min_concurrent_requests: usize,
-    /// The maximum number of requests to send concurrently.
-    /// This is equal to the max number of peers.
+    /// The maximum number of requests to send concurrently, regardless of the number of peers.
     ///
     /// Default: 100
     pub downloader_max_concurrent_requests: usize,
+    /// The number of requests to send concurrently per connected peer.
+    ///
+    /// A peer handles one request at a time, the other requests are queued and sent to the next
+    /// peer that responds, without waiting for the downloader.
+    ///
+    /// Default: 2
+    pub downloader_requests_per_peer: usize,
 }
 
 impl Default for BodiesConfig {
@@ -168,6 +217,7 @@ impl Default for BodiesConfig {
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
+            downloader_requests_per_peer: 2,
         }
     }
 }
@@ -202,6 +252,8 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// The dynamic sizing of the batches based on the observed write throughput.
+    pub adaptive: AdaptiveBatchConfig,
 }
 
 impl Default for ExecutionConfig {
@@ -213,6 +265,50 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            adaptive: AdaptiveBatchConfig::default(),
+        }
+    }
+}
+
+/// Configuration of the dynamic batch sizing of the execution stage.
+///
+/// If enabled, the cumulative gas of a batch is adapted to the observed gas per second of the
+/// database writes, so that writing a batch takes about `target_write_duration`. The fixed
+/// thresholds of the [`ExecutionConfig`] still apply on top of it.
+///
+/// Disabled by default: the memory a batch takes is only bounded by the `max_changes` threshold,
+/// so larger batches on fast disks also take more memory.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct AdaptiveBatchConfig {
+    /// Whether the batches are sized dynamically.
+    pub enabled: bool,
+    /// The targeted duration of writing a batch to the database, which bounds the commit pauses.
+    #[serde(with = "humantime_serde")]
+    pub target_write_duration: Duration,
+    /// The minimum cumulative amount of gas of a batch.
+    pub min_gas: u64,
+    /// The maximum cumulative amount of gas of a batch.
+    pub max_gas: u64,
+}
+
+impl AdaptiveBatchConfig {
+    /// Returns the batch size controller, if enabled.
+    pub fn batch_size(&self) -> Option<AdaptiveBatchSize> {
+        self.enabled
+            .then(|| AdaptiveBatchSize::new(self.target_write_duration, self.min_gas, self.max_gas))
+    }
+}
+
+impl Default for AdaptiveBatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_write_duration: Duration::from_secs(30),
+            // 100 full blocks of 30M gas
+            min_gas: 30_000_000 * 100,
+            // 50k full blocks of 30M gas
+            max_gas: 30_000_000 * 50_000,
         }
     }
 }
@@ -346,6 +442,16 @@ impl PruneConfig {
     }
 }
 
+/// Static files configuration.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct StaticFilesConfig {
+    /// Compression of newly written static files, per segment.
+    pub compression: SegmentCompression,
+    /// Layout of the receipts in newly written receipts static files.
+    pub receipts_layout: ReceiptsLayout,
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -405,6 +511,21 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_changed_keys() {
+        let config = Config::default();
+        assert!(config.changed_keys(&config).unwrap().is_empty());
+
+        let mut other = config.clone();
+        other.peers.connection_info.max_inbound += 1;
+        other.stages.execution.max_duration = Some(Duration::from_secs(60));
+        other.update_prune_config(Default::default());
+        assert_eq!(
+            config.changed_keys(&other).unwrap(),
+            vec!["peers.connection_info.max_inbound", "prune", "stages.execution.max_duration"]
+        );
+    }
+
     #[test]
     fn test_load_execution_stage() {
         with_tempdir("config-load-test", |config_path| {
@@ -735,4 +856,70 @@ connect_trusted_nodes_only = true
         let conf: Config = toml::from_str(trusted_nodes_only).unwrap();
         assert!(conf.peers.trusted_nodes_only);
     }
+
+    #[test]
+    fn test_conf_bandwidth() {
+        let bandwidth = r"#
+[bandwidth]
+max_bytes_per_second = 1048576
+
+[bandwidth.weights]
+sync = 1
+#";
+        let conf: Config = toml::from_str(bandwidth).unwrap();
+        assert_eq!(conf.bandwidth.max_bytes_per_second, Some(1048576));
+        assert_eq!(conf.bandwidth.weights.sync, 1);
+        assert_eq!(
+            conf.bandwidth.weights.gossip,
+            reth_network_types::BandwidthWeights::default().gossip
+        );
+    }
+
+    #[test]
+    fn test_conf_static_files_compression() {
+        let static_files = r"#
+[static_files.compression]
+receipts = 'zstd-dict'
+transactions = 'none'
+#";
+        let conf: Config = toml::from_str(static_files).unwrap();
+        assert_eq!(
+            conf.static_files.compression.receipts,
+            reth_static_file_types::Compression::ZstdWithDictionary
+        );
+        assert_eq!(
+            conf.static_files.compression.transactions,
+            reth_static_file_types::Compression::Uncompressed
+        );
+        assert_eq!(conf.static_files.compression.headers, reth_static_file_types::Compression::Lz4);
+        assert_eq!(conf.static_files.receipts_layout, reth_static_file_types::ReceiptsLayout::Rows);
+    }
+
+    #[test]
+    fn test_conf_static_files_receipts_layout() {
+        let static_files = r"#
+[static_files]
+receipts_layout = 'columns'
+#";
+        let conf: Config = toml::from_str(static_files).unwrap();
+        assert_eq!(
+            conf.static_files.receipts_layout,
+            reth_static_file_types::ReceiptsLayout::Columns
+        );
+    }
+
+    #[test]
+    fn test_conf_hardforks() {
+        let hardforks = r"#
+[hardforks]
+cancun = 1710338135
+prague = 1800000000
+#";
+        let conf: Config = toml::from_str(hardforks).unwrap();
+        assert_eq!(conf.hardforks.get("prague"), Some(&1800000000));
+        assert_eq!(conf.hardforks.len(), 2);
+
+        // no empty table is written
+        assert!(!toml::to_string(&Config::default()).unwrap().contains("hardforks"));
+    }
 }
@@ -31,7 +31,7 @@ impl AutoSealClient {
         trace!(target: "consensus::auto", ?request, "received headers request");
 
         let storage = self.storage.read().await;
-        let HeadersRequest { start, limit, direction } = request;
+        let HeadersRequest { start, limit, skip, direction } = request;
         let mut headers = Vec::new();
 
         let mut block: BlockHashOrNumber = match start {
@@ -50,9 +50,16 @@ impl AutoSealClient {
             // fetch from storage
             if let Some(header) = storage.header_by_hash_or_number(block) {
                 match direction {
-                    HeadersDirection::Falling => block = header.parent_hash.into(),
+                    HeadersDirection::Falling if skip == 0 => block = header.parent_hash.into(),
+                    HeadersDirection::Falling => {
+                        let Some(next) = header.number.checked_sub(skip as u64 + 1) else {
+                            headers.push(header);
+                            break
+                        };
+                        block = next.into()
+                    }
                     HeadersDirection::Rising => {
-                        let next = header.number + 1;
+                        let next = header.number + skip as u64 + 1;
                         block = next.into()
                     }
                 }
@@ -0,0 +1,173 @@
+//! A handle to control the block production and state of the [`MiningTask`](crate::MiningTask).
+
+use crate::Storage;
+use reth_blockchain_tree_api::error::CanonicalError;
+use reth_evm::dev::DevState;
+use reth_primitives::{Address, Bytes, SealedHeader, B256, U256};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// Commands sent to the [`MiningTask`](crate::MiningTask) by a [`MiningHandle`].
+pub(crate) enum MiningCommand {
+    /// Mines a block with the ready transactions of the pool, and sends its header once it is
+    /// canonical.
+    Mine(oneshot::Sender<SealedHeader>),
+    /// Reverts the chain to the snapshot with the given id, and sends whether the snapshot
+    /// existed.
+    Revert(U256, oneshot::Sender<Result<bool, MiningHandleError>>),
+    /// Changes the state overrides of the dev chain once no block is being mined, and notifies
+    /// the sender.
+    ChangeState(Box<dyn FnOnce(&DevState) + Send>, oneshot::Sender<()>),
+}
+
+impl std::fmt::Debug for MiningCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mine(_) => f.write_str("Mine"),
+            Self::Revert(id, _) => f.debug_tuple("Revert").field(id).finish(),
+            Self::ChangeState(_, _) => f.write_str("ChangeState"),
+        }
+    }
+}
+
+/// Errors returned by the [`MiningHandle`].
+#[derive(Debug, thiserror::Error)]
+pub enum MiningHandleError {
+    /// The timestamp of the next block is not after the timestamp of the best block.
+    #[error("timestamp {timestamp} is not after the timestamp {best_timestamp} of the best block")]
+    TimestampNotAfterBestBlock {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the best block.
+        best_timestamp: u64,
+    },
+    /// The block could not be mined, e.g. because its execution failed.
+    #[error("failed to mine block")]
+    MiningFailed,
+    /// The canonical chain could not be unwound to the block of a snapshot.
+    #[error("failed to revert to snapshot: {0}")]
+    RevertFailed(#[from] CanonicalError),
+}
+
+/// A handle to mine blocks on demand and to control the time and state of the dev chain,
+/// regardless of the [`MiningMode`](crate::MiningMode) the miner operates in.
+#[derive(Debug, Clone)]
+pub struct MiningHandle {
+    /// Shared storage of the mining task
+    storage: Storage,
+    /// The state overrides and impersonated accounts of the dev chain
+    dev_state: DevState,
+    /// Sender half of the command channel of the mining task
+    to_task: UnboundedSender<MiningCommand>,
+}
+
+// === impl MiningHandle ===
+
+impl MiningHandle {
+    pub(crate) const fn new(
+        storage: Storage,
+        dev_state: DevState,
+        to_task: UnboundedSender<MiningCommand>,
+    ) -> Self {
+        Self { storage, dev_state, to_task }
+    }
+
+    /// Mines a block with the ready transactions of the pool, and returns its header once it is
+    /// canonical.
+    pub async fn mine(&self) -> Result<SealedHeader, MiningHandleError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_task.send(MiningCommand::Mine(tx)).map_err(|_| MiningHandleError::MiningFailed)?;
+        rx.await.map_err(|_| MiningHandleError::MiningFailed)
+    }
+
+    /// Sets the timestamp of the next block. The time of later blocks continues from it.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), MiningHandleError> {
+        self.storage.write().await.set_next_block_timestamp(timestamp)
+    }
+
+    /// Moves the time of all later blocks forward by the given number of seconds, and returns the
+    /// total time offset in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> i64 {
+        self.storage.write().await.increase_time(seconds)
+    }
+
+    /// Takes a snapshot of the chain at the best block, and returns its id.
+    ///
+    /// Blocks above the oldest snapshot are no longer finalized, so that the chain can be
+    /// reverted to it.
+    pub async fn snapshot(&self) -> U256 {
+        self.storage.write().await.snapshot(&self.dev_state)
+    }
+
+    /// Reverts the chain to the snapshot with the given id. The snapshot and all later snapshots
+    /// are removed.
+    ///
+    /// The reverted blocks are kept in memory by the blockchain tree, together with their
+    /// execution outcome. Returns `false` if there is no snapshot with the id.
+    pub async fn revert(&self, id: U256) -> Result<bool, MiningHandleError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_task
+            .send(MiningCommand::Revert(id, tx))
+            .map_err(|_| MiningHandleError::MiningFailed)?;
+        rx.await.map_err(|_| MiningHandleError::MiningFailed)?
+    }
+
+    /// Sets the balance of the account.
+    ///
+    /// The latest and pending state show the change right away, and the next mined block applies
+    /// it, see [`reth_evm::dev`].
+    pub async fn set_balance(
+        &self,
+        address: Address,
+        balance: U256,
+    ) -> Result<(), MiningHandleError> {
+        self.change_state(move |dev_state| dev_state.set_balance(address, balance)).await
+    }
+
+    /// Sets the nonce of the account.
+    pub async fn set_nonce(&self, address: Address, nonce: u64) -> Result<(), MiningHandleError> {
+        self.change_state(move |dev_state| dev_state.set_nonce(address, nonce)).await
+    }
+
+    /// Sets the code of the account.
+    pub async fn set_code(&self, address: Address, code: Bytes) -> Result<(), MiningHandleError> {
+        self.change_state(move |dev_state| dev_state.set_code(address, code)).await
+    }
+
+    /// Sets the value of a storage slot of the account.
+    pub async fn set_storage_at(
+        &self,
+        address: Address,
+        slot: B256,
+        value: U256,
+    ) -> Result<(), MiningHandleError> {
+        self.change_state(move |dev_state| dev_state.set_storage_at(address, slot, value)).await
+    }
+
+    /// Changes the state overrides once no block is being mined, since the overrides must not
+    /// change while a block that applies them is mined.
+    async fn change_state(
+        &self,
+        f: impl FnOnce(&DevState) + Send + 'static,
+    ) -> Result<(), MiningHandleError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_task
+            .send(MiningCommand::ChangeState(Box::new(f), tx))
+            .map_err(|_| MiningHandleError::MiningFailed)?;
+        rx.await.map_err(|_| MiningHandleError::MiningFailed)
+    }
+
+    /// Impersonates the account, so that transactions can be sent on its behalf without its key.
+    pub fn impersonate(&self, address: Address) {
+        self.dev_state.impersonate(address)
+    }
+
+    /// Stops impersonating the account.
+    pub fn stop_impersonating(&self, address: &Address) {
+        self.dev_state.stop_impersonating(address)
+    }
+
+    /// Configures whether all accounts are impersonated.
+    pub fn set_auto_impersonate(&self, enabled: bool) {
+        self.dev_state.set_auto_impersonate(enabled)
+    }
+}
@@ -16,21 +16,22 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use reth_beacon_consensus::BeaconEngineMessage;
+use reth_blockchain_tree_api::{error::CanonicalError, BlockchainTreeEngine};
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_engine_primitives::EngineTypes;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    constants::ETHEREUM_BLOCK_GAS_LIMIT, eip4844::calculate_excess_blob_gas, proofs, Block,
-    BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header,
-    Requests, SealedBlock, SealedHeader, TransactionSigned, Withdrawals, B256, U256,
+    constants::ETHEREUM_BLOCK_GAS_LIMIT, proofs, Address, Block, BlockBody, BlockHash,
+    BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock,
+    SealedHeader, TransactionSigned, Withdrawals, B256, U256,
 };
 use reth_provider::{BlockReaderIdExt, StateProviderFactory, StateRootProvider};
 use reth_revm::database::StateProviderDatabase;
 use reth_transaction_pool::TransactionPool;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -38,12 +39,19 @@ use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGua
 use tracing::trace;
 
 mod client;
+mod handle;
 mod mode;
+mod rpc;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use handle::{MiningHandle, MiningHandleError};
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
-use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_evm::{
+    dev::{AccountOverride, DevState},
+    execute::{BlockExecutionOutput, BlockExecutorProvider, Executor},
+};
+pub use rpc::AnvilApi;
 pub use task::MiningTask;
 
 /// A consensus implementation intended for local development and testing purposes.
@@ -105,6 +113,7 @@ pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
     storage: Storage,
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     evm_config: EvmConfig,
+    dev_state: DevState,
 }
 
 // === impl AutoSealBuilder ===
@@ -138,9 +147,18 @@ where
             mode,
             to_engine,
             evm_config,
+            dev_state: DevState::default(),
         }
     }
 
+    /// Sets the state overrides of the dev chain, which the mined blocks apply.
+    ///
+    /// These must be the overrides of the executor and of the provider, see [`reth_evm::dev`].
+    pub fn dev_state(mut self, dev_state: DevState) -> Self {
+        self.dev_state = dev_state;
+        self
+    }
+
     /// Sets the [`MiningMode`] it operates in, default is [`MiningMode::Auto`]
     pub fn mode(mut self, mode: MiningMode) -> Self {
         self.mode = mode;
@@ -152,7 +170,8 @@ where
     pub fn build(
         self,
     ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, evm_config } = self;
+        let Self { client, consensus, pool, mode, storage, to_engine, evm_config, dev_state } =
+            self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -162,6 +181,7 @@ where
             client,
             pool,
             evm_config,
+            dev_state,
         );
         (consensus, auto_client, task)
     }
@@ -217,6 +237,27 @@ pub(crate) struct StorageInner {
     pub(crate) best_hash: B256,
     /// The total difficulty of the chain until this block
     pub(crate) total_difficulty: U256,
+    /// The timestamp set for the next block
+    pub(crate) next_timestamp: Option<u64>,
+    /// The offset in seconds from the current time to the time of new blocks
+    pub(crate) time_offset: i64,
+    /// The snapshots the chain can be reverted to, by id
+    pub(crate) snapshots: BTreeMap<U256, Snapshot>,
+    /// The id of the next snapshot
+    pub(crate) next_snapshot_id: U256,
+}
+
+/// A snapshot of the chain the auto seal engine is building.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot {
+    /// The best block when the snapshot was taken
+    header: SealedHeader,
+    /// The total difficulty of the chain until the best block
+    total_difficulty: U256,
+    /// The time offset when the snapshot was taken
+    time_offset: i64,
+    /// The state overrides that were not applied yet when the snapshot was taken
+    overrides: BTreeMap<Address, AccountOverride>,
 }
 
 // === impl StorageInner ===
@@ -239,6 +280,111 @@ impl StorageInner {
         self.headers.get(&num).cloned()
     }
 
+    /// Returns the timestamp of the best block.
+    pub(crate) fn best_timestamp(&self) -> u64 {
+        self.headers.get(&self.best_block).map(|header| header.timestamp).unwrap_or_default()
+    }
+
+    /// Returns the timestamp of the next block: the timestamp set for it, or the current time
+    /// shifted by the time offset.
+    ///
+    /// The timestamp is always after the timestamp of the best block.
+    pub(crate) fn next_block_timestamp(&mut self) -> u64 {
+        let timestamp = self
+            .next_timestamp
+            .take()
+            .unwrap_or_else(|| unix_timestamp().saturating_add_signed(self.time_offset));
+        timestamp.max(self.best_timestamp() + 1)
+    }
+
+    /// Sets the timestamp of the next block, and shifts the time of all later blocks to continue
+    /// from it.
+    pub(crate) fn set_next_block_timestamp(
+        &mut self,
+        timestamp: u64,
+    ) -> Result<(), MiningHandleError> {
+        let best_timestamp = self.best_timestamp();
+        if timestamp <= best_timestamp {
+            return Err(MiningHandleError::TimestampNotAfterBestBlock { timestamp, best_timestamp })
+        }
+        self.time_offset = (timestamp as i64).saturating_sub(unix_timestamp() as i64);
+        self.next_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    /// Moves the time of all later blocks forward by the given number of seconds, and returns the
+    /// total time offset.
+    pub(crate) fn increase_time(&mut self, seconds: u64) -> i64 {
+        self.time_offset = self.time_offset.saturating_add(seconds.try_into().unwrap_or(i64::MAX));
+        self.time_offset
+    }
+
+    /// Takes a snapshot of the chain at the best block and of the state overrides, and returns
+    /// its id.
+    pub(crate) fn snapshot(&mut self, dev_state: &DevState) -> U256 {
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += U256::from(1);
+
+        let header = self.headers.get(&self.best_block).cloned().unwrap_or_default();
+        self.snapshots.insert(
+            id,
+            Snapshot {
+                header: header.seal(self.best_hash),
+                total_difficulty: self.total_difficulty,
+                time_offset: self.time_offset,
+                overrides: dev_state.overrides(),
+            },
+        );
+        id
+    }
+
+    /// Returns the header of the oldest snapshot, if any.
+    ///
+    /// Blocks above it must not be finalized, so that the chain can still be reverted to the
+    /// snapshot.
+    pub(crate) fn finalized_header(&self) -> Option<SealedHeader> {
+        self.snapshots.values().next().map(|snapshot| snapshot.header.clone())
+    }
+
+    /// Reverts the chain to the snapshot with the given id, by unwinding the canonical chain of
+    /// the tree to the block of the snapshot and restoring the state overrides. The snapshot and
+    /// all later snapshots are removed.
+    ///
+    /// The tree keeps the reverted blocks and their execution outcome as a side chain.
+    ///
+    /// Returns the header of the new best block, or `None` if there is no snapshot with the id.
+    pub(crate) fn revert_to_snapshot<Tree>(
+        &mut self,
+        id: U256,
+        tree: &Tree,
+        dev_state: &DevState,
+    ) -> Result<Option<SealedHeader>, CanonicalError>
+    where
+        Tree: BlockchainTreeEngine,
+    {
+        let Some(snapshot) = self.snapshots.get(&id).cloned() else { return Ok(None) };
+        tree.unwind(snapshot.header.number)?;
+
+        self.snapshots.retain(|snapshot_id, _| *snapshot_id < id);
+        for number in snapshot.header.number + 1..=self.best_block {
+            if let Some(header) = self.headers.remove(&number) {
+                let hash = header.hash_slow();
+                self.bodies.remove(&hash);
+                self.hash_to_number.remove(&hash);
+            }
+        }
+
+        trace!(target: "consensus::auto", num=snapshot.header.number, hash=?snapshot.header.hash(), "reverted to snapshot");
+        self.best_block = snapshot.header.number;
+        self.best_hash = snapshot.header.hash();
+        self.total_difficulty = snapshot.total_difficulty;
+        self.time_offset = snapshot.time_offset;
+        self.next_timestamp = None;
+        dev_state.set_overrides(snapshot.overrides);
+
+        Ok(Some(snapshot.header))
+    }
+
     /// Inserts a new header+body pair
     pub(crate) fn insert_new_block(&mut self, mut header: Header, body: BlockBody) {
         header.number = self.best_block + 1;
@@ -270,18 +416,6 @@ impl StorageInner {
             parent.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
         });
 
-        let blob_gas_used = if chain_spec.is_cancun_active_at_timestamp(timestamp) {
-            let mut sum_blob_gas_used = 0;
-            for tx in transactions {
-                if let Some(blob_tx) = tx.transaction.as_eip4844() {
-                    sum_blob_gas_used += blob_tx.blob_gas();
-                }
-            }
-            Some(sum_blob_gas_used)
-        } else {
-            None
-        };
-
         let mut header = Header {
             parent_hash: self.best_hash,
             ommers_hash: proofs::calculate_ommers_root(ommers),
@@ -299,7 +433,7 @@ impl StorageInner {
             mix_hash: Default::default(),
             nonce: 0,
             base_fee_per_gas,
-            blob_gas_used,
+            blob_gas_used: None,
             excess_blob_gas: None,
             extra_data: Default::default(),
             parent_beacon_block_root: None,
@@ -308,9 +442,17 @@ impl StorageInner {
 
         if chain_spec.is_cancun_active_at_timestamp(timestamp) {
             let parent = self.headers.get(&self.best_block);
+            // there is no beacon chain in dev mode, the beacon roots contract only requires a
+            // root to be set
             header.parent_beacon_block_root =
-                parent.and_then(|parent| parent.parent_beacon_block_root);
-            header.blob_gas_used = Some(0);
+                Some(parent.and_then(|parent| parent.parent_beacon_block_root).unwrap_or_default());
+            header.blob_gas_used = Some(
+                transactions
+                    .iter()
+                    .filter_map(|tx| tx.transaction.as_eip4844())
+                    .map(|blob_tx| blob_tx.blob_gas())
+                    .sum(),
+            );
 
             let (parent_excess_blob_gas, parent_blob_gas_used) = match parent {
                 Some(parent_block)
@@ -323,8 +465,11 @@ impl StorageInner {
                 }
                 _ => (0, 0),
             };
-            header.excess_blob_gas =
-                Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+            header.excess_blob_gas = Some(
+                chain_spec
+                    .blob_params_at_timestamp(header.timestamp)
+                    .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+            )
         }
 
         header
@@ -332,6 +477,9 @@ impl StorageInner {
 
     /// Builds and executes a new block with the given transactions, on the provided executor.
     ///
+    /// The block applies the state overrides of the dev chain, so they must not change until it
+    /// is canonical.
+    ///
     /// This returns the header of the executed block, as well as the poststate from execution.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn build_and_execute<Provider, Executor>(
@@ -341,12 +489,13 @@ impl StorageInner {
         provider: &Provider,
         chain_spec: Arc<ChainSpec>,
         executor: &Executor,
+        dev_state: &DevState,
     ) -> Result<(SealedHeader, ExecutionOutcome), BlockExecutionError>
     where
         Executor: BlockExecutorProvider,
         Provider: StateProviderFactory,
     {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let timestamp = self.next_block_timestamp();
 
         // if shanghai is active, include empty withdrawals
         let withdrawals =
@@ -376,8 +525,13 @@ impl StorageInner {
 
         trace!(target: "consensus::auto", transactions=?&block.body, "executing transactions");
 
+        // the executor applies the state overrides, so the block is executed on the state of the
+        // best block without them
+        dev_state.apply_in_block(block.number);
         let mut db = StateProviderDatabase::new(
-            provider.latest().map_err(BlockExecutionError::LatestBlock)?,
+            provider
+                .history_by_block_hash(self.best_hash)
+                .map_err(BlockExecutionError::LatestBlock)?,
         );
 
         // execute the block
@@ -392,14 +546,14 @@ impl StorageInner {
             state,
             receipts.into(),
             block.number,
-            vec![block_execution_requests.into()],
+            vec![block_execution_requests.clone().into()],
         );
 
-        // todo(onbjerg): we should not pass requests around as this is building a block, which
-        // means we need to extract the requests from the execution output and compute the requests
-        // root here
-
         let Block { mut header, body, .. } = block.block;
+
+        // the requests of the block are the requests collected during its execution
+        let requests = requests.map(|_| Requests(block_execution_requests));
+        header.requests_root = requests.as_ref().map(|r| proofs::calculate_requests_root(&r.0));
         let body = BlockBody { transactions: body, ommers, withdrawals, requests };
 
         trace!(target: "consensus::auto", ?execution_outcome, ?header, ?body, "executed block, calculating state root and completing header");
@@ -439,3 +593,8 @@ impl StorageInner {
         Ok((new_header, execution_outcome))
     }
 }
+
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
@@ -0,0 +1,408 @@
+//! Implementation of the `anvil_` and `hardhat_` RPC methods of the dev chain.
+
+use crate::{MiningHandle, MiningHandleError};
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::METHOD_NOT_FOUND_CODE, ErrorObject},
+};
+use reth_primitives::{Address, Bytes, SealedHeader, B256, U256};
+use reth_rpc_api::{AnvilApiServer, GanacheApiServer, HardhatApiServer};
+use reth_rpc_server_types::result::{
+    internal_rpc_err, invalid_params_rpc_err, rpc_error_with_code,
+};
+use reth_rpc_types::{
+    anvil::{Forking, Metadata, MineOptions, NodeInfo},
+    Block,
+};
+
+/// `anvil_`, `hardhat_` and `evm_` API implementation to control the block production and change
+/// the state of the dev chain, and to impersonate accounts.
+///
+/// Methods that the dev chain doesn't support return a method not found error.
+#[derive(Debug, Clone)]
+pub struct AnvilApi {
+    handle: MiningHandle,
+}
+
+impl AnvilApi {
+    /// Creates a new instance of the anvil API, changing the dev chain with the given handle.
+    pub const fn new(handle: MiningHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Mines the given number of blocks, or a single block, regardless of the configured mining
+    /// mode. The first block includes the ready transactions of the pool.
+    ///
+    /// If an interval is given, the timestamps of the blocks are the interval apart.
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks
+            .map_or(Ok(1), u64::try_from)
+            .map_err(|_| invalid_params_rpc_err("number of blocks exceeds u64"))?;
+        let interval = interval
+            .map(u64::try_from)
+            .transpose()
+            .map_err(|_| invalid_params_rpc_err("interval exceeds u64"))?;
+
+        let mut last: Option<SealedHeader> = None;
+        for _ in 0..blocks {
+            if let (Some(interval), Some(last)) = (interval, &last) {
+                self.handle
+                    .set_next_block_timestamp(last.timestamp.saturating_add(interval))
+                    .await
+                    .map_err(mining_err)?;
+            }
+            last = Some(self.handle.mine().await.map_err(mining_err)?);
+        }
+        Ok(())
+    }
+
+    async fn set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = nonce.try_into().map_err(|_| invalid_params_rpc_err("nonce exceeds u64"))?;
+        self.handle.set_nonce(address, nonce).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    async fn set_storage_at(&self, address: Address, slot: U256, value: B256) -> RpcResult<()> {
+        self.handle
+            .set_storage_at(address, slot.into(), value.into())
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+/// Returns the rpc error of a failed mining command.
+fn mining_err(err: MiningHandleError) -> ErrorObject<'static> {
+    match err {
+        MiningHandleError::TimestampNotAfterBestBlock { .. } => {
+            invalid_params_rpc_err(err.to_string())
+        }
+        MiningHandleError::MiningFailed | MiningHandleError::RevertFailed(_) => {
+            internal_rpc_err(err.to_string())
+        }
+    }
+}
+
+/// Returns the error of a method the dev chain doesn't support.
+fn unsupported(method: &str) -> ErrorObject<'static> {
+    rpc_error_with_code(METHOD_NOT_FOUND_CODE, format!("{method} is not supported"))
+}
+
+#[async_trait]
+impl AnvilApiServer for AnvilApi {
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.handle.impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.handle.stop_impersonating(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.handle.set_auto_impersonate(enabled);
+        Ok(())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.handle
+            .set_balance(address, balance)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.handle.set_code(address, code).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.set_nonce(address, nonce).await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        self.set_storage_at(address, slot, value).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_getAutomine`, not supported
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        Err(unsupported("anvil_getAutomine"))
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        Ok(self.handle.snapshot().await)
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        self.handle.revert(id).await.map_err(mining_err)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        let seconds =
+            seconds.try_into().map_err(|_| invalid_params_rpc_err("seconds exceed u64"))?;
+        Ok(self.handle.increase_time(seconds).await)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.handle.set_next_block_timestamp(seconds).await.map_err(mining_err)
+    }
+
+    /// Handler for `anvil_setAutomine`, not supported
+    async fn anvil_set_automine(&self, _enabled: bool) -> RpcResult<()> {
+        Err(unsupported("anvil_setAutomine"))
+    }
+
+    /// Handler for `anvil_setIntervalMining`, not supported
+    async fn anvil_set_interval_mining(&self, _interval: u64) -> RpcResult<()> {
+        Err(unsupported("anvil_setIntervalMining"))
+    }
+
+    /// Handler for `anvil_dropTransaction`, not supported
+    async fn anvil_drop_transaction(&self, _tx_hash: B256) -> RpcResult<Option<B256>> {
+        Err(unsupported("anvil_dropTransaction"))
+    }
+
+    /// Handler for `anvil_reset`, not supported
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(unsupported("anvil_reset"))
+    }
+
+    /// Handler for `anvil_setRpcUrl`, not supported
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(unsupported("anvil_setRpcUrl"))
+    }
+
+    /// Handler for `anvil_setCoinbase`, not supported
+    async fn anvil_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(unsupported("anvil_setCoinbase"))
+    }
+
+    /// Handler for `anvil_setChainId`, not supported
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(unsupported("anvil_setChainId"))
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`, not supported
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(unsupported("anvil_setLoggingEnabled"))
+    }
+
+    /// Handler for `anvil_setMinGasPrice`, not supported
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(unsupported("anvil_setMinGasPrice"))
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`, not supported
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(unsupported("anvil_setNextBlockBaseFeePerGas"))
+    }
+
+    /// Handler for `anvil_setTime`, not supported
+    async fn anvil_set_time(&self, _timestamp: u64) -> RpcResult<u64> {
+        Err(unsupported("anvil_setTime"))
+    }
+
+    /// Handler for `anvil_dumpState`, not supported
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        Err(unsupported("anvil_dumpState"))
+    }
+
+    /// Handler for `anvil_loadState`, not supported
+    async fn anvil_load_state(&self, _state: Bytes) -> RpcResult<bool> {
+        Err(unsupported("anvil_loadState"))
+    }
+
+    /// Handler for `anvil_nodeInfo`, not supported
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(unsupported("anvil_nodeInfo"))
+    }
+
+    /// Handler for `anvil_metadata`, not supported
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        Err(unsupported("anvil_metadata"))
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`, not supported
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(unsupported("anvil_setBlockGasLimit"))
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`, not supported
+    async fn anvil_set_block_timestamp_interval(&self, _seconds: u64) -> RpcResult<()> {
+        Err(unsupported("anvil_setBlockTimestampInterval"))
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`, not supported
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        Err(unsupported("anvil_removeBlockTimestampInterval"))
+    }
+
+    /// Handler for `anvil_mine_detailed`, not supported
+    async fn anvil_mine_detailed(&self, _opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        Err(unsupported("anvil_mine_detailed"))
+    }
+
+    /// Handler for `anvil_enableTraces`, not supported
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(unsupported("anvil_enableTraces"))
+    }
+
+    /// Handler for `anvil_removePoolTransactions`, not supported
+    async fn anvil_remove_pool_transactions(&self, _address: Address) -> RpcResult<()> {
+        Err(unsupported("anvil_removePoolTransactions"))
+    }
+}
+
+#[async_trait]
+impl HardhatApiServer for AnvilApi {
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.handle.impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.handle.stop_impersonating(&address);
+        Ok(())
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.handle
+            .set_balance(address, balance)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.handle.set_code(address, code).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.set_nonce(address, nonce).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        self.set_storage_at(address, slot, value).await
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    /// Handler for `hardhat_dropTransaction`, not supported
+    async fn hardhat_drop_transaction(&self, _tx_hash: B256) -> RpcResult<bool> {
+        Err(unsupported("hardhat_dropTransaction"))
+    }
+
+    /// Handler for `hardhat_getAutomine`, not supported
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        Err(unsupported("hardhat_getAutomine"))
+    }
+
+    /// Handler for `hardhat_metadata`, not supported
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        Err(unsupported("hardhat_metadata"))
+    }
+
+    /// Handler for `hardhat_reset`, not supported
+    async fn hardhat_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(unsupported("hardhat_reset"))
+    }
+
+    /// Handler for `hardhat_setCoinbase`, not supported
+    async fn hardhat_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(unsupported("hardhat_setCoinbase"))
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`, not supported
+    async fn hardhat_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(unsupported("hardhat_setLoggingEnabled"))
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`, not supported
+    async fn hardhat_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(unsupported("hardhat_setMinGasPrice"))
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`, not supported
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        _base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        Err(unsupported("hardhat_setNextBlockBaseFeePerGas"))
+    }
+
+    /// Handler for `hardhat_setPrevRandao`, not supported
+    async fn hardhat_set_prev_randao(&self, _prev_randao: B256) -> RpcResult<()> {
+        Err(unsupported("hardhat_setPrevRandao"))
+    }
+}
+
+#[async_trait]
+impl GanacheApiServer for AnvilApi {
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.anvil_increase_time(seconds).await
+    }
+
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        let (timestamp, blocks) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (timestamp, blocks),
+            Some(MineOptions::Timestamp(timestamp)) => (timestamp, None),
+            None => (None, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.handle.set_next_block_timestamp(timestamp).await.map_err(mining_err)?;
+        }
+        self.mine(blocks.map(U256::from), None).await?;
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        self.anvil_revert(snapshot_id).await
+    }
+
+    /// Handler for `evm_setTime`, not supported
+    async fn evm_set_time(&self, _timestamp: u64) -> RpcResult<bool> {
+        Err(unsupported("evm_setTime"))
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        self.anvil_snapshot().await
+    }
+}
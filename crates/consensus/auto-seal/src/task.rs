@@ -1,15 +1,20 @@
-use crate::{mode::MiningMode, Storage};
+use crate::{
+    handle::{MiningCommand, MiningHandle, MiningHandleError},
+    mode::MiningMode,
+    Storage,
+};
 use futures_util::{future::BoxFuture, FutureExt};
 use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
+use reth_blockchain_tree_api::BlockchainTreeEngine;
 use reth_chainspec::ChainSpec;
 use reth_engine_primitives::EngineTypes;
-use reth_evm::execute::BlockExecutorProvider;
-use reth_primitives::IntoRecoveredTransaction;
+use reth_evm::{dev::DevState, execute::BlockExecutorProvider};
+use reth_primitives::{IntoRecoveredTransaction, SealedHeader, U256};
 use reth_provider::{CanonChainTracker, StateProviderFactory};
 use reth_rpc_types::engine::ForkchoiceState;
 use reth_stages_api::PipelineEvent;
 use reth_tokio_util::EventStream;
-use reth_transaction_pool::{TransactionPool, ValidPoolTransaction};
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
 use std::{
     collections::VecDeque,
     future::Future,
@@ -17,7 +22,10 @@ use std::{
     sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
 use tracing::{debug, error, warn};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
@@ -34,14 +42,20 @@ pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTyp
     storage: Storage,
     /// Pool where transactions are stored
     pool: Pool,
-    /// backlog of sets of transactions ready to be mined
-    queued: VecDeque<Vec<Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>>,
+    /// backlog of sets of transactions ready to be mined and of reverts, processed in order
+    queued: VecDeque<QueuedWork<<Pool as TransactionPool>::Transaction>>,
+    /// Sender half of the command channel, cloned into every [`MiningHandle`]
+    to_task: UnboundedSender<MiningCommand>,
+    /// Commands sent by [`MiningHandle`]s
+    commands: UnboundedReceiver<MiningCommand>,
     // TODO: ideally this would just be a sender of hashes
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     /// The pipeline events to listen on
     pipe_line_events: Option<EventStream<PipelineEvent>>,
     /// The type used for block execution
     block_executor: Executor,
+    /// The state overrides and impersonated accounts of the dev chain
+    dev_state: DevState,
 }
 
 // === impl MiningTask ===
@@ -59,7 +73,9 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
         client: Client,
         pool: Pool,
         block_executor: Executor,
+        dev_state: DevState,
     ) -> Self {
+        let (to_task, commands) = unbounded_channel();
         Self {
             chain_spec,
             client,
@@ -69,11 +85,20 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
             pool,
             to_engine,
             queued: Default::default(),
+            to_task,
+            commands,
             pipe_line_events: None,
             block_executor,
+            dev_state,
         }
     }
 
+    /// Returns a handle to mine blocks on demand and to control the time and state of the dev
+    /// chain.
+    pub fn handle(&self) -> MiningHandle {
+        MiningHandle::new(self.storage.clone(), self.dev_state.clone(), self.to_task.clone())
+    }
+
     /// Sets the pipeline events to listen on.
     pub fn set_pipeline_events(&mut self, events: EventStream<PipelineEvent>) {
         self.pipe_line_events = Some(events);
@@ -82,7 +107,8 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
 
 impl<Executor, Client, Pool, Engine> Future for MiningTask<Client, Pool, Executor, Engine>
 where
-    Client: StateProviderFactory + CanonChainTracker + Clone + Unpin + 'static,
+    Client:
+        StateProviderFactory + CanonChainTracker + BlockchainTreeEngine + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     <Pool as TransactionPool>::Transaction: IntoRecoveredTransaction,
     Engine: EngineTypes + 'static,
@@ -97,24 +123,82 @@ where
         loop {
             if let Poll::Ready(transactions) = this.miner.poll(&this.pool, cx) {
                 // miner returned a set of transaction that we feed to the producer
-                this.queued.push_back(transactions);
+                this.queued.push_back(QueuedWork::Mine(transactions, None));
+            }
+
+            // blocks requested by a handle are mined regardless of the mining mode
+            while let Poll::Ready(Some(command)) = this.commands.poll_recv(cx) {
+                match command {
+                    MiningCommand::Mine(tx) => {
+                        this.queued.push_back(QueuedWork::Mine(
+                            this.pool.best_transactions().collect(),
+                            Some(tx),
+                        ));
+                    }
+                    MiningCommand::Revert(id, tx) => {
+                        this.queued.push_back(QueuedWork::Revert(id, tx));
+                    }
+                    MiningCommand::ChangeState(change, tx) => {
+                        this.queued.push_back(QueuedWork::ChangeState(change, tx));
+                    }
+                }
             }
 
             if this.insert_task.is_none() {
-                if this.queued.is_empty() {
+                let Some(work) = this.queued.pop_front() else {
                     // nothing to insert
                     break
-                }
+                };
 
                 // ready to queue in new insert task
                 let storage = this.storage.clone();
-                let transactions = this.queued.pop_front().expect("not empty");
+                let client = this.client.clone();
+                let dev_state = this.dev_state.clone();
+
+                let (transactions, mined_tx) = match work {
+                    QueuedWork::Mine(transactions, mined_tx) => (transactions, mined_tx),
+                    QueuedWork::ChangeState(change, changed_tx) => {
+                        // no block is being mined, so the change can't race with a block that
+                        // applies the overrides
+                        change(&dev_state);
+                        let _ = changed_tx.send(());
+                        continue
+                    }
+                    QueuedWork::Revert(id, reverted_tx) => {
+                        // reverts run in place of an insert task, so they never race with the
+                        // insertion of a block
+                        let events = this.pipe_line_events.take();
+                        this.insert_task = Some(Box::pin(async move {
+                            let mut storage = storage.write().await;
+                            let reverted = match storage.revert_to_snapshot(id, &client, &dev_state)
+                            {
+                                Ok(Some(header)) => {
+                                    let finalized = storage
+                                        .finalized_header()
+                                        .unwrap_or_else(|| header.clone());
+                                    // update canon chain for rpc
+                                    client.set_canonical_head(header.clone());
+                                    client.set_safe(header);
+                                    client.set_finalized(finalized);
+                                    Ok(true)
+                                }
+                                Ok(None) => Ok(false),
+                                Err(err) => {
+                                    warn!(target: "consensus::auto", %err, "failed to revert to snapshot");
+                                    Err(MiningHandleError::from(err))
+                                }
+                            };
+                            let _ = reverted_tx.send(reverted);
+                            events
+                        }));
+                        continue
+                    }
+                };
 
+                let events = this.pipe_line_events.take();
                 let to_engine = this.to_engine.clone();
-                let client = this.client.clone();
                 let chain_spec = Arc::clone(&this.chain_spec);
                 let pool = this.pool.clone();
-                let events = this.pipe_line_events.take();
                 let executor = this.block_executor.clone();
 
                 // Create the mining future that creates a block, notifies the engine that drives
@@ -137,6 +221,7 @@ where
                         &client,
                         chain_spec,
                         &executor,
+                        &dev_state,
                     ) {
                         Ok((new_header, _bundle_state)) => {
                             // clear all transactions from pool
@@ -144,9 +229,12 @@ where
                                 transactions.iter().map(|tx| tx.hash()).collect(),
                             );
 
+                            // blocks above the oldest snapshot must stay revertible
+                            let finalized =
+                                storage.finalized_header().unwrap_or_else(|| new_header.clone());
                             let state = ForkchoiceState {
                                 head_block_hash: new_header.hash(),
-                                finalized_block_hash: new_header.hash(),
+                                finalized_block_hash: finalized.hash(),
                                 safe_block_hash: new_header.hash(),
                             };
                             drop(storage);
@@ -186,10 +274,17 @@ where
                                 }
                             }
 
+                            // the state of the chain includes the applied overrides now
+                            dev_state.on_canonical_block(new_header.number);
+
                             // update canon chain for rpc
                             client.set_canonical_head(new_header.clone());
                             client.set_safe(new_header.clone());
-                            client.set_finalized(new_header.clone());
+                            client.set_finalized(finalized);
+
+                            if let Some(mined_tx) = mined_tx {
+                                let _ = mined_tx.send(new_header);
+                            }
                         }
                         Err(err) => {
                             warn!(target: "consensus::auto", %err, "failed to execute block")
@@ -217,6 +312,17 @@ where
     }
 }
 
+/// Work queued for the [`MiningTask`].
+enum QueuedWork<T: PoolTransaction> {
+    /// Mines a block with the transactions, and notifies the sender once the block is mined, if it
+    /// was requested by a [`MiningHandle`].
+    Mine(Vec<Arc<ValidPoolTransaction<T>>>, Option<oneshot::Sender<SealedHeader>>),
+    /// Reverts the chain to the snapshot with the given id.
+    Revert(U256, oneshot::Sender<Result<bool, MiningHandleError>>),
+    /// Changes the state overrides of the dev chain.
+    ChangeState(Box<dyn FnOnce(&DevState) + Send>, oneshot::Sender<()>),
+}
+
 impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes> std::fmt::Debug
     for MiningTask<Client, Pool, EvmConfig, Engine>
 {
@@ -0,0 +1,131 @@
+//! Thresholds that decide when the engine switches from live sync to pipeline backfill.
+
+use reth_primitives::constants::EPOCH_SLOTS;
+use std::time::{Duration, Instant};
+
+/// The largest gap for which the tree will be used for sync. See docs for
+/// [`BackfillThresholds::distance`] for more information.
+///
+/// This is the default threshold, the distance to the head that the tree will be used for sync.
+/// If the distance exceeds this threshold, the pipeline will be used for sync.
+pub const MIN_BLOCKS_FOR_PIPELINE_RUN: u64 = EPOCH_SLOTS;
+
+/// The default number of blocks the distance threshold is raised by after a pipeline run.
+pub const DEFAULT_BACKFILL_HYSTERESIS: u64 = EPOCH_SLOTS;
+
+/// The default duration after a pipeline run for which the distance threshold is raised.
+pub const DEFAULT_BACKFILL_HYSTERESIS_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Thresholds that decide when the engine switches from live sync with the blockchain tree to
+/// backfilling the missing blocks with the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillThresholds {
+    /// After downloading a block corresponding to a recent forkchoice update, the engine will
+    /// check whether or not we can connect the block to the current canonical chain. If we can't,
+    /// we need to download and execute the missing parents of that block.
+    ///
+    /// When the block can't be connected, its block number will be compared to the canonical
+    /// head, resulting in a heuristic for the number of missing blocks, or the size of the gap
+    /// between the new block and the canonical head.
+    ///
+    /// If the gap is larger than this threshold, the engine will download and execute the missing
+    /// blocks using the pipeline. Otherwise, the engine, sync controller, and blockchain tree will
+    /// be used to download and execute the missing blocks. If this is zero, the pipeline is
+    /// always used.
+    pub distance: u64,
+    /// The maximum number of downloaded blocks that are buffered while waiting for their missing
+    /// ancestors. Once a chain of buffered blocks reaches this length, the remaining gap is
+    /// backfilled with the pipeline even if it's smaller than the distance threshold.
+    ///
+    /// If `None`, only the distance threshold is considered.
+    pub max_buffered_blocks: Option<u64>,
+    /// The number of blocks the distance threshold is raised by for the `hysteresis_period` after
+    /// a pipeline run finished.
+    ///
+    /// This prevents the engine from flip-flopping between live sync and backfill when the gap
+    /// hovers around the threshold, e.g. because of a flaky connection to the consensus layer.
+    pub hysteresis: u64,
+    /// How long after a pipeline run the distance threshold is raised by the `hysteresis`.
+    pub hysteresis_period: Duration,
+}
+
+impl BackfillThresholds {
+    /// Creates new thresholds with the given distance and without a buffered blocks limit or
+    /// hysteresis.
+    pub const fn new(distance: u64) -> Self {
+        Self {
+            distance,
+            max_buffered_blocks: None,
+            hysteresis: 0,
+            hysteresis_period: Duration::ZERO,
+        }
+    }
+
+    /// Sets the maximum number of buffered blocks before the remaining gap is backfilled.
+    pub const fn with_max_buffered_blocks(mut self, max_buffered_blocks: Option<u64>) -> Self {
+        self.max_buffered_blocks = max_buffered_blocks;
+        self
+    }
+
+    /// Sets the number of blocks the distance threshold is raised by for the given period after
+    /// a pipeline run.
+    pub const fn with_hysteresis(mut self, hysteresis: u64, period: Duration) -> Self {
+        self.hysteresis = hysteresis;
+        self.hysteresis_period = period;
+        self
+    }
+
+    /// Returns the distance above which the gap is backfilled with the pipeline, given the time
+    /// the last pipeline run finished.
+    pub fn distance_threshold(&self, last_pipeline_run: Option<Instant>) -> u64 {
+        match last_pipeline_run {
+            Some(finished) if finished.elapsed() < self.hysteresis_period => {
+                self.distance.saturating_add(self.hysteresis)
+            }
+            _ => self.distance,
+        }
+    }
+
+    /// Returns true if the given number of buffered blocks reaches the maximum.
+    pub fn exceeds_max_buffered_blocks(&self, buffered_blocks: u64) -> bool {
+        self.max_buffered_blocks.is_some_and(|max| buffered_blocks >= max)
+    }
+}
+
+impl Default for BackfillThresholds {
+    fn default() -> Self {
+        Self::new(MIN_BLOCKS_FOR_PIPELINE_RUN)
+            .with_hysteresis(DEFAULT_BACKFILL_HYSTERESIS, DEFAULT_BACKFILL_HYSTERESIS_PERIOD)
+    }
+}
+
+/// Why the engine switched to pipeline backfill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackfillReason {
+    /// The gap to the sync target exceeds the distance threshold.
+    Distance,
+    /// The chain of buffered blocks waiting for missing ancestors reached the maximum.
+    BufferedBlocks,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raises_distance_threshold_after_pipeline_run() {
+        let thresholds = BackfillThresholds::new(32)
+            .with_max_buffered_blocks(Some(8))
+            .with_hysteresis(16, Duration::from_secs(60));
+        assert_eq!(thresholds.distance_threshold(None), 32);
+        assert_eq!(thresholds.distance_threshold(Some(Instant::now())), 48);
+        assert_eq!(
+            thresholds.distance_threshold(Instant::now().checked_sub(Duration::from_secs(120))),
+            32
+        );
+
+        assert!(!thresholds.exceeds_max_buffered_blocks(7));
+        assert!(thresholds.exceeds_max_buffered_blocks(8));
+        assert!(!BackfillThresholds::new(32).exceeds_max_buffered_blocks(u64::MAX));
+    }
+}
@@ -9,9 +9,12 @@ use reth_engine_primitives::EngineTypes;
 use reth_errors::RethResult;
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
+    PayloadStatusEnum,
 };
 use reth_tokio_util::{EventSender, EventStream};
+use std::time::Duration;
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tracing::warn;
 
 /// A _shareable_ beacon consensus frontend type. Used to interact with the spawned beacon consensus
 /// engine task.
@@ -24,6 +27,9 @@ where
 {
     pub(crate) to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     event_sender: EventSender<BeaconConsensusEngineEvent>,
+    /// How long to wait for the response to a new payload before responding with `SYNCING`,
+    /// including the time the payload is queued.
+    new_payload_timeout: Option<Duration>,
 }
 
 // === impl BeaconConsensusEngineHandle ===
@@ -37,7 +43,19 @@ where
         to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
         event_sender: EventSender<BeaconConsensusEngineEvent>,
     ) -> Self {
-        Self { to_engine, event_sender }
+        Self { to_engine, event_sender, new_payload_timeout: None }
+    }
+
+    /// Responds to new payloads with `SYNCING` if the engine doesn't respond within the timeout,
+    /// e.g. because the execution of a block hangs.
+    ///
+    /// The timeout starts when the payload is sent to the engine, so it includes the time the
+    /// payload waits for the engine to process the messages before it.
+    ///
+    /// The engine still processes the payload, but the caller doesn't wait for it.
+    pub const fn with_new_payload_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.new_payload_timeout = timeout;
+        self
     }
 
     /// Sends a new payload message to the beacon consensus engine and waits for a response.
@@ -48,9 +66,20 @@ where
         payload: ExecutionPayload,
         cancun_fields: Option<CancunPayloadFields>,
     ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
+        let block_hash = payload.block_hash();
         let (tx, rx) = oneshot::channel();
         let _ = self.to_engine.send(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx });
-        rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
+        let response = match self.new_payload_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, rx).await {
+                Ok(response) => response,
+                Err(_) => {
+                    warn!(target: "consensus::engine", %block_hash, ?timeout, "New payload timed out, responding with SYNCING");
+                    return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+                }
+            },
+            None => rx.await,
+        };
+        response.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
     /// Sends a forkchoice update message to the beacon consensus engine and waits for a response.
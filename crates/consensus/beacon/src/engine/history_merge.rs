@@ -0,0 +1,131 @@
+//! Background merging of the sealed history shards.
+
+use futures::{Stream, StreamExt};
+use metrics::Counter;
+use reth_db_api::{
+    database::Database,
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+};
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::{CanonStateNotification, HistoryWriter, ProviderFactory, ProviderResult};
+use reth_tasks::TaskSpawner;
+use tokio::sync::oneshot;
+use tracing::{debug, error, warn};
+
+/// The default number of blocks between two runs of the [`HistoryMerger`].
+pub const DEFAULT_HISTORY_MERGE_INTERVAL: u64 = 64;
+
+/// The default maximum number of shards of each history table that are walked in one run of the
+/// [`HistoryMerger`].
+pub const DEFAULT_HISTORY_MERGE_LIMIT: usize = 100_000;
+
+/// Merges the sealed shards of the account and storage history indices into full shards.
+///
+/// Appending to the history indices seals small shards to keep the writes small. Every run walks a
+/// limited number of shards of each table from where the previous run stopped and merges the
+/// sealed shards it encounters, starting over once the end of a table is reached.
+#[derive(Debug)]
+pub struct HistoryMerger<DB> {
+    provider_factory: ProviderFactory<DB>,
+    /// The number of blocks between two runs.
+    interval: u64,
+    /// The maximum number of shards of each table that are walked in one run.
+    limit: usize,
+    /// The tip block number of the last run.
+    last_run: Option<BlockNumber>,
+    /// The key the next run continues at in the account history.
+    next_account: ShardedKey<Address>,
+    /// The key the next run continues at in the storage history.
+    next_storage: StorageShardedKey,
+}
+
+impl<DB: Database> HistoryMerger<DB> {
+    /// Creates a new merger that runs every `interval` blocks and walks at most `limit` shards of
+    /// each table per run.
+    pub const fn new(provider_factory: ProviderFactory<DB>, interval: u64, limit: usize) -> Self {
+        Self {
+            provider_factory,
+            interval,
+            limit,
+            last_run: None,
+            next_account: ShardedKey::new(Address::ZERO, 0),
+            next_storage: StorageShardedKey::new(Address::ZERO, B256::ZERO, 0),
+        }
+    }
+
+    /// Returns `true` if the merger should run at the given tip.
+    pub fn is_merge_needed(&self, tip_block_number: BlockNumber) -> bool {
+        self.last_run
+            .map_or(true, |last_run| tip_block_number >= last_run.saturating_add(self.interval))
+    }
+
+    /// Merges the shards of the next batch in one database transaction, and returns the number
+    /// of merged shards.
+    pub fn run(&mut self, tip_block_number: BlockNumber) -> ProviderResult<usize> {
+        let provider = self.provider_factory.provider_rw()?;
+        let (accounts, next_account) =
+            provider.merge_account_history_shards(self.next_account.clone(), self.limit, false)?;
+        let (storages, next_storage) =
+            provider.merge_storage_history_shards(self.next_storage.clone(), self.limit, false)?;
+        provider.commit()?;
+
+        self.next_account = next_account.unwrap_or_default();
+        self.next_storage = next_storage.unwrap_or_default();
+        self.last_run = Some(tip_block_number);
+        debug!(target: "consensus::engine::history_merge", accounts, storages, "Merged history shards");
+
+        Ok(accounts + storages)
+    }
+}
+
+/// Runs the [`HistoryMerger`] in the background as the canonical chain advances.
+///
+/// The merger runs outside of the engine hooks, so it doesn't pause the engine like the hooks
+/// with database write access. Each run holds the database write lock for a single transaction
+/// that is bounded by the merge limit, so writes of the engine wait for at most one run.
+///
+/// Returns once the notification stream ends.
+pub async fn history_merge_task<DB, St>(
+    mut merger: HistoryMerger<DB>,
+    mut notifications: St,
+    task_spawner: Box<dyn TaskSpawner>,
+) where
+    DB: Database + 'static,
+    St: Stream<Item = CanonStateNotification> + Unpin,
+{
+    let metrics = Metrics::default();
+    while let Some(notification) = notifications.next().await {
+        let tip_block_number = notification.tip().number;
+        if !merger.is_merge_needed(tip_block_number) {
+            continue
+        }
+
+        let (tx, rx) = oneshot::channel();
+        task_spawner.spawn_blocking(Box::pin(async move {
+            let result = merger.run(tip_block_number);
+            let _ = tx.send((merger, result));
+        }));
+        metrics.runs_total.increment(1);
+
+        let Ok((returned, result)) = rx.await else {
+            error!(target: "consensus::engine::history_merge", "History merge task dropped the merger");
+            return
+        };
+        merger = returned;
+        match result {
+            Ok(merged) => metrics.merged_shards_total.increment(merged as u64),
+            Err(err) => {
+                warn!(target: "consensus::engine::history_merge", %err, "Failed to merge history shards")
+            }
+        }
+    }
+}
+
+#[derive(reth_metrics::Metrics)]
+#[metrics(scope = "consensus.engine.history_merge")]
+struct Metrics {
+    /// The number of times the merger was run.
+    runs_total: Counter,
+    /// The number of sealed shards that were merged.
+    merged_shards_total: Counter,
+}
@@ -9,6 +9,14 @@ use reth_metrics::{
 pub(crate) struct EngineMetrics {
     /// The number of times the pipeline was run.
     pub(crate) pipeline_runs: Counter,
+    /// The number of switches to pipeline backfill because the gap exceeded the distance
+    /// threshold.
+    pub(crate) backfill_distance_triggers: Counter,
+    /// The number of switches to pipeline backfill because too many blocks were buffered.
+    pub(crate) backfill_buffered_blocks_triggers: Counter,
+    /// The number of times a gap above the distance threshold was synced with the tree because
+    /// of the hysteresis after a pipeline run.
+    pub(crate) backfill_hysteresis_deferrals: Counter,
     /// The total count of forkchoice updated messages received.
     pub(crate) forkchoice_updated_messages: Counter,
     /// The total count of new payload messages received.
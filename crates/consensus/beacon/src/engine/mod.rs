@@ -15,10 +15,7 @@ use reth_network_p2p::{
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributes, PayloadBuilderAttributes};
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_primitives::{
-    constants::EPOCH_SLOTS, BlockNumHash, BlockNumber, Head, Header, SealedBlock, SealedHeader,
-    B256,
-};
+use reth_primitives::{BlockNumHash, BlockNumber, Head, Header, SealedBlock, SealedHeader, B256};
 use reth_provider::{
     BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
     StageCheckpointReader,
@@ -67,6 +64,21 @@ pub use forkchoice::{ForkchoiceStateHash, ForkchoiceStateTracker, ForkchoiceStat
 mod metrics;
 use metrics::EngineMetrics;
 
+mod backfill;
+use backfill::BackfillReason;
+pub use backfill::{
+    BackfillThresholds, DEFAULT_BACKFILL_HYSTERESIS, DEFAULT_BACKFILL_HYSTERESIS_PERIOD,
+    MIN_BLOCKS_FOR_PIPELINE_RUN,
+};
+
+mod watchdog;
+pub use watchdog::ExecutionWatchdog;
+
+mod history_merge;
+pub use history_merge::{
+    history_merge_task, HistoryMerger, DEFAULT_HISTORY_MERGE_INTERVAL, DEFAULT_HISTORY_MERGE_LIMIT,
+};
+
 pub mod sync;
 use sync::{EngineSyncController, EngineSyncEvent};
 
@@ -81,13 +93,6 @@ pub mod test_utils;
 /// The maximum number of invalid headers that can be tracked by the engine.
 const MAX_INVALID_HEADERS: u32 = 512u32;
 
-/// The largest gap for which the tree will be used for sync. See docs for `pipeline_run_threshold`
-/// for more information.
-///
-/// This is the default threshold, the distance to the head that the tree will be used for sync.
-/// If the distance exceeds this threshold, the pipeline will be used for sync.
-pub const MIN_BLOCKS_FOR_PIPELINE_RUN: u64 = EPOCH_SLOTS;
-
 /// Represents a pending forkchoice update.
 ///
 /// This type encapsulates the necessary components for a pending forkchoice update
@@ -205,18 +210,13 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
-    /// After downloading a block corresponding to a recent forkchoice update, the engine will
-    /// check whether or not we can connect the block to the current canonical chain. If we can't,
-    /// we need to download and execute the missing parents of that block.
-    ///
-    /// When the block can't be connected, its block number will be compared to the canonical head,
-    /// resulting in a heuristic for the number of missing blocks, or the size of the gap between
-    /// the new block and the canonical head.
-    ///
-    /// If the gap is larger than this threshold, the engine will download and execute the missing
-    /// blocks using the pipeline. Otherwise, the engine, sync controller, and blockchain tree will
-    /// be used to download and execute the missing blocks.
-    pipeline_run_threshold: u64,
+    /// The thresholds that decide when the missing blocks are backfilled with the pipeline
+    /// instead of being downloaded and executed with the blockchain tree.
+    backfill_thresholds: BackfillThresholds,
+    /// When the last pipeline run finished, used for the hysteresis of the backfill thresholds.
+    last_pipeline_run: Option<Instant>,
+    /// Reports the block executions that exceed a deadline, if configured.
+    execution_watchdog: Option<ExecutionWatchdog>,
     hooks: EngineHooksController,
     /// Sender for engine events.
     event_sender: EventSender<BeaconConsensusEngineEvent>,
@@ -248,7 +248,7 @@ where
         max_block: Option<BlockNumber>,
         payload_builder: PayloadBuilderHandle<EngineT>,
         target: Option<B256>,
-        pipeline_run_threshold: u64,
+        backfill_thresholds: BackfillThresholds,
         hooks: EngineHooks,
     ) -> RethResult<(Self, BeaconConsensusEngineHandle<EngineT>)> {
         let (to_engine, rx) = mpsc::unbounded_channel();
@@ -261,7 +261,7 @@ where
             max_block,
             payload_builder,
             target,
-            pipeline_run_threshold,
+            backfill_thresholds,
             to_engine,
             Box::pin(UnboundedReceiverStream::from(rx)),
             hooks,
@@ -291,7 +291,7 @@ where
         max_block: Option<BlockNumber>,
         payload_builder: PayloadBuilderHandle<EngineT>,
         target: Option<B256>,
-        pipeline_run_threshold: u64,
+        backfill_thresholds: BackfillThresholds,
         to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
         engine_message_stream: BoxStream<'static, BeaconEngineMessage<EngineT>>,
         hooks: EngineHooks,
@@ -318,7 +318,9 @@ where
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
             blockchain_tree_action: None,
             pending_forkchoice_update: None,
-            pipeline_run_threshold,
+            backfill_thresholds,
+            last_pipeline_run: None,
+            execution_watchdog: None,
             hooks: EngineHooksController::new(hooks),
             event_sender,
             metrics: EngineMetrics::default(),
@@ -337,6 +339,12 @@ where
         Ok((this, handle))
     }
 
+    /// Sets the watchdog that reports the block executions exceeding its deadline.
+    pub fn with_execution_watchdog(mut self, watchdog: Option<ExecutionWatchdog>) -> Self {
+        self.execution_watchdog = watchdog;
+        self
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -625,15 +633,6 @@ where
         self.handle.clone()
     }
 
-    /// Returns true if the distance from the local tip to the block is greater than the configured
-    /// threshold.
-    ///
-    /// If the `local_tip` is greater than the `block`, then this will return false.
-    #[inline]
-    const fn exceeds_pipeline_run_threshold(&self, local_tip: u64, block: u64) -> bool {
-        block > local_tip && block - local_tip > self.pipeline_run_threshold
-    }
-
     /// Returns the finalized hash to sync to if the distance from the local tip to the block is
     /// greater than the configured threshold and we're not synced to the finalized block yet
     /// yet (if we've seen that block already).
@@ -648,9 +647,8 @@ where
     ) -> Option<B256> {
         let sync_target_state = self.forkchoice_state_tracker.sync_target_state();
 
-        // check if the distance exceeds the threshold for pipeline sync
-        let mut exceeds_pipeline_run_threshold =
-            self.exceeds_pipeline_run_threshold(canonical_tip_num, target_block_number);
+        // the block whose distance to the local tip is checked against the threshold
+        let mut block_number = target_block_number;
 
         // check if the downloaded block is the tracked finalized block
         if let Some(ref buffered_finalized) = sync_target_state
@@ -659,8 +657,7 @@ where
         {
             // if we have buffered the finalized block, we should check how far
             // we're off
-            exceeds_pipeline_run_threshold =
-                self.exceeds_pipeline_run_threshold(canonical_tip_num, buffered_finalized.number);
+            block_number = buffered_finalized.number;
         }
 
         // If this is invoked after we downloaded a block we can check if this block is the
@@ -668,14 +665,36 @@ where
         if let (Some(downloaded_block), Some(ref state)) = (downloaded_block, sync_target_state) {
             if downloaded_block.hash == state.finalized_block_hash {
                 // we downloaded the finalized block
-                exceeds_pipeline_run_threshold =
-                    self.exceeds_pipeline_run_threshold(canonical_tip_num, downloaded_block.number);
+                block_number = downloaded_block.number;
+            }
+        }
+
+        // check if the distance exceeds the threshold for pipeline sync, the threshold is raised
+        // for a while after a pipeline run to avoid flip-flopping between live sync and backfill
+        let distance = block_number.saturating_sub(canonical_tip_num);
+        let threshold = self.backfill_thresholds.distance_threshold(self.last_pipeline_run);
+        let mut reason = None;
+        if distance > threshold {
+            reason = Some(BackfillReason::Distance);
+        } else if distance > self.backfill_thresholds.distance {
+            debug!(target: "consensus::engine", distance, threshold, "Deferring pipeline backfill due to hysteresis");
+            self.metrics.backfill_hysteresis_deferrals.increment(1);
+        }
+
+        // a long chain of downloaded blocks that can't be connected to the canonical chain is
+        // backfilled with the pipeline as well, instead of downloading the ancestors one by one
+        if let Some(downloaded_block) = downloaded_block.filter(|_| reason.is_none()) {
+            let buffered_blocks = downloaded_block.number.saturating_sub(target_block_number);
+            if target_block_number > canonical_tip_num &&
+                self.backfill_thresholds.exceeds_max_buffered_blocks(buffered_blocks)
+            {
+                reason = Some(BackfillReason::BufferedBlocks);
             }
         }
 
         // if the number of missing blocks is greater than the max, run the
         // pipeline
-        if exceeds_pipeline_run_threshold {
+        if let Some(reason) = reason {
             if let Some(state) = sync_target_state {
                 // if we have already canonicalized the finalized block, we should
                 // skip the pipeline run
@@ -688,6 +707,7 @@ where
                         if !state.finalized_block_hash.is_zero() {
                             // we don't have the block yet and the distance exceeds the allowed
                             // threshold
+                            self.on_backfill_triggered(reason, distance, threshold);
                             return Some(state.finalized_block_hash)
                         }
 
@@ -704,6 +724,7 @@ where
                         // However, optimism chains will do this. The risk of a reorg is however
                         // low.
                         debug!(target: "consensus::engine", hash=?state.head_block_hash, "Setting head hash as an optimistic pipeline target.");
+                        self.on_backfill_triggered(reason, distance, threshold);
                         return Some(state.head_block_hash)
                     }
                     Ok(Some(_)) => {
@@ -717,6 +738,17 @@ where
         None
     }
 
+    /// Records the switch from live sync to pipeline backfill.
+    fn on_backfill_triggered(&self, reason: BackfillReason, distance: u64, threshold: u64) {
+        match reason {
+            BackfillReason::Distance => self.metrics.backfill_distance_triggers.increment(1),
+            BackfillReason::BufferedBlocks => {
+                self.metrics.backfill_buffered_blocks_triggers.increment(1)
+            }
+        }
+        info!(target: "consensus::engine", ?reason, distance, threshold, "Switching from live sync to pipeline backfill");
+    }
+
     /// Returns how far the local tip is from the given block. If the local tip is at the same
     /// height or its block number is greater than the given block, this returns None.
     #[inline]
@@ -1036,7 +1068,7 @@ where
 
         // if the threshold is zero, we should not download the block first, and just use the
         // pipeline. Otherwise we use the tree to insert the block first
-        if self.pipeline_run_threshold == 0 {
+        if self.backfill_thresholds.distance == 0 {
             // use the pipeline to sync to the target
             trace!(target: "consensus::engine", %target, "Triggering pipeline run to sync missing ancestors of the new head");
             self.sync.set_pipeline_sync_target(target.into());
@@ -1233,9 +1265,11 @@ where
 
         let block_hash = block.hash();
         let start = Instant::now();
+        let watched = self.execution_watchdog.as_ref().map(|watchdog| watchdog.watch(&block));
         let status = self
             .blockchain
             .insert_block_without_senders(block.clone(), BlockValidationKind::Exhaustive)?;
+        drop(watched);
 
         let elapsed = start.elapsed();
         let mut latest_valid_hash = None;
@@ -1278,9 +1312,9 @@ where
     /// This mainly compares the missing parent of the downloaded block with the current canonical
     /// tip, and decides whether or not the pipeline should be run.
     ///
-    /// The canonical tip is compared to the missing parent using `exceeds_pipeline_run_threshold`,
-    /// which returns true if the missing parent is sufficiently ahead of the canonical tip. If so,
-    /// the pipeline is run. Otherwise, we need to insert blocks using the blockchain tree, and
+    /// The canonical tip is compared to the missing parent using the [`BackfillThresholds`], which
+    /// decide whether the missing parent is sufficiently ahead of the canonical tip. If so, the
+    /// pipeline is run. Otherwise, we need to insert blocks using the blockchain tree, and
     /// must download blocks outside of the pipeline. In this case, the distance is used to
     /// determine how many blocks we should download at once.
     fn on_disconnected_block(
@@ -1418,6 +1452,7 @@ where
             }
             EngineSyncEvent::PipelineFinished { result, reached_max_block } => {
                 trace!(target: "consensus::engine", ?result, ?reached_max_block, "Pipeline finished");
+                self.last_pipeline_run = Some(Instant::now());
                 // Any pipeline error at this point is fatal.
                 let ctrl = result?;
                 if reached_max_block {
@@ -1726,10 +1761,14 @@ where
 
             BlockchainTreeAction::InsertDownloadedPayload { block } => {
                 let downloaded_num_hash = block.num_hash();
-                match self.blockchain.insert_block_without_senders(
+                let watched =
+                    self.execution_watchdog.as_ref().map(|watchdog| watchdog.watch(&block));
+                let inserted = self.blockchain.insert_block_without_senders(
                     block,
                     BlockValidationKind::SkipStateRootValidation,
-                ) {
+                );
+                drop(watched);
+                match inserted {
                     Ok(status) => {
                         match status {
                             InsertPayloadOk::Inserted(BlockStatus::Valid(_)) => {
@@ -1,5 +1,5 @@
 use crate::{
-    engine::hooks::PruneHook, hooks::EngineHooks, BeaconConsensusEngine,
+    engine::hooks::PruneHook, hooks::EngineHooks, BackfillThresholds, BeaconConsensusEngine,
     BeaconConsensusEngineError, BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError,
     BeaconOnNewPayloadError, EthBeaconConsensus, MIN_BLOCKS_FOR_PIPELINE_RUN,
 };
@@ -422,7 +422,9 @@ where
             None,
             payload_builder,
             None,
-            self.base_config.pipeline_run_threshold.unwrap_or(MIN_BLOCKS_FOR_PIPELINE_RUN),
+            BackfillThresholds::new(
+                self.base_config.pipeline_run_threshold.unwrap_or(MIN_BLOCKS_FOR_PIPELINE_RUN),
+            ),
             hooks,
         )
         .expect("failed to create consensus engine");
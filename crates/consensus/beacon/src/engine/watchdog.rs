@@ -0,0 +1,249 @@
+//! Watchdog that detects block executions exceeding a deadline.
+
+use metrics::Counter;
+use reth_metrics::Metrics;
+use reth_primitives::{SealedBlock, B256};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::{error, warn};
+
+/// The shortest interval in which the watchdog checks the running execution.
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Watches the blocks executed by the engine and reports the executions that exceed a deadline.
+///
+/// A hung execution is logged with the hash of the offending block, and if a dump directory is
+/// configured, a dump with the block and the state of the executing thread is written to it.
+#[derive(Debug, Clone)]
+pub struct ExecutionWatchdog {
+    inner: Arc<WatchdogInner>,
+}
+
+#[derive(Debug)]
+struct WatchdogInner {
+    /// The duration after which an execution is considered hung.
+    deadline: Duration,
+    /// The directory to write the dumps of hung executions to.
+    dump_dir: Option<PathBuf>,
+    /// The id of the next watched execution.
+    next_id: AtomicU64,
+    /// The execution that is currently running.
+    running: Mutex<Option<RunningExecution>>,
+    metrics: WatchdogMetrics,
+}
+
+/// A block execution that is watched.
+#[derive(Debug)]
+struct RunningExecution {
+    /// Distinguishes the executions, so a guard only stops watching its own execution.
+    id: u64,
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+    transactions: usize,
+    gas_used: u64,
+    started_at: Instant,
+    /// The procfs directory of the executing thread, if available.
+    thread: Option<PathBuf>,
+    /// Whether the execution was already reported as hung.
+    reported: bool,
+}
+
+impl ExecutionWatchdog {
+    /// Creates a new watchdog with the given deadline and spawns the thread that watches the
+    /// executions.
+    ///
+    /// The thread exits once all clones of the watchdog are dropped.
+    pub fn spawn(deadline: Duration, dump_dir: Option<PathBuf>) -> io::Result<Self> {
+        let inner = Arc::new(WatchdogInner {
+            deadline,
+            dump_dir,
+            next_id: AtomicU64::new(0),
+            running: Mutex::new(None),
+            metrics: WatchdogMetrics::default(),
+        });
+        let check_interval = (deadline / 4).max(MIN_CHECK_INTERVAL);
+        let watched = Arc::downgrade(&inner);
+        thread::Builder::new()
+            .name("execution-watchdog".to_string())
+            .spawn(move || watch(watched, check_interval))?;
+        Ok(Self { inner })
+    }
+
+    /// Starts watching the execution of the block on the current thread, until the returned guard
+    /// is dropped.
+    pub(crate) fn watch(&self, block: &SealedBlock) -> ExecutionGuard {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        *self.inner.lock() = Some(RunningExecution {
+            id,
+            number: block.number,
+            hash: block.hash(),
+            parent_hash: block.parent_hash,
+            transactions: block.body.len(),
+            gas_used: block.gas_used,
+            started_at: Instant::now(),
+            thread: current_thread_dir(),
+            reported: false,
+        });
+        ExecutionGuard { inner: self.inner.clone(), id }
+    }
+}
+
+impl WatchdogInner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<RunningExecution>> {
+        self.running.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Reports the running execution if it exceeded the deadline and wasn't reported yet.
+    fn check(&self) {
+        let mut running = self.lock();
+        let Some(execution) = running.as_mut().filter(|execution| !execution.reported) else {
+            return
+        };
+        let elapsed = execution.started_at.elapsed();
+        if elapsed < self.deadline {
+            return
+        }
+        execution.reported = true;
+        self.metrics.hung_executions.increment(1);
+
+        error!(
+            target: "consensus::engine::watchdog",
+            number = execution.number,
+            hash = %execution.hash,
+            transactions = execution.transactions,
+            gas_used = execution.gas_used,
+            ?elapsed,
+            deadline = ?self.deadline,
+            "Block execution exceeded the deadline"
+        );
+
+        if let Some(dir) = &self.dump_dir {
+            match write_dump(dir, execution, elapsed) {
+                Ok(path) => {
+                    error!(target: "consensus::engine::watchdog", ?path, "Wrote dump of hung block execution")
+                }
+                Err(err) => {
+                    warn!(target: "consensus::engine::watchdog", %err, "Failed to write dump of hung block execution")
+                }
+            }
+        }
+    }
+}
+
+/// Stops watching the execution when dropped.
+#[derive(Debug)]
+pub(crate) struct ExecutionGuard {
+    inner: Arc<WatchdogInner>,
+    id: u64,
+}
+
+impl Drop for ExecutionGuard {
+    fn drop(&mut self) {
+        let mut running = self.inner.lock();
+        if running.as_ref().map_or(true, |execution| execution.id != self.id) {
+            return
+        }
+        if let Some(execution) = running.take().filter(|execution| execution.reported) {
+            warn!(
+                target: "consensus::engine::watchdog",
+                number = execution.number,
+                hash = %execution.hash,
+                elapsed = ?execution.started_at.elapsed(),
+                "Hung block execution finished"
+            );
+        }
+    }
+}
+
+/// Checks the running execution in the interval until the watchdog is dropped.
+fn watch(inner: Weak<WatchdogInner>, check_interval: Duration) {
+    loop {
+        thread::sleep(check_interval);
+        let Some(inner) = inner.upgrade() else { return };
+        inner.check();
+    }
+}
+
+/// Returns the procfs directory of the current thread.
+#[cfg(target_os = "linux")]
+fn current_thread_dir() -> Option<PathBuf> {
+    std::fs::read_link("/proc/thread-self").ok().map(|task| Path::new("/proc").join(task))
+}
+
+/// Thread states are only available on Linux.
+#[cfg(not(target_os = "linux"))]
+fn current_thread_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Writes a dump of the hung execution into the directory and returns its path.
+///
+/// Besides the block, the dump contains the state, wait channel, current syscall and kernel stack
+/// of the executing thread, as far as they are readable, which tells whether the execution is
+/// busy or blocked on I/O.
+fn write_dump(dir: &Path, execution: &RunningExecution, elapsed: Duration) -> io::Result<PathBuf> {
+    let mut dump = format!(
+        "number: {}\nhash: {}\nparent_hash: {}\ntransactions: {}\ngas_used: {}\nelapsed: {elapsed:?}\n",
+        execution.number,
+        execution.hash,
+        execution.parent_hash,
+        execution.transactions,
+        execution.gas_used,
+    );
+    if let Some(thread) = &execution.thread {
+        dump.push_str(&format!("thread: {}\n", thread.display()));
+        for file in ["stat", "wchan", "syscall", "stack"] {
+            let contents = std::fs::read_to_string(thread.join(file))
+                .unwrap_or_else(|err| format!("unavailable: {err}"));
+            dump.push_str(&format!("\n[{file}]\n{}\n", contents.trim_end()));
+        }
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let timestamp =
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_millis();
+    let path = dir.join(format!("{timestamp}-{}.txt", execution.hash));
+    std::fs::write(&path, dump)?;
+    Ok(path)
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.execution_watchdog")]
+struct WatchdogMetrics {
+    /// The number of block executions that exceeded the deadline.
+    hung_executions: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_hung_execution() {
+        let dir = tempfile::tempdir().unwrap();
+        let watchdog =
+            ExecutionWatchdog::spawn(Duration::from_millis(10), Some(dir.path().to_path_buf()))
+                .unwrap();
+        let block = SealedBlock::default();
+
+        let guard = watchdog.watch(&block);
+        thread::sleep(Duration::from_millis(500));
+        assert!(watchdog.inner.lock().as_ref().unwrap().reported);
+        drop(guard);
+        assert!(watchdog.inner.lock().is_none());
+
+        let dumps = std::fs::read_dir(dir.path()).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(dumps.len(), 1);
+        let dump = std::fs::read_to_string(dumps[0].path()).unwrap();
+        assert!(dump.contains(&format!("hash: {}", block.hash())));
+    }
+}
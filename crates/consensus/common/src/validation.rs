@@ -3,11 +3,7 @@
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus::ConsensusError;
 use reth_primitives::{
-    constants::{
-        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
-        MAXIMUM_EXTRA_DATA_SIZE,
-    },
-    eip4844::calculate_excess_blob_gas,
+    constants::{eip4844::DATA_GAS_PER_BLOB, MAXIMUM_EXTRA_DATA_SIZE},
     EthereumHardfork, GotExpected, Header, SealedBlock, SealedHeader,
 };
 
@@ -109,10 +105,13 @@ pub fn validate_block_pre_execution(
 ///  * `blob_gas_used` exists as a header field
 ///  * `excess_blob_gas` exists as a header field
 ///  * `parent_beacon_block_root` exists as a header field
-///  * `blob_gas_used` is less than or equal to `MAX_DATA_GAS_PER_BLOCK`
+///  * `blob_gas_used` is less than or equal to the maximum blob gas per block of the chain spec
 ///  * `blob_gas_used` is a multiple of `DATA_GAS_PER_BLOB`
 ///  * `excess_blob_gas` is a multiple of `DATA_GAS_PER_BLOB`
-pub fn validate_4844_header_standalone(header: &SealedHeader) -> Result<(), ConsensusError> {
+pub fn validate_4844_header_standalone(
+    header: &SealedHeader,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
     let blob_gas_used = header.blob_gas_used.ok_or(ConsensusError::BlobGasUsedMissing)?;
     let excess_blob_gas = header.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
 
@@ -120,10 +119,12 @@ pub fn validate_4844_header_standalone(header: &SealedHeader) -> Result<(), Cons
         return Err(ConsensusError::ParentBeaconBlockRootMissing)
     }
 
-    if blob_gas_used > MAX_DATA_GAS_PER_BLOCK {
+    let max_blob_gas_per_block =
+        chain_spec.blob_params_at_timestamp(header.timestamp).max_blob_gas_per_block();
+    if blob_gas_used > max_blob_gas_per_block {
         return Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
             blob_gas_used,
-            max_blob_gas_per_block: MAX_DATA_GAS_PER_BLOCK,
+            max_blob_gas_per_block,
         })
     }
 
@@ -135,7 +136,7 @@ pub fn validate_4844_header_standalone(header: &SealedHeader) -> Result<(), Cons
     }
 
     // `excess_blob_gas` must also be a multiple of `DATA_GAS_PER_BLOB`. This will be checked later
-    // (via `calculate_excess_blob_gas`), but it doesn't hurt to catch the problem sooner.
+    // (via `validate_against_parent_4844`), but it doesn't hurt to catch the problem sooner.
     if excess_blob_gas % DATA_GAS_PER_BLOB != 0 {
         return Err(ConsensusError::ExcessBlobGasNotMultipleOfBlobGasPerBlob {
             excess_blob_gas,
@@ -235,17 +236,18 @@ pub fn validate_against_parent_timestamp(
 /// Validates that the EIP-4844 header fields are correct with respect to the parent block. This
 /// ensures that the `blob_gas_used` and `excess_blob_gas` fields exist in the child header, and
 /// that the `excess_blob_gas` field matches the expected `excess_blob_gas` calculated from the
-/// parent header fields.
+/// parent header fields with the blob parameters of the chain spec at the child header.
 pub fn validate_against_parent_4844(
     header: &SealedHeader,
     parent: &SealedHeader,
+    chain_spec: &ChainSpec,
 ) -> Result<(), ConsensusError> {
     // From [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#header-extension):
     //
     // > For the first post-fork block, both parent.blob_gas_used and parent.excess_blob_gas
     // > are evaluated as 0.
     //
-    // This means in the first post-fork block, calculate_excess_blob_gas will return 0.
+    // This means in the first post-fork block, the expected excess blob gas is 0.
     let parent_blob_gas_used = parent.blob_gas_used.unwrap_or(0);
     let parent_excess_blob_gas = parent.excess_blob_gas.unwrap_or(0);
 
@@ -254,8 +256,9 @@ pub fn validate_against_parent_4844(
     }
     let excess_blob_gas = header.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
 
-    let expected_excess_blob_gas =
-        calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used);
+    let expected_excess_blob_gas = chain_spec
+        .blob_params_at_timestamp(header.timestamp)
+        .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used);
     if expected_excess_blob_gas != excess_blob_gas {
         return Err(ConsensusError::ExcessBlobGasDiff {
             diff: GotExpected { got: excess_blob_gas, expected: expected_excess_blob_gas },
@@ -272,7 +275,7 @@ mod tests {
     use super::*;
     use mockall::mock;
     use rand::Rng;
-    use reth_chainspec::ChainSpecBuilder;
+    use reth_chainspec::{BlobParams, ChainSpecBuilder};
     use reth_primitives::{
         hex_literal::hex, proofs, Account, Address, BlockBody, BlockHash, BlockHashOrNumber,
         BlockNumber, Bytes, Signature, Transaction, TransactionSigned, TxEip4844, Withdrawal,
@@ -532,4 +535,55 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn blob_gas_follows_blob_schedule() {
+        let cancun = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let mut custom = cancun.clone();
+        custom.blob_schedule = vec![(
+            EthereumHardfork::Cancun.boxed(),
+            BlobParams {
+                target_blobs_per_block: 6,
+                max_blobs_per_block: 9,
+                base_fee_update_fraction: 5_007_716,
+            },
+        )]
+        .into();
+
+        let parent = Header {
+            blob_gas_used: Some(6 * DATA_GAS_PER_BLOB),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        }
+        .seal_slow();
+        let header = Header {
+            number: 1,
+            blob_gas_used: Some(9 * DATA_GAS_PER_BLOB),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(Default::default()),
+            ..Default::default()
+        }
+        .seal_slow();
+
+        // 9 blobs exceed the cancun maximum
+        assert_eq!(validate_4844_header_standalone(&header, &custom), Ok(()));
+        assert_eq!(
+            validate_4844_header_standalone(&header, &cancun),
+            Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                blob_gas_used: 9 * DATA_GAS_PER_BLOB,
+                max_blob_gas_per_block: 6 * DATA_GAS_PER_BLOB,
+            })
+        );
+
+        // 6 blobs are at the custom target, but 3 blobs above the cancun target
+        assert_eq!(validate_against_parent_4844(&header, &parent, &custom), Ok(()));
+        assert_eq!(
+            validate_against_parent_4844(&header, &parent, &cancun),
+            Err(ConsensusError::ExcessBlobGasDiff {
+                diff: GotExpected { got: 0, expected: 3 * DATA_GAS_PER_BLOB },
+                parent_excess_blob_gas: 0,
+                parent_blob_gas_used: 6 * DATA_GAS_PER_BLOB,
+            })
+        );
+    }
 }
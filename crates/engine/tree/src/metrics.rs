@@ -1,4 +1,7 @@
-use reth_metrics::{metrics::Gauge, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
 
 /// Metrics for the `BasicBlockDownloader`.
 #[derive(Metrics)]
@@ -7,3 +10,23 @@ pub(crate) struct BlockDownloaderMetrics {
     /// How many blocks are currently being downloaded.
     pub(crate) active_block_downloads: Gauge,
 }
+
+/// Metrics for the `EngineApiTreeHandlerImpl`.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.tree")]
+pub(crate) struct EngineApiTreeMetrics {
+    /// The number of executed in-memory blocks that are neither canonical nor descendants of the
+    /// canonical head.
+    pub(crate) side_chain_blocks: Gauge,
+    /// The length of the longest in-memory side chain.
+    pub(crate) side_chain_max_depth: Gauge,
+    /// The number of executed blocks that were inserted into a side chain.
+    pub(crate) side_chain_blocks_inserted: Counter,
+    /// The number of reorgs to a side chain.
+    pub(crate) reorgs: Counter,
+    /// The number of executed side-chain blocks that became canonical without re-execution.
+    pub(crate) reorg_reused_blocks: Counter,
+    /// The number of buffered blocks that were executed when they were made canonical, because
+    /// they had no cached execution outcome.
+    pub(crate) canonical_blocks_executed: Counter,
+}
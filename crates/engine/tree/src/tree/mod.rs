@@ -1,5 +1,7 @@
-use crate::{backfill::BackfillAction, engine::DownloadRequest};
-use reth_beacon_consensus::{ForkchoiceStateTracker, InvalidHeaderCache, OnForkChoiceUpdated};
+use crate::{backfill::BackfillAction, engine::DownloadRequest, metrics::EngineApiTreeMetrics};
+use reth_beacon_consensus::{
+    ForkchoiceStateTracker, ForkchoiceStatus, InvalidHeaderCache, OnForkChoiceUpdated,
+};
 use reth_blockchain_tree::{
     error::InsertBlockErrorKind, BlockAttachment, BlockBuffer, BlockStatus,
 };
@@ -11,8 +13,8 @@ use reth_evm::execute::{BlockExecutorProvider, Executor};
 use reth_payload_primitives::PayloadTypes;
 use reth_payload_validator::ExecutionPayloadValidator;
 use reth_primitives::{
-    Address, Block, BlockNumber, Receipts, Requests, SealedBlock, SealedBlockWithSenders, B256,
-    U256,
+    Address, Block, BlockNumHash, BlockNumber, Receipts, Requests, SealedBlock,
+    SealedBlockWithSenders, B256, U256,
 };
 use reth_provider::{BlockReader, ExecutionOutcome, StateProvider, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
@@ -25,7 +27,7 @@ use reth_rpc_types::{
 };
 use reth_trie::{updates::TrieUpdates, HashedPostState};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     marker::PhantomData,
     sync::Arc,
 };
@@ -71,6 +73,23 @@ impl ExecutedBlock {
     }
 }
 
+/// The update of the canonical chain after a new canonical head was set.
+#[derive(Debug)]
+pub enum NewCanonicalChain {
+    /// The new head extends the previous canonical chain.
+    Commit {
+        /// The blocks that became canonical, in ascending order.
+        new: Vec<ExecutedBlock>,
+    },
+    /// The new head is not a descendant of the previous canonical head.
+    Reorg {
+        /// The blocks that became canonical, in ascending order.
+        new: Vec<ExecutedBlock>,
+        /// The previously canonical blocks above the fork point, in ascending order.
+        old: Vec<ExecutedBlock>,
+    },
+}
+
 /// Keeps track of the state of the tree.
 #[derive(Debug)]
 pub struct TreeState {
@@ -78,18 +97,143 @@ pub struct TreeState {
     blocks_by_hash: HashMap<B256, ExecutedBlock>,
     /// Executed blocks grouped by their respective block number.
     blocks_by_number: BTreeMap<BlockNumber, Vec<ExecutedBlock>>,
+    /// The current canonical head.
+    current_canonical_head: BlockNumHash,
+    /// Hashes of the executed blocks that are neither canonical nor descendants of the canonical
+    /// head.
+    ///
+    /// Side-chain blocks keep their execution output and trie updates, so their state is
+    /// overlaid on top of their own ancestors and they can become canonical without being
+    /// executed again.
+    side_chain_blocks: HashSet<B256>,
 }
 
 impl TreeState {
+    /// Returns a new, empty tree state that points to the given canonical head.
+    pub(crate) fn new(current_canonical_head: BlockNumHash) -> Self {
+        Self {
+            blocks_by_hash: HashMap::new(),
+            blocks_by_number: BTreeMap::new(),
+            current_canonical_head,
+            side_chain_blocks: HashSet::new(),
+        }
+    }
+
     fn block_by_hash(&self, hash: B256) -> Option<Arc<SealedBlock>> {
         self.blocks_by_hash.get(&hash).map(|b| b.block.clone())
     }
 
     /// Insert executed block into the state.
-    fn insert_executed(&mut self, executed: ExecutedBlock) {
+    ///
+    /// Returns `true` if the block was inserted into a side chain.
+    fn insert_executed(&mut self, executed: ExecutedBlock) -> bool {
+        let hash = executed.block.hash();
         self.blocks_by_number.entry(executed.block.number).or_default().push(executed.clone());
-        let existing = self.blocks_by_hash.insert(executed.block.hash(), executed);
+        let existing = self.blocks_by_hash.insert(hash, executed);
         debug_assert!(existing.is_none(), "inserted duplicate block");
+
+        // a new block can't change the side-chain status of already executed blocks
+        let is_side_chain = self.is_side_chain_block(hash, &self.canonical_chain());
+        if is_side_chain {
+            self.side_chain_blocks.insert(hash);
+        }
+        is_side_chain
+    }
+
+    /// Returns the hashes of the canonical head and its in-memory ancestors, by block number.
+    fn canonical_chain(&self) -> BTreeMap<BlockNumber, B256> {
+        let mut chain = BTreeMap::from([(
+            self.current_canonical_head.number,
+            self.current_canonical_head.hash,
+        )]);
+        let mut current = self.current_canonical_head.hash;
+        while let Some(executed) = self.blocks_by_hash.get(&current) {
+            chain.insert(executed.block.number, current);
+            current = executed.block.parent_hash;
+        }
+        chain
+    }
+
+    /// Returns `true` if the executed block is neither canonical nor a descendant of the canonical
+    /// head.
+    fn is_side_chain_block(&self, hash: B256, canonical: &BTreeMap<BlockNumber, B256>) -> bool {
+        let mut current = hash;
+        while let Some(executed) = self.blocks_by_hash.get(&current) {
+            let number = executed.block.number;
+            if canonical.get(&number) == Some(&current) {
+                return false
+            }
+            if number <= self.current_canonical_head.number {
+                return true
+            }
+            current = executed.block.parent_hash;
+        }
+        // the lowest in-memory ancestor is above the canonical head, so it must be its child
+        current != self.current_canonical_head.hash
+    }
+
+    /// Recomputes the side-chain blocks after the canonical head changed.
+    fn update_side_chains(&mut self) {
+        let canonical = self.canonical_chain();
+        self.side_chain_blocks = self
+            .blocks_by_hash
+            .keys()
+            .copied()
+            .filter(|hash| self.is_side_chain_block(*hash, &canonical))
+            .collect();
+    }
+
+    /// Returns the number of side-chain blocks of the longest side chain.
+    fn side_chain_max_depth(&self) -> usize {
+        self.side_chain_blocks
+            .iter()
+            .map(|hash| {
+                let mut depth = 0;
+                let mut current = *hash;
+                while self.side_chain_blocks.contains(&current) {
+                    depth += 1;
+                    current = self.blocks_by_hash[&current].block.parent_hash;
+                }
+                depth
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Makes the executed block with the given hash the canonical head.
+    ///
+    /// The blocks that become canonical are taken from memory as they were executed on insertion.
+    /// Returns `None` if the block is not in memory.
+    fn make_canonical(&mut self, hash: B256) -> Option<NewCanonicalChain> {
+        let head = self.blocks_by_hash.get(&hash)?.block.num_hash();
+        let canonical = self.canonical_chain();
+
+        // walk back to the fork point with the current canonical chain
+        let mut new = Vec::new();
+        let mut current = hash;
+        while let Some(executed) = self.blocks_by_hash.get(&current) {
+            if canonical.get(&executed.block.number) == Some(&current) {
+                break
+            }
+            current = executed.block.parent_hash;
+            new.push(executed.clone());
+        }
+        new.reverse();
+
+        let fork_number = new.first().map_or(head.number, |executed| executed.block.number - 1);
+        let old = canonical
+            .range(fork_number + 1..)
+            .filter_map(|(_, hash)| self.blocks_by_hash.get(hash).cloned())
+            .collect::<Vec<_>>();
+
+        self.current_canonical_head = head;
+        self.update_side_chains();
+
+        Some(if old.is_empty() {
+            NewCanonicalChain::Commit { new }
+        } else {
+            NewCanonicalChain::Reorg { new, old }
+        })
     }
 
     /// Remove blocks before specified block number.
@@ -108,6 +252,7 @@ impl TreeState {
                     removed.is_some(),
                     "attempted to remove non-existing block {block_hash}"
                 );
+                self.side_chain_blocks.remove(&block_hash);
             }
         }
     }
@@ -222,6 +367,7 @@ pub struct EngineApiTreeHandlerImpl<P, E, T: EngineTypes> {
     state: EngineApiTreeState,
     /// (tmp) The flag indicating whether the pipeline is active.
     is_pipeline_active: bool,
+    metrics: EngineApiTreeMetrics,
     _marker: PhantomData<T>,
 }
 
@@ -231,6 +377,33 @@ where
     E: BlockExecutorProvider,
     T: EngineTypes,
 {
+    /// Creates a new handler on top of the given persisted canonical head.
+    pub fn new(
+        provider: P,
+        executor_provider: E,
+        consensus: Arc<dyn Consensus>,
+        payload_validator: ExecutionPayloadValidator,
+        canonical_head: BlockNumHash,
+        block_buffer_limit: u32,
+        max_invalid_header_cache_length: u32,
+    ) -> Self {
+        Self {
+            provider,
+            executor_provider,
+            consensus,
+            payload_validator,
+            state: EngineApiTreeState {
+                tree_state: TreeState::new(canonical_head),
+                forkchoice_state_tracker: ForkchoiceStateTracker::default(),
+                buffer: BlockBuffer::new(block_buffer_limit),
+                invalid_headers: InvalidHeaderCache::new(max_invalid_header_cache_length),
+            },
+            is_pipeline_active: false,
+            metrics: Default::default(),
+            _marker: PhantomData,
+        }
+    }
+
     /// Return block from database or in-memory state by hash.
     fn block_by_hash(&self, hash: B256) -> ProviderResult<Option<Block>> {
         // check database first
@@ -264,6 +437,71 @@ where
         Ok(MemoryOverlayStateProvider::new(in_memory, historical))
     }
 
+    /// Updates the side-chain metrics from the tree state.
+    fn update_side_chain_metrics(&self) {
+        let tree_state = &self.state.tree_state;
+        self.metrics.side_chain_blocks.set(tree_state.side_chain_blocks.len() as f64);
+        self.metrics.side_chain_max_depth.set(tree_state.side_chain_max_depth() as f64);
+    }
+
+    /// Makes the executed block with the given hash the canonical head.
+    ///
+    /// Side-chain blocks were fully validated when they were inserted, so a reorg to a side chain
+    /// reuses their execution output and trie updates instead of executing them again. Only
+    /// buffered blocks, which have no execution outcome yet, are executed.
+    ///
+    /// Returns `None` if the block is neither executed nor connected to an executed block.
+    fn make_canonical(&mut self, target: B256) -> Option<NewCanonicalChain> {
+        if !self.state.tree_state.blocks_by_hash.contains_key(&target) {
+            self.execute_buffered_blocks(target);
+        }
+
+        let chain = self.state.tree_state.make_canonical(target)?;
+        if let NewCanonicalChain::Reorg { new, old } = &chain {
+            debug!(target: "engine::tree", head=?target, new=new.len(), old=old.len(), "Reorged to side chain");
+            self.metrics.reorgs.increment(1);
+            self.metrics.reorg_reused_blocks.increment(new.len() as u64);
+        }
+        self.update_side_chain_metrics();
+        Some(chain)
+    }
+
+    /// Executes the buffered blocks that are connected to the lowest buffered ancestor of the
+    /// target block, including the target block itself.
+    fn execute_buffered_blocks(&mut self, target: B256) {
+        let Some((lowest_ancestor, parent_hash)) = self
+            .state
+            .buffer
+            .lowest_ancestor(&target)
+            .map(|block| (block.hash(), block.parent_hash))
+        else {
+            return
+        };
+
+        // the buffered blocks can only be executed on top of an executed or persisted block
+        if !matches!(self.block_by_hash(parent_hash), Ok(Some(_))) {
+            return
+        }
+
+        // the descendants of blocks that failed to execute can't be executed either
+        let mut failed = HashSet::new();
+        for block in self.state.buffer.remove_block_with_children(&lowest_ancestor) {
+            if failed.contains(&block.parent_hash) {
+                failed.insert(block.hash());
+                continue
+            }
+
+            let hash = block.hash();
+            match self.insert_block(block) {
+                Ok(_) => self.metrics.canonical_blocks_executed.increment(1),
+                Err(error) => {
+                    warn!(target: "engine::tree", %error, ?hash, "Failed to execute buffered block");
+                    failed.insert(hash);
+                }
+            }
+        }
+    }
+
     /// Return the parent hash of the lowest buffered ancestor for the requested block, if there
     /// are any buffered ancestors. If there are no buffered ancestors, and the block itself does
     /// not exist in the buffer, this returns the hash that is passed in.
@@ -454,7 +692,11 @@ where
             hashed_state: Arc::new(hashed_state),
             trie: Arc::new(trie_output),
         };
-        self.state.tree_state.insert_executed(executed);
+        if self.state.tree_state.insert_executed(executed) {
+            debug!(target: "engine::tree", number=block_number, hash=?block_hash, "Inserted side-chain block");
+            self.metrics.side_chain_blocks_inserted.increment(1);
+        }
+        self.update_side_chain_metrics();
 
         let attachment = BlockAttachment::Canonical; // TODO: remove or revise attachment
         Ok(InsertPayloadOk::Inserted(BlockStatus::Valid(attachment)))
@@ -589,6 +831,153 @@ where
         state: ForkchoiceState,
         attrs: Option<<Self::Engine as PayloadTypes>::PayloadAttributes>,
     ) -> TreeOutcome<Result<OnForkChoiceUpdated, String>> {
-        todo!()
+        if state.head_block_hash.is_zero() {
+            return TreeOutcome::new(Ok(OnForkChoiceUpdated::invalid_state()))
+        }
+
+        // the head is invalid if it has a known invalid ancestor
+        let lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(state.head_block_hash);
+        match self.check_invalid_ancestor_with_head(lowest_buffered_ancestor, state.head_block_hash)
+        {
+            Ok(Some(status)) => {
+                return TreeOutcome::new(Ok(OnForkChoiceUpdated::with_invalid(status)))
+            }
+            Ok(None) => {}
+            Err(error) => return TreeOutcome::new(Err(error.to_string())),
+        }
+
+        if self.is_pipeline_active {
+            self.state.forkchoice_state_tracker.set_latest(state, ForkchoiceStatus::Syncing);
+            return TreeOutcome::new(Ok(OnForkChoiceUpdated::syncing()))
+        }
+
+        // executed side-chain blocks become canonical without being executed again
+        let is_canonical = match self.make_canonical(state.head_block_hash) {
+            Some(_) => true,
+            // the head is already persisted
+            None => matches!(self.provider.block_by_hash(state.head_block_hash), Ok(Some(_))),
+        };
+        if !is_canonical {
+            self.state.forkchoice_state_tracker.set_latest(state, ForkchoiceStatus::Syncing);
+            let download = DownloadRequest::BlockSet(HashSet::from([state.head_block_hash]));
+            return TreeOutcome::new(Ok(OnForkChoiceUpdated::syncing()))
+                .with_event(TreeEvent::Download(download))
+        }
+
+        // TODO: start the payload job for the attributes, the tree has no payload builder yet
+        self.state.forkchoice_state_tracker.set_latest(state, ForkchoiceStatus::Valid);
+        TreeOutcome::new(Ok(OnForkChoiceUpdated::valid(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            Some(state.head_block_hash),
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_chainspec::MAINNET;
+    use reth_consensus::test_utils::TestConsensus;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_evm::test_utils::MockExecutorProvider;
+    use reth_primitives::Header;
+    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+
+    fn executed_block(number: BlockNumber, parent_hash: B256, timestamp: u64) -> ExecutedBlock {
+        let block = Block {
+            header: Header { number, parent_hash, timestamp, ..Default::default() },
+            ..Default::default()
+        };
+        ExecutedBlock {
+            block: Arc::new(block.seal_slow()),
+            senders: Arc::new(Vec::new()),
+            execution_output: Arc::new(ExecutionOutcome::default()),
+            hashed_state: Arc::new(HashedPostState::default()),
+            trie: Arc::new(TrieUpdates::default()),
+        }
+    }
+
+    fn hashes(blocks: &[ExecutedBlock]) -> Vec<B256> {
+        blocks.iter().map(|executed| executed.block.hash()).collect()
+    }
+
+    #[test]
+    fn tracks_side_chains_and_reorgs_without_execution() {
+        let persisted_head = BlockNumHash::new(0, B256::with_last_byte(1));
+        let mut tree_state = TreeState::new(persisted_head);
+
+        let a1 = executed_block(1, persisted_head.hash, 0);
+        let a2 = executed_block(2, a1.block.hash(), 0);
+        assert!(!tree_state.insert_executed(a1.clone()));
+        assert!(!tree_state.insert_executed(a2.clone()));
+        assert_matches!(
+            tree_state.make_canonical(a2.block.hash()),
+            Some(NewCanonicalChain::Commit { new }) if hashes(&new) == hashes(&[a1.clone(), a2.clone()])
+        );
+
+        // a fork of the canonical chain and a descendant of the canonical head
+        let b2 = executed_block(2, a1.block.hash(), 1);
+        let b3 = executed_block(3, b2.block.hash(), 1);
+        let a3 = executed_block(3, a2.block.hash(), 0);
+        assert!(tree_state.insert_executed(b2.clone()));
+        assert!(tree_state.insert_executed(b3.clone()));
+        assert!(!tree_state.insert_executed(a3.clone()));
+        assert_eq!(tree_state.side_chain_blocks, HashSet::from([b2.block.hash(), b3.block.hash()]));
+        assert_eq!(tree_state.side_chain_max_depth(), 2);
+
+        assert_matches!(
+            tree_state.make_canonical(b3.block.hash()),
+            Some(NewCanonicalChain::Reorg { new, old })
+                if hashes(&new) == hashes(&[b2.clone(), b3.clone()]) && hashes(&old) == hashes(&[a2.clone()])
+        );
+        assert_eq!(tree_state.current_canonical_head, b3.block.num_hash());
+        assert_eq!(tree_state.side_chain_blocks, HashSet::from([a2.block.hash(), a3.block.hash()]));
+
+        tree_state.remove_before(3);
+        assert_eq!(tree_state.side_chain_blocks, HashSet::from([a3.block.hash()]));
+        assert_eq!(tree_state.side_chain_max_depth(), 1);
+        assert!(tree_state.make_canonical(a2.block.hash()).is_none());
+    }
+
+    #[test]
+    fn forkchoice_update_reorgs_to_executed_side_chain() {
+        let persisted_head = BlockNumHash::new(0, B256::with_last_byte(1));
+        let mut handler = EngineApiTreeHandlerImpl::<_, _, EthEngineTypes>::new(
+            create_test_provider_factory_with_chain_spec(MAINNET.clone()),
+            MockExecutorProvider::default(),
+            Arc::new(TestConsensus::default()),
+            ExecutionPayloadValidator::new(MAINNET.clone()),
+            persisted_head,
+            10,
+            10,
+        );
+
+        let a1 = executed_block(1, persisted_head.hash, 0);
+        let a2 = executed_block(2, a1.block.hash(), 0);
+        let b2 = executed_block(2, a1.block.hash(), 1);
+        for executed in [&a1, &a2, &b2] {
+            handler.state.tree_state.insert_executed(executed.clone());
+        }
+
+        let forkchoice_update = |handler: &mut EngineApiTreeHandlerImpl<_, _, _>, head: B256| {
+            let state = ForkchoiceState { head_block_hash: head, ..Default::default() };
+            handler.on_forkchoice_updated(state, None)
+        };
+
+        let outcome = forkchoice_update(&mut handler, a2.block.hash());
+        assert_eq!(outcome.outcome.unwrap().forkchoice_status(), ForkchoiceStatus::Valid);
+        assert_eq!(handler.state.tree_state.current_canonical_head, a2.block.num_hash());
+
+        // the side chain becomes canonical without being executed
+        let outcome = forkchoice_update(&mut handler, b2.block.hash());
+        assert_eq!(outcome.outcome.unwrap().forkchoice_status(), ForkchoiceStatus::Valid);
+        assert_eq!(handler.state.tree_state.current_canonical_head, b2.block.num_hash());
+        assert_eq!(handler.state.tree_state.side_chain_blocks, HashSet::from([a2.block.hash()]));
+
+        // an unknown head is downloaded
+        let outcome = forkchoice_update(&mut handler, B256::with_last_byte(2));
+        assert_eq!(outcome.outcome.unwrap().forkchoice_status(), ForkchoiceStatus::Syncing);
+        assert_matches!(outcome.event, Some(TreeEvent::Download(DownloadRequest::BlockSet(_))));
     }
 }
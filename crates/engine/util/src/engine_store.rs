@@ -1,23 +1,27 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
 use futures::{Stream, StreamExt};
-use reth_beacon_consensus::BeaconEngineMessage;
+use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
 use reth_engine_primitives::EngineTypes;
 use reth_fs_util as fs;
 use reth_rpc_types::{
-    engine::{CancunPayloadFields, ForkchoiceState},
+    engine::{CancunPayloadFields, ForkchoiceState, PayloadStatus, PayloadStatusEnum},
     ExecutionPayload,
 };
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The suffix of the files that store the responses to the stored messages.
+const RESPONSE_FILE_SUFFIX: &str = ".response.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -38,6 +42,59 @@ pub enum StoredEngineApiMessage<Attributes> {
     },
 }
 
+/// The response of the node to an engine API message that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredEngineApiResponse {
+    /// The time the response was sent, in milliseconds since the unix epoch.
+    pub responded_at: u64,
+    /// The response, or the error message if the message failed.
+    pub result: Result<StoredEngineApiResult, String>,
+}
+
+/// The on-disk representation of the result of an engine API method call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResult {
+    /// The status of the forkchoice state of an `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated(StoredForkchoiceStatus),
+    /// The [`PayloadStatus`] returned by an `engine_newPayload` call.
+    NewPayload(PayloadStatus),
+}
+
+/// The status of the forkchoice state of a stored `engine_forkchoiceUpdated` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StoredForkchoiceStatus {
+    /// The forkchoice state is valid.
+    Valid,
+    /// The forkchoice state is invalid.
+    Invalid,
+    /// The forkchoice state is unknown.
+    Syncing,
+}
+
+impl StoredForkchoiceStatus {
+    /// Returns the forkchoice status of the payload status returned by a forkchoice update.
+    pub const fn from_payload_status(status: &PayloadStatusEnum) -> Self {
+        match status {
+            PayloadStatusEnum::Valid | PayloadStatusEnum::Accepted => Self::Valid,
+            PayloadStatusEnum::Invalid { .. } => Self::Invalid,
+            PayloadStatusEnum::Syncing => Self::Syncing,
+        }
+    }
+}
+
+impl From<ForkchoiceStatus> for StoredForkchoiceStatus {
+    fn from(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
@@ -55,31 +112,39 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message, if it was stored.
     pub fn on_message<Engine>(
         &self,
         msg: &BeaconEngineMessage<Engine>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<Option<PathBuf>>
     where
         Engine: EngineTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let path = match msg {
             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                path
             }
             BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(
                         &StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
                             payload: payload.clone(),
@@ -87,21 +152,63 @@ impl EngineMessageStore {
                         },
                     )?,
                 )?;
+                path
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
+            BeaconEngineMessage::TransitionConfigurationExchanged => return Ok(None),
         };
+        Ok(Some(path))
+    }
+
+    /// Stores the response to the message stored at the given path next to it.
+    pub fn on_response(
+        message_path: &Path,
+        result: Result<StoredEngineApiResult, String>,
+        responded_at: SystemTime,
+    ) -> eyre::Result<()> {
+        let responded_at =
+            responded_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
+        fs::write(
+            Self::response_path(message_path),
+            serde_json::to_vec(&StoredEngineApiResponse { responded_at, result })?,
+        )?;
         Ok(())
     }
 
+    /// Reads the stored response to the message stored at the given path, if any.
+    ///
+    /// Messages that were stored without their responses don't have one.
+    pub fn response(message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Returns the path of the response to the message stored at the given path.
+    fn response_path(message_path: &Path) -> PathBuf {
+        let stem = message_path.file_stem().unwrap_or_default().to_string_lossy();
+        message_path.with_file_name(format!("{stem}{RESPONSE_FILE_SUFFIX}"))
+    }
+
+    /// Returns the time the message stored at the given path was received, in milliseconds since
+    /// the unix epoch.
+    pub fn received_at(message_path: &Path) -> Option<u64> {
+        message_path.file_name()?.to_str()?.split('-').next()?.parse().ok()
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(RESPONSE_FILE_SUFFIX)) {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
-                if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
+                if let Some(timestamp) = Self::received_at(Path::new(filename)) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
                     tracing::debug!(target: "engine::store", timestamp, filename, "Queued engine API message");
                 } else {
@@ -115,7 +222,7 @@ impl EngineMessageStore {
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
+/// A wrapper stream that stores Engine API messages and the responses to them in
 /// the specified directory.
 #[derive(Debug)]
 #[pin_project::pin_project]
@@ -143,12 +250,60 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
+        let Some(mut msg) = ready!(this.stream.poll_next_unpin(cx)) else {
+            return Poll::Ready(None)
+        };
+        match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(Some(path)) => record_response(&mut msg, path),
+            Ok(None) => (),
+            Err(error) => {
                 error!(target: "engine::intercept", ?msg, %error, "Error handling Engine API message");
             }
         }
-        Poll::Ready(next)
+        Poll::Ready(Some(msg))
+    }
+}
+
+/// Intercepts the response to the message, stores it next to the message stored at the given
+/// path, and forwards it to the original receiver.
+fn record_response<Engine: EngineTypes>(msg: &mut BeaconEngineMessage<Engine>, path: PathBuf) {
+    match msg {
+        BeaconEngineMessage::ForkchoiceUpdated { tx, .. } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let tx = std::mem::replace(tx, response_tx);
+            tokio::spawn(async move {
+                let Ok(response) = response_rx.await else { return };
+                let result = response
+                    .as_ref()
+                    .map(|on_updated| {
+                        StoredEngineApiResult::ForkchoiceUpdated(
+                            on_updated.forkchoice_status().into(),
+                        )
+                    })
+                    .map_err(ToString::to_string);
+                let _ = tx.send(response);
+                store_response(&path, result);
+            });
+        }
+        BeaconEngineMessage::NewPayload { tx, .. } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let tx = std::mem::replace(tx, response_tx);
+            tokio::spawn(async move {
+                let Ok(response) = response_rx.await else { return };
+                let result = response
+                    .as_ref()
+                    .map(|status| StoredEngineApiResult::NewPayload(status.clone()))
+                    .map_err(ToString::to_string);
+                let _ = tx.send(response);
+                store_response(&path, result);
+            });
+        }
+        BeaconEngineMessage::TransitionConfigurationExchanged => (),
+    }
+}
+
+fn store_response(path: &Path, result: Result<StoredEngineApiResult, String>) {
+    if let Err(error) = EngineMessageStore::on_response(path, result, SystemTime::now()) {
+        error!(target: "engine::intercept", ?path, %error, "Error storing Engine API response");
     }
 }
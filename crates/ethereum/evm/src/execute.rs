@@ -2,11 +2,13 @@
 
 use crate::{
     dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
+    supply::{check_supply, SupplyCheck},
     EthEvmConfig,
 };
 use reth_chainspec::{ChainSpec, EthereumHardforks, MAINNET};
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
+    dev::DevState,
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
@@ -29,6 +31,7 @@ use revm_primitives::{
     db::{Database, DatabaseCommit},
     BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ResultAndState,
 };
+use tracing::error;
 
 #[cfg(feature = "std")]
 use std::{fmt::Display, sync::Arc, vec, vec::Vec};
@@ -37,6 +40,10 @@ use std::{fmt::Display, sync::Arc, vec, vec::Vec};
 pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Whether executed blocks are checked to conserve the ETH supply.
+    supply_check: Option<SupplyCheck>,
+    /// The state overrides of the dev chain, applied in the blocks it mines.
+    dev_state: Option<DevState>,
 }
 
 impl EthExecutorProvider {
@@ -54,7 +61,23 @@ impl EthExecutorProvider {
 impl<EvmConfig> EthExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, supply_check: None, dev_state: None }
+    }
+
+    /// Configures the check that every executed block conserves the ETH supply.
+    ///
+    /// See [`SupplyCheck`].
+    pub fn with_supply_check(mut self, supply_check: Option<SupplyCheck>) -> Self {
+        self.supply_check = supply_check;
+        self
+    }
+
+    /// Configures the state overrides of the dev chain.
+    ///
+    /// This must only be set in dev mode, see [`reth_evm::dev`].
+    pub fn with_dev_state(mut self, dev_state: Option<DevState>) -> Self {
+        self.dev_state = dev_state;
+        self
     }
 }
 
@@ -71,6 +94,8 @@ where
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
         )
+        .with_supply_check(self.supply_check)
+        .with_dev_state(self.dev_state.clone())
     }
 }
 
@@ -242,12 +267,37 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
     executor: EthEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// Whether executed blocks are checked to conserve the ETH supply.
+    supply_check: Option<SupplyCheck>,
+    /// The state overrides of the dev chain, applied in the blocks it mines.
+    dev_state: Option<DevState>,
 }
 
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor { chain_spec, evm_config },
+            state,
+            supply_check: None,
+            dev_state: None,
+        }
+    }
+
+    /// Configures the check that every executed block conserves the ETH supply.
+    ///
+    /// The check requires the state to track the transitions of the block, see
+    /// [`StateBuilder::with_bundle_update`](reth_revm::db::StateBuilder::with_bundle_update).
+    pub fn with_supply_check(mut self, supply_check: Option<SupplyCheck>) -> Self {
+        self.supply_check = supply_check;
+        self
+    }
+
+    /// Configures the state overrides of the dev chain, which are applied before the
+    /// transactions of the block they are assigned to.
+    pub fn with_dev_state(mut self, dev_state: Option<DevState>) -> Self {
+        self.dev_state = dev_state;
+        self
     }
 
     #[inline]
@@ -299,6 +349,20 @@ where
     ) -> Result<EthExecuteOutput, BlockExecutionError> {
         // 1. prepare state on new block
         self.on_new_block(&block.header);
+        let impersonated;
+        let block = match &self.dev_state {
+            Some(dev_state) => {
+                dev_state.apply_overrides(&mut self.state, block.number)?;
+                match dev_state.with_impersonated_senders(block) {
+                    Some(block) => {
+                        impersonated = block;
+                        &impersonated
+                    }
+                    None => block,
+                }
+            }
+            None => block,
+        };
 
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
@@ -310,6 +374,24 @@ where
         // 3. apply post execution changes
         self.post_execution(block, total_difficulty)?;
 
+        // 4. check the balance changes of the block before the transitions are merged
+        if let Some(supply_check) = self.supply_check {
+            if let Err(err) = check_supply(
+                self.chain_spec(),
+                block,
+                total_difficulty,
+                output.gas_used,
+                self.state.transition_state.as_ref(),
+            ) {
+                match supply_check {
+                    SupplyCheck::Log => {
+                        error!(target: "evm", number = block.number, hash = %block.hash_slow(), %err, "Block violates the ETH supply")
+                    }
+                    SupplyCheck::Halt => return Err(BlockExecutionError::other(err)),
+                }
+            }
+        }
+
         Ok(output)
     }
 
@@ -517,7 +599,12 @@ mod tests {
     }
 
     fn executor_provider(chain_spec: Arc<ChainSpec>) -> EthExecutorProvider<EthEvmConfig> {
-        EthExecutorProvider { chain_spec, evm_config: Default::default() }
+        EthExecutorProvider {
+            chain_spec,
+            evm_config: Default::default(),
+            supply_check: None,
+            dev_state: None,
+        }
     }
 
     #[test]
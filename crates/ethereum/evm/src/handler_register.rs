@@ -0,0 +1,46 @@
+//! Handler registers that are appended to the EVMs of the [`EthEvmConfig`](crate::EthEvmConfig).
+
+use core::fmt::Debug;
+use reth_revm::{handler::register::EvmHandler, Database};
+
+/// A revm handler register that modifies the handler of every EVM created by an
+/// [`EthEvmConfig`](crate::EthEvmConfig).
+///
+/// Registers can replace any handle of the [`EvmHandler`], e.g. the reward of the beneficiary or
+/// the validation of transactions, without reimplementing [`ConfigureEvm`](reth_evm::ConfigureEvm).
+/// They are applied again whenever the spec id of the EVM changes.
+///
+/// Registers are composed with tuples: `()` doesn't modify the handler and `(A, B)` applies `A`
+/// before `B`.
+pub trait EvmHandlerRegister: Debug + Clone + Send + Sync + Unpin + 'static {
+    /// Modifies the handler.
+    fn register<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>);
+
+    /// Returns `true` if the register doesn't modify the handler.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl EvmHandlerRegister for () {
+    fn register<EXT, DB: Database>(&self, _handler: &mut EvmHandler<'_, EXT, DB>) {}
+
+    fn is_empty(&self) -> bool {
+        true
+    }
+}
+
+impl<A, B> EvmHandlerRegister for (A, B)
+where
+    A: EvmHandlerRegister,
+    B: EvmHandlerRegister,
+{
+    fn register<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+        self.0.register(handler);
+        self.1.register(handler);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty() && self.1.is_empty()
+    }
+}
@@ -15,12 +15,21 @@ extern crate alloc;
 use reth_chainspec::{ChainSpec, Head};
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{transaction::FillTxEnv, Address, Header, TransactionSigned, U256};
-use reth_revm::{Database, EvmBuilder};
+use reth_revm::{
+    handler::register::{EvmHandler, HandleRegisterBox},
+    inspector_handle_register, Database, Evm, EvmBuilder, GetInspector,
+};
 use revm_primitives::{AnalysisKind, Bytes, CfgEnvWithHandlerCfg, Env, TxEnv, TxKind};
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 mod config;
 pub use config::{revm_spec, revm_spec_by_timestamp_after_merge};
 
+mod handler_register;
+pub use handler_register::EvmHandlerRegister;
+
 pub mod execute;
 
 /// Ethereum DAO hardfork state change data.
@@ -29,12 +38,56 @@ pub mod dao_fork;
 /// [EIP-6110](https://eips.ethereum.org/EIPS/eip-6110) handling.
 pub mod eip6110;
 
+/// Checks of the ETH supply of executed blocks.
+pub mod supply;
+
 /// Ethereum-related EVM configuration.
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// Small modifications of the EVM, e.g. of the reward of the beneficiary or the validation of
+/// transactions, don't require a custom [`ConfigureEvm`] implementation, they can be appended as
+/// revm handler registers with [`EthEvmConfig::with_handler_register`].
+#[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
-pub struct EthEvmConfig;
+pub struct EthEvmConfig<R = ()> {
+    /// The handler register that is appended to every EVM.
+    handler_register: R,
+}
+
+impl EthEvmConfig {
+    /// Creates a new config without additional handler registers.
+    pub const fn new() -> Self {
+        Self { handler_register: () }
+    }
+}
+
+impl Default for EthEvmConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> EthEvmConfig<R> {
+    /// Appends a handler register that is applied after the registers that were already
+    /// appended.
+    pub const fn with_handler_register<N>(self, register: N) -> EthEvmConfig<(R, N)> {
+        EthEvmConfig { handler_register: (self.handler_register, register) }
+    }
+
+    /// Returns the appended handler registers.
+    pub const fn handler_register(&self) -> &R {
+        &self.handler_register
+    }
+}
 
-impl ConfigureEvmEnv for EthEvmConfig {
+impl<R: EvmHandlerRegister> EthEvmConfig<R> {
+    /// Returns the appended handler registers as a revm handler register.
+    fn handler_register_box<EXT, DB: Database>(&self) -> HandleRegisterBox<EXT, DB> {
+        let register = self.handler_register.clone();
+        Box::new(move |handler: &mut EvmHandler<'_, EXT, DB>| register.register(handler))
+    }
+}
+
+impl<R: EvmHandlerRegister> ConfigureEvmEnv for EthEvmConfig<R> {
     fn fill_cfg_env(
         cfg_env: &mut CfgEnvWithHandlerCfg,
         chain_spec: &ChainSpec,
@@ -102,14 +155,30 @@ impl ConfigureEvmEnv for EthEvmConfig {
     }
 }
 
-impl ConfigureEvm for EthEvmConfig {
+impl<R: EvmHandlerRegister> ConfigureEvm for EthEvmConfig<R> {
     type DefaultExternalContext<'a> = ();
 
-    fn evm<'a, DB: Database + 'a>(
-        &self,
-        db: DB,
-    ) -> reth_revm::Evm<'a, Self::DefaultExternalContext<'a>, DB> {
-        EvmBuilder::default().with_db(db).build()
+    fn evm<'a, DB: Database + 'a>(&self, db: DB) -> Evm<'a, Self::DefaultExternalContext<'a>, DB> {
+        let builder = EvmBuilder::default().with_db(db);
+        if self.handler_register.is_empty() {
+            return builder.build()
+        }
+        builder.append_handler_register_box(self.handler_register_box()).build()
+    }
+
+    fn evm_with_inspector<'a, DB, I>(&'a self, db: DB, inspector: I) -> Evm<'a, I, DB>
+    where
+        DB: Database + 'a,
+        I: GetInspector<DB>,
+    {
+        let builder = EvmBuilder::default().with_db(db).with_external_context(inspector);
+        if self.handler_register.is_empty() {
+            return builder.append_handler_register(inspector_handle_register).build()
+        }
+        builder
+            .append_handler_register_box(self.handler_register_box())
+            .append_handler_register(inspector_handle_register)
+            .build()
     }
 }
 
@@ -121,7 +190,20 @@ mod tests {
         revm_primitives::{BlockEnv, CfgEnv, SpecId},
         Header, U256,
     };
-    use revm_primitives::CfgEnvWithHandlerCfg;
+    use reth_revm::{db::EmptyDB, inspectors::NoOpInspector};
+    use revm_primitives::{CfgEnvWithHandlerCfg, EVMError};
+    use std::sync::Arc;
+
+    /// Rejects every transaction.
+    #[derive(Debug, Clone)]
+    struct RejectAll;
+
+    impl EvmHandlerRegister for RejectAll {
+        fn register<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+            handler.validation.env =
+                Arc::new(|_: &Env| Err(EVMError::Custom("rejected".to_string())));
+        }
+    }
 
     #[test]
     #[ignore]
@@ -132,7 +214,7 @@ mod tests {
         let chain_spec = ChainSpec::default();
         let total_difficulty = U256::ZERO;
 
-        EthEvmConfig::fill_cfg_and_block_env(
+        <EthEvmConfig>::fill_cfg_and_block_env(
             &mut cfg_env,
             &mut block_env,
             &chain_spec,
@@ -142,4 +224,21 @@ mod tests {
 
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
     }
+
+    #[test]
+    fn appends_handler_registers() {
+        let evm_config = EthEvmConfig::default();
+        assert!(!matches!(evm_config.evm(EmptyDB::default()).transact(), Err(EVMError::Custom(_))));
+
+        let evm_config = evm_config.with_handler_register(RejectAll);
+        let mut evm = evm_config.evm(EmptyDB::default());
+        assert!(matches!(evm.transact(), Err(EVMError::Custom(_))));
+
+        // the registers are applied again when the spec id changes
+        evm.modify_spec_id(SpecId::FRONTIER);
+        assert!(matches!(evm.transact(), Err(EVMError::Custom(_))));
+
+        let mut evm = evm_config.evm_with_inspector(EmptyDB::default(), NoOpInspector);
+        assert!(matches!(evm.transact(), Err(EVMError::Custom(_))));
+    }
 }
@@ -0,0 +1,146 @@
+//! Checks that the execution of a block conserves the ETH supply.
+
+use reth_chainspec::ChainSpec;
+use reth_primitives::{BlockWithSenders, U256};
+use reth_revm::{state_change::post_block_balance_increments, TransitionState};
+use std::fmt;
+
+/// How the executor reacts to a block that doesn't conserve the ETH supply.
+///
+/// The ETH supply is conserved if the balances of all accounts changed by at most the block and
+/// ommer rewards and the withdrawals that are issued, minus the base fee and blob fee that are
+/// burned. This is a safety net for changes to the executor and the system calls, blocks of a
+/// valid chain never violate it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyCheck {
+    /// Logs the violation and continues.
+    Log,
+    /// Fails the execution of the block with a [`SupplyMismatch`] error.
+    Halt,
+}
+
+/// Error when the sum of all balances after the block doesn't match the sum before the block
+/// plus the issued ETH minus the burned ETH.
+///
+/// This is not a block validation error, since it's caused by a bug in the executor or in the
+/// check itself rather than by the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyMismatch {
+    /// The sum of the balances of the changed accounts before the block.
+    pub before: U256,
+    /// The sum of the balances of the changed accounts after the block.
+    pub after: U256,
+    /// The expected sum of the balances of the changed accounts after the block.
+    pub expected: U256,
+}
+
+impl fmt::Display for SupplyMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { before, after, expected } = self;
+        write!(
+            f,
+            "ETH supply is not conserved: balances changed from {before} to {after}, expected {expected}"
+        )
+    }
+}
+
+impl std::error::Error for SupplyMismatch {}
+
+/// Checks that the balance changes of the executed block match the issued and burned ETH.
+///
+/// The transitions must contain all changes of the block, including the post execution changes.
+///
+/// A self-destruct that names the destroyed account as the beneficiary, or a transfer to an
+/// account that is destroyed later in the same transaction, burns the transferred ETH. If any
+/// account was destroyed in the block, the check only ensures that no ETH was created.
+pub(crate) fn check_supply(
+    chain_spec: &ChainSpec,
+    block: &BlockWithSenders,
+    total_difficulty: U256,
+    gas_used: u64,
+    transitions: Option<&TransitionState>,
+) -> Result<(), SupplyMismatch> {
+    let issued: U256 = post_block_balance_increments(chain_spec, block, total_difficulty)
+        .into_values()
+        .map(U256::from)
+        .sum();
+
+    let mut burned = U256::ZERO;
+    if let Some(base_fee) = block.base_fee_per_gas {
+        burned += U256::from(base_fee) * U256::from(gas_used);
+    }
+    if let Some((blob_fee, blob_gas_used)) = block.blob_fee().zip(block.blob_gas_used) {
+        burned += U256::from(blob_fee) * U256::from(blob_gas_used);
+    }
+
+    let mut before = U256::ZERO;
+    let mut after = U256::ZERO;
+    let mut destroyed = false;
+    for account in transitions.iter().flat_map(|state| state.transitions.values()) {
+        before += account.previous_info.as_ref().map(|info| info.balance).unwrap_or_default();
+        after += account.info.as_ref().map(|info| info.balance).unwrap_or_default();
+        destroyed |= account.status.was_destroyed();
+    }
+
+    let expected = (before + issued).saturating_sub(burned);
+    if after == expected || (destroyed && after < expected) {
+        return Ok(())
+    }
+    Err(SupplyMismatch { before, after, expected })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_primitives::{
+        constants::GWEI_TO_WEI, Address, Block, Header, Withdrawal, Withdrawals,
+    };
+    use reth_revm::{db::AccountStatus, TransitionAccount};
+    use revm_primitives::AccountInfo;
+    use std::collections::HashMap;
+
+    fn transitions(address: Address, before: u128, after: u128) -> TransitionState {
+        let info =
+            |balance| Some(AccountInfo { balance: U256::from(balance), ..Default::default() });
+        let account = TransitionAccount {
+            info: info(after),
+            status: AccountStatus::Changed,
+            previous_info: info(before),
+            ..Default::default()
+        };
+        TransitionState { transitions: HashMap::from([(address, account)]) }
+    }
+
+    #[test]
+    fn balance_changes_match_withdrawals_and_burned_fees() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let address = Address::with_last_byte(1);
+        let block = Block {
+            header: Header { base_fee_per_gas: Some(7), ..Default::default() },
+            withdrawals: Some(Withdrawals::new(vec![Withdrawal {
+                address,
+                amount: 2,
+                ..Default::default()
+            }])),
+            ..Default::default()
+        }
+        .with_recovered_senders()
+        .unwrap();
+        let withdrawn = 2 * GWEI_TO_WEI as u128;
+
+        // 10 gas at a base fee of 7 burns 70 wei
+        let state = transitions(address, 100, 100 + withdrawn - 70);
+        assert_eq!(check_supply(&chain_spec, &block, U256::ZERO, 10, Some(&state)), Ok(()));
+
+        let state = transitions(address, 100, 100 + withdrawn);
+        assert_eq!(
+            check_supply(&chain_spec, &block, U256::ZERO, 10, Some(&state)),
+            Err(SupplyMismatch {
+                before: U256::from(100),
+                after: U256::from(100 + withdrawn),
+                expected: U256::from(100 + withdrawn - 70),
+            })
+        );
+    }
+}
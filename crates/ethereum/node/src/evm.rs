@@ -3,4 +3,46 @@
 #[doc(inline)]
 pub use reth_evm_ethereum::execute::EthExecutorProvider;
 #[doc(inline)]
+pub use reth_evm_ethereum::supply::SupplyCheck;
+#[doc(inline)]
 pub use reth_evm_ethereum::EthEvmConfig;
+
+use reth_chainspec::ChainSpec;
+use reth_evm::dev::DevState;
+use reth_node_core::args::{EvmArgs, EvmBackend, SupplyCheckMode};
+use std::sync::Arc;
+
+/// Returns the [`SupplyCheck`] for the mode of the `--debug.supply-check` argument.
+pub const fn supply_check(mode: Option<SupplyCheckMode>) -> Option<SupplyCheck> {
+    match mode {
+        Some(SupplyCheckMode::Log) => Some(SupplyCheck::Log),
+        Some(SupplyCheckMode::Halt) => Some(SupplyCheck::Halt),
+        None => None,
+    }
+}
+
+/// Returns the executor provider of the EVM backend.
+///
+/// The overrides of the `dev_state` are only applied in dev mode, see [`reth_evm::dev`].
+pub fn backend_executor_provider(
+    chain_spec: Arc<ChainSpec>,
+    backend: EvmBackend,
+    supply_check: Option<SupplyCheck>,
+    dev_state: Option<DevState>,
+) -> EthExecutorProvider {
+    match backend {
+        EvmBackend::Revm => EthExecutorProvider::new(chain_spec, EthEvmConfig::default())
+            .with_supply_check(supply_check)
+            .with_dev_state(dev_state),
+    }
+}
+
+/// Returns the executor provider that is configured by the [`EvmArgs`].
+pub fn executor_provider(
+    chain_spec: Arc<ChainSpec>,
+    args: &EvmArgs,
+    supply_check: Option<SupplyCheck>,
+    dev_state: Option<DevState>,
+) -> EthExecutorProvider {
+    backend_executor_provider(chain_spec, args.backend, supply_check, dev_state)
+}
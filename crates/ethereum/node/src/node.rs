@@ -1,13 +1,18 @@
 //! Ethereum Node types config.
 
-use crate::{EthEngineTypes, EthEvmConfig};
+use crate::{
+    evm::{executor_provider, supply_check},
+    EthEngineTypes, EthEvmConfig, EthExecutorProvider,
+};
 use reth_auto_seal_consensus::AutoSealConsensus;
-use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_basic_payload_builder::{
+    BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig, StatePrefetcher,
+    StatePrefetcherConfig,
+};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_evm_ethereum::execute::EthExecutorProvider;
 use reth_network::NetworkHandle;
 use reth_node_builder::{
     components::{
@@ -92,15 +97,19 @@ where
     Node: FullNodeTypes,
 {
     type EVM = EthEvmConfig;
-    type Executor = EthExecutorProvider<Self::EVM>;
+    type Executor = EthExecutorProvider;
 
     async fn build_evm(
         self,
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
-        let chain_spec = ctx.chain_spec();
         let evm_config = EthEvmConfig::default();
-        let executor = EthExecutorProvider::new(chain_spec, evm_config);
+        let executor = executor_provider(
+            ctx.chain_spec(),
+            &ctx.config().evm,
+            supply_check(ctx.config().debug.supply_check),
+            ctx.dev_state().cloned(),
+        );
 
         Ok((evm_config, executor))
     }
@@ -125,11 +134,17 @@ where
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
+        if let Some(lists) = ctx.config().txpool.policy_lists()? {
+            pool_config.transaction_policy.set_lists(lists);
+        }
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
+            .with_transaction_policy(pool_config.transaction_policy.clone())
+            .with_blob_proof_computation(pool_config.blob_proof_computation.clone())
+            .with_tip_floor(pool_config.tip_floor.clone())
             .with_additional_tasks(1)
             .build_with_tasks(
                 ctx.provider().clone(),
@@ -148,7 +163,7 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                ctx.config().txpool.local_transactions_backup_config(transactions_path);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -208,14 +223,33 @@ where
             .max_payload_tasks(conf.max_payload_tasks())
             .extradata(conf.extradata_bytes());
 
-        let payload_generator = BasicPayloadJobGenerator::with_builder(
+        let mut payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
-            pool,
+            pool.clone(),
             ctx.task_executor().clone(),
             payload_job_config,
             ctx.chain_spec(),
             payload_builder,
         );
+
+        if conf.prefetch_transactions() > 0 {
+            let prefetcher = StatePrefetcher::new(
+                ctx.provider().clone(),
+                pool,
+                EthEvmConfig::default(),
+                ctx.chain_spec(),
+                StatePrefetcherConfig {
+                    max_transactions: conf.prefetch_transactions(),
+                    ..Default::default()
+                },
+            );
+            payload_generator =
+                payload_generator.with_prefetched_state(prefetcher.prefetched_state());
+            ctx.task_executor()
+                .spawn_blocking(prefetcher.run(ctx.provider().canonical_state_stream()));
+            debug!(target: "reth::cli", "Spawned payload state prefetcher");
+        }
+
         let (payload_service, payload_builder) =
             PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
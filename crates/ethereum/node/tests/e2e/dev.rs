@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_primitives::{b256, hex, B256};
 use futures::StreamExt;
-use reth::rpc::api::eth::helpers::EthTransactions;
+use reth::rpc::api::{eth::helpers::EthTransactions, DevApiClient};
 use reth_chainspec::ChainSpec;
 use reth_e2e_test_utils::setup;
-use reth_provider::CanonStateSubscriptions;
+use reth_provider::{CanonStateNotification, CanonStateSubscriptions};
 
 use crate::utils::EthNode;
 
@@ -15,11 +15,40 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     reth_tracing::init_test_tracing();
     let (mut nodes, _tasks, _) = setup(1, custom_chain(), true).await?;
 
-    assert_chain_advances(nodes.pop().unwrap()).await;
+    assert_chain_advances(&nodes.pop().unwrap()).await;
     Ok(())
 }
 
-async fn assert_chain_advances(node: EthNode) {
+#[tokio::test]
+async fn can_revert_dev_node_to_snapshot() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, _tasks, _) = setup(1, custom_chain(), true).await?;
+    let node = nodes.pop().unwrap();
+    let client = node.inner.rpc_server_handle().http_client().unwrap();
+    let mut notifications = node.inner.provider.canonical_state_stream();
+
+    let snapshot = DevApiClient::evm_snapshot(&client).await?;
+    let hash = assert_chain_advances(&node).await;
+
+    assert!(DevApiClient::evm_revert(&client, snapshot).await?);
+
+    // the revert is announced, so the pool re-injects the transaction and it is mined again
+    let _ = notifications.next().await.unwrap();
+    let reorg = notifications.next().await.unwrap();
+    let CanonStateNotification::Reorg { old, new } = &reorg else {
+        panic!("expected a reorg, got {reorg:?}")
+    };
+    assert_eq!(new.tip().number, 0);
+    assert_eq!(old.tip().number, 1);
+    assert!(old.tip().transactions().any(|tx| tx.hash() == hash));
+
+    let head = notifications.next().await.unwrap();
+    assert_eq!(head.tip().number, 1);
+    assert!(head.tip().transactions().any(|tx| tx.hash() == hash));
+    Ok(())
+}
+
+async fn assert_chain_advances(node: &EthNode) -> B256 {
     let mut notifications = node.inner.provider.canonical_state_stream();
 
     // submit tx through rpc
@@ -39,6 +68,7 @@ async fn assert_chain_advances(node: EthNode) {
     let tx = head.tip().transactions().next().unwrap();
     assert_eq!(tx.hash(), hash);
     println!("mined transaction: {hash}");
+    hash
 }
 
 fn custom_chain() -> Arc<ChainSpec> {
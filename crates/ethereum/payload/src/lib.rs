@@ -26,17 +26,15 @@ use reth_payload_builder::{
     error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
 };
 use reth_primitives::{
-    constants::{
-        eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
-    },
-    eip4844::calculate_excess_blob_gas,
+    constants::{BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS},
+    kzg::KzgSettings,
     proofs::{self, calculate_requests_root},
-    Block, EthereumHardforks, Header, IntoRecoveredTransaction, Receipt, EMPTY_OMMER_ROOT_HASH,
-    U256,
+    BlobTransactionSidecar, BlobTransactionValidationError, Block, EthereumHardforks, Header,
+    IntoRecoveredTransaction, Receipt, TxHash, B256, EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::StateProviderFactory;
 use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{BestTransactionsAttributes, BlobStoreError, TransactionPool};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
@@ -44,6 +42,9 @@ use revm::{
 };
 use tracing::{debug, trace, warn};
 
+mod metrics;
+use metrics::BlobQuarantineMetrics;
+
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
@@ -181,14 +182,18 @@ where
         let mut blob_gas_used = None;
 
         if chain_spec.is_cancun_active_at_timestamp(attributes.timestamp) {
+            let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
             excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
                 let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
                 let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-                Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+                Some(
+                    blob_params
+                        .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+                )
             } else {
                 // for the first post-fork block, both parent.blob_gas_used and
                 // parent.excess_blob_gas are evaluated as 0
-                Some(calculate_excess_blob_gas(0, 0))
+                Some(blob_params.next_block_excess_blob_gas(0, 0))
             };
 
             blob_gas_used = Some(0);
@@ -279,8 +284,14 @@ where
     debug!(target: "payload_builder", id=%attributes.id, parent_hash = ?parent_block.hash(), parent_number = parent_block.number, "building new payload");
     let mut cumulative_gas_used = 0;
     let mut sum_blob_gas_used = 0;
+    // the verified sidecars of the executed blob transactions
+    let mut blob_sidecars = Vec::new();
+    let is_cancun = chain_spec.is_cancun_active_at_timestamp(attributes.timestamp);
+    let blob_quarantine_metrics = BlobQuarantineMetrics::default();
     let block_gas_limit: u64 = initialized_block_env.gas_limit.try_into().unwrap_or(u64::MAX);
     let base_fee = initialized_block_env.basefee.to::<u64>();
+    let max_blob_gas_per_block =
+        chain_spec.blob_params_at_timestamp(attributes.timestamp).max_blob_gas_per_block();
 
     let mut executed_txs = Vec::new();
 
@@ -346,7 +357,7 @@ where
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
-            if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                 // we can't fit this _blob_ transaction into the block, so we mark it as
                 // invalid, which removes its dependent transactions from
                 // the iterator. This is similar to the gas limit condition
@@ -357,6 +368,33 @@ where
             }
         }
 
+        // The sidecar is verified against the versioned hashes of the transaction before the
+        // transaction is executed, a corrupted blob store or a sidecar that was replaced since the
+        // transaction was validated would otherwise result in a payload with invalid blobs. Blob
+        // transactions can't be included before Cancun, so their sidecars aren't needed.
+        let mut blob_sidecar = None;
+        if let Some(blob_tx) = tx.transaction.as_eip4844().filter(|_| is_cancun) {
+            match blob_sidecar_for_payload(
+                &pool,
+                tx.hash,
+                &blob_tx.blob_versioned_hashes,
+                initialized_cfg.kzg_settings.get(),
+            ) {
+                Ok(sidecar) => blob_sidecar = Some(sidecar),
+                Err(err) => {
+                    warn!(target: "payload_builder", tx=?tx.hash, ?err, "quarantining blob transaction with invalid sidecar");
+                    err.record(&blob_quarantine_metrics);
+                    best_txs.mark_invalid(&pool_tx);
+                    // a sidecar that couldn't be read may be valid, but a missing or mismatching
+                    // sidecar would fail every payload that includes the transaction
+                    if !matches!(err, InvalidBlobSidecar::BlobStore(_)) {
+                        pool.remove_transactions(vec![tx.hash]);
+                    }
+                    continue
+                }
+            }
+        }
+
         let env = EnvWithHandlerCfg::new_with_cfg_env(
             initialized_cfg.clone(),
             initialized_block_env.clone(),
@@ -399,9 +437,10 @@ where
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
             sum_blob_gas_used += tx_blob_gas;
+            blob_sidecars.extend(blob_sidecar);
 
             // if we've reached the max data gas per block, we can skip blob txs entirely
-            if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used == max_blob_gas_per_block {
                 best_txs.skip_blobs();
             }
         }
@@ -483,26 +522,23 @@ where
     // create the block header
     let transactions_root = proofs::calculate_transaction_root(&executed_txs);
 
-    // initialize empty blob sidecars at first. If cancun is active then this will
-    let mut blob_sidecars = Vec::new();
     let mut excess_blob_gas = None;
     let mut blob_gas_used = None;
 
     // only determine cancun fields when active
-    if chain_spec.is_cancun_active_at_timestamp(attributes.timestamp) {
-        // grab the blob sidecars from the executed txs
-        blob_sidecars = pool.get_all_blobs_exact(
-            executed_txs.iter().filter(|tx| tx.is_eip4844()).map(|tx| tx.hash).collect(),
-        )?;
-
+    if is_cancun {
+        let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
         excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
             let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
             let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-            Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+            Some(
+                blob_params
+                    .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+            )
         } else {
             // for the first post-fork block, both parent.blob_gas_used and
             // parent.excess_blob_gas are evaluated as 0
-            Some(calculate_excess_blob_gas(0, 0))
+            Some(blob_params.next_block_excess_blob_gas(0, 0))
         };
 
         blob_gas_used = Some(sum_blob_gas_used);
@@ -545,3 +581,50 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// The reason a blob transaction is quarantined instead of being included in a payload.
+#[derive(Debug)]
+enum InvalidBlobSidecar {
+    /// The sidecar is missing from the blob store.
+    Missing,
+    /// The sidecar doesn't match the versioned hashes of the transaction.
+    VersionedHashMismatch,
+    /// The KZG verification of the sidecar failed.
+    InvalidProof(BlobTransactionValidationError),
+    /// The sidecar couldn't be read from the blob store.
+    BlobStore(BlobStoreError),
+}
+
+impl InvalidBlobSidecar {
+    /// Increments the metric of this reason.
+    fn record(&self, metrics: &BlobQuarantineMetrics) {
+        match self {
+            Self::Missing => metrics.missing_sidecars.increment(1),
+            Self::VersionedHashMismatch => metrics.versioned_hash_mismatches.increment(1),
+            Self::InvalidProof(_) => metrics.invalid_proofs.increment(1),
+            Self::BlobStore(_) => metrics.blob_store_errors.increment(1),
+        }
+    }
+}
+
+/// Returns the sidecar of a blob transaction from the pool if it matches the versioned hashes of
+/// the transaction and its KZG proofs are valid.
+fn blob_sidecar_for_payload<Pool: TransactionPool>(
+    pool: &Pool,
+    tx_hash: TxHash,
+    versioned_hashes: &[B256],
+    kzg_settings: &KzgSettings,
+) -> Result<BlobTransactionSidecar, InvalidBlobSidecar> {
+    let sidecar = pool
+        .get_blob(tx_hash)
+        .map_err(InvalidBlobSidecar::BlobStore)?
+        .ok_or(InvalidBlobSidecar::Missing)?;
+    if sidecar.blobs.len() != versioned_hashes.len() ||
+        sidecar.proofs.len() != versioned_hashes.len() ||
+        !sidecar.versioned_hashes().eq(versioned_hashes.iter().copied())
+    {
+        return Err(InvalidBlobSidecar::VersionedHashMismatch)
+    }
+    sidecar.validate(versioned_hashes, kzg_settings).map_err(InvalidBlobSidecar::InvalidProof)?;
+    Ok(sidecar)
+}
@@ -0,0 +1,17 @@
+//! Metrics of the Ethereum payload builder.
+
+use reth_metrics::{metrics::Counter, Metrics};
+
+/// Metrics of the blob transactions that were quarantined instead of being included in a payload.
+#[derive(Metrics)]
+#[metrics(scope = "payloads.blob_quarantine")]
+pub(crate) struct BlobQuarantineMetrics {
+    /// Number of blob transactions whose sidecar was missing from the blob store
+    pub(crate) missing_sidecars: Counter,
+    /// Number of blob transactions whose sidecar didn't match their versioned hashes
+    pub(crate) versioned_hash_mismatches: Counter,
+    /// Number of blob transactions whose sidecar failed the KZG verification
+    pub(crate) invalid_proofs: Counter,
+    /// Number of blob transactions whose sidecar couldn't be read from the blob store
+    pub(crate) blob_store_errors: Counter,
+}
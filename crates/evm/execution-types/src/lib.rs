@@ -13,3 +13,6 @@ pub use execution_outcome::*;
 
 mod chain;
 pub use chain::*;
+
+mod state_diff;
+pub use state_diff::*;
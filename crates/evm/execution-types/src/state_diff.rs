@@ -0,0 +1,355 @@
+//! A stable, RLP based encoding of the state changes of a block.
+
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{Account, Address, BlockHash, BlockNumber, Bytes, B256, KECCAK_EMPTY, U256};
+use revm::db::BundleState;
+use std::collections::BTreeMap;
+
+/// The version of the encoding of [`BlockStateDiff`].
+///
+/// The version is bumped on every change of the encoding, decoding fails for other versions.
+pub const BLOCK_STATE_DIFF_VERSION: u8 = 1;
+
+/// The state changes of a single block.
+///
+/// The diff is encoded as the RLP list
+/// `[version, block_number, block_hash, [account_diff, ...], [contract, ...]]` where
+/// - `account_diff` is `[address, before, after, [[slot, before, after], ...]]`,
+/// - `before` and `after` of an account are `[]` if the account doesn't exist and `[[nonce,
+///   balance, code_hash]]` otherwise,
+/// - `contract` is `[code_hash, code]`.
+///
+/// Accounts are sorted by address, storage slots by slot and contracts by code hash, so every
+/// diff has exactly one encoding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockStateDiff {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: BlockHash,
+    /// The changed accounts.
+    pub accounts: Vec<AccountDiff>,
+    /// The contracts created in the block.
+    pub contracts: Vec<ContractCode>,
+}
+
+/// The changes of an account in a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The address of the account.
+    pub address: Address,
+    /// The account before the block, `None` if it didn't exist.
+    pub before: Option<Account>,
+    /// The account after the block, `None` if it doesn't exist.
+    pub after: Option<Account>,
+    /// The changed storage slots.
+    pub storage: Vec<StorageSlotDiff>,
+}
+
+/// The change of a storage slot in a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageSlotDiff {
+    /// The storage slot.
+    pub slot: B256,
+    /// The value before the block.
+    pub before: U256,
+    /// The value after the block.
+    pub after: U256,
+}
+
+/// The code of a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ContractCode {
+    /// The hash of the code.
+    pub code_hash: B256,
+    /// The code.
+    pub code: Bytes,
+}
+
+impl BlockStateDiff {
+    /// Creates the diff of a block from the bundle state of its execution.
+    ///
+    /// Only the storage slots that are part of the bundle are included, which doesn't include the
+    /// untouched slots of destroyed accounts.
+    pub fn from_bundle_state(
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        bundle: &BundleState,
+    ) -> Self {
+        let accounts = bundle
+            .state
+            .iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(slot, value)| StorageSlotDiff {
+                        slot: B256::from(*slot),
+                        before: value.original_value(),
+                        after: value.present_value(),
+                    })
+                    .collect();
+                AccountDiff {
+                    address: *address,
+                    before: account.original_info.clone().map(Into::into),
+                    after: account.info.clone().map(Into::into),
+                    storage,
+                }
+            })
+            .collect();
+        let contracts = bundle
+            .contracts
+            .iter()
+            .map(|(code_hash, code)| ContractCode {
+                code_hash: *code_hash,
+                code: code.original_bytes(),
+            })
+            .collect();
+        Self { block_number, block_hash, accounts, contracts }.sorted()
+    }
+
+    /// Sorts the accounts, storage slots and contracts into the canonical order of the encoding.
+    pub fn sorted(mut self) -> Self {
+        self.accounts.sort_unstable_by_key(|account| account.address);
+        for account in &mut self.accounts {
+            account.storage.sort_unstable_by_key(|slot| slot.slot);
+        }
+        self.contracts.sort_unstable_by_key(|contract| contract.code_hash);
+        self
+    }
+}
+
+/// The encoding of an [`Account`], without the distinction between an empty and no code hash.
+#[derive(RlpEncodable, RlpDecodable)]
+struct RlpAccount {
+    nonce: u64,
+    balance: U256,
+    code_hash: B256,
+}
+
+impl From<&Account> for RlpAccount {
+    fn from(account: &Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        }
+    }
+}
+
+impl From<RlpAccount> for Account {
+    fn from(account: RlpAccount) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            bytecode_hash: (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash),
+        }
+    }
+}
+
+/// The encoding of an [`AccountDiff`], optional accounts are encoded as lists with at most one
+/// element.
+#[derive(RlpEncodable, RlpDecodable)]
+struct RlpAccountDiff {
+    address: Address,
+    before: Vec<RlpAccount>,
+    after: Vec<RlpAccount>,
+    storage: Vec<StorageSlotDiff>,
+}
+
+#[derive(RlpEncodable, RlpDecodable)]
+struct RlpBlockStateDiff {
+    version: u8,
+    block_number: BlockNumber,
+    block_hash: BlockHash,
+    accounts: Vec<RlpAccountDiff>,
+    contracts: Vec<ContractCode>,
+}
+
+impl From<&BlockStateDiff> for RlpBlockStateDiff {
+    fn from(diff: &BlockStateDiff) -> Self {
+        Self {
+            version: BLOCK_STATE_DIFF_VERSION,
+            block_number: diff.block_number,
+            block_hash: diff.block_hash,
+            accounts: diff
+                .accounts
+                .iter()
+                .map(|account| RlpAccountDiff {
+                    address: account.address,
+                    before: account.before.iter().map(Into::into).collect(),
+                    after: account.after.iter().map(Into::into).collect(),
+                    storage: account.storage.clone(),
+                })
+                .collect(),
+            contracts: diff.contracts.clone(),
+        }
+    }
+}
+
+/// Decodes an optional account from a list with at most one element.
+fn decode_account(mut accounts: Vec<RlpAccount>) -> alloy_rlp::Result<Option<Account>> {
+    if accounts.len() > 1 {
+        return Err(alloy_rlp::Error::Custom("more than one account state"))
+    }
+    Ok(accounts.pop().map(Into::into))
+}
+
+impl Encodable for BlockStateDiff {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        RlpBlockStateDiff::from(self).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        RlpBlockStateDiff::from(self).length()
+    }
+}
+
+impl Decodable for BlockStateDiff {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let diff = RlpBlockStateDiff::decode(buf)?;
+        if diff.version != BLOCK_STATE_DIFF_VERSION {
+            return Err(alloy_rlp::Error::Custom("unsupported state diff version"))
+        }
+        let accounts = diff
+            .accounts
+            .into_iter()
+            .map(|account| {
+                Ok(AccountDiff {
+                    address: account.address,
+                    before: decode_account(account.before)?,
+                    after: decode_account(account.after)?,
+                    storage: account.storage,
+                })
+            })
+            .collect::<alloy_rlp::Result<_>>()?;
+        Ok(Self {
+            block_number: diff.block_number,
+            block_hash: diff.block_hash,
+            accounts,
+            contracts: diff.contracts,
+        })
+    }
+}
+
+/// Collects the account and storage changesets of a block into a [`BlockStateDiff`].
+///
+/// The changesets only contain the values before the block, the values after the block are
+/// looked up when the diff is built.
+#[derive(Debug, Default)]
+pub struct BlockStateDiffBuilder {
+    /// The accounts before the block, `None` if only the storage of the account changed.
+    accounts: BTreeMap<Address, (Option<Option<Account>>, BTreeMap<B256, U256>)>,
+}
+
+impl BlockStateDiffBuilder {
+    /// Records the state of the account before the block.
+    pub fn account_before(&mut self, address: Address, before: Option<Account>) {
+        self.accounts.entry(address).or_default().0.get_or_insert(before);
+    }
+
+    /// Records the value of the storage slot before the block.
+    pub fn storage_before(&mut self, address: Address, slot: B256, before: U256) {
+        self.accounts.entry(address).or_default().1.entry(slot).or_insert(before);
+    }
+
+    /// Builds the diff with the states after the block.
+    ///
+    /// The closures return the account and the value of a storage slot after the block, and the
+    /// code of a contract by its hash. The code of every account whose code hash changed is
+    /// included in the diff.
+    pub fn build<E>(
+        self,
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        mut account_after: impl FnMut(Address) -> Result<Option<Account>, E>,
+        mut storage_after: impl FnMut(Address, B256) -> Result<U256, E>,
+        mut code: impl FnMut(B256) -> Result<Option<Bytes>, E>,
+    ) -> Result<BlockStateDiff, E> {
+        let mut accounts = Vec::with_capacity(self.accounts.len());
+        let mut contracts = BTreeMap::new();
+        for (address, (before, storage)) in self.accounts {
+            let after = account_after(address)?;
+            // accounts without a changeset entry only changed their storage
+            let before = before.unwrap_or(after);
+
+            let code_hash = after.and_then(|account| account.bytecode_hash);
+            if let Some(code_hash) = code_hash {
+                if before.and_then(|account| account.bytecode_hash) != Some(code_hash) {
+                    if let Some(code) = code(code_hash)? {
+                        contracts.insert(code_hash, code);
+                    }
+                }
+            }
+
+            let storage = storage
+                .into_iter()
+                .map(|(slot, before)| {
+                    Ok(StorageSlotDiff { slot, before, after: storage_after(address, slot)? })
+                })
+                .collect::<Result<_, E>>()?;
+            accounts.push(AccountDiff { address, before, after, storage });
+        }
+
+        Ok(BlockStateDiff {
+            block_number,
+            block_hash,
+            accounts,
+            contracts: contracts
+                .into_iter()
+                .map(|(code_hash, code)| ContractCode { code_hash, code })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{address, b256, bytes};
+    use std::convert::Infallible;
+
+    #[test]
+    fn block_state_diff_roundtrip() {
+        let code_hash = b256!("0000000000000000000000000000000000000000000000000000000000000c0d");
+        let mut builder = BlockStateDiffBuilder::default();
+        let created = address!("0000000000000000000000000000000000000002");
+        let updated = address!("0000000000000000000000000000000000000001");
+        builder.account_before(created, None);
+        builder.storage_before(updated, B256::with_last_byte(2), U256::from(7));
+        builder.storage_before(updated, B256::with_last_byte(1), U256::ZERO);
+        builder.storage_before(updated, B256::with_last_byte(1), U256::from(5));
+
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: Some(code_hash) };
+        let diff = builder
+            .build::<Infallible>(
+                1,
+                B256::with_last_byte(1),
+                |_| Ok(Some(account)),
+                |_, _| Ok(U256::from(9)),
+                |_| Ok(Some(bytes!("6000"))),
+            )
+            .unwrap();
+        assert_eq!(diff.accounts[0].address, updated);
+        assert_eq!(diff.accounts[0].before, Some(account));
+        assert_eq!(
+            diff.accounts[0].storage[0],
+            StorageSlotDiff {
+                slot: B256::with_last_byte(1),
+                before: U256::ZERO,
+                after: U256::from(9)
+            }
+        );
+        assert_eq!(diff.accounts[1].before, None);
+        assert_eq!(diff.contracts, vec![ContractCode { code_hash, code: bytes!("6000") }]);
+
+        let encoded = alloy_rlp::encode(&diff);
+        assert_eq!(BlockStateDiff::decode(&mut encoded.as_slice()).unwrap(), diff);
+
+        let mut unsupported = RlpBlockStateDiff::from(&diff);
+        unsupported.version = BLOCK_STATE_DIFF_VERSION + 1;
+        let encoded = alloy_rlp::encode(&unsupported);
+        assert!(BlockStateDiff::decode(&mut encoded.as_slice()).is_err());
+    }
+}
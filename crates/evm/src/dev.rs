@@ -0,0 +1,287 @@
+//! State overrides of the dev chain.
+//!
+//! In dev mode, accounts can be changed over RPC without mining a block, e.g. with
+//! `anvil_setBalance`, and transactions can be sent on behalf of impersonated accounts. Both are
+//! kept by a [`DevState`] that the node shares between its state providers and its executor. The
+//! latest and pending state show the overrides right away, and the executor applies them before
+//! the transactions of the next mined block, so that they become part of the state of the chain.
+//!
+//! The overrides and impersonated senders are not part of the mined blocks, so these blocks can
+//! only be re-executed by the dev node that mined them.
+
+use parking_lot::RwLock;
+use reth_primitives::{Address, BlockNumber, BlockWithSenders, Bytes, TxHash, B256, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm::{Database, DatabaseCommit};
+use revm_primitives::{Account, AccountInfo, Bytecode, EvmStorageSlot, HashMap, KECCAK_EMPTY};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+/// The overridden fields of an account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account, empty if the code is removed.
+    pub code: Option<Bytes>,
+    /// The new values of storage slots of the account.
+    pub storage: BTreeMap<B256, U256>,
+}
+
+impl AccountOverride {
+    /// Returns the account with the overridden fields applied.
+    pub fn apply(&self, account: Option<reth_primitives::Account>) -> reth_primitives::Account {
+        let mut account = account.unwrap_or_default();
+        if let Some(balance) = self.balance {
+            account.balance = balance;
+        }
+        if let Some(nonce) = self.nonce {
+            account.nonce = nonce;
+        }
+        if let Some(code) = self.bytecode() {
+            account.bytecode_hash = code.map(|code| code.hash_slow());
+        }
+        account
+    }
+
+    /// Returns the overridden code of the account: `None` if the code isn't overridden, and
+    /// `Some(None)` if it's removed.
+    pub fn bytecode(&self) -> Option<Option<Bytecode>> {
+        self.code.as_ref().map(|code| (!code.is_empty()).then(|| Bytecode::new_raw(code.clone())))
+    }
+}
+
+/// The overrides and impersonated accounts of the dev chain, shared by all clones.
+#[derive(Debug, Clone, Default)]
+pub struct DevState {
+    inner: Arc<RwLock<DevStateInner>>,
+}
+
+#[derive(Debug, Default)]
+struct DevStateInner {
+    /// The overridden accounts.
+    overrides: BTreeMap<Address, AccountOverride>,
+    /// The block the overrides are applied in, once it is built.
+    block: Option<BlockNumber>,
+    /// The impersonated accounts.
+    impersonated: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    auto_impersonate: bool,
+    /// The senders of the transactions signed on behalf of impersonated accounts.
+    impersonated_senders: HashMap<TxHash, Address>,
+}
+
+impl DevState {
+    /// Sets the balance of the account.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.inner.write().overrides.entry(address).or_default().balance = Some(balance);
+    }
+
+    /// Sets the nonce of the account.
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.inner.write().overrides.entry(address).or_default().nonce = Some(nonce);
+    }
+
+    /// Sets the code of the account. Empty code removes the code of the account.
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.inner.write().overrides.entry(address).or_default().code = Some(code);
+    }
+
+    /// Sets the value of a storage slot of the account.
+    pub fn set_storage_at(&self, address: Address, slot: B256, value: U256) {
+        self.inner.write().overrides.entry(address).or_default().storage.insert(slot, value);
+    }
+
+    /// Returns the overridden fields of the account, if any.
+    pub fn account_override(&self, address: &Address) -> Option<AccountOverride> {
+        self.inner.read().overrides.get(address).cloned()
+    }
+
+    /// Returns the overridden value of the storage slot, if any.
+    pub fn storage_override(&self, address: &Address, slot: &B256) -> Option<U256> {
+        self.inner.read().overrides.get(address)?.storage.get(slot).copied()
+    }
+
+    /// Returns the overridden code with the given hash, if any.
+    pub fn bytecode_by_hash(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.inner
+            .read()
+            .overrides
+            .values()
+            .filter_map(|account| account.bytecode().flatten())
+            .find(|code| code.hash_slow() == *code_hash)
+    }
+
+    /// Returns all overridden accounts, e.g. to restore them with [`Self::set_overrides`].
+    pub fn overrides(&self) -> BTreeMap<Address, AccountOverride> {
+        self.inner.read().overrides.clone()
+    }
+
+    /// Replaces all overridden accounts.
+    pub fn set_overrides(&self, overrides: BTreeMap<Address, AccountOverride>) {
+        let mut inner = self.inner.write();
+        inner.overrides = overrides;
+        inner.block = None;
+    }
+
+    /// Applies the current overrides in the block with the given number, when it is executed.
+    ///
+    /// The overrides must not change until the block is canonical, see
+    /// [`Self::on_canonical_block`].
+    pub fn apply_in_block(&self, number: BlockNumber) {
+        let mut inner = self.inner.write();
+        inner.block = (!inner.overrides.is_empty()).then_some(number);
+    }
+
+    /// Removes the overrides once the block that applied them is canonical, since the state of
+    /// the chain includes them from then on.
+    pub fn on_canonical_block(&self, number: BlockNumber) {
+        let mut inner = self.inner.write();
+        if inner.block == Some(number) {
+            inner.overrides.clear();
+            inner.block = None;
+        }
+    }
+
+    /// Applies the overrides to the state, if the block is the one they are applied in.
+    ///
+    /// The overrides are committed like the changes of a transaction, so that they are part of
+    /// the state transitions of the block.
+    pub fn apply_overrides<DB>(&self, db: &mut DB, number: BlockNumber) -> Result<(), ProviderError>
+    where
+        DB: Database<Error: Into<ProviderError>> + DatabaseCommit,
+    {
+        let inner = self.inner.read();
+        if inner.block != Some(number) {
+            return Ok(())
+        }
+
+        for (address, change) in &inner.overrides {
+            let info = db.basic(*address).map_err(Into::into)?.unwrap_or_default();
+            let code = match change.bytecode() {
+                Some(code) => code,
+                None if info.code_hash == KECCAK_EMPTY => None,
+                None => Some(match info.code {
+                    Some(code) => code,
+                    None => db.code_by_hash(info.code_hash).map_err(Into::into)?,
+                }),
+            };
+            let mut account = Account::from(AccountInfo {
+                balance: change.balance.unwrap_or(info.balance),
+                nonce: change.nonce.unwrap_or(info.nonce),
+                code_hash: code.as_ref().map_or(KECCAK_EMPTY, Bytecode::hash_slow),
+                code,
+            });
+            for (slot, value) in &change.storage {
+                let slot = U256::from_be_bytes(slot.0);
+                let original = db.storage(*address, slot).map_err(Into::into)?;
+                account.storage.insert(slot, EvmStorageSlot::new_changed(original, *value));
+            }
+
+            account.mark_touch();
+            db.commit(HashMap::from([(*address, account)]));
+        }
+
+        Ok(())
+    }
+
+    /// Impersonates the account, so that transactions can be sent on its behalf.
+    pub fn impersonate(&self, address: Address) {
+        self.inner.write().impersonated.insert(address);
+    }
+
+    /// Stops impersonating the account.
+    pub fn stop_impersonating(&self, address: &Address) {
+        self.inner.write().impersonated.remove(address);
+    }
+
+    /// Configures whether all accounts are impersonated.
+    pub fn set_auto_impersonate(&self, enabled: bool) {
+        self.inner.write().auto_impersonate = enabled;
+    }
+
+    /// Returns `true` if the account is impersonated.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        let inner = self.inner.read();
+        inner.auto_impersonate || inner.impersonated.contains(address)
+    }
+
+    /// Records the sender of a transaction signed on behalf of an impersonated account.
+    ///
+    /// The signature of the transaction doesn't recover to the impersonated account, so the
+    /// executor uses the recorded sender instead.
+    pub fn record_impersonated_transaction(&self, hash: TxHash, sender: Address) {
+        self.inner.write().impersonated_senders.insert(hash, sender);
+    }
+
+    /// Returns the block with the senders of transactions sent on behalf of impersonated
+    /// accounts, or `None` if the block has no such transactions.
+    pub fn with_impersonated_senders(&self, block: &BlockWithSenders) -> Option<BlockWithSenders> {
+        let inner = self.inner.read();
+        if inner.impersonated_senders.is_empty() {
+            return None
+        }
+
+        let mut senders = block.senders.clone();
+        let mut impersonated = false;
+        for (sender, transaction) in senders.iter_mut().zip(&block.body) {
+            if let Some(impersonated_sender) = inner.impersonated_senders.get(&transaction.hash) {
+                *sender = *impersonated_sender;
+                impersonated = true;
+            }
+        }
+        impersonated.then(|| BlockWithSenders { block: block.block.clone(), senders })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{states::bundle_state::BundleRetention, EmptyDB},
+        State,
+    };
+
+    #[test]
+    fn apply_overrides_in_block() {
+        let address = Address::with_last_byte(1);
+        let dev_state = DevState::default();
+        dev_state.set_balance(address, U256::from(10));
+        dev_state.set_nonce(address, 1);
+        dev_state.set_code(address, Bytes::from_static(&[0x60, 0x00]));
+        dev_state.set_storage_at(address, B256::with_last_byte(1), U256::from(2));
+
+        let account = dev_state.account_override(&address).unwrap().apply(None);
+        assert_eq!(account.balance, U256::from(10));
+        assert_eq!(
+            dev_state.storage_override(&address, &B256::with_last_byte(1)),
+            Some(U256::from(2))
+        );
+        assert!(dev_state.bytecode_by_hash(&account.bytecode_hash.unwrap()).is_some());
+
+        let mut state =
+            State::builder().with_database(EmptyDB::default()).with_bundle_update().build();
+        // the overrides are only applied in the block they were assigned to
+        dev_state.apply_overrides(&mut state, 1).unwrap();
+        dev_state.apply_in_block(2);
+        dev_state.apply_overrides(&mut state, 2).unwrap();
+        state.merge_transitions(BundleRetention::Reverts);
+        let bundle = state.take_bundle();
+
+        let account = bundle.account(&address).unwrap();
+        let info = account.info.as_ref().unwrap();
+        assert_eq!(info.balance, U256::from(10));
+        assert_eq!(info.nonce, 1);
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(2)));
+        assert!(bundle.bytecode(&info.code_hash).is_some());
+
+        dev_state.on_canonical_block(1);
+        assert!(dev_state.account_override(&address).is_some());
+        dev_state.on_canonical_block(2);
+        assert!(dev_state.account_override(&address).is_none());
+    }
+}
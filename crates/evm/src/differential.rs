@@ -0,0 +1,301 @@
+//! Executor that executes blocks with two executors and compares their outputs.
+
+use crate::execute::{BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::{Address, BlockWithSenders, Receipt, B256, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm_primitives::{db::Database, AccountInfo, Bytecode};
+use std::{
+    fmt::{self, Display},
+    sync::{Arc, Mutex, PoisonError},
+};
+use tracing::error;
+
+/// How a [`DifferentialExecutorProvider`] reacts to a block whose execution outputs differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifferentialMode {
+    /// Logs the mismatch and continues with the output of the primary executor.
+    Log,
+    /// Fails the execution of the block with an [`ExecutorMismatch`] error.
+    Halt,
+}
+
+/// Error when the execution outputs of the executors of a [`DifferentialExecutorProvider`]
+/// differ.
+///
+/// This is not a block validation error, since either executor may be wrong about a valid block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutorMismatch(pub String);
+
+impl Display for ExecutorMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "executors disagree on the execution of the block: {}", self.0)
+    }
+}
+
+impl std::error::Error for ExecutorMismatch {}
+
+/// A [`BlockExecutorProvider`] that executes sampled blocks with a secondary executor in addition
+/// to the primary executor and compares their outputs.
+///
+/// This is used to validate an alternative EVM implementation, e.g. a new revm release or an
+/// experimental interpreter, against the trusted one with live blocks. The output of the primary
+/// executor is always returned. It isn't exposed by the node, since revm is the only EVM backend
+/// so far.
+///
+/// Only single blocks executed with [`BlockExecutorProvider::executor`] are compared. Batch
+/// executors only use the primary executor, the secondary executor would have to execute every
+/// block of the batch to track the state.
+#[derive(Debug, Clone)]
+pub struct DifferentialExecutorProvider<A, B> {
+    /// The executor provider whose outputs are returned.
+    primary: A,
+    /// The executor provider that is checked against the primary executor provider.
+    secondary: B,
+    /// Blocks whose number is a multiple of the interval are executed with both executors.
+    sample_interval: u64,
+    /// How mismatches are handled.
+    mode: DifferentialMode,
+}
+
+impl<A, B> DifferentialExecutorProvider<A, B> {
+    /// Creates a new provider that compares the outputs of all blocks and logs mismatches.
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary, sample_interval: 1, mode: DifferentialMode::Log }
+    }
+
+    /// Only compares the outputs of blocks whose number is a multiple of the interval.
+    pub fn with_sample_interval(mut self, sample_interval: u64) -> Self {
+        self.sample_interval = sample_interval.max(1);
+        self
+    }
+
+    /// Sets how mismatches are handled.
+    pub const fn with_mode(mut self, mode: DifferentialMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl<A, B> BlockExecutorProvider for DifferentialExecutorProvider<A, B>
+where
+    A: BlockExecutorProvider,
+    B: BlockExecutorProvider,
+{
+    type Executor<DB: Database<Error: Into<ProviderError> + Display>> =
+        DifferentialExecutor<A::Executor<SharedDatabase<DB>>, B::Executor<SharedDatabase<DB>>>;
+
+    type BatchExecutor<DB: Database<Error: Into<ProviderError> + Display>> = A::BatchExecutor<DB>;
+
+    fn executor<DB>(&self, db: DB) -> Self::Executor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        let db = SharedDatabase::new(db);
+        DifferentialExecutor {
+            primary: self.primary.executor(db.clone()),
+            secondary: self.secondary.executor(db),
+            sample_interval: self.sample_interval,
+            mode: self.mode,
+        }
+    }
+
+    fn batch_executor<DB>(&self, db: DB) -> Self::BatchExecutor<DB>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        self.primary.batch_executor(db)
+    }
+}
+
+/// The executor of a [`DifferentialExecutorProvider`].
+#[derive(Debug)]
+pub struct DifferentialExecutor<A, B> {
+    primary: A,
+    secondary: B,
+    sample_interval: u64,
+    mode: DifferentialMode,
+}
+
+impl<A, B, DB> Executor<DB> for DifferentialExecutor<A, B>
+where
+    A: for<'a> Executor<
+        SharedDatabase<DB>,
+        Input<'a> = BlockExecutionInput<'a, BlockWithSenders>,
+        Output = BlockExecutionOutput<Receipt>,
+        Error = BlockExecutionError,
+    >,
+    B: for<'a> Executor<
+        SharedDatabase<DB>,
+        Input<'a> = BlockExecutionInput<'a, BlockWithSenders>,
+        Output = BlockExecutionOutput<Receipt>,
+        Error = BlockExecutionError,
+    >,
+    DB: Database<Error: Into<ProviderError> + Display>,
+{
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = BlockExecutionOutput<Receipt>;
+    type Error = BlockExecutionError;
+
+    fn execute(self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
+        let BlockExecutionInput { block, total_difficulty } = input;
+        let primary = self.primary.execute(BlockExecutionInput::new(block, total_difficulty));
+        if block.number % self.sample_interval != 0 {
+            return primary
+        }
+
+        let secondary = self.secondary.execute(BlockExecutionInput::new(block, total_difficulty));
+        if let Some(mismatch) = compare_outputs(&primary, &secondary) {
+            match self.mode {
+                DifferentialMode::Log => {
+                    error!(target: "evm::differential", number = block.number, hash = %block.hash_slow(), %mismatch, "EVM backends disagree on the execution of the block")
+                }
+                DifferentialMode::Halt => {
+                    return Err(BlockExecutionError::other(ExecutorMismatch(mismatch)))
+                }
+            }
+        }
+        primary
+    }
+}
+
+/// Returns a description of the first difference between the execution outputs, if any.
+fn compare_outputs(
+    primary: &Result<BlockExecutionOutput<Receipt>, BlockExecutionError>,
+    secondary: &Result<BlockExecutionOutput<Receipt>, BlockExecutionError>,
+) -> Option<String> {
+    let (primary, secondary) = match (primary, secondary) {
+        (Ok(primary), Ok(secondary)) => (primary, secondary),
+        (Ok(_), Err(err)) => return Some(format!("only the secondary executor failed: {err}")),
+        (Err(err), Ok(_)) => return Some(format!("only the primary executor failed: {err}")),
+        (Err(primary), Err(secondary)) => {
+            let (primary, secondary) = (primary.to_string(), secondary.to_string());
+            return (primary != secondary)
+                .then(|| format!("errors differ: {primary} != {secondary}"))
+        }
+    };
+
+    if primary.gas_used != secondary.gas_used {
+        return Some(format!("gas used {} != {}", primary.gas_used, secondary.gas_used))
+    }
+    if primary.receipts.len() != secondary.receipts.len() {
+        return Some(format!(
+            "number of receipts {} != {}",
+            primary.receipts.len(),
+            secondary.receipts.len()
+        ))
+    }
+    if let Some(idx) = primary.receipts.iter().zip(&secondary.receipts).position(|(a, b)| a != b) {
+        return Some(format!("receipt {idx} differs"))
+    }
+    if primary.requests != secondary.requests {
+        return Some("requests differ".to_string())
+    }
+    if primary.state != secondary.state {
+        let changed = primary
+            .state
+            .state
+            .iter()
+            .find(|(address, account)| secondary.state.state.get(*address) != Some(*account));
+        return Some(match changed {
+            Some((address, _)) => format!("state of account {address} differs"),
+            None => "state differs".to_string(),
+        })
+    }
+    None
+}
+
+/// A database that is shared by the executors of a [`DifferentialExecutorProvider`].
+///
+/// Execution only reads from the database, the changes of each executor are kept in its own
+/// state.
+#[derive(Debug)]
+pub struct SharedDatabase<DB>(Arc<Mutex<DB>>);
+
+impl<DB> SharedDatabase<DB> {
+    fn new(db: DB) -> Self {
+        Self(Arc::new(Mutex::new(db)))
+    }
+}
+
+impl<DB> Clone for SharedDatabase<DB> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<DB: Database> Database for SharedDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutorProvider;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{Block, Header};
+    use revm::db::EmptyDB;
+
+    fn provider(cumulative_gas_used: u64) -> MockExecutorProvider {
+        let provider = MockExecutorProvider::default();
+        let receipt = Receipt { cumulative_gas_used, ..Default::default() };
+        provider.extend([ExecutionOutcome {
+            receipts: vec![vec![Some(receipt)]].into(),
+            ..Default::default()
+        }]);
+        provider
+    }
+
+    fn block(number: u64) -> BlockWithSenders {
+        BlockWithSenders {
+            block: Block { header: Header { number, ..Default::default() }, ..Default::default() },
+            senders: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn halts_on_sampled_mismatch() {
+        let executor = || {
+            DifferentialExecutorProvider::new(provider(1), provider(2))
+                .with_sample_interval(2)
+                .with_mode(DifferentialMode::Halt)
+        };
+
+        let unsampled = block(1);
+        let sampled = block(2);
+        assert!(executor()
+            .executor(EmptyDB::default())
+            .execute((&unsampled, U256::ZERO).into())
+            .is_ok());
+
+        let err = executor()
+            .executor(EmptyDB::default())
+            .execute((&sampled, U256::ZERO).into())
+            .unwrap_err();
+        let BlockExecutionError::Other(err) = err else { panic!("expected an internal error") };
+        assert_eq!(err.downcast_ref(), Some(&ExecutorMismatch("receipt 0 differs".to_string())));
+
+        let executor = DifferentialExecutorProvider::new(provider(1), provider(1))
+            .with_mode(DifferentialMode::Halt);
+        assert!(executor
+            .executor(EmptyDB::default())
+            .execute((&sampled, U256::ZERO).into())
+            .is_ok());
+    }
+}
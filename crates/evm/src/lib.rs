@@ -20,9 +20,14 @@ use reth_primitives::{
 };
 use revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 use revm_primitives::{
-    BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, SpecId, TxEnv,
+    BlobExcessGasAndPrice, BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, SpecId,
+    TxEnv,
 };
 
+#[cfg(feature = "std")]
+pub mod dev;
+#[cfg(feature = "std")]
+pub mod differential;
 pub mod either;
 pub mod execute;
 pub mod noop;
@@ -145,12 +150,15 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
         after_merge: bool,
     ) {
         let coinbase = block_coinbase(chain_spec, header, after_merge);
-        Self::fill_block_env_with_coinbase(block_env, header, after_merge, coinbase);
+        Self::fill_block_env_with_coinbase(block_env, chain_spec, header, after_merge, coinbase);
     }
 
     /// Fill block environment with coinbase.
+    ///
+    /// The blob gas price is computed with the blob parameters of the chain spec at the block.
     fn fill_block_env_with_coinbase(
         block_env: &mut BlockEnv,
+        chain_spec: &ChainSpec,
         header: &Header,
         after_merge: bool,
         coinbase: Address,
@@ -170,7 +178,11 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
 
         // EIP-4844 excess blob gas of this block, introduced in Cancun
         if let Some(excess_blob_gas) = header.excess_blob_gas {
-            block_env.set_blob_excess_gas_and_price(excess_blob_gas);
+            let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp);
+            block_env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.blob_fee(excess_blob_gas),
+            });
         }
     }
 
@@ -187,6 +199,7 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
         let after_merge = cfg.handler_cfg.spec_id >= SpecId::MERGE;
         Self::fill_block_env_with_coinbase(
             block_env,
+            chain_spec,
             header,
             after_merge,
             block_coinbase(chain_spec, header, after_merge),
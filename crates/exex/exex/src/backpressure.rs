@@ -0,0 +1,41 @@
+/// How the [`ExExManager`](crate::ExExManager) treats an `ExEx` that falls behind the
+/// notifications.
+///
+/// The manager keeps the notifications in a buffer shared by all `ExEx`'s until every `ExEx`
+/// received them. While the buffer is full, the manager accepts no new notifications, which stalls
+/// the pipeline and the advancement of the canonical chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExBackpressurePolicy {
+    /// The `ExEx` holds its notifications in the shared buffer, however far it lags behind.
+    ///
+    /// A slow `ExEx` stalls the canonical chain.
+    #[default]
+    Block,
+    /// Once the `ExEx` lags behind by more than `max_lag` notifications, its pending
+    /// notifications are moved out of the shared buffer into a buffer of its own.
+    ///
+    /// The `ExEx` still receives every notification, but the buffer of the `ExEx` is unbounded.
+    Buffer {
+        /// The number of notifications the `ExEx` may lag behind in the shared buffer.
+        max_lag: usize,
+    },
+    /// Once the `ExEx` lags behind by more than `max_lag` notifications, its pending
+    /// notifications are dropped and it receives a
+    /// [`ExExNotification::NotificationsSkipped`](crate::ExExNotification::NotificationsSkipped)
+    /// instead.
+    Skip {
+        /// The number of notifications the `ExEx` may lag behind in the shared buffer.
+        max_lag: usize,
+    },
+}
+
+impl ExExBackpressurePolicy {
+    /// Returns the number of notifications the `ExEx` may lag behind in the shared buffer, `None`
+    /// if there is no limit.
+    pub const fn max_lag(&self) -> Option<usize> {
+        match self {
+            Self::Block => None,
+            Self::Buffer { max_lag } | Self::Skip { max_lag } => Some(*max_lag),
+        }
+    }
+}
@@ -1,7 +1,8 @@
+use crate::ExExNotificationFilter;
 use reth_primitives::BlockNumber;
 
 /// Events emitted by an `ExEx`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExExEvent {
     /// Highest block processed by the `ExEx`.
     ///
@@ -10,4 +11,9 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumber),
+    /// Filter for the parts of the chain the `ExEx` is interested in.
+    ///
+    /// The filter replaces any previous filter and applies to all notifications that are sent to
+    /// the `ExEx` after the event was received by the manager.
+    SetNotificationFilter(ExExNotificationFilter),
 }
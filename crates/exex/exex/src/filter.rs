@@ -0,0 +1,266 @@
+use crate::ExExNotification;
+use reth_primitives::{
+    Address, Receipt, SealedBlock, SealedBlockWithSenders, TransactionSigned, TxType, B256,
+};
+use reth_provider::{Chain, ExecutionOutcome};
+use reth_revm::db::{states::reverts::Reverts, BundleAccount, BundleState};
+use std::{collections::HashSet, sync::Arc};
+
+/// A filter for the parts of the chain an `ExEx` is interested in.
+///
+/// An `ExEx` registers its filter with
+/// [`ExExEvent::SetNotificationFilter`](crate::ExExEvent::SetNotificationFilter). The
+/// [`ExExManager`](crate::ExExManager) then applies the filter to every notification before it is
+/// sent to the `ExEx`:
+///
+/// - Blocks only keep the transactions that match the filter, together with their senders and
+///   receipts. Headers are kept for all blocks, so the range of the chain doesn't change.
+/// - The state changes only keep the accounts of the filtered addresses.
+/// - Trie updates are dropped.
+///
+/// The filtered blocks are therefore not valid blocks: the body no longer matches the
+/// `transactions_root` and the receipts no longer match the `receipts_root` of the header, and the
+/// position of a kept transaction in the body is not its transaction index in the block. Since the
+/// receipts of the dropped transactions are missing, neither the log index of a log nor the gas
+/// used by a transaction can be derived from the cumulative gas used of the receipts. `ExEx`es that
+/// need those have to read the block from the provider of their
+/// [`ExExContext`](crate::ExExContext).
+///
+/// A transaction matches if it matches all criteria of the filter. Criteria without any values
+/// match every transaction, so an empty filter leaves notifications untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExExNotificationFilter {
+    /// Addresses a transaction has to be sent from or to, or emit a log from.
+    addresses: HashSet<Address>,
+    /// Topics one of the logs of a transaction has to contain.
+    topics: HashSet<B256>,
+    /// Types a transaction has to be of.
+    tx_types: HashSet<TxType>,
+}
+
+impl ExExNotificationFilter {
+    /// Only keeps transactions sent from or to one of the addresses, or that emitted a log from
+    /// one of the addresses, and only keeps the state changes of the addresses.
+    pub fn with_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.addresses.extend(addresses);
+        self
+    }
+
+    /// Only keeps transactions that emitted a log with one of the topics.
+    pub fn with_topics(mut self, topics: impl IntoIterator<Item = B256>) -> Self {
+        self.topics.extend(topics);
+        self
+    }
+
+    /// Only keeps transactions of one of the types.
+    pub fn with_tx_types(mut self, tx_types: impl IntoIterator<Item = TxType>) -> Self {
+        self.tx_types.extend(tx_types);
+        self
+    }
+
+    /// Returns `true` if the filter has no criteria and matches everything.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty() && self.tx_types.is_empty()
+    }
+
+    /// Returns `true` if the transaction, sent by the given sender, matches the filter.
+    ///
+    /// Logs are read from the receipt. If the receipt was pruned, only the sender and recipient
+    /// can match the addresses, and the topics never match.
+    pub fn matches_transaction(
+        &self,
+        transaction: &TransactionSigned,
+        sender: Address,
+        receipt: Option<&Receipt>,
+    ) -> bool {
+        let logs = receipt.map(|receipt| receipt.logs.as_slice()).unwrap_or_default();
+
+        let matches_tx_type =
+            self.tx_types.is_empty() || self.tx_types.contains(&transaction.tx_type());
+        let matches_address = self.addresses.is_empty() ||
+            self.addresses.contains(&sender) ||
+            transaction.to().is_some_and(|to| self.addresses.contains(&to)) ||
+            logs.iter().any(|log| self.addresses.contains(&log.address));
+        let matches_topic = self.topics.is_empty() ||
+            logs.iter().any(|log| log.topics().iter().any(|topic| self.topics.contains(topic)));
+
+        matches_tx_type && matches_address && matches_topic
+    }
+
+    /// Applies the filter to the chains of the notification.
+    pub fn filter_notification(&self, notification: &ExExNotification) -> ExExNotification {
+        if self.is_empty() {
+            return notification.clone()
+        }
+
+        match notification {
+            ExExNotification::ChainCommitted { new } => {
+                ExExNotification::ChainCommitted { new: self.filter_chain(new) }
+            }
+            ExExNotification::ChainReorged { old, new } => ExExNotification::ChainReorged {
+                old: self.filter_chain(old),
+                new: self.filter_chain(new),
+            },
+            ExExNotification::ChainReverted { old } => {
+                ExExNotification::ChainReverted { old: self.filter_chain(old) }
+            }
+            ExExNotification::NotificationsSkipped { count } => {
+                ExExNotification::NotificationsSkipped { count: *count }
+            }
+        }
+    }
+
+    /// Returns a chain with only the parts of the given chain that match the filter.
+    pub fn filter_chain(&self, chain: &Chain) -> Arc<Chain> {
+        let mut receipts = Vec::with_capacity(chain.len());
+        let blocks = chain
+            .blocks_and_receipts()
+            .map(|(block, block_receipts)| {
+                let mut transactions = Vec::new();
+                let mut senders = Vec::new();
+                let mut kept_receipts = Vec::new();
+                for ((transaction, sender), receipt) in
+                    block.body.iter().zip(&block.senders).zip(block_receipts)
+                {
+                    if self.matches_transaction(transaction, *sender, receipt.as_ref()) {
+                        transactions.push(transaction.clone());
+                        senders.push(*sender);
+                        kept_receipts.push(receipt.clone());
+                    }
+                }
+                receipts.push(kept_receipts);
+
+                SealedBlockWithSenders {
+                    block: SealedBlock {
+                        header: block.header.clone(),
+                        body: transactions,
+                        ommers: block.ommers.clone(),
+                        withdrawals: block.withdrawals.clone(),
+                        requests: block.requests.clone(),
+                    },
+                    senders,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let execution_outcome = chain.execution_outcome();
+        Arc::new(Chain::new(
+            blocks,
+            ExecutionOutcome::new(
+                self.filter_bundle(&execution_outcome.bundle),
+                receipts.into(),
+                execution_outcome.first_block,
+                execution_outcome.requests.clone(),
+            ),
+            None,
+        ))
+    }
+
+    /// Returns the state changes of the filtered addresses, or all state changes if the filter
+    /// has no addresses.
+    fn filter_bundle(&self, bundle: &BundleState) -> BundleState {
+        if self.addresses.is_empty() {
+            return bundle.clone()
+        }
+
+        let state = bundle
+            .state
+            .iter()
+            .filter(|(address, _)| self.addresses.contains(*address))
+            .map(|(address, account)| (*address, account.clone()))
+            .collect::<reth_revm::primitives::HashMap<_, _>>();
+        let code_hashes = state
+            .values()
+            .filter_map(|account| account.info.as_ref())
+            .map(|info| info.code_hash)
+            .collect::<HashSet<_>>();
+        let contracts = bundle
+            .contracts
+            .iter()
+            .filter(|(code_hash, _)| code_hashes.contains(*code_hash))
+            .map(|(code_hash, code)| (*code_hash, code.clone()))
+            .collect();
+        let reverts = bundle
+            .reverts
+            .iter()
+            .map(|block_reverts| {
+                block_reverts
+                    .iter()
+                    .filter(|(address, _)| self.addresses.contains(address))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        BundleState {
+            state_size: state.values().map(BundleAccount::size_hint).sum(),
+            reverts_size: reverts.iter().flatten().map(|(_, revert)| revert.size_hint()).sum(),
+            state,
+            contracts,
+            reverts: Reverts::new(reverts),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, Log, TxKind, TxLegacy};
+
+    #[test]
+    fn filters_transactions() {
+        let target = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let transaction = |to: Address| {
+            TransactionSigned::from_transaction_and_signature(
+                TxLegacy { to: TxKind::Call(to), ..Default::default() }.into(),
+                Default::default(),
+            )
+        };
+        let receipt = |log_address: Address| Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: 0,
+            logs: vec![Log::new_unchecked(log_address, vec![topic], Bytes::new())],
+            ..Default::default()
+        };
+
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                body: vec![transaction(target), transaction(Address::ZERO)],
+                ..Default::default()
+            },
+            senders: vec![Address::ZERO, Address::ZERO],
+        };
+        let chain = Chain::new(
+            [block],
+            ExecutionOutcome {
+                receipts: vec![vec![Some(receipt(Address::ZERO)), Some(receipt(target))]].into(),
+                ..Default::default()
+            },
+            None,
+        );
+
+        // the first transaction is sent to the address, the second emits a log from it
+        let filtered =
+            ExExNotificationFilter::default().with_addresses([target]).filter_chain(&chain);
+        assert_eq!(filtered.tip().body.len(), 2);
+
+        let filtered = ExExNotificationFilter::default()
+            .with_addresses([target])
+            .with_tx_types([TxType::Eip1559])
+            .filter_chain(&chain);
+        assert!(filtered.tip().body.is_empty());
+        assert_eq!(filtered.tip().number, chain.tip().number);
+
+        let filtered = ExExNotificationFilter::default()
+            .with_topics([B256::with_last_byte(3)])
+            .filter_chain(&chain);
+        assert!(filtered.tip().body.is_empty());
+        assert!(filtered.execution_outcome().receipts[0].is_empty());
+
+        let filtered = ExExNotificationFilter::default().with_topics([topic]).filter_chain(&chain);
+        assert_eq!(filtered.tip().senders.len(), 2);
+        assert_eq!(filtered.execution_outcome().receipts[0].len(), 2);
+    }
+}
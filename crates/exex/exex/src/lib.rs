@@ -23,6 +23,12 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Filtering
+//!
+//! An `ExEx` that is only interested in some addresses, topics or transaction types can emit an
+//! `ExExEvent::SetNotificationFilter` event. The manager then sends it smaller notifications that
+//! only contain the matching parts of the chain, see [`ExExNotificationFilter`].
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -37,12 +43,18 @@
 mod backfill;
 pub use backfill::*;
 
+mod backpressure;
+pub use backpressure::*;
+
 mod context;
 pub use context::*;
 
 mod event;
 pub use event::*;
 
+mod filter;
+pub use filter::*;
+
 mod manager;
 pub use manager::*;
 
@@ -1,4 +1,6 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{
+    ExExBackpressurePolicy, ExExEvent, ExExNotification, ExExNotificationFilter, FinishedExExHeight,
+};
 use metrics::Gauge;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
@@ -27,6 +29,10 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The total number of notifications dropped for an `ExEx` that fell too far behind.
+    notifications_skipped_total: Counter,
+    /// The number of notifications in the buffer of an `ExEx` that fell too far behind.
+    buffered_notifications: Gauge,
 }
 
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -48,6 +54,17 @@ pub struct ExExHandle {
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
 
+    /// How the manager treats this `ExEx` if it falls behind.
+    backpressure_policy: ExExBackpressurePolicy,
+    /// Notifications that were moved out of the manager's buffer because this `ExEx` fell too far
+    /// behind, see [`ExExBackpressurePolicy::Buffer`].
+    buffered: VecDeque<ExExNotification>,
+    /// The number of notifications that were dropped because this `ExEx` fell too far behind,
+    /// and that it wasn't notified about yet, see [`ExExBackpressurePolicy::Skip`].
+    skipped: usize,
+    /// The filter applied to the notifications before they are sent to this `ExEx`.
+    filter: ExExNotificationFilter,
+
     /// The finished block number of the `ExEx`.
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
@@ -70,6 +87,10 @@ impl ExExHandle {
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
+                backpressure_policy: ExExBackpressurePolicy::default(),
+                buffered: VecDeque::new(),
+                skipped: 0,
+                filter: ExExNotificationFilter::default(),
                 finished_height: None,
             },
             event_tx,
@@ -77,6 +98,85 @@ impl ExExHandle {
         )
     }
 
+    /// Sets how the manager treats this `ExEx` if it falls behind.
+    ///
+    /// Defaults to [`ExExBackpressurePolicy::Block`].
+    pub fn with_backpressure_policy(mut self, policy: ExExBackpressurePolicy) -> Self {
+        self.backpressure_policy = policy;
+        self
+    }
+
+    /// Releases the notifications this `ExEx` hasn't received yet from the manager's buffer
+    /// if it lags behind by more than its backpressure policy allows.
+    ///
+    /// The `buffer` starts at the notification with ID `min_id`, and `next_id` is the ID of the
+    /// next notification that will be pushed to it.
+    fn apply_backpressure_policy(
+        &mut self,
+        buffer: &VecDeque<(usize, ExExNotification)>,
+        min_id: usize,
+        next_id: usize,
+    ) {
+        let lag = next_id - self.next_notification_id;
+        if self.backpressure_policy.max_lag().map_or(true, |max_lag| lag <= max_lag) {
+            return
+        }
+
+        let pending = buffer.iter().skip(self.next_notification_id - min_id);
+        match self.backpressure_policy {
+            ExExBackpressurePolicy::Block => unreachable!("no lag limit"),
+            ExExBackpressurePolicy::Buffer { .. } => {
+                debug!(exex_id = %self.id, %lag, "Buffering notifications of lagging ExEx");
+                self.buffered.extend(
+                    pending.map(|(_, notification)| self.filter.filter_notification(notification)),
+                );
+                self.metrics.buffered_notifications.set(self.buffered.len() as f64);
+            }
+            ExExBackpressurePolicy::Skip { .. } => {
+                debug!(exex_id = %self.id, %lag, "Skipping notifications of lagging ExEx");
+                self.skipped += lag;
+                self.metrics.notifications_skipped_total.increment(lag as u64);
+            }
+        }
+        self.next_notification_id = next_id;
+    }
+
+    /// Returns `true` if there are notifications for this `ExEx` outside of the manager's buffer
+    /// that have to be sent first.
+    fn has_pending(&self) -> bool {
+        self.skipped > 0 || !self.buffered.is_empty()
+    }
+
+    /// Sends the notice about skipped notifications, or the next buffered notification.
+    fn send_pending(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
+        if self.skipped > 0 {
+            let notification = ExExNotification::NotificationsSkipped { count: self.skipped };
+            ready!(self.deliver(cx, notification))?;
+            self.skipped = 0;
+        } else if let Some(notification) = self.buffered.front().cloned() {
+            ready!(self.deliver(cx, notification))?;
+            self.buffered.pop_front();
+            self.metrics.buffered_notifications.set(self.buffered.len() as f64);
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Sends a notification that is not in the manager's buffer, once a slot in the `PollSender`
+    /// channel is reserved.
+    fn deliver(
+        &mut self,
+        cx: &mut Context<'_>,
+        notification: ExExNotification,
+    ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
+        ready!(self.sender.poll_reserve(cx))?;
+        self.sender.send_item(notification)?;
+        self.metrics.notifications_sent_total.increment(1);
+        Poll::Ready(Ok(()))
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -109,7 +209,9 @@ impl ExExHandle {
                 // [ExExNotification::ChainReverted] cases and always send the
                 // notification, because the ExEx should be aware of the reorgs and reverts lower
                 // than its finished height
-                ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => {}
+                ExExNotification::ChainReorged { .. } |
+                ExExNotification::ChainReverted { .. } |
+                ExExNotification::NotificationsSkipped { .. } => {}
             }
         }
 
@@ -128,7 +230,7 @@ impl ExExHandle {
             %notification_id,
             "Sending notification"
         );
-        match self.sender.send_item(notification.clone()) {
+        match self.sender.send_item(self.filter.filter_notification(notification)) {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
@@ -304,17 +406,25 @@ impl Future for ExExManager {
         for idx in (0..self.exex_handles.len()).rev() {
             let mut exex = self.exex_handles.swap_remove(idx);
 
+            // release the buffered notifications of an exex that fell too far behind
+            exex.apply_backpressure_policy(&self.buffer, self.min_id, self.next_id);
+
             // it is a logic error for this to ever underflow since the manager manages the
             // notification IDs
             let notification_index = exex
                 .next_notification_id
                 .checked_sub(self.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = self.buffer.get(notification_index) {
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
-                    // the channel was closed, which is irrecoverable for the manager
-                    return Poll::Ready(Err(err.into()))
-                }
+            let sent = if exex.has_pending() {
+                exex.send_pending(cx)
+            } else if let Some(notification) = self.buffer.get(notification_index) {
+                exex.send(cx, notification)
+            } else {
+                Poll::Pending
+            };
+            if let Poll::Ready(Err(err)) = sent {
+                // the channel was closed, which is irrecoverable for the manager
+                return Poll::Ready(Err(err.into()))
             }
             min_id = min_id.min(exex.next_notification_id);
             self.exex_handles.push(exex);
@@ -335,6 +445,7 @@ impl Future for ExExManager {
                 exex.metrics.events_sent_total.increment(1);
                 match event {
                     ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::SetNotificationFilter(filter) => exex.filter = filter,
                 }
             }
         }
@@ -481,6 +592,38 @@ impl Clone for ExExManagerHandle {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use reth_provider::Chain;
+
+    #[test]
+    fn applies_backpressure_policy() {
+        let notification = ExExNotification::ChainCommitted { new: Arc::new(Chain::default()) };
+        let buffer = (0..5).map(|id| (id, notification.clone())).collect::<VecDeque<_>>();
+
+        let (mut exex, _, _) = ExExHandle::new("block".to_string());
+        exex.apply_backpressure_policy(&buffer, 0, 5);
+        assert_eq!(exex.next_notification_id, 0);
+        assert!(!exex.has_pending());
+
+        let (exex, _, _) = ExExHandle::new("skip".to_string());
+        let mut exex = exex.with_backpressure_policy(ExExBackpressurePolicy::Skip { max_lag: 3 });
+        exex.next_notification_id = 2;
+        exex.apply_backpressure_policy(&buffer, 0, 5);
+        assert_eq!(exex.next_notification_id, 2);
+        exex.next_notification_id = 1;
+        exex.apply_backpressure_policy(&buffer, 0, 5);
+        assert_eq!(exex.next_notification_id, 5);
+        assert_eq!(exex.skipped, 4);
+        assert!(exex.has_pending());
+
+        let (exex, _, _) = ExExHandle::new("buffer".to_string());
+        let mut exex = exex.with_backpressure_policy(ExExBackpressurePolicy::Buffer { max_lag: 2 });
+        exex.next_notification_id = 2;
+        exex.apply_backpressure_policy(&buffer, 0, 5);
+        assert_eq!(exex.next_notification_id, 5);
+        assert_eq!(exex.buffered.len(), 3);
+    }
+
     #[tokio::test]
     async fn delivers_events() {}
 
@@ -23,6 +23,15 @@ pub enum ExExNotification {
         /// The old chain before reversion.
         old: Arc<Chain>,
     },
+    /// Notifications were dropped because the `ExEx` fell too far behind, see
+    /// [`ExExBackpressurePolicy::Skip`](crate::ExExBackpressurePolicy::Skip), or because the
+    /// [`ExExManager`](crate::ExExManager) itself lagged behind the canonical state notifications.
+    ///
+    /// The `ExEx` has to catch up with the canonical chain on its own, e.g. using the provider.
+    NotificationsSkipped {
+        /// The number of dropped notifications.
+        count: usize,
+    },
 }
 
 impl ExExNotification {
@@ -31,7 +40,7 @@ impl ExExNotification {
     pub fn committed_chain(&self) -> Option<Arc<Chain>> {
         match self {
             Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => Some(new.clone()),
-            Self::ChainReverted { .. } => None,
+            Self::ChainReverted { .. } | Self::NotificationsSkipped { .. } => None,
         }
     }
 
@@ -40,7 +49,7 @@ impl ExExNotification {
     pub fn reverted_chain(&self) -> Option<Arc<Chain>> {
         match self {
             Self::ChainReorged { old, new: _ } | Self::ChainReverted { old } => Some(old.clone()),
-            Self::ChainCommitted { .. } => None,
+            Self::ChainCommitted { .. } | Self::NotificationsSkipped { .. } => None,
         }
     }
 }
@@ -0,0 +1,78 @@
+//! clap [Args](clap::Args) for the indexer `ExEx`.
+
+use clap::Args;
+use reth_primitives::B256;
+use std::path::{Path, PathBuf};
+
+/// The file name of the index database in the data directory.
+pub const INDEXER_DB_FILE: &str = "exex-indexer.sqlite";
+
+/// Parameters for the indexer `ExEx`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+#[command(next_help_heading = "Indexer ExEx")]
+pub struct IndexerArgs {
+    /// Index the ERC-20 and ERC-721 transfers of new blocks and serve them in the `index` RPC
+    /// namespace.
+    #[arg(long = "exex.indexer", default_value_t = false)]
+    pub enabled: bool,
+
+    /// Comma separated signatures of additional events to index, i.e. their first topics.
+    #[arg(
+        long = "exex.indexer.events",
+        value_name = "SIGNATURES",
+        value_delimiter = ',',
+        requires = "enabled"
+    )]
+    pub events: Vec<B256>,
+
+    /// The path of the index database.
+    ///
+    /// Defaults to `exex-indexer.sqlite` in the data directory.
+    #[arg(long = "exex.indexer.db", value_name = "PATH", requires = "enabled")]
+    pub db: Option<PathBuf>,
+}
+
+impl IndexerArgs {
+    /// Returns the path of the index database, given the data directory of the node.
+    pub fn db_path(&self, data_dir: &Path) -> PathBuf {
+        self.db.clone().unwrap_or_else(|| data_dir.join(INDEXER_DB_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_indexer_args() {
+        let args = CommandParser::<IndexerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, IndexerArgs::default());
+
+        let signature = B256::with_last_byte(1);
+        let args = CommandParser::<IndexerArgs>::parse_from([
+            "reth",
+            "--exex.indexer",
+            "--exex.indexer.events",
+            &format!("{signature},{}", B256::ZERO),
+        ])
+        .args;
+        assert!(args.enabled);
+        assert_eq!(args.events, vec![signature, B256::ZERO]);
+        assert_eq!(args.db_path(Path::new("/data")), PathBuf::from("/data").join(INDEXER_DB_FILE));
+
+        assert!(CommandParser::<IndexerArgs>::try_parse_from([
+            "reth",
+            "--exex.indexer.events",
+            &signature.to_string()
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,384 @@
+//! The embedded database of the indexer.
+
+use crate::{EventFilter, IndexedEvent, Transfer, TransferFilter, TransferKind};
+use reth_primitives::{Address, BlockNumber, Bytes, Log, B256, U256};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Errors of the indexer database.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerDbError {
+    /// An error of the underlying `SQLite` database.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    /// The directory of the database could not be created.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A value in the database has an unexpected length.
+    #[error("invalid {0} in the index")]
+    Corrupted(&'static str),
+}
+
+/// A log of an indexed block, together with its position in the block.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockLog<'a> {
+    /// The hash of the transaction that emitted the log.
+    pub transaction_hash: B256,
+    /// The index of the log in the block.
+    pub log_index: u64,
+    /// The log.
+    pub log: &'a Log,
+}
+
+/// The `SQLite` database the transfers and events are indexed in.
+///
+/// Cloned handles share the same connection.
+#[derive(Debug, Clone)]
+pub struct IndexerDb {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl IndexerDb {
+    /// Opens the database at the given path, creating it and its directory if they don't exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IndexerDbError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::new(Connection::open(path)?)
+    }
+
+    /// Opens a database that is kept in memory.
+    pub fn open_in_memory() -> Result<Self, IndexerDbError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self, IndexerDbError> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS transfers (
+                block_number     INTEGER NOT NULL,
+                transaction_hash BLOB NOT NULL,
+                log_index        INTEGER NOT NULL,
+                token            BLOB NOT NULL,
+                sender           BLOB NOT NULL,
+                recipient        BLOB NOT NULL,
+                kind             INTEGER NOT NULL,
+                value            BLOB NOT NULL,
+                PRIMARY KEY (block_number, log_index)
+            );
+            CREATE INDEX IF NOT EXISTS transfers_token ON transfers (token, block_number);
+            CREATE INDEX IF NOT EXISTS transfers_sender ON transfers (sender, block_number);
+            CREATE INDEX IF NOT EXISTS transfers_recipient ON transfers (recipient, block_number);
+            CREATE TABLE IF NOT EXISTS events (
+                block_number     INTEGER NOT NULL,
+                transaction_hash BLOB NOT NULL,
+                log_index        INTEGER NOT NULL,
+                address          BLOB NOT NULL,
+                signature        BLOB NOT NULL,
+                topics           BLOB NOT NULL,
+                data             BLOB NOT NULL,
+                PRIMARY KEY (block_number, log_index)
+            );
+            CREATE INDEX IF NOT EXISTS events_signature ON events (signature, block_number);",
+        )?;
+        Ok(Self { connection: Arc::new(Mutex::new(connection)) })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().expect("not poisoned")
+    }
+
+    /// Indexes the transfers and the events with one of the given signatures in the logs of a
+    /// block.
+    ///
+    /// Returns the number of indexed transfers and events.
+    pub fn insert_block<'a>(
+        &self,
+        block_number: BlockNumber,
+        logs: impl IntoIterator<Item = BlockLog<'a>>,
+        events: &[B256],
+    ) -> Result<usize, IndexerDbError> {
+        let mut connection = self.connection();
+        let tx = connection.transaction()?;
+        let mut indexed = 0;
+        {
+            let mut insert_transfer = tx.prepare_cached(
+                "INSERT OR REPLACE INTO transfers VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            let mut insert_event = tx.prepare_cached(
+                "INSERT OR REPLACE INTO events VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for BlockLog { transaction_hash, log_index, log } in logs {
+                let Some(signature) = log.topics().first() else { continue };
+
+                if let Some((kind, from, to, value)) = decode_transfer(log) {
+                    insert_transfer.execute(params![
+                        block_number,
+                        transaction_hash.as_slice(),
+                        log_index,
+                        log.address.as_slice(),
+                        from.as_slice(),
+                        to.as_slice(),
+                        kind as u8,
+                        value.to_be_bytes::<32>(),
+                    ])?;
+                    indexed += 1;
+                }
+
+                if events.contains(signature) {
+                    let topics = log.topics().iter().flat_map(|topic| topic.0).collect::<Vec<_>>();
+                    insert_event.execute(params![
+                        block_number,
+                        transaction_hash.as_slice(),
+                        log_index,
+                        log.address.as_slice(),
+                        signature.as_slice(),
+                        topics,
+                        log.data.data.as_ref(),
+                    ])?;
+                    indexed += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(indexed)
+    }
+
+    /// Removes everything that was indexed at or above the given block, e.g. because the blocks
+    /// were reverted.
+    pub fn remove_from(&self, block_number: BlockNumber) -> Result<(), IndexerDbError> {
+        let mut connection = self.connection();
+        let tx = connection.transaction()?;
+        tx.execute("DELETE FROM transfers WHERE block_number >= ?1", [block_number])?;
+        tx.execute("DELETE FROM events WHERE block_number >= ?1", [block_number])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the highest block with an indexed transfer or event.
+    pub fn latest_block(&self) -> Result<Option<BlockNumber>, IndexerDbError> {
+        Ok(self
+            .connection()
+            .query_row(
+                "SELECT MAX(block_number) FROM (
+                    SELECT block_number FROM transfers UNION ALL SELECT block_number FROM events
+                )",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten())
+    }
+
+    /// Returns the transfers that match the filter, ordered by block and log index.
+    pub fn transfers(&self, filter: &TransferFilter) -> Result<Vec<Transfer>, IndexerDbError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT * FROM transfers
+            WHERE block_number >= ?1 AND block_number <= ?2
+                AND (?3 IS NULL OR token = ?3)
+                AND (?4 IS NULL OR sender = ?4)
+                AND (?5 IS NULL OR recipient = ?5)
+            ORDER BY block_number, log_index
+            LIMIT ?6",
+        )?;
+        let rows = statement.query_map(
+            params![
+                filter.from_block.unwrap_or_default(),
+                filter.to_block.map_or(i64::MAX, |block| block as i64),
+                filter.token.as_ref().map(|token| token.as_slice()),
+                filter.from.as_ref().map(|from| from.as_slice()),
+                filter.to.as_ref().map(|to| to.as_slice()),
+                filter.limit(),
+            ],
+            |row| Ok(decode_transfer_row(row)),
+        )?;
+        rows.map(|row| row?).collect()
+    }
+
+    /// Returns the events that match the filter, ordered by block and log index.
+    pub fn events(&self, filter: &EventFilter) -> Result<Vec<IndexedEvent>, IndexerDbError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached(
+            "SELECT block_number, transaction_hash, log_index, address, topics, data FROM events
+            WHERE signature = ?1 AND block_number >= ?2 AND block_number <= ?3
+                AND (?4 IS NULL OR address = ?4)
+            ORDER BY block_number, log_index
+            LIMIT ?5",
+        )?;
+        let rows = statement.query_map(
+            params![
+                filter.signature.as_slice(),
+                filter.from_block.unwrap_or_default(),
+                filter.to_block.map_or(i64::MAX, |block| block as i64),
+                filter.address.as_ref().map(|address| address.as_slice()),
+                filter.limit(),
+            ],
+            |row| Ok(decode_event_row(row)),
+        )?;
+        rows.map(|row| row?).collect()
+    }
+}
+
+/// The signature of the ERC-20 and ERC-721 `Transfer(address,address,uint256)` event.
+pub const TRANSFER_EVENT_SIGNATURE: B256 =
+    reth_primitives::b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Decodes an ERC-20 or ERC-721 transfer from the log.
+///
+/// Both standards share the event signature: ERC-20 transfers carry the amount in the data,
+/// ERC-721 transfers carry the token id as an indexed topic.
+fn decode_transfer(log: &Log) -> Option<(TransferKind, Address, Address, U256)> {
+    let data = log.data.data.as_ref();
+    match log.topics() {
+        [signature, from, to] if *signature == TRANSFER_EVENT_SIGNATURE && data.len() == 32 => {
+            let value = U256::from_be_slice(data);
+            Some((TransferKind::Erc20, Address::from_word(*from), Address::from_word(*to), value))
+        }
+        [signature, from, to, token_id]
+            if *signature == TRANSFER_EVENT_SIGNATURE && data.is_empty() =>
+        {
+            let token_id = U256::from_be_bytes(token_id.0);
+            Some((
+                TransferKind::Erc721,
+                Address::from_word(*from),
+                Address::from_word(*to),
+                token_id,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn decode_transfer_row(row: &Row<'_>) -> Result<Transfer, IndexerDbError> {
+    let kind = match row.get::<_, u8>(6)? {
+        0 => TransferKind::Erc20,
+        1 => TransferKind::Erc721,
+        _ => return Err(IndexerDbError::Corrupted("transfer kind")),
+    };
+    Ok(Transfer {
+        block_number: row.get(0)?,
+        transaction_hash: b256(row, 1)?,
+        log_index: row.get(2)?,
+        token: address(row, 3)?,
+        from: address(row, 4)?,
+        to: address(row, 5)?,
+        kind,
+        value: U256::from_be_bytes(b256(row, 7)?.0),
+    })
+}
+
+fn decode_event_row(row: &Row<'_>) -> Result<IndexedEvent, IndexerDbError> {
+    let topics = row.get::<_, Vec<u8>>(4)?;
+    if topics.len() % 32 != 0 {
+        return Err(IndexerDbError::Corrupted("event topics"))
+    }
+    Ok(IndexedEvent {
+        block_number: row.get(0)?,
+        transaction_hash: b256(row, 1)?,
+        log_index: row.get(2)?,
+        address: address(row, 3)?,
+        topics: topics.chunks_exact(32).map(B256::from_slice).collect(),
+        data: Bytes::from(row.get::<_, Vec<u8>>(5)?),
+    })
+}
+
+fn b256(row: &Row<'_>, index: usize) -> Result<B256, IndexerDbError> {
+    Ok(B256::from(row.get::<_, [u8; 32]>(index)?))
+}
+
+fn address(row: &Row<'_>, index: usize) -> Result<Address, IndexerDbError> {
+    let bytes = row.get::<_, Vec<u8>>(index)?;
+    Address::try_from(bytes.as_slice()).map_err(|_| IndexerDbError::Corrupted("address"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::address;
+
+    fn transfer_log(token: Address, from: Address, to: Address, amount: u64) -> Log {
+        Log::new_unchecked(
+            token,
+            vec![TRANSFER_EVENT_SIGNATURE, from.into_word(), to.into_word()],
+            U256::from(amount).to_be_bytes::<32>().to_vec().into(),
+        )
+    }
+
+    fn nft_log(token: Address, from: Address, to: Address, token_id: u64) -> Log {
+        Log::new_unchecked(
+            token,
+            vec![
+                TRANSFER_EVENT_SIGNATURE,
+                from.into_word(),
+                to.into_word(),
+                B256::from(U256::from(token_id)),
+            ],
+            Bytes::new(),
+        )
+    }
+
+    fn block_logs(logs: &[Log]) -> impl Iterator<Item = BlockLog<'_>> {
+        logs.iter().enumerate().map(|(index, log)| BlockLog {
+            transaction_hash: B256::with_last_byte(index as u8),
+            log_index: index as u64,
+            log,
+        })
+    }
+
+    #[test]
+    fn indexes_and_reverts_transfers() {
+        let db = IndexerDb::open_in_memory().unwrap();
+        let token = address!("00000000000000000000000000000000000000aa");
+        let nft = address!("00000000000000000000000000000000000000bb");
+        let alice = address!("0000000000000000000000000000000000000001");
+        let bob = address!("0000000000000000000000000000000000000002");
+
+        let first = [transfer_log(token, alice, bob, 100), nft_log(nft, bob, alice, 7)];
+        assert_eq!(db.insert_block(1, block_logs(&first), &[]).unwrap(), 2);
+        let second = [transfer_log(token, bob, alice, 50)];
+        assert_eq!(db.insert_block(2, block_logs(&second), &[]).unwrap(), 1);
+        assert_eq!(db.latest_block().unwrap(), Some(2));
+
+        let all = db.transfers(&TransferFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[1].kind, TransferKind::Erc721);
+        assert_eq!(all[1].value, U256::from(7));
+
+        let filter = TransferFilter { token: Some(token), from: Some(bob), ..Default::default() };
+        let transfers = db.transfers(&filter).unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].block_number, 2);
+        assert_eq!(transfers[0].to, alice);
+        assert_eq!(transfers[0].value, U256::from(50));
+
+        let filter = TransferFilter { to_block: Some(1), ..Default::default() };
+        assert_eq!(db.transfers(&filter).unwrap().len(), 2);
+
+        db.remove_from(2).unwrap();
+        assert_eq!(db.transfers(&TransferFilter::default()).unwrap().len(), 2);
+        assert_eq!(db.latest_block().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn indexes_configured_events() {
+        let db = IndexerDb::open_in_memory().unwrap();
+        let signature = B256::with_last_byte(0xee);
+        let emitter = address!("00000000000000000000000000000000000000cc");
+        let logs = [
+            Log::new_unchecked(emitter, vec![signature, B256::ZERO], Bytes::from_static(b"data")),
+            Log::new_unchecked(emitter, vec![B256::with_last_byte(0xff)], Bytes::new()),
+        ];
+        assert_eq!(db.insert_block(5, block_logs(&logs), &[signature]).unwrap(), 1);
+
+        let events = db.events(&EventFilter { signature, ..Default::default() }).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].address, emitter);
+        assert_eq!(events[0].topics, vec![signature, B256::ZERO]);
+        assert_eq!(events[0].data, Bytes::from_static(b"data"));
+        assert!(db.transfers(&TransferFilter::default()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! The indexer `ExEx`.
+
+use crate::{BlockLog, IndexerDb};
+use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_node_api::FullNodeComponents;
+use reth_primitives::B256;
+use reth_tracing::tracing::{debug, warn};
+
+/// Indexes the transfers and the events with the given signatures of the committed blocks into
+/// the database, and removes the ones of reverted blocks.
+///
+/// Only the blocks the node commits while the `ExEx` is running are indexed, it doesn't backfill
+/// older blocks.
+pub async fn indexer_exex<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    db: IndexerDb,
+    events: Vec<B256>,
+) -> eyre::Result<()> {
+    while let Some(notification) = ctx.notifications.recv().await {
+        if let ExExNotification::NotificationsSkipped { count } = notification {
+            warn!(target: "exex::indexer", count, "Skipped notifications, the index has gaps");
+            continue
+        }
+
+        if let Some(reverted) = notification.reverted_chain() {
+            db.remove_from(reverted.first().number)?;
+            debug!(target: "exex::indexer", range = ?reverted.range(), "Removed reverted blocks");
+        }
+
+        if let Some(committed) = notification.committed_chain() {
+            for (block, receipts) in committed.blocks_and_receipts() {
+                // receipts are missing if they were pruned. The log index counts the logs of all
+                // previous receipts of the block, so only the logs before the first missing
+                // receipt are indexed.
+                let logs = block
+                    .body
+                    .iter()
+                    .zip(receipts)
+                    .map_while(|(tx, receipt)| Some((tx.hash(), receipt.as_ref()?)))
+                    .flat_map(|(hash, receipt)| receipt.logs.iter().map(move |log| (hash, log)))
+                    .enumerate()
+                    .map(|(log_index, (transaction_hash, log))| BlockLog {
+                        transaction_hash,
+                        log_index: log_index as u64,
+                        log,
+                    });
+                let indexed = db.insert_block(block.number, logs, &events)?;
+                debug!(target: "exex::indexer", block = block.number, indexed, "Indexed block");
+            }
+            ctx.events.send(ExExEvent::FinishedHeight(committed.tip().number))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransferFilter, TRANSFER_EVENT_SIGNATURE};
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use reth_exex_test_utils::{test_exex_context, PollOnce};
+    use reth_primitives::{Address, Log, Receipt, Receipts, U256};
+    use reth_testing_utils::generators::{self, random_block};
+    use std::pin::pin;
+
+    fn transfer_log() -> Log {
+        Log::new_unchecked(
+            Address::with_last_byte(0xaa),
+            vec![
+                TRANSFER_EVENT_SIGNATURE,
+                Address::with_last_byte(1).into_word(),
+                Address::with_last_byte(2).into_word(),
+            ],
+            U256::from(100).to_be_bytes::<32>().to_vec().into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn indexes_committed_and_removes_reverted_blocks() -> eyre::Result<()> {
+        let (ctx, mut handle) = test_exex_context().await?;
+        let db = IndexerDb::open_in_memory()?;
+
+        let block = random_block(&mut generators::rng(), 1, None, Some(1), Some(0))
+            .seal_with_senders()
+            .unwrap();
+        let receipt = Receipt { success: true, logs: vec![transfer_log()], ..Default::default() };
+        let chain = Chain::from_block(
+            block.clone(),
+            ExecutionOutcome::new(Default::default(), vec![receipt].into(), 1, vec![]),
+            None,
+        );
+
+        let mut exex = pin!(indexer_exex(ctx, db.clone(), vec![]));
+
+        handle.send_notification_chain_committed(chain.clone()).await?;
+        exex.poll_once().await?;
+        handle.assert_event_finished_height(1)?;
+
+        let transfers = db.transfers(&TransferFilter::default())?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].transaction_hash, block.body[0].hash());
+        assert_eq!(transfers[0].value, U256::from(100));
+
+        handle.send_notification_chain_reverted(chain).await?;
+        exex.poll_once().await?;
+        assert!(db.transfers(&TransferFilter::default())?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_logs_after_pruned_receipt() -> eyre::Result<()> {
+        let (ctx, mut handle) = test_exex_context().await?;
+        let db = IndexerDb::open_in_memory()?;
+
+        let block = random_block(&mut generators::rng(), 1, None, Some(3), Some(0))
+            .seal_with_senders()
+            .unwrap();
+        let receipt = Receipt { success: true, logs: vec![transfer_log()], ..Default::default() };
+        let receipts =
+            Receipts { receipt_vec: vec![vec![Some(receipt.clone()), None, Some(receipt)]] };
+        let chain = Chain::from_block(
+            block.clone(),
+            ExecutionOutcome::new(Default::default(), receipts, 1, vec![]),
+            None,
+        );
+
+        let mut exex = pin!(indexer_exex(ctx, db.clone(), vec![]));
+
+        handle.send_notification_chain_committed(chain).await?;
+        exex.poll_once().await?;
+
+        // the log index of the third transaction's log is unknown
+        let transfers = db.transfers(&TransferFilter::default())?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].transaction_hash, block.body[0].hash());
+        assert_eq!(transfers[0].log_index, 0);
+
+        Ok(())
+    }
+}
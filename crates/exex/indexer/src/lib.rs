@@ -0,0 +1,30 @@
+//! An `ExEx` that indexes the ERC-20 and ERC-721 transfers and selected events of new blocks in an
+//! embedded `SQLite` database, and serves them in the `index` RPC namespace.
+//!
+//! It is also meant as a reference implementation of a stateful `ExEx`: the committed blocks are
+//! written to the index, the reverted ones are removed from it, and the
+//! [`FinishedHeight`](reth_exex::ExExEvent::FinishedHeight) is only emitted once the index is
+//! updated.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod args;
+pub use args::{IndexerArgs, INDEXER_DB_FILE};
+
+mod db;
+pub use db::{BlockLog, IndexerDb, IndexerDbError, TRANSFER_EVENT_SIGNATURE};
+
+mod exex;
+pub use exex::indexer_exex;
+
+mod rpc;
+pub use rpc::{IndexApiServer, IndexRpc};
+
+mod types;
+pub use types::{EventFilter, IndexedEvent, Transfer, TransferFilter, TransferKind, MAX_RESULTS};
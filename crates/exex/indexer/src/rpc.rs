@@ -0,0 +1,41 @@
+//! The `index` RPC namespace.
+
+use crate::{EventFilter, IndexedEvent, IndexerDb, Transfer, TransferFilter};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_server_types::result::internal_rpc_err;
+
+/// Queries the transfers and events indexed by the indexer `ExEx`.
+#[rpc(server, namespace = "index")]
+pub trait IndexApi {
+    /// Returns the indexed ERC-20 and ERC-721 transfers that match the filter, ordered by block
+    /// and log index.
+    #[method(name = "getTransfers", blocking)]
+    fn get_transfers(&self, filter: TransferFilter) -> RpcResult<Vec<Transfer>>;
+
+    /// Returns the indexed events that match the filter, ordered by block and log index.
+    #[method(name = "getEvents", blocking)]
+    fn get_events(&self, filter: EventFilter) -> RpcResult<Vec<IndexedEvent>>;
+}
+
+/// Implementation of the `index` RPC namespace.
+#[derive(Debug, Clone)]
+pub struct IndexRpc {
+    db: IndexerDb,
+}
+
+impl IndexRpc {
+    /// Creates the namespace that serves the given database.
+    pub const fn new(db: IndexerDb) -> Self {
+        Self { db }
+    }
+}
+
+impl IndexApiServer for IndexRpc {
+    fn get_transfers(&self, filter: TransferFilter) -> RpcResult<Vec<Transfer>> {
+        self.db.transfers(&filter).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    fn get_events(&self, filter: EventFilter) -> RpcResult<Vec<IndexedEvent>> {
+        self.db.events(&filter).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
@@ -0,0 +1,107 @@
+//! Types of the indexed data and the filters to query it.
+
+use reth_primitives::{Address, BlockNumber, Bytes, TxHash, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of results a query returns.
+pub const MAX_RESULTS: usize = 1_000;
+
+/// The token standard of a [`Transfer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferKind {
+    /// A transfer of an amount of ERC-20 tokens.
+    Erc20 = 0,
+    /// A transfer of an ERC-721 token.
+    Erc721 = 1,
+}
+
+/// An indexed ERC-20 or ERC-721 transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    /// The block the transfer was included in.
+    pub block_number: BlockNumber,
+    /// The hash of the transaction that emitted the transfer.
+    pub transaction_hash: TxHash,
+    /// The index of the log in the block.
+    pub log_index: u64,
+    /// The token contract.
+    pub token: Address,
+    /// The sender of the tokens.
+    pub from: Address,
+    /// The recipient of the tokens.
+    pub to: Address,
+    /// The token standard.
+    pub kind: TransferKind,
+    /// The transferred amount for ERC-20 transfers, the token id for ERC-721 transfers.
+    pub value: U256,
+}
+
+/// An indexed event with one of the configured signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+    /// The block the event was included in.
+    pub block_number: BlockNumber,
+    /// The hash of the transaction that emitted the event.
+    pub transaction_hash: TxHash,
+    /// The index of the log in the block.
+    pub log_index: u64,
+    /// The contract that emitted the event.
+    pub address: Address,
+    /// The topics of the event, starting with the signature.
+    pub topics: Vec<B256>,
+    /// The data of the event.
+    pub data: Bytes,
+}
+
+/// Filter for the indexed transfers.
+///
+/// Unset fields match all transfers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransferFilter {
+    /// The first block to return transfers of.
+    pub from_block: Option<BlockNumber>,
+    /// The last block to return transfers of.
+    pub to_block: Option<BlockNumber>,
+    /// The token contract.
+    pub token: Option<Address>,
+    /// The sender of the tokens.
+    pub from: Option<Address>,
+    /// The recipient of the tokens.
+    pub to: Option<Address>,
+    /// The maximum number of transfers to return, at most [`MAX_RESULTS`].
+    pub limit: Option<usize>,
+}
+
+impl TransferFilter {
+    /// Returns the maximum number of transfers to return.
+    pub fn limit(&self) -> usize {
+        self.limit.map_or(MAX_RESULTS, |limit| limit.min(MAX_RESULTS))
+    }
+}
+
+/// Filter for the indexed events.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventFilter {
+    /// The signature of the events, which has to be one of the indexed signatures.
+    pub signature: B256,
+    /// The first block to return events of.
+    pub from_block: Option<BlockNumber>,
+    /// The last block to return events of.
+    pub to_block: Option<BlockNumber>,
+    /// The contract that emitted the events.
+    pub address: Option<Address>,
+    /// The maximum number of events to return, at most [`MAX_RESULTS`].
+    pub limit: Option<usize>,
+}
+
+impl EventFilter {
+    /// Returns the maximum number of events to return.
+    pub fn limit(&self) -> usize {
+        self.limit.map_or(MAX_RESULTS, |limit| limit.min(MAX_RESULTS))
+    }
+}
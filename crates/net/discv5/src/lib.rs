@@ -153,6 +153,26 @@ impl Discv5 {
         (&enr).try_into().unwrap()
     }
 
+    /// Returns the local [`Enr`] of the node, as advertised to peers.
+    ///
+    /// This includes the currently tracked external IP address of the node and the network stack
+    /// kv-pair, e.g. the `eth` [`ForkId`] entry.
+    pub fn local_enr(&self) -> Enr<SecretKey> {
+        EnrCombinedKeyWrapper(self.discv5.local_enr()).into()
+    }
+
+    /// Updates the [`ForkId`] advertised in the local [`Enr`] under the configured network stack
+    /// key. Does nothing if no network stack key is configured.
+    pub fn update_fork_id(&self, fork_id: ForkId) {
+        let Some(key) = self.fork_key.and_then(|key| std::str::from_utf8(key).ok()) else { return };
+        if let Err(err) = self.discv5.enr_insert(key, &EnrForkIdEntry::from(fork_id)) {
+            error!(target: "discv5",
+                %err,
+                "failed to update fork id in local enr"
+            );
+        }
+    }
+
     /// Spawns [`discv5::Discv5`]. Returns [`discv5::Discv5`] handle in reth compatible wrapper type
     /// [`Discv5`], a receiver of [`discv5::Event`]s from the underlying node, and the local
     /// [`Enr`](discv5::Enr) converted into the reth compatible [`NodeRecord`] type.
@@ -319,7 +339,13 @@ impl Discv5 {
 
         // todo: extend for all network stacks in reth-network rlpx logic
         let fork_id = (self.fork_key == Some(NetworkStackId::ETH))
-            .then(|| self.get_fork_id(enr).ok())
+            .then(|| match self.get_fork_id(enr) {
+                Ok(fork_id) => Some(fork_id),
+                Err(_) => {
+                    self.metrics.discovered_peers.increment_established_sessions_missing_fork_id(1);
+                    None
+                }
+            })
             .flatten();
 
         trace!(target: "net::discv5",
@@ -871,6 +897,16 @@ mod test {
         }
     }
 
+    #[test]
+    fn update_fork_id_in_local_enr() {
+        let discv5 = Discv5 { fork_key: Some(NetworkStackId::ETH), ..discv5_noop() };
+
+        let fork_id = MAINNET.latest_fork_id();
+        discv5.update_fork_id(fork_id);
+
+        assert_eq!(fork_id, discv5.get_fork_id(&discv5.local_enr()).unwrap());
+    }
+
     #[test]
     fn build_enr_from_config() {
         const TCP_PORT: u16 = 30303;
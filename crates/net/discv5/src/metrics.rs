@@ -48,6 +48,11 @@ pub struct DiscoveredPeersMetrics {
     /// Total number of sessions established by [`discv5::Discv5`], that pass configured
     /// [`filter`](crate::filter) rules.
     established_sessions_custom_filtered_total: Counter,
+    /// Total number of sessions established by [`discv5::Discv5`], with peers that don't advertise
+    /// a fork ID under the configured network stack key in their node record.
+    ///
+    /// These peers can't be filtered by fork ID before an `RLPx` connection is attempted.
+    established_sessions_missing_fork_id_total: Counter,
     /// Total number of unverifiable ENRs discovered by [`discv5::Discv5`].
     ///
     /// These are peers that fail [`discv5::Discv5`] session establishment, because the UDP socket
@@ -90,6 +95,12 @@ impl DiscoveredPeersMetrics {
         self.established_sessions_custom_filtered_total.increment(num)
     }
 
+    /// Increments number of sessions established by [`discv5::Discv5`], with peers that don't
+    /// advertise a fork ID in their node record.
+    pub fn increment_established_sessions_missing_fork_id(&self, num: u64) {
+        self.established_sessions_missing_fork_id_total.increment(num)
+    }
+
     /// Increments number of unverifiable ENRs discovered by [`discv5::Discv5`]. These are peers
     /// that fail session establishment because their advertised UDP socket doesn't match the
     /// socket they are making the connection from.
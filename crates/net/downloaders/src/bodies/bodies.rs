@@ -44,6 +44,8 @@ pub struct BodiesDownloader<B: BodiesClient, Provider> {
     stream_batch_size: usize,
     /// The allowed range for number of concurrent requests.
     concurrent_requests_range: RangeInclusive<usize>,
+    /// The number of concurrent requests per connected peer.
+    requests_per_peer: usize,
     /// Maximum number of bytes of received blocks to buffer internally.
     max_buffered_blocks_size_bytes: usize,
     /// Current estimated size of buffered blocks in bytes.
@@ -134,13 +136,15 @@ where
 
     /// Max requests to handle at the same time
     ///
-    /// This depends on the number of active peers but will always be
+    /// This depends on the number of active peers and the requests per peer, but will always be
     /// [`min_concurrent_requests`..`max_concurrent_requests`]
     #[inline]
     fn concurrent_request_limit(&self) -> usize {
         let num_peers = self.client.num_connected_peers();
 
-        let max_requests = num_peers.max(*self.concurrent_requests_range.start());
+        let max_requests = num_peers
+            .saturating_mul(self.requests_per_peer)
+            .max(*self.concurrent_requests_range.start());
 
         // if we're only connected to a few peers, we keep it low
         if num_peers < *self.concurrent_requests_range.start() {
@@ -499,6 +503,8 @@ pub struct BodiesDownloaderBuilder {
     pub max_buffered_blocks_size_bytes: usize,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
+    /// The number of requests to send concurrently per connected peer.
+    pub requests_per_peer: usize,
 }
 
 impl BodiesDownloaderBuilder {
@@ -513,6 +519,7 @@ impl BodiesDownloaderBuilder {
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
             )
+            .with_requests_per_peer(config.downloader_requests_per_peer)
     }
 }
 
@@ -523,6 +530,7 @@ impl Default for BodiesDownloaderBuilder {
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             concurrent_requests_range: 5..=100,
+            requests_per_peer: 2,
         }
     }
 }
@@ -549,6 +557,12 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set the number of concurrent requests per connected peer on the downloader.
+    pub const fn with_requests_per_peer(mut self, requests_per_peer: usize) -> Self {
+        self.requests_per_peer = requests_per_peer;
+        self
+    }
+
     /// Set max buffered block bytes on the downloader.
     pub const fn with_max_buffered_blocks_size_bytes(
         mut self,
@@ -573,6 +587,7 @@ impl BodiesDownloaderBuilder {
             request_limit,
             stream_batch_size,
             concurrent_requests_range,
+            requests_per_peer,
             max_buffered_blocks_size_bytes,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
@@ -585,6 +600,7 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             max_buffered_blocks_size_bytes,
             concurrent_requests_range,
+            requests_per_peer,
             in_progress_queue,
             metrics,
             download_range: RangeInclusive::new(1, 0),
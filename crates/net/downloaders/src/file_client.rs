@@ -36,7 +36,7 @@ pub const DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE: u64 = 1_000_000_000;
 /// transactions in memory for use in the bodies stage.
 ///
 /// This reads the entire file into memory, so it is not suitable for large files.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct FileClient {
     /// The buffered headers retrieved when fetching new bodies.
     headers: HashMap<BlockNumber, Header>,
@@ -276,15 +276,14 @@ impl HeadersClient for FileClient {
             BlockHashOrNumber::Number(num) => num,
         };
 
-        let range = if request.limit == 1 {
-            Either::Left(start_num..start_num + 1)
-        } else {
-            match request.direction {
-                HeadersDirection::Rising => Either::Left(start_num..start_num + request.limit),
-                HeadersDirection::Falling => {
-                    Either::Right((start_num - request.limit + 1..=start_num).rev())
-                }
+        let step = request.skip as u64 + 1;
+        let range = match request.direction {
+            HeadersDirection::Rising => {
+                Either::Left((start_num..start_num + request.limit * step).step_by(step as usize))
             }
+            HeadersDirection::Falling => Either::Right(
+                (start_num + step - request.limit * step..=start_num).rev().step_by(step as usize),
+            ),
         };
 
         trace!(target: "downloaders::file", range=?range, "Getting headers with range");
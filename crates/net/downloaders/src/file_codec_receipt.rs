@@ -0,0 +1,114 @@
+//! Codec for reading and encoding receipts in files, for any chain.
+
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{
+    bytes::{Buf, BytesMut},
+    ReceiptWithBloom,
+};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{file_client::FileClientError, receipt_file_client::ReceiptWithBlockNumber};
+
+/// Codec for reading and encoding receipts in files.
+///
+/// Every entry in the file is an RLP list of the block number and the receipt, where the receipt
+/// uses its network encoding, i.e. `rlp([number, receipt])`. Entries must be ordered by block
+/// number and, within a block, by transaction index. Blocks without receipts are simply omitted.
+///
+/// If using with [`FramedRead`](tokio_util::codec::FramedRead), the user should make sure the
+/// framed reader has capacity for the entire receipts file. Otherwise, the decoder will return
+/// [`InputTooShort`](alloy_rlp::Error::InputTooShort), because RLP receipts can only be
+/// decoded if the internal buffer is large enough to contain the entire receipt.
+///
+/// It's recommended to use [`with_capacity`](tokio_util::codec::FramedRead::with_capacity) to set
+/// the capacity of the framed reader to the size of the file.
+#[derive(Debug, Default)]
+pub struct ReceiptFileCodec;
+
+impl Decoder for ReceiptFileCodec {
+    type Item = Option<ReceiptWithBlockNumber>;
+    type Error = FileClientError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None)
+        }
+
+        let buf_slice = &mut src.as_ref();
+        let ReceiptFileEntry { number, receipt } = ReceiptFileEntry::decode(buf_slice)
+            .map_err(|err| Self::Error::Rlp(err, src.to_vec()))?;
+        src.advance(src.len() - buf_slice.len());
+
+        Ok(Some(Some(ReceiptWithBlockNumber { receipt: receipt.receipt, number })))
+    }
+}
+
+impl Encoder<ReceiptWithBlockNumber> for ReceiptFileCodec {
+    type Error = FileClientError;
+
+    fn encode(
+        &mut self,
+        item: ReceiptWithBlockNumber,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        let ReceiptWithBlockNumber { receipt, number } = item;
+        ReceiptFileEntry { number, receipt: receipt.with_bloom() }.encode(dst);
+        Ok(())
+    }
+}
+
+/// An entry of a receipts file.
+#[derive(Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct ReceiptFileEntry {
+    number: u64,
+    receipt: ReceiptWithBloom,
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::{Address, Log, LogData, Receipt, TxType, B256};
+
+    use super::*;
+
+    #[test]
+    fn receipt_file_codec_roundtrip() {
+        let receipts = [
+            ReceiptWithBlockNumber { receipt: Receipt::default(), number: 1 },
+            ReceiptWithBlockNumber {
+                #[allow(clippy::needless_update)]
+                receipt: Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    logs: vec![Log {
+                        address: Address::repeat_byte(0x11),
+                        data: LogData::new_unchecked(
+                            vec![B256::repeat_byte(0x22)],
+                            Default::default(),
+                        ),
+                    }],
+                    ..Default::default()
+                },
+                number: 3,
+            },
+        ];
+
+        let mut buf = BytesMut::new();
+        for receipt in &receipts {
+            ReceiptFileCodec
+                .encode(
+                    ReceiptWithBlockNumber {
+                        receipt: receipt.receipt.clone(),
+                        number: receipt.number,
+                    },
+                    &mut buf,
+                )
+                .unwrap();
+        }
+
+        for receipt in receipts {
+            assert_eq!(ReceiptFileCodec.decode(&mut buf).unwrap(), Some(Some(receipt)));
+        }
+        assert_eq!(ReceiptFileCodec.decode(&mut buf).unwrap(), None);
+    }
+}
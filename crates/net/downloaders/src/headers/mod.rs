@@ -1,6 +1,9 @@
 /// A Linear downloader implementation.
 pub mod reverse_headers;
 
+/// Anchor headers the ranges of the reverse headers download are verified against.
+mod skeleton;
+
 /// A header downloader that does nothing. Useful to build unwind-only pipelines.
 pub mod noop;
 
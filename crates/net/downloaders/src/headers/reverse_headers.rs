@@ -1,6 +1,6 @@
 //! A headers downloader that can handle multiple requests concurrently.
 
-use super::task::TaskDownloader;
+use super::{skeleton::Skeleton, task::TaskDownloader};
 use crate::metrics::HeaderDownloaderMetrics;
 use futures::{stream::Stream, FutureExt};
 use futures_util::{stream::FuturesUnordered, StreamExt};
@@ -62,6 +62,12 @@ impl From<HeadersResponseError> for ReverseHeadersDownloaderError {
 /// **Note:** This downloader downloads in reverse, see also [`HeadersDirection::Falling`], this
 /// means the batches of headers that this downloader yields will start at the chain tip and move
 /// towards the local head: falling block numbers.
+///
+/// If the gap is large, the downloader first requests a skeleton of the gap: the first header of
+/// every range it will request. The ranges are then requested in parallel and verified against the
+/// skeleton as soon as they arrive, so responses of peers on another chain are rejected before
+/// they are buffered. A mismatch alone doesn't tell whether the range or the skeleton is wrong, so
+/// only the peer whose headers don't match the validated chain is penalized.
 #[must_use = "Stream does nothing unless polled"]
 #[derive(Debug)]
 pub struct ReverseHeadersDownloader<H: HeadersClient> {
@@ -94,6 +100,10 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     /// This will give us the block number of the `sync_target`, after which we can send multiple
     /// requests at a time.
     sync_target_request: Option<HeadersRequestFuture<H::Output>>,
+    /// The anchors the requested ranges are verified against
+    skeleton: Skeleton,
+    /// Contains the request for the next anchors of the `skeleton`
+    skeleton_request: Option<HeadersRequestFuture<H::Output>>,
     /// requests in progress
     in_progress_queue: FuturesUnordered<HeadersRequestFuture<H::Output>>,
     /// Buffered, unvalidated responses
@@ -171,7 +181,9 @@ where
     /// Returns `None` if no more requests are required.
     fn next_request(&mut self) -> Option<HeadersRequest> {
         if let Some(local_head) = self.local_block_number() {
-            if self.next_request_block_number > local_head {
+            if self.next_request_block_number > local_head &&
+                self.skeleton.is_ready(self.next_request_block_number)
+            {
                 let request = calc_next_request(
                     local_head,
                     self.next_request_block_number,
@@ -268,6 +280,17 @@ where
             validated.push(parent);
         }
 
+        // The validated chain is authoritative, if it doesn't match the skeleton the peer that
+        // served the skeleton is on another chain.
+        if let Some(skeleton_peer) =
+            validated.first().and_then(|header| self.skeleton.on_validated(header))
+        {
+            trace!(target: "downloaders::headers", ?skeleton_peer, "Skeleton doesn't match the validated headers");
+            self.client.report_bad_message(skeleton_peer);
+            self.skeleton_request.take();
+            self.skeleton.reset(self.next_request_block_number);
+        }
+
         // If the last (smallest) validated header attaches to the local head, validate it.
         if let Some((last_header, head)) = validated
             .last_mut()
@@ -332,6 +355,11 @@ where
                 self.next_request_block_number = next_block;
                 self.next_chain_tip_block_number = next_block;
                 self.clear();
+                self.skeleton = Skeleton::new(
+                    next_block,
+                    self.local_block_number().unwrap_or_default(),
+                    self.request_limit,
+                );
             } else {
                 // ensure already validated headers are in range
                 let skip = self
@@ -346,6 +374,11 @@ where
             // this occurs on the initial sync target request
             self.next_request_block_number = next_block;
             self.next_chain_tip_block_number = next_block;
+            self.skeleton = Skeleton::new(
+                next_block,
+                self.local_block_number().unwrap_or_default(),
+                self.request_limit,
+            );
         }
     }
 
@@ -493,6 +526,14 @@ where
                         .map(Err::<(), ReverseHeadersDownloaderError>)
                         .transpose()?;
                 } else if highest.number > self.existing_local_block_number() {
+                    // verify the response against the skeleton before it's buffered. Either the
+                    // range or the skeleton can be wrong, so the peer isn't penalized and the range
+                    // is requested again: once it's the next expected response it's validated
+                    // against the chain, which also checks the anchor.
+                    if let Err(error) = self.skeleton.validate(&headers) {
+                        trace!(target: "downloaders::headers", ?peer_id, %error, "Headers response doesn't match the skeleton");
+                        return Err(HeadersResponseError { request, peer_id: None, error }.into())
+                    }
                     self.metrics.buffered_responses.increment(1.);
                     // can't validate yet
                     self.buffered_responses.push(OrderedHeadersResponse {
@@ -513,6 +554,34 @@ where
         }
     }
 
+    /// Handles the response for a request of the skeleton
+    fn on_skeleton_outcome(
+        &mut self,
+        response: HeadersRequestOutcome,
+    ) -> Result<(), Box<HeadersResponseError>> {
+        let HeadersRequestOutcome { request, outcome } = response;
+        match outcome {
+            Ok(res) => {
+                let (peer_id, mut headers) = res.split();
+
+                // sort headers from highest to lowest block number
+                headers.sort_unstable_by_key(|h| Reverse(h.number));
+
+                trace!(target: "downloaders::headers", len=%headers.len(), "Received skeleton response");
+
+                let local_head = self.existing_local_block_number();
+                self.skeleton.on_response(&request, &headers, peer_id, local_head).map_err(
+                    |error| {
+                        Box::new(HeadersResponseError { request, peer_id: Some(peer_id), error })
+                    },
+                )
+            }
+            Err(err) => {
+                Err(Box::new(HeadersResponseError { request, peer_id: None, error: err.into() }))
+            }
+        }
+    }
+
     fn penalize_peer(&self, peer_id: Option<PeerId>, error: &DownloadError) {
         // Penalize the peer for bad response
         if let Some(peer_id) = peer_id {
@@ -565,7 +634,7 @@ where
 
     /// Returns the request for the `sync_target` header.
     const fn get_sync_target_request(&self, start: BlockHashOrNumber) -> HeadersRequest {
-        HeadersRequest { start, limit: 1, direction: HeadersDirection::Falling }
+        HeadersRequest { start, limit: 1, skip: 0, direction: HeadersDirection::Falling }
     }
 
     /// Starts a request future
@@ -598,6 +667,8 @@ where
         self.queued_validated_headers = Vec::new();
         self.buffered_responses = BinaryHeap::new();
         self.in_progress_queue.clear();
+        self.skeleton = Skeleton::default();
+        self.skeleton_request.take();
 
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
@@ -798,6 +869,26 @@ where
         // 3. return batch, if enough validated
         // 4. return Pending if 2.) did not submit a new request, else continue
         loop {
+            // poll the skeleton request, ranges are only requested once their anchor is known
+            if let Some(mut req) = this.skeleton_request.take() {
+                match req.poll_unpin(cx) {
+                    Poll::Ready(outcome) => {
+                        if let Err(error) = this.on_skeleton_outcome(outcome) {
+                            trace!(target: "downloaders::headers", %error, "invalid skeleton response");
+                            if error.is_channel_closed() {
+                                // download channel closed which means the network was dropped
+                                return Poll::Ready(None)
+                            }
+
+                            // the request is sent again below
+                            this.penalize_peer(error.peer_id, &error.error);
+                            this.metrics.increment_errors(&error.error);
+                        }
+                    }
+                    Poll::Pending => this.skeleton_request = Some(req),
+                }
+            }
+
             // poll requests
             while let Poll::Ready(Some(outcome)) = this.in_progress_queue.poll_next_unpin(cx) {
                 this.metrics.in_flight_requests.decrement(1.);
@@ -824,6 +915,16 @@ where
             // marks the loop's exit condition: exit if no requests submitted
             let mut progress = false;
 
+            if this.skeleton_request.is_none() {
+                if let Some(request) =
+                    this.skeleton.next_request(this.existing_local_block_number())
+                {
+                    trace!(target: "downloaders::headers", ?request, "Requesting skeleton");
+                    progress = true;
+                    this.skeleton_request = Some(this.request_fut(request, Priority::High));
+                }
+            }
+
             let concurrent_request_limit = this.concurrent_request_limit();
             // populate requests
             while this.in_progress_queue.len() < concurrent_request_limit &&
@@ -864,7 +965,7 @@ where
         }
 
         // all requests are handled, stream is finished
-        if this.in_progress_queue.is_empty() {
+        if this.in_progress_queue.is_empty() && this.skeleton_request.is_none() {
             let next_batch = this.split_next_batch();
             if next_batch.is_empty() {
                 this.clear();
@@ -1178,6 +1279,8 @@ impl ReverseHeadersDownloaderBuilder {
             stream_batch_size,
             max_buffered_responses,
             sync_target_request: None,
+            skeleton: Skeleton::default(),
+            skeleton_request: None,
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
             queued_validated_headers: Default::default(),
@@ -1202,13 +1305,13 @@ fn calc_next_request(
     let diff = next_request_block_number - local_head;
     let limit = diff.min(request_limit);
     let start = next_request_block_number;
-    HeadersRequest { start: start.into(), limit, direction: HeadersDirection::Falling }
+    HeadersRequest { start: start.into(), limit, skip: 0, direction: HeadersDirection::Falling }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::headers::test_utils::child_header;
+    use crate::{file_client::FileClient, headers::test_utils::child_header};
     use assert_matches::assert_matches;
     use reth_consensus::test_utils::TestConsensus;
     use reth_network_p2p::test_utils::TestHeadersClient;
@@ -1370,14 +1473,24 @@ mod tests {
         let hi = 1u64;
         heap.push(OrderedHeadersResponse {
             headers: vec![],
-            request: HeadersRequest { start: hi.into(), limit: 0, direction: Default::default() },
+            request: HeadersRequest {
+                start: hi.into(),
+                limit: 0,
+                skip: 0,
+                direction: Default::default(),
+            },
             peer_id: Default::default(),
         });
 
         let lo = 0u64;
         heap.push(OrderedHeadersResponse {
             headers: vec![],
-            request: HeadersRequest { start: lo.into(), limit: 0, direction: Default::default() },
+            request: HeadersRequest {
+                start: lo.into(),
+                limit: 0,
+                skip: 0,
+                direction: Default::default(),
+            },
             peer_id: Default::default(),
         });
 
@@ -1419,6 +1532,29 @@ mod tests {
         assert!(downloader.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn download_with_skeleton() {
+        reth_tracing::init_test_tracing();
+
+        let mut headers = vec![SealedHeader::default()];
+        for _ in 0..100 {
+            headers.push(child_header(headers.last().unwrap()));
+        }
+        let client = FileClient::default()
+            .with_headers(headers.iter().map(|h| (h.number, h.as_ref().clone())).collect());
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(100)
+            .request_limit(5)
+            .build(client, Arc::new(TestConsensus::default()));
+        downloader.update_local_head(headers[0].clone());
+        downloader.update_sync_target(SyncTarget::Tip(headers[100].hash()));
+
+        let downloaded = downloader.next().await.unwrap();
+        assert_eq!(downloaded, Ok(headers[1..].iter().rev().cloned().collect::<Vec<_>>()));
+        assert!(downloader.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn download_one_by_one() {
         reth_tracing::init_test_tracing();
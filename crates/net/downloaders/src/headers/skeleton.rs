@@ -0,0 +1,209 @@
+use reth_network_p2p::{
+    error::{DownloadError, DownloadResult},
+    headers::client::HeadersRequest,
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{BlockNumber, GotExpected, Header, HeadersDirection, SealedHeader, B256};
+use std::collections::BTreeMap;
+
+/// The maximum number of anchors to request at once.
+///
+/// This is the max number of headers commonly served in a headers response.
+const MAX_SKELETON_REQUEST: u64 = 1024;
+
+/// The minimum number of ranges the gap has to span for a skeleton to be requested.
+///
+/// For smaller gaps the additional round trip isn't worth it.
+const MIN_SKELETON_ANCHORS: u64 = 8;
+
+/// Anchor headers, one for every range the reverse headers download requests.
+///
+/// The anchors are requested with a single `GetBlockHeaders` request that skips the headers in
+/// between, starting at the highest range. This allows to verify a range as soon as it's
+/// received, instead of once all higher ranges were validated. The anchors themselves are verified
+/// once the range they start is attached to the validated chain.
+#[derive(Debug, Default)]
+pub(crate) struct Skeleton {
+    /// The hash of every anchor and the peer it was received from, by block number.
+    anchors: BTreeMap<BlockNumber, (B256, PeerId)>,
+    /// The number of blocks between two anchors.
+    interval: u64,
+    /// The next anchor to request, `None` if there is none left.
+    next_anchor: Option<BlockNumber>,
+}
+
+impl Skeleton {
+    /// Creates a new skeleton for the gap between the local head and `highest`, which is the start
+    /// of the first range.
+    ///
+    /// The skeleton is empty if the gap is too small.
+    pub(crate) fn new(highest: BlockNumber, local_head: BlockNumber, interval: u64) -> Self {
+        let is_worth_it = interval > 0 &&
+            highest.saturating_sub(local_head) >= interval.saturating_mul(MIN_SKELETON_ANCHORS);
+        Self { anchors: BTreeMap::new(), interval, next_anchor: is_worth_it.then_some(highest) }
+    }
+
+    /// Returns `true` if the range starting at the block can be requested, which is the case if
+    /// its anchor was received or no anchor will be requested for it.
+    pub(crate) fn is_ready(&self, start: BlockNumber) -> bool {
+        self.next_anchor.map_or(true, |next| start > next)
+    }
+
+    /// Returns the request for the next anchors above the local head, if any.
+    pub(crate) fn next_request(&self, local_head: BlockNumber) -> Option<HeadersRequest> {
+        let next = self.next_anchor.filter(|next| *next > local_head)?;
+        let remaining = (next - local_head - 1) / self.interval + 1;
+        Some(HeadersRequest {
+            start: next.into(),
+            limit: remaining.min(MAX_SKELETON_REQUEST),
+            skip: (self.interval - 1) as u32,
+            direction: HeadersDirection::Falling,
+        })
+    }
+
+    /// Inserts the anchors of the response to the given skeleton request.
+    ///
+    /// Caution: this expects the `headers` to be sorted with _falling_ block numbers.
+    pub(crate) fn on_response(
+        &mut self,
+        request: &HeadersRequest,
+        headers: &[Header],
+        peer_id: PeerId,
+        local_head: BlockNumber,
+    ) -> DownloadResult<()> {
+        if headers.is_empty() {
+            return Err(DownloadError::EmptyResponse)
+        }
+        let start = request.start.as_number().expect("is number");
+        let mut expected = start;
+        for header in headers {
+            if header.number != expected {
+                return Err(DownloadError::HeadersResponseStartBlockMismatch(GotExpected {
+                    got: header.number,
+                    expected,
+                }))
+            }
+            expected = expected.saturating_sub(self.interval);
+        }
+
+        // a shorter response is fine, the remaining anchors are requested again
+        for header in headers {
+            self.anchors.insert(header.number, (header.hash_slow(), peer_id));
+        }
+        let lowest = headers.last().expect("not empty").number;
+        self.next_anchor = lowest.checked_sub(self.interval).filter(|next| *next > local_head);
+        Ok(())
+    }
+
+    /// Verifies the response to a range request against the anchors of the range and of the next
+    /// lower range.
+    ///
+    /// Caution: this expects the `headers` to be sorted with _falling_ block numbers.
+    pub(crate) fn validate(&self, headers: &[Header]) -> DownloadResult<()> {
+        let (Some(highest), Some(lowest)) = (headers.first(), headers.last()) else {
+            return Ok(())
+        };
+        if let Some((anchor, _)) = self.anchors.get(&highest.number) {
+            let hash = highest.hash_slow();
+            if hash != *anchor {
+                return Err(DownloadError::SkeletonMismatch {
+                    number: highest.number,
+                    hash: GotExpected { got: hash, expected: *anchor }.into(),
+                })
+            }
+        }
+        let parent_number = lowest.number.saturating_sub(1);
+        if let Some((anchor, _)) = self.anchors.get(&parent_number) {
+            if lowest.parent_hash != *anchor {
+                return Err(DownloadError::SkeletonMismatch {
+                    number: parent_number,
+                    hash: GotExpected { got: lowest.parent_hash, expected: *anchor }.into(),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the anchor of a header that was attached to the validated chain.
+    ///
+    /// The anchor is no longer needed afterwards. Returns the peer that served the anchor if it
+    /// doesn't match the header, in which case the skeleton can't be trusted.
+    pub(crate) fn on_validated(&mut self, header: &SealedHeader) -> Option<PeerId> {
+        let (anchor, peer_id) = self.anchors.remove(&header.number)?;
+        (anchor != header.hash()).then_some(peer_id)
+    }
+
+    /// Drops all anchors and requests them again, starting at the given block.
+    pub(crate) fn reset(&mut self, next_anchor: BlockNumber) {
+        self.anchors.clear();
+        self.next_anchor = (self.interval > 0).then_some(next_anchor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::test_utils::child_header;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn requests_anchors_of_large_gaps() {
+        assert!(Skeleton::new(100, 90, 10).next_request(90).is_none());
+
+        let mut skeleton = Skeleton::new(1_000, 0, 10);
+        assert!(!skeleton.is_ready(1_000));
+        let request = skeleton.next_request(0).unwrap();
+        assert_eq!(request.start, 1_000u64.into());
+        assert_eq!(request.limit, 100);
+        assert_eq!(request.skip, 9);
+
+        // a short response
+        let headers = (0..40)
+            .map(|i| Header { number: 1_000 - i * 10, ..Default::default() })
+            .collect::<Vec<_>>();
+        skeleton.on_response(&request, &headers, PeerId::random(), 0).unwrap();
+        assert!(skeleton.is_ready(1_000));
+        assert!(skeleton.is_ready(610));
+        assert!(!skeleton.is_ready(600));
+        let request = skeleton.next_request(0).unwrap();
+        assert_eq!(request.start, 600u64.into());
+        assert_eq!(request.limit, 60);
+
+        let mut gapped = (0..60)
+            .map(|i| Header { number: 600 - i * 10, ..Default::default() })
+            .collect::<Vec<_>>();
+        gapped.remove(1);
+        assert_matches!(
+            skeleton.on_response(&request, &gapped, PeerId::random(), 0),
+            Err(DownloadError::HeadersResponseStartBlockMismatch(_))
+        );
+    }
+
+    #[test]
+    fn validates_ranges_against_anchors() {
+        let lower_anchor = SealedHeader::default();
+        let lower = child_header(&lower_anchor);
+        let upper_anchor = child_header(&lower);
+        let peer_id = PeerId::random();
+
+        let mut skeleton = Skeleton::new(2, 0, 2);
+        skeleton.anchors.insert(2, (upper_anchor.hash(), peer_id));
+        skeleton.anchors.insert(0, (lower_anchor.hash(), peer_id));
+
+        let range = vec![upper_anchor.as_ref().clone(), lower.as_ref().clone()];
+        assert!(skeleton.validate(&range).is_ok());
+
+        let mut forked = lower.as_ref().clone();
+        forked.parent_hash = B256::random();
+        assert_matches!(
+            skeleton.validate(&[upper_anchor.as_ref().clone(), forked]),
+            Err(DownloadError::SkeletonMismatch { number: 0, .. })
+        );
+
+        assert_eq!(skeleton.on_validated(&upper_anchor), None);
+        assert_eq!(skeleton.on_validated(&lower), None);
+        assert_eq!(skeleton.on_validated(&child_header(&upper_anchor)), None);
+        skeleton.anchors.insert(3, (B256::random(), peer_id));
+        assert_eq!(skeleton.on_validated(&child_header(&upper_anchor)), Some(peer_id));
+    }
+}
@@ -51,5 +51,11 @@ pub mod file_codec;
 /// Enables decoding and encoding `HackReceipt` type. See <https://github.com/testinprod-io/op-geth/pull/1>.
 pub mod file_codec_ovm_receipt;
 
+/// Module with a codec for reading and encoding receipts in files, for any chain.
+///
+/// Enables decoding and encoding receipts, prefixed with their block number, using the network
+/// encoding of `Receipt`.
+pub mod file_codec_receipt;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -127,7 +127,9 @@ impl HeaderDownloaderMetrics {
     pub fn increment_errors(&self, error: &DownloadError) {
         match error {
             DownloadError::Timeout => self.timeout_errors.increment(1),
-            DownloadError::HeaderValidation { .. } => self.validation_errors.increment(1),
+            DownloadError::HeaderValidation { .. } | DownloadError::SkeletonMismatch { .. } => {
+                self.validation_errors.increment(1)
+            }
             _error => self.unexpected_errors.increment(1),
         }
     }
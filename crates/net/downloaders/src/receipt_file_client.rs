@@ -134,9 +134,16 @@ where
 
                         if block_number == number {
                             receipts_for_block.push(Some(receipt));
+                        } else if number < block_number {
+                            return Err(FileClientError::Custom(
+                                "receipts in file are not ordered by block number",
+                            ))
                         } else {
                             receipts.push(receipts_for_block);
 
+                            // blocks skipped in the file have no receipts
+                            receipts.extend((block_number + 1..number).map(|_| vec![]));
+
                             // next block
                             block_number = number;
                             receipts_for_block = vec![Some(receipt)];
@@ -220,19 +227,23 @@ pub struct ReceiptWithBlockNumber {
 
 #[cfg(test)]
 mod test {
-    use reth_primitives::hex;
+    use reth_primitives::{bytes::BytesMut, hex};
     use reth_tracing::init_test_tracing;
-
-    use crate::file_codec_ovm_receipt::{
-        test::{
-            receipt_block_1 as op_mainnet_receipt_block_1,
-            receipt_block_2 as op_mainnet_receipt_block_2,
-            receipt_block_3 as op_mainnet_receipt_block_3,
-            HACK_RECEIPT_ENCODED_BLOCK_1 as HACK_RECEIPT_ENCODED_BLOCK_1_OP_MAINNET,
-            HACK_RECEIPT_ENCODED_BLOCK_2 as HACK_RECEIPT_ENCODED_BLOCK_2_OP_MAINNET,
-            HACK_RECEIPT_ENCODED_BLOCK_3 as HACK_RECEIPT_ENCODED_BLOCK_3_OP_MAINNET,
+    use tokio_util::codec::Encoder;
+
+    use crate::{
+        file_codec_ovm_receipt::{
+            test::{
+                receipt_block_1 as op_mainnet_receipt_block_1,
+                receipt_block_2 as op_mainnet_receipt_block_2,
+                receipt_block_3 as op_mainnet_receipt_block_3,
+                HACK_RECEIPT_ENCODED_BLOCK_1 as HACK_RECEIPT_ENCODED_BLOCK_1_OP_MAINNET,
+                HACK_RECEIPT_ENCODED_BLOCK_2 as HACK_RECEIPT_ENCODED_BLOCK_2_OP_MAINNET,
+                HACK_RECEIPT_ENCODED_BLOCK_3 as HACK_RECEIPT_ENCODED_BLOCK_3_OP_MAINNET,
+            },
+            HackReceiptFileCodec,
         },
-        HackReceiptFileCodec,
+        file_codec_receipt::ReceiptFileCodec,
     };
 
     use super::*;
@@ -336,4 +347,31 @@ mod test {
         assert_eq!(op_mainnet_receipt_block_2().receipt, receipts[2][1].clone().unwrap());
         assert_eq!(op_mainnet_receipt_block_3().receipt, receipts[3][0].clone().unwrap());
     }
+
+    #[tokio::test]
+    async fn skipped_blocks_have_no_receipts() {
+        init_test_tracing();
+
+        // receipts for blocks 1 and 3, block 2 is skipped
+        let mut encoded_receipts = BytesMut::new();
+        for receipt in [op_mainnet_receipt_block_1(), op_mainnet_receipt_block_3()] {
+            ReceiptFileCodec.encode(receipt, &mut encoded_receipts).unwrap();
+        }
+
+        let encoded_byte_len = encoded_receipts.len() as u64;
+        let reader = &mut &encoded_receipts[..];
+
+        let (
+            ReceiptFileClient { receipts, first_block, total_receipts, _marker },
+            _remaining_bytes,
+        ) = ReceiptFileClient::<ReceiptFileCodec>::from_reader(reader, encoded_byte_len)
+            .await
+            .unwrap();
+
+        assert_eq!(2, total_receipts);
+        assert_eq!(1, first_block);
+        assert_eq!(op_mainnet_receipt_block_1().receipt, receipts[0][0].clone().unwrap());
+        assert!(receipts[1].is_empty());
+        assert_eq!(op_mainnet_receipt_block_3().receipt, receipts[2][0].clone().unwrap());
+    }
 }
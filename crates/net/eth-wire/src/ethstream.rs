@@ -189,6 +189,10 @@ where
 pub struct EthStream<S> {
     /// Negotiated eth version.
     version: EthVersion,
+    /// Length of the last frame read from the stream.
+    last_received_len: usize,
+    /// Length of the last frame written to the sink.
+    last_sent_len: usize,
     #[pin]
     inner: S,
 }
@@ -198,7 +202,7 @@ impl<S> EthStream<S> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { version, inner }
+        Self { version, last_received_len: 0, last_sent_len: 0, inner }
     }
 
     /// Returns the eth version.
@@ -207,6 +211,18 @@ impl<S> EthStream<S> {
         self.version
     }
 
+    /// Returns the length of the last frame read from the stream.
+    #[inline]
+    pub const fn last_received_len(&self) -> usize {
+        self.last_received_len
+    }
+
+    /// Returns the length of the last frame written to the sink.
+    #[inline]
+    pub const fn last_sent_len(&self) -> usize {
+        self.last_sent_len
+    }
+
     /// Returns the underlying stream.
     #[inline]
     pub const fn inner(&self) -> &S {
@@ -236,9 +252,9 @@ where
         &mut self,
         item: EthBroadcastMessage,
     ) -> Result<(), EthStreamError> {
-        self.inner.start_send_unpin(Bytes::from(alloy_rlp::encode(
-            ProtocolBroadcastMessage::from(item),
-        )))?;
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolBroadcastMessage::from(item)));
+        self.last_sent_len = bytes.len();
+        self.inner.start_send_unpin(bytes)?;
 
         Ok(())
     }
@@ -260,6 +276,8 @@ where
             None => return Poll::Ready(None),
         };
 
+        *this.last_received_len = bytes.len();
+
         if bytes.len() > MAX_MESSAGE_SIZE {
             return Poll::Ready(Some(Err(EthStreamError::MessageTooBig(bytes.len()))))
         }
@@ -316,9 +334,10 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
         }
 
-        self.project()
-            .inner
-            .start_send(Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item))))?;
+        let this = self.project();
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item)));
+        *this.last_sent_len = bytes.len();
+        this.inner.start_send(bytes)?;
 
         Ok(())
     }
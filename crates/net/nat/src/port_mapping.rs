@@ -0,0 +1,527 @@
+//! Port mapping on the local gateway, via `UPnP` IGD or NAT-PMP.
+//!
+//! Opens the ports of the node on the gateway so that peers can reach it from outside the local
+//! network. Mappings are leased, so [`PortMapper::run`] keeps renewing them until it's told to
+//! shut down, at which point the mappings are removed again.
+
+use crate::NatResolver;
+use futures_util::future::{select, Either};
+use std::{
+    fmt,
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+    pin::pin,
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+use tracing::{debug, trace, warn};
+
+/// Default lease duration of a port mapping.
+///
+/// Default is 1 hour. Mappings are renewed at half of the lease duration.
+pub const DEFAULT_PORT_MAPPING_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// Description set on `UPnP` port mappings.
+const PORT_MAPPING_DESCRIPTION: &str = "reth";
+
+/// Port of the NAT-PMP service on the gateway.
+const NATPMP_PORT: u16 = 5351;
+
+/// Multicast address `UPnP` devices listen on for SSDP discovery requests.
+const SSDP_MULTICAST_ADDR: SocketAddrV4 =
+    SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+
+/// How long to wait for responses from the gateway.
+const GATEWAY_RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Number of attempts made for a NAT-PMP request, doubling the timeout after each attempt.
+const NATPMP_ATTEMPTS: u32 = 4;
+
+/// Transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortMappingProtocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl PortMappingProtocol {
+    const fn natpmp_opcode(self) -> u8 {
+        match self {
+            Self::Udp => 1,
+            Self::Tcp => 2,
+        }
+    }
+}
+
+impl fmt::Display for PortMappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp => f.write_str("TCP"),
+            Self::Udp => f.write_str("UDP"),
+        }
+    }
+}
+
+/// A port to map on the gateway. The external port is requested to be the same as the local port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortMapping {
+    /// Transport protocol.
+    pub protocol: PortMappingProtocol,
+    /// Local port, which is also requested as external port.
+    pub port: u16,
+}
+
+impl PortMapping {
+    /// Returns a new TCP [`PortMapping`].
+    pub const fn tcp(port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Tcp, port }
+    }
+
+    /// Returns a new UDP [`PortMapping`].
+    pub const fn udp(port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Udp, port }
+    }
+}
+
+/// Errors that can occur when mapping ports on the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// The [`NatResolver`] doesn't support port mapping.
+    #[error("nat resolver {0} doesn't support port mapping")]
+    Unsupported(NatResolver),
+    /// The default gateway of the local network could not be determined.
+    #[error("default gateway not found")]
+    GatewayNotFound,
+    /// No `UPnP` internet gateway device answered the discovery request.
+    #[error("no UPnP internet gateway device found")]
+    NoIgdFound,
+    /// The `UPnP` device description doesn't contain a WAN connection service.
+    #[error("UPnP device at {0} has no WAN connection service")]
+    NoWanService(String),
+    /// The gateway responded with an error.
+    #[error("gateway error: {0}")]
+    Gateway(String),
+    /// The gateway sent a response that couldn't be parsed.
+    #[error("invalid response from gateway")]
+    InvalidResponse,
+    /// The gateway didn't respond in time.
+    #[error("gateway didn't respond")]
+    Timeout,
+    /// Error during HTTP request to `UPnP` device.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// Error on the UDP socket.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Maps a set of ports on the local gateway, using the configured [`NatResolver`].
+///
+/// Supported resolvers are [`NatResolver::Upnp`] and [`NatResolver::NatPmp`].
+#[derive(Debug, Clone)]
+pub struct PortMapper {
+    resolver: NatResolver,
+    mappings: Vec<PortMapping>,
+    lifetime: Duration,
+}
+
+impl PortMapper {
+    /// Returns a new [`PortMapper`] for the given ports, using [`DEFAULT_PORT_MAPPING_LIFETIME`].
+    pub fn new(resolver: NatResolver, mappings: impl IntoIterator<Item = PortMapping>) -> Self {
+        Self {
+            resolver,
+            mappings: mappings.into_iter().collect(),
+            lifetime: DEFAULT_PORT_MAPPING_LIFETIME,
+        }
+    }
+
+    /// Sets the lease duration of the mappings.
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Returns `true` if the resolver supports port mapping.
+    pub const fn is_supported(resolver: NatResolver) -> bool {
+        matches!(resolver, NatResolver::Upnp | NatResolver::NatPmp)
+    }
+
+    /// Maps all ports on the gateway. Returns the external IP address of the gateway.
+    pub async fn map_all(&self) -> Result<IpAddr, PortMappingError> {
+        let gateway = Gateway::discover(self.resolver).await?;
+        for mapping in &self.mappings {
+            gateway.add_port_mapping(*mapping, self.lifetime).await?;
+            debug!(target: "net::nat", ?mapping, resolver=%self.resolver, "mapped port on gateway");
+        }
+        gateway.external_ip().await
+    }
+
+    /// Removes all port mappings from the gateway.
+    pub async fn unmap_all(&self) -> Result<(), PortMappingError> {
+        let gateway = Gateway::discover(self.resolver).await?;
+        for mapping in &self.mappings {
+            gateway.remove_port_mapping(*mapping).await?;
+            debug!(target: "net::nat", ?mapping, resolver=%self.resolver, "removed port mapping from gateway");
+        }
+        Ok(())
+    }
+
+    /// Maps all ports and renews the mappings at half of their lifetime, until `shutdown`
+    /// resolves. The mappings are then removed from the gateway.
+    pub async fn run(self, shutdown: impl Future<Output = ()>) {
+        let mut shutdown = pin!(shutdown);
+        loop {
+            if let Err(err) = self.map_all().await {
+                warn!(target: "net::nat", %err, resolver=%self.resolver, "failed to map ports on gateway");
+            }
+            let renew = pin!(tokio::time::sleep(self.lifetime / 2));
+            if let Either::Right(_) = select(renew, shutdown.as_mut()).await {
+                break
+            }
+        }
+        if let Err(err) = self.unmap_all().await {
+            debug!(target: "net::nat", %err, "failed to remove port mappings from gateway");
+        }
+    }
+}
+
+/// A gateway that supports port mapping.
+#[derive(Debug)]
+enum Gateway {
+    /// NAT-PMP service at the given address.
+    NatPmp(SocketAddr),
+    /// `UPnP` WAN connection service.
+    Upnp {
+        /// URL of the control endpoint of the service.
+        control_url: reqwest::Url,
+        /// URN of the service.
+        service_type: String,
+        /// Local address that's reachable from the gateway.
+        local_ip: IpAddr,
+    },
+}
+
+impl Gateway {
+    async fn discover(resolver: NatResolver) -> Result<Self, PortMappingError> {
+        match resolver {
+            NatResolver::NatPmp => {
+                let gateway = default_gateway().ok_or(PortMappingError::GatewayNotFound)?;
+                Ok(Self::NatPmp(SocketAddr::new(gateway.into(), NATPMP_PORT)))
+            }
+            NatResolver::Upnp => discover_upnp_gateway().await,
+            resolver => Err(PortMappingError::Unsupported(resolver)),
+        }
+    }
+
+    async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        match self {
+            Self::NatPmp(gateway) => {
+                let response = natpmp_request(*gateway, &[0, 0], 12).await?;
+                Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]).into())
+            }
+            Self::Upnp { control_url, service_type, .. } => {
+                let response =
+                    soap_request(control_url, service_type, "GetExternalIPAddress", &[]).await?;
+                xml_element(&response, "NewExternalIPAddress")
+                    .and_then(|ip| ip.trim().parse().ok())
+                    .ok_or(PortMappingError::InvalidResponse)
+            }
+        }
+    }
+
+    async fn add_port_mapping(
+        &self,
+        mapping: PortMapping,
+        lifetime: Duration,
+    ) -> Result<(), PortMappingError> {
+        match self {
+            Self::NatPmp(gateway) => {
+                let request = natpmp_mapping_request(mapping, lifetime.as_secs() as u32);
+                natpmp_request(*gateway, &request, 16).await?;
+            }
+            Self::Upnp { control_url, service_type, local_ip } => {
+                let port = mapping.port.to_string();
+                let protocol = mapping.protocol.to_string();
+                let local_ip = local_ip.to_string();
+                let lifetime = lifetime.as_secs().to_string();
+                soap_request(
+                    control_url,
+                    service_type,
+                    "AddPortMapping",
+                    &[
+                        ("NewRemoteHost", ""),
+                        ("NewExternalPort", &port),
+                        ("NewProtocol", &protocol),
+                        ("NewInternalPort", &port),
+                        ("NewInternalClient", &local_ip),
+                        ("NewEnabled", "1"),
+                        ("NewPortMappingDescription", PORT_MAPPING_DESCRIPTION),
+                        ("NewLeaseDuration", &lifetime),
+                    ],
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove_port_mapping(&self, mapping: PortMapping) -> Result<(), PortMappingError> {
+        match self {
+            Self::NatPmp(gateway) => {
+                // a lifetime of zero deletes the mapping
+                let request = natpmp_mapping_request(mapping, 0);
+                natpmp_request(*gateway, &request, 16).await?;
+            }
+            Self::Upnp { control_url, service_type, .. } => {
+                let port = mapping.port.to_string();
+                let protocol = mapping.protocol.to_string();
+                soap_request(
+                    control_url,
+                    service_type,
+                    "DeletePortMapping",
+                    &[
+                        ("NewRemoteHost", ""),
+                        ("NewExternalPort", &port),
+                        ("NewProtocol", &protocol),
+                    ],
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the external IP address reported by the gateway, using the given [`NatResolver`].
+pub async fn gateway_external_ip(resolver: NatResolver) -> Result<IpAddr, PortMappingError> {
+    Gateway::discover(resolver).await?.external_ip().await
+}
+
+/// Returns the IPv4 address of the default gateway, read from the kernel routing table.
+///
+/// Only supported on linux.
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&routes)
+}
+
+/// Parses the default gateway from the contents of `/proc/net/route`.
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let (_iface, destination, gateway) = (fields.next()?, fields.next()?, fields.next()?);
+        if destination != "00000000" {
+            return None
+        }
+        // addresses are printed as hex of the address in network byte order, read as native u32
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(Ipv4Addr::from(gateway.to_ne_bytes())).filter(|ip| !ip.is_unspecified())
+    })
+}
+
+/// Encodes a NAT-PMP mapping request, see <https://datatracker.ietf.org/doc/html/rfc6886#section-3.3>.
+fn natpmp_mapping_request(mapping: PortMapping, lifetime: u32) -> [u8; 12] {
+    let mut request = [0u8; 12];
+    request[1] = mapping.protocol.natpmp_opcode();
+    request[4..6].copy_from_slice(&mapping.port.to_be_bytes());
+    if lifetime > 0 {
+        request[6..8].copy_from_slice(&mapping.port.to_be_bytes());
+    }
+    request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+    request
+}
+
+/// Sends a NAT-PMP request to the gateway and returns the response, after checking the result
+/// code.
+async fn natpmp_request(
+    gateway: SocketAddr,
+    request: &[u8],
+    response_len: usize,
+) -> Result<Vec<u8>, PortMappingError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect(gateway).await?;
+
+    let mut timeout = Duration::from_millis(250);
+    let mut buf = [0u8; 16];
+    for _ in 0..NATPMP_ATTEMPTS {
+        socket.send(request).await?;
+        match tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+            Ok(len) => {
+                let len = len?;
+                // response opcode is request opcode + 128
+                if len < response_len || buf[0] != 0 || buf[1] != request[1] + 128 {
+                    return Err(PortMappingError::InvalidResponse)
+                }
+                let result = u16::from_be_bytes([buf[2], buf[3]]);
+                if result != 0 {
+                    return Err(PortMappingError::Gateway(format!("NAT-PMP result code {result}")))
+                }
+                return Ok(buf[..len].to_vec())
+            }
+            Err(_) => timeout *= 2,
+        }
+    }
+
+    Err(PortMappingError::Timeout)
+}
+
+/// Discovers an internet gateway device with SSDP and looks up its WAN connection service.
+async fn discover_upnp_gateway() -> Result<Gateway, PortMappingError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\n\
+         HOST: {SSDP_MULTICAST_ADDR}\r\n\
+         ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+         MAN: \"ssdp:discover\"\r\n\
+         MX: 2\r\n\r\n"
+    );
+    socket.send_to(request.as_bytes(), SSDP_MULTICAST_ADDR).await?;
+
+    let mut buf = [0u8; 2048];
+    let (len, from) = tokio::time::timeout(GATEWAY_RESPONSE_TIMEOUT, socket.recv_from(&mut buf))
+        .await
+        .map_err(|_| PortMappingError::NoIgdFound)??;
+    let response = String::from_utf8_lossy(&buf[..len]);
+    trace!(target: "net::nat", %from, %response, "received SSDP response");
+
+    let location = response
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim().eq_ignore_ascii_case("location").then(|| value.trim().to_string())
+        })
+        .ok_or(PortMappingError::InvalidResponse)?;
+    let location = reqwest::Url::parse(&location).map_err(|_| PortMappingError::InvalidResponse)?;
+
+    let description = reqwest::get(location.clone()).await?.error_for_status()?.text().await?;
+    let (service_type, control_url) = wan_connection_service(&description)
+        .ok_or_else(|| PortMappingError::NoWanService(location.to_string()))?;
+    let control_url = location.join(&control_url).map_err(|_| PortMappingError::InvalidResponse)?;
+
+    // the address of the interface the gateway is reached through
+    let local_socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    local_socket.connect(from).await?;
+    let local_ip = local_socket.local_addr()?.ip();
+
+    Ok(Gateway::Upnp { control_url, service_type, local_ip })
+}
+
+/// Returns the service type and control URL of the first WAN IP or PPP connection service in a
+/// `UPnP` device description.
+fn wan_connection_service(description: &str) -> Option<(String, String)> {
+    description.split("<service>").skip(1).find_map(|service| {
+        let service_type = xml_element(service, "serviceType")?;
+        if !service_type.contains(":WANIPConnection:") &&
+            !service_type.contains(":WANPPPConnection:")
+        {
+            return None
+        }
+        let control_url = xml_element(service, "controlURL")?;
+        Some((service_type.trim().to_string(), control_url.trim().to_string()))
+    })
+}
+
+/// Returns the text content of the first element with the given name, ignoring namespaces.
+fn xml_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("{name}>"))? + name.len() + 1;
+    let end = start + xml[start..].find("</")?;
+    Some(&xml[start..end])
+}
+
+/// Invokes an action on a `UPnP` service and returns the response body.
+async fn soap_request(
+    control_url: &reqwest::Url,
+    service_type: &str,
+    action: &str,
+    args: &[(&str, &str)],
+) -> Result<String, PortMappingError> {
+    let args =
+        args.iter().map(|(name, value)| format!("<{name}>{value}</{name}>")).collect::<String>();
+    let body = format!(
+        "<?xml version=\"1.0\"?>\
+         <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+         <s:Body><u:{action} xmlns:u=\"{service_type}\">{args}</u:{action}></s:Body>\
+         </s:Envelope>"
+    );
+
+    let response = reqwest::Client::new()
+        .post(control_url.clone())
+        .header("Content-Type", "text/xml; charset=\"utf-8\"")
+        .header("SOAPAction", format!("\"{service_type}#{action}\""))
+        .body(body)
+        .timeout(GATEWAY_RESPONSE_TIMEOUT)
+        .send()
+        .await?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        let description = xml_element(&text, "errorDescription").unwrap_or(status.as_str());
+        return Err(PortMappingError::Gateway(format!("{action} failed: {description}")))
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_route_table() {
+        let routes =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+                      eth0\t0000A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0\n\
+                      eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\t0\t0\t0\n";
+        let expected = Ipv4Addr::from(u32::from_str_radix("0101A8C0", 16).unwrap().to_ne_bytes());
+        assert_eq!(parse_default_gateway(routes), Some(expected));
+        if cfg!(target_endian = "little") {
+            assert_eq!(expected, Ipv4Addr::new(192, 168, 1, 1));
+        }
+    }
+
+    #[test]
+    fn encode_natpmp_mapping_request() {
+        let request = natpmp_mapping_request(PortMapping::tcp(30303), 3600);
+        assert_eq!(request, [0, 2, 0, 0, 0x76, 0x5f, 0x76, 0x5f, 0, 0, 0x0e, 0x10]);
+
+        // deletion requests external port 0
+        let request = natpmp_mapping_request(PortMapping::udp(30303), 0);
+        assert_eq!(request, [0, 1, 0, 0, 0x76, 0x5f, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn find_wan_connection_service() {
+        let description = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+        <controlURL>/ctl/L3F</controlURL>
+      </service>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+        <serviceId>urn:upnp-org:serviceId:WANIPConn1</serviceId>
+        <controlURL>/ctl/IPConn</controlURL>
+      </service>
+    </serviceList>
+  </device>
+</root>"#;
+        assert_eq!(
+            wan_connection_service(description),
+            Some((
+                "urn:schemas-upnp-org:service:WANIPConnection:1".to_string(),
+                "/ctl/IPConn".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_external_ip_response() {
+        let response = r#"<?xml version="1.0"?><s:Envelope><s:Body><u:GetExternalIPAddressResponse xmlns:u="urn:schemas-upnp-org:service:WANIPConnection:1"><NewExternalIPAddress>203.0.113.7</NewExternalIPAddress></u:GetExternalIPAddressResponse></s:Body></s:Envelope>"#;
+        assert_eq!(xml_element(response, "NewExternalIPAddress"), Some("203.0.113.7"));
+    }
+}
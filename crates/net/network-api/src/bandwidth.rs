@@ -0,0 +1,57 @@
+//! Types for weighting bandwidth between the different kinds of network traffic.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of network traffic that is scheduled by the bandwidth scheduler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BandwidthClass {
+    /// Historical sync, i.e. headers and bodies downloaded during backfill.
+    Sync,
+    /// Live gossip, i.e. transactions fetched from peers.
+    Gossip,
+    /// Serving `eth` requests of peers, i.e. headers, bodies and receipts.
+    Serving,
+}
+
+impl BandwidthClass {
+    /// All classes.
+    pub const ALL: [Self; 3] = [Self::Sync, Self::Gossip, Self::Serving];
+}
+
+/// Relative weights of the [`BandwidthClass`]es.
+///
+/// Each class is allotted a share of the bandwidth budget proportional to its weight. A class
+/// with weight zero is never throttled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandwidthWeights {
+    /// Weight of [`BandwidthClass::Sync`].
+    pub sync: u32,
+    /// Weight of [`BandwidthClass::Gossip`].
+    pub gossip: u32,
+    /// Weight of [`BandwidthClass::Serving`].
+    pub serving: u32,
+}
+
+impl BandwidthWeights {
+    /// Returns the weight of the given class.
+    pub const fn get(&self, class: BandwidthClass) -> u32 {
+        match class {
+            BandwidthClass::Sync => self.sync,
+            BandwidthClass::Gossip => self.gossip,
+            BandwidthClass::Serving => self.serving,
+        }
+    }
+
+    /// Returns the sum of all weights.
+    pub const fn total(&self) -> u64 {
+        self.sync as u64 + self.gossip as u64 + self.serving as u64
+    }
+}
+
+impl Default for BandwidthWeights {
+    fn default() -> Self {
+        // favour block and transaction propagation over backfill
+        Self { sync: 2, gossip: 5, serving: 3 }
+    }
+}
@@ -14,9 +14,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
+pub use bandwidth::{BandwidthClass, BandwidthWeights};
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
-use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthMessageID, EthVersion, Status};
 use reth_network_peers::NodeRecord;
 use serde::{Deserialize, Serialize};
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
@@ -24,6 +25,8 @@ use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
 
+/// Bandwidth scheduling
+pub mod bandwidth;
 /// Network Error
 pub mod error;
 /// Reputation score
@@ -48,6 +51,18 @@ pub trait NetworkInfo: Send + Sync {
 
     /// Returns `true` when the node is undergoing the very first Pipeline sync.
     fn is_initially_syncing(&self) -> bool;
+
+    /// Returns the current weights of the bandwidth scheduler, or `None` if bandwidth scheduling
+    /// is disabled.
+    fn bandwidth_weights(&self) -> Option<BandwidthWeights> {
+        None
+    }
+
+    /// Updates the weights of the bandwidth scheduler. Returns `false` if bandwidth scheduling is
+    /// disabled.
+    fn set_bandwidth_weights(&self, _weights: BandwidthWeights) -> bool {
+        false
+    }
 }
 
 /// Provides general purpose information about Peers in the network.
@@ -121,6 +136,15 @@ pub trait Peers: PeersInfo {
     /// Returns the rpc [`PeerInfo`] for all connected peers.
     fn get_all_peers(&self) -> impl Future<Output = Result<Vec<PeerInfo>, NetworkError>> + Send;
 
+    /// Returns the node records and the [`StaticPeerHealth`] of all configured static nodes,
+    /// connected or not.
+    fn get_static_peers(
+        &self,
+    ) -> impl Future<Output = Result<Vec<(NodeRecord, StaticPeerHealth)>, NetworkError>> + Send;
+
+    /// Returns the [`PeerStats`] of all connected peers.
+    fn get_peer_stats(&self) -> impl Future<Output = Result<Vec<PeerStats>, NetworkError>> + Send;
+
     /// Returns the rpc [`PeerInfo`] for the given peer id.
     ///
     /// Returns `None` if the peer is not connected.
@@ -215,6 +239,86 @@ pub struct PeerInfo {
     pub kind: PeerKind,
 }
 
+/// The health of a configured static node, which is redialed whenever it's disconnected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticPeerHealth {
+    /// The number of consecutive failed connection attempts since the last session.
+    pub failed_attempts: u8,
+    /// The number of seconds until the peer is redialed, if it's backed off.
+    pub next_dial_in_secs: Option<u64>,
+    /// The error of the last failed connection attempt or session.
+    pub last_error: Option<String>,
+}
+
+/// A peer returned by `admin_peers`.
+///
+/// This is the rpc [`PeerInfo`](alloy_rpc_types_admin::PeerInfo) of a connected peer or of a
+/// disconnected static node, extended by the [`StaticPeerHealth`] of configured static nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminPeerInfo {
+    /// The rpc info of the peer.
+    #[serde(flatten)]
+    pub info: alloy_rpc_types_admin::PeerInfo,
+    /// The health of the peer if it's a configured static node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub static_health: Option<StaticPeerHealth>,
+}
+
+/// The protocol and bandwidth statistics of an active peer session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerStats {
+    /// The identifier of the remote peer.
+    pub peer_id: PeerId,
+    /// The client's name and version.
+    pub client_version: String,
+    /// The number of seconds since the session was established.
+    pub session_duration_secs: u64,
+    /// The `eth` messages exchanged with the peer, for every type of message that was sent or
+    /// received.
+    pub messages: Vec<MessageStats>,
+    /// The requests sent to the peer, for every type of request that was sent.
+    pub requests: Vec<RequestStats>,
+}
+
+/// The number and size of the `eth` messages of a type exchanged with a peer.
+///
+/// Sizes are the RLP encoded sizes of the messages, before compression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageStats {
+    /// The type of the messages.
+    pub message: EthMessageID,
+    /// The number of messages received from the peer.
+    pub received: u64,
+    /// The total size of the messages received from the peer, in bytes.
+    pub received_bytes: u64,
+    /// The number of messages sent to the peer.
+    pub sent: u64,
+    /// The total size of the messages sent to the peer, in bytes.
+    pub sent_bytes: u64,
+}
+
+/// The responses and latencies of the requests of a type sent to a peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestStats {
+    /// The type of the requests.
+    pub request: EthMessageID,
+    /// The number of requests sent to the peer.
+    pub sent: u64,
+    /// The number of requests the peer answered with a matching response in time.
+    pub answered: u64,
+    /// The number of requests that timed out.
+    pub timed_out: u64,
+    /// The average time the peer took to answer, in milliseconds.
+    pub avg_latency_ms: u64,
+    /// The longest time the peer took to answer, in milliseconds.
+    pub max_latency_ms: u64,
+}
+
 /// The direction of the connection.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Direction {
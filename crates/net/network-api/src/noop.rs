@@ -4,8 +4,8 @@
 //! generic over it.
 
 use crate::{
-    NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerKind, Peers, PeersInfo,
-    Reputation, ReputationChangeKind,
+    NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerKind, PeerStats, Peers,
+    PeersInfo, Reputation, ReputationChangeKind, StaticPeerHealth,
 };
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
@@ -88,6 +88,14 @@ impl Peers for NoopNetwork {
         Ok(vec![])
     }
 
+    async fn get_static_peers(&self) -> Result<Vec<(NodeRecord, StaticPeerHealth)>, NetworkError> {
+        Ok(vec![])
+    }
+
+    async fn get_peer_stats(&self) -> Result<Vec<PeerStats>, NetworkError> {
+        Ok(vec![])
+    }
+
     async fn get_peer_by_id(&self, _peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError> {
         Ok(None)
     }
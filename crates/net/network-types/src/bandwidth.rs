@@ -0,0 +1,33 @@
+//! Configuration for the bandwidth scheduler.
+
+pub use reth_network_api::BandwidthWeights;
+
+/// Configuration for weighting bandwidth between historical sync, live gossip and serving
+/// requests of peers.
+///
+/// By default, no budget is set and traffic is not throttled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BandwidthConfig {
+    /// Total budget in bytes per second, shared between the
+    /// [`BandwidthClass`](reth_network_api::BandwidthClass)es according to their weights.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_bytes_per_second: Option<u64>,
+    /// Relative weights of the traffic classes.
+    pub weights: BandwidthWeights,
+}
+
+impl BandwidthConfig {
+    /// Sets the total budget in bytes per second.
+    pub const fn with_max_bytes_per_second(mut self, max_bytes_per_second: u64) -> Self {
+        self.max_bytes_per_second = Some(max_bytes_per_second);
+        self
+    }
+
+    /// Sets the weights of the traffic classes.
+    pub const fn with_weights(mut self, weights: BandwidthWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
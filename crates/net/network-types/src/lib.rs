@@ -19,6 +19,9 @@ pub use peers::{ConnectionsConfig, PeersConfig, ReputationChangeWeights};
 pub mod session;
 pub use session::{SessionLimits, SessionsConfig};
 
+pub mod bandwidth;
+pub use bandwidth::{BandwidthConfig, BandwidthWeights};
+
 /// [`BackoffKind`] definition.
 mod backoff;
 pub use backoff::BackoffKind;
@@ -2,9 +2,9 @@
 
 use crate::{BackoffKind, ReputationChangeWeights};
 use reth_net_banlist::BanList;
-use reth_network_peers::NodeRecord;
+use reth_network_peers::{NodeRecord, PeerId};
 use std::{
-    collections::HashSet,
+    collections::{BTreeSet, HashSet},
     io::{self, ErrorKind},
     path::Path,
     time::Duration,
@@ -64,6 +64,17 @@ impl PeerBackoffDurations {
         now + backoff_time.min(self.max)
     }
 
+    /// Returns the timestamp until which a peer should be backed off after the given number of
+    /// consecutive failed connection attempts.
+    ///
+    /// The backoff duration starts at the low duration and doubles with every failed attempt,
+    /// capped by the configured maximum backoff duration.
+    pub fn exponential_backoff_until(&self, failed_attempts: u8) -> std::time::Instant {
+        let exponent = failed_attempts.saturating_sub(1).min(31);
+        let backoff_time = self.low.saturating_mul(1 << exponent);
+        std::time::Instant::now() + backoff_time.min(self.max)
+    }
+
     /// Returns durations for testing.
     #[cfg(any(test, feature = "test-utils"))]
     pub const fn test() -> Self {
@@ -126,6 +137,16 @@ pub struct PeersConfig {
     /// Connect to or accept from trusted nodes only?
     #[cfg_attr(feature = "serde", serde(alias = "connect_trusted_nodes_only"))]
     pub trusted_nodes_only: bool,
+    /// Static nodes to always connect to.
+    ///
+    /// Static nodes are never removed from the peer set or banned, they're redialed with an
+    /// exponential backoff whenever they're disconnected.
+    pub static_nodes: HashSet<NodeRecord>,
+    /// Peers that are exempt from pruning.
+    ///
+    /// Protected peers are kept in the peer set when they fail to connect too often, disconnect
+    /// or are removed by discovery, so the node keeps reconnecting to them.
+    pub protected_nodes: BTreeSet<PeerId>,
     /// Maximum number of backoff attempts before we give up on a peer and dropping.
     ///
     /// The max time spent of a peer before it's removed from the set is determined by the
@@ -167,6 +188,8 @@ impl Default for PeersConfig {
             backoff_durations: Default::default(),
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
+            static_nodes: Default::default(),
+            protected_nodes: Default::default(),
             basic_nodes: Default::default(),
             max_backoff_count: 5,
         }
@@ -232,6 +255,18 @@ impl PeersConfig {
         self
     }
 
+    /// Static nodes to always connect to and redial.
+    pub fn with_static_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
+        self.static_nodes = nodes;
+        self
+    }
+
+    /// Peers that are exempt from pruning.
+    pub fn with_protected_nodes(mut self, nodes: BTreeSet<PeerId>) -> Self {
+        self.protected_nodes = nodes;
+        self
+    }
+
     /// Nodes available at launch.
     pub fn with_basic_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
         self.basic_nodes = nodes;
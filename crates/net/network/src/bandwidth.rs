@@ -0,0 +1,174 @@
+//! Bandwidth scheduling between historical sync, live gossip and serving peers.
+//!
+//! Keeps backfill from starving block and transaction propagation on constrained links.
+
+use parking_lot::Mutex;
+use reth_network_api::{BandwidthClass, BandwidthWeights};
+use reth_network_types::BandwidthConfig;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Shares a bandwidth budget between the [`BandwidthClass`]es, proportional to their
+/// [`BandwidthWeights`].
+///
+/// Each class has a token bucket that is refilled at its share of the budget, and that can hold
+/// at most one second worth of its share. Traffic is accounted for after the fact with
+/// [`BandwidthScheduler::consume`], so a bucket can go into debt. New traffic of a class in debt
+/// should be delayed until the debt is paid off, see [`BandwidthScheduler::delay`].
+///
+/// A class with weight zero is never throttled.
+#[derive(Debug, Clone)]
+pub struct BandwidthScheduler {
+    inner: Arc<Mutex<SchedulerInner>>,
+}
+
+impl BandwidthScheduler {
+    /// Creates a new scheduler with the given budget in bytes per second.
+    pub fn new(max_bytes_per_second: u64, weights: BandwidthWeights) -> Self {
+        let now = Instant::now();
+        let mut inner = SchedulerInner {
+            max_bytes_per_second,
+            weights,
+            buckets: [Bucket { tokens: 0., last_refill: now }; 3],
+        };
+        // start with full buckets
+        for class in BandwidthClass::ALL {
+            inner.buckets[class_index(class)].tokens = inner.rate(class);
+        }
+        Self { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// Creates a new scheduler from the given config. Returns `None` if no budget is configured.
+    pub fn from_config(config: &BandwidthConfig) -> Option<Self> {
+        config.max_bytes_per_second.map(|budget| Self::new(budget, config.weights))
+    }
+
+    /// Returns the current weights.
+    pub fn weights(&self) -> BandwidthWeights {
+        self.inner.lock().weights
+    }
+
+    /// Updates the weights. Takes effect immediately for all classes.
+    pub fn set_weights(&self, weights: BandwidthWeights) {
+        let mut inner = self.inner.lock();
+        let now = Instant::now();
+        // settle the buckets at the old rates before switching
+        for class in BandwidthClass::ALL {
+            inner.refill(class, now);
+        }
+        inner.weights = weights;
+    }
+
+    /// Accounts for `bytes` of traffic of the given class.
+    pub fn consume(&self, class: BandwidthClass, bytes: usize) {
+        let mut inner = self.inner.lock();
+        if inner.weights.get(class) == 0 {
+            return
+        }
+        inner.refill(class, Instant::now());
+        inner.buckets[class_index(class)].tokens -= bytes as f64;
+    }
+
+    /// Returns how long new traffic of the given class should be delayed, or `None` if it can
+    /// proceed right away.
+    pub fn delay(&self, class: BandwidthClass) -> Option<Duration> {
+        let mut inner = self.inner.lock();
+        if inner.weights.get(class) == 0 {
+            return None
+        }
+        inner.refill(class, Instant::now());
+        let tokens = inner.buckets[class_index(class)].tokens;
+        if tokens >= 0. {
+            return None
+        }
+        let rate = inner.rate(class);
+        if rate <= 0. {
+            return None
+        }
+        Some(Duration::from_secs_f64(-tokens / rate))
+    }
+
+    /// Waits until new traffic of the given class can proceed.
+    pub async fn ready(&self, class: BandwidthClass) {
+        while let Some(delay) = self.delay(class) {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SchedulerInner {
+    max_bytes_per_second: u64,
+    weights: BandwidthWeights,
+    buckets: [Bucket; 3],
+}
+
+impl SchedulerInner {
+    /// Returns the share of the budget of the given class, in bytes per second.
+    fn rate(&self, class: BandwidthClass) -> f64 {
+        let total = self.weights.total();
+        if total == 0 {
+            return 0.
+        }
+        self.max_bytes_per_second as f64 * self.weights.get(class) as f64 / total as f64
+    }
+
+    fn refill(&mut self, class: BandwidthClass, now: Instant) {
+        let rate = self.rate(class);
+        let bucket = &mut self.buckets[class_index(class)];
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.last_refill = now;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+const fn class_index(class: BandwidthClass) -> usize {
+    match class {
+        BandwidthClass::Sync => 0,
+        BandwidthClass::Gossip => 1,
+        BandwidthClass::Serving => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_class_in_debt() {
+        let weights = BandwidthWeights { sync: 1, gossip: 1, serving: 0 };
+        let scheduler = BandwidthScheduler::new(1_000, weights);
+
+        // each class has a share of 500 bytes per second
+        assert!(scheduler.delay(BandwidthClass::Sync).is_none());
+        scheduler.consume(BandwidthClass::Sync, 1_000);
+        let delay = scheduler.delay(BandwidthClass::Sync).unwrap();
+        assert!(delay <= Duration::from_secs(1) && delay > Duration::from_millis(900));
+
+        // other classes are unaffected
+        assert!(scheduler.delay(BandwidthClass::Gossip).is_none());
+        // weight zero is never throttled
+        scheduler.consume(BandwidthClass::Serving, 1_000_000);
+        assert!(scheduler.delay(BandwidthClass::Serving).is_none());
+    }
+
+    #[test]
+    fn update_weights() {
+        let scheduler = BandwidthScheduler::new(1_000, BandwidthWeights::default());
+        scheduler.consume(BandwidthClass::Sync, 10_000);
+        assert!(scheduler.delay(BandwidthClass::Sync).is_some());
+
+        let weights = BandwidthWeights { sync: 0, ..Default::default() };
+        scheduler.set_weights(weights);
+        assert_eq!(scheduler.weights(), weights);
+        assert!(scheduler.delay(BandwidthClass::Sync).is_none());
+    }
+}
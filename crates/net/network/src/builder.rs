@@ -74,7 +74,9 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let bandwidth = network.handle().bandwidth_scheduler().cloned();
+        let request_handler =
+            EthRequestHandler::new(client, peers, rx).with_bandwidth_scheduler(bandwidth);
         NetworkBuilder { network, request_handler, transactions }
     }
 }
@@ -11,8 +11,9 @@ use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOV
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_net_nat::PortMapper;
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
-use reth_network_types::{PeersConfig, SessionsConfig};
+use reth_network_types::{BandwidthConfig, PeersConfig, SessionsConfig};
 use reth_primitives::{ForkFilter, Head};
 use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -79,6 +80,11 @@ pub struct NetworkConfig<C> {
     pub tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// The [`NatResolver`] to map the listener and discovery ports on the gateway with, if it
+    /// supports port mapping.
+    pub port_mapping: Option<NatResolver>,
+    /// How to share bandwidth between sync, gossip and serving peers.
+    pub bandwidth_config: BandwidthConfig,
 }
 
 // === impl NetworkConfig ===
@@ -185,6 +191,10 @@ pub struct NetworkConfigBuilder {
     block_import: Option<Box<dyn BlockImport>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// The [`NatResolver`] to map ports on the gateway with.
+    port_mapping: Option<NatResolver>,
+    /// How to share bandwidth between sync, gossip and serving peers.
+    bandwidth_config: BandwidthConfig,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -217,6 +227,8 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            port_mapping: None,
+            bandwidth_config: Default::default(),
         }
     }
 
@@ -355,6 +367,21 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Maps the listener and discovery ports on the gateway, if the given resolver supports port
+    /// mapping, i.e. [`NatResolver::Upnp`] or [`NatResolver::NatPmp`].
+    ///
+    /// The mappings are renewed for as long as the network is running, and removed on shutdown.
+    pub fn port_mapping(mut self, resolver: NatResolver) -> Self {
+        self.port_mapping = PortMapper::is_supported(resolver).then_some(resolver);
+        self
+    }
+
+    /// Sets the bandwidth config to use.
+    pub const fn bandwidth_config(mut self, config: BandwidthConfig) -> Self {
+        self.bandwidth_config = config;
+        self
+    }
+
     /// Sets the discv4 config to use.
     pub fn discovery(mut self, builder: Discv4ConfigBuilder) -> Self {
         self.discovery_v4_builder = Some(builder);
@@ -486,17 +513,10 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            port_mapping,
+            bandwidth_config,
         } = self;
 
-        discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
-            if let Some(network_stack_id) = NetworkStackId::id(&chain_spec) {
-                let fork_id = chain_spec.latest_fork_id();
-                builder = builder.fork(network_stack_id, fork_id)
-            }
-
-            builder
-        });
-
         let listener_addr = listener_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS);
 
         let mut hello_message =
@@ -517,6 +537,16 @@ impl NetworkConfigBuilder {
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
 
+        // advertise the fork ID of the head in the discv5 ENR, so that peers can filter on it
+        // before connecting
+        discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
+            if let Some(network_stack_id) = NetworkStackId::id(&chain_spec) {
+                builder = builder.fork(network_stack_id, status.forkid)
+            }
+
+            builder
+        });
+
         // If default DNS config is used then we add the known dns network to bootstrap from
         if let Some(dns_networks) =
             dns_discovery_config.as_mut().and_then(|c| c.bootstrap_dns_networks.as_mut())
@@ -549,6 +579,8 @@ impl NetworkConfigBuilder {
             fork_filter,
             tx_gossip_disabled,
             transactions_manager_config,
+            port_mapping,
+            bandwidth_config,
         }
     }
 }
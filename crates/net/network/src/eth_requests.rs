@@ -1,15 +1,16 @@
 //! Blocks/Headers management for the p2p network.
 
 use crate::{
-    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
-    metrics::EthRequestHandlerMetrics, peers::PeersHandle,
+    bandwidth::BandwidthScheduler, budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    metered_poll_nested_stream_with_budget, metrics::EthRequestHandlerMetrics, peers::PeersHandle,
 };
 use alloy_rlp::Encodable;
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
     Receipts,
 };
+use reth_network_api::BandwidthClass;
 use reth_network_p2p::error::RequestResult;
 use reth_network_peers::PeerId;
 use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection};
@@ -20,7 +21,10 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
-use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio::{
+    sync::{mpsc::Receiver, oneshot},
+    time::Sleep,
+};
 use tokio_stream::wrappers::ReceiverStream;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
@@ -60,6 +64,10 @@ pub struct EthRequestHandler<C> {
     incoming_requests: ReceiverStream<IncomingEthRequest>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// Throttles serving peers, if a bandwidth budget is configured.
+    bandwidth: Option<BandwidthScheduler>,
+    /// Set while serving is throttled, incoming requests are not processed until it fires.
+    throttle: Option<Pin<Box<Sleep>>>,
 }
 
 // === impl EthRequestHandler ===
@@ -71,6 +79,43 @@ impl<C> EthRequestHandler<C> {
             peers,
             incoming_requests: ReceiverStream::new(incoming),
             metrics: Default::default(),
+            bandwidth: None,
+            throttle: None,
+        }
+    }
+
+    /// Sets the [`BandwidthScheduler`] that throttles serving peers.
+    pub fn with_bandwidth_scheduler(mut self, bandwidth: Option<BandwidthScheduler>) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Accounts for a response of the given size.
+    fn on_response_served(&self, bytes: usize) {
+        if let Some(bandwidth) = &self.bandwidth {
+            bandwidth.consume(BandwidthClass::Serving, bytes);
+        }
+    }
+
+    /// Returns `true` if serving is currently throttled.
+    ///
+    /// If so, the task is woken up again once the throttle expires.
+    fn poll_throttled(&mut self, cx: &mut Context<'_>) -> bool {
+        loop {
+            if let Some(throttle) = &mut self.throttle {
+                if throttle.poll_unpin(cx).is_pending() {
+                    return true
+                }
+                self.throttle = None;
+            }
+            match self
+                .bandwidth
+                .as_ref()
+                .and_then(|bandwidth| bandwidth.delay(BandwidthClass::Serving))
+            {
+                Some(delay) => self.throttle = Some(Box::pin(tokio::time::sleep(delay))),
+                None => return false,
+            }
         }
     }
 }
@@ -147,6 +192,7 @@ where
     ) {
         self.metrics.eth_headers_requests_received_total.increment(1);
         let headers = self.get_headers_response(request);
+        self.on_response_served(headers.length());
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
@@ -176,6 +222,7 @@ where
             }
         }
 
+        self.on_response_served(total_bytes);
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
@@ -211,6 +258,7 @@ where
             }
         }
 
+        self.on_response_served(total_bytes);
         let _ = response.send(Ok(Receipts(receipts)));
     }
 }
@@ -227,6 +275,10 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        if this.poll_throttled(cx) {
+            return Poll::Pending
+        }
+
         let mut acc = Duration::ZERO;
         let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
             acc,
@@ -1,9 +1,13 @@
 //! A client implementation that can interact with the network and download data.
 
-use crate::{fetch::DownloadRequest, flattened_response::FlattenedResponse, peers::PeersHandle};
+use crate::{
+    bandwidth::BandwidthScheduler, fetch::DownloadRequest, flattened_response::FlattenedResponse,
+    peers::PeersHandle,
+};
+use alloy_rlp::Encodable;
 use futures::{future, future::Either};
 
-use reth_network_api::ReputationChangeKind;
+use reth_network_api::{BandwidthClass, ReputationChangeKind};
 use reth_network_p2p::{
     bodies::client::{BodiesClient, BodiesFut},
     download::DownloadClient,
@@ -34,6 +38,19 @@ pub struct FetchClient {
     pub(crate) peers_handle: PeersHandle,
     /// Number of active peer sessions the node's currently handling.
     pub(crate) num_active_peers: Arc<AtomicUsize>,
+    /// Throttles normal priority body downloads, if a bandwidth budget is configured.
+    pub(crate) bandwidth: Option<BandwidthScheduler>,
+}
+
+impl FetchClient {
+    /// Sets the [`BandwidthScheduler`] that throttles normal priority downloads.
+    pub(crate) fn with_bandwidth_scheduler(
+        mut self,
+        bandwidth: Option<BandwidthScheduler>,
+    ) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
 }
 
 impl DownloadClient for FetchClient {
@@ -81,6 +98,21 @@ impl BodiesClient for FetchClient {
         request: Vec<B256>,
         priority: Priority,
     ) -> Self::Output {
+        // historical sync is throttled, high priority requests are needed for the live chain
+        if let Some(bandwidth) = self.bandwidth.clone().filter(|_| priority.is_normal()) {
+            let request_tx = self.request_tx.clone();
+            return Box::pin(async move {
+                bandwidth.ready(BandwidthClass::Sync).await;
+                let (response, rx) = oneshot::channel();
+                request_tx
+                    .send(DownloadRequest::GetBlockBodies { request, response, priority })
+                    .map_err(|_| RequestError::ChannelClosed)?;
+                let bodies = FlattenedResponse::from(rx).await?;
+                bandwidth.consume(BandwidthClass::Sync, bodies.data().length());
+                Ok(bodies)
+            })
+        }
+
         let (response, rx) = oneshot::channel();
         if self
             .request_tx
@@ -1,6 +1,7 @@
 //! Fetch data from the network.
 
 use crate::{message::BlockRequest, peers::PeersHandle};
+use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
 use reth_network_api::ReputationChangeKind;
@@ -18,6 +19,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -25,6 +27,9 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 mod client;
 pub use client::FetchClient;
 
+/// The weight of a new sample in the moving average of the bodies throughput of a peer.
+const THROUGHPUT_SAMPLE_WEIGHT: f64 = 0.3;
+
 /// Manages data fetching operations.
 ///
 /// This type is hooked into the staged sync pipeline and delegates download request to available
@@ -86,6 +91,8 @@ impl StateFetcher {
                 best_number,
                 timeout,
                 last_response_likely_bad: false,
+                bodies_throughput: None,
+                is_fork_compatible: true,
             },
         );
     }
@@ -120,6 +127,15 @@ impl StateFetcher {
         false
     }
 
+    /// Updates whether the fork id of the peer is compatible with the local fork id.
+    ///
+    /// Block requests are only sent to compatible peers.
+    pub(crate) fn set_fork_compatible(&mut self, peer_id: &PeerId, is_compatible: bool) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.is_fork_compatible = is_compatible;
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -131,7 +147,7 @@ impl StateFetcher {
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
     fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+        let mut idle = self.peers.iter().filter(|(_, peer)| peer.is_available());
 
         let mut best_peer = idle.next()?;
 
@@ -153,6 +169,25 @@ impl StateFetcher {
         Some(*best_peer.0)
     }
 
+    /// Returns the _next_ idle peer that's ready to accept a `GetBlockBodies` request,
+    /// prioritizing those with the highest throughput observed on previous bodies responses.
+    ///
+    /// Peers without an observed throughput are preferred, so that every peer is measured. Ties are
+    /// broken like in [`Self::next_best_peer`].
+    fn next_best_bodies_peer(&self) -> Option<PeerId> {
+        let mut idle = self.peers.iter().filter(|(_, peer)| peer.is_available());
+
+        let mut best_peer = idle.next()?;
+
+        for maybe_better in idle {
+            if maybe_better.1.is_better_bodies_peer_than(best_peer.1) {
+                best_peer = maybe_better;
+            }
+        }
+
+        Some(*best_peer.0)
+    }
+
     /// Returns the next action to return
     fn poll_action(&mut self) -> PollAction {
         // we only check and not pop here since we don't know yet whether a peer is available.
@@ -160,7 +195,11 @@ impl StateFetcher {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let best_peer = match self.queued_requests.front() {
+            Some(DownloadRequest::GetBlockBodies { .. }) => self.next_best_bodies_peer(),
+            _ => self.next_best_peer(),
+        };
+        let Some(peer_id) = best_peer else { return PollAction::NoPeersAvailable };
 
         let request = self.queued_requests.pop_front().expect("not empty");
         let request = self.prepare_block_request(peer_id, request);
@@ -220,18 +259,20 @@ impl StateFetcher {
 
         match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight =
+                    Request { request: request.clone(), response, started_at: Instant::now() };
                 self.inflight_headers_requests.insert(peer_id, inflight);
-                let HeadersRequest { start, limit, direction } = request;
+                let HeadersRequest { start, limit, skip, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
                     start_block: start,
                     limit,
-                    skip: 0,
+                    skip,
                     direction,
                 })
             }
             DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight =
+                    Request { request: request.clone(), response, started_at: Instant::now() };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
@@ -296,13 +337,18 @@ impl StateFetcher {
         res: RequestResult<Vec<BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
+        let response_size = res.as_ref().map_or(0, |bodies| bodies.length());
 
-        if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+        let started_at = self.inflight_bodies_requests.remove(&peer_id).map(|resp| {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
-        }
+            resp.started_at
+        });
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            if let Some(started_at) = started_at.filter(|_| !is_likely_bad_response) {
+                peer.on_bodies_received(response_size, started_at.elapsed().as_secs_f64());
+            }
 
             if peer.state.on_request_finished() && !is_likely_bad_response {
                 return self.followup_request(peer_id)
@@ -317,6 +363,7 @@ impl StateFetcher {
             request_tx: self.download_requests_tx.clone(),
             peers_handle: self.peers_handle.clone(),
             num_active_peers: Arc::clone(&self.num_active_peers),
+            bandwidth: None,
         }
     }
 }
@@ -346,12 +393,49 @@ struct Peer {
     /// downloaded), but we still want to avoid requesting from the same peer again if it has the
     /// lowest timeout.
     last_response_likely_bad: bool,
+    /// Moving average of the bytes per second of the peer's `GetBlockBodies` responses, if any
+    /// were received.
+    bodies_throughput: Option<f64>,
+    /// Whether the fork id of the peer is compatible with the local fork id.
+    ///
+    /// This can change once the local node activates a fork the peer isn't aware of.
+    is_fork_compatible: bool,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer can accept a request.
+    fn is_available(&self) -> bool {
+        self.state.is_idle() && self.is_fork_compatible
+    }
+
+    /// Records the throughput of a `GetBlockBodies` response of the given size in bytes, that
+    /// took the given number of seconds.
+    fn on_bodies_received(&mut self, size: usize, elapsed: f64) {
+        // guard against responses that arrive within the resolution of the clock
+        let sample = size as f64 / elapsed.max(0.001);
+        self.bodies_throughput = Some(match self.bodies_throughput {
+            Some(throughput) => throughput + THROUGHPUT_SAMPLE_WEIGHT * (sample - throughput),
+            None => sample,
+        });
+    }
+
+    /// Returns `true` if this peer is expected to serve a `GetBlockBodies` request better than
+    /// the other one.
+    fn is_better_bodies_peer_than(&self, other: &Self) -> bool {
+        if self.last_response_likely_bad != other.last_response_likely_bad {
+            return other.last_response_likely_bad
+        }
+        match (self.bodies_throughput, other.bodies_throughput) {
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            (Some(this), Some(other)) if this != other => this > other,
+            _ => self.timeout() < other.timeout(),
+        }
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -398,6 +482,8 @@ struct Request<Req, Resp> {
     #[allow(dead_code)]
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// When the request was sent to the peer.
+    started_at: Instant,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -540,6 +626,52 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(), Some(peer2));
     }
 
+    #[tokio::test]
+    async fn test_bodies_peer_prioritization() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let peer3 = B512::random();
+
+        // peer1 has the lowest timeout, but the lowest throughput
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(10)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(100)));
+        fetcher.peers.get_mut(&peer1).unwrap().on_bodies_received(1_000, 1.0);
+        fetcher.peers.get_mut(&peer2).unwrap().on_bodies_received(10_000, 1.0);
+
+        // peer3 wasn't measured yet
+        assert_eq!(fetcher.next_best_bodies_peer(), Some(peer3));
+        fetcher.peers.get_mut(&peer3).unwrap().on_bodies_received(5_000, 1.0);
+        assert_eq!(fetcher.next_best_bodies_peer(), Some(peer2));
+        // headers are still requested from the peer with the lowest timeout
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+
+        // the throughput of peer2 drops below the one of peer3
+        for _ in 0..2 {
+            fetcher.peers.get_mut(&peer2).unwrap().on_bodies_received(0, 1.0);
+        }
+        assert_eq!(fetcher.next_best_bodies_peer(), Some(peer3));
+    }
+
+    #[tokio::test]
+    async fn test_skip_fork_incompatible_peers() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(10)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(50)));
+
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        fetcher.set_fork_compatible(&peer1, false);
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_bodies_peer(), Some(peer2));
+        fetcher.set_fork_compatible(&peer2, false);
+        assert_eq!(fetcher.next_best_peer(), None);
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -582,9 +714,11 @@ mod tests {
                 request: HeadersRequest {
                     start: 0u64.into(),
                     limit: 1,
+                    skip: 0,
                     direction: Default::default(),
                 },
                 response: tx,
+                started_at: Instant::now(),
             };
             let mut header = SealedHeader::default().unseal();
             header.number = 0u64;
@@ -114,6 +114,7 @@
 /// Common helpers for network testing.
 pub mod test_utils;
 
+pub mod bandwidth;
 mod budget;
 mod builder;
 pub mod cache;
@@ -136,6 +137,7 @@ mod state;
 mod swarm;
 pub mod transactions;
 
+pub use bandwidth::BandwidthScheduler;
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
 pub use discovery::{Discovery, DiscoveryEvent};
@@ -151,4 +153,4 @@ pub use session::{
 pub use transactions::{FilterAnnouncement, MessageFilter, ValidateTx68};
 
 pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
-pub use reth_network_types::{PeersConfig, SessionsConfig};
+pub use reth_network_types::{BandwidthConfig, PeersConfig, SessionsConfig};
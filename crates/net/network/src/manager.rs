@@ -16,6 +16,7 @@
 //! to the local node. Once a (tcp) connection is established, both peers start to authenticate a [RLPx session](https://github.com/ethereum/devp2p/blob/master/rlpx.md) via a handshake. If the handshake was successful, both peers announce their capabilities and are now ready to exchange sub-protocol messages via the `RLPx` session.
 
 use crate::{
+    bandwidth::BandwidthScheduler,
     budget::{DEFAULT_BUDGET_TRY_DRAIN_NETWORK_HANDLE_CHANNEL, DEFAULT_BUDGET_TRY_DRAIN_SWARM},
     config::NetworkConfig,
     discovery::Discovery,
@@ -43,6 +44,7 @@ use reth_eth_wire::{
 };
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
+use reth_net_nat::{PortMapper, PortMapping};
 use reth_network_api::{EthProtocolInfo, NetworkStatus, PeerInfo, ReputationChangeKind};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_primitives::ForkId;
@@ -61,7 +63,10 @@ use std::{
     task::{Context, Poll},
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    oneshot,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, trace, warn};
 
@@ -112,6 +117,11 @@ pub struct NetworkManager<C> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Signals the port mapping task to remove the mappings from the gateway, if port mapping is
+    /// enabled. Dropping it has the same effect.
+    port_mapping_shutdown: Option<oneshot::Sender<()>>,
+    /// Shares bandwidth between sync, gossip and serving peers, if a budget is configured.
+    bandwidth: Option<BandwidthScheduler>,
 }
 
 // === impl NetworkManager ===
@@ -190,8 +200,12 @@ where
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
+            port_mapping,
+            bandwidth_config,
         } = config;
 
+        let bandwidth = BandwidthScheduler::from_config(&bandwidth_config);
+
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -216,6 +230,25 @@ where
             disc_config
         });
 
+        // map the listener and discovery ports on the gateway, removing the mappings again when
+        // the network shuts down
+        let port_mapping_shutdown = port_mapping.map(|resolver| {
+            let mut mappings = vec![PortMapping::tcp(listener_addr.port())];
+            if discovery_v4_config.is_some() {
+                mappings.push(PortMapping::udp(discovery_v4_addr.port()));
+            }
+            if let Some(discv5_config) = &discovery_v5_config {
+                mappings.push(PortMapping::udp(discv5_config.discovery_socket().port()));
+            }
+            mappings.dedup();
+
+            let (tx, rx) = oneshot::channel();
+            executor.spawn(Box::pin(PortMapper::new(resolver, mappings).run(async move {
+                let _ = rx.await;
+            })));
+            tx
+        });
+
         let discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
@@ -228,6 +261,7 @@ where
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
+        let discv5 = discovery.discv5();
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
@@ -261,6 +295,8 @@ where
             Arc::new(AtomicU64::new(chain_spec.chain.id())),
             tx_gossip_disabled,
             discv4,
+            discv5,
+            bandwidth.clone(),
             event_sender.clone(),
         );
 
@@ -275,6 +311,8 @@ where
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            port_mapping_shutdown,
+            bandwidth,
         })
     }
 
@@ -366,7 +404,7 @@ where
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
     pub fn fetch_client(&self) -> FetchClient {
-        self.swarm.state().fetch_client()
+        self.swarm.state().fetch_client().with_bandwidth_scheduler(self.bandwidth.clone())
     }
 
     /// Returns the current [`NetworkStatus`] for the local node.
@@ -593,6 +631,15 @@ where
                 // connection.
                 self.swarm.on_network_state_change(net_state);
             }
+            NetworkHandleMessage::SetPeerLimits { max_inbound, max_outbound } => {
+                self.swarm.state_mut().peers_mut().set_connection_limits(max_inbound, max_outbound);
+            }
+            NetworkHandleMessage::ProtectPeer(peer_id) => {
+                self.swarm.state_mut().peers_mut().protect_peer(peer_id);
+            }
+            NetworkHandleMessage::UnprotectPeer(peer_id) => {
+                self.swarm.state_mut().peers_mut().unprotect_peer(peer_id);
+            }
 
             NetworkHandleMessage::Shutdown(tx) => {
                 // Set connection status to `Shutdown`. Stops node to accept
@@ -603,6 +650,10 @@ where
                 self.swarm.sessions_mut().disconnect_all(Some(DisconnectReason::ClientQuitting));
                 // drop pending connections
                 self.swarm.sessions_mut().disconnect_all_pending();
+                // remove port mappings from the gateway
+                if let Some(port_mapping_shutdown) = self.port_mapping_shutdown.take() {
+                    let _ = port_mapping_shutdown.send(());
+                }
                 let _ = tx.send(());
             }
             NetworkHandleMessage::ReputationChange(peer_id, kind) => {
@@ -618,8 +669,9 @@ where
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
-                if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
-                    self.swarm.state_mut().update_fork_id(transition.current);
+                if self.swarm.sessions_mut().on_status_update(head).is_some() {
+                    let fork_filter = self.swarm.sessions().fork_filter().clone();
+                    self.swarm.state_mut().update_fork_id(&fork_filter);
                 }
             }
             NetworkHandleMessage::GetPeerInfos(tx) => {
@@ -635,6 +687,19 @@ where
                 let peer_ids = self.swarm.state().peers().peers_by_kind(kind);
                 let _ = tx.send(self.get_peer_infos_by_ids(peer_ids));
             }
+            NetworkHandleMessage::GetStaticPeers(tx) => {
+                let _ = tx.send(self.swarm.state().peers().static_peers());
+            }
+            NetworkHandleMessage::GetPeerStats(tx) => {
+                let _ = tx.send(
+                    self.swarm
+                        .sessions()
+                        .active_sessions()
+                        .values()
+                        .map(|session| session.peer_stats())
+                        .collect(),
+                );
+            }
             NetworkHandleMessage::AddRlpxSubProtocol(proto) => self.add_rlpx_sub_protocol(proto),
             NetworkHandleMessage::GetTransactionsHandle(tx) => {
                 if let Some(ref tx_inner) = self.to_transactions_manager {
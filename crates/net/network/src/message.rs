@@ -6,8 +6,8 @@
 use futures::FutureExt;
 use reth_eth_wire::{
     capability::RawCapabilityMessage, message::RequestPair, BlockBodies, BlockHeaders, EthMessage,
-    GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, NewBlock,
-    NewBlockHashes, NewPooledTransactionHashes, NodeData, PooledTransactions, Receipts,
+    EthMessageID, GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts,
+    NewBlock, NewBlockHashes, NewPooledTransactionHashes, NodeData, PooledTransactions, Receipts,
     SharedTransactions, Transactions,
 };
 use reth_network_p2p::error::{RequestError, RequestResult};
@@ -143,6 +143,17 @@ impl PeerRequest {
         };
     }
 
+    /// Returns the [`EthMessageID`] of the request message.
+    pub const fn message_id(&self) -> EthMessageID {
+        match self {
+            Self::GetBlockHeaders { .. } => EthMessageID::GetBlockHeaders,
+            Self::GetBlockBodies { .. } => EthMessageID::GetBlockBodies,
+            Self::GetPooledTransactions { .. } => EthMessageID::GetPooledTransactions,
+            Self::GetNodeData { .. } => EthMessageID::GetNodeData,
+            Self::GetReceipts { .. } => EthMessageID::GetReceipts,
+        }
+    }
+
     /// Returns the [`EthMessage`] for this type
     pub fn create_request_message(&self, request_id: u64) -> EthMessage {
         match self {
@@ -94,6 +94,10 @@ pub struct TransactionsManagerMetrics {
     pub(crate) propagated_transactions: Counter,
     /// Total number of reported bad transactions
     pub(crate) reported_bad_transactions: Counter,
+    /// Total number of transactions that were only announced as hashes to peers that receive
+    /// full transactions, because they are blob transactions or exceed the max byte size of a
+    /// full broadcast
+    pub(crate) announced_only_transactions: Counter,
 
     /* -- Freq txns already marked as seen by peer -- */
     /// Total number of messages from a peer, announcing transactions that have already been
@@ -208,6 +212,23 @@ pub struct TransactionFetcherMetrics {
     /// [`PooledTransactions`](reth_eth_wire::PooledTransactions) responses, that weren't
     /// requested.
     pub(crate) unsolicited_transactions: Counter,
+    /// Total number of fetched transactions that had already been received from another peer by
+    /// the time the response arrived.
+    pub(crate) wasted_fetched_transactions: Counter,
+    /// Total byte size of fetched transactions that had already been received from another peer
+    /// by the time the response arrived.
+    pub(crate) wasted_fetched_transactions_bytes: Counter,
+    /// Total number of announced hashes that were already inflight or pending fetch.
+    pub(crate) duplicate_announced_hashes: Counter,
+    /// Total number of hashes dropped after reaching the max retry limit.
+    pub(crate) hashes_dropped_max_retries: Counter,
+    /// Total number of hashes evicted from the cache of hashes pending fetch.
+    pub(crate) hashes_evicted_pending_fetch: Counter,
+    /// Time it takes for a [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions)
+    /// request to resolve.
+    ///
+    /// Duration in seconds.
+    pub(crate) fetch_duration_seconds: Histogram,
     /* ================ SEARCH DURATION ================ */
     /// Time spent searching for an idle peer in call to
     /// [`TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash`](crate::transactions::TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash).
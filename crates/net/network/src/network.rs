@@ -1,4 +1,5 @@
 use crate::{
+    bandwidth::BandwidthScheduler,
     config::NetworkMode,
     discovery::DiscoveryEvent,
     manager::NetworkEvent,
@@ -12,10 +13,11 @@ use crate::{
 use enr::Enr;
 use parking_lot::Mutex;
 use reth_discv4::Discv4;
+use reth_discv5::Discv5;
 use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, SharedTransactions};
 use reth_network_api::{
-    NetworkError, NetworkInfo, NetworkStatus, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
-    ReputationChangeKind,
+    BandwidthWeights, NetworkError, NetworkInfo, NetworkStatus, PeerInfo, PeerKind, PeerStats,
+    Peers, PeersInfo, Reputation, ReputationChangeKind, StaticPeerHealth,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -60,6 +62,8 @@ impl NetworkHandle {
         chain_id: Arc<AtomicU64>,
         tx_gossip_disabled: bool,
         discv4: Option<Discv4>,
+        discv5: Option<Discv5>,
+        bandwidth: Option<BandwidthScheduler>,
         event_sender: EventSender<NetworkEvent>,
     ) -> Self {
         let inner = NetworkInner {
@@ -75,6 +79,8 @@ impl NetworkHandle {
             chain_id,
             tx_gossip_disabled,
             discv4,
+            discv5,
+            bandwidth,
             event_sender,
         };
         Self { inner: Arc::new(inner) }
@@ -180,6 +186,23 @@ impl NetworkHandle {
         self.set_network_conn(NetworkConnectionState::Hibernate);
     }
 
+    /// Updates the maximum number of inbound and outbound peer connections.
+    ///
+    /// Established connections are not dropped if a limit is lowered.
+    pub fn set_peer_limits(&self, max_inbound: usize, max_outbound: usize) {
+        self.send_message(NetworkHandleMessage::SetPeerLimits { max_inbound, max_outbound });
+    }
+
+    /// Marks the peer as protected, which exempts it from pruning.
+    pub fn protect_peer(&self, peer: PeerId) {
+        self.send_message(NetworkHandleMessage::ProtectPeer(peer));
+    }
+
+    /// Removes the peer from the protected peers.
+    pub fn unprotect_peer(&self, peer: PeerId) {
+        self.send_message(NetworkHandleMessage::UnprotectPeer(peer));
+    }
+
     /// Set network connection state.
     fn set_network_conn(&self, network_conn: NetworkConnectionState) {
         self.send_message(NetworkHandleMessage::SetNetworkState(network_conn));
@@ -194,6 +217,11 @@ impl NetworkHandle {
     pub fn secret_key(&self) -> &SecretKey {
         &self.inner.secret_key
     }
+
+    /// Returns the [`BandwidthScheduler`] if a bandwidth budget is configured.
+    pub fn bandwidth_scheduler(&self) -> Option<&BandwidthScheduler> {
+        self.inner.bandwidth.as_ref()
+    }
 }
 
 // === API Implementations ===
@@ -224,6 +252,8 @@ impl PeersInfo for NetworkHandle {
     fn local_node_record(&self) -> NodeRecord {
         if let Some(discv4) = &self.inner.discv4 {
             discv4.node_record()
+        } else if let Some(discv5) = &self.inner.discv5 {
+            discv5.node_record()
         } else {
             let id = *self.peer_id();
             let mut socket_addr = *self.inner.listener_address.lock();
@@ -242,6 +272,12 @@ impl PeersInfo for NetworkHandle {
     }
 
     fn local_enr(&self) -> Enr<SecretKey> {
+        if let Some(discv5) = &self.inner.discv5 {
+            // the discv5 ENR is signed and kept up to date by the discv5 service, including the
+            // fork ID of the network stack
+            return discv5.local_enr()
+        }
+
         let local_node_record = self.local_node_record();
         let mut builder = Enr::builder();
         builder.ip(local_node_record.address);
@@ -286,6 +322,18 @@ impl Peers for NetworkHandle {
         Ok(rx.await?)
     }
 
+    async fn get_static_peers(&self) -> Result<Vec<(NodeRecord, StaticPeerHealth)>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetStaticPeers(tx));
+        Ok(rx.await?)
+    }
+
+    async fn get_peer_stats(&self) -> Result<Vec<PeerStats>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetPeerStats(tx));
+        Ok(rx.await?)
+    }
+
     async fn get_peer_by_id(&self, peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError> {
         let (tx, rx) = oneshot::channel();
         let _ = self.manager().send(NetworkHandleMessage::GetPeerInfoById(peer_id, tx));
@@ -350,6 +398,16 @@ impl NetworkInfo for NetworkHandle {
     fn is_initially_syncing(&self) -> bool {
         SyncStateProvider::is_initially_syncing(self)
     }
+
+    fn bandwidth_weights(&self) -> Option<BandwidthWeights> {
+        self.inner.bandwidth.as_ref().map(BandwidthScheduler::weights)
+    }
+
+    fn set_bandwidth_weights(&self, weights: BandwidthWeights) -> bool {
+        let Some(bandwidth) = &self.inner.bandwidth else { return false };
+        bandwidth.set_weights(weights);
+        true
+    }
 }
 
 impl SyncStateProvider for NetworkHandle {
@@ -407,6 +465,10 @@ struct NetworkInner {
     tx_gossip_disabled: bool,
     /// The instance of the discv4 service
     discv4: Option<Discv4>,
+    /// The instance of the discv5 service
+    discv5: Option<Discv5>,
+    /// Shares bandwidth between sync, gossip and serving peers, if a budget is configured.
+    bandwidth: Option<BandwidthScheduler>,
     /// Sender for high level network events.
     event_sender: EventSender<NetworkEvent>,
 }
@@ -480,6 +542,11 @@ pub(crate) enum NetworkHandleMessage {
     GetPeerInfoById(PeerId, oneshot::Sender<Option<PeerInfo>>),
     /// Gets `PeerInfo` for a specific peer kind via a oneshot sender.
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
+    /// Gets the node records and `StaticPeerHealth` of all configured static nodes via a oneshot
+    /// sender.
+    GetStaticPeers(oneshot::Sender<Vec<(NodeRecord, StaticPeerHealth)>>),
+    /// Gets the `PeerStats` of all connected peers via a oneshot sender.
+    GetPeerStats(oneshot::Sender<Vec<PeerStats>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
@@ -488,6 +555,17 @@ pub(crate) enum NetworkHandleMessage {
     Shutdown(oneshot::Sender<()>),
     /// Sets the network state between hibernation and active.
     SetNetworkState(NetworkConnectionState),
+    /// Updates the maximum number of peer connections.
+    SetPeerLimits {
+        /// The maximum number of inbound connections.
+        max_inbound: usize,
+        /// The maximum number of outbound connections.
+        max_outbound: usize,
+    },
+    /// Marks a peer as protected, which exempts it from pruning.
+    ProtectPeer(PeerId),
+    /// Removes a peer from the protected peers.
+    UnprotectPeer(PeerId),
     /// Adds a new listener for `DiscoveryEvent`.
     DiscoveryListener(UnboundedSender<DiscoveryEvent>),
     /// Adds an additional `RlpxSubProtocol`.
@@ -8,7 +8,7 @@ use crate::{
 use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_banlist::BanList;
-use reth_network_api::{PeerKind, ReputationChangeKind};
+use reth_network_api::{PeerKind, ReputationChangeKind, StaticPeerHealth};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
@@ -17,7 +17,7 @@ use reth_network_types::{
             is_banned_reputation, DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE,
         },
     },
-    ConnectionsConfig, PeersConfig, ReputationChangeWeights,
+    BackoffKind, ConnectionsConfig, PeersConfig, ReputationChangeWeights,
 };
 use reth_primitives::ForkId;
 use std::{
@@ -97,6 +97,8 @@ pub struct PeersManager {
     /// This tracks peer ids that are considered trusted, but for which we don't necessarily have
     /// an address: [`Self::add_trusted_peer_id`]
     trusted_peer_ids: HashSet<PeerId>,
+    /// The set of protected peer ids, which are exempt from pruning.
+    protected_peer_ids: HashSet<PeerId>,
     /// Copy of the sender half, so new [`PeersHandle`] can be created on demand.
     manager_tx: mpsc::UnboundedSender<PeerCommand>,
     /// Receiver half of the command channel.
@@ -143,6 +145,8 @@ impl PeersManager {
             backoff_durations,
             trusted_nodes,
             trusted_nodes_only,
+            static_nodes,
+            protected_nodes,
             basic_nodes,
             max_backoff_count,
         } = config;
@@ -152,7 +156,8 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + static_nodes.len() + basic_nodes.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
         for NodeRecord { address, tcp_port, udp_port, id } in trusted_nodes {
@@ -162,6 +167,12 @@ impl PeersManager {
             });
         }
 
+        for NodeRecord { address, tcp_port, udp_port, id } in static_nodes {
+            peers.entry(id).or_insert_with(|| {
+                Peer::static_node(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
+            });
+        }
+
         for NodeRecord { address, tcp_port, udp_port, id } in basic_nodes {
             peers.entry(id).or_insert_with(|| {
                 Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
@@ -171,6 +182,7 @@ impl PeersManager {
         Self {
             peers,
             trusted_peer_ids,
+            protected_peer_ids: protected_nodes.into_iter().collect(),
             manager_tx,
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
@@ -227,6 +239,32 @@ impl PeersManager {
         })
     }
 
+    /// Returns the node records and the [`StaticPeerHealth`] of all configured static nodes.
+    pub(crate) fn static_peers(&self) -> Vec<(NodeRecord, StaticPeerHealth)> {
+        let now = std::time::Instant::now();
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.is_static_node())
+            .map(|(peer_id, peer)| {
+                let record = NodeRecord::new_with_ports(
+                    peer.addr.tcp.ip(),
+                    peer.addr.tcp.port(),
+                    peer.addr.udp.map(|addr| addr.port()),
+                    *peer_id,
+                );
+                let health = StaticPeerHealth {
+                    failed_attempts: peer.failed_attempts,
+                    next_dial_in_secs: self
+                        .backed_off_peers
+                        .get(peer_id)
+                        .map(|until| until.saturating_duration_since(now).as_secs()),
+                    last_error: peer.last_error.clone(),
+                };
+                (record, health)
+            })
+            .collect()
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -349,6 +387,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.on_session_established();
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -440,7 +479,9 @@ impl PeersManager {
     /// reputation changes that can be attributed to network conditions. If the peer is a
     /// trusted peer, it will also be less strict with the reputation slashing.
     pub(crate) fn apply_reputation_change(&mut self, peer_id: &PeerId, rep: ReputationChangeKind) {
+        let mut is_static = false;
         let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
+            is_static = peer.is_static_node();
             // First check if we should reset the reputation
             if rep.is_reset() {
                 peer.reset_reputation()
@@ -472,6 +513,23 @@ impl PeersManager {
 
         match outcome {
             ReputationChangeOutcome::None => {}
+            ReputationChangeOutcome::Ban | ReputationChangeOutcome::DisconnectAndBan
+                if is_static =>
+            {
+                // static peers are exempt from bans, the session is terminated instead and the
+                // peer is redialed after a backoff
+                if let Some(peer) = self.peers.get_mut(peer_id) {
+                    peer.unban();
+                }
+                if matches!(outcome, ReputationChangeOutcome::DisconnectAndBan) {
+                    self.queued_actions.push_back(PeerAction::Disconnect {
+                        peer_id: *peer_id,
+                        reason: Some(DisconnectReason::DisconnectRequested),
+                    });
+                }
+                let backoff_until = self.backoff_durations.backoff_until(BackoffKind::High, 0);
+                self.backoff_peer_until(*peer_id, backoff_until);
+            }
             ReputationChangeOutcome::Ban => {
                 self.ban_peer(*peer_id);
             }
@@ -511,7 +569,10 @@ impl PeersManager {
             Entry::Occupied(mut entry) => {
                 self.connection_info.decr_state(entry.get().state);
 
-                if entry.get().remove_after_disconnect && !entry.get().is_trusted() {
+                if entry.get().remove_after_disconnect &&
+                    !entry.get().is_trusted() &&
+                    !self.protected_peer_ids.contains(&peer_id)
+                {
                     // this peer should be removed from the set
                     entry.remove();
                     self.queued_actions.push_back(PeerAction::PeerRemoved(peer_id));
@@ -536,6 +597,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.on_session_established();
         }
     }
 
@@ -582,6 +644,21 @@ impl PeersManager {
     ) {
         trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "handling failed connection");
 
+        if let Some(peer) = self.peers.get_mut(peer_id).filter(|peer| peer.is_static_node()) {
+            // static peers are never removed or banned, they're redialed with an exponential
+            // backoff instead
+            peer.failed_attempts = peer.failed_attempts.saturating_add(1);
+            peer.last_error = Some(err.to_string());
+            self.connection_info.decr_state(peer.state);
+            peer.state = PeerConnectionState::Idle;
+
+            let backoff_until =
+                self.backoff_durations.exponential_backoff_until(peer.failed_attempts);
+            self.backoff_peer_until(*peer_id, backoff_until);
+            self.fill_outbound_slots();
+            return
+        }
+
         if err.is_fatal_protocol_error() {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
@@ -628,9 +705,12 @@ impl PeersManager {
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
-                if peer.severe_backoff_counter > self.max_backoff_count && !peer.is_trusted() {
+                if peer.severe_backoff_counter > self.max_backoff_count &&
+                    !peer.is_trusted() &&
+                    !self.protected_peer_ids.contains(peer_id)
+                {
                     // mark peer for removal if it has been backoff too many times and is _not_
-                    // trusted
+                    // trusted or protected
                     remove_peer = true;
                 }
             }
@@ -714,7 +794,10 @@ impl PeersManager {
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
-                peer.kind = kind;
+                // configured static nodes stay static when they're rediscovered
+                if !(peer.is_static_node() && matches!(kind, PeerKind::Basic)) {
+                    peer.kind = kind;
+                }
                 peer.fork_id = fork_id;
                 peer.addr = addr;
 
@@ -739,10 +822,25 @@ impl PeersManager {
         }
     }
 
+    /// Marks the given peer as protected, which exempts it from pruning.
+    pub(crate) fn protect_peer(&mut self, peer_id: PeerId) {
+        self.protected_peer_ids.insert(peer_id);
+    }
+
+    /// Removes the given peer from the protected set.
+    pub(crate) fn unprotect_peer(&mut self, peer_id: PeerId) {
+        self.protected_peer_ids.remove(&peer_id);
+    }
+
     /// Removes the tracked node from the set.
+    ///
+    /// Trusted peers, configured static nodes and protected peers are not removed.
     pub(crate) fn remove_peer(&mut self, peer_id: PeerId) {
         let Entry::Occupied(entry) = self.peers.entry(peer_id) else { return };
-        if entry.get().is_trusted() {
+        if entry.get().is_trusted() ||
+            entry.get().is_static_node() ||
+            self.protected_peer_ids.contains(&peer_id)
+        {
             return
         }
         let mut peer = entry.remove();
@@ -799,13 +897,13 @@ impl PeersManager {
         // keep track of the best peer, if there's one
         let mut best_peer = unconnected.next()?;
 
-        if best_peer.1.is_trusted() {
+        if best_peer.1.is_trusted() || best_peer.1.is_static_node() {
             return Some((*best_peer.0, best_peer.1))
         }
 
         for maybe_better in unconnected {
-            // if the peer is trusted, return it immediately
-            if maybe_better.1.is_trusted() {
+            // if the peer is trusted or a static node, return it immediately
+            if maybe_better.1.is_trusted() || maybe_better.1.is_static_node() {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
@@ -855,6 +953,15 @@ impl PeersManager {
         self.net_connection_state = state;
     }
 
+    /// Updates the maximum number of inbound and outbound connections.
+    ///
+    /// Lowering a limit doesn't disconnect established connections, the number of connections
+    /// drops to the new limit as peers disconnect.
+    pub fn set_connection_limits(&mut self, max_inbound: usize, max_outbound: usize) {
+        self.connection_info.config.max_inbound = max_inbound;
+        self.connection_info.config.max_outbound = max_outbound;
+    }
+
     /// Returns the current network connection state.
     pub const fn connection_state(&self) -> &NetworkConnectionState {
         &self.net_connection_state
@@ -1063,6 +1170,14 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`reth_network_types::BackoffKind`].
     severe_backoff_counter: u8,
+    /// Whether the peer is a configured static node, which is never removed or banned.
+    ///
+    /// Peers added as [`PeerKind::Static`] at runtime, e.g. via `admin_addPeer`, are not.
+    static_node: bool,
+    /// Counts the consecutive failed connection attempts of a static node since its last session.
+    failed_attempts: u8,
+    /// The error of the last failed connection attempt or session of a static node.
+    last_error: Option<String>,
 }
 
 // === impl Peer ===
@@ -1091,6 +1206,9 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            static_node: false,
+            failed_attempts: 0,
+            last_error: None,
         }
     }
 
@@ -1098,6 +1216,10 @@ impl Peer {
         Self { kind, ..Self::new(addr) }
     }
 
+    fn static_node(addr: PeerAddr) -> Self {
+        Self { static_node: true, ..Self::with_kind(addr, PeerKind::Static) }
+    }
+
     /// Resets the reputation of the peer to the default value. This always returns
     /// [`ReputationChangeOutcome::None`].
     fn reset_reputation(&mut self) -> ReputationChangeOutcome {
@@ -1141,6 +1263,12 @@ impl Peer {
         self.backed_off
     }
 
+    /// Resets the failed connection attempts after a session was established.
+    fn on_session_established(&mut self) {
+        self.failed_attempts = 0;
+        self.last_error = None;
+    }
+
     /// Unbans the peer by resetting its reputation
     #[inline]
     fn unban(&mut self) {
@@ -1152,6 +1280,12 @@ impl Peer {
     const fn is_trusted(&self) -> bool {
         matches!(self.kind, PeerKind::Trusted)
     }
+
+    /// Returns whether this peer is a configured static node
+    #[inline]
+    const fn is_static_node(&self) -> bool {
+        self.static_node
+    }
 }
 
 /// Outcomes when a reputation change is applied to a peer
@@ -1332,7 +1466,7 @@ mod tests {
     use reth_network_types::{peers::reputation::DEFAULT_REPUTATION, BackoffKind};
     use reth_primitives::B512;
     use std::{
-        collections::HashSet,
+        collections::{BTreeSet, HashSet},
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -1744,6 +1878,110 @@ mod tests {
         assert!(!peers.peers.contains_key(&peer));
     }
 
+    #[tokio::test]
+    async fn test_keep_protected_on_max_backoff_count() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let config = PeersConfig::test().with_protected_nodes(BTreeSet::from([peer]));
+        let mut peers = PeersManager::new(config.clone());
+        peers.add_peer(peer, PeerAddr::tcp(socket_addr), None);
+        let peer_struct = peers.peers.get_mut(&peer).unwrap();
+
+        // Simulate a peer that was already backed off once
+        peer_struct.severe_backoff_counter = config.max_backoff_count;
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.on_outgoing_pending_session_dropped(
+            &socket_addr,
+            &peer,
+            &PendingSessionHandshakeError::Eth(
+                io::Error::new(io::ErrorKind::ConnectionRefused, "peer unreachable").into(),
+            ),
+        );
+        assert!(peers.peers.get(&peer).unwrap().is_backed_off());
+
+        // protected peers are not removed by discovery either
+        peers.remove_peer(peer);
+        assert!(peers.peers.contains_key(&peer));
+
+        peers.unprotect_peer(peer);
+        peers.remove_peer(peer);
+        assert!(!peers.peers.contains_key(&peer));
+    }
+
+    #[tokio::test]
+    async fn test_redial_static_peer_with_backoff() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let record = NodeRecord::new(socket_addr, PeerId::random());
+        let peer = record.id;
+        let config = PeersConfig::test().with_static_nodes(HashSet::from([record]));
+        let mut peers = PeersManager::new(config);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(remote_addr, socket_addr);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.on_outgoing_pending_session_dropped(
+            &socket_addr,
+            &peer,
+            &PendingSessionHandshakeError::Eth(EthStreamError::P2PStreamError(
+                P2PStreamError::HandshakeError(P2PHandshakeError::Disconnected(
+                    DisconnectReason::UselessPeer,
+                )),
+            )),
+        );
+
+        // a fatal error neither removes nor bans a static peer, it's backed off instead
+        assert!(!peers.ban_list.is_banned_peer(&peer));
+        let static_peers = peers.static_peers();
+        assert_eq!(static_peers.len(), 1);
+        let (enode, health) = &static_peers[0];
+        assert_eq!(*enode, record);
+        assert_eq!(health.failed_attempts, 1);
+        assert!(health.next_dial_in_secs.is_some());
+        assert!(health.last_error.is_some());
+
+        peers.apply_reputation_change(&peer, ReputationChangeKind::BadProtocol);
+        assert!(!peers.ban_list.is_banned_peer(&peer));
+        assert!(peers.peers.get(&peer).unwrap().is_backed_off());
+
+        peers.remove_peer(peer);
+        assert!(peers.peers.contains_key(&peer));
+    }
+
+    #[tokio::test]
+    async fn test_remove_and_ban_added_static_peer() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let peer = PeerId::random();
+        let mut peers = PeersManager::new(PeersConfig::test());
+
+        // a peer added as static at runtime is not a configured static node
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::tcp(socket_addr), None);
+        assert!(peers.static_peers().is_empty());
+        peers.remove_peer(peer);
+        assert!(!peers.peers.contains_key(&peer));
+
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::tcp(socket_addr), None);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::BadProtocol);
+        assert!(peers.ban_list.is_banned_peer(&peer));
+    }
+
     #[tokio::test]
     async fn test_ban_on_pending_drop() {
         let peer = PeerId::random();
@@ -1900,6 +2138,19 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_set_connection_limits() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test().with_max_inbound(1));
+
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
+        peers.on_incoming_session_established(PeerId::random(), socket_addr);
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_err());
+
+        peers.set_connection_limits(2, peers.connection_info.config.max_outbound);
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
+    }
+
     #[tokio::test]
     async fn accept_incoming_trusted_unknown_peer_address() {
         let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 99)), 8008);
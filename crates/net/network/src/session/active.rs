@@ -5,6 +5,7 @@ use crate::{
     session::{
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
+        stats::SessionStats,
         SessionId,
     },
 };
@@ -14,7 +15,7 @@ use reth_eth_wire::{
     capability::Capabilities,
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    DisconnectP2P, DisconnectReason, EthMessage,
+    DisconnectP2P, DisconnectReason, EthMessage, EthMessageID,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_p2p::error::RequestError;
@@ -95,6 +96,8 @@ pub(crate) struct ActiveSession {
     pub(crate) protocol_breach_request_timeout: Duration,
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message: Option<(PollSender<ActiveSessionMessage>, ActiveSessionMessage)>,
+    /// The statistics of the messages and requests of this session.
+    pub(crate) stats: SessionStats,
 }
 
 impl ActiveSession {
@@ -150,7 +153,12 @@ impl ActiveSession {
                     match req.request {
                         RequestState::Waiting(PeerRequest::$item { response, .. }) => {
                             let _ = response.send(Ok(message));
-                            self.update_request_timeout(req.timestamp, Instant::now());
+                            let now = Instant::now();
+                            self.stats.on_request_answered(
+                                req.message_id,
+                                now.saturating_duration_since(req.timestamp),
+                            );
+                            self.update_request_timeout(req.timestamp, now);
                         }
                         RequestState::Waiting(request) => {
                             request.send_bad_response();
@@ -239,7 +247,10 @@ impl ActiveSession {
         let request_id = self.next_id();
         let msg = request.create_request_message(request_id);
         self.queued_outgoing.push_back(msg.into());
+        let message_id = request.message_id();
+        self.stats.on_request_sent(message_id);
         let req = InflightRequest {
+            message_id,
             request: RequestState::Waiting(request),
             timestamp: Instant::now(),
             deadline,
@@ -427,6 +438,7 @@ impl ActiveSession {
             if req.is_timed_out(now) {
                 if req.is_waiting() {
                     debug!(target: "net::session", ?id, remote_peer_id=?self.remote_peer_id, "timed out outgoing request");
+                    self.stats.on_request_timed_out(req.message_id);
                     req.timeout();
                 } else if now - req.timestamp > self.protocol_breach_request_timeout {
                     return true
@@ -551,6 +563,7 @@ impl Future for ActiveSession {
             while this.conn.poll_ready_unpin(cx).is_ready() {
                 if let Some(msg) = this.queued_outgoing.pop_front() {
                     progress = true;
+                    let message_id = msg.message_id();
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
                         OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
@@ -560,6 +573,7 @@ impl Future for ActiveSession {
                         // notify the manager
                         return this.close_on_error(err, cx)
                     }
+                    this.stats.on_message_sent(message_id, this.conn.last_sent_len());
                 } else {
                     // no more messages to send over the wire
                     break
@@ -606,6 +620,10 @@ impl Future for ActiveSession {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.stats.on_message_received(
+                                    msg.message_id(),
+                                    this.conn.last_received_len(),
+                                );
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -666,6 +684,8 @@ pub(crate) struct ReceivedRequest {
 
 /// A request that waits for a response from the peer
 pub(crate) struct InflightRequest {
+    /// The type of the request
+    message_id: EthMessageID,
     /// Request we sent to peer and the internal response channel
     request: RequestState,
     /// Instant when the request was sent
@@ -734,6 +754,16 @@ pub(crate) enum OutgoingMessage {
     Broadcast(EthBroadcastMessage),
 }
 
+impl OutgoingMessage {
+    /// Returns the message's ID.
+    const fn message_id(&self) -> EthMessageID {
+        match self {
+            Self::Eth(msg) => msg.message_id(),
+            Self::Broadcast(msg) => msg.message_id(),
+        }
+    }
+}
+
 impl From<EthMessage> for OutgoingMessage {
     fn from(value: EthMessage) -> Self {
         Self::Eth(value)
@@ -891,6 +921,7 @@ mod tests {
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
+                        stats: Default::default(),
                     }
                 }
                 ev => {
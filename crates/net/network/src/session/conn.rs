@@ -44,6 +44,24 @@ impl EthRlpxConnection {
         }
     }
 
+    /// Returns the length of the last frame read from the ETH stream.
+    #[inline]
+    pub(crate) const fn last_received_len(&self) -> usize {
+        match self {
+            Self::EthOnly(conn) => conn.last_received_len(),
+            Self::Satellite(conn) => conn.primary().last_received_len(),
+        }
+    }
+
+    /// Returns the length of the last frame written to the ETH stream.
+    #[inline]
+    pub(crate) const fn last_sent_len(&self) -> usize {
+        match self {
+            Self::EthOnly(conn) => conn.last_sent_len(),
+            Self::Satellite(conn) => conn.primary().last_sent_len(),
+        }
+    }
+
     /// Consumes this type and returns the wrapped [`P2PStream`].
     #[inline]
     pub(crate) fn into_inner(self) -> P2PStream<ECIESStream<TcpStream>> {
@@ -2,7 +2,7 @@
 
 use crate::{
     message::PeerMessage,
-    session::{conn::EthRlpxConnection, Direction, SessionId},
+    session::{conn::EthRlpxConnection, stats::SessionStats, Direction, SessionId},
     PendingSessionHandshakeError,
 };
 use reth_ecies::ECIESError;
@@ -11,7 +11,7 @@ use reth_eth_wire::{
     errors::EthStreamError,
     DisconnectReason, EthVersion, Status,
 };
-use reth_network_api::{PeerInfo, PeerKind};
+use reth_network_api::{PeerInfo, PeerKind, PeerStats};
 use reth_network_peers::{NodeRecord, PeerId};
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::{
@@ -75,6 +75,8 @@ pub struct ActiveSessionHandle {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// The statistics of the messages and requests of the session.
+    pub(crate) stats: SessionStats,
 }
 
 // === impl ActiveSessionHandle ===
@@ -152,6 +154,18 @@ impl ActiveSessionHandle {
             kind,
         }
     }
+
+    /// Returns the [`PeerStats`] of the session.
+    pub(crate) fn peer_stats(&self) -> PeerStats {
+        let (messages, requests) = self.stats.snapshot();
+        PeerStats {
+            peer_id: self.remote_id,
+            client_version: self.client_version.to_string(),
+            session_duration_secs: self.established.elapsed().as_secs(),
+            messages,
+            requests,
+        }
+    }
 }
 
 /// Events a pending session can produce.
@@ -1,6 +1,10 @@
 //! Support for handling peer sessions.
 
-use crate::{message::PeerMessage, metrics::SessionManagerMetrics, session::active::ActiveSession};
+use crate::{
+    message::PeerMessage,
+    metrics::SessionManagerMetrics,
+    session::{active::ActiveSession, stats::SessionStats},
+};
 use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
@@ -38,6 +42,7 @@ mod active;
 mod conn;
 mod counter;
 mod handle;
+mod stats;
 pub use crate::message::PeerRequestSender;
 use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols};
 pub use handle::{
@@ -153,6 +158,11 @@ impl SessionManager {
         self.fork_filter.validate(fork_id).is_ok()
     }
 
+    /// Returns the [`ForkFilter`] used to validate the peer's `Status` message.
+    pub(crate) const fn fork_filter(&self) -> &ForkFilter {
+        &self.fork_filter
+    }
+
     /// Returns the next unique [`SessionId`].
     fn next_id(&mut self) -> SessionId {
         let id = self.next_id;
@@ -465,6 +475,8 @@ impl SessionManager {
                 // negotiated version
                 let version = conn.version();
 
+                let stats = SessionStats::default();
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -485,6 +497,7 @@ impl SessionManager {
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
+                    stats: stats.clone(),
                 };
 
                 self.spawn(session);
@@ -502,6 +515,7 @@ impl SessionManager {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    stats,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
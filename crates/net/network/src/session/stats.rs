@@ -0,0 +1,181 @@
+//! Protocol and bandwidth statistics of active sessions.
+
+use parking_lot::Mutex;
+use reth_eth_wire::EthMessageID;
+use reth_network_api::{MessageStats, RequestStats};
+use std::{sync::Arc, time::Duration};
+
+/// The number of distinct [`EthMessageID`]s.
+const MESSAGE_IDS: usize = EthMessageID::max() as usize + 1;
+
+/// Records the messages exchanged with a peer and the latencies of the requests sent to it.
+///
+/// The [`ActiveSession`](super::active::ActiveSession) records, the
+/// [`ActiveSessionHandle`](super::ActiveSessionHandle) reports. This is cheap to clone, all clones
+/// share the same counters.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionStats {
+    inner: Arc<Mutex<SessionStatsInner>>,
+}
+
+impl SessionStats {
+    /// Records a message of `bytes` received from the peer.
+    pub(crate) fn on_message_received(&self, id: EthMessageID, bytes: usize) {
+        let mut inner = self.inner.lock();
+        let message = &mut inner.messages[id as usize];
+        message.received += 1;
+        message.received_bytes += bytes as u64;
+    }
+
+    /// Records a message of `bytes` sent to the peer.
+    pub(crate) fn on_message_sent(&self, id: EthMessageID, bytes: usize) {
+        let mut inner = self.inner.lock();
+        let message = &mut inner.messages[id as usize];
+        message.sent += 1;
+        message.sent_bytes += bytes as u64;
+    }
+
+    /// Records a request sent to the peer.
+    pub(crate) fn on_request_sent(&self, id: EthMessageID) {
+        self.inner.lock().requests[id as usize].sent += 1;
+    }
+
+    /// Records a matching response of the peer that arrived `latency` after the request was sent.
+    pub(crate) fn on_request_answered(&self, id: EthMessageID, latency: Duration) {
+        let mut inner = self.inner.lock();
+        let request = &mut inner.requests[id as usize];
+        request.answered += 1;
+        request.total_latency += latency;
+        request.max_latency = request.max_latency.max(latency);
+    }
+
+    /// Records a request that timed out.
+    pub(crate) fn on_request_timed_out(&self, id: EthMessageID) {
+        self.inner.lock().requests[id as usize].timed_out += 1;
+    }
+
+    /// Returns the stats of every type of message that was exchanged, and of every type of
+    /// request that was sent.
+    pub(crate) fn snapshot(&self) -> (Vec<MessageStats>, Vec<RequestStats>) {
+        let inner = self.inner.lock();
+        let ids =
+            (0..MESSAGE_IDS).filter_map(|index| Some((index, EthMessageID::try_from(index).ok()?)));
+
+        let messages = ids
+            .clone()
+            .filter(|(index, _)| {
+                let counters = &inner.messages[*index];
+                counters.received + counters.sent > 0
+            })
+            .map(|(index, message)| {
+                let counters = &inner.messages[index];
+                MessageStats {
+                    message,
+                    received: counters.received,
+                    received_bytes: counters.received_bytes,
+                    sent: counters.sent,
+                    sent_bytes: counters.sent_bytes,
+                }
+            })
+            .collect();
+
+        let requests = ids
+            .filter(|(index, _)| inner.requests[*index].sent > 0)
+            .map(|(index, request)| {
+                let counters = &inner.requests[index];
+                let avg_latency = counters
+                    .total_latency
+                    .checked_div(counters.answered as u32)
+                    .unwrap_or_default();
+                RequestStats {
+                    request,
+                    sent: counters.sent,
+                    answered: counters.answered,
+                    timed_out: counters.timed_out,
+                    avg_latency_ms: avg_latency.as_millis() as u64,
+                    max_latency_ms: counters.max_latency.as_millis() as u64,
+                }
+            })
+            .collect();
+
+        (messages, requests)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SessionStatsInner {
+    /// The counters of the messages, indexed by [`EthMessageID`].
+    messages: [MessageCounters; MESSAGE_IDS],
+    /// The counters of the requests sent to the peer, indexed by [`EthMessageID`].
+    requests: [RequestCounters; MESSAGE_IDS],
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct MessageCounters {
+    received: u64,
+    received_bytes: u64,
+    sent: u64,
+    sent_bytes: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RequestCounters {
+    sent: u64,
+    answered: u64,
+    timed_out: u64,
+    total_latency: Duration,
+    max_latency: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_stats_snapshot() {
+        let stats = SessionStats::default();
+        assert_eq!(stats.snapshot(), (vec![], vec![]));
+
+        stats.on_message_received(EthMessageID::Transactions, 100);
+        stats.on_message_received(EthMessageID::Transactions, 50);
+        stats.on_message_sent(EthMessageID::GetBlockHeaders, 10);
+        stats.on_request_sent(EthMessageID::GetBlockHeaders);
+        stats.on_request_sent(EthMessageID::GetBlockHeaders);
+        stats.on_request_sent(EthMessageID::GetBlockHeaders);
+        stats.on_request_answered(EthMessageID::GetBlockHeaders, Duration::from_millis(100));
+        stats.on_request_answered(EthMessageID::GetBlockHeaders, Duration::from_millis(300));
+        stats.on_request_timed_out(EthMessageID::GetBlockHeaders);
+
+        let (messages, requests) = stats.snapshot();
+        assert_eq!(
+            messages,
+            vec![
+                MessageStats {
+                    message: EthMessageID::Transactions,
+                    received: 2,
+                    received_bytes: 150,
+                    sent: 0,
+                    sent_bytes: 0,
+                },
+                MessageStats {
+                    message: EthMessageID::GetBlockHeaders,
+                    received: 0,
+                    received_bytes: 0,
+                    sent: 1,
+                    sent_bytes: 10,
+                },
+            ]
+        );
+        assert_eq!(
+            requests,
+            vec![RequestStats {
+                request: EthMessageID::GetBlockHeaders,
+                sent: 3,
+                answered: 2,
+                timed_out: 1,
+                avg_latency_ms: 200,
+                max_latency_ms: 300,
+            }]
+        );
+    }
+}
@@ -19,7 +19,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::PeerKind;
 use reth_network_peers::PeerId;
-use reth_primitives::{ForkId, B256};
+use reth_primitives::{ForkFilter, ForkId, B256};
 use reth_storage_api::BlockNumReader;
 use std::{
     collections::{HashMap, VecDeque},
@@ -139,6 +139,7 @@ where
             peer,
             ActivePeer {
                 best_hash: status.blockhash,
+                fork_id: status.forkid,
                 capabilities,
                 request_tx,
                 pending_response: None,
@@ -234,8 +235,14 @@ where
     }
 
     /// Invoked when a new [`ForkId`] is activated.
-    pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
-        self.discovery.update_fork_id(fork_id)
+    ///
+    /// Block requests are no longer sent to the peers whose [`ForkId`] is incompatible with it.
+    pub(crate) fn update_fork_id(&mut self, fork_filter: &ForkFilter) {
+        self.discovery.update_fork_id(fork_filter.current());
+        for (peer_id, peer) in &self.active_peers {
+            let is_compatible = fork_filter.validate(peer.fork_id).is_ok();
+            self.state_fetcher.set_fork_compatible(peer_id, is_compatible);
+        }
     }
 
     /// Invoked after a `NewBlock` message was received by the peer.
@@ -471,6 +478,8 @@ where
 pub(crate) struct ActivePeer {
     /// Best block of the peer.
     pub(crate) best_hash: B256,
+    /// The [`ForkId`] the peer announced in its status.
+    pub(crate) fork_id: ForkId,
     /// The capabilities of the remote peer.
     #[allow(dead_code)]
     pub(crate) capabilities: Arc<Capabilities>,
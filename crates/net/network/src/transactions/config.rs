@@ -1,8 +1,15 @@
 use super::{
+    constants::{
+        tx_fetcher::{
+            DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+        },
+        DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION,
+    },
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
 use derive_more::Constructor;
+use reth_primitives::TxType;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Default, Clone)]
@@ -10,12 +17,50 @@ use derive_more::Constructor;
 pub struct TransactionsManagerConfig {
     /// Configuration for fetching transactions.
     pub transaction_fetcher_config: TransactionFetcherConfig,
+    /// Configuration for propagating transactions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transaction_propagation_config: TransactionPropagationConfig,
+}
+
+/// Configuration for propagating transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionPropagationConfig {
+    /// Max encoded byte size of a transaction that is broadcast in full. Larger transactions are
+    /// only announced as hashes, which peers request if they don't know them yet. Spec'd at 4
+    /// KiB.
+    pub max_byte_size_full_broadcast_transaction: usize,
+}
+
+impl TransactionPropagationConfig {
+    /// Returns `true` if a transaction of the given type and encoded size must only be announced
+    /// as a hash and never broadcast in full.
+    ///
+    /// EIP-4844 transactions are always announce-only, see also
+    /// <https://eips.ethereum.org/EIPS/eip-4844#networking>.
+    pub const fn is_announce_only(&self, tx_type: TxType, size: usize) -> bool {
+        matches!(tx_type, TxType::Eip4844) || size > self.max_byte_size_full_broadcast_transaction
+    }
+}
+
+impl Default for TransactionPropagationConfig {
+    fn default() -> Self {
+        Self {
+            max_byte_size_full_broadcast_transaction:
+                DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION,
+        }
+    }
 }
 
 /// Configuration for fetching transactions.
 #[derive(Debug, Constructor, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransactionFetcherConfig {
+    /// Max inflight [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) requests.
+    pub max_inflight_requests: u32,
+    /// Max inflight [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) requests per
+    /// peer.
+    pub max_inflight_requests_per_peer: u8,
     /// Soft limit for the byte size of a
     /// [`PooledTransactions`](reth_eth_wire::PooledTransactions) response on assembling a
     /// [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) request. Spec'd at 2
@@ -29,7 +74,11 @@ pub struct TransactionFetcherConfig {
 
 impl Default for TransactionFetcherConfig {
     fn default() -> Self {
-        Self { soft_limit_byte_size_pooled_transactions_response: SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE, soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ
+        Self {
+            max_inflight_requests: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
+            max_inflight_requests_per_peer: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+            soft_limit_byte_size_pooled_transactions_response: SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
+            soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         }
     }
 }
@@ -8,6 +8,14 @@
 /// <https://github.com/ethereum/devp2p/blob/master/caps/eth.md#newpooledtransactionhashes-0x08>
 pub const SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE: usize = 4096;
 
+/// Default maximum encoded byte size of a transaction that is broadcast in full. Larger
+/// transactions are only announced as hashes.
+///
+/// Default is 4 KiB.
+///
+/// <https://github.com/ethereum/devp2p/blob/master/caps/eth.md#transactions-0x02>
+pub const DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION: usize = 4 * 1024;
+
 /// Default soft limit for the byte size of a [`Transactions`](reth_eth_wire::Transactions)
 /// broadcast message.
 ///
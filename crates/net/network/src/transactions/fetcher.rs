@@ -50,7 +50,7 @@ use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::{debug, trace};
@@ -124,8 +124,11 @@ impl TransactionFetcher {
 
     /// Sets up transaction fetcher with config
     pub fn with_transaction_fetcher_config(config: &TransactionFetcherConfig) -> Self {
-        let mut tx_fetcher = Self::default();
+        let mut tx_fetcher =
+            Self { active_peers: LruMap::new(config.max_inflight_requests), ..Default::default() };
 
+        tx_fetcher.info.max_inflight_requests = config.max_inflight_requests as usize;
+        tx_fetcher.info.max_inflight_requests_per_peer = config.max_inflight_requests_per_peer;
         tx_fetcher.info.soft_limit_byte_size_pooled_transactions_response =
             config.soft_limit_byte_size_pooled_transactions_response;
         tx_fetcher.info.soft_limit_byte_size_pooled_transactions_response_on_pack_request =
@@ -170,7 +173,7 @@ impl TransactionFetcher {
     /// Returns `true` if peer is idle with respect to `self.inflight_requests`.
     pub fn is_idle(&self, peer_id: &PeerId) -> bool {
         let Some(inflight_count) = self.active_peers.peek(peer_id) else { return true };
-        if *inflight_count < DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER {
+        if *inflight_count < self.info.max_inflight_requests_per_peer {
             return true
         }
         false
@@ -383,7 +386,7 @@ impl TransactionFetcher {
             let Some(TxFetchMetadata { retries, fallback_peers, .. }) =
                 self.hashes_fetch_inflight_and_pending_fetch.get(&hash)
             else {
-                return
+                continue
             };
 
             if let Some(peer_id) = fallback_peer {
@@ -397,6 +400,7 @@ impl TransactionFetcher {
                         "retry limit for `GetPooledTransactions` requests reached for hash, dropping hash"
                     );
 
+                    self.metrics.hashes_dropped_max_retries.increment(1);
                     max_retried_and_evicted_hashes.push(hash);
                     continue
                 }
@@ -404,6 +408,7 @@ impl TransactionFetcher {
             }
             if let (_, Some(evicted_hash)) = self.hashes_pending_fetch.insert_and_get_evicted(hash)
             {
+                self.metrics.hashes_evicted_pending_fetch.increment(1);
                 max_retried_and_evicted_hashes.push(evicted_hash);
             }
         }
@@ -549,13 +554,16 @@ impl TransactionFetcher {
                 // removed in favour of lru dead peers
                 let mut ended_sessions = vec![];
                 for &peer_id in fallback_peers.iter() {
-                    if is_session_active(peer_id) {
+                    if !is_session_active(peer_id) {
                         ended_sessions.push(peer_id);
                     }
                 }
                 for peer_id in ended_sessions {
                     fallback_peers.remove(&peer_id);
                 }
+                fallback_peers.insert(*peer_id);
+
+                self.metrics.duplicate_announced_hashes.increment(1);
 
                 return false
             }
@@ -646,12 +654,12 @@ impl TransactionFetcher {
             return Some(new_announced_hashes)
         };
 
-        if *inflight_count >= DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER {
+        if *inflight_count >= self.info.max_inflight_requests_per_peer {
             trace!(target: "net::tx",
                 peer_id=format!("{peer_id:#}"),
                 hashes=?*new_announced_hashes,
                 %conn_eth_version,
-                max_concurrent_tx_reqs_per_peer=DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+                max_concurrent_tx_reqs_per_peer=self.info.max_inflight_requests_per_peer,
                 "limit for concurrent `GetPooledTransactions` requests per peer reached"
             );
             return Some(new_announced_hashes)
@@ -901,7 +909,9 @@ impl TransactionFetcher {
     ) -> FetchEvent {
         // update peer activity, requests for buffered hashes can only be made to idle
         // fallback peers
-        let GetPooledTxResponse { peer_id, mut requested_hashes, result } = response;
+        let GetPooledTxResponse { peer_id, mut requested_hashes, result, elapsed } = response;
+
+        self.metrics.fetch_duration_seconds.record(elapsed);
 
         debug_assert!(
             self.active_peers.get(&peer_id).is_some(),
@@ -989,8 +999,19 @@ impl TransactionFetcher {
                 //
                 let requested_hashes_len = requested_hashes.len();
                 let mut fetched = Vec::with_capacity(valid_payload.len());
+                let (mut wasted, mut wasted_bytes) = (0, 0);
                 requested_hashes.retain(|requested_hash| {
-                    if valid_payload.contains_key(requested_hash) {
+                    if let Some(tx) = valid_payload.get(requested_hash) {
+                        // the transaction has been received from another peer while this request
+                        // was inflight, fetching it again was a waste of bandwidth
+                        if self
+                            .hashes_fetch_inflight_and_pending_fetch
+                            .peek(requested_hash)
+                            .is_none()
+                        {
+                            wasted += 1;
+                            wasted_bytes += tx.length_without_header();
+                        }
                         // hash is now known, stop tracking
                         fetched.push(*requested_hash);
                         return false
@@ -999,6 +1020,8 @@ impl TransactionFetcher {
                 });
                 fetched.shrink_to_fit();
                 self.metrics.fetched_transactions.increment(fetched.len() as u64);
+                self.metrics.wasted_fetched_transactions.increment(wasted);
+                self.metrics.wasted_fetched_transactions_bytes.increment(wasted_bytes as u64);
 
                 if fetched.len() < requested_hashes_len {
                     trace!(target: "net::tx",
@@ -1127,6 +1150,8 @@ pub struct GetPooledTxRequest {
     /// Transaction hashes that were requested, for cleanup purposes
     requested_hashes: RequestTxHashes,
     response: oneshot::Receiver<RequestResult<PooledTransactions>>,
+    /// Timestamp when the request was sent.
+    started_at: Instant,
 }
 
 /// Upon reception of a response, a [`GetPooledTxRequest`] is deconstructed to form a
@@ -1138,6 +1163,8 @@ pub struct GetPooledTxResponse {
     /// subset of requested hashes.
     requested_hashes: RequestTxHashes,
     result: Result<RequestResult<PooledTransactions>, RecvError>,
+    /// Time it took for the request to resolve.
+    elapsed: Duration,
 }
 
 /// Stores the response receiver made by sending a [`GetPooledTransactions`] request to a peer's
@@ -1152,12 +1179,19 @@ pub struct GetPooledTxRequestFut {
 
 impl GetPooledTxRequestFut {
     #[inline]
-    const fn new(
+    fn new(
         peer_id: PeerId,
         requested_hashes: RequestTxHashes,
         response: oneshot::Receiver<RequestResult<PooledTransactions>>,
     ) -> Self {
-        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response }) }
+        Self {
+            inner: Some(GetPooledTxRequest {
+                peer_id,
+                requested_hashes,
+                response,
+                started_at: Instant::now(),
+            }),
+        }
     }
 }
 
@@ -1171,6 +1205,7 @@ impl Future for GetPooledTxRequestFut {
                 peer_id: req.peer_id,
                 requested_hashes: req.requested_hashes,
                 result,
+                elapsed: req.started_at.elapsed(),
             }),
             Poll::Pending => {
                 self.project().inner.set(Some(req));
@@ -1290,6 +1325,8 @@ pub enum VerificationOutcome {
 pub struct TransactionFetcherInfo {
     /// Max inflight [`GetPooledTransactions`] requests.
     pub max_inflight_requests: usize,
+    /// Max inflight [`GetPooledTransactions`] requests per peer.
+    pub max_inflight_requests_per_peer: u8,
     /// Soft limit for the byte size of the expected [`PooledTransactions`] response, upon packing
     /// a [`GetPooledTransactions`] request with hashes (by default less than 2 MiB worth of
     /// transactions is requested).
@@ -1303,11 +1340,13 @@ impl TransactionFetcherInfo {
     /// Creates a new max
     pub const fn new(
         max_inflight_requests: usize,
+        max_inflight_requests_per_peer: u8,
         soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
         soft_limit_byte_size_pooled_transactions_response: usize,
     ) -> Self {
         Self {
             max_inflight_requests,
+            max_inflight_requests_per_peer,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             soft_limit_byte_size_pooled_transactions_response,
         }
@@ -1318,6 +1357,7 @@ impl Default for TransactionFetcherInfo {
     fn default() -> Self {
         Self::new(
             DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS as usize * DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER as usize,
+            DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
             SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE
         )
@@ -1516,6 +1556,52 @@ mod test {
         )
     }
 
+    #[test]
+    fn announced_inflight_hash_registers_fallback_peer() {
+        reth_tracing::init_test_tracing();
+
+        let tx_fetcher = &mut TransactionFetcher::default();
+
+        let hash = B256::from_slice(&[1; 32]);
+        let peer_1 = PeerId::new([1; 64]);
+        let peer_2 = PeerId::new([2; 64]);
+        // session of this peer has ended
+        let peer_3 = PeerId::new([3; 64]);
+
+        // hash is inflight, requested from peer_1
+        let mut backups = default_cache();
+        backups.insert(peer_3);
+        tx_fetcher
+            .hashes_fetch_inflight_and_pending_fetch
+            .insert(hash, TxFetchMetadata::new(0, backups, None));
+
+        let mut announcement = ValidAnnouncementData::from_partially_valid_data(
+            PartiallyValidData::from_raw_data_eth66([(hash, None)].into_iter().collect()),
+        );
+
+        tx_fetcher.filter_unseen_and_pending_hashes(
+            &mut announcement,
+            |_| false,
+            &peer_2,
+            |peer_id| peer_id != peer_3,
+            "",
+        );
+
+        // inflight hash is not requested again
+        assert!(announcement.is_empty());
+
+        let fallback_peers = tx_fetcher
+            .hashes_fetch_inflight_and_pending_fetch
+            .peek(&hash)
+            .unwrap()
+            .fallback_peers
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+        assert_eq!(fallback_peers, [peer_2].into_iter().collect::<HashSet<_>>());
+        assert!(!fallback_peers.contains(&peer_1));
+    }
+
     #[test]
     fn verify_response_hashes() {
         let input = hex!("02f871018302a90f808504890aef60826b6c94ddf4c5025d1a5742cf12f74eec246d4432c295e487e09c3bbcc12b2b80c080a0f21a4eacd0bf8fea9c5105c543be5a1d8c796516875710fafafdf16d16d8ee23a001280915021bb446d1973501a67f93d2b38894a514b976e7b46dc2fe54598daa");
@@ -14,6 +14,7 @@ use crate::{
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     NetworkEvents, NetworkHandle,
 };
+use alloy_rlp::Encodable;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
     EthVersion, GetPooledTransactions, HandleMempoolData, HandleVersionedMempoolData,
@@ -21,7 +22,7 @@ use reth_eth_wire::{
     PooledTransactions, RequestTxHashes, Transactions,
 };
 use reth_metrics::common::mpsc::UnboundedMeteredReceiver;
-use reth_network_api::{Peers, ReputationChangeKind};
+use reth_network_api::{BandwidthClass, Peers, ReputationChangeKind};
 use reth_network_p2p::{
     error::{RequestError, RequestResult},
     sync::SyncStateProvider,
@@ -57,7 +58,9 @@ pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
 pub mod validation;
-pub use config::{TransactionFetcherConfig, TransactionsManagerConfig};
+pub use config::{
+    TransactionFetcherConfig, TransactionPropagationConfig, TransactionsManagerConfig,
+};
 
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -244,6 +247,8 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Configuration for propagating transactions.
+    propagation_config: TransactionPropagationConfig,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -294,6 +299,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            propagation_config: transactions_manager_config.transaction_propagation_config,
             metrics,
         }
     }
@@ -407,6 +413,9 @@ where
     /// See [`NewPooledTransactionHashes`]
     ///
     /// Note: EIP-4844 are disallowed from being broadcast in full and are only ever sent as hashes, see also <https://eips.ethereum.org/EIPS/eip-4844#networking>.
+    /// The same applies to transactions larger than the
+    /// [`TransactionPropagationConfig::max_byte_size_full_broadcast_transaction`], peers that
+    /// receive full transactions are sent the hashes of these transactions in addition.
     fn propagate_transactions(
         &mut self,
         to_propagate: Vec<PropagateTransaction>,
@@ -425,6 +434,7 @@ where
             // filter all transactions unknown to the peer
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = FullTransactionsBuilder::default();
+            let mut announce_only_hashes = PooledTransactionsHashesBuilder::new(peer.version);
 
             // Iterate through the transactions to propagate and fill the hashes and full
             // transaction lists, before deciding whether or not to send full transactions to the
@@ -433,7 +443,7 @@ where
                 if peer.seen_transactions.insert(tx.hash()) {
                     hashes.push(tx);
 
-                    // Do not send full 4844 transaction hashes to peers.
+                    // Do not send full 4844 transactions and large transactions to peers.
                     //
                     //  Nodes MUST NOT automatically broadcast blob transactions to their peers.
                     //  Instead, those transactions are only announced using
@@ -441,7 +451,9 @@ where
                     //  via `GetPooledTransactions`.
                     //
                     // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
-                    if !tx.transaction.is_eip4844() {
+                    if self.propagation_config.is_announce_only(tx.transaction.tx_type(), tx.size) {
+                        announce_only_hashes.push(tx);
+                    } else {
                         full_transactions.push(tx);
                     }
                 }
@@ -481,6 +493,25 @@ where
 
                     // send full transactions
                     self.network.send_transactions(*peer_id, new_full_transactions);
+
+                    let announce_only_hashes = announce_only_hashes.build();
+                    if !announce_only_hashes.is_empty() {
+                        for hash in announce_only_hashes.iter_hashes().copied() {
+                            propagated
+                                .0
+                                .entry(hash)
+                                .or_default()
+                                .push(PropagateKind::Hash(*peer_id));
+                        }
+
+                        trace!(target: "net::tx", ?peer_id, num_txs=?announce_only_hashes.len(), "Announcing announce-only tx hashes to peer");
+                        self.metrics
+                            .announced_only_transactions
+                            .increment(announce_only_hashes.len() as u64);
+
+                        // send hashes of the transactions that must not be broadcast in full
+                        self.network.send_transactions_hashes(*peer_id, announce_only_hashes);
+                    }
                 }
             }
         }
@@ -507,12 +538,11 @@ where
         // filter all transactions unknown to the peer
         let mut full_transactions = FullTransactionsBuilder::default();
 
-        let to_propagate = self
-            .pool
-            .get_all(txs)
-            .into_iter()
-            .filter(|tx| !tx.transaction.is_eip4844())
-            .map(PropagateTransaction::new);
+        let propagation_config = self.propagation_config;
+        let to_propagate =
+            self.pool.get_all(txs).into_iter().map(PropagateTransaction::new).filter(|tx| {
+                !propagation_config.is_announce_only(tx.transaction.tx_type(), tx.size)
+            });
 
         // Iterate through the transactions to propagate and fill the hashes and full transaction
         for tx in to_propagate {
@@ -816,6 +846,7 @@ where
                 // broadcasted in full
 
                 let has_blob_txs = msg.has_eip4844();
+                self.on_gossip_received(msg.length());
 
                 let non_blob_txs = msg
                     .0
@@ -978,9 +1009,9 @@ where
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // pre-size to avoid reallocations
             let mut new_txs = Vec::with_capacity(transactions.len());
-            for tx in transactions {
-                // recover transaction
-                let tx = match tx.try_into_ecrecovered() {
+            // recover the transactions as a batch, large batches are recovered in parallel
+            for tx in PooledTransactionsElement::try_into_ecrecovered_batch(transactions) {
+                let tx = match tx {
                     Ok(tx) => tx,
                     Err(badtx) => {
                         trace!(target: "net::tx",
@@ -1087,6 +1118,7 @@ where
     fn on_fetch_event(&mut self, fetch_event: FetchEvent) {
         match fetch_event {
             FetchEvent::TransactionsFetched { peer_id, transactions } => {
+                self.on_gossip_received(transactions.length());
                 self.import_transactions(peer_id, transactions, TransactionSource::Response);
             }
             FetchEvent::FetchError { peer_id, error } => {
@@ -1101,6 +1133,15 @@ where
 
     /// Runs an operation to fetch hashes that are cached in [`TransactionFetcher`].
     fn on_fetch_hashes_pending_fetch(&mut self) {
+        // hashes stay buffered while gossip is over its bandwidth share
+        if self
+            .network
+            .bandwidth_scheduler()
+            .is_some_and(|bandwidth| bandwidth.delay(BandwidthClass::Gossip).is_some())
+        {
+            return
+        }
+
         // try drain transaction hashes pending fetch
         let info = &self.pending_pool_imports_info;
         let max_pending_pool_imports = info.max_pending_pool_imports;
@@ -1111,6 +1152,13 @@ where
             .on_fetch_pending_hashes(&self.peers, has_capacity_wrt_pending_pool_imports);
     }
 
+    /// Accounts for received gossip traffic, if a bandwidth budget is configured.
+    fn on_gossip_received(&self, bytes: usize) {
+        if let Some(bandwidth) = self.network.bandwidth_scheduler() {
+            bandwidth.consume(BandwidthClass::Gossip, bytes);
+        }
+    }
+
     fn report_peer_bad_transactions(&self, peer_id: PeerId) {
         self.report_peer(peer_id, ReputationChangeKind::BadTransactions);
         self.metrics.reported_bad_transactions.increment(1);
@@ -1615,7 +1663,7 @@ mod tests {
         error::{RequestError, RequestResult},
         sync::{NetworkSyncUpdater, SyncState},
     };
-    use reth_primitives::hex;
+    use reth_primitives::{hex, TxType};
     use reth_provider::test_utils::NoopProvider;
     use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
     use secp256k1::SecretKey;
@@ -2132,4 +2180,15 @@ mod tests {
         assert!(tx_fetcher.hashes_pending_fetch.is_empty());
         assert_eq!(tx_fetcher.active_peers.len(), 0);
     }
+
+    #[test]
+    fn test_announce_only_transactions() {
+        let config = TransactionPropagationConfig::default();
+        let max_size = config.max_byte_size_full_broadcast_transaction;
+
+        assert!(config.is_announce_only(TxType::Eip4844, 1));
+        assert!(!config.is_announce_only(TxType::Eip1559, max_size));
+        assert!(config.is_announce_only(TxType::Eip1559, max_size + 1));
+        assert!(config.is_announce_only(TxType::Legacy, max_size + 1));
+    }
 }
@@ -117,8 +117,12 @@ async fn test_get_header() {
 
         mock_provider.add_header(hash, header.clone());
 
-        let req =
-            HeadersRequest { start: hash.into(), limit: 1, direction: HeadersDirection::Falling };
+        let req = HeadersRequest {
+            start: hash.into(),
+            limit: 1,
+            skip: 0,
+            direction: HeadersDirection::Falling,
+        };
 
         let res = fetch0.get_headers(req).await;
         assert!(res.is_ok(), "{res:?}");
@@ -156,6 +156,14 @@ pub enum DownloadError {
     /// Received headers with less than expected items.
     #[error("received less headers than expected: {0}")]
     HeadersResponseTooShort(GotExpected<u64>),
+    /// Received a header that does not match the anchor of the header skeleton.
+    #[error("header {number} does not match the skeleton: {hash}")]
+    SkeletonMismatch {
+        /// Number of the mismatching header.
+        number: u64,
+        /// The received and the anchor hash.
+        hash: GotExpectedBoxed<B256>,
+    },
 
     /* ==================== BODIES ERRORS ==================== */
     /// Block validation failed
@@ -212,12 +220,12 @@ mod tests {
     #[test]
     fn test_is_likely_bad_headers_response() {
         let request =
-            HeadersRequest { start: 0u64.into(), limit: 0, direction: Default::default() };
+            HeadersRequest { start: 0u64.into(), limit: 0, skip: 0, direction: Default::default() };
         let headers: Vec<Header> = vec![];
         assert!(!Ok(headers).is_likely_bad_headers_response(&request));
 
         let request =
-            HeadersRequest { start: 0u64.into(), limit: 1, direction: Default::default() };
+            HeadersRequest { start: 0u64.into(), limit: 1, skip: 0, direction: Default::default() };
         let headers: Vec<Header> = vec![];
         assert!(Ok(headers).is_likely_bad_headers_response(&request));
     }
@@ -87,6 +87,7 @@ where
                 headers: Some(client.get_headers(HeadersRequest {
                     start: hash.into(),
                     limit: count,
+                    skip: 0,
                     direction: HeadersDirection::Falling,
                 })),
                 bodies: None,
@@ -573,6 +574,7 @@ where
                         this.request.headers = Some(this.client.get_headers(HeadersRequest {
                             start: this.start_hash.into(),
                             limit: this.count,
+                            skip: 0,
                             direction: HeadersDirection::Falling,
                         }));
                     }
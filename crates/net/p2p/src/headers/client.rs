@@ -16,6 +16,10 @@ pub struct HeadersRequest {
     pub start: BlockHashOrNumber,
     /// The response max size
     pub limit: u64,
+    /// The number of blocks to skip between two consecutive headers.
+    ///
+    /// This is `0` for a contiguous range of headers.
+    pub skip: u32,
     /// The direction in which headers should be returned.
     pub direction: HeadersDirection,
 }
@@ -57,6 +61,7 @@ pub trait HeadersClient: DownloadClient {
         let req = HeadersRequest {
             start,
             limit: 1,
+            skip: 0,
             // doesn't matter for a single header
             direction: HeadersDirection::Rising,
         };
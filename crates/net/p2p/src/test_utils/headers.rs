@@ -106,6 +106,7 @@ impl TestDownload {
         if self.fut.is_none() {
             let request = HeadersRequest {
                 limit: self.limit,
+                skip: 0,
                 direction: HeadersDirection::Rising,
                 start: reth_primitives::BlockHashOrNumber::Number(0), // ignored
             };
@@ -20,7 +20,8 @@ use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_exex::ExExContext;
+use reth_evm::dev::DevState;
+use reth_exex::{ExExBackpressurePolicy, ExExContext};
 use reth_network::{
     NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
@@ -384,6 +385,31 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node, with the given policy for when it
+    /// falls behind.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_policy<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        policy: ExExBackpressurePolicy,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<RethFullAdapter<DB, T>, CB::Components>>) -> R
+            + Send
+            + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_policy(exex_id, policy, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node and returns a handle to it.
     pub async fn launch(
         self,
@@ -412,17 +438,28 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs,
+    /// The state overrides of the dev chain, set in dev mode.
+    pub(crate) dev_state: Option<DevState>,
+    /// The transaction pool config, whose runtime settings are shared by all its clones.
+    pub(crate) pool_config: PoolConfig,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Create a new instance of [`BuilderContext`]
-    pub const fn new(
+    pub fn new(
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
         config_container: WithConfigs,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        let pool_config = config_container.config.txpool.pool_config();
+        Self { head, provider, executor, config_container, dev_state: None, pool_config }
+    }
+
+    /// Sets the state overrides of the dev chain, see [`reth_evm::dev`].
+    pub fn with_dev_state(mut self, dev_state: Option<DevState>) -> Self {
+        self.dev_state = dev_state;
+        self
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -462,9 +499,17 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the state overrides of the dev chain, which the executor must apply in --dev mode.
+    pub const fn dev_state(&self) -> Option<&DevState> {
+        self.dev_state.as_ref()
+    }
+
     /// Returns the transaction pool config of the node.
+    ///
+    /// All returned configs share the settings that can be changed at runtime, like the
+    /// [`PoolConfig::transaction_policy`], which the `admin` API changes.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        self.pool_config.clone()
     }
 
     /// Loads `EnvKzgSettings::Default`.
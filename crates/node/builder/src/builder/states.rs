@@ -13,7 +13,7 @@ use crate::{
     rpc::{RethRpcServerHandles, RpcContext, RpcHooks},
     FullNode,
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExBackpressurePolicy, ExExContext};
 use reth_network::NetworkHandle;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
@@ -202,13 +202,33 @@ impl<T: FullNodeTypes, CB: NodeComponentsBuilder<T>> NodeBuilderWithComponents<T
     /// # Note
     ///
     /// The `ExEx` ID must be unique.
-    pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
+    pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.install_exex_with_policy(exex_id, ExExBackpressurePolicy::default(), exex)
+    }
+
+    /// Installs an `ExEx` (Execution Extension) in the node, with the given policy for when it
+    /// falls behind.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_policy<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        policy: ExExBackpressurePolicy,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons.exexs.push((exex_id.into(), policy, Box::new(exex)));
         self
     }
 
@@ -243,5 +263,5 @@ pub(crate) struct NodeAddOns<Node: FullNodeComponents> {
     /// Additional RPC hooks.
     pub(crate) rpc: RpcHooks<Node>,
     /// The `ExExs` (execution extensions) of the node.
-    pub(crate) exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    pub(crate) exexs: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
 }
@@ -20,11 +20,13 @@ use reth_consensus::Consensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitDatabaseError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
-use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_evm::{dev::DevState, noop::NoopBlockExecutorProvider};
 use reth_network_p2p::headers::client::HeadersClient;
+use reth_network_peers::{NodeRecord, TrustedPeer};
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
+    manifest::{register_datadir, DatadirChain},
     node_config::NodeConfig,
 };
 use reth_primitives::{BlockNumber, Head, B256};
@@ -39,6 +41,7 @@ use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
+use reth_transaction_pool::PoolConfig;
 use std::{marker::PhantomData, sync::Arc, thread::available_parallelism};
 use tokio::sync::{
     mpsc::{unbounded_channel, Receiver, UnboundedSender},
@@ -71,11 +74,14 @@ impl LaunchContext {
     /// `config`.
     ///
     /// Attaches both the `NodeConfig` and the loaded `reth.toml` config to the launch context.
+    ///
+    /// The hardfork overrides of both configs are applied to the chain spec of the `NodeConfig`.
     pub fn with_loaded_toml_config(
         self,
-        config: NodeConfig,
+        mut config: NodeConfig,
     ) -> eyre::Result<LaunchContextWith<WithConfigs>> {
         let toml_config = self.load_toml_config(&config)?;
+        config.apply_hardfork_overrides(&toml_config).wrap_err("Invalid hardfork overrides")?;
         Ok(self.with(WithConfigs { config, toml_config }))
     }
 
@@ -203,25 +209,34 @@ impl<T> LaunchContextWith<T> {
 }
 
 impl LaunchContextWith<WithConfigs> {
-    /// Resolves the trusted peers and adds them to the toml config.
+    /// Resolves the trusted and static peers and adds them to the toml config.
     pub async fn with_resolved_peers(mut self) -> eyre::Result<Self> {
         if !self.attachment.config.network.trusted_peers.is_empty() {
             info!(target: "reth::cli", "Adding trusted nodes");
-
-            // resolve trusted peers if they use a domain instead of dns
-            let resolved = futures::future::try_join_all(
-            self.attachment.config.network.trusted_peers.iter().map(|peer| async move {
-                let backoff = ConstantBuilder::default()
-                    .with_max_times(self.attachment.config.network.dns_retries);
-                (move || { peer.resolve() })
-                    .retry(&backoff)
-                    .notify(|err, _| warn!(target: "reth::cli", "Error resolving peer domain: {err}. Retrying..."))
-                    .await
-            })).await?;
+            let resolved =
+                self.resolve_peers(&self.attachment.config.network.trusted_peers).await?;
             self.attachment.toml_config.peers.trusted_nodes.extend(resolved);
         }
+        if !self.attachment.config.network.static_peers.is_empty() {
+            info!(target: "reth::cli", "Adding static nodes");
+            let resolved = self.resolve_peers(&self.attachment.config.network.static_peers).await?;
+            self.attachment.toml_config.peers.static_nodes.extend(resolved);
+        }
         Ok(self)
     }
+
+    /// Resolves the peers if they use a domain instead of an ip.
+    async fn resolve_peers(&self, peers: &[TrustedPeer]) -> eyre::Result<Vec<NodeRecord>> {
+        let resolved = futures::future::try_join_all(peers.iter().map(|peer| async move {
+            let backoff = ConstantBuilder::default()
+                .with_max_times(self.attachment.config.network.dns_retries);
+            (move || { peer.resolve() })
+                .retry(&backoff)
+                .notify(|err, _| warn!(target: "reth::cli", "Error resolving peer domain: {err}. Retrying..."))
+                .await
+        })).await?;
+        Ok(resolved)
+    }
 }
 
 impl<L, R> LaunchContextWith<Attached<L, R>> {
@@ -318,7 +333,13 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
 
     /// Returns the configured [`PruneConfig`]
     pub fn prune_config(&self) -> Option<PruneConfig> {
-        self.toml_config().prune.clone().or_else(|| self.node_config().prune_config())
+        let mut config =
+            self.toml_config().prune.clone().or_else(|| self.node_config().prune_config());
+        // the transaction lookup distance of the command line takes priority over the config file
+        if let Some(mode) = self.node_config().pruning.txlookup_mode() {
+            config.get_or_insert_with(Default::default).segments.transaction_lookup = Some(mode);
+        }
+        config
     }
 
     /// Returns the configured [`PruneModes`], returning the default if no config was available.
@@ -363,7 +384,9 @@ where
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
+            StaticFileProvider::read_write(self.data_dir().static_files())?
+                .with_compression(self.toml_config().static_files.compression)
+                .with_receipts_layout(self.toml_config().static_files.receipts_layout),
         )
         .with_prune_modes(self.prune_modes())
         .with_static_files_metrics();
@@ -473,9 +496,31 @@ where
             .await
     }
 
+    /// Convenience function to [`Self::start_static_files_endpoint`]
+    pub async fn with_static_files_endpoint(self) -> eyre::Result<Self> {
+        self.start_static_files_endpoint().await?;
+        Ok(self)
+    }
+
+    /// Starts the endpoint that serves complete static files over HTTP, if enabled.
+    pub async fn start_static_files_endpoint(&self) -> eyre::Result<()> {
+        self.node_config()
+            .start_static_files_endpoint(
+                self.data_dir().static_files(),
+                self.task_executor().clone(),
+            )
+            .await
+    }
+
     /// Convenience function to [`Self::init_genesis`]
-    pub fn with_genesis(self) -> Result<Self, InitDatabaseError> {
+    ///
+    /// Also ensures the datadir was not initialized with another chain, and records the chain of
+    /// the datadir.
+    pub fn with_genesis(self) -> eyre::Result<Self> {
+        let data_dir = self.data_dir().data_dir();
+        DatadirChain::ensure_datadir_matches(data_dir, &self.chain_spec())?;
         init_genesis(self.provider_factory().clone())?;
+        register_datadir(data_dir, &self.chain_spec())?;
         Ok(self)
     }
 
@@ -524,18 +569,25 @@ where
     where
         T: FullNodeTypes<Provider = BlockchainProvider<<T as FullNodeTypes>::DB>>,
     {
-        let tree_config = BlockchainTreeConfig::default();
+        let mut tree_config = BlockchainTreeConfig::default();
+        if self.node_config().engine.trie_node_cache {
+            tree_config = tree_config.with_trie_node_cache(Default::default());
+        }
 
         // NOTE: This is a temporary workaround to provide the canon state notification sender to the components builder because there's a cyclic dependency between the blockchain provider and the tree component. This will be removed once the Blockchain provider no longer depends on an instance of the tree: <https://github.com/paradigmxyz/reth/issues/7154>
-        let (canon_state_notification_sender, _receiver) =
-            tokio::sync::broadcast::channel(tree_config.max_reorg_depth() as usize * 2);
+        let canon_state_notification_sender =
+            CanonStateNotificationSender::new(tree_config.max_reorg_depth() as usize * 2);
 
-        let blockchain_db = BlockchainProvider::new(
+        let mut blockchain_db = BlockchainProvider::new(
             self.provider_factory().clone(),
             Arc::new(NoopBlockchainTree::with_canon_state_notifications(
                 canon_state_notification_sender.clone(),
             )),
         )?;
+        // in dev mode, the latest and pending state show the state overrides of the dev chain
+        if self.is_dev() {
+            blockchain_db = blockchain_db.with_dev_state(DevState::default());
+        }
 
         let metered_providers = WithMeteredProviders {
             db_provider_container: WithMeteredProvider {
@@ -621,7 +673,8 @@ where
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
-        );
+        )
+        .with_dev_state(self.blockchain_db().dev_state().cloned());
 
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
@@ -666,6 +719,7 @@ where
             node_adapter,
             head,
             consensus,
+            pool_config: builder_ctx.pool_config(),
         };
 
         let ctx = LaunchContextWith {
@@ -727,6 +781,11 @@ where
         self.right().consensus.clone()
     }
 
+    /// Returns the transaction pool config the components were built with.
+    pub const fn pool_config(&self) -> &PoolConfig {
+        &self.right().pool_config
+    }
+
     /// Returns the metrics sender.
     pub fn sync_metrics_tx(&self) -> UnboundedSender<MetricEvent> {
         self.right().db_provider_container.metrics_sender.clone()
@@ -837,6 +896,7 @@ where
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
     consensus: Arc<dyn Consensus>,
+    pool_config: PoolConfig,
 }
 
 #[cfg(test)]
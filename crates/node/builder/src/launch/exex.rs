@@ -2,17 +2,20 @@
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 use futures::future;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{
+    ExExBackpressurePolicy, ExExContext, ExExHandle, ExExManager, ExExManagerHandle,
+    ExExNotification,
+};
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
-use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
+use reth_provider::{CanonStateRecvError, CanonStateSubscriptions};
+use reth_tracing::tracing::{debug, info, warn};
 use std::{fmt, fmt::Debug};
 
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs,
 }
@@ -22,7 +25,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -43,10 +46,10 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexs = Vec::with_capacity(extensions.len());
 
-        for (id, exex) in extensions {
+        for (id, policy, exex) in extensions {
             // create a new exex handle
             let (handle, events, notifications) = ExExHandle::new(id.clone());
-            exex_handles.push(handle);
+            exex_handles.push(handle.with_backpressure_policy(policy));
 
             // create the launch context for the exex
             let context = ExExContext {
@@ -90,16 +93,35 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         });
 
         // send notifications from the blockchain tree to exex manager
-        let mut canon_state_notifications = components.provider().subscribe_to_canonical_state();
+        let mut canon_state_notifications =
+            components.provider().subscribe_to_canonical_state().with_name("exex");
         let mut handle = exex_manager_handle.clone();
         components.task_executor().spawn_critical(
             "exex manager blockchain tree notifications",
             async move {
-                while let Ok(notification) = canon_state_notifications.recv().await {
-                    handle
-                        .send_async(notification.into())
-                        .await
-                        .expect("blockchain tree notification could not be sent to exex manager");
+                loop {
+                    match canon_state_notifications.recv().await {
+                        Ok(notification) => handle.send_async(notification.into()).await.expect(
+                            "blockchain tree notification could not be sent to exex manager",
+                        ),
+                        Err(CanonStateRecvError::Lagged(skipped)) => {
+                            warn!(
+                                target: "reth::cli",
+                                notifications = skipped.notifications,
+                                committed = skipped.committed.len(),
+                                reverted = skipped.reverted.len(),
+                                "ExEx manager lagged behind blockchain tree notifications"
+                            );
+                            // the ExExes have to catch up on their own
+                            handle
+                                .send_async(ExExNotification::NotificationsSkipped {
+                                    count: skipped.notifications,
+                                })
+                                .await
+                                .expect("skipped notifications could not be sent to exex manager");
+                        }
+                        Err(CanonStateRecvError::Closed) => break,
+                    }
                 }
             },
         );
@@ -5,12 +5,16 @@ use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::NodeHooks,
     node::FullNode,
+    reload::NodeConfigReloader,
+    shutdown::ShutdownCoordinator,
     NodeBuilderWithComponents, NodeHandle,
 };
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_beacon_consensus::{
+    history_merge_task,
     hooks::{EngineHooks, PruneHook, StaticFileHook},
-    BeaconConsensusEngine,
+    BackfillThresholds, BeaconConsensusEngine, ExecutionWatchdog, HistoryMerger,
+    DEFAULT_HISTORY_MERGE_INTERVAL, DEFAULT_HISTORY_MERGE_LIMIT,
 };
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
 use reth_engine_util::EngineMessageStreamExt;
@@ -22,16 +26,16 @@ use reth_node_core::{
     exit::NodeExitFuture,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
+use reth_node_events::{cl::ConsensusLayerHealthEvents, node, sync::SyncStats};
 use reth_primitives::format_ether;
-use reth_provider::providers::BlockchainProvider;
+use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
 use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::engine::ClientVersionV1;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::TransactionPool;
 use std::{future::Future, sync::Arc};
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub mod common;
@@ -117,6 +121,7 @@ where
                 info!(target: "reth::cli", "Database opened");
             })
             .with_prometheus().await?
+            .with_static_files_endpoint().await?
             .inspect(|this| {
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
@@ -151,7 +156,13 @@ where
             // Store messages _after_ skipping so that `replay-engine` command
             // would replay only the messages that were observed by the engine
             // during this run.
-            .maybe_store_messages(node_config.debug.engine_api_store.clone());
+            .maybe_store_messages(
+                node_config
+                    .engine
+                    .record
+                    .clone()
+                    .or_else(|| node_config.debug.engine_api_store.clone()),
+            );
 
         let max_block = ctx.max_block(network_client.clone()).await?;
         let mut hooks = EngineHooks::new();
@@ -167,7 +178,7 @@ where
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let (pipeline, client) = if ctx.is_dev() {
+        let (pipeline, client, dev_mining_handle) = if ctx.is_dev() {
             info!(target: "reth::cli", "Starting Reth in dev mode");
 
             for (idx, (address, alloc)) in ctx.chain_spec().genesis.alloc.iter().enumerate() {
@@ -179,15 +190,18 @@ where
                 ctx.dev_mining_mode(ctx.components().pool().pending_transactions_listener());
             info!(target: "reth::cli", mode=%mining_mode, "configuring dev mining mode");
 
-            let (_, client, mut task) = reth_auto_seal_consensus::AutoSealBuilder::new(
+            let mut builder = reth_auto_seal_consensus::AutoSealBuilder::new(
                 ctx.chain_spec(),
                 ctx.blockchain_db().clone(),
                 ctx.components().pool().clone(),
                 consensus_engine_tx.clone(),
                 mining_mode,
                 ctx.components().block_executor().clone(),
-            )
-            .build();
+            );
+            if let Some(dev_state) = ctx.blockchain_db().dev_state() {
+                builder = builder.dev_state(dev_state.clone());
+            }
+            let (_, client, mut task) = builder.build();
 
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
@@ -205,10 +219,11 @@ where
 
             let pipeline_events = pipeline.events();
             task.set_pipeline_events(pipeline_events);
+            let mining_handle = task.handle();
             debug!(target: "reth::cli", "Spawning auto mine task");
             ctx.task_executor().spawn(Box::pin(task));
 
-            (pipeline, Either::Left(client))
+            (pipeline, Either::Left(client), Some(mining_handle))
         } else {
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
@@ -224,15 +239,20 @@ where
                 pipeline_exex_handle,
             )?;
 
-            (pipeline, Either::Right(network_client.clone()))
+            (pipeline, Either::Right(network_client.clone()), None)
         };
 
         let pipeline_events = pipeline.events();
 
         let initial_target = ctx.node_config().debug.tip;
 
-        let mut pruner_builder =
-            ctx.pruner_builder().max_reorg_depth(ctx.tree_config().max_reorg_depth() as usize);
+        // the pruning interval can be changed by reloading the config file
+        let (prune_block_interval_tx, prune_block_interval_rx) =
+            watch::channel(ctx.prune_config().unwrap_or_default().block_interval);
+        let mut pruner_builder = ctx
+            .pruner_builder()
+            .max_reorg_depth(ctx.tree_config().max_reorg_depth() as usize)
+            .block_interval_updates(prune_block_interval_rx);
         if let Some(exex_manager_handle) = &exex_manager_handle {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
@@ -244,7 +264,28 @@ where
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
         hooks.add(PruneHook::new(pruner, Box::new(ctx.task_executor().clone())));
 
+        if ctx.node_config().engine.history_merge {
+            let history_merger = HistoryMerger::new(
+                ctx.provider_factory().clone(),
+                DEFAULT_HISTORY_MERGE_INTERVAL,
+                DEFAULT_HISTORY_MERGE_LIMIT,
+            );
+            ctx.task_executor().spawn(Box::pin(history_merge_task(
+                history_merger,
+                ctx.blockchain_db().canonical_state_stream(),
+                Box::new(ctx.task_executor().clone()),
+            )));
+        }
+
         // Configure the consensus engine
+        let engine_args = ctx.node_config().engine.clone();
+        let backfill_thresholds = BackfillThresholds::new(engine_args.backfill_distance)
+            .with_max_buffered_blocks(engine_args.backfill_max_buffered_blocks)
+            .with_hysteresis(
+                engine_args.backfill_hysteresis,
+                engine_args.backfill_hysteresis_period,
+            );
+        info!(target: "reth::cli", ?backfill_thresholds, "Backfill thresholds configured");
         let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
             client,
             pipeline,
@@ -254,11 +295,25 @@ where
             max_block,
             ctx.components().payload_builder().clone(),
             initial_target,
-            reth_beacon_consensus::MIN_BLOCKS_FOR_PIPELINE_RUN,
+            backfill_thresholds,
             consensus_engine_tx,
             Box::pin(consensus_engine_stream),
             hooks,
         )?;
+        let execution_watchdog = engine_args
+            .execution_deadline
+            .map(|deadline| {
+                let dump_dir = engine_args
+                    .execution_dump_dir
+                    .clone()
+                    .unwrap_or_else(|| ctx.data_dir().execution_dumps());
+                ExecutionWatchdog::spawn(deadline, Some(dump_dir))
+            })
+            .transpose()?;
+        let beacon_consensus_engine =
+            beacon_consensus_engine.with_execution_watchdog(execution_watchdog);
+        let beacon_engine_handle =
+            beacon_engine_handle.with_new_payload_timeout(engine_args.new_payload_timeout);
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -276,13 +331,15 @@ where
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
         );
+        let sync_stats = SyncStats::with_data_dir(ctx.data_dir().data_dir());
         ctx.task_executor().spawn_critical(
             "events task",
-            node::handle_events(
+            node::handle_events_with_sync_stats(
                 Some(ctx.components().network().clone()),
                 Some(ctx.head().number),
                 events,
                 database.clone(),
+                sync_stats.clone(),
             ),
         );
 
@@ -302,22 +359,50 @@ where
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
+        let config_reloader = Arc::new(NodeConfigReloader::new(
+            ctx.node_config().config.clone().unwrap_or_else(|| ctx.data_dir().config()),
+            ctx.toml_config().clone(),
+            ctx.components().network().clone(),
+            prune_block_interval_tx,
+        ));
+        #[cfg(unix)]
+        ctx.task_executor().spawn(Box::pin(config_reloader.clone().reload_on_sighup()));
+
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
         // Start RPC servers
         let (rpc_server_handles, rpc_registry) = crate::rpc::launch_rpc_servers(
             ctx.node_adapter().clone(),
+            ctx.consensus(),
+            config_reloader,
+            ctx.pool_config(),
+            Arc::new(move || sync_stats.progress()),
             engine_api,
+            dev_mining_handle,
             ctx.node_config(),
             jwt_secret,
             rpc,
         )
         .await?;
 
-        // in dev mode we generate 20 random dev-signer accounts
+        let shutdown_coordinator = ShutdownCoordinator::new(
+            ctx.node_config().shutdown,
+            rpc_server_handles.clone(),
+            ctx.provider_factory().write_gate().clone(),
+        );
+        ctx.task_executor()
+            .spawn_critical_with_graceful_shutdown_signal("shutdown coordinator", |shutdown| {
+                shutdown_coordinator.run(shutdown)
+            });
+
+        // in dev mode we generate 20 random dev-signer accounts, and sign for impersonated
+        // accounts
         if ctx.is_dev() {
             rpc_registry.eth_api().with_dev_accounts();
+            if let Some(dev_state) = ctx.blockchain_db().dev_state() {
+                rpc_registry.eth_api().with_impersonated_accounts(dev_state.clone());
+            }
         }
 
         // Run consensus engine to completion
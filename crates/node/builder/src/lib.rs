@@ -29,6 +29,12 @@ pub use handle::NodeHandle;
 
 pub mod rpc;
 
+/// Reloading of the configuration file while the node is running.
+pub mod reload;
+
+/// Coordinated shutdown of the node.
+pub mod shutdown;
+
 pub mod setup;
 
 /// Support for installing the ExExs (execution extensions) in a node.
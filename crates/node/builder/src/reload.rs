@@ -0,0 +1,184 @@
+//! Reloading of the `reth.toml` configuration while the node is running.
+
+use reth_config::Config;
+use reth_network::NetworkHandle;
+use reth_network_peers::PeerId;
+use reth_rpc::ConfigReloader;
+use reth_rpc_types::ConfigReloadReport;
+use reth_tracing::tracing::{info, warn};
+use std::{path::PathBuf, sync::Mutex};
+use tokio::sync::watch;
+
+/// The settings of the configuration file that can be changed without restarting the node.
+pub const RELOADABLE_KEYS: [&str; 4] = [
+    "peers.connection_info.max_inbound",
+    "peers.connection_info.max_outbound",
+    "peers.protected_nodes",
+    "prune.block_interval",
+];
+
+/// Reloads the configuration file of a running node and applies the changed
+/// [`RELOADABLE_KEYS`].
+///
+/// All other changed settings are reported as requiring a restart, on every reload until the node
+/// is restarted.
+#[derive(Debug)]
+pub struct NodeConfigReloader {
+    /// The path of the configuration file.
+    path: PathBuf,
+    /// The configuration that is in effect.
+    current: Mutex<Config>,
+    /// The network, to update the peer limits.
+    network: NetworkHandle,
+    /// The minimum pruning interval of the pruner.
+    prune_block_interval: watch::Sender<usize>,
+}
+
+impl NodeConfigReloader {
+    /// Creates a new reloader for the configuration file at `path`, whose settings in effect are
+    /// `current`.
+    pub const fn new(
+        path: PathBuf,
+        current: Config,
+        network: NetworkHandle,
+        prune_block_interval: watch::Sender<usize>,
+    ) -> Self {
+        Self { path, current: Mutex::new(current), network, prune_block_interval }
+    }
+
+    /// Reloads the configuration file and applies the changed settings that can be changed at
+    /// runtime.
+    pub fn reload_config(&self) -> eyre::Result<ConfigReloadReport> {
+        if !self.path.exists() {
+            eyre::bail!("config file {} does not exist", self.path.display())
+        }
+        let mut config = confy::load_path::<Config>(&self.path)?;
+
+        let mut current = self.current.lock().expect("not poisoned");
+        // this is set from the command line when the node is launched
+        config.peers.trusted_nodes_only = current.peers.trusted_nodes_only;
+
+        let (applied, requires_restart): (Vec<_>, Vec<_>) = current
+            .changed_keys(&config)?
+            .into_iter()
+            .partition(|key| RELOADABLE_KEYS.contains(&key.as_str()));
+
+        for key in &applied {
+            match key.as_str() {
+                "peers.connection_info.max_inbound" | "peers.connection_info.max_outbound" => {
+                    current.peers.connection_info.max_inbound =
+                        config.peers.connection_info.max_inbound;
+                    current.peers.connection_info.max_outbound =
+                        config.peers.connection_info.max_outbound;
+                }
+                "peers.protected_nodes" => {
+                    for peer_id in
+                        current.peers.protected_nodes.difference(&config.peers.protected_nodes)
+                    {
+                        self.network.unprotect_peer(*peer_id);
+                    }
+                    for peer_id in
+                        config.peers.protected_nodes.difference(&current.peers.protected_nodes)
+                    {
+                        self.network.protect_peer(*peer_id);
+                    }
+                    current.peers.protected_nodes = config.peers.protected_nodes.clone();
+                }
+                "prune.block_interval" => {
+                    let (Some(current_prune), Some(prune)) = (&mut current.prune, &config.prune)
+                    else {
+                        continue
+                    };
+                    current_prune.block_interval = prune.block_interval;
+                    self.prune_block_interval.send_replace(prune.block_interval);
+                }
+                _ => unreachable!("all reloadable keys are handled"),
+            }
+        }
+        if applied.iter().any(|key| key.starts_with("peers.connection_info.")) {
+            let limits = &current.peers.connection_info;
+            self.network.set_peer_limits(limits.max_inbound, limits.max_outbound);
+        }
+
+        let report = ConfigReloadReport { applied, requires_restart };
+        if report.is_empty() {
+            info!(target: "reth::cli", path = ?self.path, "Configuration reloaded, nothing changed");
+        } else {
+            info!(target: "reth::cli", path = ?self.path, applied = ?report.applied, "Configuration reloaded");
+        }
+        if !report.requires_restart.is_empty() {
+            warn!(target: "reth::cli", keys = ?report.requires_restart, "Changed settings take effect after a restart");
+        }
+        Ok(report)
+    }
+
+    /// Adds the peer to or removes it from the protected peers, and writes the protected peers
+    /// to the configuration file.
+    ///
+    /// Returns `false` if the peer already was or wasn't protected.
+    pub fn set_peer_protected(&self, peer_id: PeerId, protected: bool) -> eyre::Result<bool> {
+        let mut current = self.current.lock().expect("not poisoned");
+        let mut protected_nodes = current.peers.protected_nodes.clone();
+        let changed = if protected {
+            protected_nodes.insert(peer_id)
+        } else {
+            protected_nodes.remove(&peer_id)
+        };
+        if !changed {
+            return Ok(false)
+        }
+
+        // only the protected peers are written, other changes to the file that were not reloaded
+        // yet are kept
+        let mut config = if self.path.exists() {
+            confy::load_path::<Config>(&self.path)?
+        } else {
+            current.clone()
+        };
+        config.peers.protected_nodes = protected_nodes.clone();
+        config.save(&self.path)?;
+
+        current.peers.protected_nodes = protected_nodes;
+        if protected {
+            self.network.protect_peer(peer_id);
+        } else {
+            self.network.unprotect_peer(peer_id);
+        }
+        info!(target: "reth::cli", ?peer_id, protected, path = ?self.path, "Updated protected peers");
+        Ok(true)
+    }
+
+    /// Reloads the configuration every time the process receives `SIGHUP`.
+    #[cfg(unix)]
+    pub async fn reload_on_sighup(self: std::sync::Arc<Self>) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to listen for SIGHUP, configuration reloads are only available over RPC");
+                return
+            }
+        };
+        while hangup.recv().await.is_some() {
+            info!(target: "reth::cli", "Received SIGHUP, reloading configuration");
+            if let Err(err) = self.reload_config() {
+                warn!(target: "reth::cli", %err, "Failed to reload configuration");
+            }
+        }
+    }
+}
+
+impl ConfigReloader for NodeConfigReloader {
+    fn reload(&self) -> Result<ConfigReloadReport, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.reload_config()?)
+    }
+
+    fn set_peer_protected(
+        &self,
+        peer_id: PeerId,
+        protected: bool,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self::set_peer_protected(self, peer_id, protected)?)
+    }
+}
@@ -3,22 +3,39 @@
 use std::{
     fmt,
     ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Duration,
 };
 
 use futures::TryFutureExt;
+use reth_auto_seal_consensus::{AnvilApi, MiningHandle};
+use reth_consensus::Consensus;
 use reth_network::NetworkHandle;
 use reth_node_api::FullNodeComponents;
-use reth_node_core::{node_config::NodeConfig, rpc::api::EngineApiServer};
+use reth_node_core::{
+    node_config::NodeConfig,
+    rpc::api::{
+        AdminConfigApiServer, AdminTxPoolApiServer, AnvilApiServer, EngineApiServer,
+        GanacheApiServer, HardhatApiServer, RethSyncApiServer, RethValidationApiServer,
+    },
+    utils::read_jwt_secrets_from_globs,
+};
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_rpc::eth::EthApi;
+use reth_rpc::{
+    eth::{EthApi, TransactionForwarder},
+    AdminConfigApi, AdminTxPoolApi, ConfigReloader, RethSyncApi, RethValidationApi,
+    SyncProgressProvider,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    EthApiBuild, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    EthApiBuild, RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle,
+    TransportRpcModules,
 };
-use reth_rpc_layer::JwtSecret;
+use reth_rpc_layer::{JwtSecret, JwtSecrets};
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
+use reth_tracing::tracing::{debug, info, warn};
+use reth_transaction_pool::PoolConfig;
 
 /// Contains the handles to the spawned RPC servers.
 ///
@@ -250,10 +267,43 @@ impl<'a, Node: FullNodeComponents> RpcContext<'a, Node> {
     }
 }
 
+/// The interval in which the additional JWT secrets of the auth server are re-read.
+const JWT_SECRETS_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically re-reads the additional JWT secrets from the files matching the patterns, so the
+/// secrets can be rotated without restarting the node.
+///
+/// If the files can't be read, the previous secrets stay in place.
+async fn reload_jwt_secrets(patterns: Vec<String>, secrets: JwtSecrets) {
+    let mut interval = tokio::time::interval(JWT_SECRETS_RELOAD_INTERVAL);
+    // the first tick completes immediately, right after the secrets were read
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match read_jwt_secrets_from_globs(&patterns) {
+            Ok(reloaded) => {
+                if reloaded.len() != secrets.len() {
+                    info!(target: "reth::cli", secrets = reloaded.len(), "Reloaded additional JWT secrets");
+                }
+                secrets.set(reloaded);
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to reload additional JWT secrets, keeping the previous ones");
+            }
+        }
+    }
+}
+
 /// Launch the rpc servers.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn launch_rpc_servers<Node, Engine>(
     node: Node,
+    consensus: Arc<dyn Consensus>,
+    config_reloader: Arc<dyn ConfigReloader>,
+    pool_config: &PoolConfig,
+    sync_progress: Arc<dyn SyncProgressProvider>,
     engine_api: Engine,
+    dev_mining_handle: Option<MiningHandle>,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
@@ -264,7 +314,14 @@ where
 {
     let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
-    let auth_config = config.rpc.auth_server_config(jwt_secret)?;
+    let mut auth_config = config.rpc.auth_server_config(jwt_secret)?;
+    if !config.rpc.auth_jwtsecret_globs.is_empty() {
+        let patterns = config.rpc.auth_jwtsecret_globs.clone();
+        let secrets = JwtSecrets::new(read_jwt_secrets_from_globs(&patterns)?);
+        info!(target: "reth::cli", secrets = secrets.len(), "Accepting additional JWT secrets");
+        node.task_executor().spawn(Box::pin(reload_jwt_secrets(patterns, secrets.clone())));
+        auth_config = auth_config.with_additional_secrets(secrets);
+    }
     let module_config = config.rpc.transport_rpc_module_config();
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
@@ -278,6 +335,56 @@ where
         .build_with_auth_server(module_config, engine_api, EthApiBuild::build);
 
     let mut registry = RpcRegistry { registry };
+
+    // the block validation API needs the consensus and executor of the node, which the module
+    // builder doesn't know about
+    modules.merge_if_module_configured(
+        RethRpcModule::Reth,
+        RethValidationApi::new(
+            node.provider().clone(),
+            consensus,
+            node.block_executor().clone(),
+            Box::new(node.task_executor().clone()),
+        )
+        .into_rpc(),
+    )?;
+
+    modules.merge_if_module_configured(
+        RethRpcModule::Reth,
+        RethSyncApi::new(sync_progress).into_rpc(),
+    )?;
+
+    modules.merge_if_module_configured(
+        RethRpcModule::Admin,
+        AdminConfigApi::new(config_reloader).into_rpc(),
+    )?;
+
+    modules.merge_if_module_configured(
+        RethRpcModule::Admin,
+        AdminTxPoolApi::new(pool_config).into_rpc(),
+    )?;
+
+    // in dev mode, blocks can be mined and accounts changed on demand with the `anvil_`,
+    // `hardhat_` and `evm_` methods on all transports
+    if let Some(handle) = dev_mining_handle {
+        let anvil_api = AnvilApi::new(handle);
+        modules.merge_configured(AnvilApiServer::into_rpc(anvil_api.clone()))?;
+        modules.merge_configured(HardhatApiServer::into_rpc(anvil_api.clone()))?;
+        modules.merge_configured(GanacheApiServer::into_rpc(anvil_api))?;
+    }
+
+    // register the configured raw transaction forwarder, this can still be overridden by
+    // `extend_rpc_modules`, e.g. with a sequencer client
+    if let Some(url) = &config.rpc.rpc_tx_forward_url {
+        let mode = config.rpc.rpc_tx_forward_mode;
+        info!(target: "reth::cli", %url, %mode, "Forwarding raw transactions");
+        registry.set_eth_raw_transaction_forwarder(Arc::new(TransactionForwarder::new(
+            url.clone(),
+            mode,
+            config.rpc.rpc_tx_forward_max_retries,
+        )));
+    }
+
     let ctx = RpcContext {
         node: node.clone(),
         config,
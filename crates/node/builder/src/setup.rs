@@ -122,6 +122,7 @@ where
                     prune_modes,
                     exex_manager_handle,
                 )
+                .with_adaptive_batch_size(stage_config.execution.adaptive.batch_size())
                 .with_metrics_tx(metrics_tx),
             ),
         )
@@ -0,0 +1,123 @@
+//! Coordinated shutdown of a running node.
+
+use crate::rpc::RethRpcServerHandles;
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_node_core::args::ShutdownArgs;
+use reth_provider::providers::WriteGate;
+use reth_tasks::shutdown::GracefulShutdown;
+use reth_tracing::tracing::{info, warn};
+use std::fmt;
+
+/// The phases of the shutdown of a node, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShutdownPhase {
+    /// The node is running.
+    Running = 0,
+    /// The RPC servers stop accepting new connections.
+    StoppingRpc = 1,
+    /// In-flight RPC requests are given time to complete.
+    DrainingRpc = 2,
+    /// Outstanding database writes, e.g. blocks of the engine and stage checkpoints, are given
+    /// time to be committed.
+    WaitingForWrites = 3,
+    /// New database writes are rejected and the writes that are still in flight are drained.
+    ClosingDatabase = 4,
+    /// The shutdown completed.
+    Done = 5,
+}
+
+impl fmt::Display for ShutdownPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = match self {
+            Self::Running => "running",
+            Self::StoppingRpc => "stopping rpc",
+            Self::DrainingRpc => "draining rpc",
+            Self::WaitingForWrites => "waiting for writes",
+            Self::ClosingDatabase => "closing database",
+            Self::Done => "done",
+        };
+        f.write_str(phase)
+    }
+}
+
+/// Metrics of the shutdown of the node.
+#[derive(Metrics)]
+#[metrics(scope = "shutdown")]
+struct ShutdownMetrics {
+    /// The current [`ShutdownPhase`], `0` while the node is running.
+    phase: Gauge,
+}
+
+/// Shuts down the RPC servers and the database of a node in order once the node is told to shut
+/// down.
+#[derive(Debug)]
+pub struct ShutdownCoordinator {
+    /// The timeouts of the shutdown.
+    args: ShutdownArgs,
+    /// The handles of the RPC servers.
+    rpc: RethRpcServerHandles,
+    /// The gate of the database writes.
+    write_gate: WriteGate,
+}
+
+impl ShutdownCoordinator {
+    /// Creates a new coordinator.
+    pub const fn new(args: ShutdownArgs, rpc: RethRpcServerHandles, write_gate: WriteGate) -> Self {
+        Self { args, rpc, write_gate }
+    }
+
+    /// Waits for the shutdown signal and then shuts the node down.
+    ///
+    /// The graceful shutdown of the node waits until this completes.
+    pub async fn run(self, shutdown: GracefulShutdown) {
+        let metrics = ShutdownMetrics::default();
+        let enter = |phase: ShutdownPhase| {
+            metrics.phase.set(phase as u8 as f64);
+            info!(target: "reth::cli", %phase, "Shutting down");
+        };
+        metrics.phase.set(ShutdownPhase::Running as u8 as f64);
+
+        let guard = shutdown.await;
+        let Self { args, rpc, write_gate } = self;
+
+        enter(ShutdownPhase::StoppingRpc);
+        let RethRpcServerHandles { rpc, auth } = rpc;
+        // the servers are already stopped if the node handle was dropped
+        let _ = rpc.clone().stop();
+        let _ = auth.clone().stop();
+
+        enter(ShutdownPhase::DrainingRpc);
+        let drained = async {
+            rpc.stopped().await;
+            auth.stopped().await;
+        };
+        if tokio::time::timeout(args.rpc_drain_timeout, drained).await.is_err() {
+            warn!(target: "reth::cli", timeout = ?args.rpc_drain_timeout, "RPC requests did not complete in time");
+        }
+
+        enter(ShutdownPhase::WaitingForWrites);
+        let timeout = args.write_timeout;
+        let gate = write_gate.clone();
+        let idle = tokio::task::spawn_blocking(move || gate.wait_for_writers(timeout)).await;
+        if !idle.unwrap_or_default() {
+            warn!(target: "reth::cli", ?timeout, writers = write_gate.writers(), "Database writes did not complete in time");
+        }
+
+        // from here on new writes fail, the database is closed once the remaining tasks dropped
+        // their handles to it
+        enter(ShutdownPhase::ClosingDatabase);
+        let gate = write_gate.clone();
+        let drained = tokio::task::spawn_blocking(move || gate.close(timeout)).await;
+        if !drained.unwrap_or_default() {
+            warn!(
+                target: "reth::cli",
+                ?timeout,
+                writers = write_gate.writers(),
+                "Database writes did not drain in time, they may be unwound on the next start"
+            );
+        }
+
+        enter(ShutdownPhase::Done);
+        drop(guard);
+    }
+}
@@ -1,6 +1,6 @@
 //! clap [Args](clap::Args) for debugging purposes
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 use reth_primitives::B256;
 use std::path::PathBuf;
 
@@ -55,6 +55,23 @@ pub struct DebugArgs {
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
+
+    /// If provided, every executed block is checked to conserve the ETH supply: the balance
+    /// changes of the block must match the issued rewards and withdrawals minus the burned fees.
+    ///
+    /// Violations are either logged or fail the execution of the block with an internal error,
+    /// which doesn't mark the block as invalid.
+    #[arg(long = "debug.supply-check", help_heading = "Debug", value_enum, value_name = "MODE")]
+    pub supply_check: Option<SupplyCheckMode>,
+}
+
+/// How violations of the ETH supply check are handled.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum SupplyCheckMode {
+    /// Log the violation and continue
+    Log,
+    /// Fail the execution of the block with an internal error
+    Halt,
 }
 
 #[cfg(test)]
@@ -75,4 +92,11 @@ mod tests {
         let args = CommandParser::<DebugArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_supply_check() {
+        let args =
+            CommandParser::<DebugArgs>::parse_from(["reth", "--debug.supply-check", "halt"]).args;
+        assert_eq!(args.supply_check, Some(SupplyCheckMode::Halt));
+    }
 }
@@ -13,6 +13,7 @@ pub struct DevArgs {
     ///
     /// This mode uses a local proof-of-authority consensus engine with either fixed block times
     /// or automatically mined blocks.
+    /// Blocks can also be mined on demand with the `evm_mine` RPC method.
     /// Disables network discovery and enables local http server.
     /// Prefunds 20 accounts derived by mnemonic "test test test test test test test test test test
     /// test junk" with 10 000 ETH each.
@@ -0,0 +1,159 @@
+//! clap [Args](clap::Args) for the consensus engine
+
+use clap::Args;
+use reth_primitives::constants::EPOCH_SLOTS;
+use std::{path::PathBuf, time::Duration};
+
+/// Parameters for the consensus engine
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Engine")]
+pub struct EngineArgs {
+    /// The largest gap between the canonical head and the sync target that is synced by
+    /// downloading and executing blocks one by one. Larger gaps are backfilled with the pipeline.
+    ///
+    /// If zero, the pipeline is always used.
+    #[arg(long = "engine.backfill-distance", value_name = "BLOCKS", default_value_t = EPOCH_SLOTS)]
+    pub backfill_distance: u64,
+
+    /// The maximum number of downloaded blocks that are buffered while waiting for their missing
+    /// ancestors before the remaining gap is backfilled with the pipeline.
+    #[arg(long = "engine.backfill-max-buffered-blocks", value_name = "BLOCKS")]
+    pub backfill_max_buffered_blocks: Option<u64>,
+
+    /// The number of blocks the backfill distance is raised by after a pipeline run, which
+    /// prevents switching back and forth between live sync and backfill when the gap hovers
+    /// around the distance.
+    #[arg(long = "engine.backfill-hysteresis", value_name = "BLOCKS", default_value_t = EPOCH_SLOTS)]
+    pub backfill_hysteresis: u64,
+
+    /// How long after a pipeline run the backfill distance is raised.
+    #[arg(
+        long = "engine.backfill-hysteresis-period",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "10m"
+    )]
+    pub backfill_hysteresis_period: Duration,
+
+    /// Records all engine API messages, the responses of the node to them, and when they were
+    /// received and sent in the given directory.
+    ///
+    /// The recording can be replayed against a fresh node with `reth debug replay-engine <DIR>`.
+    #[arg(long = "engine.record", value_name = "DIR")]
+    pub record: Option<PathBuf>,
+
+    /// Reports the executions of blocks that take longer than the given duration, with the hash
+    /// of the block and a dump of the executing thread.
+    #[arg(long = "engine.execution-deadline", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub execution_deadline: Option<Duration>,
+
+    /// The directory to write the dumps of block executions exceeding the deadline to.
+    ///
+    /// Defaults to `<DIR>/<CHAIN_ID>/execution-dumps`.
+    #[arg(long = "engine.execution-dump-dir", value_name = "DIR", requires = "execution_deadline")]
+    pub execution_dump_dir: Option<PathBuf>,
+
+    /// Responds to `engine_newPayload` with SYNCING if the engine didn't respond within the given
+    /// duration, instead of stalling the consensus layer until the block is executed.
+    ///
+    /// The duration is measured from the moment the payload is received, so it includes the time
+    /// the payload waits for the engine to finish previous messages. The block is still executed
+    /// in the background.
+    #[arg(long = "engine.new-payload-timeout", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub new_payload_timeout: Option<Duration>,
+
+    /// Merges the sealed shards of the account and storage history indices into full shards in
+    /// the background, as the canonical chain advances.
+    #[arg(long = "engine.history-merge")]
+    pub history_merge: bool,
+
+    /// Caches the upper branch nodes of the account and storage tries between the state root
+    /// computations of blocks extending the canonical chain.
+    #[arg(long = "engine.trie-node-cache")]
+    pub trie_node_cache: bool,
+}
+
+impl Default for EngineArgs {
+    fn default() -> Self {
+        Self {
+            backfill_distance: EPOCH_SLOTS,
+            backfill_max_buffered_blocks: None,
+            backfill_hysteresis: EPOCH_SLOTS,
+            backfill_hysteresis_period: Duration::from_secs(10 * 60),
+            record: None,
+            execution_deadline: None,
+            execution_dump_dir: None,
+            new_payload_timeout: None,
+            history_merge: false,
+            trie_node_cache: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn engine_args_default_sanity_test() {
+        let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EngineArgs::default());
+    }
+
+    #[test]
+    fn parse_backfill_thresholds() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.backfill-distance",
+            "128",
+            "--engine.backfill-max-buffered-blocks",
+            "64",
+            "--engine.backfill-hysteresis",
+            "0",
+            "--engine.backfill-hysteresis-period",
+            "1m",
+        ])
+        .args;
+        assert_eq!(args.backfill_distance, 128);
+        assert_eq!(args.backfill_max_buffered_blocks, Some(64));
+        assert_eq!(args.backfill_hysteresis, 0);
+        assert_eq!(args.backfill_hysteresis_period, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn parse_record_dir() {
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.record", "/tmp/engine"])
+                .args;
+        assert_eq!(args.record, Some(PathBuf::from("/tmp/engine")));
+    }
+
+    #[test]
+    fn parse_execution_deadline() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.execution-deadline",
+            "4s",
+            "--engine.new-payload-timeout",
+            "8s",
+        ])
+        .args;
+        assert_eq!(args.execution_deadline, Some(Duration::from_secs(4)));
+        assert_eq!(args.new_payload_timeout, Some(Duration::from_secs(8)));
+
+        assert!(CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.execution-dump-dir",
+            "/tmp/dumps"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! clap [Args](clap::Args) for the EVM that executes blocks
+
+use clap::{Args, ValueEnum};
+
+/// Parameters for the EVM that executes blocks
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "EVM")]
+pub struct EvmArgs {
+    /// The EVM implementation that executes blocks.
+    #[arg(
+        long = "evm.backend",
+        value_enum,
+        value_name = "BACKEND",
+        default_value_t = EvmBackend::Revm
+    )]
+    pub backend: EvmBackend,
+}
+
+impl Default for EvmArgs {
+    fn default() -> Self {
+        Self { backend: EvmBackend::Revm }
+    }
+}
+
+/// The EVM implementations that can execute blocks.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum EvmBackend {
+    /// The revm interpreter
+    Revm,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_evm_args() {
+        let args = CommandParser::<EvmArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EvmArgs::default());
+
+        let args = CommandParser::<EvmArgs>::parse_from(["reth", "--evm.backend", "revm"]).args;
+        assert_eq!(args.backend, EvmBackend::Revm);
+
+        assert!(
+            CommandParser::<EvmArgs>::try_parse_from(["reth", "--evm.backend", "unknown"]).is_err()
+        );
+    }
+}
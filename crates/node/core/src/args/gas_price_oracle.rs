@@ -1,9 +1,9 @@
 use crate::primitives::U256;
 use clap::Args;
-use reth_rpc_eth_types::GasPriceOracleConfig;
+use reth_rpc_eth_types::{GasPriceOracleConfig, GasPriceOracleStrategy};
 use reth_rpc_server_types::constants::gas_oracle::{
-    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE,
+    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_EWMA_WEIGHT, DEFAULT_GAS_PRICE_PERCENTILE,
+    DEFAULT_IGNORE_GAS_PRICE, DEFAULT_MAX_GAS_PRICE,
 };
 
 /// Parameters to configure Gas Price Oracle
@@ -25,17 +25,27 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// The strategy used to estimate the gas price: percentile, pending-pool or ewma
+    #[arg(long = "gpo.strategy", default_value_t = GasPriceOracleStrategy::Percentile)]
+    pub strategy: GasPriceOracleStrategy,
+
+    /// The weight in percent of the newest block in the moving average of the ewma strategy
+    #[arg(long = "gpo.ewma-weight", default_value_t = DEFAULT_GAS_PRICE_EWMA_WEIGHT)]
+    pub ewma_weight: u32,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self { blocks, ignore_price, max_price, percentile, strategy, ewma_weight } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            strategy: *strategy,
+            ewma_weight: *ewma_weight,
             ..Default::default()
         }
     }
@@ -48,6 +58,8 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            strategy: GasPriceOracleStrategy::Percentile,
+            ewma_weight: DEFAULT_GAS_PRICE_EWMA_WEIGHT,
         }
     }
 }
@@ -73,10 +85,34 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                strategy: GasPriceOracleStrategy::Percentile,
+                ewma_weight: DEFAULT_GAS_PRICE_EWMA_WEIGHT,
             }
         );
     }
 
+    #[test]
+    fn test_parse_gpo_strategy() {
+        let args = CommandParser::<GasPriceOracleArgs>::parse_from([
+            "reth",
+            "--gpo.strategy",
+            "ewma",
+            "--gpo.ewma-weight",
+            "30",
+        ])
+        .args;
+        let config = args.gas_price_oracle_config();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::Ewma);
+        assert_eq!(config.ewma_weight, 30);
+
+        assert!(CommandParser::<GasPriceOracleArgs>::try_parse_from([
+            "reth",
+            "--gpo.strategy",
+            "median"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn gpo_args_default_sanity_test() {
         let default_args = GasPriceOracleArgs::default();
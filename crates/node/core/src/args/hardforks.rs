@@ -0,0 +1,64 @@
+//! Hardfork activation override arguments
+
+use clap::Args;
+use reth_chainspec::{EthereumHardfork, HardforkOverrides};
+
+/// Parameters for overriding the activation of hardforks of the chain
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Hardfork overrides")]
+pub struct HardforkOverrideArgs {
+    /// Overrides the activation timestamp of Shanghai.
+    #[arg(long = "override.shanghai", value_name = "TIMESTAMP")]
+    pub shanghai: Option<u64>,
+
+    /// Overrides the activation timestamp of Cancun.
+    #[arg(long = "override.cancun", value_name = "TIMESTAMP")]
+    pub cancun: Option<u64>,
+
+    /// Overrides the activation timestamp of Prague.
+    #[arg(long = "override.prague", value_name = "TIMESTAMP")]
+    pub prague: Option<u64>,
+}
+
+impl HardforkOverrideArgs {
+    /// Returns the configured overrides.
+    pub fn overrides(&self) -> HardforkOverrides {
+        [
+            (EthereumHardfork::Shanghai, self.shanghai),
+            (EthereumHardfork::Cancun, self.cancun),
+            (EthereumHardfork::Prague, self.prague),
+        ]
+        .into_iter()
+        .filter_map(|(fork, timestamp)| Some((fork, timestamp?)))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_hardfork_overrides() {
+        let args = CommandParser::<HardforkOverrideArgs>::parse_from(["reth"]).args;
+        assert!(args.overrides().is_empty());
+
+        let args = CommandParser::<HardforkOverrideArgs>::parse_from([
+            "reth",
+            "--override.prague",
+            "1800000000",
+        ])
+        .args;
+        let overrides = args.overrides();
+        assert_eq!(overrides.get(EthereumHardfork::Prague), Some(1800000000));
+        assert_eq!(overrides.get(EthereumHardfork::Cancun), None);
+    }
+}
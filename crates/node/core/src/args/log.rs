@@ -66,6 +66,40 @@ pub struct LogArgs {
         default_value_t = ColorMode::Always
     )]
     pub color: ColorMode,
+
+    /// The gRPC endpoint of an OpenTelemetry collector to export spans to over OTLP, e.g.
+    /// `http://localhost:4317`. If not set, spans are not exported.
+    #[cfg(feature = "otlp")]
+    #[arg(
+        long = "tracing.otlp-endpoint",
+        value_name = "URL",
+        global = true,
+        help_heading = "Tracing"
+    )]
+    pub otlp_endpoint: Option<String>,
+
+    /// The ratio of traces that are exported, between 0 and 1.
+    #[cfg(feature = "otlp")]
+    #[arg(
+        long = "tracing.otlp-sample-ratio",
+        value_name = "RATIO",
+        global = true,
+        default_value_t = 1.0,
+        help_heading = "Tracing"
+    )]
+    pub otlp_sample_ratio: f64,
+
+    /// The filter to use for the exported spans.
+    #[cfg(feature = "otlp")]
+    #[arg(
+        long = "tracing.otlp-filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "debug",
+        help_heading = "Tracing"
+    )]
+    pub otlp_filter: String,
+
     /// The verbosity settings for the tracer.
     #[command(flatten)]
     pub verbosity: Verbosity,
@@ -110,6 +144,15 @@ impl LogArgs {
             tracer = tracer.with_file(file, info);
         }
 
+        #[cfg(feature = "otlp")]
+        if let Some(endpoint) = &self.otlp_endpoint {
+            tracer = tracer.with_otlp(
+                reth_tracing::OtlpConfig::new(endpoint)
+                    .with_sample_ratio(self.otlp_sample_ratio)
+                    .with_filter(&self.otlp_filter),
+            );
+        }
+
         let guard = tracer.init()?;
         Ok(guard)
     }
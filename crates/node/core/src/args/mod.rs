@@ -14,7 +14,7 @@ pub use rpc_state_cache::RpcStateCacheArgs;
 
 /// DebugArgs struct for debugging purposes
 mod debug;
-pub use debug::DebugArgs;
+pub use debug::{DebugArgs, SupplyCheckMode};
 
 /// DatabaseArgs struct for configuring the database
 mod database;
@@ -48,6 +48,22 @@ pub use dev::DevArgs;
 mod pruning;
 pub use pruning::PruningArgs;
 
+/// HardforkOverrideArgs for overriding the activation of hardforks
+mod hardforks;
+pub use hardforks::HardforkOverrideArgs;
+
+/// EvmArgs for configuring the EVM that executes blocks
+mod evm;
+pub use evm::{EvmArgs, EvmBackend};
+
+/// ShutdownArgs for configuring the graceful shutdown of the node
+mod shutdown;
+pub use shutdown::ShutdownArgs;
+
+/// EngineArgs for configuring the consensus engine
+mod engine;
+pub use engine::EngineArgs;
+
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
 pub use datadir_args::DatadirArgs;
@@ -12,7 +12,14 @@ use reth_discv5::{
 use reth_net_nat::NatResolver;
 use reth_network::{
     transactions::{
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        constants::{
+            tx_fetcher::{
+                DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
+                DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+            },
+            DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION,
+        },
+        TransactionFetcherConfig, TransactionPropagationConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
@@ -46,6 +53,14 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_only: bool,
 
+    #[allow(clippy::doc_markdown)]
+    /// Comma separated enode URLs of static peers that are redialed with an exponential backoff
+    /// whenever they disconnect and are never banned.
+    ///
+    /// --static-peers enode://abcd@192.168.0.1:30303
+    #[arg(long, value_delimiter = ',')]
+    pub static_peers: Vec<TrustedPeer>,
+
     /// Comma separated enode URLs for P2P discovery bootstrap.
     ///
     /// Will fall back to a network-specific default if not specified.
@@ -76,7 +91,9 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|natpmp|publicip|extip:\<IP\>)
+    ///
+    /// With `upnp` and `natpmp`, the listener and discovery ports are also mapped on the gateway.
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -96,6 +113,14 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
 
+    /// Max concurrent `GetPooledTransactions` requests.
+    #[arg(long = "max-tx-reqs", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS, verbatim_doc_comment)]
+    pub max_concurrent_tx_requests: u32,
+
+    /// Max concurrent `GetPooledTransactions` requests per peer.
+    #[arg(long = "max-tx-reqs-peer", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER, verbatim_doc_comment)]
+    pub max_concurrent_tx_requests_per_peer: u8,
+
     /// Experimental, for usage in research. Sets the max accumulated byte size of transactions
     /// to pack in one response.
     /// Spec'd at 2MiB.
@@ -115,6 +140,14 @@ pub struct NetworkArgs {
     /// Default is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ, verbatim_doc_comment)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Max encoded byte size of a transaction that is broadcast in full to peers.
+    ///
+    /// Larger transactions are only announced as hashes, as are all blob transactions.
+    ///
+    /// Default is 4 KiB.
+    #[arg(long = "max-tx-broadcast-size", value_name = "BYTES", default_value_t = DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION, verbatim_doc_comment)]
+    pub max_byte_size_full_broadcast_transaction: usize,
 }
 
 impl NetworkArgs {
@@ -158,9 +191,15 @@ impl NetworkArgs {
         // Configure transactions manager
         let transactions_manager_config = TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::new(
+                self.max_concurrent_tx_requests,
+                self.max_concurrent_tx_requests_per_peer,
                 self.soft_limit_byte_size_pooled_transactions_response,
                 self.soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             ),
+            transaction_propagation_config: TransactionPropagationConfig {
+                max_byte_size_full_broadcast_transaction: self
+                    .max_byte_size_full_broadcast_transaction,
+            },
         };
 
         // Configure basic network stack
@@ -169,10 +208,12 @@ impl NetworkArgs {
                 self.persistent_peers_file(peers_file).as_deref(),
             ))
             .external_ip_resolver(self.nat)
+            .port_mapping(self.nat)
             .sessions_config(
                 SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
             )
             .peer_config(peers_config)
+            .bandwidth_config(config.bandwidth.clone())
             .boot_nodes(chain_bootnodes.clone())
             .chain_spec(chain_spec)
             .transactions_manager_config(transactions_manager_config)
@@ -239,6 +280,7 @@ impl Default for NetworkArgs {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
             trusted_only: false,
+            static_peers: vec![],
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
@@ -250,9 +292,12 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            max_concurrent_tx_requests: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
+            max_concurrent_tx_requests_per_peer: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
+            max_byte_size_full_broadcast_transaction: DEFAULT_MAX_BYTE_SIZE_FULL_BROADCAST_TRANSACTION,
         }
     }
 }
@@ -444,6 +489,9 @@ mod tests {
         let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "none"]).args;
         assert_eq!(args.nat, NatResolver::None);
 
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "natpmp"]).args;
+        assert_eq!(args.nat, NatResolver::NatPmp);
+
         let args =
             CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "extip:0.0.0.0"]).args;
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
@@ -32,6 +32,11 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum number of the best pool transactions to speculatively execute on top of each new
+    /// head block, to warm the state for the next payload. Disabled if zero.
+    #[arg(long = "builder.prefetch-txs", default_value = "0", value_name = "COUNT")]
+    pub prefetch_transactions: usize,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -42,6 +47,7 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            prefetch_transactions: 0,
         }
     }
 }
@@ -66,6 +72,10 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn prefetch_transactions(&self) -> usize {
+        self.prefetch_transactions
+    }
 }
 
 #[derive(Clone, Debug, Default)]
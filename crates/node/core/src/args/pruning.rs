@@ -13,19 +13,32 @@ pub struct PruningArgs {
     /// This flag takes priority over pruning configuration in reth.toml.
     #[arg(long, default_value_t = false)]
     pub full: bool,
+
+    /// Only index the transactions of the most recent N blocks by hash, like the
+    /// `txlookuplimit` of geth.
+    ///
+    /// Transactions of older blocks can't be looked up by hash, and the RPC returns an error
+    /// instead of `null` for a transaction that is not found. The index of older blocks can be
+    /// rebuilt with `reth db rebuild-tx-lookup`. This flag takes priority over the transaction
+    /// lookup pruning configuration in reth.toml.
+    #[arg(long = "prune.txlookup.distance", value_name = "BLOCKS")]
+    pub txlookup_distance: Option<u64>,
 }
 
 impl PruningArgs {
     /// Returns pruning configuration.
     pub fn prune_config(&self, chain_spec: &ChainSpec) -> Option<PruneConfig> {
         if !self.full {
-            return None
+            return self.txlookup_mode().map(|mode| PruneConfig {
+                segments: PruneModes { transaction_lookup: Some(mode), ..PruneModes::none() },
+                ..Default::default()
+            })
         }
         Some(PruneConfig {
             block_interval: 5,
             segments: PruneModes {
                 sender_recovery: Some(PruneMode::Full),
-                transaction_lookup: None,
+                transaction_lookup: self.txlookup_mode(),
                 receipts: chain_spec
                     .deposit_contract
                     .as_ref()
@@ -43,6 +56,12 @@ impl PruningArgs {
             },
         })
     }
+
+    /// Returns the prune mode of the transaction lookup index configured by
+    /// `--prune.txlookup.distance`.
+    pub fn txlookup_mode(&self) -> Option<PruneMode> {
+        self.txlookup_distance.map(PruneMode::Distance)
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +82,16 @@ mod tests {
         let args = CommandParser::<PruningArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn parse_txlookup_distance() {
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--prune.txlookup.distance", "1000"])
+                .args;
+        assert_eq!(args.txlookup_distance, Some(1000));
+
+        let config = args.prune_config(&reth_chainspec::MAINNET).unwrap();
+        assert_eq!(config.segments.transaction_lookup, Some(PruneMode::Distance(1000)));
+        assert_eq!(config.segments.sender_recovery, None);
+    }
 }
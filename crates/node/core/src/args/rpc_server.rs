@@ -4,15 +4,17 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_rpc_types_engine::JwtSecret;
 use clap::{
-    builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
+    builder::{PossibleValue, PossibleValuesParser, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
 use rand::Rng;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_cli_util::parse_duration_from_secs;
+use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection, TxForwardMode};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -102,6 +104,22 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Glob patterns of files with additional JWT secrets the engine-API RPC server accepts,
+    /// e.g. `/etc/reth/jwt/*.hex`.
+    ///
+    /// The matching files are re-read periodically while the node runs, so the consensus layer
+    /// can be switched to a new secret by adding its file before and removing the old one after.
+    #[arg(long = "authrpc.jwtsecret-glob", value_name = "GLOB", value_delimiter = ',')]
+    pub auth_jwtsecret_globs: Vec<String>,
+
+    /// Only accept engine-API JWTs whose `iss` claim matches one of the given issuers.
+    #[arg(long = "authrpc.jwt-issuer", value_name = "ISSUER", value_delimiter = ',')]
+    pub auth_jwt_issuers: Vec<String>,
+
+    /// Only accept engine-API JWTs whose `aud` claim contains one of the given audiences.
+    #[arg(long = "authrpc.jwt-audience", value_name = "AUDIENCE", value_delimiter = ',')]
+    pub auth_jwt_audiences: Vec<String>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -118,6 +136,18 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Require the JWT of `--rpc.jwtsecret` only for the methods of the given namespaces, e.g.
+    /// `debug,admin,trace`, while all other namespaces stay public.
+    ///
+    /// Calls of the protected namespaces without a valid JWT are rejected with an error.
+    #[arg(
+        long = "rpc.jwtsecret-namespaces",
+        value_name = "NAMESPACES",
+        requires = "rpc_jwtsecret",
+        value_parser = RpcModuleSelectionValueParser::default()
+    )]
+    pub rpc_jwtsecret_namespaces: Option<RpcModuleSelection>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -138,6 +168,24 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Maximum number of steps the struct log tracer records per transaction, later steps are
+    /// left out of the trace.
+    #[arg(long = "rpc.struct-log-max-steps", value_name = "COUNT", default_value_t = constants::DEFAULT_STRUCT_LOG_MAX_STEPS)]
+    pub rpc_struct_log_max_steps: usize,
+
+    /// Maximum number of memory bytes the struct log tracer captures per step.
+    #[arg(long = "rpc.struct-log-max-memory", value_name = "BYTES", default_value_t = constants::DEFAULT_STRUCT_LOG_MAX_MEMORY_BYTES)]
+    pub rpc_struct_log_max_memory: usize,
+
+    /// Maximum number of storage entries the struct log tracer reports per step.
+    #[arg(long = "rpc.struct-log-max-storage-entries", value_name = "COUNT", default_value_t = constants::DEFAULT_STRUCT_LOG_MAX_STORAGE_ENTRIES)]
+    pub rpc_struct_log_max_storage_entries: usize,
+
+    /// Maximum number of memory and stack bytes the struct log tracer captures per transaction,
+    /// the trace fails beyond it.
+    #[arg(long = "rpc.struct-log-max-total-memory", value_name = "BYTES", default_value_t = constants::DEFAULT_STRUCT_LOG_MAX_TOTAL_BYTES)]
+    pub rpc_struct_log_max_total_memory: usize,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -166,6 +214,53 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Prefix of the ids of installed filters.
+    ///
+    /// Lets load balancers route the polls of a filter to the node that installed it.
+    #[arg(long = "rpc.filter-id-prefix", value_name = "PREFIX")]
+    pub rpc_filter_id_prefix: Option<String>,
+
+    /// File to persist installed log and block filters to, so they survive restarts.
+    #[arg(long = "rpc.filter-store", value_name = "PATH")]
+    pub rpc_filter_store: Option<PathBuf>,
+
+    /// How long after its last poll a persisted filter is restored on startup, in seconds.
+    #[arg(
+        long = "rpc.filter-store-ttl",
+        value_name = "SECONDS",
+        value_parser = parse_duration_from_secs,
+        default_value = "300",
+        requires = "rpc_filter_store"
+    )]
+    pub rpc_filter_store_ttl: Duration,
+
+    /// Endpoint to forward transactions submitted via `eth_sendRawTransaction` to, e.g. a private
+    /// relay.
+    #[arg(long = "rpc.tx-forward-url", value_name = "HTTP_URL")]
+    pub rpc_tx_forward_url: Option<String>,
+
+    /// How transactions are forwarded to `--rpc.tx-forward-url`.
+    ///
+    /// `mirror` forwards transactions in the background and also submits them to the local pool,
+    /// `forward-only` only forwards them and returns forwarding errors to the caller.
+    #[arg(
+        long = "rpc.tx-forward-mode",
+        value_name = "MODE",
+        default_value_t = TxForwardMode::Mirror,
+        value_parser = PossibleValuesParser::new(["mirror", "forward-only"]).map(|mode| mode.parse::<TxForwardMode>().unwrap()),
+        requires = "rpc_tx_forward_url"
+    )]
+    pub rpc_tx_forward_mode: TxForwardMode,
+
+    /// Maximum number of retries with exponential backoff when forwarding a transaction fails.
+    #[arg(
+        long = "rpc.tx-forward-max-retries",
+        value_name = "COUNT",
+        default_value_t = constants::DEFAULT_TX_FORWARD_MAX_RETRIES,
+        requires = "rpc_tx_forward_url"
+    )]
+    pub rpc_tx_forward_max_retries: u32,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -285,18 +380,32 @@ impl Default for RpcServerArgs {
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_jwtsecret_globs: Vec::new(),
+            auth_jwt_issuers: Vec::new(),
+            auth_jwt_audiences: Vec::new(),
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             rpc_jwtsecret: None,
+            rpc_jwtsecret_namespaces: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_struct_log_max_steps: constants::DEFAULT_STRUCT_LOG_MAX_STEPS,
+            rpc_struct_log_max_memory: constants::DEFAULT_STRUCT_LOG_MAX_MEMORY_BYTES,
+            rpc_struct_log_max_storage_entries: constants::DEFAULT_STRUCT_LOG_MAX_STORAGE_ENTRIES,
+            rpc_struct_log_max_total_memory: constants::DEFAULT_STRUCT_LOG_MAX_TOTAL_BYTES,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_filter_id_prefix: None,
+            rpc_filter_store: None,
+            rpc_filter_store_ttl: Duration::from_secs(300),
+            rpc_tx_forward_url: None,
+            rpc_tx_forward_mode: TxForwardMode::Mirror,
+            rpc_tx_forward_max_retries: constants::DEFAULT_TX_FORWARD_MAX_RETRIES,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -384,6 +493,54 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_filter_store_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.filter-id-prefix",
+            "node-1-",
+            "--rpc.filter-store",
+            "filters.json",
+        ])
+        .args;
+
+        assert_eq!(args.rpc_filter_id_prefix.as_deref(), Some("node-1-"));
+        assert_eq!(args.rpc_filter_store, Some(PathBuf::from("filters.json")));
+        assert_eq!(args.rpc_filter_store_ttl, Duration::from_secs(300));
+
+        // the ttl requires a store
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.filter-store-ttl",
+            "60"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_tx_forward_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.tx-forward-url",
+            "http://localhost:8545",
+            "--rpc.tx-forward-mode",
+            "forward-only",
+        ])
+        .args;
+
+        assert_eq!(args.rpc_tx_forward_url.as_deref(), Some("http://localhost:8545"));
+        assert_eq!(args.rpc_tx_forward_mode, TxForwardMode::ForwardOnly);
+        assert_eq!(args.rpc_tx_forward_max_retries, constants::DEFAULT_TX_FORWARD_MAX_RETRIES);
+
+        // the mode requires an endpoint
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.tx-forward-mode",
+            "forward-only",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RPC_BLOCK_CACHE_MAX_LEN,
+    DEFAULT_TRACE_CACHE_SIZE_BYTES_MB,
 };
 
 /// Parameters to configure RPC state cache.
@@ -29,12 +30,31 @@ pub struct RpcStateCacheArgs {
     )]
     pub max_envs: u32,
 
+    /// Max number of assembled `eth_getBlockBy*` responses of recent blocks in cache.
+    ///
+    /// Set to 0 to disable the block response cache.
+    #[arg(
+        long = "rpc-cache.max-rpc-blocks",
+        default_value_t = DEFAULT_RPC_BLOCK_CACHE_MAX_LEN,
+    )]
+    pub max_rpc_blocks: u32,
+
     /// Max number of concurrent database requests.
     #[arg(
         long = "rpc-cache.max-concurrent-db-requests",
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max memory in megabytes for cached `debug_` and `trace_` results of recent blocks.
+    ///
+    /// Set to 0 to disable the trace cache.
+    #[arg(
+        long = "rpc-cache.max-trace-memory",
+        value_name = "MB",
+        default_value_t = DEFAULT_TRACE_CACHE_SIZE_BYTES_MB,
+    )]
+    pub max_trace_memory: usize,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -43,7 +63,9 @@ impl Default for RpcStateCacheArgs {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_rpc_blocks: DEFAULT_RPC_BLOCK_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_trace_memory: DEFAULT_TRACE_CACHE_SIZE_BYTES_MB,
         }
     }
 }
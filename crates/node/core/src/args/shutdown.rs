@@ -0,0 +1,80 @@
+//! Shutdown arguments
+
+use clap::Args;
+use std::time::Duration;
+
+/// Parameters for the graceful shutdown of the node
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Shutdown")]
+pub struct ShutdownArgs {
+    /// How long in-flight RPC requests are given to complete after the RPC servers stopped
+    /// accepting new connections.
+    #[arg(
+        long = "shutdown.rpc-drain-timeout",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "5s"
+    )]
+    pub rpc_drain_timeout: Duration,
+
+    /// How long outstanding database writes, e.g. of blocks of the engine or stage checkpoints,
+    /// are given to be committed before the database is closed.
+    ///
+    /// Writes that did not finish in time may be unwound on the next start.
+    #[arg(
+        long = "shutdown.write-timeout",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "30s"
+    )]
+    pub write_timeout: Duration,
+}
+
+impl ShutdownArgs {
+    /// Returns the time the whole shutdown of the node may take.
+    ///
+    /// This includes some margin for stopping the remaining tasks.
+    pub fn timeout(&self) -> Duration {
+        self.rpc_drain_timeout + self.write_timeout + Duration::from_secs(5)
+    }
+}
+
+impl Default for ShutdownArgs {
+    fn default() -> Self {
+        Self { rpc_drain_timeout: Duration::from_secs(5), write_timeout: Duration::from_secs(30) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn shutdown_args_default_sanity_test() {
+        let args = CommandParser::<ShutdownArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ShutdownArgs::default());
+    }
+
+    #[test]
+    fn parse_shutdown_timeouts() {
+        let args = CommandParser::<ShutdownArgs>::parse_from([
+            "reth",
+            "--shutdown.rpc-drain-timeout",
+            "500ms",
+            "--shutdown.write-timeout",
+            "1m",
+        ])
+        .args;
+        assert_eq!(args.rpc_drain_timeout, Duration::from_millis(500));
+        assert_eq!(args.write_timeout, Duration::from_secs(60));
+        assert_eq!(args.timeout(), Duration::from_millis(65_500));
+    }
+}
@@ -2,13 +2,25 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
+use reth_fs_util::FsPathError;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    blob_proofs::{BlobProofComputation, DEFAULT_MAX_BLOB_PROOF_COMPUTATIONS},
+    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    maintain::{
+        LocalTransactionBackupConfig, DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT,
+        DEFAULT_LOCAL_TXS_BACKUP_LIFETIME,
+    },
+    policy::AddressLists,
+    tip_floor::{TipFloor, TipFloorMode, DEFAULT_TIP_FLOOR_BLOCKS, DEFAULT_TIP_FLOOR_PERCENTILE},
+    validate::DEFAULT_MAX_TX_INPUT_BYTES,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    DEFAULT_BASEFEE_TRANSACTION_LIFETIME, DEFAULT_PRICE_BUMP, DEFAULT_QUEUED_TRANSACTION_LIFETIME,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, time::Duration};
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -34,6 +46,15 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.queued-max-size", alias = "txpool.queued_max_size", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
 
+    /// How long the queued transactions of a sender are kept after its last transaction was
+    /// added to the pool, e.g. `3h`.
+    #[arg(long = "txpool.queued-lifetime", value_parser = humantime::parse_duration, default_value = "3h")]
+    pub queued_lifetime: Duration,
+    /// How long the basefee transactions of a sender are kept after its last transaction was
+    /// added to the pool, e.g. `1h`.
+    #[arg(long = "txpool.basefee-lifetime", value_parser = humantime::parse_duration, default_value = "1h")]
+    pub basefee_lifetime: Duration,
+
     /// Max number of executable transaction slots guaranteed per account
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
@@ -63,6 +84,52 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+
+    /// Backs up locally submitted transactions to the given file instead of
+    /// `txpool-transactions-backup.rlp` in the data directory. The backup is written periodically
+    /// and on shutdown, and its transactions are reinserted into the pool after a restart, until
+    /// they are mined or expire.
+    #[arg(long = "txpool.locals-journal", value_name = "PATH")]
+    pub locals_journal: Option<PathBuf>,
+    /// Max number of backed up local transactions per account.
+    #[arg(long = "txpool.locals-journal-account-limit", default_value_t = DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT)]
+    pub locals_journal_account_limit: usize,
+    /// How long local transactions are kept in the backup, e.g. `3h`.
+    #[arg(long = "txpool.locals-journal-lifetime", value_parser = humantime::parse_duration, default_value = "3h")]
+    pub locals_journal_lifetime: Duration,
+
+    /// Path to a JSON file with the sender and recipient lists of the transaction policy.
+    ///
+    /// The file has the fields `allowedSenders`, `deniedSenders`, `deniedRecipients` and
+    /// `rejectUnlisted`. The lists can be replaced at runtime with `admin_setTxpoolPolicy`.
+    #[arg(long = "txpool.policy", value_name = "PATH")]
+    pub policy: Option<PathBuf>,
+
+    /// Computes the KZG proofs of blob transactions that are submitted over RPC with blobs but
+    /// without proofs.
+    ///
+    /// This can be toggled at runtime with `admin_setBlobProofComputation`.
+    #[arg(long = "txpool.compute-blob-proofs")]
+    pub compute_blob_proofs: bool,
+    /// Max number of blob transactions whose proofs are computed concurrently, transactions above
+    /// the limit are rejected.
+    #[arg(long = "txpool.max-blob-proof-computations", default_value_t = DEFAULT_MAX_BLOB_PROOF_COMPUTATIONS)]
+    pub max_blob_proof_computations: usize,
+
+    /// Minimum effective tip in wei that non-local transactions must pay at the current base fee
+    /// to be accepted into the pool.
+    #[arg(long = "txpool.min-tip", value_name = "WEI", conflicts_with = "dynamic_min_tip")]
+    pub min_tip: Option<u128>,
+    /// Derives the minimum tip of non-local transactions from the effective tips of the
+    /// transactions of recent blocks.
+    #[arg(long = "txpool.dynamic-min-tip")]
+    pub dynamic_min_tip: bool,
+    /// Percentile of the effective tips of recent blocks that is used as the dynamic minimum tip.
+    #[arg(long = "txpool.min-tip-percentile", default_value_t = DEFAULT_TIP_FLOOR_PERCENTILE, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub min_tip_percentile: u8,
+    /// Number of recent blocks whose effective tips determine the dynamic minimum tip.
+    #[arg(long = "txpool.min-tip-blocks", default_value_t = DEFAULT_TIP_FLOOR_BLOCKS)]
+    pub min_tip_blocks: usize,
 }
 
 impl Default for TxPoolArgs {
@@ -74,6 +141,8 @@ impl Default for TxPoolArgs {
             basefee_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+            queued_lifetime: DEFAULT_QUEUED_TRANSACTION_LIFETIME,
+            basefee_lifetime: DEFAULT_BASEFEE_TRANSACTION_LIFETIME,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
@@ -82,10 +151,57 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            locals_journal: None,
+            locals_journal_account_limit: DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT,
+            locals_journal_lifetime: DEFAULT_LOCAL_TXS_BACKUP_LIFETIME,
+            policy: None,
+            compute_blob_proofs: false,
+            max_blob_proof_computations: DEFAULT_MAX_BLOB_PROOF_COMPUTATIONS,
+            min_tip: None,
+            dynamic_min_tip: false,
+            min_tip_percentile: DEFAULT_TIP_FLOOR_PERCENTILE,
+            min_tip_blocks: DEFAULT_TIP_FLOOR_BLOCKS,
         }
     }
 }
 
+impl TxPoolArgs {
+    /// Returns the configuration of the local transactions backup, which is written to
+    /// `default_path` unless `--txpool.locals-journal` is set.
+    pub fn local_transactions_backup_config(
+        &self,
+        default_path: PathBuf,
+    ) -> LocalTransactionBackupConfig {
+        let path = self.locals_journal.clone().unwrap_or(default_path);
+        LocalTransactionBackupConfig::with_local_txs_backup(path)
+            .with_account_limit(self.locals_journal_account_limit)
+            .with_lifetime(self.locals_journal_lifetime)
+    }
+
+    /// Returns how the minimum tip of transactions is determined.
+    pub const fn tip_floor_mode(&self) -> TipFloorMode {
+        if self.dynamic_min_tip {
+            return TipFloorMode::Dynamic {
+                percentile: self.min_tip_percentile,
+                blocks: self.min_tip_blocks,
+            }
+        }
+        match self.min_tip {
+            Some(min_tip) => TipFloorMode::Static(min_tip),
+            None => TipFloorMode::Disabled,
+        }
+    }
+
+    /// Loads the lists of the transaction policy from the `--txpool.policy` file, if set.
+    pub fn policy_lists(&self) -> Result<Option<AddressLists>, FsPathError> {
+        let Some(path) = &self.policy else { return Ok(None) };
+        let contents = reth_fs_util::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|source| FsPathError::ReadJson { source, path: path.clone() })
+    }
+}
+
 impl RethTransactionPoolConfig for TxPoolArgs {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig {
@@ -116,6 +232,14 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            transaction_policy: Default::default(),
+            blob_proof_computation: BlobProofComputation::new(
+                self.compute_blob_proofs,
+                self.max_blob_proof_computations,
+            ),
+            tip_floor: TipFloor::new(self.tip_floor_mode()),
+            queued_lifetime: self.queued_lifetime,
+            basefee_lifetime: self.basefee_lifetime,
         }
     }
 }
@@ -138,4 +262,47 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_locals_journal() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.locals-journal",
+            "transactions.rlp",
+            "--txpool.locals-journal-lifetime",
+            "30m",
+        ])
+        .args;
+        let config = args.local_transactions_backup_config(PathBuf::from("backup.rlp"));
+        assert_eq!(config.transactions_path, Some(PathBuf::from("transactions.rlp")));
+        assert_eq!(config.lifetime, Duration::from_secs(30 * 60));
+        assert_eq!(config.account_limit, DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT);
+    }
+
+    #[test]
+    fn txpool_parse_min_tip() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.min-tip", "100"]).args;
+        assert_eq!(args.tip_floor_mode(), TipFloorMode::Static(100));
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.dynamic-min-tip",
+            "--txpool.min-tip-percentile",
+            "50",
+        ])
+        .args;
+        assert_eq!(
+            args.tip_floor_mode(),
+            TipFloorMode::Dynamic { percentile: 50, blocks: DEFAULT_TIP_FLOOR_BLOCKS }
+        );
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.min-tip",
+            "100",
+            "--txpool.dynamic-min-tip",
+        ])
+        .is_err());
+    }
 }
@@ -1,9 +1,16 @@
 //! Clap parser utilities
 
+use crate::manifest::{default_chain, DEFAULT_CHAIN};
 use alloy_genesis::Genesis;
 use reth_chainspec::ChainSpec;
-use reth_fs_util as fs;
-use std::{path::PathBuf, sync::Arc};
+use reth_db_common::genesis::read_genesis;
+use reth_primitives::B256;
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use reth_chainspec::DEV;
 
@@ -29,8 +36,10 @@ pub fn chain_help() -> String {
 ///
 /// The value parser matches either a known chain, the path
 /// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// [`DEFAULT_CHAIN`] is resolved to the chain returned by [`default_chain`].
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
+        DEFAULT_CHAIN => return chain_value_parser(&default_chain()),
         #[cfg(not(feature = "optimism"))]
         "mainnet" => MAINNET.clone(),
         #[cfg(not(feature = "optimism"))]
@@ -50,26 +59,44 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
         _ => {
             // try to read json from path first
-            let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
-                Ok(raw) => raw,
+            let path = PathBuf::from(shellexpand::full(s)?.into_owned());
+            let genesis = match File::open(&path) {
+                Ok(file) => parse_genesis_file(file, &path)?,
                 Err(io_err) => {
                     // valid json may start with "\n", but must contain "{"
                     if s.contains('{') {
-                        s.to_string()
+                        // both serialized Genesis and ChainSpec structs supported
+                        serde_json::from_str(s)?
                     } else {
                         return Err(io_err.into()) // assume invalid path
                     }
                 }
             };
 
-            // both serialized Genesis and ChainSpec structs supported
-            let genesis: Genesis = serde_json::from_str(&raw)?;
-
             Arc::new(genesis.into())
         }
     })
 }
 
+/// Parses a genesis file.
+///
+/// If the genesis carries the pre-computed state root, see
+/// [`ChainSpec::precomputed_genesis_state_root`], its alloc is skipped and never loaded into
+/// memory. The alloc of such a genesis is imported with `reth init --genesis.alloc`.
+fn parse_genesis_file(file: File, path: &Path) -> eyre::Result<Genesis> {
+    let genesis = read_genesis(BufReader::new(file), |_, _| Ok(()))?;
+    let state_root = genesis.config.extra_fields.get_deserialized::<B256>("stateRoot");
+    if state_root.as_ref().is_some_and(|root| root.is_ok()) {
+        return Ok(genesis)
+    }
+    if let Some(Err(err)) = state_root {
+        eyre::bail!("invalid `stateRoot` in the genesis config: {err}")
+    }
+
+    // both serialized Genesis and ChainSpec structs supported
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +107,17 @@ mod tests {
             chain_value_parser(chain).unwrap();
         }
     }
+
+    #[test]
+    fn reject_malformed_genesis_state_root() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#"{"config":{"chainId":1337,"stateRoot":"0x1234"},"difficulty":"0x1","gasLimit":"0x1c9c380","alloc":{}}"#,
+        )
+        .unwrap();
+
+        let err = chain_value_parser(file.path().to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("stateRoot"), "{err}");
+    }
 }
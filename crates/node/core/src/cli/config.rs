@@ -29,6 +29,10 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Maximum number of the best pool transactions to speculatively execute on top of each new
+    /// head block, zero if the prefetching is disabled.
+    fn prefetch_transactions(&self) -> usize;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -343,6 +343,13 @@ impl<D> ChainPath<D> {
     pub fn jwt(&self) -> PathBuf {
         self.data_dir().join("jwt.hex")
     }
+
+    /// Returns the path to the directory for the dumps of hung block executions.
+    ///
+    /// `<DIR>/<CHAIN_ID>/execution-dumps`
+    pub fn execution_dumps(&self) -> PathBuf {
+        self.data_dir().join("execution-dumps")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -12,8 +12,10 @@ pub mod args;
 pub mod cli;
 pub mod dirs;
 pub mod exit;
+pub mod manifest;
 pub mod metrics;
 pub mod node_config;
+pub mod static_files_server;
 pub mod utils;
 pub mod version;
 
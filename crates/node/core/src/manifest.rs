@@ -0,0 +1,230 @@
+//! Bookkeeping of the chains whose datadirs are managed by a reth installation.
+//!
+//! Every datadir records the chain it was initialized with in [`DATADIR_CHAIN_FILE`], which is
+//! checked on startup so a datadir is never opened with the chain spec of another chain. All
+//! datadirs in use are listed in the [`ChainManifest`] in the reth data directory.
+
+use crate::dirs::data_dir;
+use reth_chainspec::{Chain, ChainSpec};
+use reth_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// Name of the file in a datadir that records the chain the datadir belongs to.
+pub const DATADIR_CHAIN_FILE: &str = "chain.toml";
+
+/// Name of the manifest file in the reth data directory.
+pub const MANIFEST_FILE: &str = "chains.toml";
+
+/// The chain a datadir belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatadirChain {
+    /// The id of the chain.
+    pub chain_id: u64,
+    /// The hash of the genesis block of the chain.
+    pub genesis_hash: B256,
+}
+
+impl DatadirChain {
+    /// Returns the chain of the chain spec.
+    pub fn new(chain_spec: &ChainSpec) -> Self {
+        Self { chain_id: chain_spec.chain.id(), genesis_hash: chain_spec.genesis_hash() }
+    }
+
+    /// Returns the chain.
+    pub fn chain(&self) -> Chain {
+        Chain::from_id(self.chain_id)
+    }
+
+    /// Reads the chain recorded in the datadir, if any.
+    pub fn read(datadir: &Path) -> eyre::Result<Option<Self>> {
+        let path = datadir.join(DATADIR_CHAIN_FILE);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(toml::from_str(&reth_fs_util::read_to_string(path)?)?))
+    }
+
+    /// Records the chain in the datadir.
+    pub fn write(&self, datadir: &Path) -> eyre::Result<()> {
+        reth_fs_util::create_dir_all(datadir)?;
+        reth_fs_util::write(datadir.join(DATADIR_CHAIN_FILE), toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Ensures the datadir was not initialized with another chain than the one of the chain spec.
+    ///
+    /// Datadirs that don't record their chain yet are accepted.
+    pub fn ensure_datadir_matches(datadir: &Path, chain_spec: &ChainSpec) -> eyre::Result<()> {
+        let Some(recorded) = Self::read(datadir)? else { return Ok(()) };
+        let expected = Self::new(chain_spec);
+        if recorded.genesis_hash != expected.genesis_hash {
+            eyre::bail!(
+                "the datadir {} belongs to chain {} with genesis {}, but chain {} with genesis {} \
+                 was specified. Run with `--chain {}` or use another `--datadir`",
+                datadir.display(),
+                recorded.chain(),
+                recorded.genesis_hash,
+                expected.chain(),
+                expected.genesis_hash,
+                recorded.chain(),
+            )
+        }
+        Ok(())
+    }
+}
+
+/// Records the chain of the chain spec in the datadir if it doesn't record one yet, and adds the
+/// datadir to the [`ChainManifest`].
+///
+/// This is called once the genesis of the chain spec was verified against the database. Failing
+/// to update the manifest is not fatal.
+pub fn register_datadir(datadir: &Path, chain_spec: &ChainSpec) -> eyre::Result<()> {
+    let chain = DatadirChain::new(chain_spec);
+    if DatadirChain::read(datadir)?.is_none() {
+        chain.write(datadir)?;
+    }
+
+    let update = ChainManifest::load().and_then(|mut manifest| {
+        manifest.record(&chain, datadir);
+        manifest.save()
+    });
+    if let Err(err) = update {
+        warn!(target: "reth::cli", %err, "Failed to update the chain manifest");
+    }
+    Ok(())
+}
+
+/// The manifest of all datadirs used by this reth installation, stored as [`MANIFEST_FILE`] in
+/// the reth data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChainManifest {
+    /// The chain that is used if no `--chain` is specified, as accepted by `--chain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_chain: Option<String>,
+    /// The known datadirs.
+    pub datadirs: Vec<ManifestEntry>,
+}
+
+/// A datadir listed in the [`ChainManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// The path of the datadir.
+    pub path: PathBuf,
+    /// The chain of the datadir.
+    #[serde(flatten)]
+    pub chain: DatadirChain,
+    /// The unix timestamp of the last time the datadir was opened.
+    pub last_used: u64,
+}
+
+impl ChainManifest {
+    /// Returns the path of the manifest file.
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(MANIFEST_FILE))
+    }
+
+    /// Loads the manifest, or returns an empty one if it doesn't exist.
+    pub fn load() -> eyre::Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the manifest from the given file, or returns an empty one if it doesn't exist.
+    pub fn load_from(path: &Path) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default())
+        }
+        Ok(toml::from_str(&reth_fs_util::read_to_string(path)?)?)
+    }
+
+    /// Saves the manifest.
+    pub fn save(&self) -> eyre::Result<()> {
+        let path = Self::path().ok_or_else(|| eyre::eyre!("no data directory available"))?;
+        self.save_to(&path)
+    }
+
+    /// Saves the manifest to the given file.
+    pub fn save_to(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::write(path, toml::to_string_pretty(self)?)?;
+        debug!(target: "reth::cli", ?path, "Saved chain manifest");
+        Ok(())
+    }
+
+    /// Records that the datadir was opened with the chain.
+    pub fn record(&mut self, chain: &DatadirChain, datadir: &Path) {
+        let path = datadir.canonicalize().unwrap_or_else(|_| datadir.to_path_buf());
+        let last_used = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match self.datadirs.iter_mut().find(|entry| entry.path == path) {
+            Some(entry) => {
+                entry.chain = chain.clone();
+                entry.last_used = last_used;
+            }
+            None => self.datadirs.push(ManifestEntry { path, chain: chain.clone(), last_used }),
+        }
+    }
+}
+
+/// The default value of `--chain`, which
+/// [`chain_value_parser`](crate::args::utils::chain_value_parser) resolves with
+/// [`default_chain`].
+///
+/// The manifest is only read once the argument is parsed, so that the filesystem isn't touched
+/// when the command line is merely built, e.g. for `--help`.
+pub const DEFAULT_CHAIN: &str = "default";
+
+/// Returns the chain selected with `reth chain switch`, or the first supported chain.
+pub fn default_chain() -> String {
+    ChainManifest::load()
+        .ok()
+        .and_then(|manifest| manifest.default_chain)
+        .filter(|chain| chain != DEFAULT_CHAIN)
+        .unwrap_or_else(|| crate::args::utils::SUPPORTED_CHAINS[0].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{DEV, MAINNET};
+
+    #[test]
+    fn rejects_datadir_of_other_chain() {
+        let datadir = tempfile::tempdir().unwrap();
+
+        // unrecorded datadirs are accepted
+        DatadirChain::ensure_datadir_matches(datadir.path(), &MAINNET).unwrap();
+
+        DatadirChain::new(&MAINNET).write(datadir.path()).unwrap();
+        DatadirChain::ensure_datadir_matches(datadir.path(), &MAINNET).unwrap();
+
+        let err = DatadirChain::ensure_datadir_matches(datadir.path(), &DEV).unwrap_err();
+        assert!(err.to_string().contains("--chain mainnet"), "{err}");
+    }
+
+    #[test]
+    fn manifest_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MANIFEST_FILE);
+        assert_eq!(ChainManifest::load_from(&path).unwrap(), ChainManifest::default());
+
+        let mut manifest =
+            ChainManifest { default_chain: Some("dev".to_string()), ..Default::default() };
+        manifest.record(&DatadirChain::new(&MAINNET), dir.path());
+        manifest.record(&DatadirChain::new(&DEV), dir.path());
+        assert_eq!(manifest.datadirs.len(), 1);
+        assert_eq!(manifest.datadirs[0].chain.chain(), DEV.chain);
+
+        manifest.save_to(&path).unwrap();
+        assert_eq!(ChainManifest::load_from(&path).unwrap(), manifest);
+    }
+}
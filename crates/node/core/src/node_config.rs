@@ -2,17 +2,18 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EvmArgs, HardforkOverrideArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, ShutdownArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     metrics::prometheus_exporter,
+    static_files_server,
     utils::get_single_header,
 };
 use metrics_exporter_prometheus::PrometheusHandle;
 use once_cell::sync::Lazy;
-use reth_chainspec::{ChainSpec, MAINNET};
-use reth_config::config::PruneConfig;
+use reth_chainspec::{ChainSpec, HardforkOverrideError, HardforkOverrides, MAINNET};
+use reth_config::{config::PruneConfig, Config};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_primitives::{
@@ -106,6 +107,9 @@ pub struct NodeConfig {
     /// The metrics will be served at the given interface and port.
     pub metrics: Option<SocketAddr>,
 
+    /// Serve complete static files over HTTP at the given interface and port.
+    pub static_files_http: Option<SocketAddr>,
+
     /// Add a new instance of a node.
     ///
     /// Configures the ports of the node to avoid conflicts with the defaults.
@@ -145,6 +149,18 @@ pub struct NodeConfig {
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All hardfork activation override arguments with --override prefix
+    pub hardforks: HardforkOverrideArgs,
+
+    /// All graceful shutdown related arguments with --shutdown prefix
+    pub shutdown: ShutdownArgs,
+
+    /// All EVM related arguments with --evm prefix
+    pub evm: EvmArgs,
+
+    /// All consensus engine related arguments with --engine prefix
+    pub engine: EngineArgs,
 }
 
 impl NodeConfig {
@@ -252,6 +268,41 @@ impl NodeConfig {
         self
     }
 
+    /// Set the hardfork override args for the node
+    pub const fn with_hardforks(mut self, hardforks: HardforkOverrideArgs) -> Self {
+        self.hardforks = hardforks;
+        self
+    }
+
+    /// Set the graceful shutdown args for the node
+    pub const fn with_shutdown(mut self, shutdown: ShutdownArgs) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Set the EVM args for the node
+    pub const fn with_evm(mut self, evm: EvmArgs) -> Self {
+        self.evm = evm;
+        self
+    }
+
+    /// Set the consensus engine args for the node
+    pub fn with_engine(mut self, engine: EngineArgs) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Applies the hardfork overrides of the `[hardforks]` table of the toml config and, on top
+    /// of them, the command line overrides to the chain spec.
+    pub fn apply_hardfork_overrides(
+        &mut self,
+        toml_config: &Config,
+    ) -> Result<(), HardforkOverrideError> {
+        self.chain =
+            chain_with_hardfork_overrides(&self.chain, toml_config, self.hardforks.overrides())?;
+        Ok(())
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig> {
         self.pruning.prune_config(&self.chain)
@@ -316,6 +367,20 @@ impl NodeConfig {
         Ok(())
     }
 
+    /// Serves complete static files over HTTP, if enabled.
+    pub async fn start_static_files_endpoint(
+        &self,
+        static_files_dir: PathBuf,
+        task_executor: TaskExecutor,
+    ) -> eyre::Result<()> {
+        if let Some(listen_addr) = self.static_files_http {
+            info!(target: "reth::cli", addr = %listen_addr, "Starting static files endpoint");
+            static_files_server::serve(listen_addr, static_files_dir, task_executor).await?;
+        }
+
+        Ok(())
+    }
+
     /// Fetches the head block from the database.
     ///
     /// If the database is empty, returns the genesis block.
@@ -420,12 +485,37 @@ impl NodeConfig {
     }
 }
 
+/// Returns the chain spec with the hardfork overrides of the `[hardforks]` table of the toml config
+/// and, on top of them, the given overrides applied.
+///
+/// Every command that loads the toml config has to apply its overrides, so that it uses the same
+/// fork schedule as the node.
+pub fn chain_with_hardfork_overrides(
+    chain: &Arc<ChainSpec>,
+    toml_config: &Config,
+    overrides: HardforkOverrides,
+) -> Result<Arc<ChainSpec>, HardforkOverrideError> {
+    let mut all = HardforkOverrides::from_named(
+        toml_config.hardforks.iter().map(|(fork, activation)| (fork.as_str(), *activation)),
+    )?;
+    all.extend(overrides);
+    if all.is_empty() {
+        return Ok(chain.clone())
+    }
+
+    let mut chain = (**chain).clone();
+    chain.apply_hardfork_overrides(&all)?;
+    info!(target: "reth::cli", overrides = ?all, "Applied hardfork overrides");
+    Ok(Arc::new(chain))
+}
+
 impl Default for NodeConfig {
     fn default() -> Self {
         Self {
             config: None,
             chain: MAINNET.clone(),
             metrics: None,
+            static_files_http: None,
             instance: 1,
             network: NetworkArgs::default(),
             rpc: RpcServerArgs::default(),
@@ -435,6 +525,10 @@ impl Default for NodeConfig {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            hardforks: HardforkOverrideArgs::default(),
+            shutdown: ShutdownArgs::default(),
+            evm: EvmArgs::default(),
+            engine: EngineArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
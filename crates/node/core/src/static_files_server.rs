@@ -0,0 +1,378 @@
+//! Serves finalized static files over HTTP.
+//!
+//! Lets new nodes of a fleet download static file segments from existing nodes, instead of syncing
+//! them over p2p. Only complete static files are served, so their contents never change and can
+//! be identified with strong `ETag`s.
+//!
+//! All segments are served, but `reth import --static-files.url` only imports the headers, since
+//! the transactions and receipts segments depend on database tables that static files don't
+//! contain, e.g. block body indices.
+//!
+//! - `GET /static-files` lists the served static files as JSON. The list can be filtered with the
+//!   `segment`, `from` and `to` query parameters, e.g. `/static-files?segment=headers&from=1000`.
+//! - `GET /static-files/<file>` downloads one of the data, offsets or configuration files of a
+//!   listed static file. `If-None-Match` is supported.
+
+use bytes::Bytes;
+use eyre::WrapErr;
+use futures::{future::FusedFuture, FutureExt};
+use http::{header, Method, Request, Response, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use reth_db::static_file::iter_static_files;
+use reth_primitives::{
+    hex, keccak256,
+    static_file::{find_fixed_range, SegmentRangeInclusive},
+    StaticFileSegment,
+};
+use reth_tasks::TaskExecutor;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::Infallible,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncRead, ReadBuf};
+use tracing::{debug, error};
+
+/// Path under which the static files are served.
+pub const STATIC_FILES_PATH: &str = "/static-files";
+
+/// Size of the chunks a static file is streamed in.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// A static file that can be downloaded from the static files server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticFileListing {
+    /// Segment of the static file.
+    pub segment: StaticFileSegment,
+    /// Block range of the static file.
+    pub block_range: SegmentRangeInclusive,
+    /// Transaction range of the static file, if it's transaction based.
+    pub tx_range: Option<SegmentRangeInclusive>,
+    /// Data, offsets and configuration files of the static file.
+    pub files: Vec<StaticFileEntry>,
+}
+
+/// A file of a [`StaticFileListing`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticFileEntry {
+    /// File name, relative to the static files directory.
+    pub name: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Strong `ETag` of the file.
+    pub etag: String,
+}
+
+/// Filter for [`list_static_files`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StaticFileFilter {
+    /// Only include static files of this segment.
+    pub segment: Option<StaticFileSegment>,
+    /// Only include static files that end at or after this block.
+    pub from: Option<u64>,
+    /// Only include static files that start at or before this block.
+    pub to: Option<u64>,
+}
+
+impl StaticFileFilter {
+    /// Parses the filter from the query string of a request.
+    fn from_query(query: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "segment" => {
+                    filter.segment = Some(
+                        StaticFileSegment::from_str(value)
+                            .map_err(|_| format!("invalid segment: {value}"))?,
+                    )
+                }
+                "from" => {
+                    filter.from =
+                        Some(value.parse().map_err(|_| format!("invalid block: {value}"))?)
+                }
+                "to" => {
+                    filter.to = Some(value.parse().map_err(|_| format!("invalid block: {value}"))?)
+                }
+                _ => {}
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, segment: StaticFileSegment, block_range: &SegmentRangeInclusive) -> bool {
+        self.segment.map_or(true, |s| s == segment) &&
+            self.from.map_or(true, |from| block_range.end() >= from) &&
+            self.to.map_or(true, |to| block_range.start() <= to)
+    }
+}
+
+/// Lists the complete static files in the given directory, sorted by segment and block range.
+pub fn list_static_files(
+    dir: &Path,
+    filter: StaticFileFilter,
+) -> eyre::Result<Vec<StaticFileListing>> {
+    let mut listings = Vec::new();
+    for (segment, ranges) in iter_static_files(dir)? {
+        for (block_range, tx_range) in ranges {
+            // The latest static file of a segment is still being appended to
+            let fixed_block_range = find_fixed_range(block_range.start());
+            if block_range.end() != fixed_block_range.end() ||
+                !filter.matches(segment, &block_range)
+            {
+                continue
+            }
+
+            let data_path = dir.join(segment.filename(&fixed_block_range));
+            let config_path = data_path.with_extension("conf");
+            let config = reth_fs_util::read(&config_path)?;
+
+            let files = [data_path.clone(), data_path.with_extension("off"), config_path]
+                .into_iter()
+                .map(|path| {
+                    let name = path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .ok_or_else(|| eyre::eyre!("invalid static file name: {path:?}"))?
+                        .to_string();
+                    let size = reth_fs_util::metadata(&path)?.len();
+                    let etag = static_file_etag(&config, &name, size);
+                    Ok(StaticFileEntry { name, size, etag })
+                })
+                .collect::<eyre::Result<_>>()?;
+
+            listings.push(StaticFileListing { segment, block_range, tx_range, files });
+        }
+    }
+    listings.sort_by_key(|listing| (listing.segment, listing.block_range.start()));
+    Ok(listings)
+}
+
+/// Returns the strong `ETag` of a static file.
+///
+/// The configuration of a static file changes whenever its data does, e.g. when it's compacted,
+/// so it's hashed together with the file name and size.
+pub fn static_file_etag(config: &[u8], name: &str, size: u64) -> String {
+    let mut buf = Vec::with_capacity(config.len() + name.len() + 8);
+    buf.extend_from_slice(config);
+    buf.extend_from_slice(name.as_bytes());
+    buf.extend_from_slice(&size.to_be_bytes());
+    format!("\"{}\"", hex::encode(keccak256(buf)))
+}
+
+/// Serves the static files in the given directory over HTTP.
+pub async fn serve(
+    listen_addr: SocketAddr,
+    static_files_dir: PathBuf,
+    task_executor: TaskExecutor,
+) -> eyre::Result<()> {
+    let listener =
+        tokio::net::TcpListener::bind(listen_addr).await.wrap_err("Could not bind to address")?;
+    let static_files_dir = Arc::new(static_files_dir);
+
+    task_executor.spawn_with_graceful_shutdown_signal(|signal| async move {
+        let mut shutdown = signal.ignore_guard().fuse();
+        loop {
+            let io = match listener.accept().await {
+                Ok((stream, _remote_addr)) => stream,
+                Err(err) => {
+                    error!(target: "reth::static_files_server", %err, "failed to accept connection");
+                    continue;
+                }
+            };
+
+            let static_files_dir = static_files_dir.clone();
+            let service = tower::service_fn(move |req: Request<_>| {
+                let static_files_dir = static_files_dir.clone();
+                async move {
+                    let response = handle_request(&static_files_dir, &req).await;
+                    Ok::<_, Infallible>(response)
+                }
+            });
+
+            // Connections are served one at a time, downloads are expected to come from a few
+            // nodes of the same fleet.
+            if let Err(error) =
+                jsonrpsee::server::serve_with_graceful_shutdown(io, service, &mut shutdown).await
+            {
+                debug!(target: "reth::static_files_server", %error, "failed to serve request")
+            }
+
+            if shutdown.is_terminated() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+async fn handle_request<B>(static_files_dir: &Path, req: &Request<B>) -> Response<StaticFileBody> {
+    if req.method() != Method::GET {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed")
+    }
+
+    let path = req.uri().path();
+    if path == STATIC_FILES_PATH {
+        let filter = match StaticFileFilter::from_query(req.uri().query().unwrap_or_default()) {
+            Ok(filter) => filter,
+            Err(err) => return error_response(StatusCode::BAD_REQUEST, &err),
+        };
+        return match list_static_files(static_files_dir, filter) {
+            Ok(listings) => Response::builder()
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(StaticFileBody::full(serde_json::to_vec(&listings).expect("serializable")))
+                .expect("valid response"),
+            Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+        }
+    }
+
+    let Some(name) = path.strip_prefix(STATIC_FILES_PATH).and_then(|p| p.strip_prefix('/')) else {
+        return error_response(StatusCode::NOT_FOUND, "not found")
+    };
+
+    // Only files of listed static files are served, which also rules out path traversal.
+    let entry = match list_static_files(static_files_dir, StaticFileFilter::default()) {
+        Ok(listings) => {
+            listings.into_iter().flat_map(|listing| listing.files).find(|entry| entry.name == name)
+        }
+        Err(err) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    };
+    let Some(entry) = entry else { return error_response(StatusCode::NOT_FOUND, "not found") };
+
+    if req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|etag| etag.trim() == entry.etag))
+    {
+        return Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, &entry.etag)
+            .body(StaticFileBody::empty())
+            .expect("valid response")
+    }
+
+    match tokio::fs::File::open(static_files_dir.join(&entry.name)).await {
+        Ok(file) => Response::builder()
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header(header::CONTENT_LENGTH, entry.size)
+            .header(header::ETAG, &entry.etag)
+            .body(StaticFileBody::file(file, entry.size))
+            .expect("valid response"),
+        Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<StaticFileBody> {
+    Response::builder()
+        .status(status)
+        .body(StaticFileBody::full(message.as_bytes().to_vec()))
+        .expect("valid response")
+}
+
+/// Body of a static files server response, which streams files in chunks.
+#[derive(Debug)]
+pub struct StaticFileBody(BodyKind);
+
+#[derive(Debug)]
+enum BodyKind {
+    Full(Option<Bytes>),
+    File { file: tokio::fs::File, remaining: u64, buf: Vec<u8> },
+}
+
+impl StaticFileBody {
+    fn empty() -> Self {
+        Self(BodyKind::Full(None))
+    }
+
+    fn full(bytes: Vec<u8>) -> Self {
+        Self(BodyKind::Full(Some(bytes.into())))
+    }
+
+    fn file(file: tokio::fs::File, size: u64) -> Self {
+        Self(BodyKind::File { file, remaining: size, buf: Vec::new() })
+    }
+}
+
+impl Body for StaticFileBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.get_mut().0 {
+            BodyKind::Full(bytes) => Poll::Ready(bytes.take().map(|bytes| Ok(Frame::data(bytes)))),
+            BodyKind::File { file, remaining, buf } => {
+                if *remaining == 0 {
+                    return Poll::Ready(None)
+                }
+
+                buf.resize((*remaining).min(CHUNK_SIZE as u64) as usize, 0);
+                let mut read_buf = ReadBuf::new(buf);
+                ready!(Pin::new(file).poll_read(cx, &mut read_buf))?;
+                let read = read_buf.filled().len();
+                if read == 0 {
+                    return Poll::Ready(Some(Err(io::ErrorKind::UnexpectedEof.into())))
+                }
+
+                *remaining -= read as u64;
+                Poll::Ready(Some(Ok(Frame::data(Bytes::copy_from_slice(&buf[..read])))))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.0 {
+            BodyKind::Full(bytes) => bytes.is_none(),
+            BodyKind::File { remaining, .. } => *remaining == 0,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.0 {
+            BodyKind::Full(bytes) => {
+                SizeHint::with_exact(bytes.as_ref().map_or(0, |bytes| bytes.len() as u64))
+            }
+            BodyKind::File { remaining, .. } => SizeHint::with_exact(*remaining),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filter() {
+        let filter = StaticFileFilter::from_query("segment=headers&from=10&to=20").unwrap();
+        assert_eq!(
+            filter,
+            StaticFileFilter {
+                segment: Some(StaticFileSegment::Headers),
+                from: Some(10),
+                to: Some(20)
+            }
+        );
+        assert!(filter.matches(StaticFileSegment::Headers, &SegmentRangeInclusive::new(0, 10)));
+        assert!(!filter.matches(StaticFileSegment::Headers, &SegmentRangeInclusive::new(21, 30)));
+        assert!(!filter.matches(StaticFileSegment::Receipts, &SegmentRangeInclusive::new(0, 10)));
+        assert!(StaticFileFilter::from_query("segment=bodies").is_err());
+    }
+
+    #[test]
+    fn etag_changes_with_config() {
+        let etag = static_file_etag(b"config", "static_file_headers_0_499999", 10);
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, static_file_etag(b"config", "static_file_headers_0_499999", 10));
+        assert_ne!(etag, static_file_etag(b"compacted", "static_file_headers_0_499999", 10));
+    }
+}
@@ -1,7 +1,7 @@
 //! Utility functions for node startup and shutdown, for example path parsing and retrieving single
 //! blocks from the network.
 
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use reth_chainspec::ChainSpec;
 use reth_consensus_common::validation::validate_block_pre_execution;
 use reth_network_p2p::{
@@ -35,6 +35,22 @@ pub fn get_or_create_jwt_secret_from_path(path: &Path) -> Result<JwtSecret, JwtE
     }
 }
 
+/// Reads the JWT secrets from all files that match any of the glob patterns.
+pub fn read_jwt_secrets_from_globs(patterns: &[String]) -> Result<Vec<JwtSecret>> {
+    let mut secrets = Vec::new();
+    for pattern in patterns {
+        let paths =
+            glob::glob(pattern).wrap_err_with(|| format!("invalid glob pattern {pattern:?}"))?;
+        for path in paths {
+            let path = path?;
+            let secret = JwtSecret::from_file(&path)
+                .wrap_err_with(|| format!("failed to read JWT secret from {path:?}"))?;
+            secrets.push(secret);
+        }
+    }
+    Ok(secrets)
+}
+
 /// Get a single header from network
 pub async fn get_single_header<Client>(
     client: Client,
@@ -43,7 +59,8 @@ pub async fn get_single_header<Client>(
 where
     Client: HeadersClient,
 {
-    let request = HeadersRequest { direction: HeadersDirection::Rising, limit: 1, start: id };
+    let request =
+        HeadersRequest { direction: HeadersDirection::Rising, limit: 1, skip: 0, start: id };
 
     let (peer_id, response) =
         client.get_headers_with_priority(request, Priority::High).await?.split();
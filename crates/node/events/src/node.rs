@@ -1,8 +1,9 @@
 //! Support for handling events emitted by node components.
 
-use crate::cl::ConsensusLayerHealthEvent;
+use crate::{cl::ConsensusLayerHealthEvent, sync::SyncStats};
 use alloy_rpc_types_engine::ForkchoiceState;
 use futures::Stream;
+use human_bytes::human_bytes;
 use reth_beacon_consensus::{
     BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress, ForkchoiceStatus,
 };
@@ -50,6 +51,8 @@ struct NodeState<DB> {
     safe_block_hash: Option<B256>,
     /// Hash of finalized block last set by fork choice update
     finalized_block_hash: Option<B256>,
+    /// Statistics about the progress of the pipeline, fed by its events.
+    sync_stats: SyncStats,
 }
 
 impl<DB> NodeState<DB> {
@@ -57,6 +60,7 @@ impl<DB> NodeState<DB> {
         db: DB,
         network: Option<NetworkHandle>,
         latest_block: Option<BlockNumber>,
+        sync_stats: SyncStats,
     ) -> Self {
         Self {
             db,
@@ -67,6 +71,7 @@ impl<DB> NodeState<DB> {
             head_block_hash: None,
             safe_block_hash: None,
             finalized_block_hash: None,
+            sync_stats,
         }
     }
 
@@ -76,6 +81,8 @@ impl<DB> NodeState<DB> {
 
     /// Processes an event emitted by the pipeline
     fn handle_pipeline_event(&mut self, event: PipelineEvent) {
+        self.sync_stats.on_pipeline_event(&event);
+
         match event {
             PipelineEvent::Prepare { pipeline_stages_progress, stage_id, checkpoint, target } => {
                 let checkpoint = checkpoint.unwrap_or_default();
@@ -352,6 +359,36 @@ impl<DB: DatabaseMetadata> NodeState<DB> {
     }
 }
 
+impl<DB> NodeState<DB> {
+    /// Logs the throughput and the estimates of the current stage and the disk usage.
+    fn log_sync_progress(&self) {
+        // walking the data directory blocks, the log uses the size measured by the previous walk
+        let sync_stats = self.sync_stats.clone();
+        tokio::task::spawn_blocking(move || sync_stats.record_disk_usage());
+
+        let progress = self.sync_stats.progress();
+        let Some(stage) = progress
+            .current_stage
+            .as_ref()
+            .and_then(|current| progress.stages.iter().find(|stage| &stage.stage == current))
+        else {
+            return
+        };
+
+        info!(
+            target: "reth::cli",
+            stage = %stage.stage,
+            throughput = %OptionalField(stage.throughput.map(|rate| format!("{rate:.0}/s"))),
+            remaining = %OptionalField(stage.remaining),
+            eta = %OptionalField(stage.eta_seconds.map(|eta| humantime::format_duration(Duration::from_secs(eta)))),
+            disk_usage = %OptionalField(progress.disk_usage.map(|bytes| human_bytes(bytes as f64))),
+            disk_growth = %OptionalField(progress.disk_growth_per_second.map(|rate| format!("{}/h", human_bytes(rate * 3600.0)))),
+            projected_disk_usage = %OptionalField(progress.projected_disk_usage.map(|bytes| human_bytes(bytes as f64))),
+            "Sync progress"
+        );
+    }
+}
+
 /// Helper type for formatting of optional fields:
 /// - If [Some(x)], then `x` is written
 /// - If [None], then `None` is written
@@ -446,7 +483,23 @@ pub async fn handle_events<E, DB>(
     E: Stream<Item = NodeEvent> + Unpin,
     DB: DatabaseMetadata + Database + 'static,
 {
-    let state = NodeState::new(db, network, latest_block_number);
+    handle_events_with_sync_stats(network, latest_block_number, events, db, SyncStats::default())
+        .await
+}
+
+/// Same as [`handle_events`], but also feeds the given [`SyncStats`] and periodically logs the
+/// sync progress they estimate while the pipeline is running.
+pub async fn handle_events_with_sync_stats<E, DB>(
+    network: Option<NetworkHandle>,
+    latest_block_number: Option<BlockNumber>,
+    events: E,
+    db: DB,
+    sync_stats: SyncStats,
+) where
+    E: Stream<Item = NodeEvent> + Unpin,
+    DB: DatabaseMetadata + Database + 'static,
+{
+    let state = NodeState::new(db, network, latest_block_number, sync_stats);
 
     let start = tokio::time::Instant::now() + Duration::from_secs(3);
     let mut info_interval = tokio::time::interval_at(start, INFO_MESSAGE_INTERVAL);
@@ -536,6 +589,8 @@ where
                         )
                     }
                 }
+
+                this.state.log_sync_progress();
             } else if let Some(latest_block) = this.state.latest_block {
                 let now =
                     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
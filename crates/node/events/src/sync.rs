@@ -0,0 +1,307 @@
+//! Statistics about the progress of the pipeline sync.
+
+use reth_primitives::BlockNumber;
+use reth_rpc_types::{StageProgress, SyncProgress};
+use reth_stages::{ExecOutput, PipelineEvent, StageCheckpoint, StageId};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+use tracing::debug;
+
+/// The number of recent samples rates are calculated from.
+const RATE_WINDOW: usize = 16;
+
+/// Collects the progress of the pipeline stages from the checkpoints they commit, and the growth
+/// of the data directory.
+///
+/// Cloned handles share the same statistics.
+#[derive(Debug, Clone, Default)]
+pub struct SyncStats {
+    inner: Arc<Mutex<SyncStatsInner>>,
+    /// The data directory whose size is tracked, if any.
+    data_dir: Option<PathBuf>,
+    /// Whether the size of the data directory is currently being measured.
+    measuring_disk_usage: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct SyncStatsInner {
+    /// The stage that is currently executed.
+    current_stage: Option<StageId>,
+    /// The stages that ran since the node started, in the order of the pipeline.
+    stages: Vec<StageStats>,
+    /// Recent sizes of the data directory.
+    disk_usage: RateSamples,
+}
+
+/// The progress of a single stage.
+#[derive(Debug)]
+struct StageStats {
+    stage_id: StageId,
+    checkpoint: StageCheckpoint,
+    target: Option<BlockNumber>,
+    done: bool,
+    /// Recent processed entities, or block numbers if the stage doesn't report entities.
+    progress: RateSamples,
+}
+
+impl StageStats {
+    fn new(stage_id: StageId) -> Self {
+        Self {
+            stage_id,
+            checkpoint: StageCheckpoint::default(),
+            target: None,
+            done: false,
+            progress: RateSamples::default(),
+        }
+    }
+
+    /// Returns the processed and the remaining work of the stage, in entities if the stage
+    /// reports them and in blocks otherwise.
+    fn work(&self) -> (u64, Option<u64>) {
+        match self.checkpoint.entities() {
+            Some(entities) => {
+                (entities.processed, Some(entities.total.saturating_sub(entities.processed)))
+            }
+            None => (
+                self.checkpoint.block_number,
+                self.target.map(|target| target.saturating_sub(self.checkpoint.block_number)),
+            ),
+        }
+    }
+
+    /// Returns the estimated number of seconds until the stage reaches its target.
+    fn eta_seconds(&self) -> Option<u64> {
+        if self.done {
+            return Some(0)
+        }
+        let remaining = self.work().1?;
+        let throughput = self.progress.per_second().filter(|rate| *rate > 0.0)?;
+        Some((remaining as f64 / throughput) as u64)
+    }
+
+    fn progress(&self) -> StageProgress {
+        let entities = self.checkpoint.entities();
+        StageProgress {
+            stage: self.stage_id.to_string(),
+            checkpoint: self.checkpoint.block_number,
+            target: self.target,
+            done: self.done,
+            processed: entities.map(|entities| entities.processed),
+            total: entities.map(|entities| entities.total),
+            throughput: self.progress.per_second(),
+            remaining: self.work().1,
+            eta_seconds: self.eta_seconds(),
+        }
+    }
+}
+
+/// Recent samples of a growing value, to calculate its rate of change.
+#[derive(Debug, Default)]
+struct RateSamples(VecDeque<(Instant, u64)>);
+
+impl RateSamples {
+    fn push(&mut self, now: Instant, value: u64) {
+        self.0.push_back((now, value));
+        if self.0.len() > RATE_WINDOW {
+            self.0.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn last(&self) -> Option<u64> {
+        self.0.back().map(|(_, value)| *value)
+    }
+
+    /// Returns the change per second over the samples, which is negative if the value shrank.
+    fn per_second(&self) -> Option<f64> {
+        let (first_time, first) = self.0.front()?;
+        let (last_time, last) = self.0.back()?;
+        let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+        (elapsed > 0.0).then(|| (*last as f64 - *first as f64) / elapsed)
+    }
+}
+
+impl SyncStats {
+    /// Creates new statistics that also track the size of the given data directory.
+    pub fn with_data_dir(data_dir: impl Into<PathBuf>) -> Self {
+        Self { data_dir: Some(data_dir.into()), ..Default::default() }
+    }
+
+    /// Records the progress reported by the pipeline event.
+    pub fn on_pipeline_event(&self, event: &PipelineEvent) {
+        self.on_pipeline_event_at(event, Instant::now())
+    }
+
+    fn on_pipeline_event_at(&self, event: &PipelineEvent, now: Instant) {
+        let mut inner = self.inner.lock().expect("not poisoned");
+        match event {
+            PipelineEvent::Run { stage_id, checkpoint, target, .. } => {
+                inner.current_stage = Some(*stage_id);
+                let stage = inner.stage_mut(*stage_id);
+                stage.checkpoint = checkpoint.unwrap_or_default();
+                stage.target = *target;
+                stage.done = false;
+            }
+            PipelineEvent::Ran { stage_id, result: ExecOutput { checkpoint, done }, .. } => {
+                let stage = inner.stage_mut(*stage_id);
+                stage.checkpoint = *checkpoint;
+                stage.done = *done;
+                let (processed, _) = stage.work();
+                // the entities restart from zero on every run of some stages
+                if stage.progress.last().is_some_and(|last| processed < last) {
+                    stage.progress.clear();
+                }
+                stage.progress.push(now, processed);
+                if *done {
+                    inner.current_stage = None;
+                }
+            }
+            PipelineEvent::Unwind { stage_id, .. } => {
+                inner.current_stage = Some(*stage_id);
+                inner.stage_mut(*stage_id).progress.clear();
+            }
+            PipelineEvent::Unwound { stage_id, result } => {
+                inner.current_stage = None;
+                inner.stage_mut(*stage_id).checkpoint = result.checkpoint;
+            }
+            _ => {}
+        }
+    }
+
+    /// Measures the size of the data directory, if one is tracked.
+    ///
+    /// This walks the data directory, so it should only be called periodically and on a blocking
+    /// task. Does nothing if the data directory is already being measured.
+    pub fn record_disk_usage(&self) {
+        let Some(data_dir) = &self.data_dir else { return };
+        if self.measuring_disk_usage.swap(true, Ordering::Relaxed) {
+            return
+        }
+        match dir_size(data_dir) {
+            Ok(size) => self.record_disk_usage_at(size, Instant::now()),
+            Err(err) => {
+                debug!(target: "reth::cli", %err, ?data_dir, "Failed to measure disk usage")
+            }
+        }
+        self.measuring_disk_usage.store(false, Ordering::Relaxed);
+    }
+
+    fn record_disk_usage_at(&self, size: u64, now: Instant) {
+        self.inner.lock().expect("not poisoned").disk_usage.push(now, size)
+    }
+
+    /// Returns the progress of the sync.
+    pub fn progress(&self) -> SyncProgress {
+        let inner = self.inner.lock().expect("not poisoned");
+        let current_stage = inner
+            .current_stage
+            .and_then(|stage_id| inner.stages.iter().find(|stage| stage.stage_id == stage_id));
+
+        let disk_usage = inner.disk_usage.last();
+        let disk_growth_per_second = inner.disk_usage.per_second();
+        let projected_disk_usage = disk_usage
+            .zip(disk_growth_per_second)
+            .zip(current_stage.and_then(StageStats::eta_seconds))
+            .map(|((usage, growth), eta)| (usage as f64 + growth * eta as f64).max(0.0) as u64);
+
+        SyncProgress {
+            current_stage: current_stage.map(|stage| stage.stage_id.to_string()),
+            stages: inner.stages.iter().map(StageStats::progress).collect(),
+            disk_usage,
+            disk_growth_per_second,
+            projected_disk_usage,
+        }
+    }
+}
+
+impl SyncStatsInner {
+    fn stage_mut(&mut self, stage_id: StageId) -> &mut StageStats {
+        let index = match self.stages.iter().position(|stage| stage.stage_id == stage_id) {
+            Some(index) => index,
+            None => {
+                self.stages.push(StageStats::new(stage_id));
+                self.stages.len() - 1
+            }
+        };
+        &mut self.stages[index]
+    }
+}
+
+/// Returns the total size of the files in the directory and its subdirectories.
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() { dir_size(&entry.path())? } else { metadata.len() };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages::PipelineStagesProgress;
+    use std::time::Duration;
+
+    fn run(stage_id: StageId, block: BlockNumber, target: BlockNumber) -> PipelineEvent {
+        PipelineEvent::Run {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 1 },
+            stage_id,
+            checkpoint: Some(StageCheckpoint::new(block)),
+            target: Some(target),
+        }
+    }
+
+    fn ran(stage_id: StageId, block: BlockNumber, done: bool) -> PipelineEvent {
+        PipelineEvent::Ran {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 1 },
+            stage_id,
+            result: ExecOutput { checkpoint: StageCheckpoint::new(block), done },
+        }
+    }
+
+    #[test]
+    fn estimates_from_recent_checkpoints() {
+        let stats = SyncStats::default();
+        let start = Instant::now();
+
+        stats.on_pipeline_event_at(&run(StageId::Bodies, 0, 1_000), start);
+        stats.on_pipeline_event_at(&ran(StageId::Bodies, 100, false), start);
+        stats.record_disk_usage_at(1_000, start);
+        stats.on_pipeline_event_at(
+            &ran(StageId::Bodies, 300, false),
+            start + Duration::from_secs(10),
+        );
+        stats.record_disk_usage_at(3_000, start + Duration::from_secs(10));
+
+        let progress = stats.progress();
+        assert_eq!(progress.current_stage.as_deref(), Some("Bodies"));
+        let bodies = &progress.stages[0];
+        assert_eq!(bodies.checkpoint, 300);
+        assert_eq!(bodies.throughput, Some(20.0));
+        assert_eq!(bodies.remaining, Some(700));
+        assert_eq!(bodies.eta_seconds, Some(35));
+        assert_eq!(progress.disk_growth_per_second, Some(200.0));
+        assert_eq!(progress.projected_disk_usage, Some(3_000 + 200 * 35));
+
+        stats.on_pipeline_event_at(
+            &ran(StageId::Bodies, 1_000, true),
+            start + Duration::from_secs(20),
+        );
+        let progress = stats.progress();
+        assert_eq!(progress.current_stage, None);
+        assert!(progress.stages[0].done);
+        assert_eq!(progress.stages[0].eta_seconds, Some(0));
+    }
+}
@@ -13,3 +13,6 @@
 /// Optimism CLI commands.
 pub mod commands;
 pub use commands::{import::ImportOpCommand, import_receipts::ImportReceiptsOpCommand};
+
+// Only used to enable the `optimism` feature of the primitives.
+use reth_primitives as _;
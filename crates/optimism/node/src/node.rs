@@ -134,10 +134,17 @@ where
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let pool_config = ctx.pool_config();
+        if let Some(lists) = ctx.config().txpool.policy_lists()? {
+            pool_config.transaction_policy.set_lists(lists);
+        }
 
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
+            .with_transaction_policy(pool_config.transaction_policy.clone())
+            .with_blob_proof_computation(pool_config.blob_proof_computation.clone())
+            .with_tip_floor(pool_config.tip_floor.clone())
             .with_additional_tasks(1)
             .build_with_tasks(
                 ctx.provider().clone(),
@@ -150,7 +157,7 @@ where
             validator,
             CoinbaseTipOrdering::default(),
             blob_store,
-            ctx.pool_config(),
+            pool_config,
         );
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
@@ -161,7 +168,7 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                ctx.config().txpool.local_transactions_backup_config(transactions_path);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -117,8 +117,9 @@ impl PayloadBuilderAttributes for OptimismPayloadBuilderAttributes {
 
         // if the parent block did not have excess blob gas (i.e. it was pre-cancun), but it is
         // cancun now, we need to set the excess blob gas to the default value
+        let blob_params = chain_spec.blob_params_at_timestamp(self.timestamp());
         let blob_excess_gas_and_price = parent
-            .next_block_excess_blob_gas()
+            .next_block_excess_blob_gas_with_params(blob_params)
             .or_else(|| {
                 if spec_id.is_enabled_in(SpecId::CANCUN) {
                     // default excess blob gas is zero
@@ -127,7 +128,10 @@ impl PayloadBuilderAttributes for OptimismPayloadBuilderAttributes {
                     None
                 }
             })
-            .map(BlobExcessGasAndPrice::new);
+            .map(|excess_blob_gas| BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.blob_fee(excess_blob_gas),
+            });
 
         let block_env = BlockEnv {
             number: U256::from(parent.number + 1),
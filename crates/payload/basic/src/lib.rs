@@ -44,10 +44,13 @@ use tokio::{
     sync::{oneshot, Semaphore},
     time::{Interval, Sleep},
 };
-use tracing::{debug, trace, warn};
+use tracing::{debug, debug_span, field, trace, warn};
 
 mod metrics;
 
+mod prefetch;
+pub use prefetch::{PrefetchedState, StatePrefetcher, StatePrefetcherConfig};
+
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
 #[derive(Debug)]
 pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
@@ -69,6 +72,8 @@ pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
     builder: Builder,
     /// Stored `cached_reads` for new payload jobs.
     pre_cached: Option<PrecachedState>,
+    /// The state read by the [`StatePrefetcher`], if enabled.
+    prefetched: Option<PrefetchedState>,
 }
 
 // === impl BasicPayloadJobGenerator ===
@@ -93,9 +98,16 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
             chain_spec,
             builder,
             pre_cached: None,
+            prefetched: None,
         }
     }
 
+    /// Seeds new payload jobs with the state read by a [`StatePrefetcher`].
+    pub fn with_prefetched_state(mut self, prefetched: PrefetchedState) -> Self {
+        self.prefetched = Some(prefetched);
+        self
+    }
+
     /// Returns the maximum duration a job should be allowed to run.
     ///
     /// This adheres to the following specification:
@@ -126,9 +138,18 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
     }
 
     /// Returns the pre-cached reads for the given parent block if it matches the cached state's
-    /// block.
+    /// block, extended with the prefetched state of the block.
     fn maybe_pre_cached(&self, parent: B256) -> Option<CachedReads> {
-        self.pre_cached.as_ref().filter(|pc| pc.block == parent).map(|pc| pc.cached.clone())
+        let pre_cached =
+            self.pre_cached.as_ref().filter(|pc| pc.block == parent).map(|pc| pc.cached.clone());
+        let prefetched = self.prefetched.as_ref().and_then(|prefetched| prefetched.get(parent));
+        match (pre_cached, prefetched) {
+            (Some(mut pre_cached), Some(prefetched)) => {
+                pre_cached.extend(prefetched);
+                Some(pre_cached)
+            }
+            (pre_cached, prefetched) => pre_cached.or(prefetched),
+        }
     }
 }
 
@@ -373,6 +394,13 @@ where
         self.metrics.inc_initiated_payload_builds();
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
+        let span = debug_span!(
+            target: "payload_builder",
+            "build_payload",
+            block_number = payload_config.parent_block.number + 1,
+            gas_used = field::Empty,
+            tx_count = field::Empty,
+        );
         self.executor.spawn_blocking(Box::pin(async move {
             // acquire the permit for executing the task
             let _permit = guard.acquire().await;
@@ -384,7 +412,11 @@ where
                 cancel,
                 best_payload,
             };
-            let result = builder.try_build(args);
+            let result = span.in_scope(|| builder.try_build(args));
+            if let Ok(BuildOutcome::Better { payload, .. }) = &result {
+                span.record("gas_used", payload.block().gas_used);
+                span.record("tx_count", payload.block().body.len());
+            }
             let _ = tx.send(result);
         }));
 
@@ -0,0 +1,241 @@
+//! Speculative execution of pool transactions to warm the state for payload building.
+
+use crate::PrecachedState;
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use reth_chainspec::ChainSpec;
+use reth_evm::ConfigureEvm;
+use reth_payload_builder::database::CachedReads;
+use reth_primitives::{constants::SLOT_DURATION, Header, SealedHeader, TxHash, B256};
+use reth_provider::{
+    BlockReaderIdExt, CanonStateNotificationStream, ProviderError, StateProviderFactory,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use revm::{
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState},
+    DatabaseCommit, State,
+};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+use tracing::{debug, trace};
+
+/// The configuration of the [`StatePrefetcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatePrefetcherConfig {
+    /// The maximum number of the best pool transactions that are executed on top of each head
+    /// block.
+    pub max_transactions: usize,
+    /// The interval at which the best pool transactions are executed.
+    pub interval: Duration,
+}
+
+impl Default for StatePrefetcherConfig {
+    fn default() -> Self {
+        Self { max_transactions: 200, interval: Duration::from_secs(1) }
+    }
+}
+
+/// The state that the [`StatePrefetcher`] read for the current head block.
+///
+/// This is cheap to clone and shared between the prefetcher and the
+/// [`BasicPayloadJobGenerator`](crate::BasicPayloadJobGenerator), which seeds new payload jobs
+/// with the prefetched state of their parent block.
+#[derive(Debug, Clone, Default)]
+pub struct PrefetchedState(Arc<Mutex<Option<PrecachedState>>>);
+
+impl PrefetchedState {
+    /// Returns the state that was prefetched for the given block.
+    pub fn get(&self, block: B256) -> Option<CachedReads> {
+        self.0
+            .lock()
+            .as_ref()
+            .filter(|state| state.block == block)
+            .map(|state| state.cached.clone())
+    }
+
+    fn set(&self, state: PrecachedState) {
+        *self.0.lock() = Some(state);
+    }
+}
+
+/// A task that speculatively executes the best pool transactions on top of the current head block
+/// during a slot.
+///
+/// The execution reads the accounts, storage and contracts that the transactions of the next
+/// block likely access, which warms the OS page cache of the database and the [`CachedReads`] of
+/// the next payload job before `engine_forkchoiceUpdated` or `engine_getPayload` arrive. The
+/// results of the executions are discarded.
+#[derive(Debug)]
+pub struct StatePrefetcher<Client, Pool, EvmConfig> {
+    /// The client to read the state of the head block from.
+    client: Client,
+    /// The transaction pool to pull the best transactions from.
+    pool: Pool,
+    /// How to configure the EVM.
+    evm_config: EvmConfig,
+    /// The chain spec.
+    chain_spec: Arc<ChainSpec>,
+    /// The configuration of the prefetcher.
+    config: StatePrefetcherConfig,
+    /// The prefetched state, shared with the payload job generator.
+    prefetched: PrefetchedState,
+}
+
+/// The progress of the prefetcher for a head block.
+#[derive(Debug)]
+struct HeadPrefetch {
+    /// The head block.
+    header: SealedHeader,
+    /// The state read by the executed transactions.
+    cached: CachedReads,
+    /// The hashes of the executed transactions.
+    executed: HashSet<TxHash>,
+}
+
+impl HeadPrefetch {
+    fn new(header: SealedHeader) -> Self {
+        Self { header, cached: CachedReads::default(), executed: HashSet::new() }
+    }
+}
+
+impl<Client, Pool, EvmConfig> StatePrefetcher<Client, Pool, EvmConfig>
+where
+    Client: StateProviderFactory + BlockReaderIdExt,
+    Pool: TransactionPool,
+    EvmConfig: ConfigureEvm,
+{
+    /// Creates a new prefetcher.
+    pub fn new(
+        client: Client,
+        pool: Pool,
+        evm_config: EvmConfig,
+        chain_spec: Arc<ChainSpec>,
+        config: StatePrefetcherConfig,
+    ) -> Self {
+        Self { client, pool, evm_config, chain_spec, config, prefetched: Default::default() }
+    }
+
+    /// Returns the handle to the prefetched state.
+    pub fn prefetched_state(&self) -> PrefetchedState {
+        self.prefetched.clone()
+    }
+
+    /// Runs the prefetcher until the stream of canonical state notifications ends.
+    ///
+    /// The executions block, so this should be spawned as a blocking task.
+    pub async fn run(self, mut canon_state: CanonStateNotificationStream) {
+        let mut interval = tokio::time::interval(self.config.interval);
+        let mut head = match self.client.latest_header() {
+            Ok(header) => header.map(HeadPrefetch::new),
+            Err(err) => {
+                debug!(target: "payload_builder::prefetch", %err, "Failed to read the latest header");
+                None
+            }
+        };
+
+        loop {
+            tokio::select! {
+                notification = canon_state.next() => {
+                    let Some(notification) = notification else { break };
+                    head = Some(HeadPrefetch::new(notification.tip().header.clone()));
+                }
+                _ = interval.tick() => {
+                    let Some(head) = head.as_mut() else { continue };
+                    match self.prefetch(head) {
+                        Ok(0) => {}
+                        Ok(executed) => {
+                            trace!(target: "payload_builder::prefetch", block = ?head.header.hash(), executed, "Prefetched the state of pool transactions");
+                            self.prefetched.set(PrecachedState {
+                                block: head.header.hash(),
+                                cached: head.cached.clone(),
+                            });
+                        }
+                        Err(err) => {
+                            debug!(target: "payload_builder::prefetch", %err, "Failed to prefetch the state of pool transactions");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Executes the best pool transactions that were not executed yet on top of the head block,
+    /// and returns the number of executed transactions.
+    fn prefetch(&self, head: &mut HeadPrefetch) -> Result<usize, ProviderError> {
+        let remaining = self.config.max_transactions.saturating_sub(head.executed.len());
+        if remaining == 0 {
+            return Ok(0)
+        }
+
+        let (cfg, block_env) = self.next_block_env(&head.header)?;
+        let attributes = BestTransactionsAttributes::new(
+            block_env.basefee.to::<u64>(),
+            block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
+        );
+        let transactions = self
+            .pool
+            .best_transactions_with_attributes(attributes)
+            .filter(|tx| !head.executed.contains(tx.hash()))
+            .take(remaining)
+            .collect::<Vec<_>>();
+        if transactions.is_empty() {
+            return Ok(0)
+        }
+
+        let state = self.client.state_by_block_hash(head.header.hash())?;
+        let mut db = State::builder()
+            .with_database_ref(head.cached.as_db(StateProviderDatabase::new(state)))
+            .build();
+        for pool_tx in &transactions {
+            head.executed.insert(*pool_tx.hash());
+            let tx = pool_tx.to_recovered_transaction();
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                self.evm_config.tx_env(&tx),
+            );
+            let mut evm = self.evm_config.evm_with_env(&mut db, env);
+
+            // the changes are committed so that dependent transactions see them, failed
+            // transactions still loaded the state they accessed
+            let Ok(ResultAndState { state, .. }) = evm.transact() else { continue };
+            drop(evm);
+            db.commit(state);
+        }
+
+        Ok(transactions.len())
+    }
+
+    /// Returns the EVM environment of the block following the head block, assuming it's built in
+    /// the next slot.
+    fn next_block_env(
+        &self,
+        parent: &SealedHeader,
+    ) -> Result<(CfgEnvWithHandlerCfg, BlockEnv), ProviderError> {
+        let timestamp = parent.timestamp + SLOT_DURATION.as_secs();
+        let header = Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            timestamp,
+            gas_limit: parent.gas_limit,
+            base_fee_per_gas: parent
+                .next_block_base_fee(self.chain_spec.base_fee_params_at_timestamp(timestamp)),
+            excess_blob_gas: parent.next_block_excess_blob_gas_with_params(
+                self.chain_spec.blob_params_at_timestamp(timestamp),
+            ),
+            ..Default::default()
+        };
+        let total_difficulty = self.client.header_td(&parent.hash())?.unwrap_or_default();
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        EvmConfig::fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &self.chain_spec,
+            &header,
+            total_difficulty,
+        );
+        Ok((cfg, block_env))
+    }
+}
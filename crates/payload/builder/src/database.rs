@@ -59,6 +59,31 @@ impl CachedReads {
     ) {
         self.accounts.insert(address, CachedAccount { info: Some(info), storage });
     }
+
+    /// Extends the cache with the reads of another cache of the same state.
+    ///
+    /// Entries that are already cached are kept.
+    pub fn extend(&mut self, other: Self) {
+        for (address, account) in other.accounts {
+            match self.accounts.entry(address) {
+                Entry::Occupied(mut entry) => {
+                    let storage = &mut entry.get_mut().storage;
+                    for (slot, value) in account.storage {
+                        storage.entry(slot).or_insert(value);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(account);
+                }
+            }
+        }
+        for (code_hash, code) in other.contracts {
+            self.contracts.entry(code_hash).or_insert(code);
+        }
+        for (number, hash) in other.block_hashes {
+            self.block_hashes.entry(number).or_insert(hash);
+        }
+    }
 }
 
 /// A [Database] that caches reads inside [`CachedReads`].
@@ -0,0 +1,121 @@
+//! [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob parameters.
+
+use revm_primitives::{
+    fake_exponential, BLOB_GASPRICE_UPDATE_FRACTION, GAS_PER_BLOB, MAX_BLOB_NUMBER_PER_BLOCK,
+    MIN_BLOB_GASPRICE, TARGET_BLOB_NUMBER_PER_BLOCK,
+};
+use serde::{Deserialize, Serialize};
+
+/// The blob parameters of a fork: the target and maximum number of blobs per block, and how fast
+/// the blob base fee adapts to the blob gas used.
+///
+/// The field names of the serde representation match the `blobSchedule` entries of a genesis
+/// file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    #[serde(rename = "target")]
+    pub target_blobs_per_block: u64,
+    /// The maximum number of blobs per block.
+    #[serde(rename = "max")]
+    pub max_blobs_per_block: u64,
+    /// The denominator of the blob base fee update.
+    pub base_fee_update_fraction: u64,
+}
+
+impl BlobParams {
+    /// Returns the blob parameters introduced in Cancun.
+    pub const fn cancun() -> Self {
+        Self {
+            target_blobs_per_block: TARGET_BLOB_NUMBER_PER_BLOCK,
+            max_blobs_per_block: MAX_BLOB_NUMBER_PER_BLOCK,
+            base_fee_update_fraction: BLOB_GASPRICE_UPDATE_FRACTION,
+        }
+    }
+
+    /// Returns the maximum number of blobs of a single transaction.
+    ///
+    /// This is capped at the Cancun maximum, since the EVM rejects transactions with more blobs
+    /// regardless of the schedule.
+    pub const fn max_blobs_per_tx(&self) -> u64 {
+        if self.max_blobs_per_block < MAX_BLOB_NUMBER_PER_BLOCK {
+            self.max_blobs_per_block
+        } else {
+            MAX_BLOB_NUMBER_PER_BLOCK
+        }
+    }
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blobs_per_block * GAS_PER_BLOB
+    }
+
+    /// Returns the maximum blob gas per block.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blobs_per_block * GAS_PER_BLOB
+    }
+
+    /// Calculates the excess blob gas of a block from the excess blob gas and blob gas used of its
+    /// parent.
+    pub const fn next_block_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+    ) -> u64 {
+        (parent_excess_blob_gas + parent_blob_gas_used)
+            .saturating_sub(self.target_blob_gas_per_block())
+    }
+
+    /// Calculates the blob base fee from the excess blob gas of a block.
+    pub fn blob_fee(&self, excess_blob_gas: u64) -> u128 {
+        fake_exponential(MIN_BLOB_GASPRICE, excess_blob_gas, self.base_fee_update_fraction)
+    }
+}
+
+impl Default for BlobParams {
+    fn default() -> Self {
+        Self::cancun()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{calc_blob_gasprice, calc_excess_blob_gas};
+
+    #[test]
+    fn cancun_params_match_eip4844() {
+        let params = BlobParams::cancun();
+        for (excess_blob_gas, blob_gas_used) in
+            [(0, 0), (0, 6 * GAS_PER_BLOB), (10 * GAS_PER_BLOB, GAS_PER_BLOB), (1 << 30, 0)]
+        {
+            assert_eq!(
+                params.next_block_excess_blob_gas(excess_blob_gas, blob_gas_used),
+                calc_excess_blob_gas(excess_blob_gas, blob_gas_used)
+            );
+            assert_eq!(params.blob_fee(excess_blob_gas), calc_blob_gasprice(excess_blob_gas));
+        }
+    }
+
+    #[test]
+    fn custom_params() {
+        let params = BlobParams {
+            target_blobs_per_block: 6,
+            max_blobs_per_block: 9,
+            base_fee_update_fraction: 5_007_716,
+        };
+        assert_eq!(params.max_blob_gas_per_block(), 9 * GAS_PER_BLOB);
+        // the evm doesn't accept more blobs per transaction than in cancun
+        assert_eq!(params.max_blobs_per_tx(), MAX_BLOB_NUMBER_PER_BLOCK);
+        // a block at the cancun target is below the new target
+        assert_eq!(params.next_block_excess_blob_gas(0, 3 * GAS_PER_BLOB), 0);
+        assert_eq!(params.next_block_excess_blob_gas(GAS_PER_BLOB, 6 * GAS_PER_BLOB), GAS_PER_BLOB);
+
+        let params: BlobParams =
+            serde_json::from_str(r#"{"target":6,"max":9,"baseFeeUpdateFraction":5007716}"#)
+                .unwrap();
+        assert_eq!(params.target_blobs_per_block, 6);
+        assert_eq!(params.max_blobs_per_block, 9);
+    }
+}
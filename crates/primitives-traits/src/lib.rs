@@ -38,6 +38,9 @@ pub use log::{logs_bloom, Log, LogData};
 mod storage;
 pub use storage::StorageEntry;
 
+mod blob_params;
+pub use blob_params::BlobParams;
+
 /// Common header types
 pub mod header;
 #[cfg(any(test, feature = "arbitrary", feature = "test-utils"))]
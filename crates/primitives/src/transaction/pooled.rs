@@ -1,7 +1,7 @@
 //! Defines the types for blob transactions, legacy, and other EIP-2718 transactions included in a
 //! response to `GetPooledTransactions`.
 
-use super::error::TransactionConversionError;
+use super::{error::TransactionConversionError, PARALLEL_SENDER_RECOVERY_THRESHOLD};
 use crate::{
     Address, BlobTransaction, BlobTransactionSidecar, Bytes, Signature, Transaction,
     TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844, TxHash,
@@ -10,6 +10,7 @@ use crate::{
 use alloy_rlp::{Decodable, Encodable, Error as RlpError, Header, EMPTY_LIST_CODE};
 use bytes::Buf;
 use derive_more::{AsRef, Deref};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use reth_codecs::add_arbitrary_tests;
 use serde::{Deserialize, Serialize};
 
@@ -157,6 +158,21 @@ impl PooledTransactionsElement {
         }
     }
 
+    /// Tries to recover the signers of a batch of transactions, see also
+    /// [`Self::try_into_ecrecovered`].
+    ///
+    /// Large batches are recovered in parallel. The results are in the same order as the given
+    /// transactions.
+    pub fn try_into_ecrecovered_batch(
+        txs: Vec<Self>,
+    ) -> Vec<Result<PooledTransactionsElementEcRecovered, Self>> {
+        if txs.len() < *PARALLEL_SENDER_RECOVERY_THRESHOLD {
+            txs.into_iter().map(Self::try_into_ecrecovered).collect()
+        } else {
+            txs.into_par_iter().map(Self::try_into_ecrecovered).collect()
+        }
+    }
+
     /// Decodes the "raw" format of transaction (e.g. `eth_sendRawTransaction`).
     ///
     /// This should be used for `eth_sendRawTransaction`, for any transaction type. Blob
@@ -778,4 +794,46 @@ mod tests {
         let res = PooledTransactionsElement::decode_enveloped(&mut &data[..]);
         assert_matches!(res, Ok(_tx));
     }
+
+    #[test]
+    fn recover_batch_preserves_order() {
+        let secp = secp256k1::Secp256k1::new();
+        let mut rng = rand::thread_rng();
+        let mut txs = (0..*PARALLEL_SENDER_RECOVERY_THRESHOLD as u64 * 5)
+            .map(|nonce| {
+                let tx = Transaction::Legacy(TxLegacy { nonce, ..Default::default() });
+                let key_pair = secp256k1::Keypair::new(&secp, &mut rng);
+                let signature = crate::sign_message(
+                    B256::from_slice(&key_pair.secret_bytes()[..]),
+                    tx.signature_hash(),
+                )
+                .unwrap();
+                let tx = TransactionSigned::from_transaction_and_signature(tx, signature);
+                PooledTransactionsElement::try_from(tx).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // a transaction with an invalid signature
+        let invalid = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy::default()),
+            Signature::default(),
+        );
+        txs.insert(3, PooledTransactionsElement::try_from(invalid).unwrap());
+
+        let expected = txs.iter().map(|tx| tx.recover_signer()).collect::<Vec<_>>();
+        let recovered = PooledTransactionsElement::try_into_ecrecovered_batch(txs.clone());
+        assert_eq!(recovered.len(), txs.len());
+        for ((res, tx), signer) in recovered.into_iter().zip(txs).zip(expected) {
+            match res {
+                Ok(recovered) => {
+                    assert_eq!(Some(recovered.signer()), signer);
+                    assert_eq!(recovered.into_transaction(), tx);
+                }
+                Err(err) => {
+                    assert!(signer.is_none());
+                    assert_eq!(err, tx);
+                }
+            }
+        }
+    }
 }
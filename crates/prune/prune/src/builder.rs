@@ -25,6 +25,8 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Receiver of updates to the minimum pruning interval.
+    block_interval_updates: Option<watch::Receiver<usize>>,
 }
 
 impl PrunerBuilder {
@@ -80,11 +82,23 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the receiver of updates to the minimum pruning interval, so the interval can be
+    /// changed after the pruner was built.
+    ///
+    /// The value of the receiver takes precedence over [`Self::block_interval`].
+    pub fn block_interval_updates(
+        mut self,
+        block_interval_updates: watch::Receiver<usize>,
+    ) -> Self {
+        self.block_interval_updates = Some(block_interval_updates);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration.
     pub fn build<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> Pruner<DB> {
         let segments = SegmentSet::<DB>::from_prune_modes(self.segments);
 
-        Pruner::new(
+        let pruner = Pruner::new(
             provider_factory,
             segments.into_vec(),
             self.block_interval,
@@ -92,7 +106,14 @@ impl PrunerBuilder {
             self.max_reorg_depth,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+
+        match self.block_interval_updates {
+            Some(block_interval_updates) => {
+                pruner.with_block_interval_updates(block_interval_updates)
+            }
+            None => pruner,
+        }
     }
 }
 
@@ -105,6 +126,7 @@ impl Default for PrunerBuilder {
             prune_delete_limit: MAINNET.prune_delete_limit,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            block_interval_updates: None,
         }
     }
 }
@@ -36,7 +36,9 @@ pub struct Pruner<DB> {
     segments: Vec<Box<dyn Segment<DB>>>,
     /// Minimum pruning interval measured in blocks. All prune segments are checked and, if needed,
     /// pruned, when the chain advances by the specified number of blocks.
-    min_block_interval: usize,
+    ///
+    /// This is a receiver so the interval can be changed while the node is running.
+    min_block_interval: watch::Receiver<usize>,
     /// Previous tip block number when the pruner was run. Even if no data was pruned, this block
     /// number is updated with the tip block number the pruner was called with. It's used in
     /// conjunction with `min_block_interval` to determine when the pruning needs to be initiated.
@@ -69,7 +71,7 @@ impl<DB: Database> Pruner<DB> {
         Self {
             provider_factory,
             segments,
-            min_block_interval,
+            min_block_interval: watch::channel(min_block_interval).1,
             previous_tip_block_number: None,
             delete_limit_per_block: delete_limit,
             prune_max_blocks_per_run,
@@ -80,6 +82,16 @@ impl<DB: Database> Pruner<DB> {
         }
     }
 
+    /// Sets the receiver of the minimum pruning interval, which replaces the fixed interval the
+    /// pruner was created with.
+    pub fn with_block_interval_updates(
+        mut self,
+        min_block_interval: watch::Receiver<usize>,
+    ) -> Self {
+        self.min_block_interval = min_block_interval;
+        self
+    }
+
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
         self.event_sender.new_listener()
@@ -294,7 +306,7 @@ impl<DB: Database> Pruner<DB> {
         // current block number might be less than the previous tip block number.
         // If that's the case, no pruning is needed as outdated data is also reverted.
         if tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default()) >=
-            self.min_block_interval as u64
+            *self.min_block_interval.borrow() as u64
         {
             debug!(
                 target: "pruner",
@@ -364,7 +376,7 @@ mod tests {
         pruner.previous_tip_block_number = Some(first_block_number);
 
         // Tip block number delta is >= than min block interval
-        let second_block_number = first_block_number + pruner.min_block_interval as u64;
+        let second_block_number = first_block_number + *pruner.min_block_interval.borrow() as u64;
         assert!(pruner.is_pruning_needed(second_block_number));
         pruner.previous_tip_block_number = Some(second_block_number);
 
@@ -372,7 +384,7 @@ mod tests {
         assert!(!pruner.is_pruning_needed(second_block_number));
 
         // Tip block number delta is >= than min block interval
-        let third_block_number = second_block_number + pruner.min_block_interval as u64;
+        let third_block_number = second_block_number + *pruner.min_block_interval.borrow() as u64;
         assert!(pruner.is_pruning_needed(third_block_number));
 
         // Not all ExExs have emitted a `FinishedHeight` event yet
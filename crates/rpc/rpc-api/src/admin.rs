@@ -1,6 +1,7 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_network_api::{AdminPeerInfo, BandwidthWeights, PeerStats};
 use reth_network_peers::{AnyNode, NodeRecord};
-use reth_rpc_types::admin::{NodeInfo, PeerInfo};
+use reth_rpc_types::{admin::NodeInfo, txpool::TxpoolPolicy, ConfigReloadReport};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -31,8 +32,16 @@ pub trait AdminApi {
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
     /// specialized information added by each of the running application protocols
+    ///
+    /// Configured static nodes are included even if they're disconnected, together with their
+    /// health, since they're redialed with an exponential backoff whenever they disconnect.
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>>;
+
+    /// Returns the number and size of the messages exchanged with every connected peer by message
+    /// type, and the latencies of the requests sent to it.
+    #[method(name = "peerStats")]
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>>;
 
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
@@ -45,4 +54,60 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the weights used to share the bandwidth budget between sync, gossip and serving
+    /// traffic, or `null` if no bandwidth budget is configured.
+    #[method(name = "bandwidthWeights")]
+    fn bandwidth_weights(&self) -> RpcResult<Option<BandwidthWeights>>;
+
+    /// Updates the weights used to share the bandwidth budget between sync, gossip and serving
+    /// traffic. Takes effect immediately.
+    ///
+    /// Returns false if no bandwidth budget is configured.
+    #[method(name = "setBandwidthWeights")]
+    fn set_bandwidth_weights(&self, weights: BandwidthWeights) -> RpcResult<bool>;
+}
+
+/// Admin namespace rpc interface for changing the configuration of the running node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminConfigApi {
+    /// Reloads the configuration file of the node and applies the changed settings that can be
+    /// changed at runtime.
+    ///
+    /// Returns which of the changed settings were applied and which require a restart.
+    #[method(name = "reloadConfig")]
+    async fn reload_config(&self) -> RpcResult<ConfigReloadReport>;
+
+    /// Adds the given node to the protected peers, which are exempt from pruning, and persists
+    /// the protected peers to the configuration file.
+    ///
+    /// Returns false if the node is already protected.
+    #[method(name = "protectPeer")]
+    async fn protect_peer(&self, record: AnyNode) -> RpcResult<bool>;
+
+    /// Removes the given node from the protected peers and persists the protected peers to the
+    /// configuration file.
+    ///
+    /// Returns false if the node is not protected.
+    #[method(name = "unprotectPeer")]
+    async fn unprotect_peer(&self, record: AnyNode) -> RpcResult<bool>;
+}
+
+/// Admin namespace rpc interface for changing the transaction pool settings of the running node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminTxPoolApi {
+    /// Replaces the sender and recipient lists of the transaction policy of the pool.
+    ///
+    /// The policy is evaluated before new transactions are validated: transactions of allowed
+    /// senders are always accepted, transactions of denied senders or to denied recipients are
+    /// rejected. Transactions that are already in the pool are not affected.
+    #[method(name = "setTxpoolPolicy")]
+    async fn set_txpool_policy(&self, policy: TxpoolPolicy) -> RpcResult<bool>;
+
+    /// Enables or disables the computation of the missing KZG proofs of blob sidecars that are
+    /// submitted without proofs.
+    #[method(name = "setBlobProofComputation")]
+    async fn set_blob_proof_computation(&self, enabled: bool) -> RpcResult<bool>;
 }
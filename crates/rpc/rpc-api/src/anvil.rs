@@ -131,7 +131,7 @@ pub trait AnvilApi {
     async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64>;
 
     /// Similar to `evm_increaseTime` but takes the exact timestamp that you want in the next block.
-    #[method(name = "setNextBlockTimestamp")]
+    #[method(name = "setNextBlockTimestamp", aliases = ["evm_setNextBlockTimestamp"])]
     async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()>;
 
     /// Sets the next block gas limit.
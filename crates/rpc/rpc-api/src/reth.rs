@@ -1,5 +1,10 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, U256};
+use reth_rpc_types::{
+    BlockValidationResult, EffectiveChainConfig, PendingBlockReport, PoolBlock,
+    PoolBlocksAttributes, ReceiptsByBlock, ReorgEvent, SyncProgress, TipForInclusion,
+    TransactionRequest,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +17,124 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the RLP encoded state changes of a canonical block, computed from the stored
+    /// changesets.
+    ///
+    /// The encoding is the versioned `BlockStateDiff` format of `reth-execution-types`: the
+    /// changed accounts and storage slots with their values before and after the block, and the
+    /// code of the contracts deployed in the block.
+    #[method(name = "getExecutionOutcome")]
+    async fn reth_get_execution_outcome(&self, block_hash: B256) -> RpcResult<Bytes>;
+
+    /// Returns the most recent reorgs of the canonical chain recorded by this node, newest first.
+    ///
+    /// Returns at most `limit` reorgs, or the default limit if none is given.
+    #[method(name = "getReorgHistory")]
+    async fn reth_get_reorg_history(&self, limit: Option<usize>) -> RpcResult<Vec<ReorgEvent>>;
+
+    /// Creates a subscription that emits every reorg of the canonical chain as it is recorded.
+    #[subscription(
+        name = "subscribeReorgs",
+        unsubscribe = "unsubscribeReorgs",
+        item = ReorgEvent
+    )]
+    async fn reth_subscribe_reorgs(&self) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Returns the receipts of all blocks from `start` to `end`, inclusive, grouped by block.
+    ///
+    /// The range is limited to a maximum number of blocks, larger ranges can be streamed with
+    /// `reth_subscribeReceiptsByBlockRange`.
+    #[method(name = "getReceiptsByBlockRange")]
+    async fn reth_get_receipts_by_block_range(
+        &self,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> RpcResult<Vec<ReceiptsByBlock>>;
+
+    /// Creates a subscription that emits the receipts of every block from `start` to `end`,
+    /// inclusive, one block at a time.
+    ///
+    /// The last item is the receipts of block `end`, after which no more items are emitted.
+    #[subscription(
+        name = "subscribeReceiptsByBlockRange",
+        unsubscribe = "unsubscribeReceiptsByBlockRange",
+        item = ReceiptsByBlock
+    )]
+    async fn reth_subscribe_receipts_by_block_range(
+        &self,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Returns the chain configuration the node runs with: the chain id, the activation of all
+    /// hardforks, the blob parameters, the deposit contract and the hardfork overrides applied by
+    /// the node.
+    #[method(name = "chainConfig")]
+    fn reth_chain_config(&self) -> RpcResult<EffectiveChainConfig>;
+}
+
+/// Reth API namespace for tip suggestions and reports based on the pending state and the
+/// transaction pool of this node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethTipApi {
+    /// Returns the minimal priority fee the given transaction has to pay to be included within
+    /// `blocks` blocks, or the next block if none is given.
+    ///
+    /// The transaction is simulated against the locally built pending block and its gas is
+    /// weighed against the pending transactions that pay a higher tip.
+    #[method(name = "suggestTipForInclusion")]
+    async fn reth_suggest_tip_for_inclusion(
+        &self,
+        request: TransactionRequest,
+        blocks: Option<u64>,
+    ) -> RpcResult<TipForInclusion>;
+
+    /// Returns the transactions of the locally built pending block, the included transactions
+    /// that revert, and why the other transactions of the pool are not included.
+    ///
+    /// Private transactions are never reported.
+    #[method(name = "pendingBlockReport")]
+    async fn reth_pending_block_report(&self) -> RpcResult<PendingBlockReport>;
+
+    /// Returns the next `blocks` blocks a block builder would fill from the best transactions of
+    /// the pool with the given attributes, without executing them.
+    ///
+    /// Transactions are assumed to use their full gas limit. Attributes that are not given are
+    /// taken from the pending block and apply to all blocks. Fewer blocks are returned if the pool
+    /// runs out of transactions. Private transactions are never reported.
+    #[method(name = "contentAsBlocks")]
+    async fn reth_content_as_blocks(
+        &self,
+        blocks: u64,
+        attributes: Option<PoolBlocksAttributes>,
+    ) -> RpcResult<Vec<PoolBlock>>;
+}
+
+/// Reth API namespace for validating blocks outside of the Engine API.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethValidationApi {
+    /// Runs full consensus and execution validation of the given RLP encoded block and returns
+    /// the result of every check.
+    ///
+    /// The block is executed on top of the state of its parent, or of `parent` if given. The
+    /// block is neither inserted nor persisted.
+    #[method(name = "validateBlock")]
+    async fn reth_validate_block(
+        &self,
+        block: Bytes,
+        parent: Option<BlockId>,
+    ) -> RpcResult<BlockValidationResult>;
+}
+
+/// Reth API namespace for the progress of the pipeline sync.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethSyncApi {
+    /// Returns the progress of every stage of the pipeline with its recent throughput and
+    /// remaining work, and the disk usage of the node projected from its recent growth.
+    #[method(name = "syncProgress")]
+    fn reth_sync_progress(&self) -> RpcResult<SyncProgress>;
 }
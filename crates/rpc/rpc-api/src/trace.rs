@@ -38,7 +38,7 @@ pub trait TraceApi {
 
     /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces.
     ///
-    /// Expects a raw transaction data
+    /// Expects a raw transaction data, the nonce of the transaction is not checked.
     #[method(name = "rawTransaction")]
     async fn trace_raw_transaction(
         &self,
@@ -76,12 +76,10 @@ pub trait TraceApi {
     #[method(name = "filter")]
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
 
-    /// Returns transaction trace at given index.
+    /// Returns the trace of the transaction at the given trace address.
     ///
-    /// `indices` represent the index positions of the traces.
-    ///
-    /// Note: This expects a list of indices but only one is supported since this function returns a
-    /// single [LocalizedTransactionTrace].
+    /// `indices` is the trace address of the trace in the call tree of the transaction, an empty
+    /// address returns the top-level call.
     #[method(name = "get")]
     async fn trace_get(
         &self,
@@ -1,6 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolFilter, TxpoolInspect, TxpoolStatus,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -17,20 +19,30 @@ pub trait TxPoolApi {
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
+    ///
+    /// Accepts an optional [`TxpoolFilter`] to filter and paginate the result server side.
     #[method(name = "inspect")]
-    async fn txpool_inspect(&self) -> RpcResult<TxpoolInspect>;
+    async fn txpool_inspect(&self, filter: Option<TxpoolFilter>) -> RpcResult<TxpoolInspect>;
 
     /// Retrieves the transactions contained within the txpool, returning pending as well as queued
     /// transactions of this address, grouped by nonce.
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_contentFrom) for more details
+    ///
+    /// Accepts an optional [`TxpoolFilter`] to filter and paginate the result server side.
     #[method(name = "contentFrom")]
-    async fn txpool_content_from(&self, from: Address) -> RpcResult<TxpoolContentFrom>;
+    async fn txpool_content_from(
+        &self,
+        from: Address,
+        filter: Option<TxpoolFilter>,
+    ) -> RpcResult<TxpoolContentFrom>;
 
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
+    ///
+    /// Accepts an optional [`TxpoolFilter`] to filter and paginate the result server side.
     #[method(name = "content")]
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+    async fn txpool_content(&self, filter: Option<TxpoolFilter>) -> RpcResult<TxpoolContent>;
 }
@@ -11,7 +11,7 @@ use reth_rpc_api::servers::*;
 use reth_rpc_eth_types::EthSubscriptionIdProvider;
 use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthClientService, AuthLayer, JwtAuthValidator,
-    JwtSecret,
+    JwtClaimsValidation, JwtSecret, JwtSecrets,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -27,6 +27,10 @@ pub struct AuthServerConfig {
     pub(crate) socket_addr: SocketAddr,
     /// The secret for the auth layer of the server.
     pub(crate) secret: JwtSecret,
+    /// Additional secrets the auth layer accepts, which can be updated while the server runs.
+    pub(crate) additional_secrets: Option<JwtSecrets>,
+    /// The claims the auth layer validates in addition to the issuance timestamp.
+    pub(crate) claims: JwtClaimsValidation,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerBuilder<Identity, Identity>,
     /// Configs for IPC server
@@ -48,13 +52,32 @@ impl AuthServerConfig {
         self.socket_addr
     }
 
+    /// Also accepts the tokens signed with any of the additional secrets.
+    ///
+    /// The set can be updated after the server was started, e.g. to rotate the secrets.
+    pub fn with_additional_secrets(mut self, secrets: JwtSecrets) -> Self {
+        self.additional_secrets = Some(secrets);
+        self
+    }
+
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
+        let Self {
+            socket_addr,
+            secret,
+            additional_secrets,
+            claims,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+        } = self;
 
         // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let mut validator = JwtAuthValidator::new(secret).with_claims(claims);
+        if let Some(additional_secrets) = additional_secrets {
+            validator = validator.with_additional_secrets(additional_secrets);
+        }
+        let middleware = tower::ServiceBuilder::new().layer(AuthLayer::new(validator));
 
         // By default, both http and ws are enabled.
         let server = server_config
@@ -91,6 +114,8 @@ impl AuthServerConfig {
 pub struct AuthServerConfigBuilder {
     socket_addr: Option<SocketAddr>,
     secret: JwtSecret,
+    additional_secrets: Option<JwtSecrets>,
+    claims: JwtClaimsValidation,
     server_config: Option<ServerBuilder<Identity, Identity>>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
@@ -104,6 +129,8 @@ impl AuthServerConfigBuilder {
         Self {
             socket_addr: None,
             secret,
+            additional_secrets: None,
+            claims: JwtClaimsValidation::new(),
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
@@ -128,6 +155,18 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Set the additional secrets the server accepts next to the secret.
+    pub fn additional_secrets(mut self, secrets: JwtSecrets) -> Self {
+        self.additional_secrets = Some(secrets);
+        self
+    }
+
+    /// Set the claims the server validates in addition to the issuance timestamp.
+    pub fn claims(mut self, claims: JwtClaimsValidation) -> Self {
+        self.claims = claims;
+        self
+    }
+
     /// Configures the JSON-RPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -158,6 +197,8 @@ impl AuthServerConfigBuilder {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
             secret: self.secret,
+            additional_secrets: self.additional_secrets,
+            claims: self.claims,
             server_config: self.server_config.unwrap_or_else(|| {
                 ServerBuilder::new()
                     // This needs to large enough to handle large eth_getLogs responses and maximum
@@ -257,6 +298,14 @@ impl AuthServerHandle {
         self.handle.stop()
     }
 
+    /// Waits until the server has stopped, which happens once it was told to [stop] and all its
+    /// connections are closed.
+    ///
+    /// [stop]: Self::stop
+    pub async fn stopped(self) {
+        self.handle.stopped().await
+    }
+
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
         format!("http://{}", self.local_addr)
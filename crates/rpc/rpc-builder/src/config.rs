@@ -4,8 +4,9 @@ use crate::{
 };
 use jsonrpsee::server::ServerBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc_eth_types::{EthStateCacheConfig, GasPriceOracleConfig};
-use reth_rpc_layer::{JwtError, JwtSecret};
+use reth_rpc::eth::FilterPersistenceConfig;
+use reth_rpc_eth_types::{EthStateCacheConfig, GasPriceOracleConfig, StructLogLimits};
+use reth_rpc_layer::{JwtClaimsValidation, JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{net::SocketAddr, path::PathBuf};
 use tower::layer::util::Identity;
@@ -89,12 +90,25 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .trace_cache_max_bytes(self.rpc_state_cache.max_trace_memory * 1024 * 1024)
+            .struct_log_limits(StructLogLimits {
+                max_steps: self.rpc_struct_log_max_steps,
+                max_memory_bytes: self.rpc_struct_log_max_memory,
+                max_storage_entries: self.rpc_struct_log_max_storage_entries,
+                max_total_bytes: self.rpc_struct_log_max_total_memory,
+            })
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
             .rpc_gas_cap(self.rpc_gas_cap)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
+            .filter_id_prefix(self.rpc_filter_id_prefix.clone())
+            .filter_persistence(
+                self.rpc_filter_store
+                    .clone()
+                    .map(|path| FilterPersistenceConfig::new(path, self.rpc_filter_store_ttl)),
+            )
     }
 
     fn state_cache_config(&self) -> EthStateCacheConfig {
@@ -102,6 +116,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
+            max_rpc_blocks: self.rpc_state_cache.max_rpc_blocks,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
         }
     }
@@ -162,7 +177,9 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_jwt_modules(self.rpc_jwtsecret_namespaces.clone());
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -189,7 +206,10 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let claims = JwtClaimsValidation::new()
+            .with_issuers(self.auth_jwt_issuers.clone())
+            .with_audiences(self.auth_jwt_audiences.clone());
+        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address).claims(claims);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
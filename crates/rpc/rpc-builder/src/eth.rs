@@ -6,15 +6,19 @@ use reth_provider::{
     BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider,
     FullRpcProvider, StateProviderFactory,
 };
-use reth_rpc::{eth::EthFilterConfig, EthApi, EthFilter, EthPubSub};
+use reth_rpc::{
+    eth::{EthFilterConfig, FilterPersistenceConfig},
+    EthApi, EthFilter, EthPubSub,
+};
 use reth_rpc_eth_types::{
-    cache::cache_new_blocks_task, fee_history::fee_history_cache_new_blocks_task, EthStateCache,
-    EthStateCacheConfig, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
-    GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    cache::cache_new_blocks_task, fee_history::fee_history_cache_new_blocks_task,
+    gas_oracle::gas_price_oracle_new_blocks_task, EthStateCache, EthStateCacheConfig,
+    FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle, GasPriceOracleConfig,
+    GasPriceOracleStrategy, StructLogLimits, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
-    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE,
+    cache::DEFAULT_TRACE_CACHE_SIZE_BYTES_MB, default_max_tracing_requests,
+    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner};
 use reth_transaction_pool::TransactionPool;
@@ -146,6 +150,10 @@ pub struct EthConfig {
     pub eth_proof_window: u64,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
+    /// The memory budget in bytes of the cache for tracing results, `0` disables the cache.
+    pub trace_cache_max_bytes: usize,
+    /// Limits on what the struct log tracer captures of a transaction.
+    pub struct_log_limits: StructLogLimits,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
@@ -157,6 +165,10 @@ pub struct EthConfig {
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: Duration,
+    /// The prefix of the ids of installed filters, if any.
+    pub filter_id_prefix: Option<String>,
+    /// Where log and block filters are persisted to survive restarts, if enabled.
+    pub filter_persistence: Option<FilterPersistenceConfig>,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
 }
@@ -164,10 +176,17 @@ pub struct EthConfig {
 impl EthConfig {
     /// Returns the filter config for the `eth_filter` handler.
     pub fn filter_config(&self) -> EthFilterConfig {
-        EthFilterConfig::default()
+        let mut config = EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
-            .stale_filter_ttl(self.stale_filter_ttl)
+            .stale_filter_ttl(self.stale_filter_ttl);
+        if let Some(prefix) = &self.filter_id_prefix {
+            config = config.id_prefix(prefix.clone());
+        }
+        if let Some(persistence) = &self.filter_persistence {
+            config = config.persistence(persistence.clone());
+        }
+        config
     }
 }
 
@@ -178,10 +197,14 @@ impl Default for EthConfig {
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             max_tracing_requests: default_max_tracing_requests(),
+            trace_cache_max_bytes: DEFAULT_TRACE_CACHE_SIZE_BYTES_MB * 1024 * 1024,
+            struct_log_limits: StructLogLimits::default(),
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
+            filter_id_prefix: None,
+            filter_persistence: None,
             fee_history_cache: FeeHistoryCacheConfig::default(),
         }
     }
@@ -206,6 +229,18 @@ impl EthConfig {
         self
     }
 
+    /// Configures the memory budget in bytes of the trace cache
+    pub const fn trace_cache_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.trace_cache_max_bytes = max_bytes;
+        self
+    }
+
+    /// Configures the limits of the struct log tracer
+    pub const fn struct_log_limits(mut self, limits: StructLogLimits) -> Self {
+        self.struct_log_limits = limits;
+        self
+    }
+
     /// Configures the maximum block length to scan per `eth_getLogs` request
     pub const fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
         self.max_blocks_per_filter = max_blocks;
@@ -229,6 +264,18 @@ impl EthConfig {
         self.eth_proof_window = window;
         self
     }
+
+    /// Configures the prefix of the ids of installed filters
+    pub fn filter_id_prefix(mut self, prefix: Option<String>) -> Self {
+        self.filter_id_prefix = prefix;
+        self
+    }
+
+    /// Configures where log and block filters are persisted
+    pub fn filter_persistence(mut self, persistence: Option<FilterPersistenceConfig>) -> Self {
+        self.filter_persistence = persistence;
+        self
+    }
 }
 
 /// Context for building the `eth` namespace API.
@@ -263,7 +310,7 @@ impl EthApiBuild {
     ) -> EthApi<Provider, Pool, Network, EvmConfig>
     where
         Provider: FullRpcProvider,
-        Pool: TransactionPool,
+        Pool: TransactionPool + 'static,
         Network: NetworkInfo + Clone,
         Tasks: TaskSpawner + Clone + 'static,
         Events: CanonStateSubscriptions,
@@ -349,9 +396,27 @@ impl GasPriceOracleBuilder {
         ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
     ) -> GasPriceOracle<Provider>
     where
-        Provider: BlockReaderIdExt + Clone,
+        Provider: BlockReaderIdExt + Clone + 'static,
+        Pool: TransactionPool + 'static,
+        Tasks: TaskSpawner,
+        Events: CanonStateSubscriptions,
     {
-        GasPriceOracle::new(ctx.provider.clone(), ctx.config.gas_oracle, ctx.cache.clone())
+        let mut gas_oracle =
+            GasPriceOracle::new(ctx.provider.clone(), ctx.config.gas_oracle, ctx.cache.clone());
+        if ctx.config.gas_oracle.strategy == GasPriceOracleStrategy::PendingPool {
+            gas_oracle = gas_oracle.with_pending_tips(ctx.pool.clone());
+        }
+
+        let new_canonical_blocks = ctx.events.canonical_state_stream();
+        let oracle = gas_oracle.clone();
+        ctx.executor.spawn_critical(
+            "cache canonical blocks for gas price oracle task",
+            Box::pin(async move {
+                gas_price_oracle_new_blocks_task(oracle, new_canonical_blocks).await;
+            }),
+        );
+
+        gas_oracle
     }
 }
 
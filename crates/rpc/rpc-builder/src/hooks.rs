@@ -0,0 +1,226 @@
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use std::{
+    collections::HashSet,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::Layer;
+
+/// The value the params of redacted calls are replaced with.
+pub const REDACTED_PARAMS: &str = "\"<redacted>\"";
+
+/// A future returned by an [`RpcCallHook`].
+pub type RpcCallHookFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Observes every call of an RPC method, e.g. for audit logging or billing.
+///
+/// The hook is invoked once the response of the call is ready, and the returned future is spawned,
+/// so it does not delay the response.
+pub trait RpcCallHook: Send + Sync + 'static {
+    /// Invoked for every finished call.
+    fn on_call(&self, call: RpcCall) -> RpcCallHookFuture;
+}
+
+impl<F, Fut> RpcCallHook for F
+where
+    F: Fn(RpcCall) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn on_call(&self, call: RpcCall) -> RpcCallHookFuture {
+        Box::pin(self(call))
+    }
+}
+
+/// A finished call of an RPC method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcCall {
+    /// The name of the method.
+    pub method: String,
+    /// The JSON encoded params of the call, [`REDACTED_PARAMS`] if they are redacted.
+    pub params: Option<String>,
+    /// The time it took to produce the response.
+    pub latency: Duration,
+    /// Whether the call succeeded.
+    pub outcome: RpcCallOutcome,
+}
+
+/// The outcome of an [`RpcCall`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcCallOutcome {
+    /// The call returned a result.
+    Success,
+    /// The call returned an error, with the error code if available.
+    Error(Option<i32>),
+}
+
+/// The hooks registered for the calls of an RPC server, and which params are hidden from them.
+///
+/// By default there are no hooks and no params are redacted.
+#[derive(Clone, Default)]
+pub struct RpcCallHooks {
+    hooks: Vec<Arc<dyn RpcCallHook>>,
+    redaction: ParamsRedaction,
+}
+
+/// Which params are hidden from the [`RpcCallHook`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum ParamsRedaction {
+    /// The params of the given methods are redacted.
+    #[default]
+    Methods(HashSet<String>),
+    /// All params are redacted.
+    All,
+}
+
+impl RpcCallHooks {
+    /// Registers a hook.
+    pub fn with_hook(mut self, hook: impl RpcCallHook) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Redacts the params of the given methods, e.g. of `personal_unlockAccount`.
+    pub fn with_redacted_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if let ParamsRedaction::Methods(redacted) = &mut self.redaction {
+            redacted.extend(methods.into_iter().map(Into::into));
+        }
+        self
+    }
+
+    /// Redacts the params of all methods.
+    pub fn with_all_params_redacted(mut self) -> Self {
+        self.redaction = ParamsRedaction::All;
+        self
+    }
+
+    /// Returns `true` if no hooks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Returns the params of the call to the method as they are passed to the hooks.
+    fn params(&self, method: &str, params: Option<&str>) -> Option<String> {
+        let redacted = match &self.redaction {
+            ParamsRedaction::Methods(methods) => methods.contains(method),
+            ParamsRedaction::All => true,
+        };
+        params.map(|params| if redacted { REDACTED_PARAMS.to_string() } else { params.to_string() })
+    }
+
+    fn notify(&self, call: RpcCall) {
+        for hook in &self.hooks {
+            tokio::spawn(hook.on_call(call.clone()));
+        }
+    }
+}
+
+impl fmt::Debug for RpcCallHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcCallHooks")
+            .field("hooks", &self.hooks.len())
+            .field("redaction", &self.redaction)
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for RpcCallHooks {
+    type Service = RpcCallHooksService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCallHooksService { inner, hooks: self.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that invokes the [`RpcCallHooks`] for each call.
+#[derive(Clone, Debug)]
+pub struct RpcCallHooksService<S> {
+    inner: S,
+    hooks: RpcCallHooks,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcCallHooksService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = RpcCallHooksFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let call = (!self.hooks.is_empty()).then(|| {
+            let method = req.method_name();
+            let params =
+                self.hooks.params(method, req.params.as_deref().map(|params| params.get()));
+            (self.hooks.clone(), method.to_string(), params)
+        });
+        RpcCallHooksFuture { fut: self.inner.call(req), started_at: Instant::now(), call }
+    }
+}
+
+/// Response future that invokes the hooks once the response is ready.
+#[pin_project::pin_project]
+pub struct RpcCallHooksFuture<F> {
+    #[pin]
+    fut: F,
+    /// time when the request started
+    started_at: Instant,
+    /// the hooks, method name and params of the call, if any hooks are registered
+    call: Option<(RpcCallHooks, String, Option<String>)>,
+}
+
+impl<F> fmt::Debug for RpcCallHooksFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RpcCallHooksFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for RpcCallHooksFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            if let Some((hooks, method, params)) = this.call.take() {
+                let outcome = if resp.is_success() {
+                    RpcCallOutcome::Success
+                } else {
+                    RpcCallOutcome::Error(resp.as_error_code())
+                };
+                hooks.notify(RpcCall {
+                    method,
+                    params,
+                    latency: this.started_at.elapsed(),
+                    outcome,
+                });
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_params() {
+        let hooks = RpcCallHooks::default().with_redacted_methods(["personal_unlockAccount"]);
+        assert_eq!(hooks.params("eth_call", Some("[1]")).as_deref(), Some("[1]"));
+        assert_eq!(
+            hooks.params("personal_unlockAccount", Some("[\"secret\"]")).as_deref(),
+            Some(REDACTED_PARAMS)
+        );
+        assert_eq!(hooks.params("personal_unlockAccount", None), None);
+
+        let hooks = hooks.with_all_params_redacted();
+        assert_eq!(hooks.params("eth_call", Some("[1]")).as_deref(), Some(REDACTED_PARAMS));
+    }
+}
@@ -136,7 +136,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     sync::Arc,
@@ -159,17 +159,21 @@ use reth_provider::{
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, RethTipApi,
+    TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt, UpdateRawTxForwarder,
+        Call, EthApiSpec, EthCall, EthTransactions, LoadPendingBlock, TraceExt,
+        UpdateRawTxForwarder,
     },
     EthApiServer, FullEthApiServer, RawTransactionForwarder,
 };
-use reth_rpc_eth_types::{EthStateCache, EthSubscriptionIdProvider};
+use reth_rpc_eth_types::{
+    cache::trace::trace_cache_reorg_task, EthStateCache, EthSubscriptionIdProvider,
+    StructLogLimits, TraceCache,
+};
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -209,6 +213,14 @@ pub use eth::{
     GasPriceOracleBuilder,
 };
 
+/// Hooks observing the calls of the rpc server
+pub mod hooks;
+pub use hooks::{RpcCall, RpcCallHook, RpcCallHooks, RpcCallOutcome};
+
+/// Per-namespace authentication of the http and ws servers
+pub mod namespace_auth;
+use namespace_auth::{AuthenticateLayer, NamespaceAuthLayer};
+
 // Rpc server metrics
 mod metrics;
 
@@ -627,6 +639,10 @@ pub struct RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi> {
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Caches the results of trace calls
+    trace_cache: TraceCache,
+    /// Limits of the struct log tracer
+    struct_log_limits: StructLogLimits,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -661,6 +677,19 @@ where
     {
         let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
 
+        let trace_cache = TraceCache::new(config.eth.trace_cache_max_bytes);
+        let struct_log_limits = config.eth.struct_log_limits;
+        if trace_cache.is_enabled() {
+            let new_canonical_blocks = events.canonical_state_stream();
+            let c = trace_cache.clone();
+            executor.spawn_critical(
+                "trace cache reorg task",
+                Box::pin(async move {
+                    trace_cache_reorg_task(c, new_canonical_blocks).await;
+                }),
+            );
+        }
+
         let eth = EthHandlers::builder(
             provider.clone(),
             pool.clone(),
@@ -681,6 +710,8 @@ where
             executor,
             modules: Default::default(),
             blocking_pool_guard,
+            trace_cache,
+            struct_log_limits,
             events,
         }
     }
@@ -879,9 +910,14 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
-        let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Events: CanonStateSubscriptions + Clone + 'static,
+        EthApi: EthCall + LoadPendingBlock + 'static,
+    {
+        let mut module = self.reth_api().into_rpc();
+        module.merge(self.reth_tip_api().into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, module.into());
         self
     }
 
@@ -898,6 +934,7 @@ where
             self.provider.clone(),
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
+            self.trace_cache.clone(),
         )
     }
 
@@ -937,7 +974,13 @@ where
         EthApi: EthApiSpec + EthTransactions + TraceExt,
     {
         let eth_api = self.eth_api().clone();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.trace_cache.clone(),
+            self.struct_log_limits,
+        )
     }
 
     /// Instantiates `NetApi`
@@ -953,9 +996,25 @@ where
         NetApi::new(self.network.clone(), eth_api)
     }
 
+    /// Instantiates `RethTipApi`
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn reth_tip_api(&self) -> RethTipApi<EthApi> {
+        RethTipApi::new(self.eth_api().clone())
+    }
+
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&self) -> RethApi<Provider>
+    where
+        Events: CanonStateSubscriptions + Clone + 'static,
+    {
+        RethApi::new(
+            self.provider.clone(),
+            Box::new(self.events.clone()),
+            Box::new(self.executor.clone()),
+        )
     }
 }
 
@@ -1060,6 +1119,8 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.trace_cache.clone(),
+                            self.struct_log_limits,
                         )
                         .into_rpc()
                         .into(),
@@ -1078,6 +1139,7 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.trace_cache.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -1095,9 +1157,17 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                Box::new(self.events.clone()),
+                                Box::new(self.executor.clone()),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(RethTipApi::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+
+                            module.into()
                         }
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1142,6 +1212,10 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// The modules whose methods require the JWT, all methods if not set
+    jwt_modules: Option<HashSet<RethRpcModule>>,
+    /// Hooks invoked for every call on the http, ws and ipc servers
+    call_hooks: RpcCallHooks,
 }
 
 // === impl RpcServerConfig ===
@@ -1259,6 +1333,24 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the modules whose methods require the JWT of [`Self::with_jwt_secret`], e.g.
+    /// `debug` and `admin` while `eth` stays public.
+    ///
+    /// By default, all calls on the http and ws servers require the JWT if a secret is
+    /// configured. The ipc server is never authenticated.
+    pub fn with_jwt_modules(mut self, modules: Option<impl Into<RpcModuleSelection>>) -> Self {
+        self.jwt_modules = modules.map(|modules| modules.into().into_selection());
+        self
+    }
+
+    /// Configures the hooks that are invoked for every call on the http, ws and ipc servers.
+    ///
+    /// See also [`RpcCallHooks`].
+    pub fn with_call_hooks(mut self, hooks: RpcCallHooks) -> Self {
+        self.call_hooks = hooks;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1293,9 +1385,25 @@ impl RpcServerConfig {
         cors.as_deref().map(cors::create_cors_layer).transpose()
     }
 
-    /// Creates the [`AuthLayer`] if any
+    /// Creates the [`AuthLayer`] if all calls require the JWT
     fn maybe_jwt_layer(&self) -> Option<AuthLayer<JwtAuthValidator>> {
-        self.jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
+        self.jwt_secret
+            .filter(|_| self.jwt_modules.is_none())
+            .map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
+    }
+
+    /// Creates the [`AuthenticateLayer`] if only the calls of some modules require the JWT
+    fn maybe_authenticate_layer(&self) -> Option<AuthenticateLayer> {
+        self.jwt_secret.filter(|_| self.jwt_modules.is_some()).map(AuthenticateLayer::new)
+    }
+
+    /// Creates the [`NamespaceAuthLayer`] that rejects the unauthenticated calls of the modules
+    /// that require the JWT
+    fn namespace_auth_layer(&self) -> NamespaceAuthLayer {
+        match (&self.jwt_secret, &self.jwt_modules) {
+            (Some(_), Some(modules)) => NamespaceAuthLayer::new(modules.iter().cloned()),
+            _ => NamespaceAuthLayer::default(),
+        }
     }
 
     /// Builds the ws and http server(s).
@@ -1345,17 +1453,21 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(cors)?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_authenticate_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules
-                            .http
-                            .as_ref()
-                            .or(modules.ws.as_ref())
-                            .map(RpcRequestMetrics::same_port)
-                            .unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .or(modules.ws.as_ref())
+                                .map(RpcRequestMetrics::same_port)
+                                .unwrap_or_default(),
+                        )
+                        .layer(self.call_hooks.clone())
+                        .layer(self.namespace_auth_layer()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1382,11 +1494,14 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_authenticate_layer()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::new()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(self.call_hooks.clone())
+                        .layer(self.namespace_auth_layer()),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1405,12 +1520,16 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_authenticate_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(self.call_hooks.clone())
+                        .layer(self.namespace_auth_layer()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1445,7 +1564,9 @@ impl RpcServerConfig {
             let ipc_path =
                 self.ipc_endpoint.unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(metrics).layer(self.call_hooks),
+                )
                 .build(ipc_path);
             server.ipc = Some(ipc);
         }
@@ -1645,6 +1766,28 @@ impl TransportRpcModules {
         Ok(false)
     }
 
+    /// Merge the given [Methods] in the methods of every transport that has the given module
+    /// configured.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.config.http().is_some_and(|http| http.contains(&module)) {
+            self.merge_http(other.clone())?;
+        }
+        if self.config.ws().is_some_and(|ws| ws.contains(&module)) {
+            self.merge_ws(other.clone())?;
+        }
+        if self.config.ipc().is_some_and(|ipc| ipc.contains(&module)) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Merge the given [Methods] in all configured methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -1681,10 +1824,13 @@ struct WsHttpServer {
 // Define the type alias with detailed type complexity
 type WsHttpServerKind = Server<
     Stack<
-        tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
-        Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
+        tower::util::Either<AuthenticateLayer, Identity>,
+        Stack<
+            tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
+            Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
+        >,
     >,
-    Stack<RpcRequestMetrics, Identity>,
+    Stack<NamespaceAuthLayer, Stack<RpcCallHooks, Stack<RpcRequestMetrics, Identity>>>,
 >;
 
 /// Enum for holding the http and ws servers in all possible combinations.
@@ -1748,7 +1894,7 @@ pub struct RpcServer {
     /// Configured ws,http servers
     ws_http: WsHttpServer,
     /// ipc server
-    ipc: Option<IpcServer<Identity, Stack<RpcRequestMetrics, Identity>>>,
+    ipc: Option<IpcServer<Identity, Stack<RpcCallHooks, Stack<RpcRequestMetrics, Identity>>>>,
 }
 
 // === impl RpcServer ===
@@ -1884,6 +2030,24 @@ impl RpcServerHandle {
         Ok(())
     }
 
+    /// Waits until all servers have stopped, which happens once they were told to [stop] and all
+    /// their connections are closed.
+    ///
+    /// [stop]: Self::stop
+    pub async fn stopped(self) {
+        if let Some(handle) = self.http {
+            handle.stopped().await
+        }
+
+        if let Some(handle) = self.ws {
+            handle.stopped().await
+        }
+
+        if let Some(handle) = self.ipc {
+            handle.stopped().await
+        }
+    }
+
     /// Returns the endpoint of the launched IPC server, if any
     pub fn ipc_endpoint(&self) -> Option<String> {
         self.ipc_endpoint.clone()
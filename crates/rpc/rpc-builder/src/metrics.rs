@@ -12,6 +12,7 @@ use std::{
     time::Instant,
 };
 use tower::Layer;
+use tracing::{debug_span, instrument::Instrumented, Instrument};
 
 /// Metrics for the RPC server.
 ///
@@ -99,7 +100,7 @@ impl<'a, S> RpcServiceT<'a> for RpcRequestMetricsService<S>
 where
     S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
 {
-    type Future = MeteredRequestFuture<S::Future>;
+    type Future = MeteredRequestFuture<Instrumented<S::Future>>;
 
     fn call(&self, req: Request<'a>) -> Self::Future {
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
@@ -107,8 +108,9 @@ where
         if let Some((_, call_metrics)) = &call_metrics {
             call_metrics.started_total.increment(1);
         }
+        let span = debug_span!(target: "rpc", "rpc_call", method = %req.method_name());
         MeteredRequestFuture {
-            fut: self.inner.call(req),
+            fut: self.inner.call(req).instrument(span),
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
             method: call_metrics.map(|(method, _)| *method),
@@ -0,0 +1,182 @@
+//! Authentication of individual RPC namespaces on the http and ws servers.
+//!
+//! Unlike the [`AuthLayer`](reth_rpc_layer::AuthLayer), which rejects every request without a
+//! valid JWT, the [`AuthenticateLayer`] only marks the http requests that carry a valid JWT, and
+//! the [`NamespaceAuthLayer`] rejects the calls of the protected namespaces that are not marked.
+
+use http::Request as HttpRequest;
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use reth_rpc_layer::{get_bearer, JwtSecret};
+use reth_rpc_server_types::RethRpcModule;
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The error code of calls that were rejected because they are not authenticated.
+pub const UNAUTHORIZED_ERROR_CODE: i32 = -32001;
+
+/// Marks an http request that carries a valid JWT.
+#[derive(Debug, Clone, Copy)]
+struct Authenticated;
+
+/// Http middleware that marks the requests with a valid JWT, without rejecting any request.
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct AuthenticateLayer {
+    secret: JwtSecret,
+}
+
+impl AuthenticateLayer {
+    /// Creates a new layer that validates the JWT with the secret.
+    pub const fn new(secret: JwtSecret) -> Self {
+        Self { secret }
+    }
+}
+
+impl<S> Layer<S> for AuthenticateLayer {
+    type Service = AuthenticateService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthenticateService { secret: self.secret, inner }
+    }
+}
+
+/// The service of the [`AuthenticateLayer`].
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct AuthenticateService<S> {
+    secret: JwtSecret,
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for AuthenticateService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        let authenticated =
+            get_bearer(req.headers()).is_some_and(|jwt| self.secret.validate(&jwt).is_ok());
+        if authenticated {
+            req.extensions_mut().insert(Authenticated);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// RPC middleware that rejects the calls of the protected namespaces whose http request was not
+/// marked by the [`AuthenticateLayer`].
+///
+/// Without protected namespaces, all calls pass.
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceAuthLayer {
+    protected: Arc<HashSet<&'static str>>,
+}
+
+impl NamespaceAuthLayer {
+    /// Creates a new layer that protects the namespaces of the given modules.
+    pub fn new(modules: impl IntoIterator<Item = RethRpcModule>) -> Self {
+        Self { protected: Arc::new(modules.into_iter().map(|module| module.as_str()).collect()) }
+    }
+
+    /// Returns `true` if the method belongs to a protected namespace.
+    fn is_protected(&self, method: &str) -> bool {
+        method.split_once('_').is_some_and(|(namespace, _)| self.protected.contains(namespace))
+    }
+}
+
+impl<S> Layer<S> for NamespaceAuthLayer {
+    type Service = NamespaceAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NamespaceAuthService { layer: self.clone(), inner }
+    }
+}
+
+/// The service of the [`NamespaceAuthLayer`].
+#[derive(Debug, Clone)]
+pub struct NamespaceAuthService<S> {
+    layer: NamespaceAuthLayer,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for NamespaceAuthService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = NamespaceAuthFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        if self.layer.is_protected(req.method_name()) &&
+            req.extensions.get::<Authenticated>().is_none()
+        {
+            let error = ErrorObject::owned(
+                UNAUTHORIZED_ERROR_CODE,
+                format!("method {} requires authentication", req.method_name()),
+                None::<()>,
+            );
+            return NamespaceAuthFuture::Rejected {
+                response: Some(MethodResponse::error(req.id, error)),
+            }
+        }
+        NamespaceAuthFuture::Call { fut: self.inner.call(req) }
+    }
+}
+
+/// Response future of the [`NamespaceAuthService`].
+#[pin_project::pin_project(project = NamespaceAuthFutureProj)]
+#[allow(missing_debug_implementations)]
+pub enum NamespaceAuthFuture<F> {
+    /// The call is passed on.
+    Call {
+        #[pin]
+        fut: F,
+    },
+    /// The call was rejected.
+    Rejected { response: Option<MethodResponse> },
+}
+
+impl<F: Future<Output = MethodResponse>> Future for NamespaceAuthFuture<F> {
+    type Output = MethodResponse;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            NamespaceAuthFutureProj::Call { fut } => fut.poll(cx),
+            NamespaceAuthFutureProj::Rejected { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protects_namespaces() {
+        let layer = NamespaceAuthLayer::new([RethRpcModule::Debug, RethRpcModule::Admin]);
+        assert!(layer.is_protected("debug_traceTransaction"));
+        assert!(layer.is_protected("admin_addPeer"));
+        assert!(!layer.is_protected("eth_blockNumber"));
+        assert!(!layer.is_protected("debug"));
+
+        assert!(!NamespaceAuthLayer::default().is_protected("debug_traceTransaction"));
+    }
+}
@@ -144,6 +144,8 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::bandwidth_weights(client).await.unwrap();
+    AdminApiClient::set_bandwidth_weights(client, Default::default()).await.unwrap();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
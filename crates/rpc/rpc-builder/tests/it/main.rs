@@ -1,5 +1,6 @@
 mod auth;
 mod http;
+mod namespace_auth;
 mod serde;
 mod startup;
 pub mod utils;
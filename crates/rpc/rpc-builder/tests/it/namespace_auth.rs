@@ -0,0 +1,66 @@
+//! Namespace authentication tests of the http and ws servers
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::{ClientT, Error},
+    http_client::HttpClientBuilder,
+    rpc_params,
+    ws_client::WsClientBuilder,
+};
+use reth_rpc_builder::{
+    namespace_auth::UNAUTHORIZED_ERROR_CODE, EthApiBuild, RpcServerConfig, RpcServerHandle,
+    TransportRpcModuleConfig,
+};
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::RethRpcModule;
+
+/// Launches a new server with http and ws on which only the `web3` namespace requires the JWT.
+async fn launch_http_ws_protected(secret: JwtSecret) -> RpcServerHandle {
+    let modules = [RethRpcModule::Eth, RethRpcModule::Web3];
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules),
+        EthApiBuild::build,
+    );
+    server
+        .start_server(
+            RpcServerConfig::ws(Default::default())
+                .with_ws_address(test_address())
+                .with_http(Default::default())
+                .with_http_address(test_address())
+                .with_jwt_secret(Some(secret))
+                .with_jwt_modules(Some([RethRpcModule::Web3])),
+        )
+        .await
+        .unwrap()
+}
+
+/// Asserts that the protected namespace is rejected without the JWT while the other namespaces
+/// pass, and that the JWT of the authenticated client reaches the call.
+async fn test_namespace_auth<C: ClientT>(unauthenticated: &C, authenticated: &C) {
+    let err = unauthenticated
+        .request::<String, _>("web3_clientVersion", rpc_params![])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Call(err) if err.code() == UNAUTHORIZED_ERROR_CODE));
+
+    unauthenticated.request::<String, _>("eth_chainId", rpc_params![]).await.unwrap();
+    authenticated.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_namespace_auth_http() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_http_ws_protected(JwtSecret::random()).await;
+    let unauthenticated = HttpClientBuilder::default().build(handle.http_url().unwrap()).unwrap();
+    let authenticated = handle.http_client().unwrap();
+    test_namespace_auth(&unauthenticated, &authenticated).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_namespace_auth_ws() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_http_ws_protected(JwtSecret::random()).await;
+    let unauthenticated = WsClientBuilder::default().build(handle.ws_url().unwrap()).await.unwrap();
+    let authenticated = handle.ws_client().await.unwrap();
+    test_namespace_auth(&unauthenticated, &authenticated).await
+}
@@ -14,18 +14,20 @@ use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, EthereumHardfor
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_types::engine::{
     CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
-    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
-    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
-    CAPABILITIES,
+    ExecutionPayloadBodiesV2, ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3,
+    ExecutionPayloadV4, ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus,
+    TransitionConfiguration, CAPABILITIES,
 };
 use reth_rpc_types_compat::engine::payload::{
-    convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
+    convert_payload_input_v2_to_payload, convert_to_payload_body_v1, convert_to_payload_body_v2,
+};
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockReader, HeaderProvider, StateProviderFactory,
 };
-use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use std::{sync::Arc, time::Instant};
 use tokio::sync::oneshot;
-use tracing::{trace, warn};
+use tracing::{instrument, trace, warn};
 
 /// The Engine API response sender.
 pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
@@ -33,6 +35,18 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The capabilities that are supported in addition to the ones of [`CAPABILITIES`].
+const ADDITIONAL_CAPABILITIES: &[&str] = &["engine_getPayloadBodiesByRangeV2"];
+
+/// The soft limit for the size of the encoded transactions of a payload bodies by range response.
+const MAX_PAYLOAD_BODIES_RESPONSE_SIZE: usize = 32 * 1024 * 1024;
+
+/// The number of payload bodies that a range response contains regardless of its size, if the
+/// blocks are known.
+///
+/// > Clients MUST support count values of at least 32 blocks.
+const MIN_PAYLOAD_BODIES_RANGE: usize = 32;
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
 pub struct EngineApi<Provider, EngineT: EngineTypes> {
@@ -104,6 +118,17 @@ where
 
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
+    #[instrument(
+        target = "rpc::engine",
+        level = "debug",
+        name = "new_payload",
+        skip_all,
+        fields(
+            block_number = payload.block_number,
+            gas_used = payload.gas_used,
+            tx_count = payload.transactions.len(),
+        )
+    )]
     pub async fn new_payload_v1(
         &self,
         payload: ExecutionPayloadV1,
@@ -122,6 +147,17 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
+    #[instrument(
+        target = "rpc::engine",
+        level = "debug",
+        name = "new_payload",
+        skip_all,
+        fields(
+            block_number = payload.execution_payload.block_number,
+            gas_used = payload.execution_payload.gas_used,
+            tx_count = payload.execution_payload.transactions.len(),
+        )
+    )]
     pub async fn new_payload_v2(
         &self,
         payload: ExecutionPayloadInputV2,
@@ -140,6 +176,17 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+    #[instrument(
+        target = "rpc::engine",
+        level = "debug",
+        name = "new_payload",
+        skip_all,
+        fields(
+            block_number = payload.payload_inner.payload_inner.block_number,
+            gas_used = payload.payload_inner.payload_inner.gas_used,
+            tx_count = payload.payload_inner.payload_inner.transactions.len(),
+        )
+    )]
     pub async fn new_payload_v3(
         &self,
         payload: ExecutionPayloadV3,
@@ -164,6 +211,17 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/7907424db935b93c2fe6a3c0faab943adebe8557/src/engine/prague.md#engine_newpayloadv4>
+    #[instrument(
+        target = "rpc::engine",
+        level = "debug",
+        name = "new_payload",
+        skip_all,
+        fields(
+            block_number = payload.payload_inner.payload_inner.payload_inner.block_number,
+            gas_used = payload.payload_inner.payload_inner.payload_inner.gas_used,
+            tx_count = payload.payload_inner.payload_inner.payload_inner.transactions.len(),
+        )
+    )]
     pub async fn new_payload_v4(
         &self,
         payload: ExecutionPayloadV4,
@@ -419,6 +477,63 @@ where
         rx.await.map_err(|err| EngineApiError::Internal(Box::new(err)))?
     }
 
+    /// Returns the execution payload bodies by the range starting at `start`, containing `count`
+    /// blocks, including the requests of the blocks.
+    ///
+    /// Instead of reading full blocks, the bodies are assembled from the body indices,
+    /// transactions, withdrawals and requests of the blocks. The transactions are read in ranges
+    /// from the transactions static files and encoded without computing their hashes.
+    ///
+    /// The response is truncated once the encoded transactions exceed
+    /// [`MAX_PAYLOAD_BODIES_RESPONSE_SIZE`], but contains at least [`MIN_PAYLOAD_BODIES_RANGE`]
+    /// bodies.
+    ///
+    /// WARNING: See [`Self::get_payload_bodies_by_range`], the input is untrusted.
+    pub async fn get_payload_bodies_by_range_v2(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> EngineApiResult<ExecutionPayloadBodiesV2> {
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            if count > MAX_PAYLOAD_BODIES_LIMIT {
+                tx.send(Err(EngineApiError::PayloadRequestTooLarge { len: count })).ok();
+                return;
+            }
+
+            if start == 0 || count == 0 {
+                tx.send(Err(EngineApiError::InvalidBodiesRange { start, count })).ok();
+                return;
+            }
+            inner.metrics.payload_bodies.requested_bodies.record(count as f64);
+
+            // -1 so range is inclusive
+            let mut end = start.saturating_add(count - 1);
+
+            // > Client software MUST NOT return trailing null values if the request extends past the current latest known block.
+            // truncate the end if it's greater than the last block
+            if let Ok(best_block) = inner.provider.best_block_number() {
+                if end > best_block {
+                    end = best_block;
+                }
+            }
+
+            let result = match payload_bodies_by_range_v2(&inner.provider, start, end) {
+                Ok((bodies, size)) => {
+                    inner.metrics.payload_bodies.returned_bodies.record(bodies.len() as f64);
+                    inner.metrics.payload_bodies.response_size.record(size as f64);
+                    Ok(bodies)
+                }
+                Err(err) => Err(EngineApiError::Internal(Box::new(err))),
+            };
+            tx.send(result).ok();
+        }));
+
+        rx.await.map_err(|err| EngineApiError::Internal(Box::new(err)))?
+    }
+
     /// Called to retrieve execution payload bodies by hashes.
     pub fn get_payload_bodies_by_hash(
         &self,
@@ -793,6 +908,24 @@ where
         Ok(res?)
     }
 
+    /// Handler for `engine_getPayloadBodiesByRangeV2`
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/prague.md#engine_getpayloadbodiesbyrangev2>
+    ///
+    /// Note: If a block is pre shanghai, `withdrawals` field will be `null`, if it's pre prague,
+    /// `depositRequests` and `withdrawalRequests` will be `null`.
+    async fn get_payload_bodies_by_range_v2(
+        &self,
+        start: U64,
+        count: U64,
+    ) -> RpcResult<ExecutionPayloadBodiesV2> {
+        trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByRangeV2");
+        let start_time = Instant::now();
+        let res = Self::get_payload_bodies_by_range_v2(self, start.to(), count.to()).await;
+        self.inner.metrics.latency.get_payload_bodies_by_range_v2.record(start_time.elapsed());
+        Ok(res?)
+    }
+
     /// Handler for `engine_exchangeTransitionConfigurationV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_exchangeTransitionConfigurationV1>
     async fn exchange_transition_configuration(
@@ -822,8 +955,42 @@ where
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
     async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
-        Ok(CAPABILITIES.iter().cloned().map(str::to_owned).collect())
+        Ok(CAPABILITIES.iter().chain(ADDITIONAL_CAPABILITIES).copied().map(str::to_owned).collect())
+    }
+}
+
+/// Reads the execution payload bodies of the blocks in the inclusive range, and returns them
+/// together with the size of their encoded transactions.
+///
+/// Unknown blocks are `None`. Reading stops early once the size exceeds
+/// [`MAX_PAYLOAD_BODIES_RESPONSE_SIZE`] and at least [`MIN_PAYLOAD_BODIES_RANGE`] bodies are read.
+fn payload_bodies_by_range_v2<Provider: BlockReader>(
+    provider: &Provider,
+    start: BlockNumber,
+    end: BlockNumber,
+) -> ProviderResult<(ExecutionPayloadBodiesV2, usize)> {
+    let mut bodies = Vec::with_capacity(end.saturating_sub(start).saturating_add(1) as usize);
+    let mut size = 0;
+    for num in start..=end {
+        let Some((header, indices)) =
+            provider.header_by_number(num)?.zip(provider.block_body_indices(num)?)
+        else {
+            bodies.push(None);
+            continue
+        };
+
+        let transactions = provider.transactions_by_tx_range(indices.tx_num_range())?;
+        let withdrawals = provider.withdrawals_by_block(num.into(), header.timestamp)?;
+        let requests = provider.requests_by_block(num.into(), header.timestamp)?;
+        let body = convert_to_payload_body_v2(transactions, withdrawals, requests);
+
+        size += body.transactions.iter().map(|tx| tx.len()).sum::<usize>();
+        bodies.push(Some(body));
+        if size > MAX_PAYLOAD_BODIES_RESPONSE_SIZE && bodies.len() >= MIN_PAYLOAD_BODIES_RANGE {
+            break
+        }
     }
+    Ok((bodies, size))
 }
 
 impl<Provider, EngineT> std::fmt::Debug for EngineApi<Provider, EngineT>
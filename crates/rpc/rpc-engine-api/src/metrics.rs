@@ -14,6 +14,8 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) fcu_response: ForkchoiceUpdatedResponseMetrics,
     /// Engine API newPayload response type metrics
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
+    /// Engine API payload bodies by range metrics
+    pub(crate) payload_bodies: PayloadBodiesMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -44,12 +46,27 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_v4: Histogram,
     /// Latency for `engine_getPayloadBodiesByRangeV1`
     pub(crate) get_payload_bodies_by_range_v1: Histogram,
+    /// Latency for `engine_getPayloadBodiesByRangeV2`
+    pub(crate) get_payload_bodies_by_range_v2: Histogram,
     /// Latency for `engine_getPayloadBodiesByHashV1`
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
 }
 
+/// Metrics for engine API `engine_getPayloadBodiesByRangeV2` requests.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct PayloadBodiesMetrics {
+    /// The number of payload bodies requested.
+    pub(crate) requested_bodies: Histogram,
+    /// The number of payload bodies returned, which is lower than the requested number if the
+    /// range extends past the latest block or the response is truncated.
+    pub(crate) returned_bodies: Histogram,
+    /// The size of the encoded transactions of the returned payload bodies, in bytes.
+    pub(crate) response_size: Histogram,
+}
+
 /// Metrics for engine API forkchoiceUpdated responses.
 #[derive(Metrics)]
 #[metrics(scope = "engine.rpc")]
@@ -2,15 +2,29 @@
 
 use std::sync::Arc;
 
+use alloy_rlp::Encodable;
 use futures::Future;
-use reth_primitives::{BlockId, Receipt, SealedBlock, SealedBlockWithSenders, TransactionMeta};
-use reth_provider::{BlockIdReader, BlockReader, BlockReaderIdExt, HeaderProvider};
+use reth_primitives::{
+    BlockId, Receipt, SealedBlock, SealedBlockWithSenders, TransactionSignedNoHash, B256,
+};
+use reth_provider::{BlockIdReader, BlockReader, BlockReaderIdExt, HeaderProvider, ProviderError};
 use reth_rpc_eth_types::{EthApiError, EthResult, EthStateCache, ReceiptBuilder};
-use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock};
-use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
+use reth_rpc_types::{AnyTransactionReceipt, Block, BlockTransactions, Header, Index, RichBlock};
+use reth_rpc_types_compat::{
+    block::{from_block, from_block_parts, uncle_block_from_header},
+    transaction::from_recovered_with_block_context,
+};
 
 use super::{LoadPendingBlock, LoadReceipt, SpawnBlocking};
 
+/// Minimum number of transactions of a block to assemble its response while its transactions are
+/// read, see [`EthBlocks::streamed_rpc_block`].
+pub const STREAMED_BLOCK_MIN_TRANSACTIONS: u64 = 1_000;
+
+/// Number of transactions that are read at once when assembling a block response while its
+/// transactions are read.
+const STREAMED_BLOCK_CHUNK_SIZE: u64 = 256;
+
 /// Block related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
 /// `eth_` namespace.
 pub trait EthBlocks: LoadBlock {
@@ -34,6 +48,9 @@ pub trait EthBlocks: LoadBlock {
     ///
     /// If `full` is true, the block object will contain all transaction objects, otherwise it will
     /// only contain the transaction hashes.
+    ///
+    /// Recent blocks are served from the [`RpcBlockCache`](reth_rpc_eth_types::RpcBlockCache).
+    /// Large blocks that are not cached are assembled with [`EthBlocks::streamed_rpc_block`].
     fn rpc_block(
         &self,
         block_id: BlockId,
@@ -43,6 +60,23 @@ pub trait EthBlocks: LoadBlock {
         Self: LoadPendingBlock + SpawnBlocking,
     {
         async move {
+            if !block_id.is_pending() {
+                let Some(block_hash) = LoadBlock::provider(self).block_hash_for_id(block_id)?
+                else {
+                    return Ok(None)
+                };
+
+                if let Some(block) = self.cache().rpc_blocks().get(&block_hash, full.into()) {
+                    return Ok(Some(block.into()))
+                }
+
+                if full {
+                    if let Some(block) = self.streamed_rpc_block(block_hash).await? {
+                        return Ok(Some(block.into()))
+                    }
+                }
+            }
+
             let block = match self.block_with_senders(block_id).await? {
                 Some(block) => block,
                 None => return Ok(None),
@@ -57,6 +91,72 @@ pub trait EthBlocks: LoadBlock {
         }
     }
 
+    /// Assembles the response of a block with its full transaction objects while its transactions
+    /// are read in chunks, without loading the whole block into the
+    /// [`EthStateCache`](reth_rpc_eth_types::EthStateCache).
+    ///
+    /// This only applies to blocks with at least [`STREAMED_BLOCK_MIN_TRANSACTIONS`] transactions,
+    /// which are mostly historical blocks read from static files, since recent blocks are served
+    /// from the cache. Returns `None` for smaller blocks and blocks that are not in the database.
+    fn streamed_rpc_block(
+        &self,
+        block_hash: B256,
+    ) -> impl Future<Output = EthResult<Option<Block>>> + Send
+    where
+        Self: LoadPendingBlock + SpawnBlocking,
+    {
+        self.spawn_blocking_io(move |this| {
+            let provider = LoadBlock::provider(&this);
+            let Some(number) = provider.block_number(block_hash)? else { return Ok(None) };
+            let Some(indices) = provider.block_body_indices(number)? else { return Ok(None) };
+            if indices.tx_count < STREAMED_BLOCK_MIN_TRANSACTIONS {
+                return Ok(None)
+            }
+            let Some(header) = provider.sealed_header(number)? else { return Ok(None) };
+            let total_difficulty =
+                provider.header_td_by_number(number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+            let mut transactions = Vec::with_capacity(indices.tx_count as usize);
+            let mut transactions_length = 0;
+            for start in indices.tx_num_range().step_by(STREAMED_BLOCK_CHUNK_SIZE as usize) {
+                let range = start..(start + STREAMED_BLOCK_CHUNK_SIZE).min(indices.next_tx_num());
+                let chunk = provider.transactions_by_tx_range(range.clone())?;
+                let mut senders = provider.senders_by_tx_range(range)?;
+                if senders.len() != chunk.len() {
+                    senders = TransactionSignedNoHash::recover_signers(&chunk, chunk.len())
+                        .ok_or(ProviderError::SenderRecoveryError)?;
+                }
+
+                for (tx, sender) in chunk.into_iter().zip(senders) {
+                    let tx = tx.with_hash();
+                    transactions_length += tx.length();
+                    let index = transactions.len();
+                    transactions.push(from_recovered_with_block_context(
+                        tx.with_signer(sender),
+                        block_hash,
+                        number,
+                        header.base_fee_per_gas,
+                        index,
+                    ));
+                }
+            }
+
+            let ommers = provider.ommers(number.into())?.unwrap_or_default();
+            let withdrawals = provider.withdrawals_by_block(number.into(), header.timestamp)?;
+            let requests = provider.requests_by_block(number.into(), header.timestamp)?;
+
+            Ok(Some(from_block_parts(
+                header,
+                total_difficulty,
+                BlockTransactions::Full(transactions),
+                transactions_length,
+                ommers,
+                withdrawals,
+                requests,
+            )))
+        })
+    }
+
     /// Returns the number transactions in the given block.
     ///
     /// Returns `None` if the block does not exist
@@ -91,34 +191,11 @@ pub trait EthBlocks: LoadBlock {
     {
         async move {
             if let Some((block, receipts)) = self.load_block_and_receipts(block_id).await? {
-                let block_number = block.number;
-                let base_fee = block.base_fee_per_gas;
-                let block_hash = block.hash();
-                let excess_blob_gas = block.excess_blob_gas;
-                let timestamp = block.timestamp;
-                let block = block.unseal();
-
-                let receipts = block
-                    .body
+                let receipts = ReceiptBuilder::block_receipts(&block, &receipts)?
                     .into_iter()
-                    .zip(receipts.iter())
-                    .enumerate()
-                    .map(|(idx, (tx, receipt))| {
-                        let meta = TransactionMeta {
-                            tx_hash: tx.hash,
-                            index: idx as u64,
-                            block_hash,
-                            block_number,
-                            base_fee,
-                            excess_blob_gas,
-                            timestamp,
-                        };
-
-                        ReceiptBuilder::new(&tx, meta, receipt, &receipts)
-                            .map(|builder| builder.build())
-                    })
-                    .collect::<EthResult<Vec<_>>>();
-                return receipts.map(Some)
+                    .map(ReceiptBuilder::build)
+                    .collect();
+                return Ok(Some(receipts))
             }
 
             Ok(None)
@@ -2,7 +2,9 @@
 
 use futures::Future;
 use reth_primitives::U256;
-use reth_provider::{BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider};
+use reth_provider::{
+    BlockHashReader, BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider,
+};
 use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, EthApiError, EthResult, EthStateCache,
     FeeHistoryCache, FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
@@ -111,8 +113,18 @@ pub trait EthFees: LoadFee {
 
             let mut rewards: Vec<Vec<u128>> = Vec::new();
 
-            // Check if the requested range is within the cache bounds
-            let fee_entries = self.fee_history_cache().get_history(start_block, end_block).await;
+            let chain_spec = LoadFee::provider(self).chain_spec();
+
+            // Collect the requested range from the cache, following the parent hashes from the
+            // canonical end block so that all entries belong to the same fork
+            let fee_entries = match LoadFee::provider(self).block_hash(end_block)? {
+                Some(end_block_hash) => {
+                    self.fee_history_cache()
+                        .get_history(start_block, end_block_hash, &chain_spec)
+                        .await
+                }
+                None => None,
+            };
 
             if let Some(fee_entries) = fee_entries {
                 if fee_entries.len() != block_count as usize {
@@ -137,11 +149,10 @@ pub trait EthFees: LoadFee {
 
                 // Also need to include the `base_fee_per_gas` and `base_fee_per_blob_gas` for the
                 // next block
-                base_fee_per_gas
-                    .push(last_entry.next_block_base_fee(&LoadFee::provider(self).chain_spec())
-                        as u128);
+                base_fee_per_gas.push(last_entry.next_block_base_fee(&chain_spec) as u128);
 
-                base_fee_per_blob_gas.push(last_entry.next_block_blob_fee().unwrap_or_default());
+                base_fee_per_blob_gas
+                    .push(last_entry.next_block_blob_fee(&chain_spec).unwrap_or_default());
             } else {
             // read the requested header range
             let headers = LoadFee::provider(self).sealed_headers_range(start_block..=end_block)?;
@@ -150,12 +161,13 @@ pub trait EthFees: LoadFee {
             }
 
             for header in &headers {
+                let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp);
                 base_fee_per_gas.push(header.base_fee_per_gas.unwrap_or_default() as u128);
                 gas_used_ratio.push(header.gas_used as f64 / header.gas_limit as f64);
-                base_fee_per_blob_gas.push(header.blob_fee().unwrap_or_default());
+                base_fee_per_blob_gas.push(header.blob_fee_with_params(blob_params).unwrap_or_default());
                 blob_gas_used_ratio.push(
                     header.blob_gas_used.unwrap_or_default() as f64 /
-                        reth_primitives::constants::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                        blob_params.max_blob_gas_per_block() as f64,
                 );
 
                 // Percentiles were specified, so we need to collect reward percentile ino
@@ -184,7 +196,7 @@ pub trait EthFees: LoadFee {
             // The unwrap is safe since we checked earlier that we got at least 1 header.
             let last_header = headers.last().expect("is present");
             base_fee_per_gas.push(
-                LoadFee::provider(self).chain_spec().base_fee_params_at_timestamp(last_header.timestamp).next_block_base_fee(
+                chain_spec.base_fee_params_at_timestamp(last_header.timestamp).next_block_base_fee(
                     last_header.gas_used as u128,
                     last_header.gas_limit as u128,
                     last_header.base_fee_per_gas.unwrap_or_default() as u128,
@@ -193,7 +205,7 @@ pub trait EthFees: LoadFee {
             // Same goes for the `base_fee_per_blob_gas`:
             // > "[..] includes the next block after the newest of the returned range, because this value can be derived from the newest block.
             base_fee_per_blob_gas
-                .push(last_header.next_block_blob_fee().unwrap_or_default());
+                .push(last_header.next_block_blob_fee_with_params(chain_spec.blob_params_at_timestamp(last_header.timestamp)).unwrap_or_default());
         };
 
             Ok(FeeHistory {
@@ -330,7 +342,11 @@ pub trait LoadFee: LoadBlock {
         async move {
             self.block(BlockNumberOrTag::Latest.into())
                 .await?
-                .and_then(|h: reth_primitives::SealedBlock| h.next_block_blob_fee())
+                .and_then(|h: reth_primitives::SealedBlock| {
+                    let blob_params =
+                        LoadFee::provider(self).chain_spec().blob_params_at_timestamp(h.timestamp);
+                    h.next_block_blob_fee_with_params(blob_params)
+                })
                 .ok_or(EthApiError::ExcessBlobGasNotSet)
                 .map(U256::from)
         }
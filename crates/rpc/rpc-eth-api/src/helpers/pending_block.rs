@@ -8,7 +8,7 @@ use reth_chainspec::EthereumHardforks;
 use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvm, ConfigureEvmEnv};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    constants::{eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_ROOT_HASH},
+    constants::{BEACON_NONCE, EMPTY_ROOT_HASH},
     proofs::calculate_transaction_root,
     revm_primitives::{
         BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EVMError, Env, ExecutionResult, InvalidTransaction,
@@ -26,8 +26,9 @@ use reth_revm::{
 };
 use reth_rpc_eth_types::{
     pending_block::pre_block_blockhashes_update, EthApiError, EthResult, PendingBlock,
-    PendingBlockEnv, PendingBlockEnvOrigin,
+    PendingBlockEnv, PendingBlockEnvOrigin, PendingBlockStats,
 };
+use reth_rpc_types::ExclusionReason;
 use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
 use revm::{db::states::bundle_state::BundleRetention, DatabaseCommit, State};
 use tokio::sync::Mutex;
@@ -89,7 +90,9 @@ pub trait LoadPendingBlock {
             );
 
             // update excess blob gas consumed above target
-            latest_header.excess_blob_gas = latest_header.next_block_excess_blob_gas();
+            latest_header.excess_blob_gas = latest_header.next_block_excess_blob_gas_with_params(
+                chain_spec.blob_params_at_timestamp(latest_header.timestamp),
+            );
 
             // we're reusing the same block hash because we need this to lookup the block's state
             let latest = SealedHeader::new(latest_header, block_hash);
@@ -116,13 +119,28 @@ pub trait LoadPendingBlock {
     fn local_pending_block(
         &self,
     ) -> impl Future<Output = EthResult<Option<SealedBlockWithSenders>>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move { Ok(self.local_pending_block_with_stats().await?.map(|(block, _)| block)) }
+    }
+
+    /// Returns the locally built pending block with the [`PendingBlockStats`] of its build.
+    ///
+    /// The stats are empty if the pending block was received from the CL.
+    fn local_pending_block_with_stats(
+        &self,
+    ) -> impl Future<Output = EthResult<Option<(SealedBlockWithSenders, PendingBlockStats)>>> + Send
     where
         Self: SpawnBlocking,
     {
         async move {
             let pending = self.pending_block_env_and_cfg()?;
             if pending.origin.is_actual_pending() {
-                return Ok(pending.origin.into_actual_pending())
+                return Ok(pending
+                    .origin
+                    .into_actual_pending()
+                    .map(|block| (block, PendingBlockStats::default())))
             }
 
             let mut lock = self.pending_block().lock().await;
@@ -136,19 +154,19 @@ pub trait LoadPendingBlock {
                     pending.origin.header().hash() == pending_block.block.parent_hash &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some(pending_block.block.clone()))
+                    return Ok(Some((pending_block.block.clone(), pending_block.stats.clone())))
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let pending_block = match self
+            let (pending_block, stats) = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
-                    this.build_block(pending)
+                    this.build_block_with_stats(pending)
                 })
                 .await
             {
-                Ok(block) => block,
+                Ok(built) => built,
                 Err(err) => {
                     debug!(target: "rpc", "Failed to build pending block: {:?}", err);
                     return Ok(None)
@@ -156,9 +174,13 @@ pub trait LoadPendingBlock {
             };
 
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(pending_block.clone(), now + Duration::from_secs(1)));
+            *lock = Some(PendingBlock::new(
+                pending_block.clone(),
+                stats.clone(),
+                now + Duration::from_secs(1),
+            ));
 
-            Ok(Some(pending_block))
+            Ok(Some((pending_block, stats)))
         }
     }
 
@@ -200,6 +222,17 @@ pub trait LoadPendingBlock {
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
     fn build_block(&self, env: PendingBlockEnv) -> EthResult<SealedBlockWithSenders> {
+        self.build_block_with_stats(env).map(|(block, _)| block)
+    }
+
+    /// Builds a pending block like [`LoadPendingBlock::build_block`], and records the
+    /// transactions that are excluded from the block and the included transactions that revert.
+    ///
+    /// Private transactions are never recorded.
+    fn build_block_with_stats(
+        &self,
+        env: PendingBlockEnv,
+    ) -> EthResult<(SealedBlockWithSenders, PendingBlockStats)> {
         let PendingBlockEnv { cfg, block_env, origin } = env;
 
         let parent_hash = origin.build_target_hash();
@@ -229,6 +262,9 @@ pub trait LoadPendingBlock {
         };
 
         let chain_spec = self.provider().chain_spec();
+        let max_blob_gas_per_block = chain_spec
+            .blob_params_at_timestamp(block_env.timestamp.to::<u64>())
+            .max_blob_gas_per_block();
 
         let parent_beacon_block_root = if origin.is_actual_pending() {
             // apply eip-4788 pre block contract call if we got the block from the CL with the real
@@ -257,6 +293,7 @@ pub trait LoadPendingBlock {
         )?;
 
         let mut receipts = Vec::new();
+        let mut stats = PendingBlockStats::default();
 
         while let Some(pool_tx) = best_txs.next() {
             // ensure we still have capacity for this transaction
@@ -265,6 +302,7 @@ pub trait LoadPendingBlock {
                 // which also removes all dependent transaction from the iterator before we can
                 // continue
                 best_txs.mark_invalid(&pool_tx);
+                stats.exclude(&pool_tx, ExclusionReason::GasLimit);
                 continue
             }
 
@@ -283,12 +321,13 @@ pub trait LoadPendingBlock {
             // the EIP-4844 can still fit in the block
             if let Some(blob_tx) = tx.transaction.as_eip4844() {
                 let tx_blob_gas = blob_tx.blob_gas();
-                if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                     // we can't fit this _blob_ transaction into the block, so we mark it as
                     // invalid, which removes its dependent transactions from
                     // the iterator. This is similar to the gas limit condition
                     // for regular transactions above.
                     best_txs.mark_invalid(&pool_tx);
+                    stats.exclude(&pool_tx, ExclusionReason::BlobGasLimit);
                     continue
                 }
             }
@@ -309,10 +348,15 @@ pub trait LoadPendingBlock {
                         EVMError::Transaction(err) => {
                             if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
                                 // if the nonce is too low, we can skip this transaction
+                                stats.exclude(&pool_tx, ExclusionReason::NonceTooLow);
                             } else {
                                 // if the transaction is invalid, we can skip it and all of its
                                 // descendants
                                 best_txs.mark_invalid(&pool_tx);
+                                stats.exclude(
+                                    &pool_tx,
+                                    ExclusionReason::Invalid { error: err.to_string() },
+                                );
                             }
                             continue
                         }
@@ -334,11 +378,15 @@ pub trait LoadPendingBlock {
                 sum_blob_gas_used += tx_blob_gas;
 
                 // if we've reached the max data gas per block, we can skip blob txs entirely
-                if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used == max_blob_gas_per_block {
                     best_txs.skip_blobs();
                 }
             }
 
+            if !result.is_success() {
+                stats.reverted.push(tx.hash());
+            }
+
             let gas_used = result.gas_used();
 
             // add gas used by the transaction to cumulative gas used, before creating the receipt
@@ -424,6 +472,6 @@ pub trait LoadPendingBlock {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, stats))
     }
 }
@@ -22,6 +22,12 @@ pub trait EthSigner: Send + Sync + DynClone {
         self.accounts().contains(addr)
     }
 
+    /// Returns `true` if this signer impersonates the address, so that the transactions it signs
+    /// for the address don't recover to it.
+    fn impersonates(&self, _addr: &Address) -> bool {
+        false
+    }
+
     /// Returns the signature
     async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature>;
 
@@ -7,13 +7,15 @@ use alloy_dyn_abi::TypedData;
 use futures::Future;
 use reth_primitives::{
     Address, BlockId, Bytes, FromRecoveredPooledTransaction, IntoRecoveredTransaction, Receipt,
-    SealedBlockWithSenders, TransactionMeta, TransactionSigned, TxHash, TxKind, B256, U256,
+    SealedBlockWithSenders, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
+    TxHash, TxKind, B256, U256,
 };
 use reth_provider::{BlockReaderIdExt, ReceiptProvider, TransactionsProvider};
 use reth_rpc_eth_types::{
     utils::recover_raw_transaction, EthApiError, EthResult, EthStateCache, SignError,
     TransactionSource,
 };
+use reth_rpc_server_types::TxForwardMode;
 use reth_rpc_types::{
     transaction::{
         EIP1559TransactionRequest, EIP2930TransactionRequest, EIP4844TransactionRequest,
@@ -109,9 +111,10 @@ pub trait EthTransactions: LoadTransaction {
             }
 
             self.spawn_blocking_io(move |ref this| {
-                Ok(LoadTransaction::provider(this)
-                    .transaction_by_hash(hash)?
-                    .map(|tx| tx.envelope_encoded()))
+                match LoadTransaction::provider(this).transaction_by_hash(hash)? {
+                    Some(tx) => Ok(Some(tx.envelope_encoded())),
+                    None => not_found_in_index(LoadTransaction::provider(this), hash),
+                }
             })
             .await
         }
@@ -166,7 +169,7 @@ pub trait EthTransactions: LoadTransaction {
             let (tx, meta) =
                 match LoadTransaction::provider(&this).transaction_by_hash_with_meta(hash)? {
                     Some((tx, meta)) => (tx, meta),
-                    None => return Ok(None),
+                    None => return not_found_in_index(LoadTransaction::provider(&this), hash),
                 };
 
             let receipt = match EthTransactions::provider(&this).receipt_by_hash(hash)? {
@@ -236,14 +239,20 @@ pub trait EthTransactions: LoadTransaction {
     /// Returns the hash of the transaction.
     fn send_raw_transaction(&self, tx: Bytes) -> impl Future<Output = EthResult<B256>> + Send {
         async move {
+            let recovered = recover_raw_transaction(tx.clone())?;
+
             // On optimism, transactions are forwarded directly to the sequencer to be included in
-            // blocks that it builds.
+            // blocks that it builds. Other nodes can be configured to forward transactions to an
+            // upstream endpoint as well.
             if let Some(client) = self.raw_tx_forwarder().as_ref() {
-                tracing::debug!( target: "rpc::eth",  "forwarding raw transaction to");
+                tracing::debug!(target: "rpc::eth", hash=%recovered.hash(), "forwarding raw transaction");
                 client.forward_raw_transaction(&tx).await?;
+
+                if client.mode().is_forward_only() {
+                    return Ok(*recovered.hash())
+                }
             }
 
-            let recovered = recover_raw_transaction(tx)?;
             let pool_transaction =
                 <Self::Pool as TransactionPool>::Transaction::from_recovered_pooled_transaction(
                     recovered,
@@ -448,8 +457,12 @@ pub trait EthTransactions: LoadTransaction {
 
             let signed_tx = self.sign_request(&from, transaction)?;
 
-            let recovered =
-                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+            // transactions signed on behalf of an impersonated account are sent by that account
+            let recovered = if self.find_signer(&from)?.impersonates(&from) {
+                TransactionSignedEcRecovered::from_signed_transaction(signed_tx, from)
+            } else {
+                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?
+            };
 
             let pool_transaction = match recovered.try_into() {
                 Ok(converted) => <<Self as LoadTransaction>::Pool as TransactionPool>::Transaction::from_recovered_pooled_transaction(converted),
@@ -573,6 +586,10 @@ pub trait LoadTransaction: SpawnBlocking {
                     self.pool().get(&hash).map(|tx| tx.transaction.to_recovered_transaction())
                 {
                     resp = Some(TransactionSource::Pool(tx));
+                } else {
+                    return self
+                        .spawn_blocking_io(move |this| not_found_in_index(this.provider(), hash))
+                        .await
                 }
             }
 
@@ -640,6 +657,19 @@ pub trait LoadTransaction: SpawnBlocking {
     }
 }
 
+/// Returns `Ok(None)` for a transaction that was not found by hash, or
+/// [`EthApiError::TransactionIndexPruned`] if the transaction is in a block that was pruned from
+/// the transaction hash index.
+fn not_found_in_index<T>(
+    provider: impl TransactionsProvider,
+    hash: TxHash,
+) -> EthResult<Option<T>> {
+    match provider.transaction_index_pruned_to(hash)? {
+        Some(block) => Err(EthApiError::TransactionIndexPruned(block)),
+        None => Ok(None),
+    }
+}
+
 /// A trait that allows for forwarding raw transactions.
 ///
 /// For example to a sequencer.
@@ -647,6 +677,13 @@ pub trait LoadTransaction: SpawnBlocking {
 pub trait RawTransactionForwarder: fmt::Debug + Send + Sync + 'static {
     /// Forwards raw transaction bytes for `eth_sendRawTransaction`
     async fn forward_raw_transaction(&self, raw: &[u8]) -> EthResult<()>;
+
+    /// Returns how forwarded transactions are handled locally.
+    ///
+    /// By default, forwarded transactions are also submitted to the local pool.
+    fn mode(&self) -> TxForwardMode {
+        TxForwardMode::Mirror
+    }
 }
 
 /// Configure server's forwarder for `eth_sendRawTransaction`, at runtime.
@@ -7,6 +7,9 @@ use reth_rpc_types::pubsub::{Params, SubscriptionKind};
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi {
     /// Create an ethereum subscription for the given params
+    ///
+    /// A `logs` subscription whose filter has a `fromBlock` first emits the matching logs of the
+    /// canonical blocks from that block to the current head, and then the logs of new blocks.
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RPC_BLOCK_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -23,6 +23,11 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_envs: u32,
+    /// Max number of assembled `eth_getBlockBy*` responses in cache, 0 disables the cache.
+    ///
+    /// Default is 128.
+    #[serde(default = "default_max_rpc_blocks")]
+    pub max_rpc_blocks: u32,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
@@ -35,7 +40,12 @@ impl Default for EthStateCacheConfig {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_rpc_blocks: DEFAULT_RPC_BLOCK_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
 }
+
+const fn default_max_rpc_blocks() -> u32 {
+    DEFAULT_RPC_BLOCK_CACHE_MAX_LEN
+}
@@ -11,6 +11,8 @@ use reth_primitives::{
 use reth_provider::{
     BlockReader, CanonStateNotification, EvmEnvProvider, StateProviderFactory, TransactionVariant,
 };
+use reth_rpc_types::BlockTransactionsKind;
+use reth_rpc_types_compat::block::from_block;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
 use schnellru::{ByLength, Limiter};
@@ -25,13 +27,17 @@ use tokio::sync::{
     oneshot, Semaphore,
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::debug;
 
 use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use rpc_block::RpcBlockCache;
 
 pub mod config;
 pub mod db;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod rpc_block;
+pub mod trace;
 
 /// The type that can send the response to a requested [Block]
 type BlockTransactionsResponseSender =
@@ -66,10 +72,13 @@ type EnvLruCache<L> =
 #[derive(Debug, Clone)]
 pub struct EthStateCache {
     to_service: UnboundedSender<CacheAction>,
+    /// Assembled `eth_getBlockBy*` responses of recent blocks.
+    rpc_blocks: RpcBlockCache,
 }
 
 impl EthStateCache {
     /// Creates and returns both [`EthStateCache`] frontend and the memory bound service.
+    #[allow(clippy::too_many_arguments)]
     fn create<Provider, Tasks, EvmConfig>(
         provider: Provider,
         action_task_spawner: Tasks,
@@ -77,21 +86,24 @@ impl EthStateCache {
         max_blocks: u32,
         max_receipts: u32,
         max_envs: u32,
+        max_rpc_blocks: u32,
         max_concurrent_db_operations: usize,
     ) -> (Self, EthStateCacheService<Provider, Tasks, EvmConfig>) {
         let (to_service, rx) = unbounded_channel();
+        let rpc_blocks = RpcBlockCache::new(max_rpc_blocks);
         let service = EthStateCacheService {
             provider,
             full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
             receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
             evm_env_cache: EnvLruCache::new(max_envs, "evm_env"),
+            rpc_blocks: rpc_blocks.clone(),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
             evm_config,
         };
-        let cache = Self { to_service };
+        let cache = Self { to_service, rpc_blocks };
         (cache, service)
     }
 
@@ -126,8 +138,13 @@ impl EthStateCache {
         Tasks: TaskSpawner + Clone + 'static,
         EvmConfig: ConfigureEvm,
     {
-        let EthStateCacheConfig { max_blocks, max_receipts, max_envs, max_concurrent_db_requests } =
-            config;
+        let EthStateCacheConfig {
+            max_blocks,
+            max_receipts,
+            max_envs,
+            max_rpc_blocks,
+            max_concurrent_db_requests,
+        } = config;
         let (this, service) = Self::create(
             provider,
             executor.clone(),
@@ -135,12 +152,20 @@ impl EthStateCache {
             max_blocks,
             max_receipts,
             max_envs,
+            max_rpc_blocks,
             max_concurrent_db_requests,
         );
         executor.spawn_critical("eth state cache", Box::pin(service));
         this
     }
 
+    /// Returns the cache of assembled `eth_getBlockBy*` responses of recent blocks.
+    ///
+    /// The blocks of the canonical chain are assembled and inserted as they are committed.
+    pub const fn rpc_blocks(&self) -> &RpcBlockCache {
+        &self.rpc_blocks
+    }
+
     /// Requests the [Block] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -299,6 +324,8 @@ pub(crate) struct EthStateCacheService<
     receipts_cache: ReceiptsLruCache<LimitReceipts>,
     /// The LRU cache for revm environments
     evm_env_cache: EnvLruCache<LimitEnvs>,
+    /// The cache for assembled `eth_getBlockBy*` responses, shared with [`EthStateCache`].
+    rpc_blocks: RpcBlockCache,
     /// Sender half of the action channel.
     action_tx: UnboundedSender<CacheAction>,
     /// Receiver half of the action channel.
@@ -391,6 +418,43 @@ where
         }
     }
 
+    /// Assembles the responses of newly committed canonical blocks on a blocking task and inserts
+    /// them into the [`RpcBlockCache`].
+    ///
+    /// The blocks can be reorged and removed from the cache before they're assembled, so a block is
+    /// only inserted if it's still canonical.
+    fn assemble_rpc_blocks(&self, blocks: Vec<SealedBlockWithSenders>) {
+        if !self.rpc_blocks.is_enabled() || blocks.is_empty() {
+            return
+        }
+
+        let provider = self.provider.clone();
+        let rpc_blocks = self.rpc_blocks.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        self.action_task_spawner.spawn_blocking(Box::pin(async move {
+            // Acquire permit
+            let _permit = rate_limiter.acquire().await;
+            for block in blocks {
+                let block_hash = block.hash();
+                let block_number = block.number;
+                let Ok(Some(total_difficulty)) = provider.header_td(&block_hash) else { continue };
+                match from_block(
+                    block.unseal(),
+                    total_difficulty,
+                    BlockTransactionsKind::Full,
+                    Some(block_hash),
+                ) {
+                    Ok(block) => rpc_blocks.insert_if(block, || {
+                        provider.block_hash(block_number).is_ok_and(|hash| hash == Some(block_hash))
+                    }),
+                    Err(err) => {
+                        debug!(target: "rpc::eth_cache", %block_hash, %err, "Failed to assemble block")
+                    }
+                }
+            }
+        }));
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
@@ -561,6 +625,7 @@ where
                             }
                         }
                         CacheAction::CacheNewCanonicalChain { chain_change } => {
+                            this.assemble_rpc_blocks(chain_change.blocks.clone());
                             for block in chain_change.blocks {
                                 this.on_new_block(block.hash(), Ok(Some(block.unseal())));
                             }
@@ -575,6 +640,9 @@ where
                             }
                         }
                         CacheAction::RemoveReorgedChain { chain_change } => {
+                            this.rpc_blocks.remove_blocks(
+                                chain_change.blocks.iter().map(|block| block.hash()),
+                            );
                             for block in chain_change.blocks {
                                 this.on_reorg_block(block.hash(), Ok(Some(block.unseal())));
                             }
@@ -0,0 +1,177 @@
+//! Cache for assembled `eth_getBlockBy*` responses.
+
+use reth_primitives::B256;
+use reth_rpc_types::{Block, BlockTransactions, BlockTransactionsKind};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use super::metrics::CacheMetrics;
+
+/// A bounded cache of assembled RPC responses of recent blocks, so that repeated
+/// `eth_getBlockBy*` calls don't assemble the same blocks again.
+///
+/// Blocks are cached with their full transaction objects and all computed fields, the response
+/// with transaction hashes only is derived from them. The blocks of the canonical chain are
+/// inserted as they are committed, and reorged blocks are removed.
+#[derive(Clone, Default)]
+pub struct RpcBlockCache {
+    /// The cache, `None` if caching is disabled.
+    inner: Option<Arc<Mutex<RpcBlockCacheInner>>>,
+}
+
+impl RpcBlockCache {
+    /// Creates a new cache for the given number of blocks.
+    ///
+    /// A length of zero disables the cache.
+    pub fn new(max_blocks: u32) -> Self {
+        if max_blocks == 0 {
+            return Self::default()
+        }
+        let inner = RpcBlockCacheInner {
+            blocks: LruMap::new(ByLength::new(max_blocks)),
+            metrics: CacheMetrics::new_with_labels(&[("cache", "rpc_blocks")]),
+        };
+        Self { inner: Some(Arc::new(Mutex::new(inner))) }
+    }
+
+    /// Returns `true` if caching is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the cached response of the block with the given kind of transactions, if any.
+    pub fn get(&self, block_hash: &B256, kind: BlockTransactionsKind) -> Option<Block> {
+        let mut inner = self.inner.as_ref()?.lock().unwrap_or_else(|err| err.into_inner());
+        let block = inner.blocks.get(block_hash).cloned();
+        if block.is_some() {
+            inner.metrics.hits_total.increment(1);
+        } else {
+            inner.metrics.misses_total.increment(1);
+        }
+        drop(inner);
+
+        let block = block?;
+        Some(match kind {
+            BlockTransactionsKind::Full => Block::clone(&block),
+            BlockTransactionsKind::Hashes => with_transaction_hashes(&block),
+        })
+    }
+
+    /// Caches the response of a block with its full transaction objects.
+    ///
+    /// Blocks without a hash or without full transaction objects are not cached.
+    pub fn insert(&self, block: Block) {
+        self.insert_if(block, || true)
+    }
+
+    /// Caches the response of a block like [`Self::insert`], if the condition holds.
+    ///
+    /// The condition is checked while the cache is locked, so a concurrent
+    /// [`Self::remove_blocks`] either runs before the check or removes the inserted block.
+    pub fn insert_if(&self, block: Block, condition: impl FnOnce() -> bool) {
+        let Some(inner) = &self.inner else { return };
+        let Some(block_hash) = block.header.hash else { return };
+        if !matches!(block.transactions, BlockTransactions::Full(_)) {
+            return
+        }
+
+        let mut inner = inner.lock().unwrap_or_else(|err| err.into_inner());
+        if !condition() {
+            return
+        }
+        inner.blocks.insert(block_hash, Arc::new(block));
+        inner.metrics.cached_count.set(inner.blocks.len() as f64);
+    }
+
+    /// Removes the responses of the given blocks.
+    pub fn remove_blocks(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        let Some(inner) = &self.inner else { return };
+        let mut inner = inner.lock().unwrap_or_else(|err| err.into_inner());
+        for block_hash in block_hashes {
+            inner.blocks.remove(&block_hash);
+        }
+        inner.metrics.cached_count.set(inner.blocks.len() as f64);
+    }
+}
+
+impl fmt::Debug for RpcBlockCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcBlockCache").field("enabled", &self.is_enabled()).finish_non_exhaustive()
+    }
+}
+
+struct RpcBlockCacheInner {
+    blocks: LruMap<B256, Arc<Block>, ByLength>,
+    metrics: CacheMetrics,
+}
+
+/// Returns the block with the hashes of its transactions instead of the transaction objects.
+fn with_transaction_hashes(block: &Block) -> Block {
+    let transactions = match &block.transactions {
+        BlockTransactions::Full(transactions) => {
+            BlockTransactions::Hashes(transactions.iter().map(|tx| tx.hash).collect())
+        }
+        transactions => transactions.clone(),
+    };
+    Block {
+        header: block.header.clone(),
+        uncles: block.uncles.clone(),
+        transactions,
+        size: block.size,
+        withdrawals: block.withdrawals.clone(),
+        other: block.other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::{Header, Transaction};
+
+    #[test]
+    fn caches_full_blocks() {
+        let block = |i: u8| Block {
+            header: Header { hash: Some(B256::with_last_byte(i)), ..Default::default() },
+            transactions: BlockTransactions::Full(vec![Transaction {
+                hash: B256::with_last_byte(100 + i),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        let cache = RpcBlockCache::new(2);
+        for i in 0..3 {
+            cache.insert(block(i));
+        }
+        assert_eq!(cache.get(&B256::with_last_byte(0), BlockTransactionsKind::Full), None);
+        assert_eq!(
+            cache.get(&B256::with_last_byte(1), BlockTransactionsKind::Full),
+            Some(block(1))
+        );
+        assert_eq!(
+            cache
+                .get(&B256::with_last_byte(2), BlockTransactionsKind::Hashes)
+                .map(|block| block.transactions),
+            Some(BlockTransactions::Hashes(vec![B256::with_last_byte(102)]))
+        );
+
+        cache.remove_blocks([B256::with_last_byte(1)]);
+        assert_eq!(cache.get(&B256::with_last_byte(1), BlockTransactionsKind::Full), None);
+
+        // only responses with transaction objects are cached
+        cache.insert(with_transaction_hashes(&block(3)));
+        assert_eq!(cache.get(&B256::with_last_byte(3), BlockTransactionsKind::Hashes), None);
+
+        // blocks are only cached if the condition holds
+        cache.insert_if(block(4), || false);
+        assert_eq!(cache.get(&B256::with_last_byte(4), BlockTransactionsKind::Full), None);
+
+        let disabled = RpcBlockCache::new(0);
+        disabled.insert(block(0));
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.get(&B256::with_last_byte(0), BlockTransactionsKind::Full), None);
+    }
+}
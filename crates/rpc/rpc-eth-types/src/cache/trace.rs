@@ -0,0 +1,265 @@
+//! Cache for the results of tracing calls.
+
+use futures::{Stream, StreamExt};
+use metrics::Counter;
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_primitives::B256;
+use reth_provider::CanonStateNotification;
+use schnellru::{LruMap, Unlimited};
+use serde::Serialize;
+use std::{
+    any::Any,
+    collections::HashSet,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// Identifies a cached trace by the traced block or transaction, the tracer and its
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceCacheKey {
+    /// The hash of the traced block, or of the block that includes the traced transaction.
+    block_hash: B256,
+    /// The hash of the traced transaction, if a single transaction was traced.
+    transaction: Option<B256>,
+    /// The RPC method that produced the trace.
+    tracer: &'static str,
+    /// The tracer configuration, serialized as JSON.
+    config: String,
+}
+
+impl TraceCacheKey {
+    /// Creates the key of the trace of a whole block.
+    pub fn block(block_hash: B256, tracer: &'static str, config: &impl Serialize) -> Self {
+        Self { block_hash, transaction: None, tracer, config: serialize_config(config) }
+    }
+
+    /// Creates the key of the trace of a transaction in the given block.
+    pub fn transaction(
+        block_hash: B256,
+        transaction: B256,
+        tracer: &'static str,
+        config: &impl Serialize,
+    ) -> Self {
+        Self {
+            block_hash,
+            transaction: Some(transaction),
+            tracer,
+            config: serialize_config(config),
+        }
+    }
+}
+
+fn serialize_config(config: &impl Serialize) -> String {
+    serde_json::to_string(config).unwrap_or_default()
+}
+
+/// A bounded cache of trace results, so that repeated tracing calls for the same blocks don't
+/// re-execute them.
+///
+/// The cache is bounded by the size of the serialized traces and evicts the least recently used
+/// traces first. Traces are keyed by block hash, so a reorg never serves stale traces, the traces
+/// of the reorged blocks are still removed with [`TraceCache::remove_blocks`] to free the memory.
+#[derive(Clone, Default)]
+pub struct TraceCache {
+    /// The cache, `None` if caching is disabled.
+    inner: Option<Arc<Mutex<TraceCacheInner>>>,
+}
+
+impl TraceCache {
+    /// Creates a new cache with the given memory budget in bytes.
+    ///
+    /// A budget of zero disables the cache.
+    pub fn new(max_bytes: usize) -> Self {
+        if max_bytes == 0 {
+            return Self::default()
+        }
+        let inner = TraceCacheInner {
+            entries: LruMap::new(Unlimited),
+            size: 0,
+            max_bytes,
+            metrics: TraceCacheMetrics::default(),
+        };
+        Self { inner: Some(Arc::new(Mutex::new(inner))) }
+    }
+
+    /// Returns `true` if caching is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the cached trace for the key, or computes and caches it.
+    ///
+    /// Only successful traces are cached.
+    pub async fn get_or_insert_with<T, E, F>(&self, key: TraceCacheKey, f: F) -> Result<T, E>
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, E>>,
+    {
+        if let Some(trace) = self.get(&key) {
+            return Ok(trace)
+        }
+        let trace = f.await?;
+        self.insert(key, &trace);
+        Ok(trace)
+    }
+
+    /// Returns the cached trace for the key, if any.
+    pub fn get<T: Clone + 'static>(&self, key: &TraceCacheKey) -> Option<T> {
+        let mut inner = self.inner.as_ref()?.lock().unwrap_or_else(|err| err.into_inner());
+        let trace =
+            inner.entries.get(key).and_then(|entry| entry.trace.downcast_ref::<T>().cloned());
+        if trace.is_some() {
+            inner.metrics.hits_total.increment(1);
+        } else {
+            inner.metrics.misses_total.increment(1);
+        }
+        trace
+    }
+
+    /// Caches the trace, evicting the least recently used traces if the memory budget is
+    /// exceeded.
+    ///
+    /// Traces that exceed the budget on their own are not cached.
+    pub fn insert<T: Serialize + Send + Sync + Clone + 'static>(
+        &self,
+        key: TraceCacheKey,
+        trace: &T,
+    ) {
+        let Some(inner) = &self.inner else { return };
+        // the serialized size is a proxy for the memory used by the trace
+        let Ok(size) = serde_json::to_vec(trace).map(|serialized| serialized.len()) else { return };
+        let mut inner = inner.lock().unwrap_or_else(|err| err.into_inner());
+        if size > inner.max_bytes {
+            return
+        }
+
+        let entry = CachedTrace { trace: Arc::new(trace.clone()), size };
+        if let Some(replaced) = inner.entries.remove(&key) {
+            inner.size -= replaced.size;
+        }
+        inner.entries.insert(key, entry);
+        inner.size += size;
+        while inner.size > inner.max_bytes {
+            let Some((_, evicted)) = inner.entries.pop_oldest() else { break };
+            inner.size -= evicted.size;
+            inner.metrics.evictions_total.increment(1);
+        }
+        inner.update_size_metrics();
+    }
+
+    /// Removes the traces of the given blocks.
+    pub fn remove_blocks(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        let Some(inner) = &self.inner else { return };
+        let block_hashes = block_hashes.into_iter().collect::<HashSet<_>>();
+        let mut inner = inner.lock().unwrap_or_else(|err| err.into_inner());
+        let removed = inner
+            .entries
+            .iter()
+            .filter(|(key, _)| block_hashes.contains(&key.block_hash))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in removed {
+            if let Some(entry) = inner.entries.remove(&key) {
+                inner.size -= entry.size;
+            }
+        }
+        inner.update_size_metrics();
+    }
+}
+
+impl fmt::Debug for TraceCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceCache").field("enabled", &self.is_enabled()).finish_non_exhaustive()
+    }
+}
+
+struct TraceCacheInner {
+    entries: LruMap<TraceCacheKey, CachedTrace, Unlimited>,
+    /// The total size of the cached traces.
+    size: usize,
+    /// The memory budget of the cached traces.
+    max_bytes: usize,
+    metrics: TraceCacheMetrics,
+}
+
+impl TraceCacheInner {
+    fn update_size_metrics(&self) {
+        self.metrics.cached_count.set(self.entries.len() as f64);
+        self.metrics.cached_bytes.set(self.size as f64);
+    }
+}
+
+/// A cached trace with its serialized size.
+struct CachedTrace {
+    trace: Arc<dyn Any + Send + Sync>,
+    size: usize,
+}
+
+/// Removes the traces of reorged blocks from the cache.
+pub async fn trace_cache_reorg_task<St>(trace_cache: TraceCache, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        if let Some(reverted) = event.reverted() {
+            trace_cache.remove_blocks(reverted.blocks().values().map(|block| block.hash()));
+        }
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "rpc.trace_cache")]
+struct TraceCacheMetrics {
+    /// The number of cached traces.
+    cached_count: Gauge,
+    /// The total size of the cached traces in bytes.
+    cached_bytes: Gauge,
+    /// The number of cache hits.
+    hits_total: Counter,
+    /// The number of cache misses.
+    misses_total: Counter,
+    /// The number of traces that were evicted to stay within the memory budget.
+    evictions_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_traces() {
+        let cache = TraceCache::new(64);
+        let trace = vec![0u8; 10];
+        let size = serde_json::to_vec(&trace).unwrap().len();
+        let key = |i: u8| TraceCacheKey::block(B256::with_last_byte(i), "trace_block", &());
+
+        for i in 0..(64 / size) as u8 {
+            cache.insert(key(i), &trace);
+        }
+        assert_eq!(cache.get::<Vec<u8>>(&key(0)), Some(trace.clone()));
+
+        // the first trace was used most recently, so the second one is evicted
+        cache.insert(key(100), &trace);
+        assert_eq!(cache.get::<Vec<u8>>(&key(0)), Some(trace.clone()));
+        assert_eq!(cache.get::<Vec<u8>>(&key(1)), None);
+        assert_eq!(cache.get::<Vec<u8>>(&key(100)), Some(trace.clone()));
+
+        // traces of different tracers or configs are distinct
+        let other = TraceCacheKey::block(B256::with_last_byte(0), "trace_block", &"config");
+        assert_eq!(cache.get::<Vec<u8>>(&other), None);
+
+        cache.remove_blocks([B256::with_last_byte(0)]);
+        assert_eq!(cache.get::<Vec<u8>>(&key(0)), None);
+
+        // traces above the budget are not cached
+        cache.insert(key(200), &vec![0u8; 64]);
+        assert_eq!(cache.get::<Vec<u8>>(&key(200)), None);
+
+        let disabled = TraceCache::new(0);
+        disabled.insert(key(0), &trace);
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.get::<Vec<u8>>(&key(0)), None);
+    }
+}
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use alloy_sol_types::decode_revert_reason;
 use reth_errors::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, BlockNumber, Bytes};
 use reth_rpc_server_types::result::{
     internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
@@ -85,6 +85,10 @@ pub enum EthApiError {
     /// Thrown when a requested transaction is not found
     #[error("transaction not found")]
     TransactionNotFound,
+    /// Thrown when a transaction is not found by hash, but the transaction hash index is pruned up
+    /// to the given block, so the transaction may be in a pruned block
+    #[error("transaction not found, the transaction index is pruned up to block {0}")]
+    TransactionIndexPruned(BlockNumber),
     /// Some feature is unsupported
     #[error("unsupported")]
     Unsupported(&'static str),
@@ -162,7 +166,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::EvmCustom(_) |
             EthApiError::EvmPrecompile(_) |
             EthApiError::InvalidRewardPercentiles => internal_rpc_err(error.to_string()),
-            EthApiError::UnknownBlockNumber | EthApiError::UnknownBlockOrTxIndex => {
+            EthApiError::UnknownBlockNumber |
+            EthApiError::UnknownBlockOrTxIndex |
+            EthApiError::TransactionIndexPruned(_) => {
                 rpc_error_with_code(EthRpcErrorCode::ResourceNotFound.code(), error.to_string())
             }
             EthApiError::UnknownSafeOrFinalizedBlock => {
@@ -582,6 +588,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// When the transaction is rejected by the transaction policy of the pool
+    #[error("transaction rejected by policy")]
+    RejectedByPolicy,
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -624,7 +633,9 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
                 Self::Invalid(RpcInvalidTransactionError::GasTooLow)
             }
             InvalidPoolTransactionError::OversizedData(_, _) => Self::OversizedData,
-            InvalidPoolTransactionError::Underpriced => Self::Underpriced,
+            InvalidPoolTransactionError::Underpriced |
+            InvalidPoolTransactionError::TipBelowFloor { .. } => Self::Underpriced,
+            InvalidPoolTransactionError::RejectedByPolicy => Self::RejectedByPolicy,
             InvalidPoolTransactionError::Other(err) => Self::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => Self::Eip4844(err),
             InvalidPoolTransactionError::Overdraft => {
@@ -1,7 +1,7 @@
 //! Consist of types adjacent to the fee history cache and its configs
 
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::Debug,
     sync::{atomic::Ordering::SeqCst, Arc},
 };
@@ -13,9 +13,7 @@ use futures::{
 use metrics::atomics::AtomicU64;
 use reth_chainspec::ChainSpec;
 use reth_primitives::{
-    basefee::calc_next_block_base_fee,
-    eip4844::{calc_blob_gasprice, calculate_excess_blob_gas},
-    Receipt, SealedBlock, TransactionSigned, B256,
+    basefee::calc_next_block_base_fee, Receipt, SealedBlock, TransactionSigned, B256,
 };
 use reth_provider::{BlockReaderIdExt, CanonStateNotification, ChainSpecProvider};
 use reth_rpc_types::TxGasAndReward;
@@ -29,6 +27,10 @@ use super::{EthApiError, EthStateCache};
 /// Contains cached fee history entries for blocks.
 ///
 /// Purpose for this is to provide cached data for `eth_feeHistory`.
+///
+/// Entries are keyed by block hash, and a history is collected by following the parent hashes
+/// from the newest requested block, so a history never mixes blocks of different forks. Entries
+/// of reorged blocks are dropped, and missing entries are lazily loaded from disk.
 #[derive(Debug, Clone)]
 pub struct FeeHistoryCache {
     inner: Arc<FeeHistoryCacheInner>,
@@ -68,12 +70,21 @@ impl FeeHistoryCache {
         let entries = self.inner.entries.read().await;
         (self.lower_bound()..self.upper_bound())
             .rev()
-            .filter(|&block_number| !entries.contains_key(&block_number))
+            .filter(|block_number| !entries.hashes.contains_key(block_number))
             .collect()
     }
 
+    /// Removes the entries of the given block and all blocks above it.
+    ///
+    /// This is used to drop the entries of the blocks that were reverted by a reorg.
+    async fn remove_blocks_from(&self, block_number: u64) {
+        let mut entries = self.inner.entries.write().await;
+        entries.remove_from(block_number);
+        self.update_bounds(&entries);
+    }
+
     /// Insert block data into the cache.
-    async fn insert_blocks<I>(&self, blocks: I)
+    async fn insert_blocks<I>(&self, blocks: I, chain_spec: &ChainSpec)
     where
         I: IntoIterator<Item = (SealedBlock, Arc<Vec<Receipt>>)>,
     {
@@ -82,7 +93,7 @@ impl FeeHistoryCache {
         let percentiles = self.predefined_percentiles();
         // Insert all new blocks and calculate approximated rewards
         for (block, receipts) in blocks {
-            let mut fee_history_entry = FeeHistoryEntry::new(&block);
+            let mut fee_history_entry = FeeHistoryEntry::new(&block, chain_spec);
             fee_history_entry.rewards = calculate_reward_percentiles_for_block(
                 &percentiles,
                 fee_history_entry.gas_used,
@@ -91,7 +102,7 @@ impl FeeHistoryCache {
                 &receipts,
             )
             .unwrap_or_default();
-            entries.insert(block.number, fee_history_entry);
+            entries.insert(fee_history_entry);
         }
 
         // enforce bounds by popping the oldest entries
@@ -99,23 +110,28 @@ impl FeeHistoryCache {
             entries.pop_first();
         }
 
-        if entries.len() == 0 {
-            self.inner.upper_bound.store(0, SeqCst);
-            self.inner.lower_bound.store(0, SeqCst);
-            return
+        if let Some((&upper_bound, _)) = entries.hashes.last_key_value() {
+            // also enforce proper lower bound in case we have gaps
+            let target_lower = upper_bound.saturating_sub(self.inner.config.max_blocks);
+            while entries.len() > 1 &&
+                entries
+                    .hashes
+                    .first_key_value()
+                    .is_some_and(|(number, _)| *number < target_lower)
+            {
+                entries.pop_first();
+            }
         }
 
-        let upper_bound = *entries.last_entry().expect("Contains at least one entry").key();
-
-        // also enforce proper lower bound in case we have gaps
-        let target_lower = upper_bound.saturating_sub(self.inner.config.max_blocks);
-        while entries.len() > 1 && *entries.first_key_value().unwrap().0 < target_lower {
-            entries.pop_first();
-        }
+        self.update_bounds(&entries);
+    }
 
-        let lower_bound = *entries.first_entry().expect("Contains at least one entry").key();
-        self.inner.upper_bound.store(upper_bound, SeqCst);
-        self.inner.lower_bound.store(lower_bound, SeqCst);
+    /// Sets the bounds of the cache to the lowest and highest cached block.
+    fn update_bounds(&self, entries: &FeeHistoryEntries) {
+        let lower_bound = entries.hashes.first_key_value().map(|(number, _)| *number);
+        let upper_bound = entries.hashes.last_key_value().map(|(number, _)| *number);
+        self.inner.upper_bound.store(upper_bound.unwrap_or_default(), SeqCst);
+        self.inner.lower_bound.store(lower_bound.unwrap_or_default(), SeqCst);
     }
 
     /// Get `UpperBound` value for `FeeHistoryCache`
@@ -130,31 +146,40 @@ impl FeeHistoryCache {
 
     /// Collect fee history for given range.
     ///
-    /// This function retrieves fee history entries from the cache for the specified range.
-    /// If the requested range (`start_block` to `end_block`) is within the cache bounds,
-    /// it returns the corresponding entries.
-    /// Otherwise it returns None.
+    /// This function retrieves fee history entries from the cache for the range from
+    /// `start_block` to the block with the hash `end_block_hash`, by following the parent hashes
+    /// of the entries. Entries that are missing in the cache are loaded from disk.
+    ///
+    /// Returns None if `start_block` is below the cache bounds, or if the blocks could not be
+    /// loaded.
     pub async fn get_history(
         &self,
         start_block: u64,
-        end_block: u64,
+        end_block_hash: B256,
+        chain_spec: &ChainSpec,
     ) -> Option<Vec<FeeHistoryEntry>> {
-        let lower_bound = self.lower_bound();
-        let upper_bound = self.upper_bound();
-        if start_block >= lower_bound && end_block <= upper_bound {
-            let entries = self.inner.entries.read().await;
-            let result = entries
-                .range(start_block..=end_block)
-                .map(|(_, fee_entry)| fee_entry.clone())
-                .collect::<Vec<_>>();
-
-            if result.is_empty() {
+        if start_block < self.lower_bound() {
+            return None
+        }
+
+        let mut last_missing = None;
+        loop {
+            let missing = match self.inner.entries.read().await.history(start_block, end_block_hash)
+            {
+                Ok(history) => return history,
+                Err(missing) => missing,
+            };
+
+            // the loaded block was not kept in the cache
+            if last_missing.replace(missing) == Some(missing) {
                 return None
             }
 
-            Some(result)
-        } else {
-            None
+            // lazily backfill the missing block
+            trace!(target: "rpc::fee", block_hash=?missing, "Loading missing block for fee history cache");
+            let block_and_receipts =
+                self.inner.eth_cache.get_block_and_receipts(missing).await.ok().flatten()?;
+            self.insert_blocks([block_and_receipts], chain_spec).await;
         }
     }
 
@@ -199,10 +224,83 @@ struct FeeHistoryCacheInner {
     /// and max number of blocks
     config: FeeHistoryCacheConfig,
     /// Stores the entries of the cache
-    entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry>>,
+    entries: tokio::sync::RwLock<FeeHistoryEntries>,
     eth_cache: EthStateCache,
 }
 
+/// The cached entries of the [`FeeHistoryCache`], keyed by block hash.
+///
+/// Only one entry is kept per block number, the entry of the most recently inserted block.
+#[derive(Debug, Default)]
+struct FeeHistoryEntries {
+    /// Entries by the hash of their block.
+    by_hash: HashMap<B256, FeeHistoryEntry>,
+    /// Block hashes of the entries by block number.
+    hashes: BTreeMap<u64, B256>,
+}
+
+impl FeeHistoryEntries {
+    /// Returns the number of entries.
+    fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Inserts the entry, replacing the entry of another block with the same number.
+    fn insert(&mut self, entry: FeeHistoryEntry) {
+        if let Some(replaced) = self.hashes.insert(entry.number, entry.header_hash) {
+            self.by_hash.remove(&replaced);
+        }
+        self.by_hash.insert(entry.header_hash, entry);
+    }
+
+    /// Removes the entry with the lowest block number.
+    fn pop_first(&mut self) {
+        if let Some((_, hash)) = self.hashes.pop_first() {
+            self.by_hash.remove(&hash);
+        }
+    }
+
+    /// Removes the entries of the given block number and above.
+    fn remove_from(&mut self, block_number: u64) {
+        for hash in self.hashes.split_off(&block_number).into_values() {
+            self.by_hash.remove(&hash);
+        }
+    }
+
+    /// Collects the entries from `start_block` to the block with the hash `end_block_hash`, in
+    /// ascending order, by following the parent hashes.
+    ///
+    /// Returns `Ok(None)` if the end block is below `start_block`, and the hash of the first
+    /// block that is not cached if the history is incomplete.
+    fn history(
+        &self,
+        start_block: u64,
+        end_block_hash: B256,
+    ) -> Result<Option<Vec<FeeHistoryEntry>>, B256> {
+        let mut history = Vec::new();
+        let mut block_hash = end_block_hash;
+        loop {
+            let entry = self.by_hash.get(&block_hash).ok_or(block_hash)?;
+            if entry.number < start_block ||
+                history
+                    .last()
+                    .is_some_and(|child: &FeeHistoryEntry| child.number != entry.number + 1)
+            {
+                return Ok(None)
+            }
+
+            history.push(entry.clone());
+            if entry.number == start_block {
+                break
+            }
+            block_hash = entry.parent_hash;
+        }
+
+        history.reverse();
+        Ok(Some(history))
+    }
+}
+
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
 pub async fn fee_history_cache_new_blocks_task<St, Provider>(
@@ -237,7 +335,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
         tokio::select! {
             res = &mut fetch_missing_block =>  {
                 if let Ok(res) = res {
-                    fee_history_cache.insert_blocks(res.into_iter()).await;
+                    fee_history_cache.insert_blocks(res.into_iter(), &provider.chain_spec()).await;
                 }
             }
             event = events.next() =>  {
@@ -252,7 +350,14 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
                         (block.block.clone(), Arc::new(receipts.iter().flatten().cloned().collect::<Vec<_>>()))
                     })
                     .unzip();
-                fee_history_cache.insert_blocks(blocks.into_iter().zip(receipts)).await;
+
+                // drop the entries of the reverted blocks, the new chain replaces them
+                if let Some(reverted) = event.reverted() {
+                    fee_history_cache.remove_blocks_from(reverted.first().number).await;
+                }
+                fee_history_cache
+                    .insert_blocks(blocks.into_iter().zip(receipts), &provider.chain_spec())
+                    .await;
 
                 // keep track of missing blocks
                 missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
@@ -345,8 +450,12 @@ pub struct FeeHistoryEntry {
     pub gas_used: u64,
     /// Gas limit by this block.
     pub gas_limit: u64,
+    /// Number of the block.
+    pub number: u64,
     /// Hash of the block.
     pub header_hash: B256,
+    /// Hash of the parent block.
+    pub parent_hash: B256,
     /// Approximated rewards for the configured percentiles.
     pub rewards: Vec<u128>,
     /// The timestamp of the block.
@@ -357,17 +466,20 @@ impl FeeHistoryEntry {
     /// Creates a new entry from a sealed block.
     ///
     /// Note: This does not calculate the rewards for the block.
-    pub fn new(block: &SealedBlock) -> Self {
+    pub fn new(block: &SealedBlock, chain_spec: &ChainSpec) -> Self {
+        let blob_params = chain_spec.blob_params_at_timestamp(block.timestamp);
         Self {
             base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default(),
             gas_used_ratio: block.gas_used as f64 / block.gas_limit as f64,
-            base_fee_per_blob_gas: block.blob_fee(),
+            base_fee_per_blob_gas: block.blob_fee_with_params(blob_params),
             blob_gas_used_ratio: block.blob_gas_used() as f64 /
-                reth_primitives::constants::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                blob_params.max_blob_gas_per_block() as f64,
             excess_blob_gas: block.excess_blob_gas,
             blob_gas_used: block.blob_gas_used,
             gas_used: block.gas_used,
+            number: block.number,
             header_hash: block.hash(),
+            parent_hash: block.parent_hash,
             gas_limit: block.gas_limit,
             rewards: Vec::new(),
             timestamp: block.timestamp,
@@ -389,14 +501,92 @@ impl FeeHistoryEntry {
     /// Returns `None` if `excess_blob_gas` is None.
     ///
     /// See also [`Self::next_block_excess_blob_gas`]
-    pub fn next_block_blob_fee(&self) -> Option<u128> {
-        self.next_block_excess_blob_gas().map(calc_blob_gasprice)
+    pub fn next_block_blob_fee(&self, chain_spec: &ChainSpec) -> Option<u128> {
+        let blob_params = chain_spec.blob_params_at_timestamp(self.timestamp);
+        self.next_block_excess_blob_gas(chain_spec)
+            .map(|excess_blob_gas| blob_params.blob_fee(excess_blob_gas))
     }
 
     /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
     ///
     /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
-    pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
-        Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    pub fn next_block_excess_blob_gas(&self, chain_spec: &ChainSpec) -> Option<u64> {
+        Some(
+            chain_spec
+                .blob_params_at_timestamp(self.timestamp)
+                .next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Header};
+
+    fn entry(number: u64, parent_hash: B256, gas_used: u64) -> FeeHistoryEntry {
+        let block = Block {
+            header: Header { number, parent_hash, gas_used, gas_limit: 100, ..Default::default() },
+            ..Default::default()
+        };
+        FeeHistoryEntry::new(&block.seal_slow(), &reth_chainspec::MAINNET)
+    }
+
+    fn chain(
+        parent_hash: B256,
+        numbers: std::ops::RangeInclusive<u64>,
+        gas_used: u64,
+    ) -> Vec<FeeHistoryEntry> {
+        let mut parent_hash = parent_hash;
+        numbers
+            .map(|number| {
+                let entry = entry(number, parent_hash, gas_used);
+                parent_hash = entry.header_hash;
+                entry
+            })
+            .collect()
+    }
+
+    fn gas_used(history: &[FeeHistoryEntry]) -> Vec<(u64, u64)> {
+        history.iter().map(|entry| (entry.number, entry.gas_used)).collect()
+    }
+
+    #[test]
+    fn history_follows_parent_hashes() {
+        let mut entries = FeeHistoryEntries::default();
+        let old = chain(B256::ZERO, 1..=4, 1);
+        old.iter().cloned().for_each(|entry| entries.insert(entry));
+
+        let history = entries.history(2, old[3].header_hash).unwrap().unwrap();
+        assert_eq!(gas_used(&history), [(2, 1), (3, 1), (4, 1)]);
+        assert!(matches!(entries.history(4, old[1].header_hash), Ok(None)));
+    }
+
+    #[test]
+    fn history_never_mixes_forks() {
+        let mut entries = FeeHistoryEntries::default();
+        let old = chain(B256::ZERO, 1..=4, 1);
+        old.iter().cloned().for_each(|entry| entries.insert(entry));
+
+        // reorg to a shorter chain that forks off after block 2
+        let new = chain(old[1].header_hash, 3..=3, 2);
+        entries.remove_from(3);
+        new.iter().cloned().for_each(|entry| entries.insert(entry));
+        assert_eq!(entries.len(), 3);
+        assert!(!entries.by_hash.contains_key(&old[3].header_hash));
+
+        let history = entries.history(1, new[0].header_hash).unwrap().unwrap();
+        assert_eq!(gas_used(&history), [(1, 1), (2, 1), (3, 2)]);
+
+        // a stale entry of the old fork is never part of the history of the new fork, the missing
+        // block of the new fork is requested instead
+        let new_tip = entry(4, new[0].header_hash, 2);
+        entries.insert(old[2].clone());
+        entries.insert(new_tip.clone());
+        assert_eq!(entries.history(1, new_tip.header_hash).unwrap_err(), new[0].header_hash);
+
+        entries.insert(new[0].clone());
+        let history = entries.history(1, new_tip.header_hash).unwrap().unwrap();
+        assert_eq!(gas_used(&history), [(1, 1), (2, 1), (3, 2), (4, 2)]);
     }
 }
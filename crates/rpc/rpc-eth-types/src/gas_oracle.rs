@@ -1,20 +1,29 @@
 //! An implementation of the eth gas price oracle, used for providing gas price estimates based on
 //! previous blocks.
 
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+    sync::Arc,
+};
 
 use derive_more::{Deref, DerefMut, From, Into};
-use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, B256, U256};
-use reth_provider::BlockReaderIdExt;
+use futures::{Stream, StreamExt};
+use reth_primitives::{
+    constants::GWEI_TO_WEI, Address, BlockNumberOrTag, Header, SealedBlockWithSenders,
+    SealedHeader, TransactionSigned, B256, U256,
+};
+use reth_provider::{BlockReaderIdExt, CanonStateNotification};
 use reth_rpc_server_types::constants;
+use reth_transaction_pool::TransactionPool;
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use reth_rpc_server_types::constants::gas_oracle::{
-    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE, MAX_HEADER_HISTORY, SAMPLE_NUMBER,
+    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_EWMA_WEIGHT, DEFAULT_GAS_PRICE_PERCENTILE,
+    DEFAULT_IGNORE_GAS_PRICE, DEFAULT_MAX_GAS_PRICE, MAX_HEADER_HISTORY, SAMPLE_NUMBER,
 };
 
 use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
@@ -23,6 +32,55 @@ use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
 /// [`RPC_DEFAULT_GAS_CAP`](constants::gas_oracle::RPC_DEFAULT_GAS_CAP).
 pub const RPC_DEFAULT_GAS_CAP: GasCap = GasCap(constants::gas_oracle::RPC_DEFAULT_GAS_CAP);
 
+/// The strategy used by the [`GasPriceOracle`] to produce an estimate.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GasPriceOracleStrategy {
+    /// The configured percentile of the lowest tips of recent blocks, like geth.
+    #[default]
+    Percentile,
+    /// Like [`GasPriceOracleStrategy::Percentile`], but raised to the tip needed to be included
+    /// in the next block if the pending pool holds more than a full block of transactions.
+    PendingPool,
+    /// An exponentially weighted moving average of the per-block percentile of recent blocks.
+    Ewma,
+}
+
+impl GasPriceOracleStrategy {
+    /// Returns the name of the strategy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Percentile => "percentile",
+            Self::PendingPool => "pending-pool",
+            Self::Ewma => "ewma",
+        }
+    }
+}
+
+impl fmt::Display for GasPriceOracleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GasPriceOracleStrategy {
+    type Err = UnknownGasPriceOracleStrategy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "percentile" => Ok(Self::Percentile),
+            "pending-pool" => Ok(Self::PendingPool),
+            "ewma" => Ok(Self::Ewma),
+            _ => Err(UnknownGasPriceOracleStrategy(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown [`GasPriceOracleStrategy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown gas price oracle strategy: {0}, expected one of percentile, pending-pool, ewma")]
+pub struct UnknownGasPriceOracleStrategy(String);
+
 /// Settings for the [`GasPriceOracle`]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,6 +105,19 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The strategy used to produce the estimate
+    #[serde(default)]
+    pub strategy: GasPriceOracleStrategy,
+
+    /// The weight in percent of the newest block in the moving average of the
+    /// [`GasPriceOracleStrategy::Ewma`] strategy
+    #[serde(default = "default_ewma_weight")]
+    pub ewma_weight: u32,
+}
+
+const fn default_ewma_weight() -> u32 {
+    DEFAULT_GAS_PRICE_EWMA_WEIGHT
 }
 
 impl Default for GasPriceOracleConfig {
@@ -59,12 +130,40 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            strategy: GasPriceOracleStrategy::default(),
+            ewma_weight: DEFAULT_GAS_PRICE_EWMA_WEIGHT,
         }
     }
 }
 
+/// Provides the tip pending transactions have to pay to be included in the next block, used by
+/// the [`GasPriceOracleStrategy::PendingPool`] strategy.
+pub trait PendingTipProvider: Send + Sync {
+    /// Returns the effective tip of the transaction that fills up a block with the given gas limit
+    /// if the best pending transactions are included in order, or `None` if the pending
+    /// transactions don't fill up a block.
+    fn marginal_pending_tip(&self, block_gas_limit: u64) -> Option<u128>;
+}
+
+impl<Pool: TransactionPool> PendingTipProvider for Pool {
+    fn marginal_pending_tip(&self, block_gas_limit: u64) -> Option<u128> {
+        let base_fee = self.block_info().pending_basefee;
+        let mut gas = 0u64;
+        for tx in self.best_transactions() {
+            gas = gas.saturating_add(tx.gas_limit());
+            if gas >= block_gas_limit {
+                return tx.effective_tip_per_gas(base_fee)
+            }
+        }
+        None
+    }
+}
+
 /// Calculates a gas price depending on recent blocks.
-#[derive(Debug)]
+///
+/// The lowest effective tips of recent blocks are kept in a rolling cache that is shared between
+/// clones of the oracle, see [`gas_price_oracle_new_blocks_task`] for keeping it warm.
+#[derive(Clone)]
 pub struct GasPriceOracle<Provider> {
     /// The type used to subscribe to block events and get block info
     provider: Provider,
@@ -74,9 +173,24 @@ pub struct GasPriceOracle<Provider> {
     oracle_config: GasPriceOracleConfig,
     /// The price under which the sample will be ignored.
     ignore_price: Option<u128>,
+    /// Source of pending tips for the [`GasPriceOracleStrategy::PendingPool`] strategy.
+    pending_tips: Option<Arc<dyn PendingTipProvider>>,
     /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
     /// tip values of recent blocks
-    inner: Mutex<GasPriceOracleInner>,
+    inner: Arc<Mutex<GasPriceOracleInner>>,
+}
+
+impl<Provider: Debug> Debug for GasPriceOracle<Provider> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GasPriceOracle")
+            .field("provider", &self.provider)
+            .field("cache", &self.cache)
+            .field("oracle_config", &self.oracle_config)
+            .field("ignore_price", &self.ignore_price)
+            .field("pending_tips", &self.pending_tips.is_some())
+            .field("inner", &self.inner)
+            .finish()
+    }
 }
 
 impl<Provider> GasPriceOracle<Provider>
@@ -94,18 +208,32 @@ where
             warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
             oracle_config.percentile = 100;
         }
+        if oracle_config.ewma_weight > 100 {
+            warn!(prev_weight = ?oracle_config.ewma_weight, "Invalid configured gas price EWMA weight, assuming 100.");
+            oracle_config.ewma_weight = 100;
+        }
         let ignore_price = oracle_config.ignore_price.map(|price| price.saturating_to());
 
         // this is the number of blocks that we will cache the values for
         let cached_values = (oracle_config.blocks * 5).max(oracle_config.max_block_history as u32);
-        let inner = Mutex::new(GasPriceOracleInner {
+        let inner = Arc::new(Mutex::new(GasPriceOracleInner {
             last_price: Default::default(),
             lowest_effective_tip_cache: EffectiveTipLruCache(LruMap::new(ByLength::new(
                 cached_values,
             ))),
-        });
+            ewma: None,
+        }));
+
+        Self { provider, oracle_config, cache, ignore_price, pending_tips: None, inner }
+    }
 
-        Self { provider, oracle_config, cache, ignore_price, inner }
+    /// Sets the source of pending tips used by the [`GasPriceOracleStrategy::PendingPool`]
+    /// strategy.
+    ///
+    /// Without it, the strategy behaves like [`GasPriceOracleStrategy::Percentile`].
+    pub fn with_pending_tips(mut self, pending_tips: impl PendingTipProvider + 'static) -> Self {
+        self.pending_tips = Some(Arc::new(pending_tips));
+        self
     }
 
     /// Returns the configuration of the gas price oracle.
@@ -113,7 +241,10 @@ where
         &self.oracle_config
     }
 
-    /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    /// Suggests a gas price estimate based on recent blocks, using the configured strategy.
+    ///
+    /// The estimate is computed once per head block, further calls for the same head return the
+    /// stored estimate.
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
             .provider
@@ -127,6 +258,65 @@ where
             return Ok(inner.last_price.price)
         }
 
+        let mut price = match self.oracle_config.strategy {
+            GasPriceOracleStrategy::Percentile => {
+                self.percentile_price(&mut inner, &header).await?
+            }
+            GasPriceOracleStrategy::PendingPool => {
+                let price = self.percentile_price(&mut inner, &header).await?;
+                // the pending pool can only raise the estimate, an empty pool says nothing about
+                // the tips that get included
+                self.pending_tips
+                    .as_ref()
+                    .and_then(|pending_tips| pending_tips.marginal_pending_tip(header.gas_limit))
+                    .map_or(price, |pending_tip| price.max(U256::from(pending_tip)))
+            }
+            GasPriceOracleStrategy::Ewma => self.ewma_price(&mut inner, &header).await?,
+        };
+
+        // constrain to the max price
+        if let Some(max_price) = self.oracle_config.max_price {
+            if price > max_price {
+                price = max_price;
+            }
+        }
+
+        inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price };
+
+        Ok(price)
+    }
+
+    /// Inserts the lowest effective tips of new canonical blocks into the cache, so they don't
+    /// have to be read again when the next estimate is computed.
+    pub async fn insert_blocks<'a, I>(&self, blocks: I)
+    where
+        I: IntoIterator<Item = &'a SealedBlockWithSenders>,
+    {
+        let mut inner = self.inner.lock().await;
+        for block in blocks {
+            let Ok(block_values) = self.block_values(
+                block.header.header(),
+                &block.body,
+                &block.senders,
+                SAMPLE_NUMBER,
+            ) else {
+                continue
+            };
+            if self.oracle_config.strategy == GasPriceOracleStrategy::Ewma {
+                self.update_ewma(&mut inner, block.hash(), &block_values);
+            }
+            inner
+                .lowest_effective_tip_cache
+                .insert(block.hash(), (block.parent_hash, block_values));
+        }
+    }
+
+    /// Returns the configured percentile of the lowest effective tips of recent populated blocks.
+    async fn percentile_price(
+        &self,
+        inner: &mut GasPriceOracleInner,
+        header: &SealedHeader,
+    ) -> EthResult<U256> {
         // if all responses are empty, then we can return a maximum of 2*check_block blocks' worth
         // of prices
         //
@@ -144,21 +334,7 @@ where
         };
 
         for _ in 0..max_blocks {
-            // Check if current hash is in cache
-            let (parent_hash, block_values) =
-                if let Some(vals) = inner.lowest_effective_tip_cache.get(&current_hash) {
-                    vals.to_owned()
-                } else {
-                    // Otherwise we fetch it using get_block_values
-                    let (parent_hash, block_values) = self
-                        .get_block_values(current_hash, SAMPLE_NUMBER)
-                        .await?
-                        .ok_or(EthApiError::UnknownBlockNumber)?;
-                    inner
-                        .lowest_effective_tip_cache
-                        .insert(current_hash, (parent_hash, block_values.clone()));
-                    (parent_hash, block_values)
-                };
+            let (parent_hash, block_values) = self.cached_block_values(inner, current_hash).await?;
 
             if block_values.is_empty() {
                 results.push(U256::from(inner.last_price.price));
@@ -176,25 +352,72 @@ where
         }
 
         // sort results then take the configured percentile result
-        let mut price = if !results.is_empty() {
-            results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
-                "gas price index is a percent of nonzero array length, so a value always exists",
-            )
-        } else {
-            inner.last_price.price
-        };
+        Ok(percentile(&mut results, self.oracle_config.percentile)
+            .unwrap_or(inner.last_price.price))
+    }
 
-        // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
-            if price > max_price {
-                price = max_price;
-            }
+    /// Returns the moving average of the per-block percentiles, folding in the head block if it
+    /// hasn't been yet.
+    ///
+    /// The average is seeded with [`Self::percentile_price`].
+    async fn ewma_price(
+        &self,
+        inner: &mut GasPriceOracleInner,
+        header: &SealedHeader,
+    ) -> EthResult<U256> {
+        if inner.ewma.is_none() {
+            let price = self.percentile_price(inner, header).await?;
+            inner.ewma = Some(GasPriceOracleResult { block_hash: header.hash(), price });
+            return Ok(price)
         }
 
-        inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price };
+        let (_, block_values) = self.cached_block_values(inner, header.hash()).await?;
+        self.update_ewma(inner, header.hash(), &block_values);
 
-        Ok(price)
+        Ok(inner.ewma.as_ref().expect("is set").price)
+    }
+
+    /// Folds the percentile of the block's tips into the moving average, if the average is seeded
+    /// and the block is populated and not folded in already.
+    fn update_ewma(
+        &self,
+        inner: &mut GasPriceOracleInner,
+        block_hash: B256,
+        block_values: &[U256],
+    ) {
+        let Some(ewma) = inner.ewma.as_mut() else { return };
+        if ewma.block_hash == block_hash {
+            return
+        }
+        let Some(block_price) =
+            percentile(&mut block_values.to_vec(), self.oracle_config.percentile)
+        else {
+            return
+        };
+        *ewma = GasPriceOracleResult {
+            block_hash,
+            price: ewma_update(ewma.price, block_price, self.oracle_config.ewma_weight),
+        };
+    }
+
+    /// Returns the parent hash and the lowest effective tip values of the block from the cache,
+    /// reading the block if it's not cached.
+    async fn cached_block_values(
+        &self,
+        inner: &mut GasPriceOracleInner,
+        block_hash: B256,
+    ) -> EthResult<(B256, Vec<U256>)> {
+        if let Some(vals) = inner.lowest_effective_tip_cache.get(&block_hash) {
+            return Ok(vals.to_owned())
+        }
+
+        // Otherwise we fetch it using get_block_values
+        let (parent_hash, block_values) = self
+            .get_block_values(block_hash, SAMPLE_NUMBER)
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        inner.lowest_effective_tip_cache.insert(block_hash, (parent_hash, block_values.clone()));
+        Ok((parent_hash, block_values))
     }
 
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
@@ -210,20 +433,35 @@ where
         limit: usize,
     ) -> EthResult<Option<(B256, Vec<U256>)>> {
         // check the cache (this will hit the disk if the block is not cached)
-        let mut block = match self.cache.get_block(block_hash).await? {
+        let block = match self.cache.get_block_with_senders(block_hash).await? {
             Some(block) => block,
             None => return Ok(None),
         };
 
-        let base_fee_per_gas = block.base_fee_per_gas;
-        let parent_hash = block.parent_hash;
+        let prices =
+            self.block_values(&block.block.header, &block.block.body, &block.senders, limit)?;
+
+        Ok(Some((block.block.parent_hash, prices)))
+    }
+
+    /// Returns the `limit` lowest effective tip values of the given block's transactions, see
+    /// [`Self::get_block_values`].
+    fn block_values(
+        &self,
+        header: &Header,
+        body: &[TransactionSigned],
+        senders: &[Address],
+        limit: usize,
+    ) -> EthResult<Vec<U256>> {
+        let base_fee_per_gas = header.base_fee_per_gas;
 
         // sort the functions by ascending effective tip first
-        block.body.sort_by_cached_key(|tx| tx.effective_tip_per_gas(base_fee_per_gas));
+        let mut transactions = body.iter().zip(senders).collect::<Vec<_>>();
+        transactions.sort_by_cached_key(|(tx, _)| tx.effective_tip_per_gas(base_fee_per_gas));
 
         let mut prices = Vec::with_capacity(limit);
 
-        for tx in &block.body {
+        for (tx, sender) in transactions {
             let mut effective_gas_tip = None;
             // ignore transactions with a tip under the configured threshold
             if let Some(ignore_under) = self.ignore_price {
@@ -235,10 +473,8 @@ where
             }
 
             // check if the sender was the coinbase, if so, ignore
-            if let Some(sender) = tx.recover_signer() {
-                if sender == block.beneficiary {
-                    continue
-                }
+            if *sender == header.beneficiary {
+                continue
             }
 
             // a `None` effective_gas_tip represents a transaction where the max_fee_per_gas is
@@ -255,8 +491,45 @@ where
             }
         }
 
-        Ok(Some((parent_hash, prices)))
+        Ok(prices)
+    }
+}
+
+/// Awaits for new chain events and inserts the tips of the new blocks into the cache of the
+/// [`GasPriceOracle`], then computes the estimate for the new head so requests don't have to.
+pub async fn gas_price_oracle_new_blocks_task<St, Provider>(
+    oracle: GasPriceOracle<Provider>,
+    mut events: St,
+) where
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+    Provider: BlockReaderIdExt + 'static,
+{
+    // warm up the cache with the blocks of the current head
+    if let Err(err) = oracle.suggest_tip_cap().await {
+        debug!(target: "rpc::gas_oracle", %err, "Failed to compute initial gas price estimate");
     }
+
+    while let Some(event) = events.next().await {
+        oracle.insert_blocks(event.committed().blocks_iter()).await;
+        if let Err(err) = oracle.suggest_tip_cap().await {
+            debug!(target: "rpc::gas_oracle", %err, "Failed to compute gas price estimate");
+        }
+    }
+}
+
+/// Sorts the values and returns the given percentile of them, or `None` if there are none.
+fn percentile(values: &mut [U256], percentile: u32) -> Option<U256> {
+    if values.is_empty() {
+        return None
+    }
+    values.sort_unstable();
+    Some(values[(values.len() - 1) * percentile as usize / 100])
+}
+
+/// Returns the moving average updated with a new value of the given weight in percent.
+fn ewma_update(average: U256, value: U256, weight: u32) -> U256 {
+    let weight = U256::from(weight);
+    (value * weight + average * (U256::from(100) - weight)) / U256::from(100)
 }
 
 /// Container type for mutable inner state of the [`GasPriceOracle`]
@@ -264,6 +537,9 @@ where
 struct GasPriceOracleInner {
     last_price: GasPriceOracleResult,
     lowest_effective_tip_cache: EffectiveTipLruCache,
+    /// Moving average of the [`GasPriceOracleStrategy::Ewma`] strategy and the last block folded
+    /// into it
+    ewma: Option<GasPriceOracleResult>,
 }
 
 /// Wrapper struct for `LruMap`
@@ -318,4 +594,51 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn parse_strategy() {
+        for strategy in [
+            GasPriceOracleStrategy::Percentile,
+            GasPriceOracleStrategy::PendingPool,
+            GasPriceOracleStrategy::Ewma,
+        ] {
+            assert_eq!(strategy.to_string().parse::<GasPriceOracleStrategy>(), Ok(strategy));
+        }
+        assert!("median".parse::<GasPriceOracleStrategy>().is_err());
+    }
+
+    #[test]
+    fn config_without_strategy() {
+        let config: GasPriceOracleConfig = serde_json::from_str(
+            r#"{"blocks":20,"percentile":60,"maxHeaderHistory":1024,"maxBlockHistory":1024}"#,
+        )
+        .unwrap();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::Percentile);
+        assert_eq!(config.ewma_weight, DEFAULT_GAS_PRICE_EWMA_WEIGHT);
+
+        let config: GasPriceOracleConfig = serde_json::from_str(
+            r#"{"blocks":20,"percentile":60,"maxHeaderHistory":1024,"maxBlockHistory":1024,"strategy":"pending-pool"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::PendingPool);
+    }
+
+    #[test]
+    fn percentile_of_values() {
+        assert_eq!(percentile(&mut [], 60), None);
+
+        let mut values = [5, 1, 4, 2, 3].map(U256::from);
+        assert_eq!(percentile(&mut values, 0), Some(U256::from(1)));
+        assert_eq!(percentile(&mut values, 60), Some(U256::from(3)));
+        assert_eq!(percentile(&mut values, 100), Some(U256::from(5)));
+    }
+
+    #[test]
+    fn ewma_moves_towards_new_values() {
+        let average = U256::from(100);
+        assert_eq!(ewma_update(average, U256::from(200), 20), U256::from(120));
+        assert_eq!(ewma_update(average, U256::from(50), 20), U256::from(90));
+        assert_eq!(ewma_update(average, U256::from(200), 0), average);
+        assert_eq!(ewma_update(average, U256::from(200), 100), U256::from(200));
+    }
 }
@@ -19,20 +19,54 @@ impl jsonrpsee_core::traits::IdProvider for EthSubscriptionIdProvider {
     }
 }
 
+/// An [`IdProvider`](jsonrpsee_core::traits::IdProvider) for ids in a namespace.
+///
+/// Returns hex-string quantity ids like [`EthSubscriptionIdProvider`], preceded by a fixed prefix.
+/// This allows load balancers to route requests that reference an id, like filter polls, to the
+/// node that issued it.
+#[derive(Debug, Clone, Default)]
+pub struct PrefixedIdProvider {
+    prefix: String,
+}
+
+impl PrefixedIdProvider {
+    /// Creates a new provider for ids with the given prefix.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into() }
+    }
+
+    /// Returns the prefix of the ids.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+}
+
+impl jsonrpsee_core::traits::IdProvider for PrefixedIdProvider {
+    fn next_id(&self) -> SubscriptionId<'static> {
+        to_prefixed_quantity(&self.prefix, rand::random::<u128>())
+    }
+}
+
 /// Returns a hex quantity string for the given value
 ///
 /// Strips all leading zeros, `0` is returned as `0x0`
 #[inline(always)]
 fn to_quantity(val: u128) -> SubscriptionId<'static> {
+    to_prefixed_quantity("", val)
+}
+
+/// Returns a hex quantity string for the given value, preceded by the prefix.
+fn to_prefixed_quantity(prefix: &str, val: u128) -> SubscriptionId<'static> {
     let bytes = val.to_be_bytes();
     let b = bytes.as_slice();
     let non_zero = b.iter().take_while(|b| **b == 0).count();
     let b = &b[non_zero..];
     if b.is_empty() {
-        return SubscriptionId::Str("0x0".into())
+        return SubscriptionId::Str(format!("{prefix}0x0").into())
     }
 
-    let mut id = String::with_capacity(2 * b.len() + 2);
+    let mut id = String::with_capacity(prefix.len() + 2 * b.len() + 2);
+    id.push_str(prefix);
     id.push_str("0x");
     let first_byte = b[0];
     write!(id, "{first_byte:x}").unwrap();
@@ -69,4 +103,13 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_id_provider_prefix() {
+        assert_eq!(to_prefixed_quantity("node-1-", 0), SubscriptionId::Str("node-1-0x0".into()));
+        assert_eq!(
+            to_prefixed_quantity("node-1-", 0x1f2),
+            SubscriptionId::Str("node-1-0x1f2".into())
+        );
+    }
 }
@@ -15,22 +15,30 @@ pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod prestate;
 pub mod receipt;
 pub mod revm_utils;
+pub mod struct_log;
 pub mod transaction;
 pub mod utils;
 
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
+    config::EthStateCacheConfig,
+    db::StateCacheDb,
+    multi_consumer::MultiConsumerLruCache,
+    rpc_block::RpcBlockCache,
+    trace::{TraceCache, TraceCacheKey},
     EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
-    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
+    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, GasPriceOracleStrategy,
+    PendingTipProvider, UnknownGasPriceOracleStrategy, RPC_DEFAULT_GAS_CAP,
 };
-pub use id_provider::EthSubscriptionIdProvider;
+pub use id_provider::{EthSubscriptionIdProvider, PrefixedIdProvider};
 pub use logs_utils::EthFilterError;
-pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin, PendingBlockStats};
 pub use receipt::ReceiptBuilder;
+pub use struct_log::{StructLogInspector, StructLogLimits, StructLogTruncation};
 pub use transaction::TransactionSource;
@@ -9,6 +9,8 @@ use reth_chainspec::ChainSpec;
 use reth_primitives::{BlockId, BlockNumberOrTag, SealedBlockWithSenders, SealedHeader, B256};
 use reth_provider::ProviderError;
 use reth_revm::state_change::apply_blockhashes_update;
+use reth_rpc_types::{ExcludedTransaction, ExclusionReason};
+use reth_transaction_pool::{PoolTransaction, ValidPoolTransaction};
 use revm_primitives::{
     db::{Database, DatabaseCommit},
     BlockEnv, CfgEnvWithHandlerCfg,
@@ -118,6 +120,40 @@ impl PendingBlockEnvOrigin {
 pub struct PendingBlock {
     /// The cached pending block
     pub block: SealedBlockWithSenders,
+    /// The transactions the block builder didn't include and the reverted transactions
+    pub stats: PendingBlockStats,
     /// Timestamp when the pending block is considered outdated
     pub expires_at: Instant,
 }
+
+/// The pool transactions that the builder of a local pending block didn't include, and the
+/// included transactions that revert.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PendingBlockStats {
+    /// The hashes of the included transactions that revert.
+    pub reverted: Vec<B256>,
+    /// The transactions that the builder excluded.
+    ///
+    /// This doesn't contain the descendants of excluded transactions, which the builder skips,
+    /// nor private transactions.
+    pub excluded: Vec<ExcludedTransaction>,
+}
+
+impl PendingBlockStats {
+    /// Records that the transaction was excluded for the given reason, unless it is private.
+    pub fn exclude<T: PoolTransaction>(
+        &mut self,
+        tx: &ValidPoolTransaction<T>,
+        reason: ExclusionReason,
+    ) {
+        if tx.origin.is_private() {
+            return
+        }
+        self.excluded.push(ExcludedTransaction {
+            hash: *tx.hash(),
+            sender: tx.sender(),
+            nonce: tx.nonce(),
+            reason,
+        });
+    }
+}
@@ -0,0 +1,186 @@
+//! Helpers for the geth `prestateTracer`.
+
+use std::collections::BTreeMap;
+
+use reth_primitives::{Bytes, B256};
+use reth_rpc_types::trace::geth::{AccountState, DiffMode};
+use revm::primitives::{db::DatabaseRef, AccountInfo, EvmState};
+
+/// Builds the `diffMode` frame of the `prestateTracer` from the state changes of a transaction.
+///
+/// `db` must hold the state before the transaction. The frame matches the output of geth:
+///  - accounts and storage slots that were accessed but not changed are omitted
+///  - `pre` holds the balance, and the nonce and code if set, of every changed account, `post` only
+///    the changed fields
+///  - slots that were zero before are omitted from `pre`, slots that are zero after from `post`
+///  - destroyed accounts are kept in `pre` with all accessed slots and are omitted from `post`
+///  - created accounts are omitted from `pre` if they were empty, the code of created contracts is
+///    in `post`
+pub fn prestate_diff<DB: DatabaseRef>(state: &EvmState, db: &DB) -> Result<DiffMode, DB::Error> {
+    let mut diff = DiffMode { pre: BTreeMap::new(), post: BTreeMap::new() };
+
+    for (address, account) in state {
+        let pre_info = db.basic_ref(*address)?.unwrap_or_default();
+        let pre_code = code(&pre_info, db)?;
+        let mut pre = AccountState {
+            balance: Some(pre_info.balance),
+            code: (!pre_code.is_empty()).then(|| pre_code.clone()),
+            nonce: (pre_info.nonce != 0).then_some(pre_info.nonce),
+            storage: BTreeMap::new(),
+        };
+
+        if account.is_selfdestructed() {
+            pre.storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (B256::from(*slot), B256::from(value.original_value)))
+                .collect();
+        } else {
+            let mut post =
+                AccountState { balance: None, code: None, nonce: None, storage: BTreeMap::new() };
+            let mut modified = false;
+
+            if account.info.balance != pre_info.balance {
+                modified = true;
+                post.balance = Some(account.info.balance);
+            }
+            if account.info.nonce != pre_info.nonce {
+                modified = true;
+                post.nonce = Some(account.info.nonce);
+            }
+            let post_code = code(&account.info, db)?;
+            if post_code != pre_code {
+                modified = true;
+                post.code = (!post_code.is_empty()).then_some(post_code);
+            }
+            for (slot, value) in &account.storage {
+                if value.original_value == value.present_value {
+                    continue
+                }
+                modified = true;
+                let slot = B256::from(*slot);
+                if !value.original_value.is_zero() {
+                    pre.storage.insert(slot, B256::from(value.original_value));
+                }
+                if !value.present_value.is_zero() {
+                    post.storage.insert(slot, B256::from(value.present_value));
+                }
+            }
+
+            if !modified {
+                continue
+            }
+            diff.post.insert(*address, post);
+        }
+
+        let pre_empty = pre.storage.is_empty() &&
+            pre.code.is_none() &&
+            pre.nonce.is_none() &&
+            pre_info.balance.is_zero();
+        if !(account.is_created() && pre_empty) {
+            diff.pre.insert(*address, pre);
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Returns the code of the account, loading it from the database if it's not set.
+fn code<DB: DatabaseRef>(info: &AccountInfo, db: &DB) -> Result<Bytes, DB::Error> {
+    if info.is_empty_code_hash() {
+        return Ok(Bytes::new())
+    }
+    match &info.code {
+        Some(code) => Ok(code.original_bytes()),
+        None => Ok(db.code_by_hash_ref(info.code_hash)?.original_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, U256};
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{Account, Bytecode, EvmStorageSlot},
+    };
+
+    #[test]
+    fn diff_matches_geth() {
+        let sender = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let created = Address::with_last_byte(3);
+        let destroyed = Address::with_last_byte(4);
+        let read = Address::with_last_byte(5);
+
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let contract_info = AccountInfo::new(U256::from(7), 1, code.hash_slow(), code.clone());
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            sender,
+            AccountInfo { balance: U256::from(100), ..Default::default() },
+        );
+        db.insert_account_info(contract, contract_info.clone());
+        db.insert_account_info(destroyed, contract_info.clone());
+        db.insert_account_info(read, AccountInfo { balance: U256::from(1), ..Default::default() });
+
+        let mut state = EvmState::default();
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(90), nonce: 1, ..Default::default() });
+        account.mark_touch();
+        state.insert(sender, account);
+
+        let mut account = Account::from(contract_info.clone());
+        account.storage.insert(U256::from(1), EvmStorageSlot::new(U256::from(5)));
+        account
+            .storage
+            .insert(U256::from(2), EvmStorageSlot::new_changed(U256::from(5), U256::ZERO));
+        account
+            .storage
+            .insert(U256::from(3), EvmStorageSlot::new_changed(U256::ZERO, U256::from(6)));
+        account.mark_touch();
+        state.insert(contract, account);
+
+        let mut account = Account::from(AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code));
+        account.mark_touch();
+        account.mark_created();
+        state.insert(created, account);
+
+        let mut account = Account::from(contract_info);
+        account.storage.insert(U256::from(1), EvmStorageSlot::new_changed(U256::ZERO, U256::ZERO));
+        account.mark_touch();
+        account.mark_selfdestruct();
+        state.insert(destroyed, account);
+
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(1), ..Default::default() });
+        account.mark_touch();
+        state.insert(read, account);
+
+        let diff = prestate_diff(&state, &db).unwrap();
+        let slot = |i: u64| B256::from(U256::from(i));
+
+        assert_eq!(diff.pre.keys().copied().collect::<Vec<_>>(), vec![sender, contract, destroyed]);
+        assert_eq!(diff.post.keys().copied().collect::<Vec<_>>(), vec![sender, contract, created]);
+
+        // only the changed fields are in `post`
+        assert_eq!(diff.pre[&sender].balance, Some(U256::from(100)));
+        assert_eq!(diff.pre[&sender].nonce, None);
+        assert_eq!(diff.post[&sender].balance, Some(U256::from(90)));
+        assert_eq!(diff.post[&sender].nonce, Some(1));
+
+        // unchanged slots are elided, zero slots are omitted
+        assert_eq!(diff.pre[&contract].storage, BTreeMap::from([(slot(2), slot(5))]));
+        assert_eq!(diff.post[&contract].storage, BTreeMap::from([(slot(3), slot(6))]));
+        assert!(diff.pre[&contract].code.is_some());
+        assert_eq!(diff.post[&contract].balance, None);
+        assert_eq!(diff.post[&contract].code, None);
+
+        // the code of the created contract is in `post`
+        assert_eq!(diff.post[&created].code, Some(Bytes::from_static(&[0x60, 0x00])));
+
+        // the destroyed contract keeps its code and accessed slots in `pre`
+        assert!(diff.pre[&destroyed].code.is_some());
+        assert_eq!(diff.pre[&destroyed].storage, BTreeMap::from([(slot(1), B256::ZERO)]));
+    }
+}
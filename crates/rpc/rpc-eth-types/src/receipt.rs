@@ -1,6 +1,6 @@
 //! RPC receipt response builder, extends a layer one receipt with layer two data.
 
-use reth_primitives::{Address, Receipt, TransactionMeta, TransactionSigned, TxKind};
+use reth_primitives::{Address, Receipt, SealedBlock, TransactionMeta, TransactionSigned, TxKind};
 use reth_rpc_types::{
     AnyReceiptEnvelope, AnyTransactionReceipt, Log, OtherFields, ReceiptWithBloom,
     TransactionReceipt, WithOtherFields,
@@ -29,12 +29,6 @@ impl ReceiptBuilder {
         receipt: &Receipt,
         all_receipts: &[Receipt],
     ) -> EthResult<Self> {
-        // Note: we assume this transaction is valid, because it's mined (or part of pending block)
-        // and we don't need to check for pre EIP-2
-        let from = transaction
-            .recover_signer_unchecked()
-            .ok_or(EthApiError::InvalidTransactionSignature)?;
-
         // get the previous transaction cumulative gas used
         let gas_used = if meta.index == 0 {
             receipt.cumulative_gas_used
@@ -46,18 +40,36 @@ impl ReceiptBuilder {
                 .unwrap_or_default()
         };
 
-        let blob_gas_used = transaction.transaction.blob_gas_used();
-        // Blob gas price should only be present if the transaction is a blob transaction
-        let blob_gas_price =
-            blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
-        let logs_bloom = receipt.bloom_slow();
-
         // get number of logs in the block
         let mut num_logs = 0;
         for prev_receipt in all_receipts.iter().take(meta.index as usize) {
             num_logs += prev_receipt.logs.len();
         }
 
+        Self::with_block_position(transaction, meta, receipt, gas_used, num_logs)
+    }
+
+    /// Returns a new builder with the base response body (L1 fields) set, given the gas used by
+    /// the transaction and the number of logs of the preceding transactions in the block.
+    fn with_block_position(
+        transaction: &TransactionSigned,
+        meta: TransactionMeta,
+        receipt: &Receipt,
+        gas_used: u64,
+        num_logs: usize,
+    ) -> EthResult<Self> {
+        // Note: we assume this transaction is valid, because it's mined (or part of pending block)
+        // and we don't need to check for pre EIP-2
+        let from = transaction
+            .recover_signer_unchecked()
+            .ok_or(EthApiError::InvalidTransactionSignature)?;
+
+        let blob_gas_used = transaction.transaction.blob_gas_used();
+        // Blob gas price should only be present if the transaction is a blob transaction
+        let blob_gas_price =
+            blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
+        let logs_bloom = receipt.bloom_slow();
+
         let mut logs = Vec::with_capacity(receipt.logs.len());
         for (tx_log_idx, log) in receipt.logs.iter().enumerate() {
             let rpclog = Log {
@@ -109,6 +121,37 @@ impl ReceiptBuilder {
         Ok(Self { base, other: Default::default() })
     }
 
+    /// Returns the builders of the receipts of all transactions in the block.
+    ///
+    /// Unlike calling [`ReceiptBuilder::new`] for every transaction, this walks the receipts of the
+    /// block only once.
+    pub fn block_receipts(block: &SealedBlock, receipts: &[Receipt]) -> EthResult<Vec<Self>> {
+        let mut prev_cumulative_gas_used = 0;
+        let mut num_logs = 0;
+        block
+            .body
+            .iter()
+            .zip(receipts)
+            .enumerate()
+            .map(|(idx, (tx, receipt))| {
+                let meta = TransactionMeta {
+                    tx_hash: tx.hash,
+                    index: idx as u64,
+                    block_hash: block.hash(),
+                    block_number: block.number,
+                    base_fee: block.base_fee_per_gas,
+                    excess_blob_gas: block.excess_blob_gas,
+                    timestamp: block.timestamp,
+                };
+                let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+                let builder = Self::with_block_position(tx, meta, receipt, gas_used, num_logs);
+                prev_cumulative_gas_used = receipt.cumulative_gas_used;
+                num_logs += receipt.logs.len();
+                builder
+            })
+            .collect()
+    }
+
     /// Adds fields to response body.
     pub fn add_other_fields(mut self, mut fields: OtherFields) -> Self {
         self.other.append(&mut fields);
@@ -0,0 +1,356 @@
+//! Struct log tracing within server-side limits.
+
+use reth_primitives::{hex, Address, Bytes, Log, U256};
+use reth_rpc_server_types::constants::{
+    DEFAULT_STRUCT_LOG_MAX_MEMORY_BYTES, DEFAULT_STRUCT_LOG_MAX_STEPS,
+    DEFAULT_STRUCT_LOG_MAX_STORAGE_ENTRIES, DEFAULT_STRUCT_LOG_MAX_TOTAL_BYTES,
+};
+use reth_rpc_types::trace::geth::{DefaultFrame, GethDefaultTracingOptions, GethTrace};
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InstructionResult,
+        Interpreter,
+    },
+    primitives::EVMError,
+    Database, EvmContext, Inspector,
+};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::{Deserialize, Serialize};
+
+/// The size of a memory word, struct logs report the memory in words.
+const WORD_SIZE: usize = 32;
+
+/// Limits on what the struct log tracer, the default tracer of the `debug_` namespace, captures
+/// of a single transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructLogLimits {
+    /// The maximum number of steps that are recorded.
+    pub max_steps: usize,
+    /// The maximum number of memory bytes that are captured per step.
+    pub max_memory_bytes: usize,
+    /// The maximum number of storage entries that are reported per step.
+    pub max_storage_entries: usize,
+    /// The maximum number of memory and stack bytes that are captured across all steps. The
+    /// execution is aborted with an error once the captured bytes exceed it.
+    pub max_total_bytes: usize,
+}
+
+impl Default for StructLogLimits {
+    fn default() -> Self {
+        Self {
+            max_steps: DEFAULT_STRUCT_LOG_MAX_STEPS,
+            max_memory_bytes: DEFAULT_STRUCT_LOG_MAX_MEMORY_BYTES,
+            max_storage_entries: DEFAULT_STRUCT_LOG_MAX_STORAGE_ENTRIES,
+            max_total_bytes: DEFAULT_STRUCT_LOG_MAX_TOTAL_BYTES,
+        }
+    }
+}
+
+/// What the struct log tracer left out of a trace to stay within the [`StructLogLimits`].
+///
+/// Returned as the `truncated` field of the trace, if anything was left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructLogTruncation {
+    /// Whether the steps after the maximum number of steps are missing.
+    pub steps: bool,
+    /// Whether the memory of some steps is cut off.
+    pub memory: bool,
+    /// Whether the storage of some steps is cut off.
+    pub storage: bool,
+}
+
+impl StructLogTruncation {
+    /// Returns `true` if anything was left out.
+    pub const fn is_truncated(&self) -> bool {
+        self.steps || self.memory || self.storage
+    }
+}
+
+/// An inspector that records the struct logs of a transaction within the [`StructLogLimits`].
+///
+/// The steps are recorded by a [`TracingInspector`] until the maximum number of steps is reached.
+/// The memory is captured by this inspector instead, so that at most the maximum number of bytes
+/// is copied per step. If the memory and stack captured across all steps exceed the total budget,
+/// the execution is aborted with an [`EVMError::Custom`] error instead of returning a trace.
+#[derive(Debug)]
+pub struct StructLogInspector {
+    inner: TracingInspector,
+    limits: StructLogLimits,
+    /// Whether the memory is captured.
+    capture_memory: bool,
+    /// Whether the stack is captured.
+    capture_stack: bool,
+    /// The number of memory and stack bytes captured so far.
+    captured_bytes: usize,
+    /// The memory captured at each recorded step, with the full size of the memory.
+    memory: Vec<(Bytes, usize)>,
+    /// Whether the started steps that didn't end yet are recorded.
+    recording: Vec<bool>,
+    /// The number of recorded steps.
+    steps: usize,
+    truncation: StructLogTruncation,
+}
+
+impl StructLogInspector {
+    /// Creates a new inspector for the given tracing options.
+    pub fn new(config: &GethDefaultTracingOptions, limits: StructLogLimits) -> Self {
+        let mut inspector_config = TracingInspectorConfig::from_geth_config(config);
+        inspector_config.record_memory_snapshots = false;
+        Self {
+            inner: TracingInspector::new(inspector_config),
+            limits,
+            capture_memory: config.enable_memory.unwrap_or_default(),
+            capture_stack: !config.disable_stack.unwrap_or_default(),
+            captured_bytes: 0,
+            memory: Vec::new(),
+            recording: Vec::new(),
+            steps: 0,
+            truncation: StructLogTruncation::default(),
+        }
+    }
+
+    /// Builds the struct log trace of the transaction.
+    ///
+    /// If anything was left out, the trace has a `truncated` field with the
+    /// [`StructLogTruncation`].
+    pub fn into_geth_trace(
+        self,
+        gas_used: u64,
+        return_value: Bytes,
+        config: GethDefaultTracingOptions,
+    ) -> GethTrace {
+        let Self { inner, limits, capture_memory, memory, mut truncation, .. } = self;
+        let mut frame = inner.into_geth_builder().geth_traces(gas_used, return_value, config);
+
+        if capture_memory {
+            // the struct logs are in execution order, like the recorded steps
+            for (log, (memory, size)) in frame.struct_logs.iter_mut().zip(memory) {
+                log.memory = Some(memory.chunks(WORD_SIZE).map(hex::encode).collect());
+                log.memory_size = Some(size as u64);
+            }
+        }
+        for log in &mut frame.struct_logs {
+            if let Some(storage) = &mut log.storage {
+                if storage.len() > limits.max_storage_entries {
+                    *storage = std::mem::take(storage)
+                        .into_iter()
+                        .take(limits.max_storage_entries)
+                        .collect();
+                    truncation.storage = true;
+                }
+            }
+        }
+
+        if !truncation.is_truncated() {
+            return frame.into()
+        }
+        truncated_trace(frame, truncation)
+    }
+}
+
+/// Returns the trace with the `truncated` field.
+fn truncated_trace(frame: DefaultFrame, truncation: StructLogTruncation) -> GethTrace {
+    let Ok(serde_json::Value::Object(mut trace)) = serde_json::to_value(&frame) else {
+        return frame.into()
+    };
+    if let Ok(truncation) = serde_json::to_value(truncation) {
+        trace.insert("truncated".to_string(), truncation);
+    }
+    GethTrace::JS(trace.into())
+}
+
+impl<DB: Database> Inspector<DB> for StructLogInspector {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.initialize_interp(interp, context)
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.steps >= self.limits.max_steps {
+            self.truncation.steps = true;
+            self.recording.push(false);
+            return
+        }
+
+        // only whole words of the memory are captured
+        let max_memory_bytes = self.limits.max_memory_bytes / WORD_SIZE * WORD_SIZE;
+        let memory = interp.shared_memory.context_memory();
+        let memory_bytes = if self.capture_memory { memory.len().min(max_memory_bytes) } else { 0 };
+        let stack_bytes = if self.capture_stack { interp.stack.len() * WORD_SIZE } else { 0 };
+        self.captured_bytes += memory_bytes + stack_bytes;
+        if self.captured_bytes > self.limits.max_total_bytes {
+            // the step doesn't end, so it isn't recorded
+            context.error = Err(EVMError::Custom(format!(
+                "struct logs exceed the maximum size of {} bytes",
+                self.limits.max_total_bytes
+            )));
+            interp.instruction_result = InstructionResult::FatalExternalError;
+            return
+        }
+        self.steps += 1;
+        self.recording.push(true);
+
+        if self.capture_memory {
+            if memory.len() > max_memory_bytes {
+                self.truncation.memory = true;
+            }
+            let captured = Bytes::copy_from_slice(&memory[..memory_bytes]);
+            self.memory.push((captured, memory.len()));
+        }
+        self.inner.step(interp, context)
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.recording.pop().unwrap_or_default() {
+            self.inner.step_end(interp, context)
+        }
+    }
+
+    fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
+        self.inner.log(context, log)
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.inner.call(context, inputs)
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.inner.call_end(context, inputs, outcome)
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.create_end(context, inputs, outcome)
+    }
+
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.eofcreate(context, inputs)
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.eofcreate_end(context, inputs, outcome)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        <TracingInspector as Inspector<DB>>::selfdestruct(&mut self.inner, contract, target, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{address, TxKind};
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode, ExecutionResult, TxEnv},
+        Evm,
+    };
+
+    /// Traces a call to a contract that stores to memory and storage, or returns the error the
+    /// execution was aborted with.
+    fn try_trace(limits: StructLogLimits) -> Result<serde_json::Value, String> {
+        let contract = address!("0000000000000000000000000000000000001000");
+        // PUSH1 0x01 PUSH2 0x0100 MSTORE PUSH1 0x02 PUSH1 0x00 SSTORE PUSH1 0x03 PUSH1 0x01 SSTORE
+        // STOP
+        let code = Bytes::from_static(&[
+            0x60, 0x01, 0x61, 0x01, 0x00, 0x52, 0x60, 0x02, 0x60, 0x00, 0x55, 0x60, 0x03, 0x60,
+            0x01, 0x55, 0x00,
+        ]);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let config = GethDefaultTracingOptions::default().enable_memory();
+        let mut inspector = StructLogInspector::new(&config, limits);
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(&mut inspector)
+            .with_tx_env(TxEnv {
+                transact_to: TxKind::Call(contract),
+                gas_limit: 100_000,
+                ..Default::default()
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let res = evm.transact().map_err(|err| err.to_string())?;
+        drop(evm);
+        let ExecutionResult::Success { gas_used, .. } = res.result else { panic!("call failed") };
+
+        Ok(serde_json::to_value(inspector.into_geth_trace(gas_used, Bytes::new(), config)).unwrap())
+    }
+
+    fn trace(limits: StructLogLimits) -> serde_json::Value {
+        try_trace(limits).unwrap()
+    }
+
+    #[test]
+    fn truncates_struct_logs() {
+        let full = trace(StructLogLimits::default());
+        assert_eq!(full["structLogs"].as_array().unwrap().len(), 10);
+        assert!(full.get("truncated").is_none());
+
+        let truncated = trace(StructLogLimits {
+            max_steps: 4,
+            max_memory_bytes: 40,
+            max_storage_entries: 1,
+            ..Default::default()
+        });
+        let logs = truncated["structLogs"].as_array().unwrap();
+        assert_eq!(logs.len(), 4);
+        assert_eq!(
+            truncated["truncated"],
+            serde_json::json!({ "steps": true, "memory": true, "storage": false })
+        );
+        // the memory after the `MSTORE` at 0x100 is cut off after the first word
+        assert_eq!(logs[3]["memory"].as_array().unwrap().len(), 1);
+        assert_eq!(logs[3]["memSize"], 0x120);
+
+        // the second `SSTORE` reports both slots
+        let truncated = trace(StructLogLimits { max_storage_entries: 1, ..Default::default() });
+        assert_eq!(truncated["structLogs"].as_array().unwrap().len(), 10);
+        assert_eq!(
+            truncated["truncated"],
+            serde_json::json!({ "steps": false, "memory": false, "storage": true })
+        );
+    }
+
+    #[test]
+    fn aborts_above_total_bytes() {
+        // the memory after the `MSTORE` at 0x100 alone is 0x120 bytes
+        let err = try_trace(StructLogLimits { max_total_bytes: 0x100, ..Default::default() })
+            .unwrap_err();
+        assert!(err.contains("struct logs exceed the maximum size of 256 bytes"), "{err}");
+    }
+}
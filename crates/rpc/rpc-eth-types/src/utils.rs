@@ -8,12 +8,33 @@ use super::{EthApiError, EthResult};
 ///
 /// See [`PooledTransactionsElement::decode_enveloped`]
 pub fn recover_raw_transaction(data: Bytes) -> EthResult<PooledTransactionsElementEcRecovered> {
+    decode_raw_transaction(data)?
+        .try_into_ecrecovered()
+        .or(Err(EthApiError::InvalidTransactionSignature))
+}
+
+/// Recovers a batch of [`PooledTransactionsElementEcRecovered`] from enveloped encoded byte
+/// streams, preserving their order.
+///
+/// Senders of large batches are recovered in parallel, see
+/// [`PooledTransactionsElement::try_into_ecrecovered_batch`].
+pub fn recover_raw_transactions(
+    data: Vec<Bytes>,
+) -> EthResult<Vec<PooledTransactionsElementEcRecovered>> {
+    let transactions = data.into_iter().map(decode_raw_transaction).collect::<EthResult<_>>()?;
+
+    PooledTransactionsElement::try_into_ecrecovered_batch(transactions)
+        .into_iter()
+        .map(|tx| tx.or(Err(EthApiError::InvalidTransactionSignature)))
+        .collect()
+}
+
+/// Decodes a [`PooledTransactionsElement`] from an enveloped encoded byte stream.
+fn decode_raw_transaction(data: Bytes) -> EthResult<PooledTransactionsElement> {
     if data.is_empty() {
         return Err(EthApiError::EmptyRawTransactionData)
     }
 
-    let transaction = PooledTransactionsElement::decode_enveloped(&mut data.as_ref())
-        .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)?;
-
-    transaction.try_into_ecrecovered().or(Err(EthApiError::InvalidTransactionSignature))
+    PooledTransactionsElement::decode_enveloped(&mut data.as_ref())
+        .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)
 }
@@ -1,15 +1,25 @@
 use crate::{AuthValidator, JwtError, JwtSecret};
 use http::{header, HeaderMap, Response, StatusCode};
 use jsonrpsee_http_client::{HttpBody, HttpResponse};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
 use tracing::error;
 
 /// Implements JWT validation logics and integrates
 /// to an Http [`AuthLayer`][crate::AuthLayer]
 /// by implementing the [`AuthValidator`] trait.
+///
+/// Tokens signed with the secret or any of the additional secrets are accepted, which allows
+/// rotating the secret without rejecting the tokens of the consensus layer in between.
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct JwtAuthValidator {
     secret: JwtSecret,
+    additional_secrets: Option<JwtSecrets>,
+    claims: JwtClaimsValidation,
 }
 
 impl JwtAuthValidator {
@@ -17,14 +27,40 @@ impl JwtAuthValidator {
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
     pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self { secret, additional_secrets: None, claims: JwtClaimsValidation::new() }
+    }
+
+    /// Also accepts the tokens signed with any of the additional secrets.
+    pub fn with_additional_secrets(mut self, secrets: JwtSecrets) -> Self {
+        self.additional_secrets = Some(secrets);
+        self
+    }
+
+    /// Sets the claims the tokens must carry in addition to the issuance timestamp.
+    pub fn with_claims(mut self, claims: JwtClaimsValidation) -> Self {
+        self.claims = claims;
+        self
+    }
+
+    /// Validates the token with the first secret it was signed with.
+    fn validate_token(&self, jwt: &str) -> Result<(), String> {
+        let additional = self.additional_secrets.as_ref().map(JwtSecrets::get).unwrap_or_default();
+        let mut result = Err(JwtError::InvalidSignature);
+        for secret in std::iter::once(self.secret).chain(additional) {
+            result = secret.validate(jwt).map(|_| secret);
+            if !matches!(result, Err(JwtError::InvalidSignature)) {
+                break
+            }
+        }
+        let secret = result.map_err(|err| err.to_string())?;
+        self.claims.validate(&secret, jwt)
     }
 }
 
 impl AuthValidator for JwtAuthValidator {
     fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
         match get_bearer(headers) {
-            Some(jwt) => match self.secret.validate(&jwt) {
+            Some(jwt) => match self.validate_token(&jwt) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
@@ -42,9 +78,120 @@ impl AuthValidator for JwtAuthValidator {
     }
 }
 
+/// A set of JWT secrets that can be replaced while it's in use.
+///
+/// Clones share the same set, so the secrets accepted by a running [`JwtAuthValidator`] can be
+/// updated through a clone of the set it was created with.
+#[derive(Clone, Default)]
+pub struct JwtSecrets {
+    secrets: Arc<RwLock<Vec<JwtSecret>>>,
+}
+
+impl JwtSecrets {
+    /// Creates a new set with the given secrets.
+    pub fn new(secrets: Vec<JwtSecret>) -> Self {
+        Self { secrets: Arc::new(RwLock::new(secrets)) }
+    }
+
+    /// Returns the current secrets.
+    pub fn get(&self) -> Vec<JwtSecret> {
+        self.secrets.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Replaces the secrets.
+    pub fn set(&self, secrets: Vec<JwtSecret>) {
+        *self.secrets.write().unwrap_or_else(|err| err.into_inner()) = secrets;
+    }
+
+    /// Returns the number of secrets.
+    pub fn len(&self) -> usize {
+        self.secrets.read().unwrap_or_else(|err| err.into_inner()).len()
+    }
+
+    /// Returns `true` if the set doesn't contain any secret.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for JwtSecrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the secrets themselves
+        f.debug_struct("JwtSecrets").field("len", &self.len()).finish()
+    }
+}
+
+/// The optional claims a JWT must carry to be accepted.
+///
+/// The engine API spec only requires the `iat` claim, which is always validated. If issuers or
+/// audiences are configured, the token must carry an `iss` claim that matches one of the issuers,
+/// or an `aud` claim that contains one of the audiences respectively.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JwtClaimsValidation {
+    /// The accepted issuers, any issuer is accepted if empty.
+    pub issuers: Vec<String>,
+    /// The accepted audiences, any audience is accepted if empty.
+    pub audiences: Vec<String>,
+}
+
+impl JwtClaimsValidation {
+    /// Creates a new instance that doesn't validate any additional claims.
+    pub const fn new() -> Self {
+        Self { issuers: Vec::new(), audiences: Vec::new() }
+    }
+
+    /// Sets the accepted issuers.
+    pub fn with_issuers(mut self, issuers: Vec<String>) -> Self {
+        self.issuers = issuers;
+        self
+    }
+
+    /// Sets the accepted audiences.
+    pub fn with_audiences(mut self, audiences: Vec<String>) -> Self {
+        self.audiences = audiences;
+        self
+    }
+
+    /// Returns `true` if no additional claims are validated.
+    pub fn is_empty(&self) -> bool {
+        self.issuers.is_empty() && self.audiences.is_empty()
+    }
+
+    /// Validates the claims of a token that was signed with the secret.
+    fn validate(&self, secret: &JwtSecret, jwt: &str) -> Result<(), String> {
+        if self.is_empty() {
+            return Ok(())
+        }
+
+        // the signature and the issuance timestamp were already validated
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = false;
+        validation.required_spec_claims.clear();
+        if !self.issuers.is_empty() {
+            validation.set_issuer(&self.issuers);
+            validation.required_spec_claims.insert("iss".to_string());
+        }
+        if self.audiences.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&self.audiences);
+            validation.required_spec_claims.insert("aud".to_string());
+        }
+
+        jsonwebtoken::decode::<serde_json::Value>(
+            jwt,
+            &DecodingKey::from_secret(secret.as_bytes()),
+            &validation,
+        )
+        .map(|_| ())
+        .map_err(|err| format!("invalid JWT claims: {err}"))
+    }
+}
+
 /// This is an utility function that retrieves a bearer
 /// token from an authorization Http header.
-fn get_bearer(headers: &HeaderMap) -> Option<String> {
+/// Returns the bearer token of the authorization header, if any.
+pub fn get_bearer(headers: &HeaderMap) -> Option<String> {
     let header = headers.get(header::AUTHORIZATION)?;
     let auth: &str = header.to_str().ok()?;
     let prefix = "Bearer ";
@@ -53,7 +200,7 @@ fn get_bearer(headers: &HeaderMap) -> Option<String> {
     Some(token.into())
 }
 
-fn err_response(err: JwtError) -> HttpResponse {
+fn err_response(err: impl fmt::Display) -> HttpResponse {
     // We build a response from an error message.
     // We don't cope with headers or other structured fields.
     // Then we are safe to "expect" on the result.
@@ -65,8 +212,24 @@ fn err_response(err: JwtError) -> HttpResponse {
 
 #[cfg(test)]
 mod tests {
-    use crate::jwt_validator::get_bearer;
-    use http::{header, HeaderMap};
+    use super::*;
+    use crate::Claims;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn headers(jwt: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {jwt}").parse().unwrap());
+        headers
+    }
+
+    fn token(secret: &JwtSecret, claims: serde_json::Value) -> String {
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(Algorithm::HS256),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
 
     #[test]
     fn auth_header_available() {
@@ -94,4 +257,51 @@ mod tests {
         let token = get_bearer(&headers);
         assert!(token.is_none());
     }
+
+    #[test]
+    fn accepts_additional_secrets() {
+        let secret = JwtSecret::random();
+        let rotated = JwtSecret::random();
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let claims = Claims { iat, exp: None };
+
+        let additional = JwtSecrets::default();
+        let validator = JwtAuthValidator::new(secret).with_additional_secrets(additional.clone());
+        assert!(validator.validate(&headers(&secret.encode(&claims).unwrap())).is_ok());
+        assert!(validator.validate(&headers(&rotated.encode(&claims).unwrap())).is_err());
+
+        // the set can be updated while the validator is in use
+        additional.set(vec![JwtSecret::random(), rotated]);
+        assert!(validator.validate(&headers(&secret.encode(&claims).unwrap())).is_ok());
+        assert!(validator.validate(&headers(&rotated.encode(&claims).unwrap())).is_ok());
+
+        // a matching signature with an invalid timestamp is still rejected
+        let stale = Claims { iat: iat - 3600, exp: None };
+        assert!(validator.validate(&headers(&rotated.encode(&stale).unwrap())).is_err());
+    }
+
+    #[test]
+    fn validates_issuer_and_audience() {
+        let secret = JwtSecret::random();
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let validator = JwtAuthValidator::new(secret).with_claims(
+            JwtClaimsValidation::new()
+                .with_issuers(vec!["lighthouse".to_string(), "prysm".to_string()])
+                .with_audiences(vec!["reth".to_string()]),
+        );
+
+        let valid = token(&secret, serde_json::json!({"iat": iat, "iss": "prysm", "aud": "reth"}));
+        assert!(validator.validate(&headers(&valid)).is_ok());
+
+        let missing_aud = token(&secret, serde_json::json!({"iat": iat, "iss": "prysm"}));
+        assert!(validator.validate(&headers(&missing_aud)).is_err());
+
+        let wrong_iss =
+            token(&secret, serde_json::json!({"iat": iat, "iss": "teku", "aud": ["reth"]}));
+        assert!(validator.validate(&headers(&wrong_iss)).is_err());
+
+        // without configured claims, any issuer and audience is accepted
+        let validator = JwtAuthValidator::new(secret);
+        assert!(validator.validate(&headers(&wrong_iss)).is_ok());
+    }
 }
@@ -26,6 +26,19 @@ pub fn default_max_tracing_requests() -> usize {
         .map_or(25, |cpus| max(cpus.get().saturating_sub(RESERVED), RESERVED))
 }
 
+/// The default maximum number of steps the struct log tracer records per transaction.
+pub const DEFAULT_STRUCT_LOG_MAX_STEPS: usize = 1_000_000;
+
+/// The default maximum number of memory bytes the struct log tracer captures per step.
+pub const DEFAULT_STRUCT_LOG_MAX_MEMORY_BYTES: usize = 64 * 1024;
+
+/// The default maximum number of storage entries the struct log tracer reports per step.
+pub const DEFAULT_STRUCT_LOG_MAX_STORAGE_ENTRIES: usize = 1024;
+
+/// The default maximum number of memory and stack bytes the struct log tracer captures per
+/// transaction.
+pub const DEFAULT_STRUCT_LOG_MAX_TOTAL_BYTES: usize = 512 * 1024 * 1024;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -48,6 +61,9 @@ pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 /// Maximum eth historical proof window. Equivalent to roughly one month of data.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 216_000;
 
+/// The default number of times forwarding a raw transaction to the upstream endpoint is retried.
+pub const DEFAULT_TX_FORWARD_MAX_RETRIES: u32 = 3;
+
 /// GPO specific constants
 pub mod gas_oracle {
     use alloy_primitives::U256;
@@ -64,6 +80,10 @@ pub mod gas_oracle {
     /// The percentile of gas prices to use for the estimate
     pub const DEFAULT_GAS_PRICE_PERCENTILE: u32 = 60;
 
+    /// The weight in percent of the newest block in the exponentially weighted moving average of
+    /// gas prices
+    pub const DEFAULT_GAS_PRICE_EWMA_WEIGHT: u32 = 20;
+
     /// Maximum transaction priority fee (or gas price before London Fork) to be recommended by the
     /// gas price oracle
     pub const DEFAULT_MAX_GAS_PRICE: U256 = U256::from_limbs([500_000_000_000u64, 0, 0, 0]);
@@ -109,6 +129,12 @@ pub mod cache {
     /// Default cache size for the env cache: 1000 envs.
     pub const DEFAULT_ENV_CACHE_MAX_LEN: u32 = 1000;
 
+    /// Default cache size for the cache of assembled `eth_getBlockBy*` responses: 128 blocks.
+    pub const DEFAULT_RPC_BLOCK_CACHE_MAX_LEN: u32 = 128;
+
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default memory budget for the trace cache: 64MB.
+    pub const DEFAULT_TRACE_CACHE_SIZE_BYTES_MB: usize = 64;
 }
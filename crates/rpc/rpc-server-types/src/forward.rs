@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+
+/// How transactions submitted via `eth_sendRawTransaction` are handled when an upstream endpoint
+/// is configured.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    AsRefStr,
+    Display,
+    EnumString,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum TxForwardMode {
+    /// Transactions are forwarded to the upstream endpoint in the background and submitted to the
+    /// local pool.
+    #[default]
+    Mirror,
+    /// Transactions are only forwarded to the upstream endpoint and not submitted to the local
+    /// pool. Forwarding errors are returned to the caller.
+    ForwardOnly,
+}
+
+impl TxForwardMode {
+    /// Returns `true` if transactions are not submitted to the local pool.
+    pub const fn is_forward_only(&self) -> bool {
+        matches!(self, Self::ForwardOnly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_forward_mode() {
+        assert_eq!("mirror".parse::<TxForwardMode>().unwrap(), TxForwardMode::Mirror);
+        assert_eq!("forward-only".parse::<TxForwardMode>().unwrap(), TxForwardMode::ForwardOnly);
+        assert_eq!(TxForwardMode::ForwardOnly.to_string(), "forward-only");
+        assert!("forward".parse::<TxForwardMode>().is_err());
+    }
+}
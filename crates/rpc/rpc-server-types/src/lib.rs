@@ -12,6 +12,9 @@
 pub mod constants;
 pub mod result;
 
+mod forward;
+pub use forward::TxForwardMode;
+
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
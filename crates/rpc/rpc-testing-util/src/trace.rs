@@ -35,7 +35,7 @@ pub type CallManyTraceResult = Result<
 >;
 
 /// Result type for the `trace_get` method that also captures the requested transaction hash and
-/// index.
+/// trace address.
 pub type TraceGetResult =
     Result<(Option<LocalizedTransactionTrace>, B256, Vec<Index>), (RpcError, B256, Vec<Index>)>;
 
@@ -96,10 +96,11 @@ pub trait TraceApiExt {
     where
         I: IntoIterator<Item = (TransactionRequest, HashSet<TraceType>)>;
 
-    /// Returns a new stream that yields the traces for the given transaction hash and indices.
-    fn trace_get_stream<I>(&self, hash: B256, indices: I) -> TraceGetStream<'_>
+    /// Returns a new stream that yields the traces of the given transaction at the given trace
+    /// addresses.
+    fn trace_get_stream<I>(&self, hash: B256, trace_addresses: I) -> TraceGetStream<'_>
     where
-        I: IntoIterator<Item = Index>;
+        I: IntoIterator<Item = Vec<Index>>;
 
     /// Returns a new stream that yields traces for given filters.
     fn trace_filter_stream<I>(&self, filters: I) -> TraceFilterStream<'_>
@@ -326,18 +327,19 @@ impl<T: TraceApiClient + Sync> TraceApiExt for T {
         CallManyTraceStream { stream: Box::pin(stream) }
     }
 
-    fn trace_get_stream<I>(&self, hash: B256, indices: I) -> TraceGetStream<'_>
+    fn trace_get_stream<I>(&self, hash: B256, trace_addresses: I) -> TraceGetStream<'_>
     where
-        I: IntoIterator<Item = Index>,
+        I: IntoIterator<Item = Vec<Index>>,
     {
-        let index_list = indices.into_iter().collect::<Vec<_>>();
-        let stream = futures::stream::iter(index_list.into_iter().map(move |index| async move {
-            match self.trace_get(hash, vec![index]).await {
-                Ok(result) => Ok((result, hash, vec![index])),
-                Err(err) => Err((err, hash, vec![index])),
-            }
-        }))
-        .buffered(10);
+        let address_list = trace_addresses.into_iter().collect::<Vec<_>>();
+        let stream =
+            futures::stream::iter(address_list.into_iter().map(move |address| async move {
+                match self.trace_get(hash, address.clone()).await {
+                    Ok(result) => Ok((result, hash, address)),
+                    Err(err) => Err((err, hash, address)),
+                }
+            }))
+            .buffered(10);
         TraceGetStream { stream: Box::pin(stream) }
     }
 
@@ -600,9 +602,10 @@ mod tests {
 
         let tx_hash: B256 = "".parse().unwrap();
 
-        let indices: Vec<Index> = vec![Index::from(0)];
+        // the top-level call and the first call made by it
+        let trace_addresses: Vec<Vec<Index>> = vec![vec![], vec![Index::from(0)]];
 
-        let mut stream = client.trace_get_stream(tx_hash, indices);
+        let mut stream = client.trace_get_stream(tx_hash, trace_addresses);
 
         while let Some(result) = stream.next().await {
             match result {
@@ -3,7 +3,8 @@
 use crate::transaction::from_recovered_with_block_context;
 use alloy_rlp::Encodable;
 use reth_primitives::{
-    Block as PrimitiveBlock, BlockWithSenders, Header as PrimitiveHeader, Withdrawals, B256, U256,
+    Block as PrimitiveBlock, BlockWithSenders, Header as PrimitiveHeader, Requests, SealedHeader,
+    Withdrawals, B256, U256,
 };
 use reth_rpc_types::{Block, BlockError, BlockTransactions, BlockTransactionsKind, Header};
 
@@ -90,6 +91,43 @@ pub fn from_block_full(
     ))
 }
 
+/// Create a new [Block] response from the parts of a block whose transactions were converted one
+/// by one, e.g. while they are read from disk, instead of from a [`BlockWithSenders`].
+///
+/// The size of the block is computed from `transactions_length`, the sum of the RLP encoded
+/// lengths of the transactions of the block.
+pub fn from_block_parts(
+    header: SealedHeader,
+    total_difficulty: U256,
+    transactions: BlockTransactions,
+    transactions_length: usize,
+    ommers: Vec<PrimitiveHeader>,
+    withdrawals: Option<Withdrawals>,
+    requests: Option<Requests>,
+) -> Block {
+    let (header, block_hash) = header.split();
+    let block = PrimitiveBlock { header, body: Vec::new(), ommers, withdrawals, requests };
+    let block_length = block_length_with_transactions(&block, transactions_length);
+
+    from_block_with_transactions(block_length, block_hash, block, total_difficulty, transactions)
+}
+
+/// Returns the RLP encoded length of the block without transactions, once the transactions with
+/// the given total length are added.
+fn block_length_with_transactions(block: &PrimitiveBlock, transactions_length: usize) -> usize {
+    let mut encoded = Vec::with_capacity(block.length());
+    block.encode(&mut encoded);
+    let empty_payload_length = alloy_rlp::Header::decode(&mut encoded.as_slice())
+        .expect("encoded block starts with a list header")
+        .payload_length;
+
+    let transactions_header = alloy_rlp::Header { list: true, payload_length: transactions_length };
+    // the empty list of transactions is replaced
+    let payload_length =
+        empty_payload_length - 1 + transactions_header.length() + transactions_length;
+    alloy_rlp::Header { list: true, payload_length }.length() + payload_length
+}
+
 /// Converts from a [`reth_primitives::SealedHeader`] to a [`reth_rpc_types::Header`]
 ///
 /// # Note
@@ -181,14 +219,56 @@ fn from_block_with_transactions(
 pub fn uncle_block_from_header(header: PrimitiveHeader) -> Block {
     let hash = header.hash_slow();
     let rpc_header = from_primitive_with_hash(header.clone().seal(hash));
+    let withdrawals = header.withdrawals_root.is_some().then(Vec::new);
     let uncle_block = PrimitiveBlock { header, ..Default::default() };
     let size = Some(U256::from(uncle_block.length()));
     Block {
         uncles: vec![],
         header: rpc_header,
         transactions: BlockTransactions::Uncle,
-        withdrawals: Some(vec![]),
+        withdrawals,
         size,
         other: Default::default(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{TransactionSigned, Withdrawal};
+
+    #[test]
+    fn block_parts_size() {
+        for transactions in [0, 1, 100] {
+            let block = PrimitiveBlock {
+                body: vec![TransactionSigned::default(); transactions],
+                withdrawals: Some(Withdrawals::new(vec![Withdrawal::default()])),
+                ..Default::default()
+            };
+            let expected = block.length();
+            let transactions_length = block.body.iter().map(Encodable::length).sum();
+
+            let header = block.header.clone().seal_slow();
+            let rpc_block = from_block_parts(
+                header,
+                U256::ZERO,
+                BlockTransactions::Hashes(block.body.iter().map(|tx| tx.hash()).collect()),
+                transactions_length,
+                block.ommers,
+                block.withdrawals,
+                block.requests,
+            );
+            assert_eq!(rpc_block.size, Some(U256::from(expected)));
+        }
+    }
+
+    #[test]
+    fn pre_shanghai_uncle_has_no_withdrawals() {
+        let uncle = uncle_block_from_header(PrimitiveHeader { number: 1, ..Default::default() });
+        assert_eq!(uncle.withdrawals, None);
+        assert_eq!(uncle.transactions, BlockTransactions::Uncle);
+
+        let header = PrimitiveHeader { withdrawals_root: Some(B256::ZERO), ..Default::default() };
+        assert_eq!(uncle_block_from_header(header).withdrawals, Some(Vec::new()));
+    }
+}
@@ -4,12 +4,13 @@
 use reth_primitives::{
     constants::{EMPTY_OMMER_ROOT_HASH, MAXIMUM_EXTRA_DATA_SIZE},
     proofs::{self},
-    Block, Header, Request, SealedBlock, TransactionSigned, UintTryTo, Withdrawals, B256, U256,
+    Block, Header, Request, Requests, SealedBlock, TransactionSigned, TransactionSignedNoHash,
+    UintTryTo, Withdrawals, B256, U256,
 };
 use reth_rpc_types::engine::{
     payload::{ExecutionPayloadBodyV1, ExecutionPayloadFieldV2, ExecutionPayloadInputV2},
-    ExecutionPayload, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
-    ExecutionPayloadV4, PayloadError,
+    DepositRequest, ExecutionPayload, ExecutionPayloadBodyV2, ExecutionPayloadV1,
+    ExecutionPayloadV2, ExecutionPayloadV3, ExecutionPayloadV4, PayloadError, WithdrawalRequest,
 };
 
 /// Converts [`ExecutionPayloadV1`] to [Block]
@@ -212,22 +213,7 @@ pub fn block_to_payload_v3(value: SealedBlock) -> (ExecutionPayloadV3, Option<B2
 /// Converts [`SealedBlock`] to [`ExecutionPayloadV4`]
 pub fn block_to_payload_v4(mut value: SealedBlock) -> ExecutionPayloadV4 {
     let (deposit_requests, withdrawal_requests) =
-        value.requests.take().unwrap_or_default().into_iter().fold(
-            (Vec::new(), Vec::new()),
-            |(mut deposits, mut withdrawals), request| {
-                match request {
-                    Request::DepositRequest(r) => {
-                        deposits.push(r);
-                    }
-                    Request::WithdrawalRequest(r) => {
-                        withdrawals.push(r);
-                    }
-                    _ => {}
-                };
-
-                (deposits, withdrawals)
-            },
-        );
+        split_requests(value.requests.take().unwrap_or_default());
 
     ExecutionPayloadV4 {
         deposit_requests,
@@ -236,6 +222,26 @@ pub fn block_to_payload_v4(mut value: SealedBlock) -> ExecutionPayloadV4 {
     }
 }
 
+/// Splits the [`Requests`] of a block into its deposit and withdrawal requests.
+fn split_requests(requests: Requests) -> (Vec<DepositRequest>, Vec<WithdrawalRequest>) {
+    requests.into_iter().fold(
+        (Vec::new(), Vec::new()),
+        |(mut deposits, mut withdrawals), request| {
+            match request {
+                Request::DepositRequest(r) => {
+                    deposits.push(r);
+                }
+                Request::WithdrawalRequest(r) => {
+                    withdrawals.push(r);
+                }
+                _ => {}
+            };
+
+            (deposits, withdrawals)
+        },
+    )
+}
+
 /// Converts [`SealedBlock`] to [`ExecutionPayloadFieldV2`]
 pub fn convert_block_to_payload_field_v2(value: SealedBlock) -> ExecutionPayloadFieldV2 {
     // if there are withdrawals, return V2
@@ -368,6 +374,29 @@ pub fn convert_to_payload_body_v1(value: Block) -> ExecutionPayloadBodyV1 {
     }
 }
 
+/// Converts the transactions, withdrawals and requests of a block body into
+/// [`ExecutionPayloadBodyV2`].
+///
+/// The transactions are encoded into their EIP-2718 envelopes without computing their hashes.
+pub fn convert_to_payload_body_v2(
+    transactions: Vec<TransactionSignedNoHash>,
+    withdrawals: Option<Withdrawals>,
+    requests: Option<Requests>,
+) -> ExecutionPayloadBodyV2 {
+    let transactions = transactions.into_iter().map(|tx| {
+        let mut out = Vec::new();
+        tx.transaction.encode_with_signature(&tx.signature, &mut out, false);
+        out.into()
+    });
+    let (deposit_requests, withdrawal_requests) = requests.map(split_requests).unzip();
+    ExecutionPayloadBodyV2 {
+        transactions: transactions.collect(),
+        withdrawals: withdrawals.map(Withdrawals::into_inner),
+        deposit_requests,
+        withdrawal_requests,
+    }
+}
+
 /// Transforms a [`SealedBlock`] into a [`ExecutionPayloadV1`]
 pub fn execution_payload_from_sealed_block(value: SealedBlock) -> ExecutionPayloadV1 {
     let transactions = value.raw_transactions();
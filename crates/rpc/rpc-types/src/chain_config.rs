@@ -0,0 +1,149 @@
+//! Types for the chain configuration of the `reth` namespace.
+
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The chain configuration a node runs with, including the hardfork overrides applied by the
+/// node.
+///
+/// Returned by `reth_chainConfig`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveChainConfig {
+    /// The chain id.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub chain_id: u64,
+    /// The hash of the genesis block.
+    pub genesis_hash: B256,
+    /// The activation of every configured hardfork, in fork order.
+    ///
+    /// Forks that are never activated are omitted.
+    pub hardforks: Vec<HardforkActivation>,
+    /// The blob parameters of the forks that change them, in fork order.
+    ///
+    /// Empty if blob transactions are never activated on the chain.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blob_schedule: Vec<BlobSchedule>,
+    /// The deposit contract of the chain, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_contract: Option<DepositContractConfig>,
+    /// The hardfork activations overridden by the node, which are already applied to
+    /// `hardforks`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<HardforkActivation>,
+}
+
+/// The activation of a hardfork.
+///
+/// Exactly one of the block, timestamp or total difficulty is set, except for forks activated by
+/// total difficulty whose activation block is also known.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardforkActivation {
+    /// The name of the fork.
+    pub name: String,
+    /// The block number at which the fork is activated.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_rpc_types::serde_helpers::quantity::opt"
+    )]
+    pub block: Option<u64>,
+    /// The timestamp at which the fork is activated.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_rpc_types::serde_helpers::quantity::opt"
+    )]
+    pub timestamp: Option<u64>,
+    /// The total difficulty after which the fork is activated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_difficulty: Option<U256>,
+}
+
+/// The parameters of blob transactions from a fork on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobSchedule {
+    /// The name of the fork that activates the parameters.
+    pub fork: String,
+    /// The target number of blobs per block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub target_blobs_per_block: u64,
+    /// The maximum number of blobs per block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub max_blobs_per_block: u64,
+    /// The blob gas used by a single blob.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub blob_gas_per_blob: u64,
+    /// The denominator of the blob base fee update.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub base_fee_update_fraction: u128,
+    /// The minimum blob base fee.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub min_blob_base_fee: u128,
+}
+
+/// The deposit contract of the beacon chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositContractConfig {
+    /// The address of the deposit contract.
+    pub address: Address,
+    /// The block the deposit contract was deployed at.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub block: u64,
+    /// The signature of the `DepositEvent` event.
+    pub topic: B256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_effective_chain_config() {
+        let config = EffectiveChainConfig {
+            chain_id: 1,
+            genesis_hash: B256::with_last_byte(1),
+            hardforks: vec![
+                HardforkActivation {
+                    name: "Frontier".to_string(),
+                    block: Some(0),
+                    ..Default::default()
+                },
+                HardforkActivation {
+                    name: "Paris".to_string(),
+                    block: Some(15_537_394),
+                    total_difficulty: Some(U256::from(0x10)),
+                    ..Default::default()
+                },
+                HardforkActivation {
+                    name: "Cancun".to_string(),
+                    timestamp: Some(0x10),
+                    ..Default::default()
+                },
+            ],
+            blob_schedule: vec![BlobSchedule {
+                fork: "Cancun".to_string(),
+                target_blobs_per_block: 3,
+                max_blobs_per_block: 6,
+                blob_gas_per_blob: 131_072,
+                base_fee_update_fraction: 3_338_477,
+                min_blob_base_fee: 1,
+            }],
+            deposit_contract: None,
+            overrides: vec![HardforkActivation {
+                name: "Cancun".to_string(),
+                timestamp: Some(0x10),
+                ..Default::default()
+            }],
+        };
+        let s = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            s,
+            r#"{"chainId":"0x1","genesisHash":"0x0000000000000000000000000000000000000000000000000000000000000001","hardforks":[{"name":"Frontier","block":"0x0"},{"name":"Paris","block":"0xed14f2","totalDifficulty":"0x10"},{"name":"Cancun","timestamp":"0x10"}],"blobSchedule":[{"fork":"Cancun","targetBlobsPerBlock":"0x3","maxBlobsPerBlock":"0x6","blobGasPerBlob":"0x20000","baseFeeUpdateFraction":"0x32f0ed","minBlobBaseFee":"0x1"}],"overrides":[{"name":"Cancun","timestamp":"0x10"}]}"#
+        );
+        assert_eq!(serde_json::from_str::<EffectiveChainConfig>(&s).unwrap(), config);
+    }
+}
@@ -0,0 +1,61 @@
+//! Engine API types.
+
+pub use alloy_eips::{eip6110::DepositRequest, eip7002::WithdrawalRequest};
+pub use alloy_rpc_types_engine::*;
+
+use alloy_primitives::Bytes;
+use alloy_rpc_types::Withdrawal;
+use serde::{Deserialize, Serialize};
+
+/// The body of an execution payload, including the requests of the block.
+///
+/// See also: <https://github.com/ethereum/execution-apis/blob/main/src/engine/prague.md#executionpayloadbodyv2>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadBodyV2 {
+    /// The EIP-2718 encoded transactions of the block.
+    pub transactions: Vec<Bytes>,
+    /// The withdrawals of the block, `None` before Shanghai.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The deposit requests of the block, `None` before Prague.
+    pub deposit_requests: Option<Vec<DepositRequest>>,
+    /// The withdrawal requests of the block, `None` before Prague.
+    pub withdrawal_requests: Option<Vec<WithdrawalRequest>>,
+}
+
+impl From<ExecutionPayloadBodyV2> for ExecutionPayloadBodyV1 {
+    fn from(body: ExecutionPayloadBodyV2) -> Self {
+        Self { transactions: body.transactions, withdrawals: body.withdrawals }
+    }
+}
+
+/// The execution payload bodies of the response of `engine_getPayloadBodiesByRangeV2`.
+///
+/// Blocks that are unknown are `None`.
+pub type ExecutionPayloadBodiesV2 = Vec<Option<ExecutionPayloadBodyV2>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_payload_body_v2() {
+        let body = ExecutionPayloadBodyV2 {
+            transactions: vec![Bytes::from_static(&[0x02, 0xc0])],
+            withdrawals: Some(Vec::new()),
+            deposit_requests: None,
+            withdrawal_requests: None,
+        };
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "transactions": ["0x02c0"],
+                "withdrawals": [],
+                "depositRequests": null,
+                "withdrawalRequests": null,
+            })
+        );
+        assert_eq!(serde_json::from_value::<ExecutionPayloadBodyV2>(json).unwrap(), body);
+    }
+}
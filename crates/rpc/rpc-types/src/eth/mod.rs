@@ -1,7 +1,5 @@
 //! Ethereum related types
 
+pub mod engine;
 pub(crate) mod error;
 pub mod transaction;
-
-// re-export
-pub use alloy_rpc_types_engine as engine;
@@ -9,11 +9,22 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
+mod chain_config;
 #[allow(hidden_glob_reexports)]
 mod eth;
 mod mev;
 mod peer;
+mod pending_report;
+mod pool_blocks;
+mod receipts;
+mod reload;
+mod reorg;
 mod rpc;
+mod sync;
+mod tip;
+#[allow(hidden_glob_reexports)]
+pub mod txpool;
+mod validation;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -38,9 +49,6 @@ pub use alloy_rpc_types_beacon as beacon;
 // re-export admin
 pub use alloy_rpc_types_admin as admin;
 
-// re-export txpool
-pub use alloy_rpc_types_txpool as txpool;
-
 // Ethereum specific rpc types related to typed transaction requests and the engine API.
 pub use eth::{
     engine,
@@ -51,6 +59,15 @@ pub use eth::{
     transaction::{self, TransactionRequest, TypedTransactionRequest},
 };
 
+pub use chain_config::*;
 pub use mev::*;
 pub use peer::*;
+pub use pending_report::*;
+pub use pool_blocks::*;
+pub use receipts::*;
+pub use reload::*;
+pub use reorg::*;
 pub use rpc::*;
+pub use sync::*;
+pub use tip::*;
+pub use validation::*;
@@ -0,0 +1,103 @@
+//! Types for the report of the local pending block of the `reth` namespace.
+
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The transactions of the pool that the locally built pending block doesn't include, and the
+/// included transactions that revert.
+///
+/// Returned by `reth_pendingBlockReport`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlockReport {
+    /// The number of the pending block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub number: u64,
+    /// The hash of the parent of the pending block.
+    pub parent_hash: B256,
+    /// The base fee per gas of the pending block.
+    pub base_fee_per_gas: U256,
+    /// The blob fee per blob gas of the pending block, if blobs are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_fee_per_gas: Option<U256>,
+    /// The gas used by the pending block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub gas_used: u64,
+    /// The gas limit of the pending block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub gas_limit: u64,
+    /// The hashes of the transactions included in the pending block.
+    pub included: Vec<B256>,
+    /// The hashes of the included transactions that revert.
+    pub reverted: Vec<B256>,
+    /// The transactions of the pool that are not included in the pending block.
+    pub excluded: Vec<ExcludedTransaction>,
+}
+
+/// A transaction of the pool that is not included in the pending block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcludedTransaction {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub nonce: u64,
+    /// Why the transaction is not included.
+    #[serde(flatten)]
+    pub reason: ExclusionReason,
+}
+
+/// The reason a transaction of the pool is not included in the pending block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum ExclusionReason {
+    /// The max fee per gas is below the base fee of the block.
+    BaseFee,
+    /// The max fee per blob gas is below the blob fee of the block.
+    BlobFee,
+    /// The gas limit of the transaction exceeds the gas left in the block.
+    GasLimit,
+    /// The blobs of the transaction exceed the blob gas left in the block.
+    BlobGasLimit,
+    /// The nonce is lower than the nonce of the sender.
+    NonceTooLow,
+    /// The transaction waits for a transaction with a lower nonce of the same sender, or the
+    /// sender can't pay for it.
+    NonceGap,
+    /// A transaction with a lower nonce of the same sender is excluded.
+    ExcludedAncestor,
+    /// The execution of the transaction failed.
+    Invalid {
+        /// The error of the execution.
+        error: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_excluded_transaction() {
+        let tx = ExcludedTransaction {
+            hash: B256::with_last_byte(1),
+            sender: Address::with_last_byte(2),
+            nonce: 3,
+            reason: ExclusionReason::Invalid { error: "lack of funds".to_string() },
+        };
+        let s = serde_json::to_string(&tx).unwrap();
+        assert_eq!(
+            s,
+            r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","sender":"0x0000000000000000000000000000000000000002","nonce":"0x3","reason":"invalid","error":"lack of funds"}"#
+        );
+        assert_eq!(serde_json::from_str::<ExcludedTransaction>(&s).unwrap(), tx);
+
+        let tx = ExcludedTransaction { reason: ExclusionReason::BlobGasLimit, ..tx };
+        let s = serde_json::to_string(&tx).unwrap();
+        assert!(s.ends_with(r#""reason":"blobGasLimit"}"#));
+        assert_eq!(serde_json::from_str::<ExcludedTransaction>(&s).unwrap(), tx);
+    }
+}
@@ -0,0 +1,101 @@
+//! Types for simulating the next blocks from the transaction pool of the `reth` namespace.
+
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The block attributes the transactions of the pool are ordered for.
+///
+/// Attributes that are not given are taken from the pending block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolBlocksAttributes {
+    /// The base fee per gas of the blocks.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_rpc_types::serde_helpers::quantity::opt"
+    )]
+    pub base_fee_per_gas: Option<u64>,
+    /// The blob fee per blob gas of the blocks.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_rpc_types::serde_helpers::quantity::opt"
+    )]
+    pub blob_fee_per_gas: Option<u64>,
+    /// The gas limit of the blocks.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_rpc_types::serde_helpers::quantity::opt"
+    )]
+    pub gas_limit: Option<u64>,
+}
+
+/// A hypothetical block filled with the best transactions of the pool, in the order a block
+/// builder would include them.
+///
+/// Returned by `reth_contentAsBlocks`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolBlock {
+    /// The sum of the gas limits of the transactions.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub gas_used: u64,
+    /// The blob gas of the transactions.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub blob_gas_used: u64,
+    /// The transactions of the block, in order.
+    pub transactions: Vec<PoolBlockTransaction>,
+}
+
+/// A transaction of a [`PoolBlock`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolBlockTransaction {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub nonce: u64,
+    /// The gas limit of the transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub gas_limit: u64,
+    /// The priority fee per gas the transaction pays at the base fee of the block.
+    pub effective_tip_per_gas: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_pool_blocks() {
+        let attributes: PoolBlocksAttributes =
+            serde_json::from_str(r#"{"baseFeePerGas":"0x7"}"#).unwrap();
+        assert_eq!(
+            attributes,
+            PoolBlocksAttributes { base_fee_per_gas: Some(7), ..Default::default() }
+        );
+
+        let block = PoolBlock {
+            gas_used: 21_000,
+            blob_gas_used: 0,
+            transactions: vec![PoolBlockTransaction {
+                hash: B256::with_last_byte(1),
+                sender: Address::with_last_byte(2),
+                nonce: 3,
+                gas_limit: 21_000,
+                effective_tip_per_gas: U256::from(10),
+            }],
+        };
+        let s = serde_json::to_string(&block).unwrap();
+        assert_eq!(
+            s,
+            r#"{"gasUsed":"0x5208","blobGasUsed":"0x0","transactions":[{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","sender":"0x0000000000000000000000000000000000000002","nonce":"0x3","gasLimit":"0x5208","effectiveTipPerGas":"0xa"}]}"#
+        );
+        assert_eq!(serde_json::from_str::<PoolBlock>(&s).unwrap(), block);
+    }
+}
@@ -0,0 +1,21 @@
+//! Types for the receipts of block ranges of the `reth` namespace.
+
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types::AnyTransactionReceipt;
+use serde::{Deserialize, Serialize};
+
+/// The receipts of all transactions of a block.
+///
+/// Returned by `reth_getReceiptsByBlockRange` and emitted by the
+/// `reth_subscribeReceiptsByBlockRange` subscription.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptsByBlock {
+    /// Number of the block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub block_number: BlockNumber,
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Receipts of the transactions, in the order of the transactions in the block.
+    pub receipts: Vec<AnyTransactionReceipt>,
+}
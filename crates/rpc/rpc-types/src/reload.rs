@@ -0,0 +1,23 @@
+//! Types for reloading the node configuration with `admin_reloadConfig`.
+
+use serde::{Deserialize, Serialize};
+
+/// The outcome of reloading the configuration file of a running node.
+///
+/// Settings are identified by their dotted path in the configuration file, e.g.
+/// `peers.connection_info.max_inbound`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigReloadReport {
+    /// The changed settings that were applied to the running node.
+    pub applied: Vec<String>,
+    /// The changed settings that only take effect once the node is restarted.
+    pub requires_restart: Vec<String>,
+}
+
+impl ConfigReloadReport {
+    /// Returns `true` if no setting changed.
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.requires_restart.is_empty()
+    }
+}
@@ -0,0 +1,53 @@
+//! Types for the reorg journal of the `reth` namespace.
+
+use alloy_primitives::{BlockNumber, B256};
+use serde::{Deserialize, Serialize};
+
+/// A reorg of the canonical chain, as recorded by the node's reorg journal.
+///
+/// Returned by `reth_getReorgHistory` and emitted by the `reth_subscribeReorgs` subscription.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgEvent {
+    /// Sequence number of the reorg in the journal.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub id: u64,
+    /// Unix timestamp in seconds at which the reorg was committed.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub timestamp: u64,
+    /// Number of reverted blocks.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub depth: u64,
+    /// Number of the last block the old and the new chain have in common.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub fork_block_number: BlockNumber,
+    /// Hash of the last block the old and the new chain have in common.
+    pub fork_block_hash: B256,
+    /// Hashes of the reverted blocks, in ascending order.
+    pub old_blocks: Vec<B256>,
+    /// Hashes of the committed blocks, in ascending order.
+    pub new_blocks: Vec<B256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_reorg_event() {
+        let event = ReorgEvent {
+            id: 1,
+            timestamp: 1_700_000_000,
+            depth: 1,
+            fork_block_number: 100,
+            fork_block_hash: B256::with_last_byte(1),
+            old_blocks: vec![B256::with_last_byte(2)],
+            new_blocks: vec![B256::with_last_byte(3), B256::with_last_byte(4)],
+        };
+        let s = serde_json::to_string(&event).unwrap();
+        assert!(s.starts_with(
+            r#"{"id":"0x1","timestamp":"0x6553f100","depth":"0x1","forkBlockNumber":"0x64","#
+        ));
+        assert_eq!(serde_json::from_str::<ReorgEvent>(&s).unwrap(), event);
+    }
+}
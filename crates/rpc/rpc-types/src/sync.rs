@@ -0,0 +1,51 @@
+//! Types for the sync progress of the `reth` namespace.
+
+use alloy_primitives::BlockNumber;
+use serde::{Deserialize, Serialize};
+
+/// The progress of the pipeline sync of the node, returned by `reth_syncProgress`.
+///
+/// Rates and estimates are based on the checkpoints the stages committed recently, and are absent
+/// until enough checkpoints were committed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    /// The stage the pipeline is currently executing, if any.
+    pub current_stage: Option<String>,
+    /// The progress of every stage that ran since the node started, in the order of the
+    /// pipeline.
+    pub stages: Vec<StageProgress>,
+    /// The size of the data directory in bytes.
+    pub disk_usage: Option<u64>,
+    /// The recent growth of the data directory in bytes per second.
+    pub disk_growth_per_second: Option<f64>,
+    /// The size of the data directory in bytes once the current stage finished, projected from
+    /// the recent growth.
+    pub projected_disk_usage: Option<u64>,
+}
+
+/// The progress of a single stage of the pipeline.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageProgress {
+    /// The name of the stage.
+    pub stage: String,
+    /// The block number of the last committed checkpoint of the stage.
+    pub checkpoint: BlockNumber,
+    /// The block number the stage syncs to, if known.
+    pub target: Option<BlockNumber>,
+    /// Whether the stage reached its target.
+    pub done: bool,
+    /// The number of processed entities, e.g. headers or gas, if the stage reports them.
+    ///
+    /// Otherwise the progress is measured in blocks.
+    pub processed: Option<u64>,
+    /// The total number of entities to process, if the stage reports them.
+    pub total: Option<u64>,
+    /// The recent number of processed entities, or blocks, per second.
+    pub throughput: Option<f64>,
+    /// The remaining number of entities, or blocks, to process.
+    pub remaining: Option<u64>,
+    /// The estimated number of seconds until the stage reaches its target.
+    pub eta_seconds: Option<u64>,
+}
@@ -0,0 +1,48 @@
+//! Types for tip suggestions of the `reth` namespace.
+
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// The minimal priority fee a transaction has to pay to be included within a number of blocks.
+///
+/// Returned by `reth_suggestTipForInclusion`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TipForInclusion {
+    /// The suggested priority fee per gas.
+    pub max_priority_fee_per_gas: U256,
+    /// The base fee per gas of the pending block the transaction was simulated against.
+    pub base_fee_per_gas: U256,
+    /// The gas the transaction used in the simulation.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub gas_used: u64,
+    /// Number of blocks the transaction should be included within.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub blocks: u64,
+    /// Number of pending transactions that pay a higher tip and fit into the blocks before the
+    /// transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub transactions_ahead: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_tip_for_inclusion() {
+        let tip = TipForInclusion {
+            max_priority_fee_per_gas: U256::from(1_000_000_001),
+            base_fee_per_gas: U256::from(7),
+            gas_used: 21_000,
+            blocks: 2,
+            transactions_ahead: 10,
+        };
+        let s = serde_json::to_string(&tip).unwrap();
+        assert_eq!(
+            s,
+            r#"{"maxPriorityFeePerGas":"0x3b9aca01","baseFeePerGas":"0x7","gasUsed":"0x5208","blocks":"0x2","transactionsAhead":"0xa"}"#
+        );
+        assert_eq!(serde_json::from_str::<TipForInclusion>(&s).unwrap(), tip);
+    }
+}
@@ -0,0 +1,135 @@
+//! Types for the `txpool` namespace.
+
+pub use alloy_rpc_types_txpool::*;
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// Server side filter and pagination options for `txpool_content`, `txpool_contentFrom` and
+/// `txpool_inspect`.
+///
+/// Transactions are ordered by sender and nonce. Pagination is applied to the pending and the
+/// queued transactions separately, after filtering.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolFilter {
+    /// Only include transactions sent by one of these addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub senders: Option<Vec<Address>>,
+    /// Only include transactions of one of these [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_types: Option<Vec<u8>>,
+    /// Only include transactions with a max fee per gas of at least this value.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_max_fee_per_gas: Option<u128>,
+    /// Only include transactions with a max fee per gas of at most this value.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_max_fee_per_gas: Option<u128>,
+    /// Number of matching transactions to skip.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub offset: Option<u64>,
+    /// Maximum number of transactions to return.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub limit: Option<u64>,
+}
+
+impl TxpoolFilter {
+    /// Returns `true` if a transaction with the given properties passes the filter.
+    ///
+    /// Pagination is not taken into account.
+    pub fn matches(&self, sender: &Address, tx_type: u8, max_fee_per_gas: u128) -> bool {
+        if self.senders.as_ref().is_some_and(|senders| !senders.contains(sender)) {
+            return false
+        }
+        if self.tx_types.as_ref().is_some_and(|types| !types.contains(&tx_type)) {
+            return false
+        }
+        if self.min_max_fee_per_gas.is_some_and(|min| max_fee_per_gas < min) {
+            return false
+        }
+        if self.max_max_fee_per_gas.is_some_and(|max| max_fee_per_gas > max) {
+            return false
+        }
+        true
+    }
+
+    /// Returns the number of matching transactions to skip.
+    pub fn offset(&self) -> usize {
+        self.offset.unwrap_or_default() as usize
+    }
+
+    /// Returns the maximum number of transactions to return.
+    pub fn limit(&self) -> usize {
+        self.limit.map_or(usize::MAX, |limit| limit as usize)
+    }
+}
+
+/// The sender and recipient lists of the transaction policy of the pool, see
+/// `admin_setTxpoolPolicy`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TxpoolPolicy {
+    /// Senders whose transactions are always accepted, this takes precedence over all other
+    /// lists.
+    pub allowed_senders: Vec<Address>,
+    /// Senders whose transactions are rejected.
+    pub denied_senders: Vec<Address>,
+    /// Recipients that transactions are rejected for.
+    pub denied_recipients: Vec<Address>,
+    /// Whether transactions of senders that are not in `allowed_senders` are rejected.
+    pub reject_unlisted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_filter() {
+        let s = r#"{"senders":["0x0000000000000000000000000000000000000001"],"txTypes":[2,3],"minMaxFeePerGas":"0x3b9aca00","limit":"0x64"}"#;
+        let filter: TxpoolFilter = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            filter,
+            TxpoolFilter {
+                senders: Some(vec![Address::with_last_byte(1)]),
+                tx_types: Some(vec![2, 3]),
+                min_max_fee_per_gas: Some(1_000_000_000),
+                limit: Some(100),
+                ..Default::default()
+            }
+        );
+        assert_eq!(serde_json::to_string(&filter).unwrap(), s);
+    }
+
+    #[test]
+    fn filter_matches() {
+        let filter = TxpoolFilter {
+            tx_types: Some(vec![2]),
+            min_max_fee_per_gas: Some(10),
+            max_max_fee_per_gas: Some(20),
+            ..Default::default()
+        };
+        let sender = Address::with_last_byte(1);
+        assert!(filter.matches(&sender, 2, 15));
+        assert!(!filter.matches(&sender, 0, 15));
+        assert!(!filter.matches(&sender, 2, 9));
+        assert!(!filter.matches(&sender, 2, 21));
+        assert!(TxpoolFilter::default().matches(&sender, 3, 0));
+    }
+}
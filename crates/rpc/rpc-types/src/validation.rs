@@ -0,0 +1,113 @@
+//! Types for standalone block validation of the `reth` namespace and block submission validation
+//! of the `flashbots` namespace.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_beacon::relay::SignedBidSubmissionV3;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// The result of validating a block with `reth_validateBlock`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockValidationResult {
+    /// Hash of the validated block.
+    pub block_hash: B256,
+    /// Number of the validated block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub block_number: u64,
+    /// Whether all checks passed.
+    pub valid: bool,
+    /// The results of the individual checks, in the order they were run.
+    pub checks: Vec<BlockValidationCheck>,
+}
+
+/// The result of a single check of `reth_validateBlock`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockValidationCheck {
+    /// The check that was run.
+    pub check: BlockValidationStep,
+    /// The outcome of the check.
+    pub status: BlockValidationStatus,
+    /// The reason the check failed or was skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The checks run by `reth_validateBlock`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockValidationStep {
+    /// Consensus rules of the header on its own.
+    Header,
+    /// Consensus rules of the header against its parent.
+    HeaderAgainstParent,
+    /// Consensus rules of the block that don't require execution, e.g. the transactions root.
+    PreExecution,
+    /// Recovery of the transaction senders.
+    SenderRecovery,
+    /// Execution of the block on top of the parent state.
+    Execution,
+    /// Consensus rules that depend on the execution output, e.g. gas used and receipts root.
+    PostExecution,
+    /// The state root of the post-state.
+    StateRoot,
+}
+
+/// A request to validate a Cancun block submission with `flashbots_validateBuilderSubmissionV3`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuilderBlockValidationRequestV3 {
+    /// The submission to validate.
+    #[serde(flatten)]
+    pub request: SignedBidSubmissionV3,
+    /// The gas limit registered by the proposer.
+    #[serde_as(as = "DisplayFromStr")]
+    pub registered_gas_limit: u64,
+    /// The parent beacon block root of the block, which isn't part of the execution payload.
+    pub parent_beacon_block_root: B256,
+}
+
+/// The outcome of a check of `reth_validateBlock`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockValidationStatus {
+    /// The check passed.
+    Passed,
+    /// The check failed.
+    Failed,
+    /// The check could not be run because a check it depends on failed.
+    Skipped,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_block_validation_result() {
+        let result = BlockValidationResult {
+            block_hash: B256::ZERO,
+            block_number: 16,
+            valid: false,
+            checks: vec![
+                BlockValidationCheck {
+                    check: BlockValidationStep::HeaderAgainstParent,
+                    status: BlockValidationStatus::Passed,
+                    error: None,
+                },
+                BlockValidationCheck {
+                    check: BlockValidationStep::StateRoot,
+                    status: BlockValidationStatus::Failed,
+                    error: Some("state root mismatch".to_string()),
+                },
+            ],
+        };
+        let s = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            s,
+            r#"{"blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x10","valid":false,"checks":[{"check":"headerAgainstParent","status":"passed"},{"check":"stateRoot","status":"failed","error":"state root mismatch"}]}"#
+        );
+        assert_eq!(serde_json::from_str::<BlockValidationResult>(&s).unwrap(), result);
+    }
+}
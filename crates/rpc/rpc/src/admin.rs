@@ -1,18 +1,28 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use alloy_genesis::ChainConfig;
 use alloy_primitives::B256;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::ChainSpec;
-use reth_network_api::{NetworkInfo, PeerKind, Peers};
-use reth_network_peers::{id2pk, AnyNode, NodeRecord};
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
-use reth_rpc_types::admin::{
-    EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
-    Ports, ProtocolInfo,
+use reth_network_api::{AdminPeerInfo, BandwidthWeights, NetworkInfo, PeerKind, PeerStats, Peers};
+use reth_network_peers::{id2pk, AnyNode, NodeRecord, PeerId};
+use reth_rpc_api::{AdminApiServer, AdminConfigApiServer, AdminTxPoolApiServer};
+use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_types::{
+    admin::{
+        EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo,
+        PeerProtocolInfo, Ports, ProtocolInfo,
+    },
+    txpool::TxpoolPolicy,
+    ConfigReloadReport,
 };
+use reth_transaction_pool::{
+    blob_proofs::BlobProofComputation,
+    policy::{AddressListPolicy, AddressLists},
+    PoolConfig,
+};
+use tracing::trace;
 
 /// `admin` API implementation.
 ///
@@ -64,13 +74,22 @@ where
     }
 
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
-        let mut infos = Vec::with_capacity(peers.len());
+        let mut static_peers = self
+            .network
+            .get_static_peers()
+            .await
+            .to_rpc_result()?
+            .into_iter()
+            .map(|(record, health)| (record.id, (record, health)))
+            .collect::<HashMap<_, _>>();
+        let mut infos = Vec::with_capacity(peers.len() + static_peers.len());
 
         for peer in peers {
+            let static_health = static_peers.remove(&peer.remote_id).map(|(_, health)| health);
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -95,13 +114,45 @@ where
                         snap: None,
                         other: Default::default(),
                     },
-                })
+                };
+                infos.push(AdminPeerInfo { info, static_health })
+            }
+        }
+
+        // the configured static nodes that are not connected
+        for (record, health) in static_peers.into_values() {
+            if let Ok(pk) = id2pk(record.id) {
+                let info = PeerInfo {
+                    id: pk.to_string(),
+                    name: String::new(),
+                    enode: record.to_string(),
+                    enr: None,
+                    caps: Vec::new(),
+                    network: PeerNetworkInfo {
+                        remote_address: record.tcp_addr(),
+                        local_address: self.network.local_addr(),
+                        inbound: false,
+                        trusted: false,
+                        static_node: true,
+                    },
+                    protocols: PeerProtocolInfo {
+                        eth: None,
+                        snap: None,
+                        other: Default::default(),
+                    },
+                };
+                infos.push(AdminPeerInfo { info, static_health: Some(health) })
             }
         }
 
         Ok(infos)
     }
 
+    /// Handler for `admin_peerStats`
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>> {
+        self.network.get_peer_stats().await.to_rpc_result()
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -145,6 +196,16 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         Err("admin_peerEvents is not implemented yet".into())
     }
+
+    /// Handler for `admin_bandwidthWeights`
+    fn bandwidth_weights(&self) -> RpcResult<Option<BandwidthWeights>> {
+        Ok(self.network.bandwidth_weights())
+    }
+
+    /// Handler for `admin_setBandwidthWeights`
+    fn set_bandwidth_weights(&self, weights: BandwidthWeights) -> RpcResult<bool> {
+        Ok(self.network.set_bandwidth_weights(weights))
+    }
 }
 
 impl<N> std::fmt::Debug for AdminApi<N> {
@@ -152,3 +213,111 @@ impl<N> std::fmt::Debug for AdminApi<N> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// Reloads the configuration of a running node.
+pub trait ConfigReloader: Send + Sync {
+    /// Reloads the configuration file and applies the changed settings that can be changed at
+    /// runtime.
+    fn reload(&self) -> Result<ConfigReloadReport, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Adds the peer to or removes it from the protected peers, and persists the protected peers
+    /// to the configuration file.
+    ///
+    /// Returns `false` if the peer already was or wasn't protected.
+    fn set_peer_protected(
+        &self,
+        peer_id: PeerId,
+        protected: bool,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// `admin` API implementation for changing the configuration of the running node.
+pub struct AdminConfigApi {
+    /// Reloads the configuration of the node.
+    reloader: Arc<dyn ConfigReloader>,
+}
+
+impl AdminConfigApi {
+    /// Creates a new instance of `AdminConfigApi`.
+    pub const fn new(reloader: Arc<dyn ConfigReloader>) -> Self {
+        Self { reloader }
+    }
+}
+
+#[async_trait]
+impl AdminConfigApiServer for AdminConfigApi {
+    /// Handler for `admin_reloadConfig`
+    async fn reload_config(&self) -> RpcResult<ConfigReloadReport> {
+        self.reloader.reload().map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `admin_protectPeer`
+    async fn protect_peer(&self, record: AnyNode) -> RpcResult<bool> {
+        self.reloader
+            .set_peer_protected(record.peer_id(), true)
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `admin_unprotectPeer`
+    async fn unprotect_peer(&self, record: AnyNode) -> RpcResult<bool> {
+        self.reloader
+            .set_peer_protected(record.peer_id(), false)
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+impl std::fmt::Debug for AdminConfigApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminConfigApi").finish_non_exhaustive()
+    }
+}
+
+/// `admin` API implementation for changing the transaction pool settings of the running node.
+#[derive(Clone)]
+pub struct AdminTxPoolApi {
+    /// The sender and recipient lists of the transaction policy of the pool.
+    transaction_policy: AddressListPolicy,
+    /// Whether the missing proofs of blob sidecars are computed.
+    blob_proof_computation: BlobProofComputation,
+}
+
+impl AdminTxPoolApi {
+    /// Creates a new instance of `AdminTxPoolApi` that changes the settings of the pool with the
+    /// given config.
+    pub fn new(pool_config: &PoolConfig) -> Self {
+        Self {
+            transaction_policy: pool_config.transaction_policy.clone(),
+            blob_proof_computation: pool_config.blob_proof_computation.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminTxPoolApiServer for AdminTxPoolApi {
+    /// Handler for `admin_setTxpoolPolicy`
+    async fn set_txpool_policy(&self, policy: TxpoolPolicy) -> RpcResult<bool> {
+        trace!(target: "rpc::admin", ?policy, "Serving admin_setTxpoolPolicy");
+        let TxpoolPolicy { allowed_senders, denied_senders, denied_recipients, reject_unlisted } =
+            policy;
+        self.transaction_policy.set_lists(AddressLists {
+            allowed_senders: allowed_senders.into_iter().collect(),
+            denied_senders: denied_senders.into_iter().collect(),
+            denied_recipients: denied_recipients.into_iter().collect(),
+            reject_unlisted,
+        });
+        Ok(true)
+    }
+
+    /// Handler for `admin_setBlobProofComputation`
+    async fn set_blob_proof_computation(&self, enabled: bool) -> RpcResult<bool> {
+        trace!(target: "rpc::admin", enabled, "Serving admin_setBlobProofComputation");
+        self.blob_proof_computation.set_enabled(enabled);
+        Ok(true)
+    }
+}
+
+impl std::fmt::Debug for AdminTxPoolApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminTxPoolApi").finish_non_exhaustive()
+    }
+}
@@ -6,25 +6,29 @@ use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthereumHardforks;
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, Withdrawals,
-    B256, U256,
+    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSigned,
+    TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory,
-    TransactionVariant,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ProviderResult,
+    StateProviderFactory,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::helpers::{Call, EthApiSpec, EthTransactions, TraceExt};
-use reth_rpc_eth_types::{revm_utils::prepare_call_env, EthApiError, EthResult, StateCacheDb};
+use reth_rpc_eth_types::{
+    prestate::prestate_diff, revm_utils::prepare_call_env, EthApiError, EthResult, StateCacheDb,
+    StructLogInspector, StructLogLimits, TraceCache, TraceCacheKey,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
     state::EvmOverrides,
     trace::geth::{
-        BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
-        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
+        BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerConfig,
+        GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
+        NoopFrame, PreStateFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BlockError, Bundle, RichBlock, StateContext, TransactionInfo, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
@@ -35,6 +39,7 @@ use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
+use serde::Deserialize;
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `debug` API implementation.
@@ -48,8 +53,20 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [`DebugApi`]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_cache: TraceCache,
+        struct_log_limits: StructLogLimits,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            trace_cache,
+            struct_log_limits,
+        });
         Self { inner }
     }
 
@@ -76,10 +93,43 @@ where
         self.inner.blocking_task_guard.clone().acquire_owned().await
     }
 
+    /// Returns the block with its transactions as they are stored, without computing their hashes
+    /// or recovering their senders. Withdrawals are always present, as they are in RPC.
+    fn stored_block(&self, block_id: BlockId) -> ProviderResult<Option<Block>> {
+        let provider = &self.inner.provider;
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
+            // the pending block is not stored
+            return Ok(provider.block_by_id(block_id)?.map(|mut block| {
+                block.withdrawals.get_or_insert_with(Withdrawals::default);
+                block
+            }))
+        }
+
+        let Some(number) = provider.block_number_for_id(block_id)? else { return Ok(None) };
+        let Some(header) = provider.header_by_number(number)? else { return Ok(None) };
+        let Some(body_indices) = provider.block_body_indices(number)? else { return Ok(None) };
+        let body = provider
+            .transactions_by_tx_range(body_indices.tx_num_range())?
+            .into_iter()
+            .map(|tx| TransactionSigned {
+                hash: B256::ZERO,
+                signature: tx.signature,
+                transaction: tx.transaction,
+            })
+            .collect();
+        let ommers = provider.ommers(number.into())?.unwrap_or_default();
+        let withdrawals =
+            provider.withdrawals_by_block(number.into(), header.timestamp)?.unwrap_or_default();
+        let requests = provider.requests_by_block(number.into(), header.timestamp)?;
+
+        Ok(Some(Block { header, body, ommers, withdrawals: Some(withdrawals), requests }))
+    }
+
     /// Trace the entire block asynchronously
     async fn trace_block(
         &self,
         at: BlockId,
+        block_hash: Option<B256>,
         transactions: Vec<TransactionSignedEcRecovered>,
         cfg: CfgEnvWithHandlerCfg,
         block_env: BlockEnv,
@@ -89,12 +139,12 @@ where
             // nothing to trace
             return Ok(Vec::new())
         }
+        validate_mux_config(&opts)?;
 
         // replay all transactions of the block
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(at, move |state| {
-                let block_hash = at.as_block_hash();
                 let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
                 let mut transactions = transactions.into_iter().enumerate().peekable();
@@ -172,10 +222,20 @@ where
                     .collect::<EthResult<Vec<_>>>()?
             };
 
-        self.trace_block(parent.into(), transactions, cfg, block_env, opts).await
+        self.trace_block(
+            parent.into(),
+            Some(block.header.hash_slow()),
+            transactions,
+            cfg,
+            block_env,
+            opts,
+        )
+        .await
     }
 
     /// Replays a block and returns the trace of each transaction.
+    ///
+    /// The traces of canonical blocks are cached.
     pub async fn debug_trace_block(
         &self,
         block_id: BlockId,
@@ -187,6 +247,24 @@ where
             .block_hash_for_id(block_id)?
             .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
+        // the pending block changes without changing its hash
+        if block_id.is_pending() {
+            return self.trace_block_at(block_id, block_hash, opts).await
+        }
+        let key = TraceCacheKey::block(block_hash, "debug_traceBlock", &opts);
+        self.inner
+            .trace_cache
+            .get_or_insert_with(key, self.trace_block_at(block_id, block_hash, opts))
+            .await
+    }
+
+    /// Replays the block with the given id and hash and returns the trace of each transaction.
+    async fn trace_block_at(
+        &self,
+        block_id: BlockId,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
         let ((cfg, block_env, _), block) = futures::try_join!(
             self.inner.eth_api.evm_env_at(block_hash.into()),
             self.inner.eth_api.block_with_senders(block_id),
@@ -199,6 +277,7 @@ where
 
         self.trace_block(
             state_at.into(),
+            Some(block_hash),
             block.into_transactions_ecrecovered().collect(),
             cfg,
             block_env,
@@ -209,26 +288,35 @@ where
 
     /// Trace the transaction according to the provided options.
     ///
+    /// The traces are cached by the hash of the block that includes the transaction.
+    ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
     pub async fn debug_trace_transaction(
         &self,
         tx_hash: B256,
         opts: GethDebugTracingOptions,
     ) -> EthResult<GethTrace> {
+        validate_mux_config(&opts)?;
         let (transaction, block) = match self.inner.eth_api.transaction_and_block(tx_hash).await? {
             None => return Err(EthApiError::TransactionNotFound),
             Some(res) => res,
         };
-        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash().into()).await?;
+        let block_hash = block.hash();
+        let key = TraceCacheKey::transaction(block_hash, tx_hash, "debug_traceTransaction", &opts);
+        if let Some(trace) = self.inner.trace_cache.get(&key) {
+            return Ok(trace)
+        }
+
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block_hash.into()).await?;
 
         // we need to get the state of the parent block because we're essentially replaying the
         // block the transaction is included in
         let state_at: BlockId = block.parent_hash.into();
-        let block_hash = block.hash();
         let block_txs = block.into_transactions_ecrecovered();
 
         let this = self.clone();
-        self.inner
+        let trace = self
+            .inner
             .eth_api
             .spawn_with_state_at_block(state_at, move |state| {
                 // configure env for the target transaction
@@ -265,7 +353,9 @@ where
                 )
                 .map(|(trace, _)| trace)
             })
-            .await
+            .await?;
+        self.inner.trace_cache.insert(key, &trace);
+        Ok(trace)
     }
 
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
@@ -331,22 +421,27 @@ where
                             TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
                         );
 
-                        let frame =
-                            self.inner
-                                .eth_api
-                                .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                    // wrapper is hack to get around 'higher-ranked lifetime error',
-                                    // see <https://github.com/rust-lang/rust/issues/100013>
-                                    let db = db.0;
-
-                                    let (res, _) =
-                                        this.eth_api().inspect(&mut *db, env, &mut inspector)?;
-                                    let frame = inspector
-                                        .into_geth_builder()
-                                        .geth_prestate_traces(&res, prestate_config, db)?;
-                                    Ok(frame)
-                                })
-                                .await?;
+                        let frame = self
+                            .inner
+                            .eth_api
+                            .spawn_with_call_at(call, at, overrides, move |db, env| {
+                                // wrapper is hack to get around 'higher-ranked lifetime error',
+                                // see <https://github.com/rust-lang/rust/issues/100013>
+                                let db = db.0;
+
+                                let (res, _) =
+                                    this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                                if prestate_config.diff_mode.unwrap_or_default() {
+                                    return Ok(PreStateFrame::Diff(prestate_diff(&res.state, &*db)?))
+                                }
+                                let frame = inspector.into_geth_builder().geth_prestate_traces(
+                                    &res,
+                                    prestate_config,
+                                    db,
+                                )?;
+                                Ok(frame)
+                            })
+                            .await?;
                         return Ok(frame.into())
                     }
                     GethDebugBuiltInTracerType::NoopTracer => Ok(NoopFrame::default().into()),
@@ -374,6 +469,17 @@ where
                         return Ok(frame)
                     }
                 },
+                GethDebugTracerType::JsTracer(code) if code == FLAT_CALL_TRACER => {
+                    let config = FlatCallConfig::from_tracer_config(tracer_config)?;
+                    let (trace, _) = self
+                        .inner
+                        .eth_api
+                        .spawn_with_call_at(call, at, overrides, move |db, env| {
+                            this.flat_call_traces(&config, env, db.0, None)
+                        })
+                        .await?;
+                    Ok(trace)
+                }
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
 
@@ -400,9 +506,7 @@ where
         }
 
         // default structlog tracer
-        let inspector_config = TracingInspectorConfig::from_geth_config(&config);
-
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = StructLogInspector::new(&config, self.inner.struct_log_limits);
 
         let (res, inspector) = self
             .inner
@@ -414,9 +518,8 @@ where
             .await?;
         let gas_used = res.result.gas_used();
         let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
 
-        Ok(frame.into())
+        Ok(inspector.into_geth_trace(gas_used, return_value, config))
     }
 
     /// The `debug_traceCallMany` method lets you run an `eth_callMany` within the context of the
@@ -585,6 +688,10 @@ where
                         );
                         let (res, _) = self.eth_api().inspect(&mut *db, env, &mut inspector)?;
 
+                        if prestate_config.diff_mode.unwrap_or_default() {
+                            let frame = PreStateFrame::Diff(prestate_diff(&res.state, &*db)?);
+                            return Ok((frame.into(), res.state))
+                        }
                         let frame = inspector.into_geth_builder().geth_prestate_traces(
                             &res,
                             prestate_config,
@@ -608,6 +715,10 @@ where
                         return Ok((frame.into(), res.state))
                     }
                 },
+                GethDebugTracerType::JsTracer(code) if code == FLAT_CALL_TRACER => {
+                    let config = FlatCallConfig::from_tracer_config(tracer_config)?;
+                    self.flat_call_traces(&config, env, db, transaction_context)
+                }
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
                     let mut inspector = JsInspector::with_transaction_context(
@@ -625,19 +736,99 @@ where
         }
 
         // default structlog tracer
-        let inspector_config = TracingInspectorConfig::from_geth_config(&config);
-
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = StructLogInspector::new(&config, self.inner.struct_log_limits);
 
         let (res, _) = self.eth_api().inspect(db, env, &mut inspector)?;
         let gas_used = res.result.gas_used();
         let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
+        let trace = inspector.into_geth_trace(gas_used, return_value, config);
+
+        Ok((trace, res.state))
+    }
+
+    /// Executes the transaction with the `flatCallTracer` of geth, which returns the calls in the
+    /// format of the parity `trace_` namespace.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn flat_call_traces(
+        &self,
+        config: &FlatCallConfig,
+        env: EnvWithHandlerCfg,
+        db: &mut StateCacheDb<'_>,
+        transaction_context: Option<TransactionContext>,
+    ) -> EthResult<(GethTrace, revm_primitives::EvmState)> {
+        let mut inspector_config = TracingInspectorConfig::default_parity();
+        inspector_config.exclude_precompile_calls = !config.include_precompiles;
+        let mut inspector = TracingInspector::new(inspector_config);
+        let (res, env) = self.eth_api().inspect(db, env, &mut inspector)?;
+
+        let context = transaction_context.unwrap_or_default();
+        let tx_info = TransactionInfo {
+            hash: context.tx_hash,
+            index: context.tx_index.map(|index| index as u64),
+            block_hash: context.block_hash,
+            block_number: Some(env.block.number.saturating_to()),
+            base_fee: Some(env.block.basefee.saturating_to()),
+        };
+        let mut traces = inspector
+            .with_transaction_gas_used(res.result.gas_used())
+            .into_parity_builder()
+            .into_localized_transaction_traces(tx_info);
+        if !config.convert_parity_errors {
+            for trace in &mut traces {
+                if let Some(error) = trace.trace.error.as_mut() {
+                    if let Some(geth_error) = geth_error(error) {
+                        *error = geth_error.to_string();
+                    }
+                }
+            }
+        }
 
-        Ok((frame.into(), res.state))
+        let traces = serde_json::to_value(traces).map_err(|_| EthApiError::InternalEthError)?;
+        Ok((GethTrace::JS(traces), res.state))
     }
 }
 
+/// The name of the `flatCallTracer` of geth.
+///
+/// It's not one of the built-in tracer types, so it's requested like a JS tracer.
+const FLAT_CALL_TRACER: &str = "flatCallTracer";
+
+/// The config of the `flatCallTracer`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct FlatCallConfig {
+    /// Whether to report errors in the format of parity instead of geth.
+    convert_parity_errors: bool,
+    /// Whether to include the calls to precompiles.
+    include_precompiles: bool,
+}
+
+impl FlatCallConfig {
+    fn from_tracer_config(config: GethDebugTracerConfig) -> EthResult<Self> {
+        let config = config.into_json();
+        if config.is_null() {
+            return Ok(Self::default())
+        }
+        serde_json::from_value(config).map_err(|_| EthApiError::InvalidTracerConfig)
+    }
+}
+
+/// Returns the error geth reports for the error of a parity trace, if they differ.
+fn geth_error(parity_error: &str) -> Option<&'static str> {
+    let error = match parity_error {
+        "Reverted" => "execution reverted",
+        "Out of gas" => "out of gas",
+        "Bad jump destination" => "invalid jump destination",
+        "Out of bounds" => "return data out of bounds",
+        "Out of stack" => "stack limit reached 1024 (1023)",
+        "Built-in failed" => "precompiled failed",
+        "Stack underflow" => "stack underflow",
+        _ => return None,
+    };
+    Some(error)
+}
+
 #[async_trait]
 impl<Provider, Eth> DebugApiServer for DebugApi<Provider, Eth>
 where
@@ -650,6 +841,9 @@ where
     Eth: EthApiSpec + EthTransactions + TraceExt + 'static,
 {
     /// Handler for `debug_getRawHeader`
+    ///
+    /// Headers are stored in their compact encoding, so the header is re-encoded as RLP. Returns
+    /// empty bytes if the header doesn't exist.
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
         let header = match block_id {
             BlockId::Hash(hash) => self.inner.provider.header(&hash.into()).to_rpc_result()?,
@@ -664,26 +858,23 @@ where
             }
         };
 
-        let mut res = Vec::new();
-        if let Some(header) = header {
-            header.encode(&mut res);
-        }
+        let Some(header) = header else { return Ok(Bytes::new()) };
+        let mut res = Vec::with_capacity(header.length());
+        header.encode(&mut res);
 
         Ok(res.into())
     }
 
     /// Handler for `debug_getRawBlock`
+    ///
+    /// The stored transactions are encoded as is, without computing their hashes or recovering
+    /// their senders. Returns empty bytes if the block doesn't exist.
     async fn raw_block(&self, block_id: BlockId) -> RpcResult<Bytes> {
-        let block = self.inner.provider.block_by_id(block_id).to_rpc_result()?;
-
-        let mut res = Vec::new();
-        if let Some(mut block) = block {
-            // In RPC withdrawals are always present
-            if block.withdrawals.is_none() {
-                block.withdrawals = Some(Withdrawals::default());
-            }
-            block.encode(&mut res);
-        }
+        let Some(block) = self.stored_block(block_id).to_rpc_result()? else {
+            return Ok(Bytes::new())
+        };
+        let mut res = Vec::with_capacity(block.length());
+        block.encode(&mut res);
 
         Ok(res.into())
     }
@@ -698,15 +889,12 @@ where
     }
 
     /// Handler for `debug_getRawTransactions`
-    /// Returns the bytes of the transaction for the given hash.
+    ///
+    /// Returns the EIP-2718 encoded transactions of the block, as they are stored. The
+    /// transaction hashes are not computed and the senders are not recovered.
     async fn raw_transactions(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>> {
-        let block = self
-            .inner
-            .provider
-            .block_with_senders_by_id(block_id, TransactionVariant::NoHash)
-            .to_rpc_result()?
-            .unwrap_or_default();
-        Ok(block.into_transactions_ecrecovered().map(|tx| tx.envelope_encoded()).collect())
+        let block = self.stored_block(block_id).to_rpc_result()?.unwrap_or_default();
+        Ok(block.body.iter().map(TransactionSigned::envelope_encoded).collect())
     }
 
     /// Handler for `debug_getRawReceipts`
@@ -1009,6 +1197,24 @@ where
     }
 }
 
+/// Checks that the config of a `muxTracer` is valid.
+///
+/// The mux tracer runs all of its tracers in a single execution, so this rejects an invalid
+/// config before any transactions are replayed for it rather than after.
+fn validate_mux_config(opts: &GethDebugTracingOptions) -> EthResult<()> {
+    if let Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::MuxTracer)) =
+        &opts.tracer
+    {
+        let mux_config = opts
+            .tracer_config
+            .clone()
+            .into_mux_config()
+            .map_err(|_| EthApiError::InvalidTracerConfig)?;
+        MuxInspector::try_from_config(mux_config)?;
+    }
+    Ok(())
+}
+
 impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1028,4 +1234,8 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Caches the traces of recent blocks and transactions
+    trace_cache: TraceCache,
+    /// Limits on what the struct log tracer captures of a transaction
+    struct_log_limits: StructLogLimits,
 }
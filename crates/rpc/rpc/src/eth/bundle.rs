@@ -23,7 +23,7 @@ use reth_rpc_eth_api::{
     EthCallBundleApiServer,
 };
 use reth_rpc_eth_types::{
-    utils::recover_raw_transaction, EthApiError, EthResult, RpcInvalidTransactionError,
+    utils::recover_raw_transactions, EthApiError, EthResult, RpcInvalidTransactionError,
 };
 
 /// `Eth` bundle implementation.
@@ -60,10 +60,7 @@ where
             ))
         }
 
-        let transactions = txs
-            .into_iter()
-            .map(recover_raw_transaction)
-            .collect::<Result<Vec<_>, _>>()?
+        let transactions = recover_raw_transactions(txs)?
             .into_iter()
             .map(|tx| tx.into_components())
             .collect::<Vec<_>>();
@@ -4,6 +4,7 @@
 use std::sync::Arc;
 
 use derive_more::Deref;
+use reth_evm::dev::DevState;
 use reth_primitives::{BlockNumberOrTag, U256};
 use reth_provider::BlockReaderIdExt;
 use reth_rpc_eth_api::{
@@ -14,7 +15,7 @@ use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use tokio::sync::Mutex;
 
-use crate::eth::DevSigner;
+use crate::eth::{DevSigner, ImpersonationSigner};
 
 /// `Eth` API implementation.
 ///
@@ -149,6 +150,12 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
         let mut signers = self.inner.signers.write();
         *signers = DevSigner::random_signers(20);
     }
+
+    /// Signs transactions on behalf of the impersonated accounts of the dev state.
+    /// Used in DEV mode.
+    pub fn with_impersonated_accounts(&self, dev_state: DevState) {
+        self.inner.signers.write().push(Box::new(ImpersonationSigner::new(dev_state)));
+    }
 }
 
 /// Container type `EthApi`
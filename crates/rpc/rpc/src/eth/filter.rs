@@ -2,11 +2,12 @@
 
 use std::{
     collections::HashMap,
-    fmt,
+    fmt, io,
     iter::StepBy,
     ops::RangeInclusive,
+    path::PathBuf,
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -17,7 +18,7 @@ use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
 use reth_rpc_eth_api::EthFilterApiServer;
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
-    EthApiError, EthFilterError, EthStateCache, EthSubscriptionIdProvider,
+    EthApiError, EthFilterError, EthStateCache, EthSubscriptionIdProvider, PrefixedIdProvider,
 };
 use reth_rpc_server_types::ToRpcResult;
 use reth_rpc_types::{
@@ -26,15 +27,19 @@ use reth_rpc_types::{
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use tokio::{
     sync::{mpsc::Receiver, Mutex},
     time::MissedTickBehavior,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// The interval at which installed filters are persisted, if enabled.
+const FILTER_PERSIST_INTERVAL: Duration = Duration::from_secs(5);
+
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool> {
     /// All nested fields bundled together
@@ -53,7 +58,8 @@ where
     ///
     /// See also [`EthFilterConfig`].
     ///
-    /// This also spawns a task that periodically clears stale filters.
+    /// This also spawns a task that periodically clears stale filters, and if persistence is
+    /// enabled, restores the persisted filters and spawns a task that periodically persists them.
     pub fn new(
         provider: Provider,
         pool: Pool,
@@ -61,13 +67,27 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            id_prefix,
+            persistence,
+        } = config;
+        let id_provider: Arc<dyn IdProvider> = match id_prefix {
+            Some(prefix) => Arc::new(PrefixedIdProvider::new(prefix)),
+            None => Arc::new(EthSubscriptionIdProvider::default()),
+        };
+        let active_filters = ActiveFilters {
+            inner: Arc::new(Mutex::new(
+                persistence.as_ref().map(FilterPersistenceConfig::load).unwrap_or_default(),
+            )),
+        };
         let inner = EthFilterInner {
             provider,
-            active_filters: Default::default(),
+            active_filters,
             pool,
-            id_provider: Arc::new(EthSubscriptionIdProvider::default()),
+            id_provider,
             eth_cache,
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
@@ -75,6 +95,7 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            persistence,
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -87,6 +108,16 @@ where
             }),
         );
 
+        if eth_filter.inner.persistence.is_some() {
+            let this = eth_filter.clone();
+            eth_filter.inner.task_spawner.spawn_critical(
+                "eth-filters_persist-filters",
+                Box::pin(async move {
+                    this.watch_and_persist_filters().await;
+                }),
+            );
+        }
+
         eth_filter
     }
 
@@ -106,6 +137,21 @@ where
         }
     }
 
+    /// Endless future that persists the log and block filters every [`FILTER_PERSIST_INTERVAL`],
+    /// if persistence is enabled.
+    async fn watch_and_persist_filters(&self) {
+        let Some(persistence) = &self.inner.persistence else { return };
+        let mut interval = tokio::time::interval(FILTER_PERSIST_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let filters = persistence.snapshot(&*self.active_filters().inner.lock().await);
+            if let Err(err) = persistence.store(&filters) {
+                warn!(target: "rpc::eth::filter", %err, path = ?persistence.path, "Failed to persist filters");
+            }
+        }
+    }
+
     /// Clears all filters that have not been polled for longer than the configured
     /// `stale_filter_ttl` at the given instant.
     pub async fn clear_stale_filters(&self, now: Instant) {
@@ -336,6 +382,8 @@ struct EthFilterInner<Provider, Pool> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Where the log and block filters are persisted, if enabled
+    persistence: Option<FilterPersistenceConfig>,
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
@@ -465,11 +513,26 @@ where
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
+        // the block range of the aggregated log bloom the current blocks are in, and whether the
+        // bloom matches the filter, so whole static file segments without matches are skipped
+        let mut range_bloom: Option<(RangeInclusive<u64>, bool)> = None;
+
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
             BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
         {
+            if range_bloom.as_ref().map_or(true, |(range, _)| !range.contains(&from)) {
+                range_bloom = self.provider.receipts_bloom_by_block(from)?.map(|(range, bloom)| {
+                    let matches = FilteredParams::matches_address(bloom, &address_filter) &&
+                        FilteredParams::matches_topics(bloom, &topics_filter);
+                    (range, matches)
+                });
+            }
+            if matches!(&range_bloom, Some((range, false)) if range.contains(&to)) {
+                continue
+            }
+
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -531,6 +594,14 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// The prefix of the ids of installed filters.
+    ///
+    /// If `None`, the ids are plain hex quantities.
+    pub id_prefix: Option<String>,
+    /// Where log and block filters are persisted to survive restarts.
+    ///
+    /// If `None`, all filters are lost when the node restarts.
+    pub persistence: Option<FilterPersistenceConfig>,
 }
 
 impl EthFilterConfig {
@@ -552,6 +623,18 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the prefix of the ids of installed filters.
+    pub fn id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.id_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets where log and block filters are persisted.
+    pub fn persistence(mut self, persistence: FilterPersistenceConfig) -> Self {
+        self.persistence = Some(persistence);
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -561,10 +644,116 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            id_prefix: None,
+            persistence: None,
         }
     }
 }
 
+/// Where and for how long log and block filters are persisted.
+///
+/// Filters are written to the file periodically, and restored on startup if they were polled
+/// within the TTL before. Polls in the last few seconds before a shutdown may be lost, so a
+/// restored filter can return changes again that were returned before the shutdown. Pending
+/// transaction filters are never persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterPersistenceConfig {
+    /// The file the filters are persisted to.
+    pub path: PathBuf,
+    /// How long after its last poll a persisted filter can be restored.
+    pub ttl: Duration,
+}
+
+impl FilterPersistenceConfig {
+    /// Creates a new config for the given file and TTL.
+    pub const fn new(path: PathBuf, ttl: Duration) -> Self {
+        Self { path, ttl }
+    }
+
+    /// Returns the log and block filters in their persisted form.
+    fn snapshot(&self, filters: &HashMap<FilterId, ActiveFilter>) -> Vec<PersistedFilter> {
+        let now = Instant::now();
+        let wall_now = SystemTime::now();
+        filters
+            .iter()
+            .filter_map(|(id, filter)| {
+                let log_filter = match &filter.kind {
+                    FilterKind::Log(filter) => Some(filter.clone()),
+                    FilterKind::Block => None,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                let last_poll = wall_now
+                    .checked_sub(now.saturating_duration_since(filter.last_poll_timestamp))
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .unwrap_or_default();
+                Some(PersistedFilter {
+                    id: id.clone(),
+                    block: filter.block,
+                    last_poll: last_poll.as_secs(),
+                    log_filter,
+                })
+            })
+            .collect()
+    }
+
+    /// Writes the filters to the file, replacing the previously persisted filters.
+    fn store(&self, filters: &[PersistedFilter]) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(filters)?)?;
+        std::fs::rename(tmp_path, &self.path)
+    }
+
+    /// Reads the persisted filters that were polled within the TTL.
+    ///
+    /// Restored filters count as polled now.
+    fn load(&self) -> HashMap<FilterId, ActiveFilter> {
+        let filters = match std::fs::read(&self.path) {
+            Ok(bytes) => match serde_json::from_slice::<Vec<PersistedFilter>>(&bytes) {
+                Ok(filters) => filters,
+                Err(err) => {
+                    warn!(target: "rpc::eth::filter", %err, path = ?self.path, "Failed to decode persisted filters");
+                    return HashMap::new()
+                }
+            },
+            Err(err) => {
+                if err.kind() != io::ErrorKind::NotFound {
+                    warn!(target: "rpc::eth::filter", %err, path = ?self.path, "Failed to read persisted filters");
+                }
+                return HashMap::new()
+            }
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        filters
+            .into_iter()
+            .filter(|filter| now.saturating_sub(filter.last_poll) < self.ttl.as_secs())
+            .map(|filter| {
+                let kind = match filter.log_filter {
+                    Some(log_filter) => FilterKind::Log(log_filter),
+                    None => FilterKind::Block,
+                };
+                let active =
+                    ActiveFilter { block: filter.block, last_poll_timestamp: Instant::now(), kind };
+                (filter.id, active)
+            })
+            .collect()
+    }
+}
+
+/// A log or block filter as it is persisted.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedFilter {
+    /// The id of the filter.
+    id: FilterId,
+    /// At which block the filter was polled last.
+    block: u64,
+    /// The time of the last poll, in seconds since the unix epoch.
+    last_poll: u64,
+    /// The filter of a log filter, `None` for a block filter.
+    log_filter: Option<Box<Filter>>,
+}
+
 /// All active filters
 #[derive(Debug, Clone, Default)]
 pub struct ActiveFilters {
@@ -733,4 +922,37 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn restore_persisted_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let persistence =
+            FilterPersistenceConfig::new(dir.path().join("filters.json"), Duration::from_secs(60));
+        assert!(persistence.load().is_empty());
+
+        let now = Instant::now();
+        let filter =
+            |block, last_poll_timestamp, kind| ActiveFilter { block, last_poll_timestamp, kind };
+        let filters = HashMap::from([
+            (
+                FilterId::Str("a-0x1".into()),
+                filter(5, now, FilterKind::Log(Box::new(Filter::new().from_block(3)))),
+            ),
+            (FilterId::Str("a-0x2".into()), filter(7, now, FilterKind::Block)),
+            (
+                FilterId::Str("a-0x3".into()),
+                filter(9, now - Duration::from_secs(120), FilterKind::Block),
+            ),
+        ]);
+        persistence.store(&persistence.snapshot(&filters)).unwrap();
+
+        let restored = persistence.load();
+        assert_eq!(restored.len(), 2);
+        let log_filter = &restored[&FilterId::Str("a-0x1".into())];
+        assert_eq!(log_filter.block, 5);
+        assert!(
+            matches!(&log_filter.kind, FilterKind::Log(filter) if filter.get_from_block() == Some(3))
+        );
+        assert!(matches!(restored[&FilterId::Str("a-0x2".into())].kind, FilterKind::Block));
+    }
 }
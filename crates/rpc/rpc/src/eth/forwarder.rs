@@ -0,0 +1,232 @@
+//! Forwarding of `eth_sendRawTransaction` submissions to an upstream endpoint.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObject};
+use reqwest::Client;
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::hex;
+use reth_rpc_eth_api::RawTransactionForwarder;
+use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_server_types::TxForwardMode;
+use reth_rpc_types::ToRpcError;
+use tracing::{debug, warn};
+
+/// Backoff before the first retry, doubled for every subsequent retry.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Error type when forwarding transactions to the upstream endpoint.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionForwarderError {
+    /// Wrapper around an [`reqwest::Error`].
+    #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+    /// The upstream endpoint rejected the transaction.
+    #[error("upstream rejected transaction: {message}")]
+    Rejected {
+        /// The JSON-RPC error code returned by the upstream endpoint.
+        code: i32,
+        /// The JSON-RPC error message returned by the upstream endpoint.
+        message: String,
+    },
+}
+
+impl TransactionForwarderError {
+    /// Returns `true` if forwarding can be retried, i.e. the upstream endpoint could not be
+    /// reached or did not respond successfully.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::HttpError(_))
+    }
+}
+
+impl ToRpcError for TransactionForwarderError {
+    fn to_rpc_error(&self) -> ErrorObject<'static> {
+        match self {
+            Self::HttpError(_) => {
+                ErrorObject::owned(INTERNAL_ERROR_CODE, self.to_string(), None::<String>)
+            }
+            Self::Rejected { code, message } => {
+                ErrorObject::owned(*code, message.clone(), None::<String>)
+            }
+        }
+    }
+}
+
+impl From<TransactionForwarderError> for EthApiError {
+    fn from(err: TransactionForwarderError) -> Self {
+        Self::other(err)
+    }
+}
+
+/// Forwards raw transactions submitted via `eth_sendRawTransaction` to an upstream endpoint, for
+/// example a private relay.
+///
+/// Failed requests are retried with exponential backoff. Depending on the [`TxForwardMode`],
+/// transactions are either mirrored in the background and also submitted to the local pool, or
+/// only forwarded, in which case forwarding errors are returned to the caller.
+#[derive(Debug, Clone)]
+pub struct TransactionForwarder {
+    inner: Arc<TransactionForwarderInner>,
+}
+
+impl TransactionForwarder {
+    /// Creates a new [`TransactionForwarder`].
+    pub fn new(endpoint: impl Into<String>, mode: TxForwardMode, max_retries: u32) -> Self {
+        let client = Client::builder().use_rustls_tls().build().unwrap();
+        Self::with_client(endpoint, mode, max_retries, client)
+    }
+
+    /// Creates a new [`TransactionForwarder`] with the given HTTP client.
+    pub fn with_client(
+        endpoint: impl Into<String>,
+        mode: TxForwardMode,
+        max_retries: u32,
+        http_client: Client,
+    ) -> Self {
+        let inner = TransactionForwarderInner {
+            endpoint: endpoint.into(),
+            http_client,
+            mode,
+            max_retries,
+            id: AtomicUsize::new(0),
+            metrics: Default::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the upstream endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.endpoint
+    }
+
+    /// Returns how forwarded transactions are handled locally.
+    pub fn mode(&self) -> TxForwardMode {
+        self.inner.mode
+    }
+
+    /// Forwards a transaction to the upstream endpoint, retrying failed requests.
+    pub async fn forward_raw_transaction(
+        &self,
+        tx: &[u8],
+    ) -> Result<(), TransactionForwarderError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_sendRawTransaction",
+            "params": [hex::encode_prefixed(tx)],
+            "id": self.inner.id.fetch_add(1, Ordering::Relaxed)
+        })
+        .to_string();
+
+        let metrics = &self.inner.metrics;
+        let start = Instant::now();
+        let mut backoff = INITIAL_RETRY_BACKOFF;
+        let mut retries = 0;
+        let res = loop {
+            match self.inner.send(&body).await {
+                Err(err) if err.is_retryable() && retries < self.inner.max_retries => {
+                    debug!(target: "rpc::eth::forwarder", %err, retries, "retrying transaction forward");
+                    metrics.retries.increment(1);
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                res => break res,
+            }
+        };
+        metrics.forward_duration_seconds.record(start.elapsed());
+
+        match &res {
+            Ok(()) => metrics.forwarded_transactions.increment(1),
+            Err(_) => metrics.failed_transactions.increment(1),
+        }
+        res
+    }
+}
+
+#[async_trait::async_trait]
+impl RawTransactionForwarder for TransactionForwarder {
+    async fn forward_raw_transaction(&self, tx: &[u8]) -> EthResult<()> {
+        match self.mode() {
+            TxForwardMode::Mirror => {
+                // mirroring must not delay or fail the local submission
+                let this = self.clone();
+                let tx = tx.to_vec();
+                tokio::spawn(async move {
+                    if let Err(err) = Self::forward_raw_transaction(&this, &tx).await {
+                        warn!(target: "rpc::eth::forwarder", %err, endpoint=%this.endpoint(), "failed to mirror transaction");
+                    }
+                });
+            }
+            TxForwardMode::ForwardOnly => Self::forward_raw_transaction(self, tx).await?,
+        }
+        Ok(())
+    }
+
+    fn mode(&self) -> TxForwardMode {
+        Self::mode(self)
+    }
+}
+
+#[derive(Debug)]
+struct TransactionForwarderInner {
+    /// The upstream endpoint
+    endpoint: String,
+    /// The HTTP client
+    http_client: Client,
+    /// How forwarded transactions are handled locally
+    mode: TxForwardMode,
+    /// How often a failed request is retried
+    max_retries: u32,
+    /// Keeps track of unique request ids
+    id: AtomicUsize,
+    /// Forwarding metrics
+    metrics: TransactionForwarderMetrics,
+}
+
+impl TransactionForwarderInner {
+    /// Sends a single request to the upstream endpoint.
+    async fn send(&self, body: &str) -> Result<(), TransactionForwarderError> {
+        let response: serde_json::Value = self
+            .http_client
+            .post(&self.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_owned())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            let code = error.get("code").and_then(|code| code.as_i64()).unwrap_or_default();
+            let message =
+                error.get("message").and_then(|msg| msg.as_str()).unwrap_or_default().to_string();
+            return Err(TransactionForwarderError::Rejected { code: code as i32, message })
+        }
+
+        Ok(())
+    }
+}
+
+/// Metrics for forwarding transactions to the upstream endpoint.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.tx_forwarder")]
+struct TransactionForwarderMetrics {
+    /// The number of transactions forwarded successfully.
+    forwarded_transactions: Counter,
+    /// The number of transactions that could not be forwarded.
+    failed_transactions: Counter,
+    /// The number of retried requests.
+    retries: Counter,
+    /// The time it took to forward a transaction, including retries.
+    forward_duration_seconds: Histogram,
+}
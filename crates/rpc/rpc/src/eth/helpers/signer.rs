@@ -3,8 +3,9 @@
 use std::collections::HashMap;
 
 use alloy_dyn_abi::TypedData;
+use reth_evm::dev::DevState;
 use reth_primitives::{
-    eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
+    eip191_hash_message, keccak256, sign_message, Address, Signature, TransactionSigned, B256,
 };
 use reth_rpc_eth_api::helpers::{signer::Result, EthSigner};
 use reth_rpc_eth_types::SignError;
@@ -90,6 +91,63 @@ impl EthSigner for DevSigner {
     }
 }
 
+/// Signs transactions on behalf of the impersonated accounts of the dev chain.
+///
+/// The transactions are signed with a key derived from the impersonated address, and their
+/// sender is recorded in the [`DevState`], so that the dev executor executes them as sent by the
+/// impersonated account, see [`reth_evm::dev`].
+#[derive(Debug, Clone)]
+pub struct ImpersonationSigner {
+    dev_state: DevState,
+}
+
+impl ImpersonationSigner {
+    /// Creates a signer for the impersonated accounts of the dev state.
+    pub const fn new(dev_state: DevState) -> Self {
+        Self { dev_state }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        Vec::new()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.dev_state.is_impersonated(addr)
+    }
+
+    fn impersonates(&self, addr: &Address) -> bool {
+        self.is_signer_for(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        // a distinct key per account keeps the hashes of equal transactions of different accounts
+        // apart
+        let signature = sign_message(keccak256(address), transaction.signature_hash())
+            .map_err(|_| SignError::CouldNotSign)?;
+
+        let transaction = TransactionSigned::from_transaction_and_signature(transaction, signature);
+        self.dev_state.record_impersonated_transaction(transaction.hash(), *address);
+        Ok(transaction)
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -3,15 +3,17 @@
 pub mod bundle;
 pub mod core;
 pub mod filter;
+pub mod forwarder;
 pub mod helpers;
 pub mod pubsub;
 
 /// Implementation of `eth` namespace API.
 pub use bundle::EthBundle;
 pub use core::EthApi;
-pub use filter::{EthFilter, EthFilterConfig};
+pub use filter::{EthFilter, EthFilterConfig, FilterPersistenceConfig};
+pub use forwarder::TransactionForwarder;
 pub use pubsub::EthPubSub;
 
-pub use helpers::signer::DevSigner;
+pub use helpers::signer::{DevSigner, ImpersonationSigner};
 
 pub use reth_rpc_eth_api::RawTransactionForwarder;
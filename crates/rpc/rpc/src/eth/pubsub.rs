@@ -1,31 +1,38 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use futures::StreamExt;
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
+use reth_primitives::{BlockNumHash, BlockNumberOrTag, IntoRecoveredTransaction, TxHash, B256};
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
 use reth_rpc_eth_api::pubsub::EthPubSubApiServer;
 use reth_rpc_eth_types::logs_utils;
-use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_server_types::{
+    constants::DEFAULT_MAX_BLOCKS_PER_FILTER,
+    result::{internal_rpc_err, invalid_params_rpc_err},
+};
 use reth_rpc_types::{
     pubsub::{
         Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
         SyncStatusMetadata,
     },
-    FilteredParams, Header, Log,
+    Filter, FilterBlockOption, FilteredParams, Header, Log,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
 use serde::Serialize;
-use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
-    Stream,
-};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+
+/// The maximum number of past blocks a logs subscription can backfill.
+const MAX_LOG_BACKFILL_BLOCKS: u64 = DEFAULT_MAX_BLOCKS_PER_FILTER;
+
+/// The number of recent blocks a backfilled logs subscription remembers, reorgs deeper than this
+/// are assumed to not happen.
+const LOG_BACKFILL_REORG_DEPTH: u64 = 256;
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -116,7 +123,14 @@ where
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
+                Some(Params::Logs(filter)) => {
+                    // a `fromBlock` requests the logs of the past blocks before the new logs
+                    if let Some(from_block) = log_backfill_start(&filter) {
+                        return pipe_logs_with_backfill(pubsub, accepted_sink, *filter, from_block)
+                            .await
+                    }
+                    FilteredParams::new(Some(*filter))
+                }
                 Some(Params::Bool(_)) => {
                     return Err(invalid_params_rpc_err("Invalid params for logs"))
                 }
@@ -158,8 +172,7 @@ where
         }
         SubscriptionKind::Syncing => {
             // get new block subscription
-            let mut canon_state =
-                BroadcastStream::new(pubsub.chain_events.subscribe_to_canonical_state());
+            let mut canon_state = pubsub.chain_events.canonical_state_stream();
             // get current sync status
             let mut initial_sync_status = pubsub.network.is_syncing();
             let current_sub_res = pubsub.sync_status(initial_sync_status);
@@ -193,6 +206,152 @@ where
     }
 }
 
+/// Returns the block to backfill the logs of a subscription from, if the filter has a `fromBlock`.
+const fn log_backfill_start(filter: &Filter) -> Option<u64> {
+    match filter.block_option {
+        FilterBlockOption::Range { from_block: Some(BlockNumberOrTag::Number(number)), .. } => {
+            Some(number)
+        }
+        FilterBlockOption::Range { from_block: Some(BlockNumberOrTag::Earliest), .. } => Some(0),
+        _ => None,
+    }
+}
+
+/// Pipes the matching logs of the canonical blocks from `from_block` to the current head to the
+/// subscription sink, followed by the matching logs of new blocks.
+///
+/// The subscription to new blocks is created before the backfill starts, so no block is missed.
+/// New blocks that were already backfilled are skipped, and reverted blocks are only emitted with
+/// `removed: true` if their logs were emitted, regardless of whether by the backfill or as new
+/// blocks.
+async fn pipe_logs_with_backfill<Provider, Pool, Events, Network>(
+    pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
+    sink: SubscriptionSink,
+    filter: Filter,
+    from_block: u64,
+) -> Result<(), ErrorObject<'static>>
+where
+    Provider: BlockReader + 'static,
+    Events: CanonStateSubscriptions + 'static,
+{
+    let canon_state = pubsub.chain_events.canonical_state_stream();
+    let head =
+        pubsub.provider.best_block_number().map_err(|err| internal_rpc_err(err.to_string()))?;
+    if head.saturating_sub(from_block) > MAX_LOG_BACKFILL_BLOCKS {
+        return Err(invalid_params_rpc_err(format!(
+            "logs can be backfilled from at most {MAX_LOG_BACKFILL_BLOCKS} blocks in the past"
+        )))
+    }
+
+    // derive bloom filters from filter input, so we can check headers for matching logs
+    let address_filter = FilteredParams::address_filter(&filter.address);
+    let topics_filter = FilteredParams::topics_filter(&filter.topics);
+    let filter = FilteredParams::new(Some(filter));
+
+    let mut emitted = EmittedBlocks::new(from_block);
+    for number in from_block..=head {
+        if sink.is_closed() {
+            return Ok(())
+        }
+        let header = pubsub
+            .provider
+            .sealed_header(number)
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+            .ok_or_else(|| internal_rpc_err(format!("header {number} not found")))?;
+        emitted.record(header.num_hash(), false);
+
+        if !FilteredParams::matches_address(header.logs_bloom, &address_filter) ||
+            !FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+        {
+            continue
+        }
+        let Some(receipts) = pubsub
+            .provider
+            .receipts_by_block(header.hash().into())
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+        else {
+            continue
+        };
+
+        let mut logs = Vec::new();
+        logs_utils::append_matching_block_logs(
+            &mut logs,
+            &pubsub.provider,
+            &filter,
+            header.num_hash(),
+            &receipts,
+            false,
+            header.timestamp,
+        )
+        .map_err(|err| internal_rpc_err(err.to_string()))?;
+        for log in logs {
+            let msg = SubscriptionMessage::from_json(&EthSubscriptionResult::Log(Box::new(log)))
+                .map_err(SubscriptionSerializeError::new)?;
+            if sink.send(msg).await.is_err() {
+                return Ok(())
+            }
+        }
+    }
+
+    let stream = canon_state
+        .map(move |canon_state| canon_state.block_receipts())
+        .flat_map(futures::stream::iter)
+        .filter(move |(block_receipts, removed)| {
+            futures::future::ready(emitted.record(block_receipts.block, *removed))
+        })
+        .flat_map(move |(block_receipts, removed)| {
+            let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                &filter,
+                block_receipts.block,
+                block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                removed,
+            );
+            futures::stream::iter(all_logs)
+        })
+        .map(|log| EthSubscriptionResult::Log(Box::new(log)));
+    pipe_from_stream(sink, stream).await
+}
+
+/// The blocks whose logs a backfilled logs subscription emitted.
+///
+/// Used to emit the logs of every block once across the boundary between the backfill and the new
+/// blocks.
+#[derive(Debug)]
+struct EmittedBlocks {
+    /// The first backfilled block.
+    from_block: u64,
+    /// The hashes of the recently emitted blocks by number.
+    recent: BTreeMap<u64, B256>,
+}
+
+impl EmittedBlocks {
+    const fn new(from_block: u64) -> Self {
+        Self { from_block, recent: BTreeMap::new() }
+    }
+
+    /// Records a committed or reverted block and returns whether its logs should be emitted.
+    fn record(&mut self, block: BlockNumHash, removed: bool) -> bool {
+        if removed {
+            // only blocks that were emitted are removed
+            if block.number < self.from_block ||
+                self.recent.get(&block.number).is_some_and(|hash| *hash != block.hash)
+            {
+                return false
+            }
+            self.recent.remove(&block.number);
+            return true
+        }
+
+        if self.recent.get(&block.number) == Some(&block.hash) {
+            // already emitted by the backfill
+            return false
+        }
+        self.recent.insert(block.number, block.hash);
+        self.recent = self.recent.split_off(&block.number.saturating_sub(LOG_BACKFILL_REORG_DEPTH));
+        true
+    }
+}
+
 /// Helper to convert a serde error into an [`ErrorObject`]
 #[derive(Debug, thiserror::Error)]
 #[error("Failed to serialize subscription item: {0}")]
@@ -322,10 +481,9 @@ where
 
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
-            })
+        self.chain_events
+            .canonical_state_stream()
+            .map(move |canon_state| canon_state.block_receipts())
             .flat_map(futures::stream::iter)
             .flat_map(move |(block_receipts, removed)| {
                 let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
@@ -338,3 +496,31 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emitted_blocks_across_backfill_boundary() {
+        let block = |number, hash| BlockNumHash::new(number, B256::with_last_byte(hash));
+        let mut emitted = EmittedBlocks::new(10);
+
+        // backfill
+        assert!(emitted.record(block(10, 1), false));
+        assert!(emitted.record(block(11, 1), false));
+
+        // block 11 was committed before the backfill read the head
+        assert!(!emitted.record(block(11, 1), false));
+        assert!(emitted.record(block(12, 1), false));
+
+        // reorg of block 11 and 12, block 9 was never emitted
+        assert!(!emitted.record(block(9, 1), true));
+        assert!(emitted.record(block(11, 1), true));
+        assert!(emitted.record(block(12, 1), true));
+        assert!(emitted.record(block(11, 2), false));
+
+        // a reverted block that the backfill never read
+        assert!(!emitted.record(block(11, 3), true));
+    }
+}
@@ -39,18 +39,26 @@ pub mod eth;
 mod net;
 mod otterscan;
 mod reth;
+mod reth_sync;
+mod reth_tip;
+mod reth_validation;
 mod rpc;
 mod trace;
 mod txpool;
+mod validation;
 mod web3;
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminConfigApi, AdminTxPoolApi, ConfigReloader};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
+pub use reth_sync::{RethSyncApi, SyncProgressProvider};
+pub use reth_tip::RethTipApi;
+pub use reth_validation::RethValidationApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
+pub use validation::{ValidationApi, ValidationApiError, DEFAULT_BUILDER_VALIDATION_DEADLINE};
 pub use web3::Web3Api;
@@ -1,15 +1,46 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{collections::HashMap, future::Future, ops::RangeInclusive, sync::Arc};
 
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::RpcResult, server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink,
+    SubscriptionSink,
+};
+use reth_chainspec::{ChainSpec, ForkCondition};
+use reth_db_api::models::StoredReorg;
 use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_execution_types::{BlockStateDiff, BlockStateDiffBuilder};
+use reth_primitives::{
+    constants::eip4844::{BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB},
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytes, B256, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    ReorgJournalReader, StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_eth_types::{EthApiError, EthResult, ReceiptBuilder};
+use reth_rpc_types::{
+    BlobSchedule, DepositContractConfig, EffectiveChainConfig, HardforkActivation, ReceiptsByBlock,
+    ReorgEvent,
+};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+use crate::eth::pubsub::SubscriptionSerializeError;
+
+/// The default number of reorgs returned by `reth_getReorgHistory`.
+const DEFAULT_REORG_HISTORY_LIMIT: usize = 100;
+
+/// The maximum number of reorgs returned by `reth_getReorgHistory`.
+const MAX_REORG_HISTORY_LIMIT: usize = 1024;
+
+/// The maximum number of blocks whose receipts are returned by `reth_getReceiptsByBlockRange`.
+const MAX_RECEIPTS_BLOCK_RANGE: u64 = 1_000;
+
+/// The number of blocks whose receipts are read at once by `reth_subscribeReceiptsByBlockRange`.
+const RECEIPTS_STREAM_CHUNK_SIZE: u64 = 100;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -26,15 +57,24 @@ impl<Provider> RethApi<Provider> {
     }
 
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        chain_events: Box<dyn CanonStateSubscriptions>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, chain_events, task_spawner });
         Self { inner }
     }
 }
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + StateProviderFactory
+        + ReorgJournalReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -82,12 +122,255 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the state changes of the canonical block with the given hash.
+    pub async fn execution_outcome(&self, block_hash: B256) -> EthResult<BlockStateDiff> {
+        self.on_blocking_task(|this| async move { this.try_execution_outcome(block_hash) }).await
+    }
+
+    fn try_execution_outcome(&self, block_hash: B256) -> EthResult<BlockStateDiff> {
+        let Some(block_number) = self.provider().block_number_for_id(block_hash.into())? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+
+        // the changesets contain the values before the block
+        let mut builder = BlockStateDiffBuilder::default();
+        for account_before in self.provider().account_block_changeset(block_number)? {
+            builder.account_before(account_before.address, account_before.info);
+        }
+        for (address, entry) in self.provider().storage_block_changeset(block_number)? {
+            builder.storage_before(address, entry.key, entry.value);
+        }
+
+        // the state at the block contains the values after the block
+        let state = self.provider().state_by_block_hash(block_hash)?;
+        let diff = builder.build(
+            block_number,
+            block_hash,
+            |address| state.basic_account(address),
+            |address, slot| Ok(state.storage(address, slot)?.unwrap_or_default()),
+            |code_hash| Ok(state.bytecode_by_hash(code_hash)?.map(|code| code.original_bytes())),
+        )?;
+        Ok(diff)
+    }
+
+    /// Returns up to `limit` of the most recent reorgs recorded in the reorg journal, newest
+    /// first.
+    pub async fn reorg_history(&self, limit: Option<usize>) -> EthResult<Vec<ReorgEvent>> {
+        let limit = limit.unwrap_or(DEFAULT_REORG_HISTORY_LIMIT).min(MAX_REORG_HISTORY_LIMIT);
+        self.on_blocking_task(|this| async move {
+            let history = this.provider().reorg_history(limit)?;
+            Ok(history.into_iter().map(|(id, reorg)| reorg_event(id, reorg)).collect())
+        })
+        .await
+    }
+
+    /// Resolves the inclusive block range from `start` to `end`.
+    fn block_range(
+        &self,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> EthResult<RangeInclusive<BlockNumber>> {
+        let start =
+            self.provider().convert_block_number(start)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let end =
+            self.provider().convert_block_number(end)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        if start > end {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        Ok(start..=end)
+    }
+
+    /// Returns the receipts of all blocks in the range, grouped by block.
+    pub async fn receipts_by_block_range(
+        &self,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> EthResult<Vec<ReceiptsByBlock>> {
+        let range = self.block_range(start, end)?;
+        if range.end() - range.start() >= MAX_RECEIPTS_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {MAX_RECEIPTS_BLOCK_RANGE} blocks"
+            )))
+        }
+        self.on_blocking_task(|this| async move { this.try_receipts_by_block_range(range) }).await
+    }
+
+    fn try_receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> EthResult<Vec<ReceiptsByBlock>> {
+        let blocks = self.provider().sealed_block_with_senders_range(range.clone())?;
+        // the receipts of the range are read at once, which is sequential in the static files
+        let receipts = self.provider().receipts_by_block_range(range)?;
+
+        blocks
+            .into_iter()
+            .zip(receipts)
+            .map(|(block, receipts)| {
+                let receipts = ReceiptBuilder::block_receipts(&block.block, &receipts)?
+                    .into_iter()
+                    .map(ReceiptBuilder::build)
+                    .collect();
+                Ok(ReceiptsByBlock {
+                    block_number: block.number,
+                    block_hash: block.hash(),
+                    receipts,
+                })
+            })
+            .collect()
+    }
+
+    /// Sends the receipts of every block in the range to the subscription sink, reading them in
+    /// chunks.
+    async fn pipe_receipts(
+        &self,
+        sink: SubscriptionSink,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), ErrorObject<'static>> {
+        for chunk_start in range.clone().step_by(RECEIPTS_STREAM_CHUNK_SIZE as usize) {
+            let chunk_end = (chunk_start + RECEIPTS_STREAM_CHUNK_SIZE - 1).min(*range.end());
+            let chunk = self
+                .on_blocking_task(|this| async move {
+                    this.try_receipts_by_block_range(chunk_start..=chunk_end)
+                })
+                .await?;
+
+            let complete = chunk.len() as u64 == chunk_end - chunk_start + 1;
+            for receipts in chunk {
+                let msg = SubscriptionMessage::from_json(&receipts)
+                    .map_err(SubscriptionSerializeError::from)?;
+                if sink.send(msg).await.is_err() {
+                    // connection dropped
+                    return Ok(())
+                }
+            }
+            if !complete {
+                // the remaining blocks are not available
+                break
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends every reorg that is appended to the reorg journal to the subscription sink.
+    async fn pipe_reorgs(&self, sink: SubscriptionSink) -> Result<(), ErrorObject<'static>> {
+        let mut canon_state = self.inner.chain_events.canonical_state_stream();
+        let mut next_id =
+            self.provider().latest_reorg().map_err(EthApiError::from)?.map_or(0, |(id, _)| id + 1);
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break Ok(())
+                },
+                notification = canon_state.next() => {
+                    if notification.is_none() {
+                        // stream ended
+                        break Ok(())
+                    }
+
+                    // The tree journals a reorg before announcing it. The journal is checked for
+                    // new reorgs on every notification, because reorg notifications can be
+                    // dropped if this subscription lags behind.
+                    let reorgs =
+                        self.provider().reorgs_since(next_id).map_err(EthApiError::from)?;
+                    for (id, reorg) in reorgs {
+                        next_id = id + 1;
+                        let msg = SubscriptionMessage::from_json(&reorg_event(id, reorg))
+                            .map_err(SubscriptionSerializeError::from)?;
+                        if sink.send(msg).await.is_err() {
+                            return Ok(())
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the configuration of the chain spec, with the hardfork overrides that were applied to
+/// it.
+fn effective_chain_config(chain_spec: &ChainSpec) -> EffectiveChainConfig {
+    let hardforks = chain_spec
+        .hardforks
+        .forks_iter()
+        .filter_map(|(fork, condition)| hardfork_activation(fork.name(), condition))
+        .collect::<Vec<_>>();
+    let overrides = chain_spec
+        .hardfork_overrides
+        .iter()
+        .filter_map(|(fork, _)| hardfork_activation(fork.name(), chain_spec.hardforks.fork(fork)))
+        .collect();
+    let blob_schedule = chain_spec
+        .blob_schedule
+        .iter()
+        // skip the forks that are never activated
+        .filter(|(fork, _)| hardforks.iter().any(|activation| activation.name == fork.name()))
+        .map(|(fork, params)| BlobSchedule {
+            fork: fork.name().to_string(),
+            target_blobs_per_block: params.target_blobs_per_block,
+            max_blobs_per_block: params.max_blobs_per_block,
+            blob_gas_per_blob: DATA_GAS_PER_BLOB,
+            base_fee_update_fraction: u128::from(params.base_fee_update_fraction),
+            min_blob_base_fee: BLOB_TX_MIN_BLOB_GASPRICE,
+        })
+        .collect();
+    let deposit_contract =
+        chain_spec.deposit_contract.as_ref().map(|contract| DepositContractConfig {
+            address: contract.address,
+            block: contract.block,
+            topic: contract.topic,
+        });
+
+    EffectiveChainConfig {
+        chain_id: chain_spec.chain.id(),
+        genesis_hash: chain_spec.genesis_hash(),
+        hardforks,
+        blob_schedule,
+        deposit_contract,
+        overrides,
+    }
+}
+
+/// Returns the activation of the fork, or `None` if it is never activated.
+fn hardfork_activation(name: &str, condition: ForkCondition) -> Option<HardforkActivation> {
+    let mut activation = HardforkActivation { name: name.to_string(), ..Default::default() };
+    match condition {
+        ForkCondition::Block(block) => activation.block = Some(block),
+        ForkCondition::TTD { fork_block, total_difficulty } => {
+            activation.block = fork_block;
+            activation.total_difficulty = Some(total_difficulty);
+        }
+        ForkCondition::Timestamp(timestamp) => activation.timestamp = Some(timestamp),
+        ForkCondition::Never => return None,
+    }
+    Some(activation)
+}
+
+/// Converts an entry of the reorg journal into its RPC representation.
+fn reorg_event(id: u64, reorg: StoredReorg) -> ReorgEvent {
+    ReorgEvent {
+        id,
+        timestamp: reorg.timestamp,
+        depth: reorg.depth(),
+        fork_block_number: reorg.fork_block_number,
+        fork_block_hash: reorg.fork_block_hash,
+        old_blocks: reorg.old_blocks,
+        new_blocks: reorg.new_blocks,
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + StateProviderFactory
+        + ReorgJournalReader
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -96,6 +379,68 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getExecutionOutcome`
+    async fn reth_get_execution_outcome(&self, block_hash: B256) -> RpcResult<Bytes> {
+        let diff = Self::execution_outcome(self, block_hash).await?;
+        Ok(alloy_rlp::encode(&diff).into())
+    }
+
+    /// Handler for `reth_getReorgHistory`
+    async fn reth_get_reorg_history(&self, limit: Option<usize>) -> RpcResult<Vec<ReorgEvent>> {
+        Ok(Self::reorg_history(self, limit).await?)
+    }
+
+    /// Handler for `reth_subscribeReorgs`
+    async fn reth_subscribe_reorgs(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = this.pipe_reorgs(sink).await;
+        }));
+
+        Ok(())
+    }
+
+    /// Handler for `reth_getReceiptsByBlockRange`
+    async fn reth_get_receipts_by_block_range(
+        &self,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> RpcResult<Vec<ReceiptsByBlock>> {
+        Ok(Self::receipts_by_block_range(self, start, end).await?)
+    }
+
+    /// Handler for `reth_subscribeReceiptsByBlockRange`
+    async fn reth_subscribe_receipts_by_block_range(
+        &self,
+        pending: PendingSubscriptionSink,
+        start: BlockNumberOrTag,
+        end: BlockNumberOrTag,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let range = match self.block_range(start, end) {
+            Ok(range) => range,
+            Err(err) => {
+                pending.reject(ErrorObject::from(err)).await;
+                return Ok(())
+            }
+        };
+        let sink = pending.accept().await?;
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = this.pipe_receipts(sink, range).await;
+        }));
+
+        Ok(())
+    }
+
+    /// Handler for `reth_chainConfig`
+    fn reth_chain_config(&self) -> RpcResult<EffectiveChainConfig> {
+        Ok(effective_chain_config(&self.provider().chain_spec()))
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -113,6 +458,8 @@ impl<Provider> Clone for RethApi<Provider> {
 struct RethApiInner<Provider> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// Notifications about changes of the canonical chain.
+    chain_events: Box<dyn CanonStateSubscriptions>,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
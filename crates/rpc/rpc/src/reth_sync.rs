@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::RethSyncApiServer;
+use reth_rpc_types::SyncProgress;
+
+/// Provides the progress of the pipeline sync of the node.
+pub trait SyncProgressProvider: Send + Sync {
+    /// Returns the progress of the sync.
+    fn sync_progress(&self) -> SyncProgress;
+}
+
+impl<F> SyncProgressProvider for F
+where
+    F: Fn() -> SyncProgress + Send + Sync,
+{
+    fn sync_progress(&self) -> SyncProgress {
+        self()
+    }
+}
+
+/// `reth` API implementation of the sync progress.
+pub struct RethSyncApi {
+    /// Provides the progress of the sync.
+    progress: Arc<dyn SyncProgressProvider>,
+}
+
+impl RethSyncApi {
+    /// Create a new instance of the [`RethSyncApi`]
+    pub const fn new(progress: Arc<dyn SyncProgressProvider>) -> Self {
+        Self { progress }
+    }
+}
+
+impl RethSyncApiServer for RethSyncApi {
+    /// Handler for `reth_syncProgress`
+    fn reth_sync_progress(&self) -> RpcResult<SyncProgress> {
+        Ok(self.progress.sync_progress())
+    }
+}
+
+impl std::fmt::Debug for RethSyncApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethSyncApi").finish_non_exhaustive()
+    }
+}
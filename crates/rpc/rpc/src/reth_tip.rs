@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{constants::eip4844::DATA_GAS_PER_BLOB, BlockId, U256};
+use reth_provider::ChainSpecProvider;
+use reth_rpc_api::RethTipApiServer;
+use reth_rpc_eth_api::helpers::{EthCall, LoadPendingBlock};
+use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_types::{
+    ExcludedTransaction, ExclusionReason, PendingBlockReport, PoolBlock, PoolBlockTransaction,
+    PoolBlocksAttributes, TipForInclusion, TransactionRequest,
+};
+use reth_transaction_pool::{
+    AllPoolTransactions, BestTransactions, BestTransactionsAttributes, PoolTransaction,
+    TransactionPool,
+};
+
+/// The maximum number of blocks `reth_suggestTipForInclusion` looks ahead.
+const MAX_INCLUSION_BLOCKS: u64 = 64;
+
+/// The maximum number of blocks `reth_contentAsBlocks` fills.
+const MAX_POOL_BLOCKS: u64 = 64;
+
+/// `reth` API implementation of tip suggestions.
+///
+/// Simulates transactions against the locally built pending block of the `eth` API.
+#[derive(Debug, Clone)]
+pub struct RethTipApi<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethTipApi<Eth> {
+    /// Create a new instance of the [`RethTipApi`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+impl<Eth> RethTipApi<Eth>
+where
+    Eth: EthCall + LoadPendingBlock + 'static,
+{
+    /// Returns the minimal tip for the transaction to be included within the given number of
+    /// blocks.
+    ///
+    /// The transaction competes with the transactions of the local pending block and the best
+    /// transactions of the pool, which are assumed to use their full gas limit.
+    pub async fn suggest_tip_for_inclusion(
+        &self,
+        request: TransactionRequest,
+        blocks: Option<u64>,
+    ) -> EthResult<TipForInclusion> {
+        let blocks = blocks.unwrap_or(1);
+        if !(1..=MAX_INCLUSION_BLOCKS).contains(&blocks) {
+            return Err(EthApiError::InvalidParams(format!(
+                "blocks must be between 1 and {MAX_INCLUSION_BLOCKS}"
+            )))
+        }
+
+        let pending =
+            self.eth_api.local_pending_block().await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let base_fee = pending.base_fee_per_gas.unwrap_or_default();
+
+        let gas_used: u64 =
+            self.eth_api.estimate_gas_at(request, BlockId::pending(), None).await?.saturating_to();
+
+        // the pending block is what the pool would produce right now, the remaining pending
+        // transactions of the pool compete for the following blocks
+        let mut competing = pending
+            .body
+            .iter()
+            .filter_map(|tx| Some((tx.effective_tip_per_gas(Some(base_fee))?, tx.gas_limit())))
+            .collect::<Vec<_>>();
+        let included = pending.body.iter().map(|tx| tx.hash()).collect::<HashSet<_>>();
+        competing.extend(
+            LoadPendingBlock::pool(&self.eth_api)
+                .best_transactions()
+                .filter(|tx| !included.contains(tx.hash()))
+                .filter_map(|tx| Some((tx.effective_tip_per_gas(base_fee)?, tx.gas_limit()))),
+        );
+
+        let capacity = pending.gas_limit.saturating_mul(blocks).saturating_sub(gas_used);
+        let (tip, transactions_ahead) = min_tip_for_inclusion(competing, capacity);
+
+        Ok(TipForInclusion {
+            max_priority_fee_per_gas: U256::from(tip),
+            base_fee_per_gas: U256::from(base_fee),
+            gas_used,
+            blocks,
+            transactions_ahead,
+        })
+    }
+
+    /// Returns the report of the locally built pending block.
+    ///
+    /// The builder records the transactions it excludes. The remaining transactions of the pool
+    /// are either never offered to the builder, because their fees are too low or they are queued,
+    /// or they are descendants of excluded transactions. Transactions that arrived after the
+    /// block was built are not reported.
+    pub async fn pending_block_report(&self) -> EthResult<PendingBlockReport> {
+        let (block, stats) = self
+            .eth_api
+            .local_pending_block_with_stats()
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let blob_params = LoadPendingBlock::provider(&self.eth_api)
+            .chain_spec()
+            .blob_params_at_timestamp(block.timestamp);
+        let limits = PendingLimits {
+            base_fee: block.base_fee_per_gas.unwrap_or_default(),
+            blob_fee: block.blob_fee_with_params(blob_params),
+            blobs_full: block.blob_gas_used == Some(blob_params.max_blob_gas_per_block()),
+        };
+
+        let included = block.body.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+        let mut excluded = stats.excluded;
+        let mut known = included
+            .iter()
+            .copied()
+            .chain(excluded.iter().map(|tx| tx.hash))
+            .collect::<HashSet<_>>();
+        // the lowest excluded nonce of every sender, the builder skips all higher nonces unless the
+        // nonce is too low
+        let mut excluded_nonces = HashMap::new();
+        for tx in excluded.iter().filter(|tx| tx.reason != ExclusionReason::NonceTooLow) {
+            excluded_nonces
+                .entry(tx.sender)
+                .and_modify(|nonce: &mut u64| *nonce = (*nonce).min(tx.nonce))
+                .or_insert(tx.nonce);
+        }
+
+        let AllPoolTransactions { pending, queued } =
+            LoadPendingBlock::pool(&self.eth_api).all_transactions();
+        let remaining = pending
+            .iter()
+            .map(|tx| (tx, true))
+            .chain(queued.iter().map(|tx| (tx, false)))
+            .filter(|(tx, _)| !tx.origin.is_private());
+        for (tx, is_pending) in remaining {
+            if !known.insert(*tx.hash()) {
+                continue
+            }
+            let excluded_ancestor =
+                excluded_nonces.get(&tx.sender()).is_some_and(|nonce| *nonce < tx.nonce());
+            let reason = limits.exclusion_reason(
+                tx.max_fee_per_gas(),
+                tx.max_fee_per_blob_gas(),
+                is_pending,
+                excluded_ancestor,
+            );
+            if let Some(reason) = reason {
+                excluded.push(ExcludedTransaction {
+                    hash: *tx.hash(),
+                    sender: tx.sender(),
+                    nonce: tx.nonce(),
+                    reason,
+                });
+            }
+        }
+
+        Ok(PendingBlockReport {
+            number: block.number,
+            parent_hash: block.parent_hash,
+            base_fee_per_gas: U256::from(limits.base_fee),
+            blob_fee_per_gas: limits.blob_fee.map(U256::from),
+            gas_used: block.gas_used,
+            gas_limit: block.gas_limit,
+            included,
+            reverted: stats.reverted,
+            excluded,
+        })
+    }
+
+    /// Returns the next blocks filled from the best transactions of the pool.
+    ///
+    /// Attributes that are not given are taken from the pending block.
+    pub fn content_as_blocks(
+        &self,
+        blocks: u64,
+        attributes: PoolBlocksAttributes,
+    ) -> EthResult<Vec<PoolBlock>> {
+        if !(1..=MAX_POOL_BLOCKS).contains(&blocks) {
+            return Err(EthApiError::InvalidParams(format!(
+                "blocks must be between 1 and {MAX_POOL_BLOCKS}"
+            )))
+        }
+
+        let block_env = self.eth_api.pending_block_env_and_cfg()?.block_env;
+        let base_fee = attributes.base_fee_per_gas.unwrap_or_else(|| block_env.basefee.to());
+        let blob_fee = attributes
+            .blob_fee_per_gas
+            .or_else(|| block_env.get_blob_gasprice().map(|gasprice| gasprice as u64));
+        let gas_limit = attributes.gas_limit.unwrap_or_else(|| block_env.gas_limit.to());
+        let max_blob_gas = LoadPendingBlock::provider(&self.eth_api)
+            .chain_spec()
+            .blob_params_at_timestamp(block_env.timestamp.to())
+            .max_blob_gas_per_block();
+
+        Ok(fill_pool_blocks(
+            &LoadPendingBlock::pool(&self.eth_api),
+            blocks as usize,
+            BestTransactionsAttributes::new(base_fee, blob_fee),
+            gas_limit,
+            max_blob_gas,
+        ))
+    }
+}
+
+/// Fills the given number of blocks with the best transactions of the pool, assuming every
+/// transaction uses its full gas limit.
+///
+/// Every block is filled from a new iterator over the best transactions, which skips the
+/// transactions of the previous blocks so that their descendants can follow. Transactions that
+/// don't fit into a block are skipped with their descendants, and may be included in the next
+/// block. Stops early once a block stays empty.
+fn fill_pool_blocks<P: TransactionPool>(
+    pool: &P,
+    blocks: usize,
+    attributes: BestTransactionsAttributes,
+    gas_limit: u64,
+    max_blob_gas: u64,
+) -> Vec<PoolBlock> {
+    let mut included = HashSet::new();
+    let mut filled = Vec::with_capacity(blocks);
+
+    while filled.len() < blocks {
+        let mut block = PoolBlock::default();
+        let mut best_txs = pool.best_transactions_with_attributes(attributes.clone());
+        while let Some(pool_tx) = best_txs.next() {
+            if included.contains(pool_tx.hash()) {
+                continue
+            }
+
+            let blob_gas = pool_tx.transaction.blob_count() as u64 * DATA_GAS_PER_BLOB;
+            if pool_tx.origin.is_private() ||
+                block.gas_used + pool_tx.gas_limit() > gas_limit ||
+                block.blob_gas_used + blob_gas > max_blob_gas
+            {
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
+            block.gas_used += pool_tx.gas_limit();
+            block.blob_gas_used += blob_gas;
+            included.insert(*pool_tx.hash());
+            block.transactions.push(PoolBlockTransaction {
+                hash: *pool_tx.hash(),
+                sender: pool_tx.sender(),
+                nonce: pool_tx.nonce(),
+                gas_limit: pool_tx.gas_limit(),
+                effective_tip_per_gas: U256::from(
+                    pool_tx.effective_tip_per_gas(attributes.basefee).unwrap_or_default(),
+                ),
+            });
+        }
+
+        if block.transactions.is_empty() {
+            break
+        }
+        filled.push(block);
+    }
+
+    filled
+}
+
+/// The fees and the blob capacity of a pending block.
+#[derive(Debug, Clone, Copy)]
+struct PendingLimits {
+    /// The base fee per gas of the block.
+    base_fee: u64,
+    /// The blob fee per blob gas of the block, if blobs are enabled.
+    blob_fee: Option<u128>,
+    /// Whether the blob gas of the block is used up.
+    blobs_full: bool,
+}
+
+impl PendingLimits {
+    /// Returns why a pool transaction that the block builder didn't record is not included, or
+    /// `None` if it's not known.
+    fn exclusion_reason(
+        &self,
+        max_fee_per_gas: u128,
+        max_fee_per_blob_gas: Option<u128>,
+        is_pending: bool,
+        excluded_ancestor: bool,
+    ) -> Option<ExclusionReason> {
+        if max_fee_per_gas < self.base_fee as u128 {
+            return Some(ExclusionReason::BaseFee)
+        }
+        if let Some(max_fee_per_blob_gas) = max_fee_per_blob_gas {
+            if self.blob_fee.is_some_and(|blob_fee| max_fee_per_blob_gas < blob_fee) {
+                return Some(ExclusionReason::BlobFee)
+            }
+        }
+        if excluded_ancestor {
+            return Some(ExclusionReason::ExcludedAncestor)
+        }
+        if !is_pending {
+            return Some(ExclusionReason::NonceGap)
+        }
+        if max_fee_per_blob_gas.is_some() && self.blobs_full {
+            return Some(ExclusionReason::BlobGasLimit)
+        }
+        None
+    }
+}
+
+/// Returns the minimal tip that outbids the competing transactions which don't fit into the given
+/// gas capacity, and the number of competing transactions that still fit.
+///
+/// Competing transactions are given as pairs of effective tip and gas.
+fn min_tip_for_inclusion(mut competing: Vec<(u128, u64)>, capacity: u64) -> (u128, u64) {
+    competing.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+    let mut gas = 0u64;
+    let competing_count = competing.len() as u64;
+    for (ahead, (tip, tx_gas)) in competing.into_iter().enumerate() {
+        gas = gas.saturating_add(tx_gas);
+        if gas > capacity {
+            // ties are ordered by arrival, so the tip has to be strictly higher
+            return (tip.saturating_add(1), ahead as u64)
+        }
+    }
+
+    (0, competing_count)
+}
+
+#[async_trait]
+impl<Eth> RethTipApiServer for RethTipApi<Eth>
+where
+    Eth: EthCall + LoadPendingBlock + 'static,
+{
+    /// Handler for `reth_suggestTipForInclusion`
+    async fn reth_suggest_tip_for_inclusion(
+        &self,
+        request: TransactionRequest,
+        blocks: Option<u64>,
+    ) -> RpcResult<TipForInclusion> {
+        Ok(Self::suggest_tip_for_inclusion(self, request, blocks).await?)
+    }
+
+    /// Handler for `reth_pendingBlockReport`
+    async fn reth_pending_block_report(&self) -> RpcResult<PendingBlockReport> {
+        Ok(Self::pending_block_report(self).await?)
+    }
+
+    /// Handler for `reth_contentAsBlocks`
+    async fn reth_content_as_blocks(
+        &self,
+        blocks: u64,
+        attributes: Option<PoolBlocksAttributes>,
+    ) -> RpcResult<Vec<PoolBlock>> {
+        Ok(Self::content_as_blocks(self, blocks, attributes.unwrap_or_default())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::constants::eip4844::MAX_DATA_GAS_PER_BLOCK;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    #[test]
+    fn min_tip_outbids_first_excluded_transaction() {
+        let competing = vec![(5, 10), (1, 10), (10, 10), (3, 10)];
+
+        // everything fits
+        assert_eq!(min_tip_for_inclusion(competing.clone(), 40), (0, 4));
+        // only the two best transactions fit
+        assert_eq!(min_tip_for_inclusion(competing.clone(), 25), (4, 2));
+        // nothing fits
+        assert_eq!(min_tip_for_inclusion(competing, 5), (11, 0));
+        assert_eq!(min_tip_for_inclusion(Vec::new(), 0), (0, 0));
+    }
+
+    #[test]
+    fn exclusion_reason_of_remaining_transactions() {
+        let limits = PendingLimits { base_fee: 10, blob_fee: Some(5), blobs_full: false };
+
+        assert_eq!(limits.exclusion_reason(9, None, true, false), Some(ExclusionReason::BaseFee));
+        assert_eq!(
+            limits.exclusion_reason(10, Some(4), true, true),
+            Some(ExclusionReason::BlobFee)
+        );
+        assert_eq!(
+            limits.exclusion_reason(10, None, true, true),
+            Some(ExclusionReason::ExcludedAncestor)
+        );
+        assert_eq!(
+            limits.exclusion_reason(10, None, false, false),
+            Some(ExclusionReason::NonceGap)
+        );
+        // arrived after the block was built
+        assert_eq!(limits.exclusion_reason(10, Some(5), true, false), None);
+
+        let limits = PendingLimits { blobs_full: true, ..limits };
+        assert_eq!(
+            limits.exclusion_reason(10, Some(5), true, false),
+            Some(ExclusionReason::BlobGasLimit)
+        );
+    }
+
+    #[tokio::test]
+    async fn fills_blocks_from_best_transactions() {
+        let pool = testing_pool();
+        let a0 = MockTransaction::eip1559()
+            .with_gas_limit(100_000)
+            .with_max_fee(100)
+            .with_priority_fee(5);
+        let a1 = a0.next();
+        let b0 = MockTransaction::eip1559()
+            .with_gas_limit(150_000)
+            .with_max_fee(100)
+            .with_priority_fee(10);
+        for tx in [a0.clone(), a1.clone(), b0.clone()] {
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        let blocks = fill_pool_blocks(
+            &pool,
+            5,
+            BestTransactionsAttributes::base_fee(7),
+            250_000,
+            MAX_DATA_GAS_PER_BLOCK,
+        );
+        let hashes = blocks
+            .iter()
+            .map(|block| block.transactions.iter().map(|tx| tx.hash).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        // the second transaction of `a` doesn't fit into the first block and follows in the next,
+        // no transactions are left for more blocks
+        assert_eq!(hashes, vec![vec![b0.get_hash(), a0.get_hash()], vec![a1.get_hash()]]);
+        assert_eq!(blocks[0].gas_used, 250_000);
+        assert_eq!(blocks[0].transactions[0].effective_tip_per_gas, U256::from(10));
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use alloy_rlp::Decodable;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_consensus::{Consensus, PostExecutionInput};
+use reth_evm::execute::{
+    BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
+};
+use reth_primitives::{
+    Block, BlockId, BlockWithSenders, Bytes, Receipt, SealedBlock, SealedHeader, B256,
+};
+use reth_provider::{BlockReaderIdExt, ProviderResult, StateProviderFactory, StateRootProvider};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::RethValidationApiServer;
+use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_types::{
+    BlockValidationCheck, BlockValidationResult, BlockValidationStatus, BlockValidationStep,
+};
+use reth_tasks::TaskSpawner;
+use tokio::sync::oneshot;
+
+/// `reth` API implementation of standalone block validation.
+///
+/// Runs the same consensus and execution checks as the Engine API on a block, without inserting
+/// it into the tree.
+pub struct RethValidationApi<Provider, Executor> {
+    inner: Arc<RethValidationApiInner<Provider, Executor>>,
+}
+
+// === impl RethValidationApi ===
+
+impl<Provider, Executor> RethValidationApi<Provider, Executor> {
+    /// Create a new instance of the [`RethValidationApi`]
+    pub fn new(
+        provider: Provider,
+        consensus: Arc<dyn Consensus>,
+        executor_provider: Executor,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethValidationApiInner {
+            provider,
+            consensus,
+            executor_provider,
+            task_spawner,
+        });
+        Self { inner }
+    }
+
+    /// Returns the provider the blocks are validated against.
+    pub(crate) fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// Returns the type that spawns the validation tasks.
+    pub(crate) fn task_spawner(&self) -> &dyn TaskSpawner {
+        &*self.inner.task_spawner
+    }
+}
+
+impl<Provider, Executor> RethValidationApi<Provider, Executor>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Validates the RLP encoded block on top of the state of its parent, or of the given block.
+    pub async fn validate_block(
+        &self,
+        block: Bytes,
+        parent: Option<BlockId>,
+    ) -> EthResult<BlockValidationResult> {
+        let block = Block::decode(&mut block.as_ref())
+            .map_err(|err| EthApiError::InvalidParams(format!("failed to decode block: {err}")))?
+            .seal_slow();
+        self.on_blocking_task(|this| async move {
+            let validated = this.validate_sealed_block(block, parent, &AtomicBool::new(false))?;
+            Ok(validated.result)
+        })
+        .await
+    }
+
+    /// Runs all checks on the block and returns their results, together with the execution
+    /// output if the block could be executed.
+    ///
+    /// Once `cancelled` is set, the remaining execution and state root checks are skipped.
+    pub(crate) fn validate_sealed_block(
+        &self,
+        block: SealedBlock,
+        parent: Option<BlockId>,
+        cancelled: &AtomicBool,
+    ) -> ProviderResult<ValidatedBlock> {
+        let provider = &self.inner.provider;
+        let consensus = &self.inner.consensus;
+        let mut checks = ValidationChecks::default();
+
+        checks.record(BlockValidationStep::Header, consensus.validate_header(&block.header));
+
+        let parent = match parent {
+            Some(id) => provider.sealed_header_by_id(id)?,
+            None => provider
+                .header(&block.header.parent_hash)?
+                .map(|header| SealedHeader::new(header, block.header.parent_hash)),
+        };
+        let parent_td = match &parent {
+            Some(parent) => provider.header_td(&parent.hash())?,
+            None => None,
+        };
+        let total_difficulty = parent_td.map(|td| td + block.header.difficulty);
+        match (&parent, total_difficulty) {
+            (Some(parent), Some(total_difficulty)) => {
+                let result = consensus
+                    .validate_header_against_parent(&block.header, parent)
+                    .and_then(|_| {
+                        consensus
+                            .validate_header_with_total_difficulty(&block.header, total_difficulty)
+                    });
+                checks.record(BlockValidationStep::HeaderAgainstParent, result);
+            }
+            _ => checks.fail(BlockValidationStep::HeaderAgainstParent, "unknown parent block"),
+        }
+
+        checks.record(
+            BlockValidationStep::PreExecution,
+            consensus.validate_block_pre_execution(&block),
+        );
+
+        let block_hash = block.hash();
+        let block_number = block.header.number;
+        let senders = block.senders();
+        let block = checks.record(
+            BlockValidationStep::SenderRecovery,
+            senders
+                .and_then(|senders| BlockWithSenders::new(block.unseal(), senders))
+                .ok_or("invalid transaction signature"),
+        );
+
+        let (Some(parent), Some(total_difficulty), Some(block)) = (parent, total_difficulty, block)
+        else {
+            for step in [
+                BlockValidationStep::Execution,
+                BlockValidationStep::PostExecution,
+                BlockValidationStep::StateRoot,
+            ] {
+                checks.skip(step, "parent state or transaction senders unavailable");
+            }
+            return Ok(checks.into_validated(block_hash, block_number, None))
+        };
+
+        if cancelled.load(Ordering::Relaxed) {
+            for step in [
+                BlockValidationStep::Execution,
+                BlockValidationStep::PostExecution,
+                BlockValidationStep::StateRoot,
+            ] {
+                checks.skip(step, VALIDATION_CANCELLED);
+            }
+            return Ok(checks.into_validated(block_hash, block_number, None))
+        }
+
+        let state = provider.state_by_block_hash(parent.hash())?;
+        let output = checks.record(
+            BlockValidationStep::Execution,
+            self.inner
+                .executor_provider
+                .executor(StateProviderDatabase::new(&state))
+                .execute(BlockExecutionInput::new(&block, total_difficulty)),
+        );
+        let Some(output) = output else {
+            checks.skip(BlockValidationStep::PostExecution, "execution failed");
+            checks.skip(BlockValidationStep::StateRoot, "execution failed");
+            return Ok(checks.into_validated(block_hash, block_number, None))
+        };
+
+        checks.record(
+            BlockValidationStep::PostExecution,
+            consensus.validate_block_post_execution(
+                &block,
+                PostExecutionInput::new(&output.receipts, &output.requests),
+            ),
+        );
+
+        if cancelled.load(Ordering::Relaxed) {
+            checks.skip(BlockValidationStep::StateRoot, VALIDATION_CANCELLED);
+            return Ok(checks.into_validated(block_hash, block_number, Some((block, output))))
+        }
+
+        let state_root = state.state_root(&output.state)?;
+        if state_root == block.header.state_root {
+            checks.pass(BlockValidationStep::StateRoot);
+        } else {
+            checks.fail(
+                BlockValidationStep::StateRoot,
+                format!(
+                    "state root mismatch: got {state_root}, expected {}",
+                    block.header.state_root
+                ),
+            );
+        }
+
+        Ok(checks.into_validated(block_hash, block_number, Some((block, output))))
+    }
+}
+
+/// The reason the remaining checks are skipped once a validation is cancelled.
+const VALIDATION_CANCELLED: &str = "validation cancelled";
+
+/// The outcome of [`RethValidationApi::validate_sealed_block`].
+#[derive(Debug)]
+pub(crate) struct ValidatedBlock {
+    /// The results of the checks.
+    pub(crate) result: BlockValidationResult,
+    /// The block and its execution output, if it was executed.
+    pub(crate) executed: Option<(BlockWithSenders, BlockExecutionOutput<Receipt>)>,
+}
+
+/// Collects the results of the checks of a block validation in order.
+#[derive(Debug, Default)]
+struct ValidationChecks(Vec<BlockValidationCheck>);
+
+impl ValidationChecks {
+    /// Records the outcome of the check and returns the value if it passed.
+    fn record<T, E: Display>(
+        &mut self,
+        check: BlockValidationStep,
+        result: Result<T, E>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.pass(check);
+                Some(value)
+            }
+            Err(err) => {
+                self.fail(check, err);
+                None
+            }
+        }
+    }
+
+    fn pass(&mut self, check: BlockValidationStep) {
+        self.push(check, BlockValidationStatus::Passed, None)
+    }
+
+    fn fail(&mut self, check: BlockValidationStep, error: impl Display) {
+        self.push(check, BlockValidationStatus::Failed, Some(error.to_string()))
+    }
+
+    fn skip(&mut self, check: BlockValidationStep, reason: impl Display) {
+        self.push(check, BlockValidationStatus::Skipped, Some(reason.to_string()))
+    }
+
+    fn push(
+        &mut self,
+        check: BlockValidationStep,
+        status: BlockValidationStatus,
+        error: Option<String>,
+    ) {
+        self.0.push(BlockValidationCheck { check, status, error })
+    }
+
+    /// Returns the validation result, the block is valid if every check passed.
+    fn into_result(self, block_hash: B256, block_number: u64) -> BlockValidationResult {
+        let valid = self.0.iter().all(|check| check.status == BlockValidationStatus::Passed);
+        BlockValidationResult { block_hash, block_number, valid, checks: self.0 }
+    }
+
+    fn into_validated(
+        self,
+        block_hash: B256,
+        block_number: u64,
+        executed: Option<(BlockWithSenders, BlockExecutionOutput<Receipt>)>,
+    ) -> ValidatedBlock {
+        ValidatedBlock { result: self.into_result(block_hash, block_number), executed }
+    }
+}
+
+#[async_trait]
+impl<Provider, Executor> RethValidationApiServer for RethValidationApi<Provider, Executor>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Handler for `reth_validateBlock`
+    async fn reth_validate_block(
+        &self,
+        block: Bytes,
+        parent: Option<BlockId>,
+    ) -> RpcResult<BlockValidationResult> {
+        Ok(Self::validate_block(self, block, parent).await?)
+    }
+}
+
+impl<Provider, Executor> std::fmt::Debug for RethValidationApi<Provider, Executor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Executor> Clone for RethValidationApi<Provider, Executor> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethValidationApiInner<Provider, Executor> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Consensus implementation the block is validated against.
+    consensus: Arc<dyn Consensus>,
+    /// The type that can execute blocks.
+    executor_provider: Executor,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_is_valid_only_if_all_checks_pass() {
+        let mut checks = ValidationChecks::default();
+        assert_eq!(checks.record(BlockValidationStep::Header, Ok::<_, String>(1)), Some(1));
+        checks.pass(BlockValidationStep::PreExecution);
+        assert!(checks.into_result(B256::ZERO, 1).valid);
+
+        let mut checks = ValidationChecks::default();
+        checks.pass(BlockValidationStep::Header);
+        assert_eq!(checks.record(BlockValidationStep::Execution, Err::<(), _>("out of gas")), None);
+        checks.skip(BlockValidationStep::StateRoot, "execution failed");
+        let result = checks.into_result(B256::ZERO, 1);
+        assert!(!result.valid);
+        assert_eq!(result.checks[1].status, BlockValidationStatus::Failed);
+        assert_eq!(result.checks[1].error.as_deref(), Some("out of gas"));
+        assert_eq!(result.checks[2].status, BlockValidationStatus::Skipped);
+    }
+}
@@ -8,14 +8,17 @@ use reth_consensus_common::calc::{
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{BlockId, Bytes, Header, B256, U256};
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_provider::{
+    BlockIdReader, BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
-use reth_rpc_eth_api::helpers::{Call, TraceExt};
+use reth_rpc_eth_api::helpers::{Call, LoadBlock, TraceExt};
 use reth_rpc_eth_types::{
     error::{EthApiError, EthResult},
     revm_utils::prepare_call_env,
     utils::recover_raw_transaction,
+    TraceCache, TraceCacheKey,
 };
 use reth_rpc_types::{
     state::{EvmOverrides, StateOverride},
@@ -54,8 +57,13 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [`TraceApi`]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_cache: TraceCache,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard, trace_cache });
         Self { inner }
     }
 
@@ -105,6 +113,11 @@ where
     }
 
     /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces.
+    ///
+    /// The transaction is executed on top of the state of the given block, which defaults to the
+    /// latest block. Like OpenEthereum, the nonce of the transaction is not checked, so
+    /// transactions that were not submitted yet can be traced regardless of the pending
+    /// transactions of the sender.
     pub async fn trace_raw_transaction(
         &self,
         tx: Bytes,
@@ -115,11 +128,11 @@ where
 
         let (cfg, block, at) = self.inner.eth_api.evm_env_at(block_id.unwrap_or_default()).await?;
 
-        let env = EnvWithHandlerCfg::new_with_cfg_env(
-            cfg,
-            block,
-            Call::evm_config(self.eth_api()).tx_env(&tx.into_ecrecovered_transaction()),
-        );
+        let mut tx_env =
+            Call::evm_config(self.eth_api()).tx_env(&tx.into_ecrecovered_transaction());
+        // the nonce check is skipped if no nonce is set
+        tx_env.nonce = None;
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block, tx_env);
 
         let config = TracingInspectorConfig::from_parity_config(&trace_types);
 
@@ -214,22 +227,21 @@ where
             .ok_or_else(|| EthApiError::TransactionNotFound)?
     }
 
-    /// Returns transaction trace objects at the given index
+    /// Returns the trace of the transaction at the given trace address.
     ///
-    /// Note: For compatibility reasons this only supports 1 single index, since this method is
-    /// supposed to return a single trace. See also: <https://github.com/ledgerwatch/erigon/blob/862faf054b8a0fa15962a9c73839b619886101eb/turbo/jsonrpc/trace_filtering.go#L114-L133>
+    /// Like OpenEthereum, `indices` is the trace address of the trace in the call tree of the
+    /// transaction: an empty address returns the top-level call, `[0]` the first call made by it,
+    /// `[0, 1]` the second call made by that call, and so on.
     ///
-    /// This returns `None` if `indices` is empty
+    /// Returns `None` if the transaction has no trace at that address.
     pub async fn trace_get(
         &self,
         hash: B256,
         indices: Vec<usize>,
     ) -> EthResult<Option<LocalizedTransactionTrace>> {
-        if indices.len() != 1 {
-            // The OG impl failed if it gets more than a single index
-            return Ok(None)
-        }
-        self.trace_get_index(hash, indices[0]).await
+        Ok(self.trace_transaction(hash).await?.and_then(|traces| {
+            traces.into_iter().find(|trace| trace.trace.trace_address == indices)
+        }))
     }
 
     /// Returns transaction trace object at the given index.
@@ -367,9 +379,35 @@ where
     }
 
     /// Returns traces created at given block.
+    ///
+    /// The traces of canonical blocks are cached.
     pub async fn trace_block(
         &self,
         block_id: BlockId,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        // the pending block changes without changing its hash
+        let block_hash = if block_id.is_pending() {
+            None
+        } else {
+            LoadBlock::provider(self.eth_api()).block_hash_for_id(block_id)?
+        };
+        let Some(block_hash) = block_hash else { return self.trace_block_uncached(block_id).await };
+
+        let key = TraceCacheKey::block(block_hash, "trace_block", &());
+        if let Some(traces) = self.inner.trace_cache.get(&key) {
+            return Ok(Some(traces))
+        }
+        let traces = self.trace_block_uncached(block_hash.into()).await?;
+        if let Some(traces) = &traces {
+            self.inner.trace_cache.insert(key, traces);
+        }
+        Ok(traces)
+    }
+
+    /// Returns traces created at given block, without using the trace cache.
+    async fn trace_block_uncached(
+        &self,
+        block_id: BlockId,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
         let traces = self.inner.eth_api.trace_block_with(
             block_id,
@@ -638,7 +676,6 @@ where
         Ok(Self::trace_filter(self, filter).await?)
     }
 
-    /// Returns transaction trace at given index.
     /// Handler for `trace_get`
     async fn trace_get(
         &self,
@@ -692,6 +729,8 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Caches the traces of recent blocks
+    trace_cache: TraceCache,
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
@@ -3,11 +3,16 @@ use jsonrpsee::core::RpcResult as Result;
 use reth_primitives::Address;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
-    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
+    txpool::{
+        TxpoolContent, TxpoolContentFrom, TxpoolFilter, TxpoolInspect, TxpoolInspectSummary,
+        TxpoolStatus,
+    },
     Transaction,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
-use std::collections::BTreeMap;
+use reth_transaction_pool::{
+    AllPoolTransactions, PoolTransaction, TransactionPool, ValidPoolTransaction,
+};
+use std::{collections::BTreeMap, sync::Arc};
 use tracing::trace;
 
 /// `txpool` API implementation.
@@ -30,7 +35,34 @@ impl<Pool> TxPoolApi<Pool>
 where
     Pool: TransactionPool + 'static,
 {
-    fn content(&self) -> TxpoolContent {
+    /// Returns all pool transactions, filtered and paginated according to the given filter.
+    ///
+    /// If a filter is set, the pending and queued transactions are sorted by sender and nonce.
+    fn all_transactions(
+        &self,
+        filter: Option<&TxpoolFilter>,
+    ) -> AllPoolTransactions<Pool::Transaction> {
+        fn apply<T: PoolTransaction>(
+            mut txs: Vec<Arc<ValidPoolTransaction<T>>>,
+            filter: &TxpoolFilter,
+        ) -> Vec<Arc<ValidPoolTransaction<T>>> {
+            txs.retain(|tx| {
+                filter.matches(&tx.sender(), tx.tx_type(), tx.transaction.max_fee_per_gas())
+            });
+            txs.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+            txs.into_iter().skip(filter.offset()).take(filter.limit()).collect()
+        }
+
+        let all = self.pool.all_transactions();
+        let Some(filter) = filter else { return all };
+
+        AllPoolTransactions {
+            pending: apply(all.pending, filter),
+            queued: apply(all.queued, filter),
+        }
+    }
+
+    fn content(&self, filter: Option<&TxpoolFilter>) -> TxpoolContent {
         #[inline]
         fn insert<T: PoolTransaction>(
             tx: &T,
@@ -42,7 +74,7 @@ where
             );
         }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let AllPoolTransactions { pending, queued } = self.all_transactions(filter);
 
         let mut content = TxpoolContent::default();
         for pending in pending {
@@ -78,8 +110,8 @@ where
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
     ///
     /// Handler for `txpool_inspect`
-    async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
-        trace!(target: "rpc::eth", "Serving txpool_inspect");
+    async fn txpool_inspect(&self, filter: Option<TxpoolFilter>) -> Result<TxpoolInspect> {
+        trace!(target: "rpc::eth", ?filter, "Serving txpool_inspect");
 
         #[inline]
         fn insert<T: PoolTransaction>(
@@ -99,7 +131,7 @@ where
             );
         }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let AllPoolTransactions { pending, queued } = self.all_transactions(filter.as_ref());
 
         Ok(TxpoolInspect {
             pending: pending.iter().fold(Default::default(), |mut acc, tx| {
@@ -118,9 +150,14 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_contentFrom) for more details
     /// Handler for `txpool_contentFrom`
-    async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
-        trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().remove_from(&from))
+    async fn txpool_content_from(
+        &self,
+        from: Address,
+        filter: Option<TxpoolFilter>,
+    ) -> Result<TxpoolContentFrom> {
+        trace!(target: "rpc::eth", ?from, ?filter, "Serving txpool_contentFrom");
+        let filter = filter.map(|filter| TxpoolFilter { senders: Some(vec![from]), ..filter });
+        Ok(self.content(filter.as_ref()).remove_from(&from))
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -128,9 +165,9 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> Result<TxpoolContent> {
-        trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.content())
+    async fn txpool_content(&self, filter: Option<TxpoolFilter>) -> Result<TxpoolContent> {
+        trace!(target: "rpc::eth", ?filter, "Serving txpool_content");
+        Ok(self.content(filter.as_ref()))
     }
 }
 
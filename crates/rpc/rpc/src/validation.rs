@@ -0,0 +1,453 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_errors::ProviderError;
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider};
+use reth_primitives::{
+    constants::MINIMUM_GAS_LIMIT, kzg_to_versioned_hash, Address, BlockWithSenders, GotExpected,
+    Receipt, SealedBlock, B256, U256,
+};
+use reth_provider::{BlockReaderIdExt, StateProviderFactory};
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types::{
+    beacon::relay::{BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2},
+    engine::{BlobsBundleV1, PayloadError},
+    BlockValidationStatus, BlockValidationStep, BuilderBlockValidationRequestV3,
+};
+use reth_rpc_types_compat::engine::payload::{
+    try_payload_v1_to_block, try_payload_v2_to_block, try_payload_v3_to_block,
+};
+use tokio::sync::oneshot;
+
+use crate::{reth_validation::ValidatedBlock, RethValidationApi};
+
+/// The default time a block submission has to be validated in.
+pub const DEFAULT_BUILDER_VALIDATION_DEADLINE: Duration = Duration::from_secs(2);
+
+/// The `flashbots` block submission validation API used by relays.
+///
+/// A submission is valid if the block passes all checks of [`RethValidationApi`], its gas limit
+/// moves towards the gas limit registered by the proposer and it pays the bid value to the
+/// proposer's fee recipient.
+pub struct ValidationApi<Provider, Executor> {
+    inner: Arc<ValidationApiInner<Provider, Executor>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider, Executor> ValidationApi<Provider, Executor> {
+    /// Create a new instance of the [`ValidationApi`] that validates the blocks of submissions
+    /// with the given [`RethValidationApi`].
+    pub fn new(validation: RethValidationApi<Provider, Executor>) -> Self {
+        let inner = Arc::new(ValidationApiInner {
+            validation,
+            deadline: DEFAULT_BUILDER_VALIDATION_DEADLINE,
+        });
+        Self { inner }
+    }
+
+    /// Sets the time a submission has to be validated in, submissions that take longer are
+    /// rejected and their validation is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If the api is already shared.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        Arc::get_mut(&mut self.inner).expect("not shared yet").deadline = deadline;
+        self
+    }
+}
+
+impl<Provider, Executor> ValidationApi<Provider, Executor>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Executes the future on a new blocking task and fails if it doesn't finish within the
+    /// deadline.
+    ///
+    /// The flag passed to the task is set once the deadline is exceeded or the request is
+    /// dropped, so the task can stop the remaining work.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> Result<R, ValidationApiError>
+    where
+        C: FnOnce(RethValidationApi<Provider, Executor>, Arc<AtomicBool>) -> F,
+        F: Future<Output = Result<R, ValidationApiError>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let cancel = CancelOnDrop::default();
+        let f = c(self.inner.validation.clone(), Arc::clone(&cancel.0));
+        self.inner.validation.task_spawner().spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+
+        let deadline = self.inner.deadline;
+        tokio::time::timeout(deadline, rx)
+            .await
+            .map_err(|_| ValidationApiError::DeadlineExceeded(deadline))?
+            .map_err(|_| ValidationApiError::Internal)?
+    }
+
+    /// Validates the block of a bid submission against the bid.
+    pub async fn validate_submission(
+        &self,
+        message: BidTrace,
+        block: SealedBlock,
+        registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        self.on_blocking_task(|validation, cancelled| async move {
+            validate_message_against_header(&message, &block)?;
+
+            let parent_hash = block.header.parent_hash;
+            let parent = validation
+                .provider()
+                .header(&parent_hash)?
+                .ok_or(ValidationApiError::UnknownParent(parent_hash))?;
+            let expected_gas_limit = expected_gas_limit(parent.gas_limit, registered_gas_limit);
+            if block.header.gas_limit != expected_gas_limit {
+                return Err(ValidationApiError::GasLimitMismatch(GotExpected {
+                    got: block.header.gas_limit,
+                    expected: expected_gas_limit,
+                }))
+            }
+
+            let ValidatedBlock { result, executed } =
+                validation.validate_sealed_block(block, None, &cancelled)?;
+            if let Some(check) = result
+                .checks
+                .into_iter()
+                .find(|check| check.status != BlockValidationStatus::Passed)
+            {
+                return Err(ValidationApiError::InvalidBlock {
+                    check: check.check,
+                    error: check.error.unwrap_or_default(),
+                })
+            }
+            let (block, output) = executed.ok_or(ValidationApiError::Internal)?;
+
+            validate_proposer_payment(&message, &block, &output)
+        })
+        .await
+    }
+}
+
+/// Sets the shared flag once dropped, which cancels the validation task it was passed to.
+#[derive(Debug, Default)]
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Checks that the bid describes the block.
+fn validate_message_against_header(
+    message: &BidTrace,
+    block: &SealedBlock,
+) -> Result<(), ValidationApiError> {
+    if message.block_hash != block.hash() {
+        return Err(ValidationApiError::BlockHashMismatch(GotExpected {
+            got: block.hash(),
+            expected: message.block_hash,
+        }))
+    }
+    if message.parent_hash != block.header.parent_hash {
+        return Err(ValidationApiError::ParentHashMismatch(GotExpected {
+            got: block.header.parent_hash,
+            expected: message.parent_hash,
+        }))
+    }
+    if message.gas_limit != block.header.gas_limit {
+        return Err(ValidationApiError::BidGasLimitMismatch(GotExpected {
+            got: block.header.gas_limit,
+            expected: message.gas_limit,
+        }))
+    }
+    if message.gas_used != block.header.gas_used {
+        return Err(ValidationApiError::BidGasUsedMismatch(GotExpected {
+            got: block.header.gas_used,
+            expected: message.gas_used,
+        }))
+    }
+    Ok(())
+}
+
+/// Checks that the block pays at least the bid value to the proposer's fee recipient.
+///
+/// The payment is either the balance increase of the fee recipient, or, if the builder collects
+/// the fees itself, the last transaction of the block which transfers the bid value from the
+/// block's beneficiary to the fee recipient.
+fn validate_proposer_payment(
+    message: &BidTrace,
+    block: &BlockWithSenders,
+    output: &BlockExecutionOutput<Receipt>,
+) -> Result<(), ValidationApiError> {
+    let fee_recipient = message.proposer_fee_recipient;
+    let balance_increase = output
+        .state
+        .account(&fee_recipient)
+        .map(|account| {
+            let before =
+                account.original_info.as_ref().map(|info| info.balance).unwrap_or_default();
+            let after = account.info.as_ref().map(|info| info.balance).unwrap_or_default();
+            after.saturating_sub(before)
+        })
+        .unwrap_or_default();
+    if balance_increase >= message.value {
+        return Ok(())
+    }
+
+    let (Some((sender, tx)), Some(receipt)) =
+        (block.transactions_with_sender().last(), output.receipts.last())
+    else {
+        return Err(ValidationApiError::ProposerPaymentMissing(fee_recipient))
+    };
+    if tx.to() != Some(fee_recipient) || *sender != block.header.beneficiary || !receipt.success {
+        return Err(ValidationApiError::ProposerPaymentMissing(fee_recipient))
+    }
+    if tx.value() != message.value {
+        return Err(ValidationApiError::ProposerPaymentMismatch(GotExpected {
+            got: tx.value(),
+            expected: message.value,
+        }))
+    }
+
+    Ok(())
+}
+
+/// Checks that the blobs bundle of the submission belongs to the blob transactions of the block.
+fn validate_blobs_bundle(
+    block: &SealedBlock,
+    blobs_bundle: &BlobsBundleV1,
+) -> Result<(), ValidationApiError> {
+    let (commitments, proofs, blobs) =
+        (blobs_bundle.commitments.len(), blobs_bundle.proofs.len(), blobs_bundle.blobs.len());
+    if commitments != proofs || commitments != blobs {
+        return Err(ValidationApiError::BlobsBundleLength { commitments, proofs, blobs })
+    }
+
+    let versioned_hashes = blobs_bundle
+        .commitments
+        .iter()
+        .map(|commitment| kzg_to_versioned_hash(commitment.as_slice()));
+    if !block.blob_versioned_hashes_iter().copied().eq(versioned_hashes) {
+        return Err(ValidationApiError::BlobVersionedHashesMismatch)
+    }
+
+    Ok(())
+}
+
+/// Returns the gas limit a block has to use to move from the parent's gas limit towards the
+/// desired gas limit registered by the proposer, as far as the protocol allows.
+fn expected_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let max_change = (parent_gas_limit / 1024).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + max_change).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(max_change).max(desired_gas_limit)
+    }
+}
+
+#[async_trait]
+impl<Provider, Executor> BlockSubmissionValidationApiServer for ValidationApi<Provider, Executor>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV1`
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        let block = try_payload_v1_to_block(request.request.execution_payload)
+            .map_err(ValidationApiError::from)?
+            .seal_slow();
+        Ok(self
+            .validate_submission(request.request.message, block, request.registered_gas_limit)
+            .await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        let block = try_payload_v2_to_block(request.request.execution_payload)
+            .map_err(ValidationApiError::from)?
+            .seal_slow();
+        if block.header.withdrawals_root != Some(request.withdrawals_root) {
+            return Err(ValidationApiError::WithdrawalsRootMismatch(GotExpected {
+                got: block.header.withdrawals_root.unwrap_or_default(),
+                expected: request.withdrawals_root,
+            })
+            .into())
+        }
+        Ok(self
+            .validate_submission(request.request.message, block, request.registered_gas_limit)
+            .await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV3`
+    async fn validate_builder_submission_v3(
+        &self,
+        request: BuilderBlockValidationRequestV3,
+    ) -> RpcResult<()> {
+        let mut block = try_payload_v3_to_block(request.request.execution_payload)
+            .map_err(ValidationApiError::from)?;
+        block.header.parent_beacon_block_root = Some(request.parent_beacon_block_root);
+        let block = block.seal_slow();
+        validate_blobs_bundle(&block, &request.request.blobs_bundle)?;
+        Ok(self
+            .validate_submission(request.request.message, block, request.registered_gas_limit)
+            .await?)
+    }
+}
+
+impl<Provider, Executor> std::fmt::Debug for ValidationApi<Provider, Executor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi")
+            .field("deadline", &self.inner.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Executor> Clone for ValidationApi<Provider, Executor> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider, Executor> {
+    /// Runs the checks of the submitted blocks.
+    validation: RethValidationApi<Provider, Executor>,
+    /// The time a submission has to be validated in.
+    deadline: Duration,
+}
+
+/// Errors of the block submission validation API.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// The execution payload can't be converted into a block.
+    #[error("invalid execution payload: {0}")]
+    Payload(#[from] PayloadError),
+    /// The block hash of the bid doesn't match the payload.
+    #[error("block hash mismatch: {0}")]
+    BlockHashMismatch(GotExpected<B256>),
+    /// The parent hash of the bid doesn't match the payload.
+    #[error("parent hash mismatch: {0}")]
+    ParentHashMismatch(GotExpected<B256>),
+    /// The gas limit of the bid doesn't match the payload.
+    #[error("bid gas limit mismatch: {0}")]
+    BidGasLimitMismatch(GotExpected<u64>),
+    /// The gas used of the bid doesn't match the payload.
+    #[error("bid gas used mismatch: {0}")]
+    BidGasUsedMismatch(GotExpected<u64>),
+    /// The withdrawals root of the request doesn't match the payload.
+    #[error("withdrawals root mismatch: {0}")]
+    WithdrawalsRootMismatch(GotExpected<B256>),
+    /// The blobs bundle doesn't contain a proof and a blob for every commitment.
+    #[error("blobs bundle has {commitments} commitments, {proofs} proofs and {blobs} blobs")]
+    BlobsBundleLength {
+        /// The number of commitments.
+        commitments: usize,
+        /// The number of proofs.
+        proofs: usize,
+        /// The number of blobs.
+        blobs: usize,
+    },
+    /// The commitments of the blobs bundle don't match the blob versioned hashes of the block.
+    #[error("blob versioned hashes of the block don't match the blobs bundle")]
+    BlobVersionedHashesMismatch,
+    /// The gas limit doesn't move towards the gas limit registered by the proposer.
+    #[error("incorrect gas limit: {0}")]
+    GasLimitMismatch(GotExpected<u64>),
+    /// The parent block isn't known.
+    #[error("unknown parent block {0}")]
+    UnknownParent(B256),
+    /// A check of [`RethValidationApi`] didn't pass.
+    #[error("{check:?} check failed: {error}")]
+    InvalidBlock {
+        /// The check that didn't pass.
+        check: BlockValidationStep,
+        /// The reason the check failed or was skipped.
+        error: String,
+    },
+    /// Neither the balance of the fee recipient increases by the bid value nor does the last
+    /// transaction pay it.
+    #[error("no proposer payment to {0}")]
+    ProposerPaymentMissing(Address),
+    /// The proposer payment transaction doesn't pay the bid value.
+    #[error("proposer payment mismatch: {0}")]
+    ProposerPaymentMismatch(GotExpected<U256>),
+    /// The validation didn't finish within the deadline.
+    #[error("validation did not finish within {0:?}")]
+    DeadlineExceeded(Duration),
+    /// Error while reading the state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The validation task was dropped.
+    #[error("internal error")]
+    Internal,
+}
+
+impl From<ValidationApiError> for ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::Provider(_) | ValidationApiError::Internal => {
+                internal_rpc_err(error.to_string())
+            }
+            error => invalid_params_rpc_err(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_limit_moves_towards_registered_limit() {
+        let parent = 30_000_000;
+        let max_change = parent / 1024 - 1;
+
+        assert_eq!(expected_gas_limit(parent, parent), parent);
+        assert_eq!(expected_gas_limit(parent, 36_000_000), parent + max_change);
+        assert_eq!(expected_gas_limit(parent, 15_000_000), parent - max_change);
+        assert_eq!(expected_gas_limit(parent, parent + 10), parent + 10);
+        assert_eq!(expected_gas_limit(parent, parent - 10), parent - 10);
+        assert_eq!(expected_gas_limit(MINIMUM_GAS_LIMIT, 0), MINIMUM_GAS_LIMIT);
+    }
+
+    #[test]
+    fn blobs_bundle_matches_block() {
+        let block = SealedBlock::default();
+        let mut bundle = BlobsBundleV1 { commitments: vec![], proofs: vec![], blobs: vec![] };
+        assert!(validate_blobs_bundle(&block, &bundle).is_ok());
+
+        bundle.commitments.push(Default::default());
+        assert!(matches!(
+            validate_blobs_bundle(&block, &bundle),
+            Err(ValidationApiError::BlobsBundleLength { commitments: 1, proofs: 0, blobs: 0 })
+        ));
+
+        bundle.proofs.push(Default::default());
+        bundle.blobs.push(Default::default());
+        assert!(matches!(
+            validate_blobs_bundle(&block, &bundle),
+            Err(ValidationApiError::BlobVersionedHashesMismatch)
+        ));
+    }
+}
@@ -431,8 +431,17 @@ where
             });
 
             let provider_rw = self.provider_factory.provider_rw()?;
-            match stage.execute(&provider_rw, exec_input) {
+            let span = debug_span!(
+                target: "sync::pipeline",
+                "stage_batch",
+                stage = %stage_id,
+                from_block = prev_checkpoint.unwrap_or_default().block_number,
+                target_block = target,
+                to_block = field::Empty,
+            );
+            match span.in_scope(|| stage.execute(&provider_rw, exec_input)) {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
+                    span.record("to_block", checkpoint.block_number);
                     made_progress |=
                         checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
 
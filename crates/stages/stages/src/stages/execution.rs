@@ -16,9 +16,9 @@ use reth_provider::{
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
-    BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
-    ExecutionCheckpoint, ExecutionStageThresholds, MetricEvent, MetricEventsSender, Stage,
-    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    AdaptiveBatchSize, BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput,
+    ExecOutput, ExecutionCheckpoint, ExecutionStageThresholds, MetricEvent, MetricEventsSender,
+    Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
 use std::{
     cmp::Ordering,
@@ -65,6 +65,9 @@ pub struct ExecutionStage<E> {
     executor_provider: E,
     /// The commit thresholds of the execution stage.
     thresholds: ExecutionStageThresholds,
+    /// The dynamic sizing of the batches based on the observed write throughput, applied on top
+    /// of the thresholds.
+    adaptive_batch_size: Option<AdaptiveBatchSize>,
     /// The highest threshold (in number of blocks) for switching between incremental
     /// and full calculations across [`super::MerkleStage`], [`super::AccountHashingStage`] and
     /// [`super::StorageHashingStage`]. This is required to figure out if can prune or not
@@ -98,6 +101,7 @@ impl<E> ExecutionStage<E> {
             external_clean_threshold,
             executor_provider,
             thresholds,
+            adaptive_batch_size: None,
             prune_modes,
             post_execute_commit_input: None,
             post_unwind_commit_input: None,
@@ -132,6 +136,17 @@ impl<E> ExecutionStage<E> {
             prune_modes,
             ExExManagerHandle::empty(),
         )
+        .with_adaptive_batch_size(config.adaptive.batch_size())
+    }
+
+    /// Sets the dynamic sizing of the batches, which limits the cumulative gas of a batch based on
+    /// the observed write throughput in addition to the thresholds.
+    pub fn with_adaptive_batch_size(
+        mut self,
+        adaptive_batch_size: Option<AdaptiveBatchSize>,
+    ) -> Self {
+        self.adaptive_batch_size = adaptive_batch_size;
+        self
     }
 
     /// Set the metric events sender.
@@ -237,6 +252,7 @@ where
 
         // Execute block range
         let mut cumulative_gas = 0;
+        let mut hit_changes_limit = false;
         let batch_start = Instant::now();
 
         let mut blocks = Vec::new();
@@ -287,12 +303,17 @@ where
 
             // Check if we should commit now
             let bundle_size_hint = executor.size_hint().unwrap_or_default() as u64;
+            hit_changes_limit = bundle_size_hint >= self.thresholds.max_changes.unwrap_or(u64::MAX);
             if self.thresholds.is_end_of_batch(
                 block_number - start_block,
                 bundle_size_hint,
                 cumulative_gas,
                 batch_start.elapsed(),
-            ) {
+            ) || self
+                .adaptive_batch_size
+                .as_ref()
+                .is_some_and(|adaptive| adaptive.is_end_of_batch(cumulative_gas))
+            {
                 break
             }
         }
@@ -350,6 +371,20 @@ where
             "Execution time"
         );
 
+        if let Some(adaptive) = &mut self.adaptive_batch_size {
+            let gas_limit = adaptive.on_batch_written(
+                cumulative_gas,
+                write_preparation_duration + db_write_duration,
+                hit_changes_limit,
+            );
+            debug!(
+                target: "sync::stages::execution",
+                write_throughput = format_gas_throughput(cumulative_gas, db_write_duration),
+                next_batch_gas_limit = gas_limit,
+                "Adapted batch size"
+            );
+        }
+
         let done = stage_progress == max_block;
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(stage_progress)
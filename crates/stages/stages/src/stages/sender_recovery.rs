@@ -138,6 +138,15 @@ fn recover_range<DB: Database>(
         tables::TransactionSenders,
     >,
 ) -> Result<(), StageError> {
+    // Senders that were recovered before are stored with the transactions static files
+    if let Some(senders) = provider.static_file_provider().persisted_senders(tx_range.clone())? {
+        debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending persisted senders to the database");
+        for (tx_id, sender) in tx_range.zip(senders) {
+            senders_cursor.append(tx_id, sender)?;
+        }
+        return Ok(())
+    }
+
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders batch");
 
     // Preallocate channels
@@ -185,6 +194,7 @@ fn recover_range<DB: Database>(
     });
 
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders to the database");
+    let mut recovered_senders = Vec::with_capacity((tx_range.end - tx_range.start) as usize);
     for channel in receivers {
         while let Ok(recovered) = channel.recv() {
             let (tx_id, sender) = match recovered {
@@ -214,10 +224,20 @@ fn recover_range<DB: Database>(
                 }
             };
             senders_cursor.append(tx_id, sender)?;
+            recovered_senders.push(sender);
         }
     }
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Finished recovering senders batch");
 
+    // Keep the senders with the static files, so they don't have to be recovered again once they
+    // are pruned from the database. The senders in the database are enough, so failures are only
+    // logged.
+    if let Err(error) =
+        provider.static_file_provider().persist_senders(tx_range.start, &recovered_senders)
+    {
+        warn!(target: "sync::stages::sender_recovery", ?tx_range, %error, "Failed to persist recovered senders");
+    }
+
     Ok(())
 }
 
@@ -427,6 +447,58 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    /// Execute the stage again after an unwind, reusing the senders stored with the static files
+    #[tokio::test]
+    async fn execute_reuses_persisted_senders() {
+        let (stage_progress, previous_stage) = (100, 120);
+        let mut rng = generators::rng();
+
+        let runner = SenderRecoveryTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        // Senders are only stored from the first transaction of a static file
+        let blocks = (stage_progress..=previous_stage)
+            .map(|number| {
+                random_block(
+                    &mut rng,
+                    number,
+                    None,
+                    Some((number != stage_progress) as u8 * 2),
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        runner
+            .db
+            .insert_blocks(blocks.iter(), StorageKind::Static)
+            .expect("failed to insert blocks");
+
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        let senders = runner.db.table::<tables::TransactionSenders>().unwrap();
+        let total_transactions = senders.len() as u64;
+        assert_eq!(
+            runner.db.factory.static_file_provider().persisted_senders(0..total_transactions),
+            Ok(Some(senders.iter().map(|(_, sender)| *sender).collect()))
+        );
+
+        let unwind_input = UnwindInput {
+            checkpoint: result.unwrap().checkpoint,
+            unwind_to: stage_progress,
+            bad_block: None,
+        };
+        runner.unwind(unwind_input).await.expect("failed to unwind");
+        assert!(runner.validate_unwind(unwind_input).is_ok(), "unwind validation");
+
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        assert_eq!(runner.db.table::<tables::TransactionSenders>().unwrap(), senders);
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     #[test]
     fn stage_checkpoint_pruned() {
         let db = TestStageDB::default();
@@ -48,3 +48,100 @@ impl ExecutionStageThresholds {
             elapsed >= self.max_duration.unwrap_or(Duration::MAX)
     }
 }
+
+/// Adapts the cumulative gas of the batches of the execution stage to the observed throughput of
+/// the database writes.
+///
+/// After every batch, the gas that can be written within the targeted write duration is derived
+/// from the observed gas per second of the write, and the gas limit of the next batch is moved
+/// towards it. This keeps the commit pauses bounded on slow disks, while fast disks get larger
+/// batches. If a batch was cut short because it hit the in-memory changes limit, the gas limit
+/// doesn't grow beyond the gas of that batch.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    /// The targeted duration of writing a batch to the database.
+    target_write_duration: Duration,
+    /// The minimum cumulative gas of a batch.
+    min_gas: u64,
+    /// The maximum cumulative gas of a batch.
+    max_gas: u64,
+    /// The cumulative gas limit of the next batch.
+    gas_limit: u64,
+}
+
+impl AdaptiveBatchSize {
+    /// Creates a new controller, the first batch is limited to `min_gas`.
+    pub fn new(target_write_duration: Duration, min_gas: u64, max_gas: u64) -> Self {
+        let max_gas = max_gas.max(min_gas);
+        Self { target_write_duration, min_gas, max_gas, gas_limit: min_gas }
+    }
+
+    /// Returns the cumulative gas limit of the next batch.
+    pub const fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    /// Returns true if the batch reached the gas limit.
+    pub const fn is_end_of_batch(&self, cumulative_gas_used: u64) -> bool {
+        cumulative_gas_used >= self.gas_limit
+    }
+
+    /// Updates the gas limit after a batch with the given cumulative gas was written to the
+    /// database, and returns the new gas limit.
+    pub fn on_batch_written(
+        &mut self,
+        cumulative_gas_used: u64,
+        write_duration: Duration,
+        hit_changes_limit: bool,
+    ) -> u64 {
+        if cumulative_gas_used == 0 {
+            return self.gas_limit
+        }
+
+        // the gas that can be written within the targeted duration at the observed gas per second
+        let write_gas_per_second =
+            cumulative_gas_used as f64 / write_duration.as_secs_f64().max(f64::EPSILON);
+        let mut target = (write_gas_per_second * self.target_write_duration.as_secs_f64())
+            .min(u64::MAX as f64) as u64;
+        if hit_changes_limit {
+            target = target.min(cumulative_gas_used);
+        }
+
+        // move halfway towards the target to smooth out noisy measurements, and at most double
+        // the limit at once
+        let gas_limit = self.gas_limit / 2 + target / 2;
+        self.gas_limit =
+            gas_limit.min(self.gas_limit.saturating_mul(2)).clamp(self.min_gas, self.max_gas);
+        self.gas_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapts_gas_limit_to_write_throughput() {
+        let mut batch_size = AdaptiveBatchSize::new(Duration::from_secs(10), 100, 10_000);
+        assert_eq!(batch_size.gas_limit(), 100);
+        assert!(batch_size.is_end_of_batch(100));
+
+        // fast writes grow the limit, at most doubling it
+        assert_eq!(batch_size.on_batch_written(100, Duration::from_millis(100), false), 200);
+        assert_eq!(batch_size.on_batch_written(200, Duration::from_millis(100), false), 400);
+
+        // slow writes shrink the limit towards the gas that's written in 10s
+        assert_eq!(batch_size.on_batch_written(400, Duration::from_secs(20), false), 300);
+
+        // the limit doesn't grow beyond a batch that hit the changes limit
+        assert_eq!(batch_size.on_batch_written(250, Duration::from_millis(1), true), 275);
+
+        // the limit shrinks by at most half at once and stays within the bounds
+        assert_eq!(batch_size.on_batch_written(100, Duration::from_secs(1_000), false), 137);
+        assert_eq!(batch_size.on_batch_written(100, Duration::from_secs(1_000), false), 100);
+        for _ in 0..20 {
+            batch_size.on_batch_written(10_000, Duration::from_millis(1), false);
+        }
+        assert_eq!(batch_size.gas_limit(), 10_000);
+    }
+}
@@ -0,0 +1,127 @@
+//! A [`Database`] that serves the state of a witness.
+
+use crate::{trie::SparseTrie, witness::DecodedWitness, StatelessError};
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, keccak256, revm_primitives::AccountInfo, Address, BlockNumber,
+    Bytes, B256, KECCAK_EMPTY, U256,
+};
+use reth_revm::{db::BundleState, primitives::Bytecode, Database};
+use reth_trie_common::TrieAccount;
+use std::collections::HashMap;
+
+/// A [`Database`] that serves the accounts, storage, contract code and block hashes of a
+/// witness, and fails with [`StatelessError`] if the block accesses anything that is not part of
+/// the witness.
+#[derive(Debug)]
+pub struct WitnessDatabase {
+    accounts: SparseTrie,
+    storages: HashMap<Address, SparseTrie>,
+    nodes: HashMap<B256, Bytes>,
+    codes: HashMap<B256, Bytes>,
+    block_hashes: HashMap<BlockNumber, B256>,
+}
+
+impl WitnessDatabase {
+    /// Creates the database for the witness, whose state has the given state root.
+    pub(crate) fn new(state_root: B256, witness: DecodedWitness) -> Result<Self, StatelessError> {
+        Ok(Self {
+            accounts: SparseTrie::from_nodes(state_root, &witness.nodes)?,
+            storages: HashMap::new(),
+            nodes: witness.nodes,
+            codes: witness.codes,
+            block_hashes: witness.block_hashes,
+        })
+    }
+
+    fn account(&self, address: Address) -> Result<Option<TrieAccount>, StatelessError> {
+        self.accounts
+            .get(keccak256(address))?
+            .map(|mut encoded| TrieAccount::decode(&mut encoded))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Returns the storage trie of the account, building it from the witness on first access.
+    fn storage_trie(&mut self, address: Address) -> Result<&mut SparseTrie, StatelessError> {
+        if !self.storages.contains_key(&address) {
+            let storage_root =
+                self.account(address)?.map_or(EMPTY_ROOT_HASH, |account| account.storage_root);
+            self.storages.insert(address, SparseTrie::from_nodes(storage_root, &self.nodes)?);
+        }
+        Ok(self.storages.get_mut(&address).expect("inserted"))
+    }
+
+    /// Applies the changes of the bundle to the tries, and returns the new state root.
+    pub(crate) fn state_root(&mut self, bundle: &BundleState) -> Result<B256, StatelessError> {
+        for (address, account) in &bundle.state {
+            let hashed_address = keccak256(address);
+            let Some(info) = &account.info else {
+                self.accounts.remove(hashed_address)?;
+                self.storages.remove(address);
+                continue
+            };
+
+            if account.was_destroyed() {
+                self.storages
+                    .insert(*address, SparseTrie::from_nodes(EMPTY_ROOT_HASH, &self.nodes)?);
+            }
+            let storage = self.storage_trie(*address)?;
+            for (slot, value) in &account.storage {
+                let hashed_slot = keccak256(B256::from(*slot));
+                if value.present_value.is_zero() {
+                    storage.remove(hashed_slot)?;
+                } else {
+                    let mut encoded = Vec::new();
+                    value.present_value.encode(&mut encoded);
+                    storage.insert(hashed_slot, encoded)?;
+                }
+            }
+
+            let account = TrieAccount {
+                nonce: info.nonce,
+                balance: info.balance,
+                storage_root: storage.root()?,
+                code_hash: info.code_hash,
+            };
+            let mut encoded = Vec::new();
+            account.encode(&mut encoded);
+            self.accounts.insert(hashed_address, encoded)?;
+        }
+        self.accounts.root()
+    }
+}
+
+impl Database for WitnessDatabase {
+    type Error = StatelessError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Ok(self.account(address)?.map(|account| AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.code_hash,
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default())
+        }
+        let code = self.codes.get(&code_hash).ok_or(StatelessError::MissingCode(code_hash))?;
+        Ok(Bytecode::new_raw(code.clone()))
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let hashed_slot = keccak256(B256::from(index));
+        match self.storage_trie(address)?.get(hashed_slot)? {
+            Some(mut encoded) => Ok(U256::decode(&mut encoded)?),
+            None => Ok(U256::ZERO),
+        }
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let number = number.saturating_to();
+        self.block_hashes.get(&number).copied().ok_or(StatelessError::MissingBlockHash(number))
+    }
+}
@@ -0,0 +1,240 @@
+//! Stateless execution of blocks.
+//!
+//! A [`BlockWitness`] contains everything that is needed to execute a block without a database:
+//! the trie nodes of the accessed state, the executed contract code and the headers of the
+//! accessed ancestors. [`execute_witness`] re-executes the block from the witness and validates
+//! the execution outcome and the resulting state root.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_evm::execute::{
+    BlockExecutionError, BlockExecutionOutput, BlockExecutorProvider, Executor, ProviderError,
+};
+use reth_primitives::{BlockNumber, GotExpected, Receipt, B256};
+use reth_storage_errors::db::DatabaseError;
+
+mod database;
+pub use database::WitnessDatabase;
+
+mod trie;
+pub use trie::SparseTrie;
+
+mod witness;
+pub use witness::BlockWitness;
+
+/// Errors of the stateless execution of a block.
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessError {
+    /// The witness is not correctly encoded.
+    #[error("invalid witness encoding: {0}")]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The witness doesn't contain the header of the parent block.
+    #[error("the witness doesn't contain the parent header")]
+    MissingParentHeader,
+    /// A header of the witness is not the child of the previous header.
+    #[error("header {0} of the witness doesn't link to the previous header")]
+    UnlinkedHeader(BlockNumber),
+    /// The block accessed a trie node that is not part of the witness.
+    #[error("trie node {0} is not part of the witness")]
+    MissingTrieNode(B256),
+    /// The block executed contract code that is not part of the witness.
+    #[error("code {0} is not part of the witness")]
+    MissingCode(B256),
+    /// The block accessed the hash of a block whose header is not part of the witness.
+    #[error("the hash of block {0} is not part of the witness")]
+    MissingBlockHash(BlockNumber),
+    /// The senders of the transactions of the block could not be recovered.
+    #[error("failed to recover the senders of the block")]
+    SenderRecovery,
+    /// The execution of the block failed.
+    #[error("failed to execute the block: {0}")]
+    Execution(#[from] BlockExecutionError),
+    /// The execution outcome doesn't match the block, e.g. its gas used or receipts root.
+    #[error("post-execution validation failed: {0}")]
+    PostExecution(#[from] ConsensusError),
+    /// The state root after the execution doesn't match the state root of the block.
+    #[error("state root mismatch: {0}")]
+    StateRootMismatch(GotExpected<B256>),
+}
+
+impl From<StatelessError> for ProviderError {
+    fn from(err: StatelessError) -> Self {
+        Self::Database(DatabaseError::Other(err.to_string()))
+    }
+}
+
+/// Re-executes the block of the witness on top of the state of the witness, validates the
+/// execution outcome with the consensus, e.g. the gas used, receipts and requests of the block, and
+/// checks that the resulting state root matches the state root of the block.
+pub fn execute_witness<E: BlockExecutorProvider>(
+    executor_provider: &E,
+    consensus: &dyn Consensus,
+    witness: &BlockWitness,
+) -> Result<BlockExecutionOutput<Receipt>, StatelessError> {
+    let decoded = witness.decode()?;
+    let block =
+        decoded.block.clone().with_recovered_senders().ok_or(StatelessError::SenderRecovery)?;
+
+    let mut db = WitnessDatabase::new(decoded.parent.state_root, decoded)?;
+    let output =
+        executor_provider.executor(&mut db).execute((&block, witness.total_difficulty).into())?;
+    consensus.validate_block_post_execution(
+        &block,
+        PostExecutionInput::new(&output.receipts, &output.requests),
+    )?;
+
+    let state_root = db.state_root(&output.state)?;
+    if state_root != block.state_root {
+        return Err(StatelessError::StateRootMismatch(GotExpected {
+            got: state_root,
+            expected: block.state_root,
+        }))
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::{
+        constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
+        keccak256, proofs, public_key_to_address, Address, Block, Bytes, Header, Transaction,
+        TxKind, TxLegacy, TxType, KECCAK_EMPTY, U256,
+    };
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder, Nibbles, TrieAccount};
+    use std::{collections::BTreeMap, sync::Arc};
+
+    /// Returns the root and all nodes of the account trie with the nonces and balances.
+    fn account_trie(accounts: &[(Address, u64, U256)]) -> (B256, Vec<Bytes>) {
+        let accounts = accounts
+            .iter()
+            .map(|(address, nonce, balance)| {
+                let account = TrieAccount {
+                    nonce: *nonce,
+                    balance: *balance,
+                    storage_root: EMPTY_ROOT_HASH,
+                    code_hash: KECCAK_EMPTY,
+                };
+                let mut encoded = Vec::new();
+                account.encode(&mut encoded);
+                (keccak256(address), encoded)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let retainer = ProofRetainer::from_iter(accounts.keys().map(|key| Nibbles::unpack(key)));
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in &accounts {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        (root, hash_builder.take_proofs().into_values().collect())
+    }
+
+    fn encode(value: &impl Encodable) -> Bytes {
+        let mut encoded = Vec::new();
+        value.encode(&mut encoded);
+        encoded.into()
+    }
+
+    #[test]
+    fn executes_block_from_witness() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).paris_activated().build());
+        let executor_provider = EthExecutorProvider::ethereum(chain_spec.clone());
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        let (recipient, beneficiary) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (value, gas_price, base_fee, gas_used) = (U256::from(1_000), 10, 7, 21_000);
+
+        let (parent_state_root, state) =
+            account_trie(&[(sender, 0, U256::from(ETH_TO_WEI)), (recipient, 0, U256::from(1))]);
+        let parent = Header {
+            state_root: parent_state_root,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(base_fee),
+            ..Default::default()
+        };
+
+        let transaction = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price,
+                gas_limit: gas_used,
+                to: TxKind::Call(recipient),
+                value,
+                input: Default::default(),
+            }),
+        );
+        #[allow(clippy::needless_update)]
+        let receipt = Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: gas_used,
+            logs: Vec::new(),
+            ..Default::default()
+        };
+        let (state_root, _) = account_trie(&[
+            (sender, 1, U256::from(ETH_TO_WEI - gas_used as u128 * gas_price) - value),
+            (recipient, 0, U256::from(1) + value),
+            (beneficiary, 0, U256::from(gas_used as u128 * (gas_price - base_fee as u128))),
+        ]);
+        let mut block = Block {
+            header: Header {
+                parent_hash: parent.hash_slow(),
+                number: 1,
+                beneficiary,
+                state_root,
+                receipts_root: proofs::calculate_receipt_root(&[receipt.clone().with_bloom()]),
+                gas_limit: parent.gas_limit,
+                gas_used,
+                base_fee_per_gas: Some(base_fee),
+                ..Default::default()
+            },
+            body: vec![transaction],
+            ..Default::default()
+        };
+
+        let witness = |block: &Block| BlockWitness {
+            chain_id: chain_spec.chain.id(),
+            total_difficulty: U256::ZERO,
+            block: encode(block),
+            headers: vec![encode(&parent)],
+            state: state.clone(),
+            codes: Vec::new(),
+            keys: BTreeMap::new(),
+        };
+
+        let output = execute_witness(&executor_provider, &consensus, &witness(&block)).unwrap();
+        assert_eq!(output.gas_used, gas_used);
+
+        // the receipts are validated after the execution
+        block.header.receipts_root = B256::ZERO;
+        assert!(matches!(
+            execute_witness(&executor_provider, &consensus, &witness(&block)),
+            Err(StatelessError::PostExecution(_))
+        ));
+
+        // the resulting state root is validated
+        block.header.receipts_root = proofs::calculate_receipt_root(&[receipt.with_bloom()]);
+        block.header.state_root = parent_state_root;
+        assert!(matches!(
+            execute_witness(&executor_provider, &consensus, &witness(&block)),
+            Err(StatelessError::StateRootMismatch(_))
+        ));
+    }
+}
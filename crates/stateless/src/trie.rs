@@ -0,0 +1,465 @@
+//! A Merkle Patricia Trie of which only the nodes of a witness are known.
+
+use crate::StatelessError;
+use alloy_rlp::{Decodable, Encodable, Header, EMPTY_STRING_CODE};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Bytes, B256};
+use std::collections::HashMap;
+
+/// A node of a [`SparseTrie`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Empty,
+    Leaf {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Extension {
+        key: Vec<u8>,
+        child: Box<Node>,
+    },
+    Branch {
+        children: Box<[Node; 16]>,
+    },
+    /// A node that is not part of the witness, only its hash is known.
+    Hash(B256),
+}
+
+/// A Merkle Patricia Trie that is built from the RLP encoded nodes of a witness.
+///
+/// Nodes that are referenced but not part of the witness are kept as hashes, so the root can be
+/// calculated after updates as long as the updates only touch the known paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseTrie {
+    root: Node,
+}
+
+impl SparseTrie {
+    /// Builds the trie with the given root from the nodes, which are looked up by their hash.
+    pub fn from_nodes(root: B256, nodes: &HashMap<B256, Bytes>) -> Result<Self, StatelessError> {
+        let root = if root == EMPTY_ROOT_HASH {
+            Node::Empty
+        } else {
+            match nodes.get(&root) {
+                Some(node) => decode_node(node, nodes)?,
+                None => Node::Hash(root),
+            }
+        };
+        Ok(Self { root })
+    }
+
+    /// Returns the value of the key, or `None` if the key is not in the trie.
+    pub fn get(&self, key: B256) -> Result<Option<&[u8]>, StatelessError> {
+        let path = unpack(key.as_slice());
+        let mut node = &self.root;
+        let mut path = path.as_slice();
+        loop {
+            match node {
+                Node::Empty => return Ok(None),
+                Node::Leaf { key, value } => {
+                    return Ok((key.as_slice() == path).then_some(value.as_slice()))
+                }
+                Node::Extension { key, child } => {
+                    let Some(rest) = path.strip_prefix(key.as_slice()) else { return Ok(None) };
+                    path = rest;
+                    node = child;
+                }
+                Node::Branch { children } => {
+                    let Some((nibble, rest)) = path.split_first() else { return Ok(None) };
+                    path = rest;
+                    node = &children[*nibble as usize];
+                }
+                Node::Hash(hash) => return Err(StatelessError::MissingTrieNode(*hash)),
+            }
+        }
+    }
+
+    /// Inserts the value of the key.
+    pub fn insert(&mut self, key: B256, value: Vec<u8>) -> Result<(), StatelessError> {
+        let root = std::mem::replace(&mut self.root, Node::Empty);
+        self.root = insert(root, &unpack(key.as_slice()), value)?;
+        Ok(())
+    }
+
+    /// Removes the key, if it is in the trie.
+    pub fn remove(&mut self, key: B256) -> Result<(), StatelessError> {
+        let root = std::mem::replace(&mut self.root, Node::Empty);
+        self.root = remove(root, &unpack(key.as_slice()))?;
+        Ok(())
+    }
+
+    /// Returns the root hash of the trie.
+    ///
+    /// Fails if a branch that was left with a single child by removals collapses onto a node that
+    /// is not part of the witness, since the collapsed node depends on the kind of that node.
+    pub fn root(&mut self) -> Result<B256, StatelessError> {
+        let root = std::mem::replace(&mut self.root, Node::Empty);
+        self.root = collapse(root)?;
+        Ok(match &self.root {
+            Node::Empty => EMPTY_ROOT_HASH,
+            Node::Hash(hash) => *hash,
+            node => keccak256(encode_node(node)),
+        })
+    }
+}
+
+/// Splits the bytes into nibbles.
+fn unpack(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn empty_children() -> Box<[Node; 16]> {
+    Box::new(std::array::from_fn(|_| Node::Empty))
+}
+
+fn insert(node: Node, path: &[u8], value: Vec<u8>) -> Result<Node, StatelessError> {
+    Ok(match node {
+        Node::Empty => Node::Leaf { key: path.to_vec(), value },
+        Node::Leaf { key, value: existing } => {
+            if key == path {
+                return Ok(Node::Leaf { key, value })
+            }
+            let common = common_prefix_len(&key, path);
+            let mut children = empty_children();
+            children[key[common] as usize] =
+                Node::Leaf { key: key[common + 1..].to_vec(), value: existing };
+            children[path[common] as usize] =
+                Node::Leaf { key: path[common + 1..].to_vec(), value };
+            with_prefix(&path[..common], Node::Branch { children })
+        }
+        Node::Extension { key, child } => {
+            let common = common_prefix_len(&key, path);
+            if common == key.len() {
+                let child = insert(*child, &path[common..], value)?;
+                return Ok(Node::Extension { key, child: Box::new(child) })
+            }
+            let mut children = empty_children();
+            children[key[common] as usize] = with_prefix(&key[common + 1..], *child);
+            children[path[common] as usize] =
+                Node::Leaf { key: path[common + 1..].to_vec(), value };
+            with_prefix(&path[..common], Node::Branch { children })
+        }
+        Node::Branch { mut children } => {
+            let index = path[0] as usize;
+            let child = std::mem::replace(&mut children[index], Node::Empty);
+            children[index] = insert(child, &path[1..], value)?;
+            Node::Branch { children }
+        }
+        Node::Hash(hash) => return Err(StatelessError::MissingTrieNode(hash)),
+    })
+}
+
+fn remove(node: Node, path: &[u8]) -> Result<Node, StatelessError> {
+    Ok(match node {
+        Node::Empty => Node::Empty,
+        Node::Leaf { key, value } => {
+            if key == path {
+                Node::Empty
+            } else {
+                Node::Leaf { key, value }
+            }
+        }
+        Node::Extension { key, child } => {
+            let Some(rest) = path.strip_prefix(key.as_slice()) else {
+                return Ok(Node::Extension { key, child })
+            };
+            with_prefix(&key, remove(*child, rest)?)
+        }
+        Node::Branch { mut children } => {
+            let index = path[0] as usize;
+            let child = std::mem::replace(&mut children[index], Node::Empty);
+            children[index] = remove(child, &path[1..])?;
+
+            let mut remaining = children
+                .iter()
+                .enumerate()
+                .filter(|(_, child)| !matches!(child, Node::Empty))
+                .map(|(index, _)| index);
+            match (remaining.next(), remaining.next()) {
+                (None, _) => Node::Empty,
+                // a branch with a single child collapses into the child, unless only the hash of
+                // the child is known, then the branch is collapsed when the root is calculated, in
+                // case a later insert adds a sibling again
+                (Some(index), None) if !matches!(children[index], Node::Hash(_)) => {
+                    let child = std::mem::replace(&mut children[index], Node::Empty);
+                    with_prefix(&[index as u8], child)
+                }
+                _ => Node::Branch { children },
+            }
+        }
+        Node::Hash(hash) => return Err(StatelessError::MissingTrieNode(hash)),
+    })
+}
+
+/// Collapses the branches that were left with a single child whose hash is the only thing known
+/// about it.
+fn collapse(node: Node) -> Result<Node, StatelessError> {
+    Ok(match node {
+        Node::Extension { key, child } => with_prefix(&key, collapse(*child)?),
+        Node::Branch { mut children } => {
+            for child in children.iter_mut() {
+                *child = collapse(std::mem::replace(child, Node::Empty))?;
+            }
+
+            let mut remaining = children
+                .iter()
+                .enumerate()
+                .filter(|(_, child)| !matches!(child, Node::Empty))
+                .map(|(index, _)| index);
+            match (remaining.next(), remaining.next()) {
+                (None, _) => Node::Empty,
+                (Some(index), None) => match std::mem::replace(&mut children[index], Node::Empty) {
+                    // the collapsed node depends on whether the child is a branch
+                    Node::Hash(hash) => return Err(StatelessError::MissingTrieNode(hash)),
+                    child => with_prefix(&[index as u8], child),
+                },
+                _ => Node::Branch { children },
+            }
+        }
+        node => node,
+    })
+}
+
+/// Prepends the nibbles to the path of the node.
+fn with_prefix(prefix: &[u8], node: Node) -> Node {
+    if prefix.is_empty() {
+        return node
+    }
+    match node {
+        Node::Empty => Node::Empty,
+        Node::Leaf { key, value } => Node::Leaf { key: [prefix, &key].concat(), value },
+        Node::Extension { key, child } => Node::Extension { key: [prefix, &key].concat(), child },
+        node => Node::Extension { key: prefix.to_vec(), child: Box::new(node) },
+    }
+}
+
+/// Decodes an RLP encoded node, resolving the referenced nodes from the witness.
+fn decode_node(mut buf: &[u8], nodes: &HashMap<B256, Bytes>) -> Result<Node, StatelessError> {
+    let header = Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString.into())
+    }
+    let mut payload = &buf[..header.payload_length];
+    let mut items = Vec::with_capacity(17);
+    while !payload.is_empty() {
+        items.push(next_item(&mut payload)?);
+    }
+
+    match items.len() {
+        2 => {
+            let path = Bytes::decode(&mut &items[0][..])?;
+            let (key, is_leaf) = decode_path(&path)?;
+            if is_leaf {
+                let value = Bytes::decode(&mut &items[1][..])?;
+                Ok(Node::Leaf { key, value: value.to_vec() })
+            } else {
+                Ok(Node::Extension { key, child: Box::new(decode_child(items[1], nodes)?) })
+            }
+        }
+        17 => {
+            let mut children = empty_children();
+            for (child, item) in children.iter_mut().zip(&items) {
+                *child = decode_child(item, nodes)?;
+            }
+            Ok(Node::Branch { children })
+        }
+        _ => Err(alloy_rlp::Error::Custom("invalid trie node").into()),
+    }
+}
+
+/// Returns the next RLP item of the list payload, including its header.
+fn next_item<'a>(payload: &mut &'a [u8]) -> Result<&'a [u8], StatelessError> {
+    let start = *payload;
+    let header = Header::decode(payload)?;
+    let header_len = start.len() - payload.len();
+    let item =
+        start.get(..header_len + header.payload_length).ok_or(alloy_rlp::Error::InputTooShort)?;
+    *payload = &start[item.len()..];
+    Ok(item)
+}
+
+/// Decodes a reference to a child node, which is either its hash or the node itself if its
+/// encoding is shorter than 32 bytes.
+fn decode_child(item: &[u8], nodes: &HashMap<B256, Bytes>) -> Result<Node, StatelessError> {
+    if item.first().is_some_and(|byte| *byte >= alloy_rlp::EMPTY_LIST_CODE) {
+        return decode_node(item, nodes)
+    }
+    let reference = Bytes::decode(&mut &item[..])?;
+    match reference.len() {
+        0 => Ok(Node::Empty),
+        32 => {
+            let hash = B256::from_slice(&reference);
+            match nodes.get(&hash) {
+                Some(node) => decode_node(node, nodes),
+                None => Ok(Node::Hash(hash)),
+            }
+        }
+        _ => Err(alloy_rlp::Error::UnexpectedLength.into()),
+    }
+}
+
+/// Decodes a hex-prefix encoded path, and returns the nibbles and whether it is the path of a
+/// leaf.
+fn decode_path(path: &[u8]) -> Result<(Vec<u8>, bool), StatelessError> {
+    let first = *path.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+    let flag = first >> 4;
+    let mut nibbles = if flag & 1 == 1 { vec![first & 0x0f] } else { Vec::new() };
+    nibbles.extend(unpack(&path[1..]));
+    Ok((nibbles, flag & 2 == 2))
+}
+
+/// Hex-prefix encodes the nibbles of a path.
+fn encode_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut path = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        path.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        path.push(flag << 4);
+        nibbles
+    };
+    path.extend(rest.chunks_exact(2).map(|pair| (pair[0] << 4) | pair[1]));
+    path
+}
+
+fn encode_node(node: &Node) -> Vec<u8> {
+    let mut payload = Vec::new();
+    match node {
+        Node::Empty => return vec![EMPTY_STRING_CODE],
+        Node::Hash(hash) => {
+            hash.encode(&mut payload);
+            return payload
+        }
+        Node::Leaf { key, value } => {
+            encode_path(key, true).as_slice().encode(&mut payload);
+            value.as_slice().encode(&mut payload);
+        }
+        Node::Extension { key, child } => {
+            encode_path(key, false).as_slice().encode(&mut payload);
+            encode_child(child, &mut payload);
+        }
+        Node::Branch { children } => {
+            for child in children.iter() {
+                encode_child(child, &mut payload);
+            }
+            payload.push(EMPTY_STRING_CODE);
+        }
+    }
+    let mut out = Vec::with_capacity(payload.len() + 3);
+    Header { list: true, payload_length: payload.len() }.encode(&mut out);
+    out.extend(payload);
+    out
+}
+
+/// Encodes the reference to a child node into its parent.
+fn encode_child(child: &Node, out: &mut Vec<u8>) {
+    match child {
+        Node::Empty => out.push(EMPTY_STRING_CODE),
+        Node::Hash(hash) => hash.encode(out),
+        node => {
+            let encoded = encode_node(node);
+            if encoded.len() < 32 {
+                out.extend(encoded);
+            } else {
+                keccak256(encoded).encode(out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder, Nibbles};
+    use std::collections::BTreeMap;
+
+    /// Returns the root and all nodes of the trie with the entries.
+    fn build(entries: &BTreeMap<B256, Vec<u8>>) -> (B256, HashMap<B256, Bytes>) {
+        build_with_proofs(entries, entries.keys())
+    }
+
+    /// Returns the root and the nodes on the paths to the targets of the trie with the entries.
+    fn build_with_proofs<'a>(
+        entries: &BTreeMap<B256, Vec<u8>>,
+        targets: impl IntoIterator<Item = &'a B256>,
+    ) -> (B256, HashMap<B256, Bytes>) {
+        let retainer =
+            ProofRetainer::from_iter(targets.into_iter().map(|key| Nibbles::unpack(key)));
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in entries {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let nodes =
+            hash_builder.take_proofs().into_values().map(|node| (keccak256(&node), node)).collect();
+        (root, nodes)
+    }
+
+    fn entries(count: u8) -> BTreeMap<B256, Vec<u8>> {
+        (0..count).map(|i| (keccak256([i]), vec![i; 1 + i as usize % 40])).collect()
+    }
+
+    #[test]
+    fn updates_match_full_trie() {
+        let mut entries = entries(64);
+        let (root, nodes) = build(&entries);
+        let mut trie = SparseTrie::from_nodes(root, &nodes).unwrap();
+        assert_eq!(trie.root().unwrap(), root);
+        for (key, value) in &entries {
+            assert_eq!(trie.get(*key).unwrap(), Some(value.as_slice()));
+        }
+        assert_eq!(trie.get(keccak256([200])).unwrap(), None);
+
+        let removed = entries.keys().step_by(3).copied().collect::<Vec<_>>();
+        for key in removed {
+            trie.remove(key).unwrap();
+            entries.remove(&key);
+        }
+        for i in 100..110 {
+            trie.insert(keccak256([i]), vec![i; 33]).unwrap();
+            entries.insert(keccak256([i]), vec![i; 33]);
+        }
+        assert_eq!(trie.root().unwrap(), build(&entries).0);
+
+        for key in entries.keys().copied().collect::<Vec<_>>() {
+            trie.remove(key).unwrap();
+        }
+        assert_eq!(trie.root().unwrap(), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let entries = entries(16);
+        let (root, mut nodes) = build(&entries);
+        // keep only the root node
+        nodes.retain(|hash, _| *hash == root);
+        let mut trie = SparseTrie::from_nodes(root, &nodes).unwrap();
+        assert_eq!(trie.root().unwrap(), root);
+
+        let key = *entries.keys().next().unwrap();
+        assert!(matches!(trie.get(key), Err(StatelessError::MissingTrieNode(_))));
+        assert!(matches!(trie.insert(key, vec![1]), Err(StatelessError::MissingTrieNode(_))));
+    }
+
+    #[test]
+    fn removal_next_to_unknown_sibling() {
+        // the keys differ in the first nibble, so the root is a branch with a hashed leaf per key
+        let (removed, sibling, inserted) = (keccak256([0]), keccak256([1]), keccak256([2]));
+        let entries = BTreeMap::from([(removed, vec![0; 40]), (sibling, vec![1; 40])]);
+        let (root, nodes) = build_with_proofs(&entries, [&removed]);
+        let mut trie = SparseTrie::from_nodes(root, &nodes).unwrap();
+        trie.remove(removed).unwrap();
+
+        // collapsing the root onto the sibling needs the node of the sibling
+        assert!(matches!(trie.clone().root(), Err(StatelessError::MissingTrieNode(_))));
+
+        // unless another key is inserted next to it
+        trie.insert(inserted, vec![2; 40]).unwrap();
+        let entries = BTreeMap::from([(sibling, vec![1; 40]), (inserted, vec![2; 40])]);
+        assert_eq!(trie.root().unwrap(), build(&entries).0);
+    }
+}
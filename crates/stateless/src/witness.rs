@@ -0,0 +1,79 @@
+//! The witness to execute a block statelessly.
+
+use crate::StatelessError;
+use alloy_rlp::Decodable;
+use reth_primitives::{
+    keccak256, Address, Block, BlockNumber, Bytes, ChainId, Header, SealedHeader, B256, U256,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// A witness to execute a block statelessly, serialized as JSON with hex encoded byte strings.
+///
+/// To re-execute the block, the state is looked up by walking the trie nodes in `state` from the
+/// state root of the parent header, resolving every node by its keccak hash. Contract code is
+/// resolved from `codes` by its code hash, and the hash of an ancestor block by hashing the headers
+/// in `headers`, which link to the parent of the block through their parent hashes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWitness {
+    /// The chain the block belongs to.
+    pub chain_id: ChainId,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+    /// The RLP encoded block.
+    pub block: Bytes,
+    /// The RLP encoded headers from the oldest ancestor whose hash the block accesses to the
+    /// parent of the block, oldest first. Contains at least the parent header.
+    pub headers: Vec<Bytes>,
+    /// The RLP encoded nodes of the account trie and of the storage tries of the parent state on
+    /// the paths to all accessed accounts and storage slots, including the proofs of absence of
+    /// accessed accounts and slots that don't exist.
+    pub state: Vec<Bytes>,
+    /// The bytecode of all contracts the block executes.
+    pub codes: Vec<Bytes>,
+    /// The accessed accounts and their accessed storage slots.
+    pub keys: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+/// The decoded contents of a [`BlockWitness`].
+#[derive(Debug)]
+pub(crate) struct DecodedWitness {
+    pub(crate) block: Block,
+    pub(crate) parent: SealedHeader,
+    pub(crate) block_hashes: HashMap<BlockNumber, B256>,
+    pub(crate) nodes: HashMap<B256, Bytes>,
+    pub(crate) codes: HashMap<B256, Bytes>,
+}
+
+impl BlockWitness {
+    /// Decodes the witness and checks that its headers link to the block.
+    pub(crate) fn decode(&self) -> Result<DecodedWitness, StatelessError> {
+        let block = Block::decode(&mut self.block.as_ref())?;
+
+        let mut block_hashes = HashMap::with_capacity(self.headers.len());
+        let mut parent: Option<SealedHeader> = None;
+        for encoded in &self.headers {
+            let header = Header::decode(&mut encoded.as_ref())?.seal_slow();
+            if let Some(previous) = &parent {
+                if header.parent_hash != previous.hash() {
+                    return Err(StatelessError::UnlinkedHeader(header.number))
+                }
+            }
+            block_hashes.insert(header.number, header.hash());
+            parent = Some(header);
+        }
+        let parent = parent.ok_or(StatelessError::MissingParentHeader)?;
+        if block.parent_hash != parent.hash() {
+            return Err(StatelessError::MissingParentHeader)
+        }
+
+        Ok(DecodedWitness {
+            block,
+            parent,
+            block_hashes,
+            nodes: self.state.iter().map(|node| (keccak256(node), node.clone())).collect(),
+            codes: self.codes.iter().map(|code| (keccak256(code), code.clone())).collect(),
+        })
+    }
+}
@@ -1,20 +1,87 @@
+use crate::StaticFileSegment;
+use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 
 /// Static File compression types.
-#[derive(Debug, Copy, Clone, Default, AsRefStr)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum Compression {
     /// LZ4 compression algorithm.
     #[strum(serialize = "lz4")]
+    #[serde(rename = "lz4")]
     Lz4,
     /// Zstandard (Zstd) compression algorithm.
     #[strum(serialize = "zstd")]
+    #[serde(rename = "zstd")]
     Zstd,
     /// Zstandard (Zstd) compression algorithm with a dictionary.
     #[strum(serialize = "zstd-dict")]
+    #[serde(rename = "zstd-dict")]
+    #[cfg_attr(feature = "clap", value(alias = "zstd-dict"))]
     ZstdWithDictionary,
     /// No compression.
     #[strum(serialize = "uncompressed")]
+    #[serde(rename = "uncompressed", alias = "none")]
+    #[cfg_attr(feature = "clap", value(alias = "none"))]
     #[default]
     Uncompressed,
 }
+
+/// Compression used for newly written static files, per segment.
+///
+/// Existing static files keep the compression they were written with, until they are rewritten
+/// with `reth db compact-static-files`. Dictionaries can only be trained on existing data, so
+/// [`Compression::ZstdWithDictionary`] files are written with plain zstd, and get their
+/// dictionaries when compacted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SegmentCompression {
+    /// Compression of the headers segment.
+    pub headers: Compression,
+    /// Compression of the transactions segment.
+    pub transactions: Compression,
+    /// Compression of the receipts segment.
+    pub receipts: Compression,
+}
+
+impl SegmentCompression {
+    /// Returns the compression of the given segment.
+    pub const fn get(&self, segment: StaticFileSegment) -> Compression {
+        match segment {
+            StaticFileSegment::Headers => self.headers,
+            StaticFileSegment::Transactions => self.transactions,
+            StaticFileSegment::Receipts => self.receipts,
+        }
+    }
+}
+
+impl Default for SegmentCompression {
+    fn default() -> Self {
+        // Transactions and receipts already use zstd dictionaries natively in their encoding.
+        Self {
+            headers: Compression::Lz4,
+            transactions: Compression::Uncompressed,
+            receipts: Compression::Uncompressed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_segment_compression() {
+        let compression: SegmentCompression =
+            serde_json::from_str(r#"{"transactions":"none","receipts":"zstd-dict"}"#).unwrap();
+        assert_eq!(
+            compression,
+            SegmentCompression {
+                headers: Compression::Lz4,
+                transactions: Compression::Uncompressed,
+                receipts: Compression::ZstdWithDictionary,
+            }
+        );
+        assert_eq!(compression.get(StaticFileSegment::Receipts), Compression::ZstdWithDictionary);
+    }
+}
@@ -10,11 +10,13 @@
 
 mod compression;
 mod filters;
+mod receipts;
 mod segment;
 
 use alloy_primitives::BlockNumber;
-pub use compression::Compression;
+pub use compression::{Compression, SegmentCompression};
 pub use filters::{Filters, InclusionFilter, PerfectHashingFunction};
+pub use receipts::ReceiptsLayout;
 pub use segment::{SegmentConfig, SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
 
 /// Default static file block count.
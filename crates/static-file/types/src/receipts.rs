@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use strum::AsRefStr;
+
+/// Layout of the receipts in receipts static files.
+///
+/// Existing static files keep the layout they were written with, until they are rewritten with
+/// `reth db compact-static-files`. Readers detect the layout of a static file from its number of
+/// columns, so files of both layouts can be mixed.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ReceiptsLayout {
+    /// Every receipt is stored in a single column, in its database encoding.
+    #[strum(serialize = "rows")]
+    #[serde(rename = "rows")]
+    #[default]
+    Rows,
+    /// The logs of every receipt are split into a column of log addresses, a column of log
+    /// topics and a column of log data, next to a column with the remaining receipt fields.
+    ///
+    /// Values of the same kind compress much better together, in particular with the per-column
+    /// dictionaries of [`Compression::ZstdWithDictionary`](crate::Compression). The columns are
+    /// always read together and decoded into whole receipts, so this only reduces the size of the
+    /// files.
+    #[strum(serialize = "columns")]
+    #[serde(rename = "columns")]
+    Columns,
+}
+
+impl ReceiptsLayout {
+    /// Returns the number of columns of receipts static files in this layout.
+    pub const fn columns(&self) -> usize {
+        match self {
+            Self::Rows => 1,
+            Self::Columns => 4,
+        }
+    }
+
+    /// Returns the layout of a receipts static file with the given number of columns.
+    pub const fn from_columns(columns: usize) -> Option<Self> {
+        match columns {
+            1 => Some(Self::Rows),
+            4 => Some(Self::Columns),
+            _ => None,
+        }
+    }
+
+    /// Returns the mask that selects all columns of this layout.
+    pub const fn mask(&self) -> usize {
+        (1 << self.columns()) - 1
+    }
+}
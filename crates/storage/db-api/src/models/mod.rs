@@ -14,13 +14,19 @@ pub mod accounts;
 pub mod blocks;
 pub mod client_version;
 pub mod integer_list;
+pub mod reorg;
 pub mod sharded_key;
 pub mod storage_sharded_key;
+#[cfg(feature = "zero-copy")]
+pub mod zero_copy;
 
 pub use accounts::*;
 pub use blocks::*;
 pub use client_version::ClientVersion;
+pub use reorg::StoredReorg;
 pub use sharded_key::ShardedKey;
+#[cfg(feature = "zero-copy")]
+pub use zero_copy::TransactionRef;
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
 macro_rules! impl_uints {
@@ -221,6 +227,7 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    StoredReorg,
     Requests,
     // Non-DB
     GenesisAccount
@@ -339,6 +346,7 @@ mod tests {
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+        assert_eq!(StoredReorg::bitflag_encoded_bytes(), 1);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
         assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
@@ -372,6 +380,7 @@ mod tests {
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+        assert_eq!(StoredReorg::bitflag_encoded_bytes(), 1);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
         assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
@@ -0,0 +1,34 @@
+//! Reorg journal related models and types.
+
+use reth_codecs::{main_codec, Compact};
+use reth_primitives::{BlockNumHash, BlockNumber, B256};
+
+/// A reorg of the canonical chain, as stored in the reorg journal.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredReorg {
+    /// Unix timestamp in seconds at which the reorg was committed.
+    pub timestamp: u64,
+    /// Number of the last block the old and the new chain have in common.
+    pub fork_block_number: BlockNumber,
+    /// Hash of the last block the old and the new chain have in common.
+    pub fork_block_hash: B256,
+    /// Hashes of the reverted blocks, in ascending order, starting with the child of the fork
+    /// block.
+    pub old_blocks: Vec<B256>,
+    /// Hashes of the committed blocks, in ascending order, starting with the child of the fork
+    /// block.
+    pub new_blocks: Vec<B256>,
+}
+
+impl StoredReorg {
+    /// Returns the fork block.
+    pub const fn fork_block(&self) -> BlockNumHash {
+        BlockNumHash { number: self.fork_block_number, hash: self.fork_block_hash }
+    }
+
+    /// Returns the number of reverted blocks.
+    pub fn depth(&self) -> u64 {
+        self.old_blocks.len() as u64
+    }
+}
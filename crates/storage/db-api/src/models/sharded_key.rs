@@ -10,6 +10,14 @@ use std::hash::Hash;
 /// Number of indices in one shard.
 pub const NUM_OF_INDICES_IN_SHARD: usize = 2_000;
 
+/// Maximum number of indices in the last shard of a history index key, the one keyed by
+/// `u64::MAX`.
+///
+/// The last shard is rewritten whenever indices are appended to it, so it's kept small and sealed
+/// under its highest block number once it's full. The sealed shards are merged into shards of
+/// [`NUM_OF_INDICES_IN_SHARD`] indices in the background.
+pub const NUM_OF_INDICES_IN_TAIL_SHARD: usize = 250;
+
 /// Sometimes data can be too big to be saved for a single key. This helps out by dividing the data
 /// into different shards. Example:
 ///
@@ -0,0 +1,138 @@
+//! Zero-copy views of the values of hot tables.
+//!
+//! Values that decode without allocating, like [`Account`] and [`StorageEntry`], are their own
+//! view, so reading them by reference saves no allocations. Transactions are viewed as a
+//! [`TransactionRef`], which borrows the encoded transaction and only decodes the fields that are
+//! accessed.
+//!
+//! This is only the read API: the state providers and transaction lookups still read owned values.
+
+use crate::{
+    table::{Decompress, DecompressRef},
+    DatabaseError,
+};
+use reth_codecs::Compact;
+use reth_primitives::{Account, Signature, StorageEntry, TransactionSignedNoHash, TxType};
+
+/// Size of the bitflags and signature that precede the transaction in its database encoding.
+const TRANSACTION_HEADER_SIZE: usize = 1 + 64;
+
+macro_rules! impl_decompress_ref_owned {
+    ($($name:tt),+) => {
+        $(
+            impl<'a> DecompressRef<'a> for $name {
+                type Ref = Self;
+
+                fn decompress_ref(value: &'a [u8]) -> Result<Self::Ref, DatabaseError> {
+                    Self::decompress(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_decompress_ref_owned!(Account, StorageEntry);
+
+/// A [`TransactionSignedNoHash`] borrowed from the database.
+///
+/// The signature and type are read from the encoding without decoding the transaction, which
+/// involves allocating its input and access list, and decompressing it for large inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionRef<'a> {
+    /// The transaction in its database encoding.
+    value: &'a [u8],
+}
+
+impl<'a> TransactionRef<'a> {
+    /// Returns the transaction in its database encoding.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.value
+    }
+
+    /// Returns the signature of the transaction.
+    pub fn signature(&self) -> Signature {
+        let signature = &self.value[1..TRANSACTION_HEADER_SIZE];
+        Signature::from_compact(signature, (self.bitflags() & 1) as usize).0
+    }
+
+    /// Returns `true` if the transaction is stored zstd compressed.
+    pub const fn is_compressed(&self) -> bool {
+        self.bitflags() >> 3 != 0
+    }
+
+    /// Returns the type of the transaction.
+    ///
+    /// Returns `None` for compressed transactions whose type doesn't fit into the bitflags, since
+    /// their type is stored in the compressed data.
+    pub fn tx_type(&self) -> Option<TxType> {
+        let identifier = match (self.bitflags() & 0b110) >> 1 {
+            // the type is stored in the first byte of the transaction
+            3 if self.is_compressed() => return None,
+            3 => *self.value.get(TRANSACTION_HEADER_SIZE)?,
+            identifier => identifier,
+        };
+        TxType::try_from(identifier).ok()
+    }
+
+    /// Decodes the transaction.
+    pub fn decode(&self) -> Result<TransactionSignedNoHash, DatabaseError> {
+        TransactionSignedNoHash::decompress(self.value)
+    }
+
+    const fn bitflags(&self) -> u8 {
+        self.value[0]
+    }
+}
+
+impl<'a> DecompressRef<'a> for TransactionSignedNoHash {
+    type Ref = TransactionRef<'a>;
+
+    fn decompress_ref(value: &'a [u8]) -> Result<Self::Ref, DatabaseError> {
+        if value.len() < TRANSACTION_HEADER_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+        Ok(TransactionRef { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Compress;
+    use reth_primitives::{Bytes, Transaction, TxEip1559, TxEip4844, TxLegacy};
+
+    #[test]
+    fn transaction_ref() {
+        let signature = Signature { odd_y_parity: true, ..Default::default() };
+        for (transaction, tx_type) in [
+            (Transaction::Legacy(TxLegacy::default()), Some(TxType::Legacy)),
+            (Transaction::Eip4844(TxEip4844::default()), Some(TxType::Eip4844)),
+            (
+                // large inputs are compressed
+                Transaction::Eip1559(TxEip1559 {
+                    input: Bytes::from(vec![1; 64]),
+                    ..Default::default()
+                }),
+                Some(TxType::Eip1559),
+            ),
+            (
+                Transaction::Eip4844(TxEip4844 {
+                    input: Bytes::from(vec![1; 64]),
+                    ..Default::default()
+                }),
+                None,
+            ),
+        ] {
+            let transaction = TransactionSignedNoHash { signature, transaction };
+            let value = transaction.clone().compress();
+
+            let view = TransactionSignedNoHash::decompress_ref(&value).unwrap();
+            assert_eq!(view.signature(), signature);
+            assert_eq!(view.tx_type(), tx_type);
+            assert_eq!(view.is_compressed(), transaction.transaction.input().len() >= 32);
+            assert_eq!(view.decode().unwrap(), transaction);
+        }
+
+        assert!(TransactionSignedNoHash::decompress_ref(&[0; 64]).is_err());
+    }
+}
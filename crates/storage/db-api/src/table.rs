@@ -46,6 +46,19 @@ pub trait Decompress: Send + Sync + Sized + Debug {
     }
 }
 
+/// Trait that will transform the data to be read from the DB without copying it.
+///
+/// Values are borrowed from the database pages, so they can only be read in read-only
+/// transactions, whose pages are not modified while the transaction is open.
+#[cfg(feature = "zero-copy")]
+pub trait DecompressRef<'a>: Decompress {
+    /// View of the value that borrows from the database.
+    type Ref: Debug + 'a;
+
+    /// Decompresses data coming from the database, borrowing from it.
+    fn decompress_ref(value: &'a [u8]) -> Result<Self::Ref, DatabaseError>;
+}
+
 /// Trait that will transform the data to be saved in the DB.
 pub trait Encode: Send + Sync + Sized + Debug {
     /// Encoded type.
@@ -0,0 +1,156 @@
+//! Streaming reader for genesis files.
+//!
+//! Genesis files of large chains can allocate millions of accounts, which are too many to hold in
+//! memory at once. [`read_genesis`] parses a genesis file while handing the accounts of its
+//! `alloc` section to a callback one at a time.
+
+use alloy_genesis::{Genesis, GenesisAccount};
+use reth_primitives::Address;
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, Visitor};
+use serde_json::value::RawValue;
+use std::{fmt, io::Read};
+
+/// Reads a genesis JSON document and passes the accounts of its `alloc` section to `on_account`
+/// in the order of the file, instead of collecting them.
+///
+/// The returned [`Genesis`] has an empty alloc.
+pub fn read_genesis<R, F>(reader: R, mut on_account: F) -> eyre::Result<Genesis>
+where
+    R: Read,
+    F: FnMut(Address, GenesisAccount) -> eyre::Result<()>,
+{
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let fields = GenesisVisitor(&mut on_account).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    // reassemble the remaining fields, they are small compared to the alloc
+    let mut json = String::from("{");
+    for (index, (key, value)) in fields.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str(&serde_json::to_string(key)?);
+        json.push(':');
+        json.push_str(value.get());
+    }
+    json.push('}');
+
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Visits the top level object of a genesis file and collects all fields except the alloc.
+struct GenesisVisitor<'a, F>(&'a mut F);
+
+impl<'de, F> DeserializeSeed<'de> for GenesisVisitor<'_, F>
+where
+    F: FnMut(Address, GenesisAccount) -> eyre::Result<()>,
+{
+    type Value = Vec<(String, Box<RawValue>)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for GenesisVisitor<'_, F>
+where
+    F: FnMut(Address, GenesisAccount) -> eyre::Result<()>,
+{
+    type Value = Vec<(String, Box<RawValue>)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a genesis object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let Self(on_account) = self;
+        let mut fields = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "alloc" {
+                map.next_value_seed(AllocVisitor(&mut *on_account))?;
+            } else {
+                fields.push((key, map.next_value()?));
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// Visits the alloc object of a genesis file and passes every account to the callback.
+struct AllocVisitor<'a, F>(&'a mut F);
+
+impl<'de, F> DeserializeSeed<'de> for AllocVisitor<'_, F>
+where
+    F: FnMut(Address, GenesisAccount) -> eyre::Result<()>,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for AllocVisitor<'_, F>
+where
+    F: FnMut(Address, GenesisAccount) -> eyre::Result<()>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map of addresses to genesis accounts")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        while let Some((address, account)) = map.next_entry::<Address, GenesisAccount>()? {
+            (self.0)(address, account).map_err(A::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use std::collections::BTreeMap;
+
+    const GENESIS: &str = r#"{
+        "config": {
+            "chainId": 1337,
+            "terminalTotalDifficulty": 58750000000000000000000
+        },
+        "alloc": {
+            "0x0000000000000000000000000000000000000001": { "balance": "0x1" },
+            "0x0000000000000000000000000000000000000002": {
+                "balance": "0x2",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                }
+            }
+        },
+        "gasLimit": "0x1c9c380",
+        "difficulty": "0x0"
+    }"#;
+
+    #[test]
+    fn streams_alloc() {
+        let mut alloc = BTreeMap::new();
+        let genesis = read_genesis(GENESIS.as_bytes(), |address, account| {
+            alloc.insert(address, account);
+            Ok(())
+        })
+        .unwrap();
+
+        let expected: Genesis = serde_json::from_str(GENESIS).unwrap();
+        assert!(genesis.alloc.is_empty());
+        assert_eq!(alloc, expected.alloc);
+        assert_eq!(Genesis { alloc, ..genesis }, expected);
+        assert_eq!(expected.alloc[&Address::with_last_byte(2)].balance, U256::from(2));
+    }
+
+    #[test]
+    fn callback_error_aborts() {
+        let err = read_genesis(GENESIS.as_bytes(), |_, _| eyre::bail!("disk full")).unwrap_err();
+        assert!(err.to_string().contains("disk full"));
+    }
+}
@@ -1,11 +1,16 @@
 //! Reth genesis initialization utility functions.
 
+use crate::genesis::read_genesis;
 use alloy_genesis::GenesisAccount;
 use reth_chainspec::ChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
-use reth_db_api::{database::Database, transaction::DbTxMut, DatabaseError};
+use reth_db_api::{
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
 use reth_etl::Collector;
 use reth_primitives::{
     Account, Address, Bytecode, Receipts, StaticFileSegment, StorageEntry, B256, U256,
@@ -16,18 +21,22 @@ use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockNumReader, ChainSpecProvider, DatabaseProviderRW, ExecutionOutcome,
     HashingWriter, HistoryWriter, OriginalValuesKnown, ProviderError, ProviderFactory,
-    StageCheckpointWriter, StateWriter, StaticFileProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter, StateWriter, StaticFileProviderFactory,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
+use reth_trie::{
+    root::storage_root_unhashed, IntermediateStateRootState, StateRoot as StateRootComputer,
+    StateRootProgress,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
-    io::BufRead,
+    io::{BufRead, Read},
+    mem::size_of,
     ops::DerefMut,
     sync::Arc,
 };
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 /// Default soft limit for number of bytes to read from state dump file, before inserting into
 /// database.
@@ -46,6 +55,13 @@ pub const AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP: usize = 285_228;
 /// Soft limit for the number of flushed updates after which to log progress summary.
 const SOFT_LIMIT_COUNT_FLUSHED_UPDATES: usize = 1_000_000;
 
+/// Stage id under which the progress of a state import is saved, so that an interrupted import
+/// can be resumed.
+///
+/// The progress is the state root of the imported state, followed by the address of the last
+/// written account.
+const STATE_IMPORT_PROGRESS_ID: StageId = StageId::Other("StateImport");
+
 /// Database initialization error type.
 #[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
 pub enum InitDatabaseError {
@@ -71,6 +87,30 @@ pub enum InitDatabaseError {
         /// Actual state root.
         computed_state_root: B256,
     },
+    /// The genesis alloc was imported into a chain spec that already contains it.
+    #[error("genesis alloc is already part of the chainspec")]
+    GenesisAllocInChainspec,
+    /// The genesis alloc was imported into a database that already contains state.
+    #[error("database already contains state")]
+    StateAlreadyInitialized,
+    /// The storage of an account in the state dump file doesn't match its storage root.
+    #[error("storage root mismatch for account {address}, state dump: {expected_storage_root}, computed: {computed_storage_root}")]
+    StorageRootMismatch {
+        /// Address of the account.
+        address: Address,
+        /// Expected storage root.
+        expected_storage_root: B256,
+        /// Actual storage root.
+        computed_storage_root: B256,
+    },
+    /// The database contains a partial import of a different state dump.
+    #[error("database contains a partial import of the state dump with state root {found_state_root}, expected {expected_state_root}")]
+    PartialImportMismatch {
+        /// State root of the state dump that is imported.
+        expected_state_root: B256,
+        /// State root of the partially imported state dump.
+        found_state_root: B256,
+    },
 }
 
 impl From<DatabaseError> for InitDatabaseError {
@@ -106,19 +146,36 @@ pub fn init_genesis<DB: Database>(factory: ProviderFactory<DB>) -> Result<B256,
     debug!("Writing genesis block.");
 
     let alloc = &genesis.alloc;
+    if alloc.is_empty() && chain.precomputed_genesis_state_root().is_some() {
+        warn!(target: "reth::cli", "Genesis alloc is not part of the chainspec, it has to be imported with `reth init --genesis.alloc`");
+    }
 
     // use transaction to insert genesis header
     let provider_rw = factory.provider_rw()?;
-    insert_genesis_hashes(&provider_rw, alloc.iter())?;
-    insert_genesis_history(&provider_rw, alloc.iter())?;
+
+    // insert the alloc in batches, so the intermediate state of large allocs stays bounded
+    let accounts = alloc.iter().collect::<Vec<_>>();
+    for (index, batch) in accounts.chunks(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP).enumerate() {
+        insert_genesis_hashes(&provider_rw, batch.iter().copied())?;
+        insert_genesis_history(&provider_rw, batch.iter().copied())?;
+        insert_genesis_state::<DB>(provider_rw.tx_ref(), batch.len(), batch.iter().copied())?;
+
+        if accounts.len() > AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP {
+            let inserted = index * AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP + batch.len();
+            info!(target: "reth::cli",
+                inserted,
+                total = accounts.len(),
+                progress = %format!("{:.2}%", inserted as f64 / accounts.len() as f64 * 100.0),
+                "Inserting genesis alloc"
+            );
+        }
+    }
 
     // Insert header
     let tx = provider_rw.tx_ref();
     let static_file_provider = factory.static_file_provider();
     insert_genesis_header::<DB>(tx, &static_file_provider, chain.clone())?;
 
-    insert_genesis_state::<DB>(tx, alloc.len(), alloc.iter())?;
-
     // insert sync stage
     for stage in StageId::ALL {
         provider_rw.save_stage_checkpoint(stage, Default::default())?;
@@ -322,19 +379,84 @@ pub fn init_from_state_dump<DB: Database>(
     // remaining lines are accounts
     let collector = parse_accounts(&mut reader, etl_config)?;
 
+    import_state(collector, factory, block, expected_state_root)?;
+
+    Ok(hash)
+}
+
+/// Reads the alloc of a genesis file from a [`Read`] reader and inserts it as the state of the
+/// genesis block, which has to be written already.
+///
+/// The accounts are streamed from the file and sorted on disk, so allocs with millions of
+/// accounts can be imported with bounded memory. This is meant for chain specs whose genesis file
+/// carries the pre-computed state root and leaves the alloc out of memory, see
+/// [`ChainSpec::precomputed_genesis_state_root`]. The state root of the imported alloc is checked
+/// against the genesis header.
+pub fn init_genesis_alloc<DB: Database>(
+    reader: impl Read,
+    factory: ProviderFactory<DB>,
+    etl_config: EtlConfig,
+) -> eyre::Result<B256> {
+    let chain = factory.chain_spec();
+    if !chain.genesis().alloc.is_empty() {
+        Err(InitDatabaseError::GenesisAllocInChainspec)?
+    }
+    if factory.provider()?.tx_ref().entries::<tables::PlainAccountState>()? > 0 {
+        Err(InitDatabaseError::StateAlreadyInitialized)?
+    }
+    let hash = chain.genesis_hash();
+
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+    read_genesis(reader, |address, account| {
+        collector.insert(address, account)?;
+        if collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0 {
+            info!(target: "reth::cli", parsed_new_accounts = collector.len());
+        }
+        Ok(())
+    })?;
+
+    import_state(collector, factory, 0, chain.genesis_header().state_root)?;
+
+    Ok(hash)
+}
+
+/// Writes the collected accounts as the state at the given block and verifies the resulting state
+/// root.
+fn import_state<DB: Database>(
+    collector: Collector<Address, GenesisAccount>,
+    factory: ProviderFactory<DB>,
+    block: u64,
+    expected_state_root: B256,
+) -> eyre::Result<()> {
+    // accounts are written in address order, so a partial import resumes after the last account
+    let resume_after = state_import_progress(&factory, expected_state_root)?;
+    if let Some(address) = resume_after {
+        info!(target: "reth::cli", %address, "Resuming partial state import");
+    }
+
     // write state to db
-    let mut provider_rw = factory.provider_rw()?;
-    dump_state(collector, &mut provider_rw, block)?;
+    dump_state(collector, &factory, block, expected_state_root, resume_after)?;
 
     // compute and compare state root. this advances the stage checkpoints.
+    let provider_rw = factory.provider_rw()?;
     let computed_state_root = compute_state_root(&provider_rw)?;
     if computed_state_root != expected_state_root {
         error!(target: "reth::cli",
             ?computed_state_root,
             ?expected_state_root,
-            "Computed state root does not match state root in state dump"
+            "Computed state root does not match state root in state dump, clearing the import progress"
         );
 
+        // discard the trie updates and clear the progress, so that a retry imports the whole
+        // state dump again instead of resuming after the last account
+        drop(provider_rw);
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.tx_ref().delete::<tables::StageCheckpointProgresses>(
+            STATE_IMPORT_PROGRESS_ID.to_string(),
+            None,
+        )?;
+        provider_rw.commit()?;
+
         Err(InitDatabaseError::SateRootMismatch { expected_state_root, computed_state_root })?
     } else {
         info!(target: "reth::cli",
@@ -347,10 +469,36 @@ pub fn init_from_state_dump<DB: Database>(
     for stage in StageId::STATE_REQUIRED {
         provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(block))?;
     }
+    provider_rw
+        .tx_ref()
+        .delete::<tables::StageCheckpointProgresses>(STATE_IMPORT_PROGRESS_ID.to_string(), None)?;
 
     provider_rw.commit()?;
 
-    Ok(hash)
+    Ok(())
+}
+
+/// Returns the address of the last account written by a partial import of the state with the
+/// given state root, if any.
+fn state_import_progress<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    expected_state_root: B256,
+) -> eyre::Result<Option<Address>> {
+    let Some(progress) =
+        factory.provider()?.get_stage_checkpoint_progress(STATE_IMPORT_PROGRESS_ID)?
+    else {
+        return Ok(None)
+    };
+    if progress.len() != size_of::<B256>() + size_of::<Address>() {
+        eyre::bail!("malformed state import progress")
+    }
+
+    let (state_root, address) = progress.split_at(size_of::<B256>());
+    let found_state_root = B256::from_slice(state_root);
+    if found_state_root != expected_state_root {
+        Err(InitDatabaseError::PartialImportMismatch { expected_state_root, found_state_root })?
+    }
+    Ok(Some(Address::from_slice(address)))
 }
 
 /// Parses and returns expected state root.
@@ -379,7 +527,11 @@ fn parse_accounts(
             break
         }
 
-        let GenesisAccountWithAddress { genesis_account, address } = serde_json::from_str(&line)?;
+        let GenesisAccountWithAddress { genesis_account, address, storage_root } =
+            serde_json::from_str(&line)?;
+        if let Some(expected_storage_root) = storage_root {
+            verify_storage_root(address, &genesis_account, expected_storage_root)?;
+        }
         collector.insert(address, genesis_account)?;
 
         if !collector.is_empty() && collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0
@@ -395,11 +547,41 @@ fn parse_accounts(
     Ok(collector)
 }
 
+/// Checks the storage of the account against the storage root from the state dump file.
+fn verify_storage_root(
+    address: Address,
+    account: &GenesisAccount,
+    expected_storage_root: B256,
+) -> Result<(), InitDatabaseError> {
+    let storage = account.storage.iter().flatten().filter(|(_, value)| !value.is_zero());
+    let computed_storage_root =
+        storage_root_unhashed(storage.map(|(slot, value)| (*slot, U256::from_be_bytes(value.0))));
+    if computed_storage_root != expected_storage_root {
+        error!(target: "reth::cli",
+            %address,
+            ?computed_storage_root,
+            ?expected_storage_root,
+            "Computed storage root does not match storage root in state dump"
+        );
+        return Err(InitDatabaseError::StorageRootMismatch {
+            address,
+            expected_storage_root,
+            computed_storage_root,
+        })
+    }
+    Ok(())
+}
+
 /// Takes a [`Collector`] and processes all accounts.
+///
+/// Every chunk of accounts is committed together with the address of its last account, so that
+/// an interrupted import can resume after it.
 fn dump_state<DB: Database>(
     mut collector: Collector<Address, GenesisAccount>,
-    provider_rw: &mut DatabaseProviderRW<DB>,
+    factory: &ProviderFactory<DB>,
     block: u64,
+    state_root: B256,
+    resume_after: Option<Address>,
 ) -> Result<(), eyre::Error> {
     let accounts_len = collector.len();
     let mut accounts = Vec::with_capacity(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP);
@@ -410,26 +592,34 @@ fn dump_state<DB: Database>(
         let (address, _) = Address::from_compact(address.as_slice(), address.len());
         let (account, _) = GenesisAccount::from_compact(account.as_slice(), account.len());
 
-        accounts.push((address, account));
+        if resume_after.map_or(true, |last| address > last) {
+            accounts.push((address, account));
+        } else {
+            total_inserted_accounts += 1;
+        }
 
-        if (index > 0 && index % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0) ||
-            index == accounts_len - 1
+        if ((index > 0 && index % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0) ||
+            index == accounts_len - 1) &&
+            !accounts.is_empty()
         {
             total_inserted_accounts += accounts.len();
 
             info!(target: "reth::cli",
                 total_inserted_accounts,
+                progress = %format!("{:.2}%", total_inserted_accounts as f64 / accounts_len as f64 * 100.0),
                 "Writing accounts to db"
             );
 
+            let mut provider_rw = factory.provider_rw()?;
+
             // use transaction to insert genesis header
             insert_genesis_hashes(
-                provider_rw,
+                &provider_rw,
                 accounts.iter().map(|(address, account)| (address, account)),
             )?;
 
             insert_history(
-                provider_rw,
+                &provider_rw,
                 accounts.iter().map(|(address, account)| (address, account)),
                 block,
             )?;
@@ -443,6 +633,13 @@ fn dump_state<DB: Database>(
                 block,
             )?;
 
+            let last_address = accounts.last().map(|(address, _)| *address).unwrap_or_default();
+            provider_rw.save_stage_checkpoint_progress(
+                STATE_IMPORT_PROGRESS_ID,
+                [state_root.as_slice(), last_address.as_slice()].concat(),
+            )?;
+            provider_rw.commit()?;
+
             accounts.clear();
         }
     }
@@ -502,19 +699,28 @@ fn compute_state_root<DB: Database>(provider: &DatabaseProviderRW<DB>) -> eyre::
 
 /// Type to deserialize state root from state dump file.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-struct StateRoot {
-    root: B256,
+pub(crate) struct StateRoot {
+    pub(crate) root: B256,
 }
 
 /// An account as in the state dump file. This contains a [`GenesisAccount`] and the account's
 /// address.
 #[derive(Debug, Serialize, Deserialize)]
-struct GenesisAccountWithAddress {
+pub(crate) struct GenesisAccountWithAddress {
     /// The account's balance, nonce, code, and storage.
     #[serde(flatten)]
-    genesis_account: GenesisAccount,
+    pub(crate) genesis_account: GenesisAccount,
     /// The account's address.
-    address: Address,
+    pub(crate) address: Address,
+    /// The root of the account's storage trie. If present, the storage of the account is
+    /// verified against it while the state dump file is read.
+    #[serde(
+        default,
+        rename = "storageRoot",
+        alias = "root",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) storage_root: Option<B256>,
 }
 
 #[cfg(test)]
@@ -647,4 +853,156 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    fn init_genesis_alloc_with_precomputed_state_root() {
+        let alloc = r#"{
+            "0x0000000000000000000000000000000000000001": { "balance": "0x1" },
+            "0x0000000000000000000000000000000000000002": {
+                "balance": "0x2",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                }
+            }
+        }"#;
+        let genesis_file = format!(
+            r#"{{"config":{{"chainId":1337}},"difficulty":"0x1","gasLimit":"0x1c9c380","alloc":{alloc}}}"#
+        );
+        let genesis: Genesis = serde_json::from_str(&genesis_file).unwrap();
+        let state_root = ChainSpec::from(genesis).genesis_header().state_root;
+
+        // the chainspec only carries the state root, the alloc is imported from the file
+        let genesis: Genesis = serde_json::from_str(&format!(
+            r#"{{"config":{{"chainId":1337,"stateRoot":"{state_root}"}},"difficulty":"0x1","gasLimit":"0x1c9c380","alloc":{{}}}}"#
+        ))
+        .unwrap();
+        let chain_spec = Arc::new(ChainSpec::from(genesis));
+        let genesis_hash = chain_spec.genesis_hash();
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(factory.clone()).unwrap();
+
+        let hash =
+            init_genesis_alloc(genesis_file.as_bytes(), factory.clone(), EtlConfig::default())
+                .unwrap();
+        assert_eq!(hash, genesis_hash);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::PlainAccountState>().unwrap(), 2);
+        assert_eq!(provider.tx_ref().entries::<tables::PlainStorageState>().unwrap(), 1);
+        drop(provider);
+
+        // the alloc can't be imported twice
+        let err =
+            init_genesis_alloc(genesis_file.as_bytes(), factory, EtlConfig::default()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InitDatabaseError>(),
+            Some(&InitDatabaseError::StateAlreadyInitialized)
+        );
+    }
+
+    const STATE_DUMP_ACCOUNTS: [&str; 2] = [
+        r#"{"address":"0x0000000000000000000000000000000000000001","balance":"0x1"}"#,
+        r#"{"address":"0x0000000000000000000000000000000000000002","balance":"0x2","storage":{"0x0000000000000000000000000000000000000000000000000000000000000001":"0x0000000000000000000000000000000000000000000000000000000000000001"},"storageRoot":"STORAGE_ROOT"}"#,
+    ];
+
+    /// Returns a factory with an empty genesis, and the state root of the state dump accounts.
+    fn state_dump_factory() -> (ProviderFactory<Arc<DatabaseEnv>>, B256, B256) {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":1337},"difficulty":"0x1","gasLimit":"0x1c9c380","alloc":{}}"#,
+        )
+        .unwrap();
+        let factory = create_test_provider_factory_with_chain_spec(Arc::new(genesis.into()));
+        init_genesis(factory.clone()).unwrap();
+
+        let storage_root = storage_root_unhashed([(B256::with_last_byte(1), U256::from(1))]);
+        let alloc = BTreeMap::from([
+            (
+                Address::with_last_byte(1),
+                GenesisAccount { balance: U256::from(1), ..Default::default() },
+            ),
+            (
+                Address::with_last_byte(2),
+                GenesisAccount {
+                    balance: U256::from(2),
+                    storage: Some(BTreeMap::from([(
+                        B256::with_last_byte(1),
+                        B256::with_last_byte(1),
+                    )])),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let state_root =
+            ChainSpec::from(Genesis::default().extend_accounts(alloc)).genesis_header().state_root;
+        (factory, state_root, storage_root)
+    }
+
+    fn state_dump(state_root: B256, storage_root: B256) -> String {
+        let accounts =
+            STATE_DUMP_ACCOUNTS.join("\n").replace("STORAGE_ROOT", &storage_root.to_string());
+        format!("{{\"root\":\"{state_root}\"}}\n{accounts}\n")
+    }
+
+    #[test]
+    fn init_from_state_dump_rejects_storage_root_mismatch() {
+        let (factory, state_root, _) = state_dump_factory();
+
+        let err = init_from_state_dump(
+            state_dump(state_root, B256::ZERO).as_bytes(),
+            factory.clone(),
+            EtlConfig::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<InitDatabaseError>(),
+            Some(InitDatabaseError::StorageRootMismatch { address, .. })
+                if *address == Address::with_last_byte(2)
+        ));
+
+        // nothing was written
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::PlainAccountState>().unwrap(), 0);
+    }
+
+    #[test]
+    fn init_from_state_dump_resumes_partial_import() {
+        let (factory, state_root, storage_root) = state_dump_factory();
+
+        // an interrupted import only wrote the first account
+        let etl_config = EtlConfig::default();
+        let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+        collector
+            .insert(
+                Address::with_last_byte(1),
+                GenesisAccount { balance: U256::from(1), ..Default::default() },
+            )
+            .unwrap();
+        dump_state(collector, &factory, 0, state_root, None).unwrap();
+
+        // a different state dump can't be imported on top of it
+        let err = init_from_state_dump(
+            state_dump(B256::ZERO, storage_root).as_bytes(),
+            factory.clone(),
+            etl_config.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InitDatabaseError>(),
+            Some(&InitDatabaseError::PartialImportMismatch {
+                expected_state_root: B256::ZERO,
+                found_state_root: state_root
+            })
+        );
+
+        init_from_state_dump(
+            state_dump(state_root, storage_root).as_bytes(),
+            factory.clone(),
+            etl_config,
+        )
+        .unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::PlainAccountState>().unwrap(), 2);
+        assert_eq!(provider.get_stage_checkpoint_progress(STATE_IMPORT_PROGRESS_ID).unwrap(), None);
+    }
 }
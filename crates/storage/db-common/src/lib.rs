@@ -8,7 +8,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod genesis;
 pub mod init;
+pub mod snapshot;
+pub mod state_dump;
 
 mod db_tool;
 pub use db_tool::*;
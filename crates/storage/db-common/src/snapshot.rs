@@ -0,0 +1,413 @@
+//! Portable state snapshots.
+//!
+//! A state snapshot contains the full hashed state (accounts, storage and bytecodes) and the
+//! intermediate nodes of the account and storage tries at a given block. The snapshot is encoded
+//! as JSON lines: the first line is a [`StateSnapshotHeader`], all subsequent lines are
+//! [`StateSnapshotEntry`] items. The format does not depend on the database encoding of the
+//! tables, so snapshots can be exchanged between nodes running different database versions.
+
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_primitives::{Account, BlockNumber, Bytecode, Bytes, StorageEntry, B256};
+use reth_provider::{
+    BlockHashReader, BlockNumReader, DatabaseProviderRO, DatabaseProviderRW, HeaderProvider,
+    ProviderError,
+};
+use reth_trie::{
+    trie_cursor::noop::NoopTrieCursorFactory, BranchNodeCompact, Nibbles, StateRoot,
+    StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use tracing::{info, trace};
+
+/// Current version of the state snapshot format.
+pub const STATE_SNAPSHOT_VERSION: u64 = 1;
+
+/// Number of snapshot entries after which to log progress.
+const SOFT_LIMIT_COUNT_ENTRIES_LOG: usize = 1_000_000;
+
+/// State snapshot error type.
+#[derive(Debug, thiserror::Error)]
+pub enum StateSnapshotError {
+    /// The snapshot was written with an unsupported format version.
+    #[error("unsupported state snapshot version {0}, expected {STATE_SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u64),
+    /// The snapshot is for a block that doesn't match the block in the database.
+    #[error("state snapshot is for block {snapshot_block} ({snapshot_hash}), database has {database_hash:?} at that height")]
+    BlockMismatch {
+        /// Block number of the snapshot.
+        snapshot_block: BlockNumber,
+        /// Block hash of the snapshot.
+        snapshot_hash: B256,
+        /// Hash of the block at the same height in the database, if any.
+        database_hash: Option<B256>,
+    },
+    /// The state root computed from the snapshot doesn't match the one in the header.
+    #[error("state root mismatch, snapshot: {expected}, computed: {computed}")]
+    StateRootMismatch {
+        /// State root recorded in the snapshot.
+        expected: B256,
+        /// Computed state root.
+        computed: B256,
+    },
+    /// The hashed state or trie tables in the target database are not empty.
+    #[error(
+        "table {0} is not empty, state snapshots can only be imported into empty state tables"
+    )]
+    TableNotEmpty(&'static str),
+    /// Snapshot entry could not be (de)serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<DatabaseError> for StateSnapshotError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Provider(ProviderError::Database(error))
+    }
+}
+
+/// First line of a state snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshotHeader {
+    /// Version of the snapshot format.
+    pub version: u64,
+    /// Block number the state corresponds to.
+    pub block_number: BlockNumber,
+    /// Hash of the block the state corresponds to.
+    pub block_hash: B256,
+    /// State root of the block.
+    pub state_root: B256,
+}
+
+/// A single entry of a state snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StateSnapshotEntry {
+    /// An entry of the hashed accounts table.
+    #[serde(rename_all = "camelCase")]
+    Account {
+        /// Hashed address of the account.
+        hashed_address: B256,
+        /// The account.
+        account: Account,
+    },
+    /// An entry of the hashed storages table.
+    #[serde(rename_all = "camelCase")]
+    Storage {
+        /// Hashed address of the account owning the slot.
+        hashed_address: B256,
+        /// Hashed slot and its value.
+        entry: StorageEntry,
+    },
+    /// Contract bytecode.
+    Bytecode {
+        /// Hash of the bytecode.
+        hash: B256,
+        /// The original, unanalyzed bytecode.
+        code: Bytes,
+    },
+    /// An intermediate node of the account trie.
+    AccountNode {
+        /// Path of the node.
+        path: Nibbles,
+        /// The node.
+        node: BranchNodeCompact,
+    },
+    /// An intermediate node of a storage trie.
+    #[serde(rename_all = "camelCase")]
+    StorageNode {
+        /// Hashed address of the account owning the storage trie.
+        hashed_address: B256,
+        /// Path of the node.
+        path: Nibbles,
+        /// The node.
+        node: BranchNodeCompact,
+    },
+}
+
+/// Exports the hashed state and the state trie at the latest block to the given writer.
+///
+/// Before anything is written, the stored trie is checked against the state root of the latest
+/// header.
+pub fn export_state_snapshot<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    mut writer: impl Write,
+) -> Result<StateSnapshotHeader, StateSnapshotError> {
+    let block_number = provider.last_block_number()?;
+    let header = provider
+        .sealed_header(block_number)?
+        .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+
+    let tx = provider.tx_ref();
+    let computed = StateRoot::from_tx(tx).root().map_err(DatabaseError::from)?;
+    if computed != header.state_root {
+        return Err(StateSnapshotError::StateRootMismatch { expected: header.state_root, computed })
+    }
+
+    let snapshot_header = StateSnapshotHeader {
+        version: STATE_SNAPSHOT_VERSION,
+        block_number,
+        block_hash: header.hash(),
+        state_root: header.state_root,
+    };
+    serde_json::to_writer(&mut writer, &snapshot_header)?;
+    writeln!(writer)?;
+
+    let mut written = 0;
+    let mut write_entry = |entry: StateSnapshotEntry| -> Result<(), StateSnapshotError> {
+        serde_json::to_writer(&mut writer, &entry)?;
+        writeln!(writer)?;
+        written += 1;
+        if written % SOFT_LIMIT_COUNT_ENTRIES_LOG == 0 {
+            info!(target: "reth::cli", written, "Exporting state snapshot");
+        }
+        Ok(())
+    };
+
+    for entry in tx.cursor_read::<tables::HashedAccounts>()?.walk(None)? {
+        let (hashed_address, account) = entry?;
+        write_entry(StateSnapshotEntry::Account { hashed_address, account })?;
+    }
+
+    for entry in tx.cursor_dup_read::<tables::HashedStorages>()?.walk(None)? {
+        let (hashed_address, entry) = entry?;
+        write_entry(StateSnapshotEntry::Storage { hashed_address, entry })?;
+    }
+
+    for entry in tx.cursor_read::<tables::Bytecodes>()?.walk(None)? {
+        let (hash, bytecode) = entry?;
+        write_entry(StateSnapshotEntry::Bytecode { hash, code: bytecode.original_bytes() })?;
+    }
+
+    for entry in tx.cursor_read::<tables::AccountsTrie>()?.walk(None)? {
+        let (path, node) = entry?;
+        write_entry(StateSnapshotEntry::AccountNode { path: path.0, node: node.0 })?;
+    }
+
+    for entry in tx.cursor_dup_read::<tables::StoragesTrie>()?.walk(None)? {
+        let (hashed_address, entry) = entry?;
+        write_entry(StateSnapshotEntry::StorageNode {
+            hashed_address,
+            path: entry.nibbles.0,
+            node: entry.node,
+        })?;
+    }
+
+    writer.flush()?;
+
+    info!(target: "reth::cli", block_number, entries = written, "Exported state snapshot");
+
+    Ok(snapshot_header)
+}
+
+/// Imports a state snapshot from the given reader.
+///
+/// The block of the snapshot must already be present in the database, and the hashed state and
+/// trie tables must be empty. After all entries are written, the state root is recomputed from
+/// the hashed state and checked against both the stored trie and the snapshot header.
+///
+/// Only the hashed state and the trie tables are populated, stage checkpoints are left untouched.
+pub fn import_state_snapshot<DB: Database>(
+    provider_rw: &DatabaseProviderRW<DB>,
+    mut reader: impl BufRead,
+) -> Result<StateSnapshotHeader, StateSnapshotError> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let header: StateSnapshotHeader = serde_json::from_str(&line)?;
+
+    if header.version != STATE_SNAPSHOT_VERSION {
+        return Err(StateSnapshotError::UnsupportedVersion(header.version))
+    }
+
+    let database_hash = provider_rw.block_hash(header.block_number)?;
+    if database_hash != Some(header.block_hash) {
+        return Err(StateSnapshotError::BlockMismatch {
+            snapshot_block: header.block_number,
+            snapshot_hash: header.block_hash,
+            database_hash,
+        })
+    }
+
+    let tx = provider_rw.tx_ref();
+    ensure_empty::<_, tables::HashedAccounts>(tx)?;
+    ensure_empty::<_, tables::HashedStorages>(tx)?;
+    ensure_empty::<_, tables::Bytecodes>(tx)?;
+    ensure_empty::<_, tables::AccountsTrie>(tx)?;
+    ensure_empty::<_, tables::StoragesTrie>(tx)?;
+
+    let mut accounts_cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+    let mut storages_cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    let mut account_trie_cursor = tx.cursor_write::<tables::AccountsTrie>()?;
+    let mut storage_trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+
+    let mut imported = 0;
+    line.clear();
+    while reader.read_line(&mut line)? != 0 {
+        // entries of each table are exported in key order, which allows appending
+        match serde_json::from_str(&line)? {
+            StateSnapshotEntry::Account { hashed_address, account } => {
+                accounts_cursor.append(hashed_address, account)?;
+            }
+            StateSnapshotEntry::Storage { hashed_address, entry } => {
+                storages_cursor.append_dup(hashed_address, entry)?;
+            }
+            StateSnapshotEntry::Bytecode { hash, code } => {
+                tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+            }
+            StateSnapshotEntry::AccountNode { path, node } => {
+                account_trie_cursor.append(StoredNibbles(path), StoredBranchNode(node))?;
+            }
+            StateSnapshotEntry::StorageNode { hashed_address, path, node } => {
+                storage_trie_cursor.append_dup(
+                    hashed_address,
+                    StorageTrieEntry { nibbles: StoredNibblesSubKey(path), node },
+                )?;
+            }
+        }
+
+        imported += 1;
+        if imported % SOFT_LIMIT_COUNT_ENTRIES_LOG == 0 {
+            info!(target: "reth::cli", imported, "Importing state snapshot");
+        }
+        line.clear();
+    }
+
+    trace!(target: "reth::cli", imported, "Imported state snapshot entries, verifying state root");
+
+    // the trie is verified separately from the hashed state, since walking the stored trie
+    // without any changed prefixes only touches the root branch nodes.
+    for computed in [
+        StateRoot::from_tx(tx).root().map_err(DatabaseError::from)?,
+        StateRoot::from_tx(tx)
+            .with_trie_cursor_factory(NoopTrieCursorFactory)
+            .root()
+            .map_err(DatabaseError::from)?,
+    ] {
+        if computed != header.state_root {
+            return Err(StateSnapshotError::StateRootMismatch {
+                expected: header.state_root,
+                computed,
+            })
+        }
+    }
+
+    info!(target: "reth::cli", block_number = header.block_number, entries = imported, "Imported state snapshot");
+
+    Ok(header)
+}
+
+/// Returns an error if the given table contains any entries.
+fn ensure_empty<TX: DbTx, T: Table>(tx: &TX) -> Result<(), StateSnapshotError> {
+    if tx.entries::<T>()? > 0 {
+        return Err(StateSnapshotError::TableNotEmpty(T::NAME))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use reth_chainspec::{Chain, ChainSpec};
+    use reth_primitives::{Address, U256};
+    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    fn chain_spec() -> Arc<ChainSpec> {
+        Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis {
+                alloc: BTreeMap::from([
+                    (
+                        Address::with_last_byte(1),
+                        GenesisAccount { balance: U256::from(1), ..Default::default() },
+                    ),
+                    (
+                        Address::with_last_byte(2),
+                        GenesisAccount {
+                            code: Some(Bytes::from_static(&[0x60, 0x00])),
+                            storage: Some(BTreeMap::from([
+                                (B256::with_last_byte(1), B256::with_last_byte(2)),
+                                (B256::with_last_byte(3), B256::with_last_byte(4)),
+                            ])),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn state_snapshot_roundtrip() {
+        let chain_spec = chain_spec();
+
+        let source = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(source.clone()).unwrap();
+        let provider_rw = source.provider_rw().unwrap();
+        let (_, updates) = StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+        updates.write_to_database(provider_rw.tx_ref()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut snapshot = Vec::new();
+        let exported = export_state_snapshot(&source.provider().unwrap(), &mut snapshot).unwrap();
+        assert_eq!(exported.state_root, chain_spec.genesis_header().state_root);
+
+        let target = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(target.clone()).unwrap();
+        let provider_rw = target.provider_rw().unwrap();
+        provider_rw.tx_ref().clear::<tables::HashedAccounts>().unwrap();
+        provider_rw.tx_ref().clear::<tables::HashedStorages>().unwrap();
+        provider_rw.tx_ref().clear::<tables::Bytecodes>().unwrap();
+
+        let imported = import_state_snapshot(&provider_rw, snapshot.as_slice()).unwrap();
+        assert_eq!(imported, exported);
+    }
+
+    #[test]
+    fn state_snapshot_rejects_tampered_state() {
+        let chain_spec = chain_spec();
+
+        let source = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(source.clone()).unwrap();
+        let provider_rw = source.provider_rw().unwrap();
+        let (_, updates) = StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+        updates.write_to_database(provider_rw.tx_ref()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut snapshot = Vec::new();
+        export_state_snapshot(&source.provider().unwrap(), &mut snapshot).unwrap();
+        let snapshot = String::from_utf8(snapshot)
+            .unwrap()
+            .replace(r#""balance":"0x1""#, r#""balance":"0x2""#);
+
+        let target = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(target.clone()).unwrap();
+        let provider_rw = target.provider_rw().unwrap();
+        provider_rw.tx_ref().clear::<tables::HashedAccounts>().unwrap();
+        provider_rw.tx_ref().clear::<tables::HashedStorages>().unwrap();
+        provider_rw.tx_ref().clear::<tables::Bytecodes>().unwrap();
+
+        assert!(matches!(
+            import_state_snapshot(&provider_rw, snapshot.as_bytes()),
+            Err(StateSnapshotError::StateRootMismatch { .. })
+        ));
+    }
+}
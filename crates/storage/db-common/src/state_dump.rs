@@ -0,0 +1,354 @@
+//! State dumps at historical blocks.
+//!
+//! A state dump contains the plain state (accounts, storage and bytecodes) at a given block, in
+//! the format read by [`init_from_state_dump`](crate::init::init_from_state_dump): the first line
+//! is the state root, all subsequent lines are accounts with their storage root, ordered by
+//! address. Accounts at historical blocks are read through the history indexes, so the dump of a
+//! block is the same on every node.
+
+use crate::init::{GenesisAccountWithAddress, StateRoot};
+use alloy_genesis::GenesisAccount;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    models::storage_sharded_key::StorageShardedKey,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_primitives::{Address, BlockNumber, B256, U256};
+use reth_provider::{
+    providers::{HistoricalStateProviderRef, LowestAvailableBlocks},
+    AccountReader, HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader,
+    StateProvider, StateRootProvider,
+};
+use reth_prune_types::PruneSegment;
+use reth_trie::root::storage_root_unhashed;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+};
+use tracing::info;
+
+/// Number of dumped accounts after which to log progress.
+const SOFT_LIMIT_COUNT_ACCOUNTS_LOG: usize = 1_000_000;
+
+/// State dump error type.
+#[derive(Debug, thiserror::Error)]
+pub enum StateDumpError {
+    /// The state root computed at the block doesn't match the one in the header.
+    #[error(
+        "state root mismatch at block {block_number}, header: {expected}, computed: {computed}"
+    )]
+    StateRootMismatch {
+        /// Block number of the dump.
+        block_number: BlockNumber,
+        /// State root of the header.
+        expected: B256,
+        /// Computed state root.
+        computed: B256,
+    },
+    /// Dump entry could not be serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<DatabaseError> for StateDumpError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Provider(ProviderError::Database(error))
+    }
+}
+
+/// Summary of a written state dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateDumpSummary {
+    /// State root of the dumped state.
+    pub state_root: B256,
+    /// Number of dumped accounts.
+    pub accounts: usize,
+    /// Number of dumped non-zero storage slots.
+    pub storage_slots: usize,
+}
+
+/// Dumps the state at the given block to the writer.
+///
+/// Before anything is written, the state root at the block is computed and checked against its
+/// header. Fails if the history of the block was pruned.
+pub fn dump_state_at<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    block_number: BlockNumber,
+    writer: impl Write,
+) -> Result<StateDumpSummary, StateDumpError> {
+    // the state root and the dump are read from the same transaction, so they match
+    let provider = factory.provider()?;
+    let header = provider
+        .header_by_number(block_number)?
+        .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+
+    let lowest_available_blocks = LowestAvailableBlocks {
+        account_history_block_number: provider
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|block_number| block_number + 1),
+        storage_history_block_number: provider
+            .get_prune_checkpoint(PruneSegment::StorageHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|block_number| block_number + 1),
+    };
+    // +1 as the changesets of the block are the ones applied after it
+    let state = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        provider.tx_ref(),
+        block_number + 1,
+        lowest_available_blocks,
+        provider.static_file_provider().clone(),
+    );
+
+    let computed = state.state_root(&Default::default())?;
+    if computed != header.state_root {
+        return Err(StateDumpError::StateRootMismatch {
+            block_number,
+            expected: header.state_root,
+            computed,
+        })
+    }
+
+    let summary = write_state_at(provider.tx_ref(), &state, header.state_root, writer)?;
+
+    info!(target: "reth::cli",
+        block_number,
+        state_root = %summary.state_root,
+        accounts = summary.accounts,
+        storage_slots = summary.storage_slots,
+        "Dumped state"
+    );
+
+    Ok(summary)
+}
+
+/// Writes the state of the historical state provider.
+///
+/// The accounts that exist at the block are either in the plain state, or were changed later and
+/// are in the account history index. Both tables are walked in address order.
+fn write_state_at<TX: DbTx>(
+    tx: &TX,
+    state: &HistoricalStateProviderRef<'_, TX>,
+    state_root: B256,
+    mut writer: impl Write,
+) -> Result<StateDumpSummary, StateDumpError> {
+    serde_json::to_writer(&mut writer, &StateRoot { root: state_root })?;
+    writeln!(writer)?;
+
+    let mut summary = StateDumpSummary { state_root, accounts: 0, storage_slots: 0 };
+    let mut plain_accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut account_history = tx.cursor_read::<tables::AccountsHistory>()?;
+    let mut plain_storage = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut storage_history = tx.cursor_read::<tables::StoragesHistory>()?;
+
+    let mut next_plain = plain_accounts.first()?.map(|(address, _)| address);
+    let mut next_history = account_history.first()?.map(|(key, _)| key.key);
+    loop {
+        let address = match (next_plain, next_history) {
+            (Some(plain), Some(history)) => plain.min(history),
+            (Some(address), None) | (None, Some(address)) => address,
+            (None, None) => break,
+        };
+        if next_plain == Some(address) {
+            next_plain = plain_accounts.next()?.map(|(address, _)| address);
+        }
+        // skip the remaining history shards of the account
+        while next_history == Some(address) {
+            next_history = account_history.next()?.map(|(key, _)| key.key);
+        }
+
+        let Some(account) = state.basic_account(address)? else { continue };
+
+        let mut storage = BTreeMap::new();
+        for slot in storage_slots(&mut plain_storage, &mut storage_history, address)? {
+            if let Some(value) = state.storage(address, slot)?.filter(|value| !value.is_zero()) {
+                storage.insert(slot, B256::from(value));
+            }
+        }
+        summary.storage_slots += storage.len();
+
+        let code = account
+            .bytecode_hash
+            .map(|hash| tx.get::<tables::Bytecodes>(hash))
+            .transpose()?
+            .flatten()
+            .map(|bytecode| bytecode.original_bytes());
+        let storage_root = storage_root_unhashed(
+            storage.iter().map(|(slot, value)| (*slot, U256::from_be_bytes(value.0))),
+        );
+        let genesis_account = GenesisAccount {
+            nonce: Some(account.nonce),
+            balance: account.balance,
+            code,
+            storage: (!storage.is_empty()).then_some(storage),
+            ..Default::default()
+        };
+        serde_json::to_writer(
+            &mut writer,
+            &GenesisAccountWithAddress {
+                genesis_account,
+                address,
+                storage_root: Some(storage_root),
+            },
+        )?;
+        writeln!(writer)?;
+
+        summary.accounts += 1;
+        if summary.accounts % SOFT_LIMIT_COUNT_ACCOUNTS_LOG == 0 {
+            info!(target: "reth::cli", accounts = summary.accounts, "Dumping state");
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(summary)
+}
+
+/// Returns the storage slots of the account that may be set at the block: the slots in the plain
+/// state and the slots that were changed later, which are in the storage history index.
+fn storage_slots(
+    plain_storage: &mut impl DbDupCursorRO<tables::PlainStorageState>,
+    storage_history: &mut impl DbCursorRO<tables::StoragesHistory>,
+    address: Address,
+) -> Result<BTreeSet<B256>, DatabaseError> {
+    let mut slots = BTreeSet::new();
+    for entry in plain_storage.walk_dup(Some(address), None)? {
+        let (key, entry) = entry?;
+        if key != address {
+            break
+        }
+        slots.insert(entry.key);
+    }
+    for entry in storage_history.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))? {
+        let (key, _) = entry?;
+        if key.address != address {
+            break
+        }
+        slots.insert(key.sharded_key.key);
+    }
+    Ok(slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use alloy_genesis::Genesis;
+    use reth_chainspec::{Chain, ChainSpec};
+    use reth_db::BlockNumberList;
+    use reth_db_api::{
+        cursor::DbDupCursorRW,
+        models::{AccountBeforeTx, BlockNumberAddress, ShardedKey},
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{Account, Bytes, StorageEntry};
+    use reth_provider::{
+        providers::StaticFileProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    };
+    use std::sync::Arc;
+
+    fn chain_spec() -> Arc<ChainSpec> {
+        Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis {
+                alloc: BTreeMap::from([
+                    (
+                        Address::with_last_byte(1),
+                        GenesisAccount { balance: U256::from(1), ..Default::default() },
+                    ),
+                    (
+                        Address::with_last_byte(2),
+                        GenesisAccount {
+                            code: Some(Bytes::from_static(&[0x60, 0x00])),
+                            storage: Some(BTreeMap::from([(
+                                B256::with_last_byte(1),
+                                B256::with_last_byte(2),
+                            )])),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn dump<TX: DbTx>(
+        tx: &TX,
+        static_file_provider: &StaticFileProvider,
+        block_number: BlockNumber,
+    ) -> Vec<String> {
+        let state =
+            HistoricalStateProviderRef::new(tx, block_number + 1, static_file_provider.clone());
+        let mut dump = Vec::new();
+        write_state_at(tx, &state, B256::ZERO, &mut dump).unwrap();
+        String::from_utf8(dump).unwrap().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn dump_reverts_later_changes() {
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec());
+        init_genesis(factory.clone()).unwrap();
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        let static_files = provider_rw.static_file_provider();
+        let genesis = dump(tx, static_files, 0);
+        assert_eq!(genesis.len(), 3);
+
+        // block 1 changes the balance of the first account, creates a third account and clears
+        // the storage slot of the second account
+        let account = Account { balance: U256::from(1), ..Default::default() };
+        tx.put::<tables::PlainAccountState>(
+            Address::with_last_byte(1),
+            Account { balance: U256::from(2), ..account },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(Address::with_last_byte(3), account).unwrap();
+        let mut storage_cursor = tx.cursor_dup_write::<tables::PlainStorageState>().unwrap();
+        storage_cursor.seek_exact(Address::with_last_byte(2)).unwrap();
+        storage_cursor.delete_current_duplicates().unwrap();
+        let mut changesets = tx.cursor_dup_write::<tables::AccountChangeSets>().unwrap();
+        changesets
+            .append_dup(
+                1,
+                AccountBeforeTx { address: Address::with_last_byte(1), info: Some(account) },
+            )
+            .unwrap();
+        changesets
+            .append_dup(1, AccountBeforeTx { address: Address::with_last_byte(3), info: None })
+            .unwrap();
+        tx.cursor_dup_write::<tables::StorageChangeSets>()
+            .unwrap()
+            .append_dup(
+                BlockNumberAddress((1, Address::with_last_byte(2))),
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(2) },
+            )
+            .unwrap();
+        let history = BlockNumberList::new([1]).unwrap();
+        for address in [Address::with_last_byte(1), Address::with_last_byte(3)] {
+            tx.put::<tables::AccountsHistory>(ShardedKey::new(address, u64::MAX), history.clone())
+                .unwrap();
+        }
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::new(Address::with_last_byte(2), B256::with_last_byte(1), u64::MAX),
+            history,
+        )
+        .unwrap();
+
+        assert_eq!(dump(tx, static_files, 0), genesis);
+        let latest = dump(tx, static_files, 1);
+        assert_eq!(latest.len(), 4);
+        assert!(latest[1].contains(r#""balance":"0x2""#));
+        assert!(!latest[2].contains(r#""storage":"#));
+    }
+}
@@ -0,0 +1,79 @@
+#![allow(missing_docs)]
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use pprof::criterion::{Output, PProfProfiler};
+use reth_db::tables::*;
+use reth_db_api::{
+    table::{Decompress, DecompressRef},
+    transaction::DbTx,
+};
+use reth_primitives::TransactionSignedNoHash;
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = zero_copy
+}
+criterion_main!(benches);
+
+pub fn zero_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tables_zero_copy");
+    group.measurement_time(std::time::Duration::from_millis(200));
+    group.warm_up_time(std::time::Duration::from_millis(200));
+
+    measure_table_get_ref::<PlainAccountState>(&mut group);
+    measure_table_get_ref::<HashedAccounts>(&mut group);
+    measure_table_get_ref::<Transactions>(&mut group);
+
+    // Reading only the signature of transactions, e.g. to filter them, skips decoding the rest
+    let input = &load_vectors::<Transactions>();
+    group.bench_function("Transactions.DecompressSignature", |b| {
+        b.iter(|| {
+            for (_, _, _, value) in input {
+                black_box(TransactionSignedNoHash::decompress(value.as_ref()).unwrap().signature);
+            }
+        })
+    });
+    group.bench_function("Transactions.DecompressRefSignature", |b| {
+        b.iter(|| {
+            for (_, _, _, value) in input {
+                black_box(
+                    TransactionSignedNoHash::decompress_ref(value.as_ref()).unwrap().signature(),
+                );
+            }
+        })
+    });
+}
+
+/// Measures `RandomRead` using `tx.get` and `tx.get_ref`.
+fn measure_table_get_ref<T>(group: &mut BenchmarkGroup<'_, WallTime>)
+where
+    T: Table,
+    T::Key: Default + Clone + for<'de> serde::Deserialize<'de>,
+    T::Value: Default + Clone + for<'de> serde::Deserialize<'de> + for<'a> DecompressRef<'a>,
+{
+    let input = &load_vectors::<T>();
+    let bench_db_path = Path::new(BENCH_DB_PATH);
+    let db = set_up_db::<T>(bench_db_path, input);
+
+    group.bench_function(format!("{}.RandomRead", T::NAME), |b| {
+        b.iter(|| {
+            let tx = db.tx().expect("tx");
+            for index in RANDOM_INDEXES {
+                black_box(tx.get::<T>(input.get(index).unwrap().0.clone()).unwrap());
+            }
+        })
+    });
+
+    group.bench_function(format!("{}.RandomReadRef", T::NAME), |b| {
+        b.iter(|| {
+            let tx = db.tx().expect("tx");
+            for index in RANDOM_INDEXES {
+                black_box(tx.get_ref::<T>(input.get(index).unwrap().0.clone()).unwrap());
+            }
+        })
+    });
+}
+
+include!("./utils.rs");
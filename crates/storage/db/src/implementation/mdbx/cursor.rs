@@ -5,6 +5,8 @@ use crate::{
     tables::utils::*,
     DatabaseError,
 };
+#[cfg(feature = "zero-copy")]
+use reth_db_api::table::DecompressRef;
 use reth_db_api::{
     common::{PairResult, ValueOnlyResult},
     cursor::{
@@ -233,6 +235,53 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     }
 }
 
+#[cfg(feature = "zero-copy")]
+impl<T: Table> Cursor<RO, T>
+where
+    T::Value: for<'a> DecompressRef<'a>,
+{
+    /// Seeks to the key and returns its value without copying it, borrowing it from the database
+    /// instead.
+    ///
+    /// This disables the timeout of the transaction of the cursor, see
+    /// [`Tx::get_ref`](super::tx::Tx::get_ref).
+    pub fn seek_exact_ref(
+        &mut self,
+        key: T::Key,
+    ) -> Result<Option<<T::Value as DecompressRef<'_>>::Ref>, DatabaseError> {
+        self.inner.txn().disable_timeout();
+        self.inner
+            .set(key.encode().as_ref())
+            .map_err(|e| DatabaseError::Read(e.into()))?
+            .map(decode_ref::<T>)
+            .transpose()
+    }
+}
+
+#[cfg(feature = "zero-copy")]
+impl<T: DupSort> Cursor<RO, T>
+where
+    T::Value: for<'a> DecompressRef<'a>,
+{
+    /// Seeks to the first value of the key that is greater than or equal to the subkey, and
+    /// returns it without copying it, borrowing it from the database instead.
+    ///
+    /// This disables the timeout of the transaction of the cursor, see
+    /// [`Tx::get_ref`](super::tx::Tx::get_ref).
+    pub fn seek_by_key_subkey_ref(
+        &mut self,
+        key: T::Key,
+        subkey: T::SubKey,
+    ) -> Result<Option<<T::Value as DecompressRef<'_>>::Ref>, DatabaseError> {
+        self.inner.txn().disable_timeout();
+        self.inner
+            .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+            .map_err(|e| DatabaseError::Read(e.into()))?
+            .map(decode_ref::<T>)
+            .transpose()
+    }
+}
+
 impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     /// Database operation that will update an existing row if a specified value already
     /// exists in a table, and insert a new row if the specified value doesn't already exist
@@ -539,6 +539,37 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[cfg(feature = "zero-copy")]
+    #[test]
+    fn db_get_ref() {
+        use crate::{RawKey, RawTable};
+        use reth_db_api::table::Compress;
+
+        let env = create_test_db(DatabaseEnvKind::RW);
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+        let entry = StorageEntry { key: B256::with_last_byte(2), value: U256::from(3) };
+
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<PlainAccountState>(address, account).expect(ERROR_PUT);
+        tx.put::<PlainStorageState>(address, entry).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get_ref::<PlainAccountState>(address).expect(ERROR_GET), Some(account));
+        assert_eq!(tx.get_ref::<PlainAccountState>(Address::ZERO).expect(ERROR_GET), None);
+
+        let raw = tx.get_ref::<RawTable<PlainAccountState>>(RawKey::new(address)).expect(ERROR_GET);
+        assert_eq!(raw, Some(account.compress().as_slice()));
+
+        let mut cursor = tx.new_cursor::<PlainStorageState>().unwrap();
+        assert_eq!(
+            cursor.seek_by_key_subkey_ref(address, B256::ZERO).expect(ERROR_GET),
+            Some(entry)
+        );
+        assert_eq!(cursor.seek_exact_ref(address).expect(ERROR_GET), Some(entry));
+    }
+
     #[test]
     fn db_dup_cursor_delete_first() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
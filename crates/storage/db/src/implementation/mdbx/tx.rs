@@ -1,15 +1,21 @@
 //! Transaction wrapper for libmdbx-sys.
 
 use super::cursor::Cursor;
+#[cfg(feature = "zero-copy")]
+use crate::tables::utils::decode_ref;
 use crate::{
     metrics::{DatabaseEnvMetrics, Operation, TransactionMode, TransactionOutcome},
     tables::utils::decode_one,
     DatabaseError,
 };
+#[cfg(feature = "zero-copy")]
+use reth_db_api::table::DecompressRef;
 use reth_db_api::{
     table::{Compress, DupSort, Encode, Table, TableImporter},
     transaction::{DbTx, DbTxMut},
 };
+#[cfg(feature = "zero-copy")]
+use reth_libmdbx::RO;
 use reth_libmdbx::{ffi::MDBX_dbi, CommitLatency, Transaction, TransactionKind, WriteFlags, RW};
 use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
 use reth_tracing::tracing::{debug, trace, warn};
@@ -163,6 +169,34 @@ impl<K: TransactionKind> Tx<K> {
     }
 }
 
+#[cfg(feature = "zero-copy")]
+impl Tx<RO> {
+    /// Gets a value by key without copying it, borrowing it from the database instead.
+    ///
+    /// Only available for read-only transactions, since their pages are not modified while the
+    /// transaction is open.
+    ///
+    /// This disables the timeout of the transaction for the rest of its lifetime, see
+    /// [`DbTx::disable_long_read_transaction_safety`]. A timed out transaction is reset, after
+    /// which its pages can be reused by writers while the value is still borrowed.
+    pub fn get_ref<T>(
+        &self,
+        key: T::Key,
+    ) -> Result<Option<<T::Value as DecompressRef<'_>>::Ref>, DatabaseError>
+    where
+        T: Table,
+        T::Value: for<'a> DecompressRef<'a>,
+    {
+        self.inner.disable_timeout();
+        self.execute_with_operation_metric::<T, _>(Operation::Get, None, |tx| {
+            tx.get(self.get_dbi::<T>()?, key.encode().as_ref())
+                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map(decode_ref::<T>)
+                .transpose()
+        })
+    }
+}
+
 #[derive(Debug)]
 struct MetricsHandler<K: TransactionKind> {
     /// Cached internal transaction ID provided by libmdbx.
@@ -1,8 +1,14 @@
-use super::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo};
+use super::{
+    decode_receipt,
+    mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo},
+};
 use derive_more::{Deref, DerefMut};
 use reth_db_api::table::Decompress;
 use reth_nippy_jar::{DataReader, NippyJar, NippyJarCursor};
-use reth_primitives::{static_file::SegmentHeader, B256};
+use reth_primitives::{
+    static_file::{ReceiptsLayout, SegmentHeader},
+    Receipt, TxNumber, B256,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::sync::Arc;
 
@@ -68,6 +74,17 @@ impl<'a> StaticFileCursor<'a> {
         }
     }
 
+    /// Gets a receipt from a receipts static file, in any layout.
+    pub fn get_receipt(&mut self, num: TxNumber) -> ColumnResult<Receipt> {
+        // selects all columns of either layout
+        let row = self.get(num.into(), ReceiptsLayout::Columns.mask())?;
+
+        match row {
+            Some(row) => Ok(Some(decode_receipt(&row)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Gets two column values from a row.
     pub fn get_two<M: ColumnSelectorTwo>(
         &mut self,
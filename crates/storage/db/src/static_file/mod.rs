@@ -18,6 +18,9 @@ use reth_primitives::{
 
 mod masks;
 
+mod receipts;
+pub use receipts::{decode_receipt, encode_receipt_columns};
+
 /// Alias type for a map of [`StaticFileSegment`] and sorted lists of existing static file ranges.
 type SortedStaticFiles =
     HashMap<StaticFileSegment, Vec<(SegmentRangeInclusive, Option<SegmentRangeInclusive>)>>;
@@ -0,0 +1,135 @@
+//! Encoding of receipts in the layouts of receipts static files.
+//!
+//! In [`ReceiptsLayout::Columns`], a receipt is split into four columns:
+//!
+//! 1. The receipt without its logs, in its database encoding.
+//! 2. The addresses of the logs, 20 bytes each.
+//! 3. The topics of the logs, as the number of topics of a log in one byte followed by its topics.
+//! 4. The data of the logs, as the length of the data of a log in four big-endian bytes followed by
+//!    its data.
+
+use reth_db_api::table::{Compress, Decompress};
+use reth_primitives::{static_file::ReceiptsLayout, Address, Bytes, Log, Receipt, B256};
+use reth_storage_errors::db::DatabaseError;
+
+/// Encodes the receipt into the columns of [`ReceiptsLayout::Columns`].
+pub fn encode_receipt_columns(mut receipt: Receipt) -> [Vec<u8>; 4] {
+    let logs = std::mem::take(&mut receipt.logs);
+
+    let mut addresses = Vec::with_capacity(logs.len() * Address::len_bytes());
+    let mut topics = Vec::new();
+    let mut data = Vec::new();
+    for log in logs {
+        addresses.extend_from_slice(log.address.as_slice());
+
+        topics.push(log.topics().len() as u8);
+        for topic in log.topics() {
+            topics.extend_from_slice(topic.as_slice());
+        }
+
+        data.extend_from_slice(&(log.data.data.len() as u32).to_be_bytes());
+        data.extend_from_slice(&log.data.data);
+    }
+
+    [receipt.compress(), addresses, topics, data]
+}
+
+/// Decodes a receipt from the columns of a row of a receipts static file, in either layout.
+///
+/// The layout is detected from the number of columns.
+pub fn decode_receipt(row: &[&[u8]]) -> Result<Receipt, DatabaseError> {
+    match ReceiptsLayout::from_columns(row.len()) {
+        Some(ReceiptsLayout::Rows) => Receipt::decompress(row[0]),
+        Some(ReceiptsLayout::Columns) => decode_receipt_columns(row[0], row[1], row[2], row[3]),
+        None => Err(DatabaseError::Decode),
+    }
+}
+
+fn decode_receipt_columns(
+    receipt: &[u8],
+    addresses: &[u8],
+    mut topics: &[u8],
+    mut data: &[u8],
+) -> Result<Receipt, DatabaseError> {
+    let mut receipt = Receipt::decompress(receipt)?;
+    if addresses.len() % Address::len_bytes() != 0 {
+        return Err(DatabaseError::Decode)
+    }
+
+    receipt.logs = Vec::with_capacity(addresses.len() / Address::len_bytes());
+    for address in addresses.chunks_exact(Address::len_bytes()) {
+        let (&topics_len, rest) = topics.split_first().ok_or(DatabaseError::Decode)?;
+        let topics_size = topics_len as usize * B256::len_bytes();
+        if rest.len() < topics_size {
+            return Err(DatabaseError::Decode)
+        }
+        let (log_topics, rest) = rest.split_at(topics_size);
+        topics = rest;
+
+        if data.len() < 4 {
+            return Err(DatabaseError::Decode)
+        }
+        let (data_len, rest) = data.split_at(4);
+        let data_len = u32::from_be_bytes(data_len.try_into().expect("4 bytes")) as usize;
+        if rest.len() < data_len {
+            return Err(DatabaseError::Decode)
+        }
+        let (log_data, rest) = rest.split_at(data_len);
+        data = rest;
+
+        receipt.logs.push(Log::new_unchecked(
+            Address::from_slice(address),
+            log_topics.chunks_exact(B256::len_bytes()).map(B256::from_slice).collect(),
+            Bytes::copy_from_slice(log_data),
+        ));
+    }
+
+    if !topics.is_empty() || !data.is_empty() {
+        return Err(DatabaseError::Decode)
+    }
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::TxType;
+
+    #[test]
+    fn receipt_columns_roundtrip() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![
+                Log::new_unchecked(
+                    Address::with_last_byte(1),
+                    vec![B256::with_last_byte(2), B256::with_last_byte(3)],
+                    Bytes::from_static(&[4, 5, 6]),
+                ),
+                Log::new_unchecked(Address::with_last_byte(7), vec![], Bytes::new()),
+            ],
+            ..Default::default()
+        };
+
+        let columns = encode_receipt_columns(receipt.clone());
+        let row = columns.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let decoded = decode_receipt(&row).unwrap();
+        assert_eq!(decoded, receipt);
+
+        // receipts are exported unchanged from the columnar layout
+        let mut encoded = Vec::new();
+        decoded.encode(&mut encoded);
+        let mut expected = Vec::new();
+        receipt.encode(&mut expected);
+        assert_eq!(encoded, expected);
+
+        let rows = receipt.clone().compress();
+        assert_eq!(decode_receipt(&[rows.as_slice()]).unwrap(), receipt);
+
+        // truncated log data
+        let data = &columns[3][..columns[3].len() - 1];
+        assert!(decode_receipt(&[&columns[0], &columns[1], &columns[2], data]).is_err());
+    }
+}
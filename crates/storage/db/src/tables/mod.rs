@@ -24,7 +24,7 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         client_version::ClientVersion,
         storage_sharded_key::StorageShardedKey,
-        CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+        CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals, StoredReorg,
     },
     table::{Decode, DupSort, Encode, Table},
 };
@@ -408,6 +408,9 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the reorgs of the canonical chain, indexed by an increasing sequence number.
+    table ReorgJournal<Key = u64, Value = StoredReorg>;
 }
 
 /// Keys for the `ChainState` table.
@@ -1,4 +1,6 @@
 use crate::DatabaseError;
+#[cfg(feature = "zero-copy")]
+use reth_db_api::table::DecompressRef;
 use reth_db_api::table::{Compress, Decode, Decompress, DupSort, Encode, Key, Table, Value};
 use serde::{Deserialize, Serialize};
 
@@ -176,3 +178,12 @@ impl<V: Value> Decompress for RawValue<V> {
         Ok(Self { value, _phantom: std::marker::PhantomData })
     }
 }
+
+#[cfg(feature = "zero-copy")]
+impl<'a, V: Value> DecompressRef<'a> for RawValue<V> {
+    type Ref = &'a [u8];
+
+    fn decompress_ref(value: &'a [u8]) -> Result<Self::Ref, DatabaseError> {
+        Ok(value)
+    }
+}
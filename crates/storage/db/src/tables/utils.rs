@@ -1,6 +1,8 @@
 //! Small database table utilities and helper functions.
 
 use crate::DatabaseError;
+#[cfg(feature = "zero-copy")]
+use reth_db_api::table::DecompressRef;
 use reth_db_api::table::{Decode, Decompress, Table, TableRow};
 use std::borrow::Cow;
 
@@ -38,6 +40,21 @@ where
     })
 }
 
+/// Helper function to decode a value of a read-only transaction, borrowing from it.
+#[cfg(feature = "zero-copy")]
+pub(crate) fn decode_ref<'a, T>(
+    value: Cow<'a, [u8]>,
+) -> Result<<T::Value as DecompressRef<'a>>::Ref, DatabaseError>
+where
+    T: Table,
+    T::Value: DecompressRef<'a>,
+{
+    match value {
+        Cow::Borrowed(v) => DecompressRef::decompress_ref(v),
+        Cow::Owned(_) => unreachable!("values of read-only transactions are always borrowed"),
+    }
+}
+
 /// Helper function to decode a value. It can be a key or subkey.
 pub(crate) fn decode_one<T>(value: Cow<'_, [u8]>) -> Result<T::Value, DatabaseError>
 where
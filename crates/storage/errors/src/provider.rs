@@ -114,6 +114,9 @@ pub enum ProviderError {
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
+    /// The database is closing and doesn't accept new writes.
+    #[error("database is closing")]
+    DatabaseClosing,
     /// Static File is not found at specified path.
     #[cfg(feature = "std")]
     #[error("not able to find {0} static file at {1}")]
@@ -50,6 +50,11 @@ where
         }
     }
 
+    /// Returns the transaction the cursor belongs to.
+    pub const fn txn(&self) -> &Transaction<K> {
+        &self.txn
+    }
+
     /// Returns a raw pointer to the underlying MDBX cursor.
     ///
     /// The caller **must** ensure that the pointer is not used after the
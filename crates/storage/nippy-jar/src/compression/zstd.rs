@@ -67,7 +67,7 @@ impl Zstd {
     }
 
     /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -185,7 +185,6 @@ impl Compression for Zstd {
         matches!(self.state, ZstdState::Ready)
     }
 
-    #[cfg(test)]
     /// If using it with dictionaries, prepares a dictionary for each column.
     fn prepare_compression(
         &mut self,
@@ -273,7 +272,6 @@ impl<'a> std::fmt::Debug for ZstdDictionaries<'a> {
 }
 
 impl<'a> ZstdDictionaries<'a> {
-    #[cfg(test)]
     /// Creates [`ZstdDictionaries`].
     pub(crate) fn new(raw: Vec<RawDictionary>) -> Self {
         Self(raw.into_iter().map(ZstdDictionary::Raw).collect())
@@ -281,11 +279,7 @@ impl<'a> ZstdDictionaries<'a> {
 
     /// Loads a list [`RawDictionary`] into a list of [`ZstdDictionary::Loaded`].
     pub(crate) fn load(raw: Vec<RawDictionary>) -> Self {
-        Self(
-            raw.into_iter()
-                .map(|dict| ZstdDictionary::Loaded(DecoderDictionary::copy(&dict)))
-                .collect(),
-        )
+        Self(raw.into_iter().map(ZstdDictionary::load).collect())
     }
 
     /// Creates a list of decompressors from a list of [`ZstdDictionary::Loaded`].
@@ -300,8 +294,8 @@ impl<'a> ZstdDictionaries<'a> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Creates a list of compressors from the raw dictionaries.
+    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
             .flat_map(|dict| {
@@ -315,18 +309,24 @@ impl<'a> ZstdDictionaries<'a> {
 
 /// A Zstd dictionary. It's created and serialized with [`ZstdDictionary::Raw`], and deserialized as
 /// [`ZstdDictionary::Loaded`].
+///
+/// A loaded dictionary keeps its raw form, so that the jar can be serialized again and new values
+/// can still be compressed with it, e.g. when appending to a reopened jar.
 pub(crate) enum ZstdDictionary<'a> {
-    #[allow(dead_code)]
     Raw(RawDictionary),
-    Loaded(DecoderDictionary<'a>),
+    Loaded(DecoderDictionary<'a>, RawDictionary),
 }
 
 impl<'a> ZstdDictionary<'a> {
-    /// Returns a reference to the expected `RawDictionary`
+    /// Loads a [`RawDictionary`] into a [`ZstdDictionary::Loaded`].
+    pub(crate) fn load(raw: RawDictionary) -> Self {
+        Self::Loaded(DecoderDictionary::copy(&raw), raw)
+    }
+
+    /// Returns a reference to the `RawDictionary`
     pub(crate) const fn raw(&self) -> Option<&RawDictionary> {
         match self {
-            ZstdDictionary::Raw(dict) => Some(dict),
-            ZstdDictionary::Loaded(_) => None,
+            ZstdDictionary::Raw(dict) | ZstdDictionary::Loaded(_, dict) => Some(dict),
         }
     }
 
@@ -334,7 +334,7 @@ impl<'a> ZstdDictionary<'a> {
     pub(crate) const fn loaded(&self) -> Option<&DecoderDictionary<'_>> {
         match self {
             ZstdDictionary::Raw(_) => None,
-            ZstdDictionary::Loaded(dict) => Some(dict),
+            ZstdDictionary::Loaded(dict, _) => Some(dict),
         }
     }
 }
@@ -345,7 +345,7 @@ impl<'de, 'a> Deserialize<'de> for ZstdDictionary<'a> {
         D: Deserializer<'de>,
     {
         let dict = RawDictionary::deserialize(deserializer)?;
-        Ok(Self::Loaded(DecoderDictionary::copy(&dict)))
+        Ok(Self::load(dict))
     }
 }
 
@@ -355,8 +355,7 @@ impl<'a> Serialize for ZstdDictionary<'a> {
         S: Serializer,
     {
         match self {
-            ZstdDictionary::Raw(r) => r.serialize(serializer),
-            ZstdDictionary::Loaded(_) => unreachable!(),
+            ZstdDictionary::Raw(r) | ZstdDictionary::Loaded(_, r) => r.serialize(serializer),
         }
     }
 }
@@ -364,9 +363,6 @@ impl<'a> Serialize for ZstdDictionary<'a> {
 #[cfg(test)]
 impl<'a> PartialEq for ZstdDictionary<'a> {
     fn eq(&self, other: &Self) -> bool {
-        if let (Self::Raw(a), Self::Raw(b)) = (self, &other) {
-            return a == b
-        }
-        unimplemented!("`DecoderDictionary` can't be compared. So comparison should be done after decompressing a value.");
+        self.raw() == other.raw()
     }
 }
@@ -28,9 +28,7 @@ pub mod filter;
 use filter::{Cuckoo, InclusionFilter, InclusionFilters};
 
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 pub mod phf;
 pub use phf::PHFKey;
@@ -43,13 +41,15 @@ mod cursor;
 pub use cursor::NippyJarCursor;
 
 mod writer;
-pub use writer::{ConsistencyFailStrategy, NippyJarWriter};
+pub use writer::{CompressionStats, ConsistencyFailStrategy, NippyJarWriter};
 
 const NIPPY_JAR_VERSION: usize = 1;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
-const OFFSETS_FILE_EXTENSION: &str = "off";
-const CONFIG_FILE_EXTENSION: &str = "conf";
+/// Extension of the offsets file of a [`NippyJar`].
+pub const OFFSETS_FILE_EXTENSION: &str = "off";
+/// Extension of the configuration file of a [`NippyJar`].
+pub const CONFIG_FILE_EXTENSION: &str = "conf";
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -356,9 +356,10 @@ impl<H: NippyJarHeader> PerfectHashingFunction for NippyJar<H> {
     }
 }
 
-#[cfg(test)]
 impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
+    ///
+    /// For zstd with dictionaries, this trains one dictionary per column on the given samples.
     pub fn prepare_compression(
         &mut self,
         columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
@@ -370,7 +371,10 @@ impl<H: NippyJarHeader> NippyJar<H> {
         }
         Ok(())
     }
+}
 
+#[cfg(test)]
+impl<H: NippyJarHeader> NippyJar<H> {
     /// Prepares beforehand the offsets index for querying rows based on `values` (eg. transaction
     /// hash). Expects `values` to be sorted in the same way as the data that is going to be
     /// later on inserted.
@@ -791,6 +795,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zstd_with_dictionaries_reopen() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy =
+            NippyJar::new_without_header(num_columns, file_path.path()).with_zstd(true, 5000);
+        nippy.prepare_compression(vec![col1.clone(), col2.clone()]).unwrap();
+        nippy
+            .freeze(
+                vec![
+                    clone_with_result(&col1[..1].to_vec()),
+                    clone_with_result(&col2[..1].to_vec()),
+                ],
+                1,
+            )
+            .unwrap();
+
+        // Reopening a jar with loaded dictionaries keeps compressing with them
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut writer = NippyJarWriter::new(loaded_nippy, ConsistencyFailStrategy::Heal).unwrap();
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[1..].to_vec()),
+                    clone_with_result(&col2[1..].to_vec()),
+                ],
+                num_rows - 1,
+            )
+            .unwrap();
+        let stats = writer.take_compression_stats();
+        assert!(stats.compressed_bytes > 0);
+        assert!(stats.uncompressed_bytes > 0);
+        writer.commit().unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded_nippy.rows(), num_rows as usize);
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, num_rows as usize);
+    }
+
     #[test]
     fn test_lz4() {
         let (col1, col2) = test_data(None);
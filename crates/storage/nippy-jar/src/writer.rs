@@ -1,10 +1,14 @@
-use crate::{compression::Compression, ColumnResult, NippyJar, NippyJarError, NippyJarHeader};
+use crate::{
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarError, NippyJarHeader,
+};
 use std::{
     cmp::Ordering,
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 const OFFSET_SIZE_BYTES: u8 = 8;
@@ -33,6 +37,11 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Per column compressors, if the jar uses zstd with dictionaries.
+    dictionary_compressors: Option<DictionaryCompressors>,
+    /// Number of uncompressed and compressed bytes written since the last call to
+    /// [`Self::take_compression_stats`].
+    compression_stats: CompressionStats,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -58,11 +67,20 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         // Makes sure we don't have dangling data and offset files
         jar.freeze_config()?;
 
+        let dictionary_compressors = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => {
+                zstd.compressors()?.map(DictionaryCompressors)
+            }
+            _ => None,
+        };
+
         let mut writer = Self {
             jar,
             data_file: BufWriter::new(data_file),
             offsets_file: BufWriter::new(offsets_file),
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
+            compression_stats: CompressionStats::default(),
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
@@ -105,6 +123,17 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.jar.rows()
     }
 
+    /// Gets the number of columns of the jar.
+    pub const fn columns(&self) -> usize {
+        self.jar.columns()
+    }
+
+    /// Returns the number of uncompressed and compressed bytes written since the last call, and
+    /// resets them.
+    pub fn take_compression_stats(&mut self) -> CompressionStats {
+        std::mem::take(&mut self.compression_stats)
+    }
+
     /// Consumes the writer and returns the associated [`NippyJar`].
     pub fn into_jar(self) -> NippyJar<H> {
         self.jar
@@ -315,7 +344,11 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let len = if let Some(compressors) = &mut self.dictionary_compressors {
+            let compressed = compressors.0[self.column].compress(value)?;
+            self.data_file.write_all(&compressed)?;
+            compressed.len()
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
@@ -325,6 +358,8 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             value.len()
         };
 
+        self.compression_stats.uncompressed_bytes += value.len() as u64;
+        self.compression_stats.compressed_bytes += len as u64;
         self.column += 1;
 
         if self.jar.columns == self.column {
@@ -561,3 +596,29 @@ impl ConsistencyFailStrategy {
         matches!(self, Self::ThrowError)
     }
 }
+
+/// Number of bytes written by a [`NippyJarWriter`], before and after compression.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Number of bytes before compression.
+    pub uncompressed_bytes: u64,
+    /// Number of bytes written to the data file.
+    pub compressed_bytes: u64,
+}
+
+impl CompressionStats {
+    /// Returns the ratio of uncompressed to compressed bytes, or `None` if nothing was written.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.compressed_bytes > 0)
+            .then(|| self.uncompressed_bytes as f64 / self.compressed_bytes as f64)
+    }
+}
+
+/// Per column zstd compressors with a trained dictionary.
+struct DictionaryCompressors(Vec<Compressor<'static>>);
+
+impl std::fmt::Debug for DictionaryCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DictionaryCompressors").field("num", &self.0.len()).finish()
+    }
+}
@@ -0,0 +1,494 @@
+use super::{chain_info::ChainInfoTracker, DatabaseProviderRO, ProviderFactory};
+use crate::{
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    HeaderProvider, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider, TransactionVariant,
+    TransactionsProvider, TreeViewer, WithdrawalsProvider,
+};
+use reth_blockchain_tree_api::BlockchainTreeViewer;
+use reth_chainspec::ChainInfo;
+use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_primitives::{
+    static_file::HighestStaticFiles, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
+    BlockNumHash, BlockNumber, BlockNumberOrTag, BlockWithSenders, Bloom, GotExpected, Header,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+};
+use reth_storage_errors::provider::{ConsistentViewError, ProviderResult};
+use std::{
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
+
+/// Maximum number of attempts to create a [`ConsistentViewProvider`] while the canonical chain is
+/// changing.
+const MAX_VIEW_ATTEMPTS: usize = 3;
+
+/// A provider over a coherent view of the database, the static files and the blockchain tree, that
+/// doesn't change while it's in use.
+///
+/// Multi-step queries that use separate providers for each step can observe different states of
+/// the chain, e.g. resolve a block number with one read transaction and miss its block with the
+/// next one, because it was moved to static files in between. This provider instead serves all
+/// reads from a single read-only transaction, together with snapshots of the static file indices,
+/// the canonical head, safe and finalized blocks, and the pending block of the blockchain tree.
+///
+/// Static files are only appended to before the data is removed from the database, so any block
+/// that is missing from the read-only transaction is already in the static files. The static file
+/// indices are captured after opening the transaction for the same reason.
+///
+/// The view should be short-lived, since it keeps a read-only transaction open. Blocks of the
+/// blockchain tree, including the pending block, are looked up by hash in the live tree.
+pub struct ConsistentViewProvider<DB: Database> {
+    /// Read-only transaction all database and static file reads are served from.
+    provider: DatabaseProviderRO<DB>,
+    /// Highest static file blocks when the view was created.
+    highest_static_files: HighestStaticFiles,
+    /// Canonical head when the view was created.
+    head: SealedHeader,
+    /// Safe block when the view was created.
+    safe: Option<SealedHeader>,
+    /// Finalized block when the view was created.
+    finalized: Option<SealedHeader>,
+    /// Pending block of the blockchain tree when the view was created.
+    pending: Option<BlockNumHash>,
+    /// The blockchain tree, for blocks that are not canonical.
+    tree: Arc<dyn TreeViewer>,
+}
+
+impl<DB: Database> std::fmt::Debug for ConsistentViewProvider<DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsistentViewProvider")
+            .field("highest_static_files", &self.highest_static_files)
+            .field("head", &self.head.num_hash())
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<DB: Database> ConsistentViewProvider<DB> {
+    /// Creates a new view of the current state of the chain.
+    ///
+    /// Fails with [`ConsistentViewError::Inconsistent`] if the canonical head kept changing while
+    /// the view was created.
+    pub(crate) fn new(
+        database: &ProviderFactory<DB>,
+        tree: Arc<dyn TreeViewer>,
+        chain_info: &ChainInfoTracker,
+    ) -> ProviderResult<Self> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            // The in-memory state is captured first, since canonical blocks are written to the
+            // database before they become the canonical head.
+            let head = chain_info.get_canonical_head();
+            let safe = chain_info.get_safe_header();
+            let finalized = chain_info.get_finalized_header();
+            let pending = tree.pending_block_num_hash();
+
+            let provider = database.provider()?;
+            let highest_static_files = provider.static_file_provider().get_highest_static_files();
+
+            // The head might have been unwound in the meantime
+            let tip = provider.block_hash(head.number)?;
+            if tip == Some(head.hash()) {
+                return Ok(Self {
+                    provider,
+                    highest_static_files,
+                    head,
+                    safe,
+                    finalized,
+                    pending,
+                    tree,
+                })
+            }
+
+            if attempt == MAX_VIEW_ATTEMPTS {
+                return Err(ConsistentViewError::Inconsistent {
+                    tip: GotExpected { got: tip, expected: Some(head.hash()) },
+                }
+                .into())
+            }
+        }
+    }
+
+    /// Returns the read-only database provider of the view.
+    pub const fn provider(&self) -> &DatabaseProviderRO<DB> {
+        &self.provider
+    }
+
+    /// Returns the highest static file blocks when the view was created.
+    pub const fn highest_static_files(&self) -> HighestStaticFiles {
+        self.highest_static_files
+    }
+
+    /// Returns the pending block from the blockchain tree.
+    fn pending_block_from_tree(&self) -> Option<SealedBlockWithSenders> {
+        self.pending.and_then(|pending| self.tree.block_with_senders_by_hash(pending.hash))
+    }
+
+    /// Returns the receipts of the pending block from the blockchain tree.
+    fn pending_receipts_from_tree(&self) -> Option<Vec<Receipt>> {
+        self.pending.and_then(|pending| self.tree.receipts_by_block_hash(pending.hash))
+    }
+}
+
+impl<DB: Database> HeaderProvider for ConsistentViewProvider<DB> {
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        self.provider.header(block_hash)
+    }
+
+    fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Header>> {
+        self.provider.header_by_number(num)
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        self.provider.header_td(hash)
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        self.provider.header_td_by_number(number)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.provider.headers_range(range)
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        self.provider.sealed_header(number)
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.provider.sealed_headers_while(range, predicate)
+    }
+}
+
+impl<DB: Database> BlockHashReader for ConsistentViewProvider<DB> {
+    fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        self.provider.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<DB: Database> BlockNumReader for ConsistentViewProvider<DB> {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        Ok(ChainInfo { best_hash: self.head.hash(), best_number: self.head.number })
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self.head.number)
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.provider.last_block_number()
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.block_number(hash)
+    }
+}
+
+impl<DB: Database> BlockIdReader for ConsistentViewProvider<DB> {
+    fn pending_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.pending)
+    }
+
+    fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.safe.as_ref().map(|header| header.num_hash()))
+    }
+
+    fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.finalized.as_ref().map(|header| header.num_hash()))
+    }
+}
+
+impl<DB: Database> BlockReader for ConsistentViewProvider<DB> {
+    fn find_block_by_hash(&self, hash: B256, source: BlockSource) -> ProviderResult<Option<Block>> {
+        let pending = || self.tree.block_by_hash(hash).map(|block| block.unseal());
+
+        Ok(match source {
+            BlockSource::Any => self.provider.block_by_hash(hash)?.or_else(pending),
+            BlockSource::Pending => pending(),
+            BlockSource::Database => self.provider.block_by_hash(hash)?,
+        })
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
+        match id {
+            BlockHashOrNumber::Hash(hash) => self.find_block_by_hash(hash, BlockSource::Any),
+            BlockHashOrNumber::Number(num) => self.provider.block_by_number(num),
+        }
+    }
+
+    fn pending_block(&self) -> ProviderResult<Option<SealedBlock>> {
+        Ok(self.pending_block_from_tree().map(|block| block.block))
+    }
+
+    fn pending_block_with_senders(&self) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        Ok(self.pending_block_from_tree())
+    }
+
+    fn pending_block_and_receipts(&self) -> ProviderResult<Option<(SealedBlock, Vec<Receipt>)>> {
+        Ok(self
+            .pending_block_from_tree()
+            .zip(self.pending_receipts_from_tree())
+            .map(|(block, receipts)| (block.block, receipts)))
+    }
+
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>> {
+        self.provider.ommers(id)
+    }
+
+    fn block_body_indices(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        self.provider.block_body_indices(number)
+    }
+
+    fn block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        self.provider.block_with_senders(id, transaction_kind)
+    }
+
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.provider.sealed_block_with_senders(id, transaction_kind)
+    }
+
+    fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        self.provider.block_range(range)
+    }
+
+    fn block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockWithSenders>> {
+        self.provider.block_with_senders_range(range)
+    }
+
+    fn sealed_block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
+        self.provider.sealed_block_with_senders_range(range)
+    }
+}
+
+impl<DB: Database> TransactionsProvider for ConsistentViewProvider<DB> {
+    fn transaction_id(&self, tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
+        self.provider.transaction_id(tx_hash)
+    }
+
+    fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
+        self.provider.transaction_by_id(id)
+    }
+
+    fn transaction_by_id_no_hash(
+        &self,
+        id: TxNumber,
+    ) -> ProviderResult<Option<TransactionSignedNoHash>> {
+        self.provider.transaction_by_id_no_hash(id)
+    }
+
+    fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
+        self.provider.transaction_by_hash(hash)
+    }
+
+    fn transaction_by_hash_with_meta(
+        &self,
+        tx_hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
+        self.provider.transaction_by_hash_with_meta(tx_hash)
+    }
+
+    fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.transaction_block(id)
+    }
+
+    fn transactions_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<TransactionSigned>>> {
+        self.provider.transactions_by_block(id)
+    }
+
+    fn transactions_by_block_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
+        self.provider.transactions_by_block_range(range)
+    }
+
+    fn transactions_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        self.provider.transactions_by_tx_range(range)
+    }
+
+    fn senders_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Address>> {
+        self.provider.senders_by_tx_range(range)
+    }
+
+    fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        self.provider.transaction_sender(id)
+    }
+
+    fn transaction_index_pruned_to(&self, hash: TxHash) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.transaction_index_pruned_to(hash)
+    }
+}
+
+impl<DB: Database> ReceiptProvider for ConsistentViewProvider<DB> {
+    fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Receipt>> {
+        self.provider.receipt(id)
+    }
+
+    fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
+        self.provider.receipt_by_hash(hash)
+    }
+
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        self.provider.receipts_by_block(block)
+    }
+
+    fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        self.provider.receipts_by_block_range(range)
+    }
+
+    fn receipts_bloom_by_block(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        self.provider.receipts_bloom_by_block(block)
+    }
+
+    fn receipts_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Receipt>> {
+        self.provider.receipts_by_tx_range(range)
+    }
+}
+
+impl<DB: Database> ReceiptProviderIdExt for ConsistentViewProvider<DB> {
+    fn receipts_by_block_id(&self, block: BlockId) -> ProviderResult<Option<Vec<Receipt>>> {
+        match block {
+            BlockId::Hash(rpc_block_hash) => {
+                let hash = rpc_block_hash.block_hash;
+                let mut receipts = self.receipts_by_block(hash.into())?;
+                if receipts.is_none() && !rpc_block_hash.require_canonical.unwrap_or(false) {
+                    receipts = self.tree.receipts_by_block_hash(hash);
+                }
+                Ok(receipts)
+            }
+            BlockId::Number(BlockNumberOrTag::Pending) => Ok(self.pending_receipts_from_tree()),
+            BlockId::Number(num_tag) => match self.convert_block_number(num_tag)? {
+                Some(num) => self.receipts_by_block(num.into()),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+impl<DB: Database> WithdrawalsProvider for ConsistentViewProvider<DB> {
+    fn withdrawals_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        timestamp: u64,
+    ) -> ProviderResult<Option<Withdrawals>> {
+        self.provider.withdrawals_by_block(id, timestamp)
+    }
+
+    fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>> {
+        self.provider.latest_withdrawal()
+    }
+}
+
+impl<DB: Database> RequestsProvider for ConsistentViewProvider<DB> {
+    fn requests_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        timestamp: u64,
+    ) -> ProviderResult<Option<reth_primitives::Requests>> {
+        self.provider.requests_by_block(id, timestamp)
+    }
+}
+
+impl<DB: Database> BlockReaderIdExt for ConsistentViewProvider<DB> {
+    fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Block>> {
+        match id {
+            BlockId::Number(num) => self.block_by_number_or_tag(num),
+            BlockId::Hash(hash) if Some(true) == hash.require_canonical => {
+                // canonical blocks are only stored in the database
+                self.find_block_by_hash(hash.block_hash, BlockSource::Database)
+            }
+            BlockId::Hash(hash) => self.block_by_hash(hash.block_hash),
+        }
+    }
+
+    fn header_by_number_or_tag(&self, id: BlockNumberOrTag) -> ProviderResult<Option<Header>> {
+        Ok(self.sealed_header_by_number_or_tag(id)?.map(|header| header.unseal()))
+    }
+
+    fn sealed_header_by_number_or_tag(
+        &self,
+        id: BlockNumberOrTag,
+    ) -> ProviderResult<Option<SealedHeader>> {
+        match id {
+            BlockNumberOrTag::Latest => Ok(Some(self.head.clone())),
+            BlockNumberOrTag::Finalized => Ok(self.finalized.clone()),
+            BlockNumberOrTag::Safe => Ok(self.safe.clone()),
+            BlockNumberOrTag::Earliest => self.sealed_header(0),
+            BlockNumberOrTag::Pending => {
+                Ok(self.pending.and_then(|pending| self.tree.header_by_hash(pending.hash)))
+            }
+            BlockNumberOrTag::Number(num) => self.sealed_header(num),
+        }
+    }
+
+    fn sealed_header_by_id(&self, id: BlockId) -> ProviderResult<Option<SealedHeader>> {
+        Ok(match id {
+            BlockId::Number(num) => self.sealed_header_by_number_or_tag(num)?,
+            BlockId::Hash(hash) => self.header(&hash.block_hash)?.map(|h| h.seal(hash.block_hash)),
+        })
+    }
+
+    fn header_by_id(&self, id: BlockId) -> ProviderResult<Option<Header>> {
+        Ok(match id {
+            BlockId::Number(num) => self.header_by_number_or_tag(num)?,
+            BlockId::Hash(hash) => self.header(&hash.block_hash)?,
+        })
+    }
+
+    fn ommers_by_id(&self, id: BlockId) -> ProviderResult<Option<Vec<Header>>> {
+        match id {
+            BlockId::Number(num) => self.ommers_by_number_or_tag(num),
+            BlockId::Hash(hash) => self.ommers(BlockHashOrNumber::Hash(hash.block_hash)),
+        }
+    }
+}
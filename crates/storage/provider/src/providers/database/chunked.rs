@@ -0,0 +1,247 @@
+//! Reads spread over multiple short-lived read-only transactions.
+
+use super::{DatabaseProviderRO, ProviderFactory};
+use crate::ProviderError;
+use reth_db::mdbx;
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, DatabaseError,
+};
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    collections::VecDeque,
+    ops::{Bound, RangeBounds},
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
+/// Limits on how long a read-only transaction is used for reads of large ranges.
+///
+/// Long-lived read-only transactions prevent MDBX from reclaiming the pages freed by newer write
+/// transactions, which makes the database file grow. Reads of large ranges are therefore split
+/// into chunks, and a new transaction is opened for the next chunk once the current one has been
+/// open for longer than [`ReadTxLimits::max_tx_duration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadTxLimits {
+    /// Maximum number of entries read per chunk.
+    pub max_chunk_size: usize,
+    /// Duration after which a read-only transaction is no longer used for new chunks.
+    pub max_tx_duration: Duration,
+    /// Maximum number of times a chunk is retried on a new transaction, if its transaction timed
+    /// out.
+    pub max_retries: usize,
+}
+
+impl ReadTxLimits {
+    /// Default maximum number of entries read per chunk.
+    pub const DEFAULT_MAX_CHUNK_SIZE: usize = 10_000;
+    /// Default duration after which a read-only transaction is no longer used for new chunks.
+    pub const DEFAULT_MAX_TX_DURATION: Duration = Duration::from_secs(1);
+    /// Default maximum number of retries of a chunk.
+    pub const DEFAULT_MAX_RETRIES: usize = 3;
+}
+
+impl Default for ReadTxLimits {
+    fn default() -> Self {
+        Self {
+            max_chunk_size: Self::DEFAULT_MAX_CHUNK_SIZE,
+            max_tx_duration: Self::DEFAULT_MAX_TX_DURATION,
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+/// Runs reads on a read-only transaction that is renewed once it exceeds the [`ReadTxLimits`].
+///
+/// Reads that fail because their transaction timed out are retried on a new transaction.
+#[derive(Debug)]
+pub struct ChunkedReader<'a, DB: Database> {
+    factory: &'a ProviderFactory<DB>,
+    limits: ReadTxLimits,
+    /// Current provider and the time its transaction was opened.
+    provider: Option<(DatabaseProviderRO<DB>, Instant)>,
+}
+
+impl<'a, DB: Database> ChunkedReader<'a, DB> {
+    /// Creates a new [`ChunkedReader`] with the limits of the given factory.
+    pub fn new(factory: &'a ProviderFactory<DB>) -> Self {
+        Self { factory, limits: factory.read_tx_limits(), provider: None }
+    }
+
+    /// Returns the limits of the reader.
+    pub const fn limits(&self) -> &ReadTxLimits {
+        &self.limits
+    }
+
+    /// Runs `f` on the current transaction, opening a new one if there is none or if it has been
+    /// open for too long.
+    pub fn read<R>(
+        &mut self,
+        mut f: impl FnMut(&DatabaseProviderRO<DB>) -> ProviderResult<R>,
+    ) -> ProviderResult<R> {
+        let mut retries = 0;
+        loop {
+            match f(self.provider()?) {
+                Err(err) if is_read_tx_timeout(&err) && retries < self.limits.max_retries => {
+                    debug!(target: "providers::db", %err, retries, "Retrying read on a new transaction");
+                    self.release();
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Closes the current transaction, e.g. before waiting on something else.
+    pub fn release(&mut self) {
+        self.provider = None;
+    }
+
+    fn provider(&mut self) -> ProviderResult<&DatabaseProviderRO<DB>> {
+        if self
+            .provider
+            .as_ref()
+            .map_or(true, |(_, opened_at)| opened_at.elapsed() >= self.limits.max_tx_duration)
+        {
+            // Close the expired transaction before opening a new one
+            self.provider = None;
+            self.provider = Some((self.factory.provider()?, Instant::now()));
+        }
+        Ok(&self.provider.as_ref().expect("provider is set").0)
+    }
+}
+
+/// Walks a range of a table in chunks, resuming at the last read key on a new read-only
+/// transaction whenever the [`ChunkedReader`] renews it.
+#[derive(Debug)]
+pub struct ResumableWalker<'a, DB: Database, T: Table> {
+    reader: ChunkedReader<'a, DB>,
+    /// Bound to resume reading at.
+    next: Bound<T::Key>,
+    end: Bound<T::Key>,
+    buffer: VecDeque<(T::Key, T::Value)>,
+    done: bool,
+}
+
+impl<'a, DB: Database, T: Table> ResumableWalker<'a, DB, T> {
+    /// Creates a new [`ResumableWalker`] over the given range of keys.
+    pub fn new(factory: &'a ProviderFactory<DB>, range: impl RangeBounds<T::Key>) -> Self {
+        Self {
+            reader: ChunkedReader::new(factory),
+            next: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Reads the next chunk of entries into the buffer.
+    fn read_chunk(&mut self) -> ProviderResult<()> {
+        let max_chunk_size = self.reader.limits().max_chunk_size;
+        let (next, end) = (&self.next, &self.end);
+
+        let chunk = self.reader.read(|provider| {
+            let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+            // Cursors can't start at an excluded key, so it's skipped after seeking to it
+            let (start, skip) = match next.clone() {
+                Bound::Included(key) => (Bound::Included(key), None),
+                Bound::Excluded(key) => (Bound::Included(key.clone()), Some(key)),
+                Bound::Unbounded => (Bound::Unbounded, None),
+            };
+            cursor
+                .walk_range((start, end.clone()))?
+                .filter(
+                    |entry| !matches!((entry, &skip), (Ok((key, _)), Some(skip)) if key == skip),
+                )
+                .take(max_chunk_size)
+                .collect::<Result<Vec<_>, DatabaseError>>()
+                .map_err(ProviderError::from)
+        })?;
+
+        self.done = chunk.len() < max_chunk_size;
+        if let Some((key, _)) = chunk.last() {
+            self.next = Bound::Excluded(key.clone());
+        }
+        self.buffer.extend(chunk);
+
+        Ok(())
+    }
+}
+
+impl<'a, DB: Database, T: Table> Iterator for ResumableWalker<'a, DB, T> {
+    type Item = ProviderResult<(T::Key, T::Value)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.done {
+            if let Err(err) = self.read_chunk() {
+                self.done = true;
+                return Some(Err(err))
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+/// Returns `true` if the error was caused by a read-only transaction that timed out.
+fn is_read_tx_timeout(err: &ProviderError) -> bool {
+    let ProviderError::Database(err) = err else { return false };
+    let info = match err {
+        DatabaseError::Open(info) |
+        DatabaseError::Read(info) |
+        DatabaseError::InitTx(info) |
+        DatabaseError::InitCursor(info) => info,
+        _ => return false,
+    };
+    info.code == mdbx::Error::ReadTransactionTimeout.to_err_code()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::B256;
+
+    #[test]
+    fn resumable_walker_reads_all_chunks() {
+        let factory = create_test_provider_factory().with_read_tx_limits(ReadTxLimits {
+            max_chunk_size: 3,
+            max_tx_duration: Duration::ZERO,
+            ..Default::default()
+        });
+
+        let provider = factory.provider_rw().unwrap();
+        for number in 0..10 {
+            provider
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let entries = ResumableWalker::<_, tables::CanonicalHeaders>::new(&factory, 2..9)
+            .collect::<ProviderResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            (2..9).map(|number| (number, B256::with_last_byte(number as u8))).collect::<Vec<_>>()
+        );
+
+        let entries = ResumableWalker::<_, tables::CanonicalHeaders>::new(&factory, 7..)
+            .map(|entry| entry.map(|(number, _)| number))
+            .collect::<ProviderResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(entries, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn read_tx_timeout_is_detected() {
+        let timeout = ProviderError::Database(DatabaseError::Open(
+            mdbx::Error::ReadTransactionTimeout.into(),
+        ));
+        assert!(is_read_tx_timeout(&timeout));
+
+        let other = ProviderError::Database(DatabaseError::Open(mdbx::Error::Corrupted.into()));
+        assert!(!is_read_tx_timeout(&other));
+    }
+}
@@ -4,17 +4,21 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
     EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    PruneCheckpointReader, ReorgJournalReader, RequestsProvider, StageCheckpointReader,
+    StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
-use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db::{init_db, mdbx::DatabaseArguments, tables, DatabaseEnv};
+use reth_db_api::{
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredReorg},
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
@@ -30,11 +34,17 @@ use std::{
 use tokio::sync::watch;
 use tracing::trace;
 
+mod chunked;
+pub use chunked::{ChunkedReader, ReadTxLimits, ResumableWalker};
+
 mod metrics;
 mod provider;
 
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
 
+mod write_gate;
+pub use write_gate::{WriteGate, WriteGuard};
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -48,6 +58,10 @@ pub struct ProviderFactory<DB> {
     static_file_provider: StaticFileProvider,
     /// Optional pruning configuration
     prune_modes: PruneModes,
+    /// Limits of read-only transactions used for reads of large ranges
+    read_tx_limits: ReadTxLimits,
+    /// Tracks the read-write providers, shared by all clones of the factory
+    write_gate: WriteGate,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -57,7 +71,14 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider, prune_modes: PruneModes::none() }
+        Self {
+            db: Arc::new(db),
+            chain_spec,
+            static_file_provider,
+            prune_modes: PruneModes::none(),
+            read_tx_limits: ReadTxLimits::default(),
+            write_gate: WriteGate::default(),
+        }
     }
 
     /// Enables metrics on the static file provider.
@@ -72,6 +93,22 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Sets the limits of read-only transactions used for reads of large ranges.
+    pub const fn with_read_tx_limits(mut self, read_tx_limits: ReadTxLimits) -> Self {
+        self.read_tx_limits = read_tx_limits;
+        self
+    }
+
+    /// Returns the limits of read-only transactions used for reads of large ranges.
+    pub const fn read_tx_limits(&self) -> ReadTxLimits {
+        self.read_tx_limits
+    }
+
+    /// Returns the [`WriteGate`] that tracks the read-write providers of this factory.
+    pub const fn write_gate(&self) -> &WriteGate {
+        &self.write_gate
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -98,6 +135,8 @@ impl ProviderFactory<DatabaseEnv> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            read_tx_limits: ReadTxLimits::default(),
+            write_gate: WriteGate::default(),
         })
     }
 }
@@ -123,14 +162,20 @@ impl<DB: Database> ProviderFactory<DB> {
     /// data from the database using different types of providers. Example: [`HeaderProvider`]
     /// [`BlockHashReader`].  This may fail if the inner read/write database transaction fails to
     /// open.
+    ///
+    /// Returns [`ProviderError::DatabaseClosing`] if the [`WriteGate`] of the factory was closed.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-        )))
+        let guard = self.write_gate.enter()?;
+        Ok(DatabaseProviderRW::new(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+            ),
+            guard,
+        ))
     }
 
     /// State provider for latest block
@@ -225,7 +270,11 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
             StaticFileSegment::Headers,
             to_range(range),
             |static_file, range, _| static_file.headers_range(range),
-            |range, _| self.provider()?.headers_range(range),
+            |range, _| {
+                ResumableWalker::<_, tables::Headers>::new(self, range)
+                    .map(|entry| entry.map(|(_, header)| header))
+                    .collect()
+            },
             |_| true,
         )
     }
@@ -280,7 +329,11 @@ impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
             StaticFileSegment::Headers,
             start..end,
             |static_file, range, _| static_file.canonical_hashes_range(range.start, range.end),
-            |range, _| self.provider()?.canonical_hashes_range(range.start, range.end),
+            |range, _| {
+                ResumableWalker::<_, tables::CanonicalHeaders>::new(self, range)
+                    .map(|entry| entry.map(|(_, hash)| hash))
+                    .collect()
+            },
             |_| true,
         )
     }
@@ -443,6 +496,10 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         self.provider()?.transaction_sender(id)
     }
+
+    fn transaction_index_pruned_to(&self, hash: TxHash) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.transaction_index_pruned_to(hash)
+    }
 }
 
 impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
@@ -463,6 +520,20 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
         self.provider()?.receipts_by_block(block)
     }
 
+    fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        self.provider()?.receipts_by_block_range(range)
+    }
+
+    fn receipts_bloom_by_block(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        self.static_file_provider.receipts_bloom_by_block(block)
+    }
+
     fn receipts_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -471,7 +542,11 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
             StaticFileSegment::Receipts,
             to_range(range),
             |static_file, range, _| static_file.receipts_by_tx_range(range),
-            |range, _| self.provider()?.receipts_by_tx_range(range),
+            |range, _| {
+                ResumableWalker::<_, tables::Receipts>::new(self, range)
+                    .map(|entry| entry.map(|(_, receipt)| receipt))
+                    .collect()
+            },
             |_| true,
         )
     }
@@ -584,6 +659,16 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> ReorgJournalReader for ProviderFactory<DB> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.provider()?.reorg_history(limit)
+    }
+
+    fn reorgs_since(&self, first_id: u64) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.provider()?.reorgs_since(first_id)
+    }
+}
+
 impl<DB> Clone for ProviderFactory<DB> {
     fn clone(&self) -> Self {
         Self {
@@ -591,6 +676,8 @@ impl<DB> Clone for ProviderFactory<DB> {
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
+            read_tx_limits: self.read_tx_limits,
+            write_gate: self.write_gate.clone(),
         }
     }
 }
@@ -600,7 +687,8 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, HistoryWriter,
+        ReceiptProvider, ReorgJournalWriter, TransactionsProvider, MAX_REORG_JOURNAL_LEN,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -610,15 +698,21 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        cursor::DbCursorRO,
+        models::ShardedKey,
+        transaction::{DbTx, DbTxMut},
     };
     use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
         generators,
-        generators::{random_block, random_header},
+        generators::{random_block, random_header, random_receipt},
     };
-    use std::{ops::RangeInclusive, sync::Arc};
+    use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -702,6 +796,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ommers_of_pre_merge_blocks() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let genesis = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let block = random_block(&mut rng, 1, Some(genesis.hash()), Some(0), Some(2));
+
+        let provider = factory.provider_rw().unwrap();
+        for block in [&genesis, &block] {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap()).unwrap();
+        }
+
+        // blocks without ommers have no ommers entry
+        assert_eq!(provider.ommers(0.into()).unwrap(), Some(Vec::new()));
+        assert_eq!(provider.ommers(genesis.hash().into()).unwrap(), Some(Vec::new()));
+        assert_eq!(provider.ommers(1.into()).unwrap(), Some(block.ommers.clone()));
+        assert_eq!(provider.ommers(block.hash().into()).unwrap(), Some(block.ommers));
+        assert_eq!(provider.ommers(2.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn reorg_journal_is_capped() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        for timestamp in 0..MAX_REORG_JOURNAL_LEN + 2 {
+            provider.append_reorg(StoredReorg { timestamp, ..Default::default() }).unwrap();
+        }
+
+        let history = provider.reorg_history(usize::MAX).unwrap();
+        assert_eq!(history.len() as u64, MAX_REORG_JOURNAL_LEN);
+        assert_eq!(history.last().map(|(id, reorg)| (*id, reorg.timestamp)), Some((2, 2)));
+
+        let ids = provider
+            .reorgs_since(MAX_REORG_JOURNAL_LEN)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [MAX_REORG_JOURNAL_LEN, MAX_REORG_JOURNAL_LEN + 1]);
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -744,6 +879,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn receipts_by_block_range() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+
+        let provider = factory.provider_rw().unwrap();
+        let mut expected = Vec::new();
+        let mut parent = None;
+        for (number, tx_count) in [(0, 2), (1, 0), (2, 3)] {
+            let block = random_block(&mut rng, number, parent, Some(tx_count), None);
+            parent = Some(block.hash());
+            let first_tx_num = provider
+                .insert_block(block.clone().try_seal_with_senders().unwrap())
+                .unwrap()
+                .first_tx_num();
+
+            let receipts = block
+                .body
+                .iter()
+                .map(|tx| random_receipt(&mut rng, tx, Some(1)))
+                .collect::<Vec<_>>();
+            for (tx_num, receipt) in (first_tx_num..).zip(&receipts) {
+                provider.tx_ref().put::<tables::Receipts>(tx_num, receipt.clone()).unwrap();
+            }
+            expected.push(receipts);
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.receipts_by_block_range(0..=2).unwrap(), expected);
+        assert_eq!(provider.receipts_by_block_range(1..=5).unwrap(), expected[1..]);
+        assert_eq!(provider.receipts_by_block_range(3..=5).unwrap(), Vec::<Vec<Receipt>>::new());
+
+        // a block with missing receipts ends the range instead of taking receipts of other blocks
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().delete::<tables::Receipts>(0, None).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.receipts_by_block_range(0..=2).unwrap(), Vec::<Vec<Receipt>>::new());
+        assert_eq!(provider.receipts_by_block_range(1..=2).unwrap(), expected[1..]);
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -775,4 +953,77 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn seal_and_merge_history_shards() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let address = Address::with_last_byte(1);
+        let shards = |provider: &DatabaseProviderRW<_>| {
+            provider
+                .tx_ref()
+                .cursor_read::<tables::AccountsHistory>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| {
+                    let (key, list) = entry.unwrap();
+                    (key.key, key.highest_block_number, list.len())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // the last shard is sealed once it exceeds its size
+        for blocks in (1..=2600u64).collect::<Vec<_>>().chunks(10) {
+            provider
+                .insert_account_history_index(BTreeMap::from([(address, blocks.to_vec())]))
+                .unwrap();
+        }
+        let mut expected = (1..=10).map(|shard| (address, shard * 250, 250)).collect::<Vec<_>>();
+        expected.push((address, u64::MAX, 100));
+        assert_eq!(shards(&provider), expected);
+
+        // the sealed shards are merged into full shards
+        assert_eq!(
+            provider
+                .merge_account_history_shards(ShardedKey::new(Address::ZERO, 0), 100, false)
+                .unwrap(),
+            (10, None)
+        );
+        assert_eq!(
+            shards(&provider),
+            vec![(address, 2000, 2000), (address, 2500, 500), (address, u64::MAX, 100)]
+        );
+        assert_eq!(
+            provider
+                .merge_account_history_shards(ShardedKey::new(Address::ZERO, 0), 100, false)
+                .unwrap(),
+            (0, None)
+        );
+
+        // oversized last shards are only split if requested, and the walk stops at the limit
+        let other = Address::with_last_byte(2);
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::last(other),
+                BlockNumberList::new_pre_sorted((1..=600).collect::<Vec<_>>()),
+            )
+            .unwrap();
+        assert_eq!(
+            provider.merge_account_history_shards(ShardedKey::new(other, 0), 100, false).unwrap(),
+            (0, None)
+        );
+        assert_eq!(
+            provider
+                .merge_account_history_shards(ShardedKey::new(Address::ZERO, 0), 3, true)
+                .unwrap(),
+            (0, Some(ShardedKey::last(other)))
+        );
+        assert_eq!(
+            provider.merge_account_history_shards(ShardedKey::last(other), 100, true).unwrap(),
+            (1, None)
+        );
+        assert_eq!(shards(&provider)[3..], [(other, 500, 500), (other, u64::MAX, 100)]);
+    }
 }
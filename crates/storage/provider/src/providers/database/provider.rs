@@ -1,6 +1,10 @@
 use crate::{
     bundle_state::{BundleStateInit, HashedStateChanges, RevertsInit},
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{
+        database::{metrics, WriteGuard},
+        static_file::StaticFileWriter,
+        StaticFileProvider,
+    },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
@@ -9,9 +13,10 @@ use crate::{
     EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter, HeaderProvider,
     HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoryWriter,
     LatestStateProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, RequestsProvider, StageCheckpointReader, StateProviderBox, StateWriter,
-    StatsReader, StorageReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
-    WithdrawalsProvider,
+    PruneCheckpointWriter, ReorgJournalReader, ReorgJournalWriter, RequestsProvider,
+    StageCheckpointReader, StateProviderBox, StateWriter, StatsReader, StorageReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    MAX_REORG_JOURNAL_LEN,
 };
 use itertools::{izip, Itertools};
 use reth_chainspec::{ChainInfo, ChainSpec, EthereumHardforks};
@@ -22,7 +27,7 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals, StoredReorg,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
@@ -33,10 +38,10 @@ use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
-    BlockWithSenders, GotExpected, Header, Receipt, Requests, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
-    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
-    Withdrawals, B256, U256,
+    BlockWithSenders, Bloom, GotExpected, Header, Receipt, Requests, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
+    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -66,7 +71,10 @@ pub type DatabaseProviderRO<DB> = DatabaseProvider<<DB as Database>::TX>;
 /// Ideally this would be an alias type. However, there's some weird compiler error (<https://github.com/rust-lang/rust/issues/102211>), that forces us to wrap this in a struct instead.
 /// Once that issue is solved, we can probably revert back to being an alias type.
 #[derive(Debug)]
-pub struct DatabaseProviderRW<DB: Database>(pub DatabaseProvider<<DB as Database>::TXMut>);
+pub struct DatabaseProviderRW<DB: Database>(
+    pub DatabaseProvider<<DB as Database>::TXMut>,
+    WriteGuard,
+);
 
 impl<DB: Database> Deref for DatabaseProviderRW<DB> {
     type Target = DatabaseProvider<<DB as Database>::TXMut>;
@@ -83,6 +91,15 @@ impl<DB: Database> DerefMut for DatabaseProviderRW<DB> {
 }
 
 impl<DB: Database> DatabaseProviderRW<DB> {
+    /// Creates a new read-write provider that is tracked by the [`WriteGate`](super::WriteGate)
+    /// of the guard until it is committed or dropped.
+    pub(crate) const fn new(
+        provider: DatabaseProvider<<DB as Database>::TXMut>,
+        guard: WriteGuard,
+    ) -> Self {
+        Self(provider, guard)
+    }
+
     /// Commit database transaction and static file if it exists.
     pub fn commit(self) -> ProviderResult<bool> {
         self.0.commit()
@@ -252,6 +269,31 @@ where
     Ok(Vec::new())
 }
 
+/// Splits the sorted indices of a key into shards, returning the highest block number of every
+/// shard with its indices.
+///
+/// If `with_last` is set, the last indices are split off into a last shard keyed by `u64::MAX`
+/// that holds at most [`sharded_key::NUM_OF_INDICES_IN_TAIL_SHARD`] indices. The remaining
+/// indices are split into shards of [`sharded_key::NUM_OF_INDICES_IN_SHARD`] indices that are
+/// keyed by their highest block number.
+fn shard_history_indices(indices: &[u64], with_last: bool) -> Vec<(BlockNumber, Vec<u64>)> {
+    let last_len = if with_last && !indices.is_empty() {
+        (indices.len() - 1) % sharded_key::NUM_OF_INDICES_IN_TAIL_SHARD + 1
+    } else {
+        0
+    };
+    let (sealed, last) = indices.split_at(indices.len() - last_len);
+
+    let mut shards = sealed
+        .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
+        .map(|chunk| (*chunk.last().expect("`chunks` does not return empty list"), chunk.to_vec()))
+        .collect::<Vec<_>>();
+    if !last.is_empty() {
+        shards.push((u64::MAX, last.to_vec()));
+    }
+    shards
+}
+
 impl<TX: DbTx> DatabaseProvider<TX> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -551,25 +593,40 @@ impl<TX: DbTx> DatabaseProvider<TX> {
                     .map(Into::into)
                     .collect::<Vec<TransactionSigned>>();
                 // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
-                        .walk_range(tx_range.clone())?
-                        .collect::<Result<HashMap<_, _>, _>>()?;
-
-                let mut senders = Vec::with_capacity(body.len());
-                for (tx_num, tx) in tx_range.zip(body.iter()) {
-                    match known_senders.get(&tx_num) {
-                        None => {
-                            // recover the sender from the transaction if not found
-                            let sender = tx
-                                .recover_signer_unchecked()
-                                .ok_or_else(|| ProviderError::SenderRecoveryError)?;
-                            senders.push(sender);
-                        }
-                        Some(sender) => senders.push(*sender),
+                let mut known_senders = senders_cursor
+                    .walk_range(tx_range.clone())?
+                    .collect::<Result<HashMap<_, _>, _>>()?;
+
+                // fall back to the senders stored with the static files, before recovering them
+                if known_senders.len() < body.len() {
+                    if let Some(persisted) =
+                        self.static_file_provider.persisted_senders(tx_range.clone())?
+                    {
+                        known_senders.extend(tx_range.clone().zip(persisted));
                     }
                 }
 
+                // recover the senders that are not found as a batch
+                let missing = tx_range
+                    .clone()
+                    .zip(body.iter())
+                    .filter(|(tx_num, _)| !known_senders.contains_key(tx_num))
+                    .map(|(_, tx)| tx)
+                    .collect::<Vec<_>>();
+                let num_missing = missing.len();
+                let mut recovered =
+                    TransactionSigned::recover_signers_unchecked(missing, num_missing)
+                        .ok_or(ProviderError::SenderRecoveryError)?
+                        .into_iter();
+
+                let senders = tx_range
+                    .zip(body.iter())
+                    .map(|(tx_num, _)| match known_senders.get(&tx_num) {
+                        Some(sender) => *sender,
+                        None => recovered.next().expect("recovered all missing senders"),
+                    })
+                    .collect::<Vec<_>>();
+
                 (body, senders)
             };
 
@@ -1224,8 +1281,10 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// Insert history index to the database.
     ///
     /// For each updated partial key, this function removes the last shard from
-    /// the database (if any), appends the new indices to it, chunks the resulting integer list and
-    /// inserts the new shards back into the database.
+    /// the database (if any), appends the new indices to it and inserts it back into the database.
+    /// Once the last shard exceeds [`sharded_key::NUM_OF_INDICES_IN_TAIL_SHARD`] indices, it's
+    /// sealed under its highest block number, so that appending only ever rewrites a small shard.
+    /// The sealed shards are merged into full shards by [`Self::merge_history_shards`].
     ///
     /// This function is used by history indexing stages.
     fn append_history_index<P, T>(
@@ -1238,23 +1297,10 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         T: Table<Value = BlockNumberList>,
     {
         for (partial_key, indices) in index_updates {
-            let last_shard = self.take_shard::<T>(sharded_key_factory(partial_key, u64::MAX))?;
-            // chunk indices and insert them in shards of N size.
-            let indices = last_shard.iter().chain(indices.iter());
-            let chunks = indices
-                .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
-                .into_iter()
-                .map(|chunks| chunks.copied().collect())
-                .collect::<Vec<Vec<_>>>();
-
-            let mut chunks = chunks.into_iter().peekable();
-            while let Some(list) = chunks.next() {
-                let highest_block_number = if chunks.peek().is_some() {
-                    *list.last().expect("`chunks` does not return empty list")
-                } else {
-                    // Insert last list with u64::MAX
-                    u64::MAX
-                };
+            let mut last_shard =
+                self.take_shard::<T>(sharded_key_factory(partial_key, u64::MAX))?;
+            last_shard.extend(indices);
+            for (highest_block_number, list) in shard_history_indices(&last_shard, true) {
                 self.tx.put::<T>(
                     sharded_key_factory(partial_key, highest_block_number),
                     BlockNumberList::new_pre_sorted(list),
@@ -1263,6 +1309,91 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Merges runs of consecutive sealed shards of a key that are not full into shards of
+    /// [`sharded_key::NUM_OF_INDICES_IN_SHARD`] indices, walking at most `limit` shards from the
+    /// start key.
+    ///
+    /// If `split_last` is set, last shards with more than
+    /// [`sharded_key::NUM_OF_INDICES_IN_TAIL_SHARD`] indices, as written by the history indexing
+    /// stages and previous versions, are split as well.
+    ///
+    /// Returns the number of merged shards and the key to continue at, or `None` if the end of the
+    /// table was reached.
+    fn merge_history_shards<T, S>(
+        &self,
+        start_key: T::Key,
+        limit: usize,
+        split_last: bool,
+        key_matches: impl Fn(&T::Key, &T::Key) -> bool,
+        sharded_key_factory: impl Fn(&T::Key, BlockNumber) -> T::Key,
+    ) -> ProviderResult<(usize, Option<T::Key>)>
+    where
+        T: Table<Value = BlockNumberList>,
+        T::Key: AsRef<ShardedKey<S>>,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+
+        // Collect the runs of shards to merge first, the shards are rewritten once the walk is
+        // done.
+        let mut runs = Vec::new();
+        let mut run = Vec::<(T::Key, Vec<u64>)>::new();
+        let mut next_key = None;
+        let mut walked = 0;
+        for entry in cursor.walk(Some(start_key))? {
+            let (key, list) = entry?;
+            if walked == limit {
+                next_key = Some(key);
+                break
+            }
+            walked += 1;
+
+            if run.last().is_some_and(|(last, _)| !key_matches(last, &key)) {
+                runs.push(std::mem::take(&mut run));
+            }
+
+            let len = list.len() as usize;
+            if key.as_ref().highest_block_number == u64::MAX {
+                // The last shard ends the run of its key, it's only merged if it's split.
+                if split_last && len > sharded_key::NUM_OF_INDICES_IN_TAIL_SHARD {
+                    run.push((key, list.iter().collect()));
+                }
+                runs.push(std::mem::take(&mut run));
+            } else if len < sharded_key::NUM_OF_INDICES_IN_SHARD {
+                run.push((key, list.iter().collect()));
+            } else {
+                // A full shard ends the run.
+                runs.push(std::mem::take(&mut run));
+            }
+        }
+        runs.push(run);
+
+        let mut merged = 0;
+        for run in runs {
+            let Some((first_key, _)) = run.first() else { continue };
+            let with_last =
+                run.last().is_some_and(|(key, _)| key.as_ref().highest_block_number == u64::MAX);
+            // A single sealed shard can't be merged with anything.
+            if run.len() < 2 && !with_last {
+                continue
+            }
+
+            let first_key = first_key.clone();
+            let indices = run.iter().flat_map(|(_, list)| list.iter().copied()).collect::<Vec<_>>();
+            merged += run.len();
+            for (key, _) in run {
+                self.tx.delete::<T>(key, None)?;
+            }
+            for (highest_block_number, list) in shard_history_indices(&indices, with_last) {
+                self.tx.put::<T>(
+                    sharded_key_factory(&first_key, highest_block_number),
+                    BlockNumberList::new_pre_sorted(list),
+                )?;
+            }
+        }
+
+        Ok((merged, next_key))
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1330,6 +1461,21 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
             })
             .collect()
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let range = BlockNumberAddress::range(block_number..=block_number);
+        self.tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> {
+                let (BlockNumberAddress((_, address)), storage_before) = result?;
+                Ok((address, storage_before))
+            })
+            .collect()
+    }
 }
 
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
@@ -1582,14 +1728,17 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
 
     fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>> {
         if let Some(number) = self.convert_hash_or_number(id)? {
-            // If the Paris (Merge) hardfork block is known and block is after it, return empty
+            // If the Paris (Merge) hardfork block is known and block is after it, there are no
             // ommers.
-            if self.chain_spec.final_paris_total_difficulty(number).is_some() {
-                return Ok(Some(Vec::new()))
+            if self.chain_spec.final_paris_total_difficulty(number).is_none() {
+                if let Some(ommers) = self.tx.get::<tables::BlockOmmers>(number)? {
+                    return Ok(Some(ommers.ommers))
+                }
             }
 
-            let ommers = self.tx.get::<tables::BlockOmmers>(number)?.map(|o| o.ommers);
-            return Ok(ommers)
+            // Ommers are only stored for blocks that have any, so an existing block without an
+            // entry has none.
+            return Ok(self.block_body_indices(number)?.map(|_| Vec::new()))
         }
 
         Ok(None)
@@ -1919,11 +2068,59 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TransactionSenders>(range).map_err(Into::into)
+        let range = to_range(range);
+        let senders = self.cursor_read_collect::<tables::TransactionSenders>(range.clone())?;
+
+        // senders that were pruned from the database may be stored with the static files
+        if (senders.len() as u64) < range.end.saturating_sub(range.start) && range.end != u64::MAX {
+            if let Some(persisted) = self.static_file_provider.persisted_senders(range)? {
+                return Ok(persisted)
+            }
+        }
+
+        Ok(senders)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TransactionSenders>(id)?)
+        if let Some(sender) = self.tx.get::<tables::TransactionSenders>(id)? {
+            return Ok(Some(sender))
+        }
+
+        Ok(self
+            .static_file_provider
+            .persisted_senders(id..id + 1)?
+            .and_then(|s| s.first().copied()))
+    }
+
+    fn transaction_index_pruned_to(&self, hash: TxHash) -> ProviderResult<Option<BlockNumber>> {
+        let Some(pruned_to) = self
+            .get_prune_checkpoint(PruneSegment::TransactionLookup)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        else {
+            return Ok(None)
+        };
+
+        // Most pruned blocks are in the static files, which can be searched by hash
+        if self.static_file_provider.transaction_by_hash(hash)?.is_some() {
+            return Ok(Some(pruned_to))
+        }
+
+        // The remaining pruned blocks are in the database, search them by hashing transactions
+        let first_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Transactions)
+            .map_or(0, |block| block + 1);
+        if first_block > pruned_to {
+            return Ok(None)
+        }
+        let tx_range = self.transaction_range_by_block_range(first_block..=pruned_to)?;
+        let mut cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        for entry in cursor.walk_range(tx_range)? {
+            if entry?.1.hash() == hash {
+                return Ok(Some(pruned_to))
+            }
+        }
+        Ok(None)
     }
 }
 
@@ -1971,6 +2168,63 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    fn receipts_bloom_by_block(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        self.static_file_provider.receipts_bloom_by_block(block)
+    }
+
+    fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let bodies = body_cursor
+            .walk_range(range)?
+            .map(|entry| entry.map(|(_, body)| body))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (Some(first), Some(last)) = (bodies.first(), bodies.last()) else {
+            return Ok(Vec::new())
+        };
+
+        // read the receipts of all blocks at once, so they are read sequentially
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+        if tx_range.is_empty() {
+            return Ok(vec![Vec::new(); bodies.len()])
+        }
+        // receipts in the database may be pruned, so they're assigned to blocks by tx number, and
+        // the blocks up to the first one with missing receipts are returned
+        let receipts = self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Receipts,
+            tx_range,
+            |static_file, range, _| {
+                Ok(range.clone().zip(static_file.receipts_by_tx_range(range)?).collect())
+            },
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::Receipts>()?
+                    .walk_range(range)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(Into::into)
+            },
+            |_| true,
+        )?;
+        let mut receipts = receipts.into_iter().peekable();
+        Ok(bodies
+            .iter()
+            .map_while(|body| {
+                let mut block_receipts = Vec::with_capacity(body.tx_count as usize);
+                while let Some((_, receipt)) =
+                    receipts.next_if(|(tx_number, _)| *tx_number < body.next_tx_num())
+                {
+                    block_receipts.push(receipt);
+                }
+                (block_receipts.len() as u64 == body.tx_count).then_some(block_receipts)
+            })
+            .collect())
+    }
 }
 
 impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
@@ -2508,6 +2762,38 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         )
     }
 
+    fn merge_account_history_shards(
+        &self,
+        from: ShardedKey<Address>,
+        limit: usize,
+        split_last: bool,
+    ) -> ProviderResult<(usize, Option<ShardedKey<Address>>)> {
+        self.merge_history_shards::<tables::AccountsHistory, _>(
+            from,
+            limit,
+            split_last,
+            |a, b| a.key == b.key,
+            |key, highest_block_number| ShardedKey::new(key.key, highest_block_number),
+        )
+    }
+
+    fn merge_storage_history_shards(
+        &self,
+        from: StorageShardedKey,
+        limit: usize,
+        split_last: bool,
+    ) -> ProviderResult<(usize, Option<StorageShardedKey>)> {
+        self.merge_history_shards::<tables::StoragesHistory, _>(
+            from,
+            limit,
+            split_last,
+            |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
+            |key, highest_block_number| {
+                StorageShardedKey::new(key.address, key.sharded_key.key, highest_block_number)
+            },
+        )
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2870,6 +3156,39 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> ReorgJournalReader for DatabaseProvider<TX> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::ReorgJournal>()?
+            .walk_back(None)?
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn reorgs_since(&self, first_id: u64) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::ReorgJournal>()?
+            .walk_range(first_id..)?
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ReorgJournalWriter for DatabaseProvider<TX> {
+    fn append_reorg(&self, reorg: StoredReorg) -> ProviderResult<u64> {
+        let mut cursor = self.tx.cursor_write::<tables::ReorgJournal>()?;
+        let id = cursor.last()?.map_or(0, |(id, _)| id + 1);
+        cursor.append(id, reorg)?;
+
+        let mut walker = cursor.walk_range(..(id + 1).saturating_sub(MAX_REORG_JOURNAL_LEN))?;
+        while walker.next().transpose()?.is_some() {
+            walker.delete_current()?;
+        }
+        Ok(id)
+    }
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
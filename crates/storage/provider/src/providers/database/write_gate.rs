@@ -0,0 +1,112 @@
+use parking_lot::{Condvar, Mutex};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{sync::Arc, time::Duration};
+
+/// Coordinates the read-write providers of a [`ProviderFactory`](crate::ProviderFactory) with the
+/// shutdown of the node.
+///
+/// Every read-write provider holds a [`WriteGuard`] until it is committed or dropped. Closing the
+/// gate waits for the outstanding read-write providers and rejects the creation of new ones, so no
+/// write is interrupted halfway, e.g. between committing static files and the database.
+#[derive(Debug, Clone, Default)]
+pub struct WriteGate(Arc<WriteGateInner>);
+
+#[derive(Debug, Default)]
+struct WriteGateInner {
+    state: Mutex<WriteGateState>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct WriteGateState {
+    /// Whether new writers are rejected.
+    closed: bool,
+    /// The number of outstanding writers.
+    writers: usize,
+}
+
+impl WriteGate {
+    /// Registers a new writer.
+    ///
+    /// Returns [`ProviderError::DatabaseClosing`] if the gate is closed.
+    pub fn enter(&self) -> ProviderResult<WriteGuard> {
+        let mut state = self.0.state.lock();
+        if state.closed {
+            return Err(ProviderError::DatabaseClosing)
+        }
+        state.writers += 1;
+        Ok(WriteGuard(self.clone()))
+    }
+
+    /// Waits up to `timeout` for the outstanding writers to finish, without rejecting new ones.
+    ///
+    /// Returns `false` if there are still outstanding writers after the timeout.
+    pub fn wait_for_writers(&self, timeout: Duration) -> bool {
+        let mut state = self.0.state.lock();
+        !self.0.changed.wait_while_for(&mut state, |state| state.writers > 0, timeout).timed_out()
+    }
+
+    /// Closes the gate and waits up to `timeout` for the outstanding writers to finish.
+    ///
+    /// Returns `false` if there are still outstanding writers after the timeout.
+    pub fn close(&self, timeout: Duration) -> bool {
+        let mut state = self.0.state.lock();
+        state.closed = true;
+        !self.0.changed.wait_while_for(&mut state, |state| state.writers > 0, timeout).timed_out()
+    }
+
+    /// Returns `true` if the gate was closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.state.lock().closed
+    }
+
+    /// Returns the number of outstanding writers.
+    pub fn writers(&self) -> usize {
+        self.0.state.lock().writers
+    }
+}
+
+/// Marks an outstanding writer of a [`WriteGate`] until it is dropped.
+#[derive(Debug)]
+pub struct WriteGuard(WriteGate);
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        self.0 .0.state.lock().writers -= 1;
+        self.0 .0.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_waits_for_writers() {
+        let gate = WriteGate::default();
+        let guard = gate.enter().unwrap();
+        assert_eq!(gate.writers(), 1);
+
+        // the outstanding writer doesn't finish in time
+        assert!(!gate.close(Duration::from_millis(10)));
+        assert!(gate.is_closed());
+
+        let handle = std::thread::spawn({
+            let gate = gate.clone();
+            move || gate.close(Duration::from_secs(10))
+        });
+        std::thread::sleep(Duration::from_millis(10));
+        drop(guard);
+        assert!(handle.join().unwrap());
+        assert_eq!(gate.writers(), 0);
+    }
+
+    #[test]
+    fn closed_gate_rejects_writers() {
+        let gate = WriteGate::default();
+        assert!(gate.close(Duration::ZERO));
+
+        assert!(matches!(gate.enter(), Err(ProviderError::DatabaseClosing)));
+        assert_eq!(gate.writers(), 0);
+    }
+}
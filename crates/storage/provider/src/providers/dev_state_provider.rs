@@ -0,0 +1,129 @@
+use crate::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use reth_evm::dev::DevState;
+use reth_primitives::{Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256};
+use reth_storage_api::StateProofProvider;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{updates::TrieUpdates, AccountProof};
+use revm::db::BundleState;
+
+/// A state provider that shows the state overrides of the dev chain on top of an underlying state
+/// provider.
+///
+/// The overrides are not part of the state trie until a block applies them, so state roots and
+/// proofs are those of the underlying state provider. See [`reth_evm::dev`].
+#[derive(Debug)]
+pub struct DevStateProvider<SP: StateProvider> {
+    /// The inner state provider.
+    pub state_provider: SP,
+    /// The state overrides of the dev chain.
+    pub dev_state: DevState,
+}
+
+impl<SP: StateProvider> DevStateProvider<SP> {
+    /// Create new dev state provider
+    pub const fn new(state_provider: SP, dev_state: DevState) -> Self {
+        Self { state_provider, dev_state }
+    }
+}
+
+/* Implement StateProvider traits */
+
+impl<SP: StateProvider> BlockHashReader for DevStateProvider<SP> {
+    fn block_hash(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state_provider.block_hash(block_number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state_provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for DevStateProvider<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        let account = self.state_provider.basic_account(address)?;
+        match self.dev_state.account_override(&address) {
+            Some(account_override) => Ok(Some(account_override.apply(account))),
+            None => Ok(account),
+        }
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for DevStateProvider<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state_provider.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_provider.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProofProvider for DevStateProvider<SP> {
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        self.state_provider.proof(address, slots)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for DevStateProvider<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.dev_state.storage_override(&account, &storage_key) {
+            return Ok(Some(value))
+        }
+
+        self.state_provider.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.dev_state.bytecode_by_hash(&code_hash) {
+            return Ok(Some(Bytecode(bytecode)))
+        }
+
+        self.state_provider.bytecode_by_hash(code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_primitives::{Bytes, U256};
+
+    #[test]
+    fn dev_state_overrides() {
+        let address = Address::with_last_byte(1);
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(1, U256::from(5)).extend_storage([(slot, U256::from(1))]),
+        );
+
+        let dev_state = DevState::default();
+        let state = DevStateProvider::new(provider, dev_state.clone());
+        assert_eq!(state.account_balance(address).unwrap(), Some(U256::from(5)));
+
+        dev_state.set_balance(address, U256::from(10));
+        dev_state.set_code(address, Bytes::from_static(&[0x60, 0x00]));
+        dev_state.set_storage_at(address, slot, U256::from(2));
+
+        let account = state.basic_account(address).unwrap().unwrap();
+        assert_eq!(account.balance, U256::from(10));
+        assert_eq!(account.nonce, 1);
+        assert_eq!(state.storage(address, slot).unwrap(), Some(U256::from(2)));
+        assert_eq!(
+            state.account_code(address).unwrap().map(|code| code.original_bytes()),
+            Some(Bytes::from_static(&[0x60, 0x00]))
+        );
+    }
+}
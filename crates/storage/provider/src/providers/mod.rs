@@ -3,9 +3,10 @@ use crate::{
     BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
     EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, ReorgJournalReader,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TreeViewer,
+    WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -15,14 +16,14 @@ use reth_blockchain_tree_api::{
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::{
     database::Database,
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, StoredBlockBodyIndices, StoredReorg},
 };
-use reth_evm::ConfigureEvmEnv;
+use reth_evm::{dev::DevState, ConfigureEvmEnv};
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    BlockNumberOrTag, BlockWithSenders, Bloom, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -41,19 +42,22 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    StaticFileAccess, StaticFileCompaction, StaticFileJarProvider, StaticFileProvider,
+    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriter,
 };
 
 mod state;
 pub use state::{
-    historical::{HistoricalStateProvider, HistoricalStateProviderRef},
+    historical::{HistoricalStateProvider, HistoricalStateProviderRef, LowestAvailableBlocks},
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
 
 mod bundle_state_provider;
 pub use bundle_state_provider::BundleStateProvider;
 
+mod dev_state_provider;
+pub use dev_state_provider::DevStateProvider;
+
 mod chain_info;
 use chain_info::ChainInfoTracker;
 
@@ -61,6 +65,9 @@ mod consistent_view;
 use alloy_rpc_types_engine::ForkchoiceState;
 pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 
+mod consistent_view_provider;
+pub use consistent_view_provider::ConsistentViewProvider;
+
 /// The main type for interacting with the blockchain.
 ///
 /// This type serves as the main entry point for interacting with the blockchain and provides data
@@ -74,6 +81,8 @@ pub struct BlockchainProvider<DB> {
     tree: Arc<dyn TreeViewer>,
     /// Tracks the chain info wrt forkchoice updates
     chain_info: ChainInfoTracker,
+    /// The state overrides of the dev chain, shown by the latest and pending state.
+    dev_state: Option<DevState>,
 }
 
 impl<DB> Clone for BlockchainProvider<DB> {
@@ -82,6 +91,7 @@ impl<DB> Clone for BlockchainProvider<DB> {
             database: self.database.clone(),
             tree: self.tree.clone(),
             chain_info: self.chain_info.clone(),
+            dev_state: self.dev_state.clone(),
         }
     }
 }
@@ -94,7 +104,7 @@ impl<DB> BlockchainProvider<DB> {
         tree: Arc<dyn TreeViewer>,
         latest: SealedHeader,
     ) -> Self {
-        Self { database, tree, chain_info: ChainInfoTracker::new(latest) }
+        Self { database, tree, chain_info: ChainInfoTracker::new(latest), dev_state: None }
     }
 
     /// Sets the treeviewer for the provider.
@@ -103,6 +113,27 @@ impl<DB> BlockchainProvider<DB> {
         self.tree = tree;
         self
     }
+
+    /// Sets the state overrides of the dev chain, which the latest and pending state show.
+    ///
+    /// This must only be set in dev mode, see [`reth_evm::dev`].
+    pub fn with_dev_state(mut self, dev_state: DevState) -> Self {
+        self.dev_state = Some(dev_state);
+        self
+    }
+
+    /// Returns the state overrides of the dev chain, if the node runs in dev mode.
+    pub const fn dev_state(&self) -> Option<&DevState> {
+        self.dev_state.as_ref()
+    }
+
+    /// Shows the state overrides of the dev chain on top of the state, if any.
+    fn with_dev_overrides(&self, state: StateProviderBox) -> StateProviderBox {
+        match &self.dev_state {
+            Some(dev_state) => Box::new(DevStateProvider::new(state, dev_state.clone())),
+            None => state,
+        }
+    }
 }
 
 impl<DB> BlockchainProvider<DB>
@@ -145,6 +176,12 @@ where
             Ok(())
         }
     }
+
+    /// Returns a [`ConsistentViewProvider`] over the current state of the chain, for queries that
+    /// need multiple reads to observe the same state.
+    pub fn consistent_view(&self) -> ProviderResult<ConsistentViewProvider<DB>> {
+        ConsistentViewProvider::new(&self.database, self.tree.clone(), &self.chain_info)
+    }
 }
 
 impl<DB> DatabaseProviderFactory<DB> for BlockchainProvider<DB>
@@ -420,6 +457,10 @@ where
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         self.database.transaction_sender(id)
     }
+
+    fn transaction_index_pruned_to(&self, hash: TxHash) -> ProviderResult<Option<BlockNumber>> {
+        self.database.transaction_index_pruned_to(hash)
+    }
 }
 
 impl<DB> ReceiptProvider for BlockchainProvider<DB>
@@ -438,6 +479,20 @@ where
         self.database.receipts_by_block(block)
     }
 
+    fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        self.database.receipts_by_block_range(range)
+    }
+
+    fn receipts_bloom_by_block(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        self.database.receipts_bloom_by_block(block)
+    }
+
     fn receipts_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -451,25 +506,7 @@ where
     DB: Database,
 {
     fn receipts_by_block_id(&self, block: BlockId) -> ProviderResult<Option<Vec<Receipt>>> {
-        match block {
-            BlockId::Hash(rpc_block_hash) => {
-                let mut receipts = self.receipts_by_block(rpc_block_hash.block_hash.into())?;
-                if receipts.is_none() && !rpc_block_hash.require_canonical.unwrap_or(false) {
-                    receipts = self.tree.receipts_by_block_hash(rpc_block_hash.block_hash);
-                }
-                Ok(receipts)
-            }
-            BlockId::Number(num_tag) => match num_tag {
-                BlockNumberOrTag::Pending => Ok(self.tree.pending_receipts()),
-                _ => {
-                    if let Some(num) = self.convert_block_number(num_tag)? {
-                        self.receipts_by_block(num.into())
-                    } else {
-                        Ok(None)
-                    }
-                }
-            },
-        }
+        self.consistent_view()?.receipts_by_block_id(block)
     }
 }
 
@@ -583,6 +620,19 @@ where
     }
 }
 
+impl<DB> ReorgJournalReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.database.reorg_history(limit)
+    }
+
+    fn reorgs_since(&self, first_id: u64) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.database.reorgs_since(first_id)
+    }
+}
+
 impl<DB> ChainSpecProvider for BlockchainProvider<DB>
 where
     DB: Send + Sync,
@@ -599,7 +649,7 @@ where
     /// Storage provider for latest block
     fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", "Getting latest block state provider");
-        self.database.latest()
+        Ok(self.with_dev_overrides(self.database.latest()?))
     }
 
     fn history_by_block_number(
@@ -640,7 +690,7 @@ where
 
         if let Some(block) = self.tree.pending_block_num_hash() {
             if let Ok(pending) = self.tree.pending_state_provider(block.hash) {
-                return self.pending_with_provider(pending)
+                return Ok(self.with_dev_overrides(self.pending_with_provider(pending)?))
             }
         }
 
@@ -708,6 +758,10 @@ where
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError> {
         self.tree.make_canonical(block_hash)
     }
+
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        self.tree.unwind(unwind_to)
+    }
 }
 
 impl<DB> BlockchainTreeViewer for BlockchainProvider<DB>
@@ -792,24 +846,24 @@ where
 
 impl<DB> BlockReaderIdExt for BlockchainProvider<DB>
 where
-    Self: BlockReader + BlockIdReader + ReceiptProviderIdExt,
+    DB: Database,
 {
+    fn block_by_number_or_tag(&self, id: BlockNumberOrTag) -> ProviderResult<Option<Block>> {
+        self.consistent_view()?.block_by_number_or_tag(id)
+    }
+
     fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Block>> {
-        match id {
-            BlockId::Number(num) => self.block_by_number_or_tag(num),
-            BlockId::Hash(hash) => {
-                // TODO: should we only apply this for the RPCs that are listed in EIP-1898?
-                // so not at the provider level?
-                // if we decide to do this at a higher level, then we can make this an automatic
-                // trait impl
-                if Some(true) == hash.require_canonical {
-                    // check the database, canonical blocks are only stored in the database
-                    self.find_block_by_hash(hash.block_hash, BlockSource::Database)
-                } else {
-                    self.block_by_hash(hash.block_hash)
-                }
-            }
-        }
+        // TODO: should we only apply `require_canonical` for the RPCs that are listed in
+        // EIP-1898? so not at the provider level?
+        self.consistent_view()?.block_by_id(id)
+    }
+
+    fn block_with_senders_by_id(
+        &self,
+        id: BlockId,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        self.consistent_view()?.block_with_senders_by_id(id, transaction_kind)
     }
 
     fn header_by_number_or_tag(&self, id: BlockNumberOrTag) -> ProviderResult<Option<Header>> {
@@ -858,14 +912,7 @@ where
     }
 
     fn ommers_by_id(&self, id: BlockId) -> ProviderResult<Option<Vec<Header>>> {
-        match id {
-            BlockId::Number(num) => self.ommers_by_number_or_tag(num),
-            BlockId::Hash(hash) => {
-                // TODO: EIP-1898 question, see above
-                // here it is not handled
-                self.ommers(BlockHashOrNumber::Hash(hash.block_hash))
-            }
-        }
+        self.consistent_view()?.ommers_by_id(id)
     }
 }
 
@@ -900,6 +947,13 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
 }
 
 impl<DB> AccountReader for BlockchainProvider<DB>
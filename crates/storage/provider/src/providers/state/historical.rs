@@ -274,8 +274,15 @@ impl<'b, TX: DbTx> StateRootProvider for HistoricalStateProviderRef<'b, TX> {
 
 impl<'b, TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'b, TX> {
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _slots: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    ///
+    /// The proofs are generated against the state root of the parent block, by reverting the
+    /// changesets of this and all later blocks on top of the hashed state. Fails if the
+    /// changesets are pruned.
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        let revert_state = self.revert_state()?;
+        Ok(revert_state
+            .account_proof(self.tx, address, slots)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
 }
 
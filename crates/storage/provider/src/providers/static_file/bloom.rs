@@ -0,0 +1,92 @@
+//! Aggregated log blooms of the receipts static files.
+//!
+//! Every receipts static file has a sidecar file with the union of the log blooms of all its
+//! receipts, which allows log queries to skip whole static files that can't contain a match.
+//! Files written before blooms were stored get their bloom built from their receipts the first
+//! time it is needed.
+
+use super::StaticFileJarProvider;
+use reth_primitives::{logs_bloom, Bloom};
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The extension of the file that stores the aggregated log bloom of a receipts static file.
+const BLOOM_FILE_EXTENSION: &str = "bloom";
+
+/// Returns the path of the aggregated log bloom of the static file.
+fn bloom_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(BLOOM_FILE_EXTENSION)
+}
+
+/// Reads the aggregated log bloom of the static file.
+///
+/// Returns `None` if the bloom was not stored, or is malformed.
+pub(crate) fn read_segment_bloom(data_path: &Path) -> ProviderResult<Option<Bloom>> {
+    let path = bloom_path(data_path);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(reth_fs_util::FsPathError::read(err, path).into()),
+    };
+    Ok(<[u8; 256]>::try_from(bytes.as_slice()).ok().map(Bloom::from))
+}
+
+/// Writes the aggregated log bloom of the static file.
+///
+/// The bloom is written to a temporary file first, so readers never see a partial bloom.
+pub(crate) fn write_segment_bloom(data_path: &Path, bloom: &Bloom) -> ProviderResult<()> {
+    let path = bloom_path(data_path);
+    let tmp_path = path.with_extension("bloom.tmp");
+    reth_fs_util::write(&tmp_path, bloom.as_slice())?;
+    reth_fs_util::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Builds the aggregated log bloom of the receipts in the static file.
+pub(crate) fn build_segment_bloom(provider: &StaticFileJarProvider<'_>) -> ProviderResult<Bloom> {
+    let Some(tx_range) = provider.user_header().tx_range().copied() else { return Ok(Bloom::ZERO) };
+
+    let mut cursor = provider.cursor()?;
+    let mut bloom = Bloom::ZERO;
+    for num in tx_range.start()..=tx_range.end() {
+        if let Some(receipt) = cursor.get_receipt(num)? {
+            bloom |= logs_bloom(receipt.logs.iter());
+        }
+    }
+    Ok(bloom)
+}
+
+/// Removes the aggregated log bloom of the static file, if it exists.
+pub(crate) fn remove_segment_bloom(data_path: &Path) -> ProviderResult<()> {
+    let path = bloom_path(data_path);
+    if path.exists() {
+        reth_fs_util::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_bloom_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("static_file_receipts_0_499999");
+        assert_eq!(read_segment_bloom(&data_path).unwrap(), None);
+
+        let bloom = Bloom::repeat_byte(0x11);
+        write_segment_bloom(&data_path, &bloom).unwrap();
+        assert_eq!(read_segment_bloom(&data_path).unwrap(), Some(bloom));
+
+        reth_fs_util::write(bloom_path(&data_path), [0u8; 3]).unwrap();
+        assert_eq!(read_segment_bloom(&data_path).unwrap(), None);
+
+        remove_segment_bloom(&data_path).unwrap();
+        assert!(!bloom_path(&data_path).exists());
+        remove_segment_bloom(&data_path).unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+use reth_db::static_file::{decode_receipt, encode_receipt_columns};
+use reth_db_api::table::Compress;
+use reth_fs_util::FsPathError;
+use reth_nippy_jar::{
+    ConsistencyFailStrategy, NippyJar, NippyJarCursor, NippyJarWriter, CONFIG_FILE_EXTENSION,
+    OFFSETS_FILE_EXTENSION,
+};
+use reth_primitives::static_file::{Compression, ReceiptsLayout, SegmentHeader};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info, warn};
+
+/// Maximum size of a trained zstd dictionary, per column.
+const ZSTD_MAX_DICTIONARY_SIZE: usize = 100_000;
+
+/// Maximum number of rows sampled from a static file to train its zstd dictionaries.
+const DICTIONARY_SAMPLE_ROWS: usize = 10_000;
+
+/// Suffix of the temporary static file that a static file is rewritten to.
+const COMPACT_FILE_SUFFIX: &str = "-compact";
+
+/// Extension of the marker file that exists while a fully written temporary static file replaces
+/// the original one.
+const COMPACT_MARKER_EXTENSION: &str = "done";
+
+/// Result of rewriting a static file with a different compression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileCompaction {
+    /// Path of the static file data file.
+    pub path: PathBuf,
+    /// Compression the static file was rewritten with.
+    pub compression: Compression,
+    /// Number of rows in the static file.
+    pub rows: usize,
+    /// Number of bytes of the row values, before compression.
+    pub uncompressed_size: u64,
+    /// Size of the data file before rewriting it.
+    pub original_size: u64,
+    /// Size of the data file after rewriting it.
+    pub compacted_size: u64,
+}
+
+impl StaticFileCompaction {
+    /// Returns the ratio of uncompressed to compressed bytes before rewriting the static file.
+    pub fn original_ratio(&self) -> f64 {
+        ratio(self.uncompressed_size, self.original_size)
+    }
+
+    /// Returns the ratio of uncompressed to compressed bytes after rewriting the static file.
+    pub fn compacted_ratio(&self) -> f64 {
+        ratio(self.uncompressed_size, self.compacted_size)
+    }
+}
+
+fn ratio(uncompressed: u64, compressed: u64) -> f64 {
+    if compressed == 0 {
+        return 1.0
+    }
+    uncompressed as f64 / compressed as f64
+}
+
+/// Rewrites the static file at `path` with the given compression.
+///
+/// If a receipts layout is given, the rows of the static file are converted to it. The static file
+/// is first written to a temporary file next to it, which then replaces the original data, offsets
+/// and configuration files. A marker file is kept during the replacement, so that
+/// [`recover_compactions`] can finish it if the node is stopped halfway through.
+pub(crate) fn compact_jar(
+    path: &Path,
+    compression: Compression,
+    receipts_layout: Option<ReceiptsLayout>,
+) -> ProviderResult<StaticFileCompaction> {
+    let jar = NippyJar::<SegmentHeader>::load(path).map_err(nippy_jar_error)?;
+    let original_size = file_size(jar.data_path())?;
+
+    let tmp_path = compact_path(path);
+
+    let columns = receipts_layout.map_or(jar.columns(), |layout| layout.columns());
+    let mut compacted = NippyJar::new(columns, &tmp_path, jar.user_header().clone());
+    compacted = match compression {
+        Compression::Lz4 => compacted.with_lz4(),
+        Compression::Zstd => compacted.with_zstd(false, 0),
+        Compression::ZstdWithDictionary => {
+            let mut with_dictionaries = compacted.with_zstd(true, ZSTD_MAX_DICTIONARY_SIZE);
+            match with_dictionaries.prepare_compression(sample_columns(&jar, receipts_layout)?) {
+                Ok(()) => with_dictionaries,
+                Err(err) => {
+                    // Training fails if there's not enough data to sample from
+                    warn!(target: "provider::static_file", ?path, %err, "Failed to train zstd dictionaries, using zstd without dictionaries");
+                    NippyJar::new(columns, &tmp_path, jar.user_header().clone()).with_zstd(false, 0)
+                }
+            }
+        }
+        Compression::Uncompressed => compacted,
+    };
+
+    let mut writer =
+        NippyJarWriter::new(compacted, ConsistencyFailStrategy::Heal).map_err(nippy_jar_error)?;
+    let mut cursor = NippyJarCursor::new(&jar).map_err(nippy_jar_error)?;
+    while let Some(row) = cursor.next_row().map_err(nippy_jar_error)? {
+        match convert_receipt_row(&row, receipts_layout)? {
+            Some(converted) => {
+                for value in converted {
+                    writer.append_column(Some(Ok(&value))).map_err(nippy_jar_error)?;
+                }
+            }
+            None => {
+                for value in row {
+                    writer.append_column(Some(Ok(value))).map_err(nippy_jar_error)?;
+                }
+            }
+        }
+    }
+    writer.commit().map_err(nippy_jar_error)?;
+    drop(cursor);
+
+    let stats = writer.take_compression_stats();
+    let compacted = writer.into_jar();
+    let compacted_size = file_size(compacted.data_path())?;
+
+    let marker = tmp_path.with_extension(COMPACT_MARKER_EXTENSION);
+    File::create(&marker)
+        .and_then(|file| file.sync_all())
+        .map_err(|err| FsPathError::create_file(err, &marker))?;
+    sync_parent(&marker)?;
+
+    replace_with_compacted(path)?;
+    reth_fs_util::remove_file(&marker)?;
+
+    debug!(target: "provider::static_file", ?path, ?compression, ?receipts_layout, original_size, compacted_size, "Compacted static file");
+
+    Ok(StaticFileCompaction {
+        path: path.to_path_buf(),
+        compression,
+        rows: jar.rows(),
+        uncompressed_size: stats.uncompressed_bytes,
+        original_size,
+        compacted_size,
+    })
+}
+
+/// Finishes the compactions that were interrupted while replacing the original static file, and
+/// removes the temporary files of the compactions that were interrupted before that.
+pub(crate) fn recover_compactions(dir: &Path) -> ProviderResult<()> {
+    if !dir.exists() {
+        return Ok(())
+    }
+
+    let mut paths = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        paths.push(entry.map_err(|err| FsPathError::read_dir(err, dir))?.path());
+    }
+
+    for marker in &paths {
+        if marker.extension().map_or(true, |extension| extension != COMPACT_MARKER_EXTENSION) {
+            continue
+        }
+        let Some(path) = marker
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_suffix(COMPACT_FILE_SUFFIX))
+            .map(|name| dir.join(name))
+        else {
+            continue
+        };
+
+        info!(target: "provider::static_file", ?path, "Finishing interrupted static file compaction");
+        replace_with_compacted(&path)?;
+        reth_fs_util::remove_file(marker)?;
+    }
+
+    for path in paths {
+        let is_compact_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.contains(COMPACT_FILE_SUFFIX));
+        if is_compact_file && path.exists() {
+            warn!(target: "provider::static_file", ?path, "Removing file of interrupted static file compaction");
+            reth_fs_util::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the path of the temporary static file that the static file at `path` is rewritten to.
+fn compact_path(path: &Path) -> PathBuf {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(COMPACT_FILE_SUFFIX);
+    PathBuf::from(tmp_path)
+}
+
+/// Replaces the data, offsets and configuration files of the static file at `path` with the ones
+/// of its temporary static file. Files that were already replaced are skipped, so that an
+/// interrupted replacement can be finished.
+///
+/// The configuration file is replaced last, since it's the one the static file is loaded from.
+fn replace_with_compacted(path: &Path) -> ProviderResult<()> {
+    let tmp_path = compact_path(path);
+    for (from, to) in [
+        (tmp_path.clone(), path.to_path_buf()),
+        (
+            tmp_path.with_extension(OFFSETS_FILE_EXTENSION),
+            path.with_extension(OFFSETS_FILE_EXTENSION),
+        ),
+        (
+            tmp_path.with_extension(CONFIG_FILE_EXTENSION),
+            path.with_extension(CONFIG_FILE_EXTENSION),
+        ),
+    ] {
+        if from.exists() {
+            reth_fs_util::rename(&from, to)?;
+        }
+    }
+    sync_parent(path)
+}
+
+/// Syncs the directory of the given path, so that the files created in or renamed into it
+/// persist.
+fn sync_parent(path: &Path) -> ProviderResult<()> {
+    if let Some(parent) = path.parent() {
+        File::open(parent)
+            .and_then(|dir| dir.sync_all())
+            .map_err(|err| FsPathError::open(err, parent))?;
+    }
+    Ok(())
+}
+
+/// Samples up to [`DICTIONARY_SAMPLE_ROWS`] rows, evenly spread over the static file, for
+/// dictionary training. Rows are converted to the receipts layout, if given.
+fn sample_columns(
+    jar: &NippyJar<SegmentHeader>,
+    receipts_layout: Option<ReceiptsLayout>,
+) -> ProviderResult<Vec<Vec<Vec<u8>>>> {
+    let mut columns =
+        vec![Vec::new(); receipts_layout.map_or(jar.columns(), |layout| layout.columns())];
+    let step = (jar.rows() / DICTIONARY_SAMPLE_ROWS).max(1);
+
+    let mut cursor = NippyJarCursor::new(jar).map_err(nippy_jar_error)?;
+    for row_number in (0..jar.rows()).step_by(step) {
+        if let Some(row) = cursor.row_by_number(row_number).map_err(nippy_jar_error)? {
+            let row = match convert_receipt_row(&row, receipts_layout)? {
+                Some(converted) => converted,
+                None => row.into_iter().map(<[u8]>::to_vec).collect(),
+            };
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Converts a row of a receipts static file to the given layout. Returns `None` if the row is
+/// already in the layout, or no layout is given.
+fn convert_receipt_row(
+    row: &[&[u8]],
+    receipts_layout: Option<ReceiptsLayout>,
+) -> ProviderResult<Option<Vec<Vec<u8>>>> {
+    let Some(layout) = receipts_layout.filter(|layout| layout.columns() != row.len()) else {
+        return Ok(None)
+    };
+
+    let receipt = decode_receipt(row)?;
+    Ok(Some(match layout {
+        ReceiptsLayout::Rows => vec![receipt.compress()],
+        ReceiptsLayout::Columns => encode_receipt_columns(receipt).into(),
+    }))
+}
+
+fn file_size(path: &Path) -> ProviderResult<u64> {
+    Ok(reth_fs_util::metadata(path)?.len())
+}
+
+fn nippy_jar_error(err: reth_nippy_jar::NippyJarError) -> ProviderError {
+    ProviderError::NippyJar(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_compactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let write =
+            |name: &str, contents: &str| reth_fs_util::write(dir.path().join(name), contents);
+        let read = |name: &str| reth_fs_util::read_to_string(dir.path().join(name)).ok();
+
+        // Interrupted after the data file was replaced
+        write("finished", "new").unwrap();
+        write("finished.off", "old").unwrap();
+        write("finished.conf", "old").unwrap();
+        write("finished-compact.off", "new").unwrap();
+        write("finished-compact.conf", "new").unwrap();
+        write("finished-compact.done", "").unwrap();
+
+        // Interrupted while writing the temporary static file
+        write("interrupted", "old").unwrap();
+        write("interrupted.off", "old").unwrap();
+        write("interrupted.conf", "old").unwrap();
+        write("interrupted-compact", "new").unwrap();
+        write("interrupted-compact.off", "new").unwrap();
+
+        recover_compactions(dir.path()).unwrap();
+
+        for name in ["finished", "finished.off", "finished.conf"] {
+            assert_eq!(read(name).as_deref(), Some("new"));
+        }
+        for name in ["interrupted", "interrupted.off", "interrupted.conf"] {
+            assert_eq!(read(name).as_deref(), Some("old"));
+        }
+        let mut names = reth_fs_util::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "finished",
+                "finished.conf",
+                "finished.off",
+                "interrupted",
+                "interrupted.conf",
+                "interrupted.off"
+            ]
+        );
+    }
+}
@@ -1,6 +1,12 @@
 use super::{
-    metrics::StaticFileProviderMetrics, LoadedJar, StaticFileJarProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, BLOCKS_PER_STATIC_FILE,
+    bloom::{build_segment_bloom, read_segment_bloom, remove_segment_bloom, write_segment_bloom},
+    compact::{compact_jar, recover_compactions, StaticFileCompaction},
+    metrics::StaticFileProviderMetrics,
+    senders::{
+        read_segment_senders, remove_segment_senders, segment_senders_len, write_segment_senders,
+    },
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, DatabaseProvider,
@@ -12,7 +18,7 @@ use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{iter_static_files, HeaderMask, StaticFileCursor, TransactionMask},
     tables,
 };
 use reth_db_api::{
@@ -24,9 +30,12 @@ use reth_db_api::{
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{
     keccak256,
-    static_file::{find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
+    static_file::{
+        find_fixed_range, Compression, HighestStaticFiles, ReceiptsLayout, SegmentCompression,
+        SegmentHeader, SegmentRangeInclusive,
+    },
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
@@ -76,6 +85,10 @@ impl StaticFileProvider {
     /// Creates a new [`StaticFileProvider`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let provider = Self(Arc::new(StaticFileProviderInner::new(path, access)?));
+        if !provider.is_read_only() {
+            // The storage lock is held at this point, so no compaction is running
+            recover_compactions(provider.directory())?;
+        }
         provider.initialize_index()?;
         Ok(provider)
     }
@@ -117,6 +130,10 @@ pub struct StaticFileProviderInner {
     /// Maintains a map of `StaticFile` writers for each [`StaticFileSegment`]
     writers: DashMap<StaticFileSegment, StaticFileProviderRW>,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Compression of newly created static files, per segment.
+    compression: SegmentCompression,
+    /// Layout of newly created receipts static files.
+    receipts_layout: ReceiptsLayout,
     /// Access rights of the provider.
     access: StaticFileAccess,
     /// Write lock for when access is [`StaticFileAccess::RW`].
@@ -140,6 +157,8 @@ impl StaticFileProviderInner {
             path: path.as_ref().to_path_buf(),
             load_filters: false,
             metrics: None,
+            compression: SegmentCompression::default(),
+            receipts_layout: ReceiptsLayout::default(),
             access,
             _lock_file,
         };
@@ -150,6 +169,16 @@ impl StaticFileProviderInner {
     pub const fn is_read_only(&self) -> bool {
         self.access.is_read_only()
     }
+
+    /// Returns the compression of newly created static files, per segment.
+    pub const fn compression(&self) -> &SegmentCompression {
+        &self.compression
+    }
+
+    /// Returns the layout of newly created receipts static files.
+    pub const fn receipts_layout(&self) -> ReceiptsLayout {
+        self.receipts_layout
+    }
 }
 
 impl StaticFileProvider {
@@ -169,6 +198,22 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Sets the compression of newly created static files, per segment.
+    pub fn with_compression(self, compression: SegmentCompression) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.compression = compression;
+        Self(Arc::new(provider))
+    }
+
+    /// Sets the layout of newly created receipts static files.
+    pub fn with_receipts_layout(self, receipts_layout: ReceiptsLayout) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.receipts_layout = receipts_layout;
+        Self(Arc::new(provider))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -285,6 +330,197 @@ impl StaticFileProvider {
         self.map.remove(&(fixed_block_range_end, segment));
     }
 
+    /// Rewrites all static files of the given segment with the given compression, training zstd
+    /// dictionaries on a sample of each file for [`Compression::ZstdWithDictionary`].
+    ///
+    /// Receipts static files are also converted to the given layout, which migrates existing
+    /// receipts between [`ReceiptsLayout::Rows`] and [`ReceiptsLayout::Columns`]. The layout is
+    /// ignored for other segments.
+    ///
+    /// Must not be called while the segment is being written to, since any open writer of the
+    /// segment is dropped.
+    pub fn compact_segment(
+        &self,
+        segment: StaticFileSegment,
+        compression: Compression,
+        receipts_layout: ReceiptsLayout,
+    ) -> ProviderResult<Vec<StaticFileCompaction>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        self.writers.remove(&segment);
+
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        let mut compactions = Vec::new();
+        for (block_range, _) in static_files.get(&segment).into_iter().flatten() {
+            let fixed_block_range = find_fixed_range(block_range.start());
+            self.remove_cached_provider(segment, fixed_block_range.end());
+
+            let compaction = compact_jar(
+                &self.path.join(segment.filename(&fixed_block_range)),
+                compression,
+                segment.is_receipts().then_some(receipts_layout),
+            )?;
+            if let Some(metrics) = &self.metrics {
+                metrics.record_segment_compaction(segment, &compaction);
+            }
+            compactions.push(compaction);
+        }
+
+        Ok(compactions)
+    }
+
+    /// Moves all static files of the given segment from the `source` directory into the static
+    /// files directory, replacing any static files with the same block ranges.
+    ///
+    /// Returns the block ranges of the moved static files. Must not be called while the segment
+    /// is being written to, since any open writer of the segment is dropped.
+    pub fn import_segment(
+        &self,
+        segment: StaticFileSegment,
+        source: &Path,
+    ) -> ProviderResult<Vec<SegmentRangeInclusive>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        self.writers.remove(&segment);
+
+        let static_files =
+            iter_static_files(source).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        let mut imported = Vec::new();
+        for (block_range, _) in static_files.get(&segment).into_iter().flatten() {
+            let fixed_block_range = find_fixed_range(block_range.start());
+            self.remove_cached_provider(segment, fixed_block_range.end());
+
+            let filename = segment.filename(&fixed_block_range);
+            let jar = NippyJar::<SegmentHeader>::load(&source.join(&filename))
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            let target = NippyJar::<SegmentHeader>::new(
+                jar.columns(),
+                &self.path.join(&filename),
+                jar.user_header().clone(),
+            );
+
+            // The stored senders belong to the replaced transactions
+            remove_segment_senders(target.data_path())?;
+
+            // The configuration file is moved last, since it's what a static file is loaded from.
+            reth_fs_util::rename(jar.data_path(), target.data_path())?;
+            reth_fs_util::rename(jar.offsets_path(), target.offsets_path())?;
+            reth_fs_util::rename(jar.config_path(), target.config_path())?;
+
+            imported.push(fixed_block_range);
+        }
+
+        self.initialize_index()?;
+
+        Ok(imported)
+    }
+
+    /// Returns the senders of the transactions in the range, if they are stored with the
+    /// transactions static files.
+    pub fn persisted_senders(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<Option<Vec<Address>>> {
+        let mut senders = Vec::new();
+        let mut tx = range.start;
+        while tx < range.end {
+            let provider = match self.get_segment_provider_from_transaction(
+                StaticFileSegment::Transactions,
+                tx,
+                None,
+            ) {
+                Ok(provider) => provider,
+                Err(ProviderError::MissingStaticFileTx(_, _)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            let Some(tx_range) = provider.user_header().tx_range().copied() else {
+                return Ok(None)
+            };
+
+            let end = range.end.min(tx_range.end() + 1);
+            let Some(file_senders) = read_segment_senders(
+                provider.data_path(),
+                tx - tx_range.start()..end - tx_range.start(),
+            )?
+            else {
+                return Ok(None)
+            };
+            senders.extend(file_senders);
+            tx = end;
+        }
+
+        Ok(Some(senders))
+    }
+
+    /// Stores the senders of the transactions starting at `first_tx` with the transactions static
+    /// files, so that they don't have to be recovered again.
+    ///
+    /// Senders are only stored up to the end of the transactions static files, and only if the
+    /// senders of all earlier transactions of their static file are stored. Returns the number of
+    /// stored senders.
+    pub fn persist_senders(
+        &self,
+        first_tx: TxNumber,
+        senders: &[Address],
+    ) -> ProviderResult<usize> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let mut stored = 0;
+        while stored < senders.len() {
+            let tx = first_tx + stored as u64;
+            let provider = match self.get_segment_provider_from_transaction(
+                StaticFileSegment::Transactions,
+                tx,
+                None,
+            ) {
+                Ok(provider) => provider,
+                Err(ProviderError::MissingStaticFileTx(_, _)) => break,
+                Err(err) => return Err(err),
+            };
+            let Some(tx_range) = provider.user_header().tx_range().copied() else { break };
+
+            let count = (senders.len() - stored).min((tx_range.end() + 1 - tx) as usize);
+            let chunk = &senders[stored..stored + count];
+            if !write_segment_senders(provider.data_path(), tx - tx_range.start(), chunk)? {
+                break
+            }
+            stored += count;
+        }
+
+        Ok(stored)
+    }
+
+    /// Returns the ranges of transactions of the transactions static files whose senders are not
+    /// stored with them, see [`StaticFileProvider::persist_senders`].
+    pub fn missing_persisted_senders(&self) -> ProviderResult<Vec<Range<TxNumber>>> {
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        let mut missing = Vec::new();
+        for (block_range, tx_range) in
+            static_files.get(&StaticFileSegment::Transactions).into_iter().flatten()
+        {
+            let Some(tx_range) = tx_range else { continue };
+            let data_path = self.path.join(
+                StaticFileSegment::Transactions.filename(&find_fixed_range(block_range.start())),
+            );
+            let start = tx_range.start() + segment_senders_len(&data_path)?;
+            if start <= tx_range.end() {
+                missing.push(start..tx_range.end() + 1);
+            }
+        }
+        missing.sort_unstable_by_key(|range| range.start);
+
+        Ok(missing)
+    }
+
     /// Given a segment and block range it deletes the jar and all files associated with it.
     ///
     /// CAUTION: destructive. Deletes files on disk.
@@ -307,6 +543,8 @@ impl StaticFileProvider {
             jar
         };
 
+        remove_segment_bloom(jar.data_path())?;
+        remove_segment_senders(jar.data_path())?;
         jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
         let mut segment_max_block = None;
@@ -1208,10 +1446,43 @@ impl ReceiptProvider for StaticFileProvider {
         self.fetch_range_with_predicate(
             StaticFileSegment::Receipts,
             to_range(range),
-            |cursor, number| cursor.get_one::<ReceiptMask<Receipt>>(number.into()),
+            |cursor, number| cursor.get_receipt(number),
             |_| true,
         )
     }
+
+    fn receipts_bloom_by_block(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        let provider =
+            match self.get_segment_provider_from_block(StaticFileSegment::Receipts, block, None) {
+                Ok(provider) => provider,
+                Err(ProviderError::MissingStaticFileBlock(_, _)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+        let Some(block_range) = provider.user_header().block_range().copied() else {
+            return Ok(None)
+        };
+
+        let bloom = match read_segment_bloom(provider.data_path())? {
+            Some(bloom) => bloom,
+            // files followed by another file are no longer written to, so their missing bloom is
+            // built once from their receipts. The writer builds the bloom of the latest file.
+            None if self
+                .get_highest_static_file_block(StaticFileSegment::Receipts)
+                .is_some_and(|highest| block_range.end() < highest) =>
+            {
+                let bloom = build_segment_bloom(&provider)?;
+                if !self.access.is_read_only() {
+                    write_segment_bloom(provider.data_path(), &bloom)?;
+                }
+                bloom
+            }
+            None => return Ok(None),
+        };
+        Ok(Some((block_range.start()..=block_range.end(), bloom)))
+    }
 }
 
 impl TransactionsProviderExt for StaticFileProvider {
@@ -1374,12 +1645,21 @@ impl TransactionsProvider for StaticFileProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
+        let range = to_range(range);
+        if let Some(senders) = self.persisted_senders(range.clone())? {
+            return Ok(senders)
+        }
+
         let txes = self.transactions_by_tx_range(range)?;
         TransactionSignedNoHash::recover_signers(&txes, txes.len())
             .ok_or(ProviderError::SenderRecoveryError)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        if let Some(senders) = self.persisted_senders(id..id + 1)? {
+            return Ok(senders.first().copied())
+        }
+
         Ok(self.transaction_by_id_no_hash(id)?.and_then(|tx| tx.recover_signer()))
     }
 }
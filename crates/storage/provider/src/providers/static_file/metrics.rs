@@ -3,7 +3,10 @@ use std::{collections::HashMap, time::Duration};
 use itertools::Itertools;
 use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
+use reth_nippy_jar::CompressionStats;
 use reth_primitives::StaticFileSegment;
+
+use super::StaticFileCompaction;
 use strum::{EnumIter, IntoEnumIterator};
 
 /// Metrics for the static file provider.
@@ -60,6 +63,31 @@ impl StaticFileProviderMetrics {
             .set(entries as f64);
     }
 
+    pub(crate) fn record_segment_compression(
+        &self,
+        segment: StaticFileSegment,
+        stats: CompressionStats,
+    ) {
+        let metrics = self.segments.get(&segment).expect("segment metrics should exist");
+        metrics.uncompressed_bytes.increment(stats.uncompressed_bytes);
+        metrics.compressed_bytes.increment(stats.compressed_bytes);
+        if let Some(ratio) = stats.ratio() {
+            metrics.compression_ratio.set(ratio);
+        }
+    }
+
+    pub(crate) fn record_segment_compaction(
+        &self,
+        segment: StaticFileSegment,
+        compaction: &StaticFileCompaction,
+    ) {
+        self.segments
+            .get(&segment)
+            .expect("segment metrics should exist")
+            .compaction_ratio
+            .set(compaction.compacted_ratio());
+    }
+
     pub(crate) fn record_segment_operation(
         &self,
         segment: StaticFileSegment,
@@ -137,6 +165,15 @@ pub(crate) struct StaticFileSegmentMetrics {
     files: Gauge,
     /// The number of entries for a static file segment
     entries: Gauge,
+    /// The number of bytes appended to a static file segment, before compression
+    uncompressed_bytes: Counter,
+    /// The number of bytes appended to a static file segment, after compression
+    compressed_bytes: Counter,
+    /// The ratio of uncompressed to compressed bytes of the last commit to a static file segment
+    compression_ratio: Gauge,
+    /// The ratio of uncompressed to compressed bytes of the last compacted static file of a
+    /// segment
+    compaction_ratio: Gauge,
 }
 
 #[derive(Metrics)]
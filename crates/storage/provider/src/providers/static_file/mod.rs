@@ -7,8 +7,15 @@ pub use jar::StaticFileJarProvider;
 mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
+mod compact;
+pub use compact::StaticFileCompaction;
+
 mod metrics;
 
+mod bloom;
+
+mod senders;
+
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -57,11 +64,15 @@ impl Deref for LoadedJar {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::create_test_provider_factory, HeaderProvider};
+    use crate::{test_utils::create_test_provider_factory, HeaderProvider, ReceiptProvider};
     use rand::seq::SliceRandom;
     use reth_db::{CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers};
     use reth_db_api::transaction::DbTxMut;
-    use reth_primitives::{static_file::find_fixed_range, B256, U256};
+    use reth_primitives::{
+        logs_bloom,
+        static_file::{find_fixed_range, Compression, ReceiptsLayout},
+        Address, Bytes, Log, Receipt, B256, U256,
+    };
     use reth_testing_utils::generators::{self, random_header_range};
 
     #[test]
@@ -141,4 +152,158 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_compact_segment() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let headers = random_header_range(&mut generators::rng(), 0..100, B256::random());
+
+        let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+        {
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in headers.clone() {
+                let hash = header.hash();
+                writer.append_header(header.unseal(), U256::ZERO, hash).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        for compression in [
+            Compression::Zstd,
+            Compression::ZstdWithDictionary,
+            Compression::Uncompressed,
+            Compression::Lz4,
+        ] {
+            let compactions = manager
+                .compact_segment(StaticFileSegment::Headers, compression, ReceiptsLayout::default())
+                .unwrap();
+            assert_eq!(compactions.len(), 1);
+            assert_eq!(compactions[0].rows, headers.len());
+
+            for header in &headers {
+                assert_eq!(
+                    manager.header_by_number(header.number).unwrap().as_ref(),
+                    Some(header.header())
+                );
+            }
+        }
+
+        // Appending to the compacted static file keeps working
+        let header =
+            random_header_range(&mut generators::rng(), 100..101, B256::random()).remove(0);
+        {
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            let hash = header.hash();
+            writer.append_header(header.clone().unseal(), U256::ZERO, hash).unwrap();
+            writer.commit().unwrap();
+        }
+        assert_eq!(manager.header_by_number(100).unwrap().as_ref(), Some(header.header()));
+    }
+
+    #[test]
+    fn test_receipts_layout_migration() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let manager = StaticFileProvider::read_write(static_files_path.path())
+            .unwrap()
+            .with_receipts_layout(ReceiptsLayout::Columns);
+        let receipts = (0..100u8)
+            .map(|i| Receipt {
+                cumulative_gas_used: i as u64,
+                logs: (0..i % 3)
+                    .map(|j| {
+                        Log::new_unchecked(
+                            Address::with_last_byte(j),
+                            vec![B256::with_last_byte(i); j as usize],
+                            Bytes::from(vec![i; j as usize]),
+                        )
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        {
+            let mut writer = manager.latest_writer(StaticFileSegment::Receipts).unwrap();
+            writer.increment_block(StaticFileSegment::Receipts, 0).unwrap();
+            writer
+                .append_receipts(
+                    receipts.iter().cloned().enumerate().map(|(i, r)| Ok((i as u64, r))),
+                )
+                .unwrap();
+            writer.commit().unwrap();
+        }
+
+        // Receipts read the same from both layouts, and files are migrated between them
+        for layout in [None, Some(ReceiptsLayout::Rows), Some(ReceiptsLayout::Columns)] {
+            if let Some(layout) = layout {
+                let compactions = manager
+                    .compact_segment(
+                        StaticFileSegment::Receipts,
+                        Compression::ZstdWithDictionary,
+                        layout,
+                    )
+                    .unwrap();
+                assert_eq!(compactions[0].rows, receipts.len());
+            }
+
+            for (tx_num, receipt) in receipts.iter().enumerate() {
+                assert_eq!(manager.receipt(tx_num as u64).unwrap().as_ref(), Some(receipt));
+            }
+        }
+    }
+
+    #[test]
+    fn test_receipts_segment_bloom() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+        let log = Log::new_unchecked(
+            Address::with_last_byte(1),
+            vec![B256::with_last_byte(2)],
+            Bytes::new(),
+        );
+
+        {
+            let mut writer = manager.latest_writer(StaticFileSegment::Receipts).unwrap();
+            writer.increment_block(StaticFileSegment::Receipts, 0).unwrap();
+            writer
+                .append_receipt(0, Receipt { logs: vec![log.clone()], ..Default::default() })
+                .unwrap();
+            writer.increment_block(StaticFileSegment::Receipts, 1).unwrap();
+            writer.append_receipt(1, Receipt::default()).unwrap();
+            writer.commit().unwrap();
+        }
+
+        let bloom = logs_bloom([&log]);
+        assert_eq!(manager.receipts_bloom_by_block(1).unwrap(), Some((0..=1, bloom)));
+        assert_eq!(manager.receipts_bloom_by_block(2).unwrap(), None);
+
+        // the bloom keeps covering the receipts that are appended after reopening the file
+        let other = Log::new_unchecked(Address::with_last_byte(3), vec![], Bytes::new());
+        {
+            let mut writer = manager.latest_writer(StaticFileSegment::Receipts).unwrap();
+            writer.increment_block(StaticFileSegment::Receipts, 2).unwrap();
+            writer
+                .append_receipt(2, Receipt { logs: vec![other.clone()], ..Default::default() })
+                .unwrap();
+            writer.commit().unwrap();
+        }
+        assert_eq!(
+            manager.receipts_bloom_by_block(0).unwrap(),
+            Some((0..=2, logs_bloom([&log, &other])))
+        );
+
+        // the writer builds the missing bloom of a file with receipts
+        let data_path = static_files_path
+            .path()
+            .join(StaticFileSegment::Receipts.filename(&find_fixed_range(0)));
+        bloom::remove_segment_bloom(&data_path).unwrap();
+        drop(manager);
+        let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+        assert_eq!(manager.receipts_bloom_by_block(0).unwrap(), None);
+        manager.latest_writer(StaticFileSegment::Receipts).unwrap();
+        assert_eq!(
+            manager.receipts_bloom_by_block(0).unwrap(),
+            Some((0..=2, logs_bloom([&log, &other])))
+        );
+    }
 }
@@ -0,0 +1,147 @@
+//! Recovered senders of the transactions static files.
+//!
+//! Every transactions static file can have a sidecar file with the senders of its transactions,
+//! so that they don't have to be recovered again once the senders are pruned from the database.
+//! The sidecar stores the 20 byte sender addresses in the order of the transactions, starting at
+//! the first transaction of the static file. It may only cover a prefix of the transactions.
+
+use reth_fs_util::FsPathError;
+use reth_primitives::Address;
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The extension of the file that stores the senders of a transactions static file.
+const SENDERS_FILE_EXTENSION: &str = "senders";
+
+/// Size of a sender in the senders file.
+const SENDER_SIZE: u64 = Address::len_bytes() as u64;
+
+/// Returns the path of the senders of the static file.
+fn senders_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(SENDERS_FILE_EXTENSION)
+}
+
+/// Returns the number of senders stored for the static file.
+pub(crate) fn segment_senders_len(data_path: &Path) -> ProviderResult<u64> {
+    let path = senders_path(data_path);
+    match std::fs::metadata(&path) {
+        Ok(metadata) => Ok(metadata.len() / SENDER_SIZE),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(FsPathError::metadata(err, path).into()),
+    }
+}
+
+/// Reads the senders of the given range of transactions of the static file, relative to its first
+/// transaction.
+///
+/// Returns `None` if the senders of the range are not stored.
+pub(crate) fn read_segment_senders(
+    data_path: &Path,
+    range: Range<u64>,
+) -> ProviderResult<Option<Vec<Address>>> {
+    if range.end > segment_senders_len(data_path)? {
+        return Ok(None)
+    }
+
+    let path = senders_path(data_path);
+    let mut bytes = vec![0; ((range.end - range.start) * SENDER_SIZE) as usize];
+    let mut file = File::open(&path).map_err(|err| FsPathError::open(err, &path))?;
+    file.seek(SeekFrom::Start(range.start * SENDER_SIZE))
+        .and_then(|_| file.read_exact(&mut bytes))
+        .map_err(|err| FsPathError::read(err, &path))?;
+
+    Ok(Some(bytes.chunks_exact(SENDER_SIZE as usize).map(Address::from_slice).collect()))
+}
+
+/// Writes the senders of the transactions of the static file, starting at the transaction at
+/// `start`, relative to its first transaction. Any senders stored after `start` are replaced.
+///
+/// Returns `false` without writing if the senders of the transactions before `start` are not
+/// stored, since the senders file can't have gaps.
+pub(crate) fn write_segment_senders(
+    data_path: &Path,
+    start: u64,
+    senders: &[Address],
+) -> ProviderResult<bool> {
+    if start > segment_senders_len(data_path)? {
+        return Ok(false)
+    }
+
+    let path = senders_path(data_path);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(|err| FsPathError::open(err, &path))?;
+    file.set_len(start * SENDER_SIZE)
+        .and_then(|_| file.seek(SeekFrom::End(0)))
+        .and_then(|_| {
+            let mut bytes = Vec::with_capacity(senders.len() * SENDER_SIZE as usize);
+            for sender in senders {
+                bytes.extend_from_slice(sender.as_slice());
+            }
+            file.write_all(&bytes)
+        })
+        .and_then(|_| file.sync_all())
+        .map_err(|err| FsPathError::write(err, &path))?;
+
+    Ok(true)
+}
+
+/// Removes the senders stored after the first `len` transactions of the static file.
+pub(crate) fn truncate_segment_senders(data_path: &Path, len: u64) -> ProviderResult<()> {
+    if segment_senders_len(data_path)? <= len {
+        return Ok(())
+    }
+
+    let path = senders_path(data_path);
+    OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_len(len * SENDER_SIZE))
+        .map_err(|err| FsPathError::write(err, &path))?;
+    Ok(())
+}
+
+/// Removes the senders of the static file, if they exist.
+pub(crate) fn remove_segment_senders(data_path: &Path) -> ProviderResult<()> {
+    let path = senders_path(data_path);
+    if path.exists() {
+        reth_fs_util::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_senders_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("static_file_transactions_0_499999");
+        let senders = (0..4).map(Address::with_last_byte).collect::<Vec<_>>();
+        assert_eq!(read_segment_senders(&data_path, 0..1).unwrap(), None);
+
+        // can't leave a gap
+        assert!(!write_segment_senders(&data_path, 1, &senders).unwrap());
+        assert!(write_segment_senders(&data_path, 0, &senders[..3]).unwrap());
+        assert!(write_segment_senders(&data_path, 2, &senders[2..]).unwrap());
+        assert_eq!(segment_senders_len(&data_path).unwrap(), 4);
+        assert_eq!(read_segment_senders(&data_path, 1..3).unwrap(), Some(senders[1..3].to_vec()));
+        assert_eq!(read_segment_senders(&data_path, 3..5).unwrap(), None);
+
+        truncate_segment_senders(&data_path, 2).unwrap();
+        assert_eq!(read_segment_senders(&data_path, 0..2).unwrap(), Some(senders[..2].to_vec()));
+        assert_eq!(read_segment_senders(&data_path, 0..3).unwrap(), None);
+
+        remove_segment_senders(&data_path).unwrap();
+        assert_eq!(segment_senders_len(&data_path).unwrap(), 0);
+    }
+}
@@ -1,16 +1,24 @@
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
 
 use super::{
-    manager::StaticFileProviderInner, metrics::StaticFileProviderMetrics, StaticFileProvider,
+    bloom::{build_segment_bloom, read_segment_bloom, remove_segment_bloom, write_segment_bloom},
+    manager::StaticFileProviderInner,
+    metrics::StaticFileProviderMetrics,
+    senders::{remove_segment_senders, truncate_segment_senders},
+    StaticFileProvider,
 };
 use dashmap::mapref::one::RefMut;
 use reth_codecs::Compact;
+use reth_db::static_file::encode_receipt_columns;
 use reth_db_api::models::CompactU256;
 use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
-    static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
-    BlockHash, BlockNumber, Header, Receipt, StaticFileSegment, TransactionSignedNoHash, TxNumber,
-    U256,
+    logs_bloom,
+    static_file::{
+        find_fixed_range, Compression, ReceiptsLayout, SegmentHeader, SegmentRangeInclusive,
+    },
+    BlockHash, BlockNumber, Bloom, Header, Receipt, StaticFileSegment, TransactionSignedNoHash,
+    TxNumber, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -41,6 +49,10 @@ pub struct StaticFileProviderRW {
     /// On commit, does the instructed pruning: number of lines, and if it applies, the last block
     /// it ends at.
     prune_on_commit: Option<(u64, Option<BlockNumber>)>,
+    /// The aggregated log bloom of the receipts in the opened file, which is stored on commit.
+    ///
+    /// `None` for the files of other segments.
+    receipts_bloom: Option<Bloom>,
 }
 
 impl StaticFileProviderRW {
@@ -52,6 +64,7 @@ impl StaticFileProviderRW {
         metrics: Option<Arc<StaticFileProviderMetrics>>,
     ) -> ProviderResult<Self> {
         let (writer, data_path) = Self::open(segment, block, reader.clone(), metrics.clone())?;
+        let receipts_bloom = Self::load_receipts_bloom(&writer, &data_path, &reader)?;
         Ok(Self {
             writer,
             data_path,
@@ -59,9 +72,51 @@ impl StaticFileProviderRW {
             reader,
             metrics,
             prune_on_commit: None,
+            receipts_bloom,
         })
     }
 
+    /// Loads the aggregated log bloom of an opened receipts file.
+    ///
+    /// If the file has receipts but no stored bloom, the bloom is built from its receipts and
+    /// stored.
+    fn load_receipts_bloom(
+        writer: &NippyJarWriter<SegmentHeader>,
+        data_path: &Path,
+        reader: &Weak<StaticFileProviderInner>,
+    ) -> ProviderResult<Option<Bloom>> {
+        let header = writer.user_header();
+        if !header.segment().is_receipts() {
+            return Ok(None)
+        }
+        if let Some(bloom) = read_segment_bloom(data_path)? {
+            return Ok(Some(bloom))
+        }
+        if header.tx_range().is_none() {
+            return Ok(Some(Bloom::ZERO))
+        }
+
+        let bloom = build_segment_bloom(
+            &Self::upgrade_provider_to_strong_reference(reader).get_segment_provider_from_block(
+                StaticFileSegment::Receipts,
+                header.expected_block_start(),
+                Some(data_path),
+            )?,
+        )?;
+        write_segment_bloom(data_path, &bloom)?;
+        Ok(Some(bloom))
+    }
+
+    /// Stores the aggregated log bloom of the opened receipts file, if it's tracked.
+    ///
+    /// Blooms only grow, so a bloom stored before its receipts are committed still covers them.
+    fn write_receipts_bloom(&self) -> ProviderResult<()> {
+        if let Some(bloom) = &self.receipts_bloom {
+            write_segment_bloom(&self.data_path, bloom)?;
+        }
+        Ok(())
+    }
+
     fn open(
         segment: StaticFileSegment,
         block: u64,
@@ -85,7 +140,9 @@ impl StaticFileProviderRW {
             ),
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
+                let compression = static_file_provider.compression().get(segment);
+                let receipts_layout = static_file_provider.receipts_layout();
+                (create_jar(segment, &path, block_range, compression, receipts_layout), path)
             }
             Err(err) => return Err(err),
         };
@@ -106,6 +163,11 @@ impl StaticFileProviderRW {
             Err(e) => Err(ProviderError::NippyJar(e.to_string())),
         }?;
 
+        // Healing may have removed transactions whose senders are stored
+        if !reader.is_read_only() && matches!(segment, StaticFileSegment::Transactions) {
+            truncate_segment_senders(&result.1, result.0.rows() as u64)?;
+        }
+
         if let Some(metrics) = &metrics {
             metrics.record_segment_operation(
                 segment,
@@ -158,6 +220,10 @@ impl StaticFileProviderRW {
             self.user_header_mut().prune(pruned_rows);
         }
 
+        if !read_only && matches!(self.user_header().segment(), StaticFileSegment::Transactions) {
+            truncate_segment_senders(&self.data_path, self.writer.rows() as u64)?;
+        }
+
         self.writer.commit().map_err(|error| ProviderError::NippyJar(error.to_string()))?;
 
         // Updates the [SnapshotProvider] manager
@@ -182,15 +248,20 @@ impl StaticFileProviderRW {
         }
 
         if self.writer.is_dirty() {
+            self.write_receipts_bloom()?;
+
             // Commits offsets and new user_header to disk
             self.writer.commit().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
+            let compression_stats = self.writer.take_compression_stats();
             if let Some(metrics) = &self.metrics {
+                let segment = self.writer.user_header().segment();
                 metrics.record_segment_operation(
-                    self.writer.user_header().segment(),
+                    segment,
                     StaticFileProviderOperation::CommitWriter,
                     Some(start.elapsed()),
                 );
+                metrics.record_segment_compression(segment, compression_stats);
             }
 
             debug!(
@@ -214,6 +285,8 @@ impl StaticFileProviderRW {
     pub fn commit_without_sync_all(&mut self) -> ProviderResult<()> {
         let start = Instant::now();
 
+        self.write_receipts_bloom()?;
+
         // Commits offsets and new user_header to disk
         self.writer
             .commit_without_sync_all()
@@ -290,6 +363,8 @@ impl StaticFileProviderRW {
 
                 *self.writer.user_header_mut() =
                     SegmentHeader::new(find_fixed_range(last_block + 1), None, None, segment);
+                self.receipts_bloom =
+                    Self::load_receipts_bloom(&self.writer, &self.data_path, &self.reader)?;
             }
         }
 
@@ -419,10 +494,14 @@ impl StaticFileProviderRW {
         )?;
         self.writer = previous_writer;
         self.data_path = data_path;
+        self.receipts_bloom =
+            Self::load_receipts_bloom(&self.writer, &self.data_path, &self.reader)?;
         NippyJar::<SegmentHeader>::load(&current_path)
             .map_err(|e| ProviderError::NippyJar(e.to_string()))?
             .delete()
             .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        remove_segment_bloom(&current_path)?;
+        remove_segment_senders(&current_path)?;
         Ok(())
     }
 
@@ -448,13 +527,43 @@ impl StaticFileProviderRW {
     ) -> ProviderResult<TxNumber> {
         debug_assert!(self.writer.user_header().segment() == segment);
 
+        self.increment_tx(tx_num);
+        self.append_column(value)?;
+
+        Ok(self.writer.user_header().tx_end().expect("qed"))
+    }
+
+    /// Adds the transaction to the transaction range of the file.
+    fn increment_tx(&mut self, tx_num: TxNumber) {
         if self.writer.user_header().tx_range().is_none() {
             self.writer.user_header_mut().set_tx_range(tx_num, tx_num);
         } else {
             self.writer.user_header_mut().increment_tx();
         }
+    }
 
-        self.append_column(value)?;
+    /// Appends receipt to static file, in the layout of the opened file.
+    ///
+    /// Returns the current [`TxNumber`] as seen in the static file.
+    fn append_receipt_with_tx_number(
+        &mut self,
+        tx_num: TxNumber,
+        receipt: Receipt,
+    ) -> ProviderResult<TxNumber> {
+        self.accrue_receipt_bloom(&receipt);
+
+        if ReceiptsLayout::from_columns(self.writer.columns()) != Some(ReceiptsLayout::Columns) {
+            return self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)
+        }
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::Receipts);
+
+        self.increment_tx(tx_num);
+        for column in encode_receipt_columns(receipt) {
+            self.writer
+                .append_column(Some(Ok(&column)))
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        }
 
         Ok(self.writer.user_header().tx_end().expect("qed"))
     }
@@ -534,7 +643,7 @@ impl StaticFileProviderRW {
         let start = Instant::now();
         self.ensure_no_queued_prune()?;
 
-        let result = self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)?;
+        let result = self.append_receipt_with_tx_number(tx_num, receipt)?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -547,6 +656,13 @@ impl StaticFileProviderRW {
         Ok(result)
     }
 
+    /// Adds the logs of the receipt to the aggregated log bloom of the file, if it's tracked.
+    fn accrue_receipt_bloom(&mut self, receipt: &Receipt) {
+        if let Some(bloom) = &mut self.receipts_bloom {
+            *bloom = *bloom | logs_bloom(receipt.logs.iter());
+        }
+    }
+
     /// Appends multiple receipts to the static file.
     ///
     /// Returns the current [`TxNumber`] as seen in the static file, if any.
@@ -569,7 +685,7 @@ impl StaticFileProviderRW {
 
         for receipt_result in receipts_iter {
             let (tx_num, receipt) = receipt_result?;
-            tx_number = self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)?;
+            tx_number = self.append_receipt_with_tx_number(tx_num, receipt)?;
             count += 1;
         }
 
@@ -651,6 +767,10 @@ impl StaticFileProviderRW {
         debug_assert!(self.writer.user_header().segment() == segment);
 
         self.truncate(segment, to_delete, Some(last_block))?;
+        truncate_segment_senders(
+            &self.data_path,
+            self.writer.user_header().tx_len().unwrap_or_default(),
+        )?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -752,18 +872,18 @@ fn create_jar(
     segment: StaticFileSegment,
     path: &Path,
     expected_block_range: SegmentRangeInclusive,
+    compression: Compression,
+    receipts_layout: ReceiptsLayout,
 ) -> NippyJar<SegmentHeader> {
-    let mut jar = NippyJar::new(
-        segment.columns(),
-        path,
-        SegmentHeader::new(expected_block_range, None, None, segment),
-    );
-
-    // Transaction and Receipt already have the compression scheme used natively in its encoding.
-    // (zstd-dictionary)
-    if segment.is_headers() {
-        jar = jar.with_lz4();
+    let columns = if segment.is_receipts() { receipts_layout.columns() } else { segment.columns() };
+    let jar =
+        NippyJar::new(columns, path, SegmentHeader::new(expected_block_range, None, None, segment));
+
+    match compression {
+        Compression::Lz4 => jar.with_lz4(),
+        // Dictionaries can only be trained on existing data, so they're added when compacting the
+        // static file.
+        Compression::Zstd | Compression::ZstdWithDictionary => jar.with_zstd(false, 0),
+        Compression::Uncompressed => jar,
     }
-
-    jar
 }
@@ -1,12 +1,14 @@
 use std::sync::{Arc, Mutex};
-use tokio::sync::broadcast::{self, Sender};
 
-use crate::{CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions, Chain};
+use crate::{
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions, Chain,
+};
 
 /// A test `ChainEventSubscriptions`
 #[derive(Clone, Debug, Default)]
 pub struct TestCanonStateSubscriptions {
-    canon_notif_tx: Arc<Mutex<Vec<Sender<CanonStateNotification>>>>,
+    canon_notif_tx: Arc<Mutex<Vec<CanonStateNotificationSender>>>,
 }
 
 impl TestCanonStateSubscriptions {
@@ -27,7 +29,8 @@ impl TestCanonStateSubscriptions {
 
 impl CanonStateSubscriptions for TestCanonStateSubscriptions {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
-        let (canon_notif_tx, canon_notif_rx) = broadcast::channel(100);
+        let canon_notif_tx = CanonStateNotificationSender::new(100);
+        let canon_notif_rx = canon_notif_tx.subscribe();
         self.canon_notif_tx.lock().as_mut().unwrap().push(canon_notif_tx);
 
         canon_notif_rx
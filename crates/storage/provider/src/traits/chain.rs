@@ -2,22 +2,22 @@
 
 use crate::{BlockReceipts, Chain};
 use auto_impl::auto_impl;
-use reth_primitives::SealedBlockWithSenders;
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::{BlockNumHash, SealedBlockWithSenders};
 use std::{
+    collections::VecDeque,
+    fmt,
     pin::Pin,
-    sync::Arc,
-    task::{ready, Context, Poll},
+    sync::{Arc, Weak},
+    task::{ready, Context, Poll, Waker},
 };
-use tokio::sync::broadcast;
-use tokio_stream::{wrappers::BroadcastStream, Stream};
+use tokio_stream::Stream;
 use tracing::debug;
 
-/// Type alias for a receiver that receives [`CanonStateNotification`]
-pub type CanonStateNotifications = broadcast::Receiver<CanonStateNotification>;
-
-/// Type alias for a sender that sends [`CanonStateNotification`]
-pub type CanonStateNotificationSender = broadcast::Sender<CanonStateNotification>;
-
 /// A type that allows to register chain related event subscriptions.
 #[auto_impl(&, Arc)]
 pub trait CanonStateSubscriptions: Send + Sync {
@@ -28,18 +28,19 @@ pub trait CanonStateSubscriptions: Send + Sync {
 
     /// Convenience method to get a stream of [`CanonStateNotification`].
     fn canonical_state_stream(&self) -> CanonStateNotificationStream {
-        CanonStateNotificationStream {
-            st: BroadcastStream::new(self.subscribe_to_canonical_state()),
-        }
+        CanonStateNotificationStream { st: self.subscribe_to_canonical_state() }
     }
 }
 
-/// A Stream of [CanonStateNotification].
+/// A Stream of [`CanonStateNotification`].
+///
+/// Notifications skipped because the stream lagged behind are logged and otherwise ignored, the
+/// stream continues with the latest notification. Consumers that must observe every block should
+/// use [`CanonStateSubscriptions::subscribe_to_canonical_state`] instead and resync on
+/// [`CanonStateRecvError::Lagged`].
 #[derive(Debug)]
-#[pin_project::pin_project]
 pub struct CanonStateNotificationStream {
-    #[pin]
-    st: BroadcastStream<CanonStateNotification>,
+    st: CanonStateNotifications,
 }
 
 impl Stream for CanonStateNotificationStream {
@@ -47,18 +48,307 @@ impl Stream for CanonStateNotificationStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            return match ready!(self.as_mut().project().st.poll_next(cx)) {
-                Some(Ok(notification)) => Poll::Ready(Some(notification)),
-                Some(Err(err)) => {
-                    debug!(%err, "canonical state notification stream lagging behind");
+            return match ready!(self.st.poll_recv(cx)) {
+                Ok(notification) => Poll::Ready(Some(notification)),
+                Err(CanonStateRecvError::Lagged(skipped)) => {
+                    debug!(
+                        notifications = skipped.notifications,
+                        committed = skipped.committed.len(),
+                        reverted = skipped.reverted.len(),
+                        "canonical state notification stream lagging behind"
+                    );
                     continue
                 }
-                None => Poll::Ready(None),
+                Err(CanonStateRecvError::Closed) => Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Sends [`CanonStateNotification`]s to all [`CanonStateNotifications`] subscribers.
+///
+/// Every subscriber has its own queue that holds at most `capacity` notifications. If a subscriber
+/// lags behind and its queue is full, the queued notifications are coalesced: they are dropped and
+/// only the blocks they committed and reverted are recorded, see
+/// [`SkippedCanonStateNotifications`]. The subscriber then receives
+/// [`CanonStateRecvError::Lagged`] followed by the latest notification. This bounds the memory
+/// held for slow subscribers, while still telling them exactly which blocks they missed.
+#[derive(Clone)]
+pub struct CanonStateNotificationSender {
+    shared: Arc<Shared>,
+}
+
+impl CanonStateNotificationSender {
+    /// Creates a new sender with the given per subscriber capacity.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be non-zero");
+        Self {
+            shared: Arc::new(Shared {
+                capacity,
+                subscribers: Default::default(),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Creates a new [`CanonStateNotifications`] subscriber.
+    ///
+    /// The subscriber only receives notifications sent after this call.
+    pub fn subscribe(&self) -> CanonStateNotifications {
+        let subscriber = Arc::new(Subscriber {
+            queue: Mutex::new(SubscriberQueue {
+                metrics: CanonStateSubscriberMetrics::new_with_labels(&[(
+                    "subscriber",
+                    DEFAULT_SUBSCRIBER_NAME,
+                )]),
+                ..Default::default()
+            }),
+        });
+        let mut subscribers = self.shared.subscribers.lock();
+        subscribers.push(Arc::downgrade(&subscriber));
+        self.shared.metrics.subscribers.set(subscribers.len() as f64);
+        CanonStateNotifications { subscriber, shared: Arc::downgrade(&self.shared) }
+    }
+
+    /// Sends the notification to all subscribers.
+    ///
+    /// Returns the number of subscribers the notification was sent to, or the notification if
+    /// there are no subscribers.
+    pub fn send(
+        &self,
+        notification: CanonStateNotification,
+    ) -> Result<usize, CanonStateNotification> {
+        let mut subscribers = self.shared.subscribers.lock();
+        subscribers.retain(|subscriber| subscriber.strong_count() > 0);
+        self.shared.metrics.subscribers.set(subscribers.len() as f64);
+
+        let mut sent = 0;
+        for subscriber in subscribers.iter().filter_map(Weak::upgrade) {
+            subscriber.push(notification.clone(), self.shared.capacity);
+            sent += 1;
+        }
+
+        if sent == 0 {
+            return Err(notification)
+        }
+        self.shared.metrics.notifications_sent.increment(1);
+        Ok(sent)
+    }
+
+    /// Returns the number of active subscribers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.subscribers.lock().iter().filter(|s| s.strong_count() > 0).count()
+    }
+}
+
+impl fmt::Debug for CanonStateNotificationSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonStateNotificationSender")
+            .field("capacity", &self.shared.capacity)
+            .field("receiver_count", &self.receiver_count())
+            .finish()
+    }
+}
+
+/// A subscriber that receives [`CanonStateNotification`]s, see
+/// [`CanonStateNotificationSender::subscribe`].
+#[derive(Debug)]
+pub struct CanonStateNotifications {
+    subscriber: Arc<Subscriber>,
+    shared: Weak<Shared>,
+}
+
+impl CanonStateNotifications {
+    /// Sets the name the metrics of this subscriber are reported under.
+    pub fn with_name(self, name: &'static str) -> Self {
+        self.subscriber.queue.lock().metrics =
+            CanonStateSubscriberMetrics::new_with_labels(&[("subscriber", name)]);
+        self
+    }
+
+    /// Returns the number of notifications that are queued for this subscriber.
+    pub fn lag(&self) -> usize {
+        self.subscriber.queue.lock().notifications.len()
+    }
+
+    /// Receives the next notification, if one is queued.
+    pub fn try_recv(&mut self) -> Result<CanonStateNotification, CanonStateTryRecvError> {
+        let mut queue = self.subscriber.queue.lock();
+        match queue.pop() {
+            Some(res) => res.map_err(CanonStateTryRecvError::Lagged),
+            None if self.shared.strong_count() == 0 => Err(CanonStateTryRecvError::Closed),
+            None => Err(CanonStateTryRecvError::Empty),
+        }
+    }
+
+    /// Receives the next notification, waiting until one is sent.
+    ///
+    /// Returns [`CanonStateRecvError::Lagged`] if notifications were skipped because this
+    /// subscriber lagged behind, and [`CanonStateRecvError::Closed`] once all senders were dropped.
+    pub async fn recv(&mut self) -> Result<CanonStateNotification, CanonStateRecvError> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Polls to receive the next notification.
+    pub fn poll_recv(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<CanonStateNotification, CanonStateRecvError>> {
+        let mut queue = self.subscriber.queue.lock();
+        match queue.pop() {
+            Some(res) => Poll::Ready(res.map_err(CanonStateRecvError::Lagged)),
+            None if self.shared.strong_count() == 0 => {
+                Poll::Ready(Err(CanonStateRecvError::Closed))
+            }
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Summary of the notifications a lagging subscriber skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkippedCanonStateNotifications {
+    /// The number of skipped notifications.
+    pub notifications: usize,
+    /// The blocks that were committed by the skipped notifications, in order.
+    pub committed: Vec<BlockNumHash>,
+    /// The blocks that were reverted by the skipped notifications, in order.
+    pub reverted: Vec<BlockNumHash>,
+}
+
+impl SkippedCanonStateNotifications {
+    fn record(&mut self, notification: &CanonStateNotification) {
+        self.notifications += 1;
+        if let Some(old) = notification.reverted() {
+            self.reverted.extend(old.blocks_iter().map(|block| block.num_hash()));
+        }
+        self.committed.extend(notification.committed().blocks_iter().map(|block| block.num_hash()));
+    }
+}
+
+/// Error returned by [`CanonStateNotifications::recv`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CanonStateRecvError {
+    /// All senders were dropped.
+    #[error("canonical state notification channel closed")]
+    Closed,
+    /// The subscriber lagged behind and the contained notifications were skipped.
+    #[error("canonical state notification subscriber lagged behind, skipped {} notifications", .0.notifications)]
+    Lagged(SkippedCanonStateNotifications),
+}
+
+/// Error returned by [`CanonStateNotifications::try_recv`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CanonStateTryRecvError {
+    /// No notification is queued.
+    #[error("no canonical state notification queued")]
+    Empty,
+    /// All senders were dropped.
+    #[error("canonical state notification channel closed")]
+    Closed,
+    /// The subscriber lagged behind and the contained notifications were skipped.
+    #[error("canonical state notification subscriber lagged behind, skipped {} notifications", .0.notifications)]
+    Lagged(SkippedCanonStateNotifications),
+}
+
+/// Name of subscribers that were not given a name with [`CanonStateNotifications::with_name`].
+const DEFAULT_SUBSCRIBER_NAME: &str = "unnamed";
+
+/// State shared by all senders.
+struct Shared {
+    /// Maximum number of notifications queued per subscriber.
+    capacity: usize,
+    /// All subscribers, dropped subscribers are removed on the next send.
+    subscribers: Mutex<Vec<Weak<Subscriber>>>,
+    metrics: CanonStateNotificationMetrics,
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        // wake up all subscribers so they can observe that the channel is closed
+        for subscriber in self.subscribers.get_mut().iter().filter_map(Weak::upgrade) {
+            if let Some(waker) = subscriber.queue.lock().waker.take() {
+                waker.wake();
             }
         }
     }
 }
 
+#[derive(Debug)]
+struct Subscriber {
+    queue: Mutex<SubscriberQueue>,
+}
+
+impl Subscriber {
+    /// Queues the notification, coalescing the queue if it is full.
+    fn push(&self, notification: CanonStateNotification, capacity: usize) {
+        let mut queue = self.queue.lock();
+        if queue.notifications.len() >= capacity {
+            let SubscriberQueue { notifications, skipped, metrics, .. } = &mut *queue;
+            let skipped = skipped.get_or_insert_with(Default::default);
+            metrics.coalesced_notifications.increment(notifications.len() as u64);
+            metrics.lagged.increment(1);
+            for notification in notifications.drain(..) {
+                skipped.record(&notification);
+            }
+        }
+        queue.notifications.push_back(notification);
+        queue.metrics.lag.record(queue.notifications.len() as f64);
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SubscriberQueue {
+    /// Queued notifications.
+    notifications: VecDeque<CanonStateNotification>,
+    /// Notifications that were skipped, these precede all queued notifications.
+    skipped: Option<SkippedCanonStateNotifications>,
+    /// The waker of the task waiting for the next notification.
+    waker: Option<Waker>,
+    metrics: CanonStateSubscriberMetrics,
+}
+
+impl SubscriberQueue {
+    fn pop(&mut self) -> Option<Result<CanonStateNotification, SkippedCanonStateNotifications>> {
+        if let Some(skipped) = self.skipped.take() {
+            return Some(Err(skipped))
+        }
+        self.notifications.pop_front().map(Ok)
+    }
+}
+
+/// Metrics for the [`CanonStateNotificationSender`].
+#[derive(Metrics)]
+#[metrics(scope = "canon_state_notifications")]
+struct CanonStateNotificationMetrics {
+    /// The number of subscribers.
+    subscribers: Gauge,
+    /// The number of notifications sent.
+    notifications_sent: Counter,
+}
+
+/// Metrics for a single [`CanonStateNotifications`] subscriber.
+#[derive(Metrics)]
+#[metrics(scope = "canon_state_notifications.subscriber")]
+struct CanonStateSubscriberMetrics {
+    /// The number of queued notifications, recorded whenever a notification is queued.
+    lag: Histogram,
+    /// How often the queue of the subscriber was full and got coalesced.
+    lagged: Counter,
+    /// The number of notifications that were coalesced.
+    coalesced_notifications: Counter,
+}
+
 /// Chain action that is triggered when a new block is imported or old block is reverted.
 /// and will return all [`crate::ExecutionOutcome`] and
 /// [`reth_primitives::SealedBlockWithSenders`] of both reverted and committed blocks.
@@ -139,3 +429,82 @@ impl CanonStateNotification {
         receipts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedHeader, B256};
+
+    fn commit(number: u64) -> CanonStateNotification {
+        let header = SealedHeader::new(
+            Header { number, ..Default::default() },
+            B256::with_last_byte(number as u8),
+        );
+        let block = SealedBlockWithSenders {
+            block: SealedBlock { header, ..Default::default() },
+            senders: vec![],
+        };
+        CanonStateNotification::Commit {
+            new: Arc::new(Chain::from_block(block, Default::default(), None)),
+        }
+    }
+
+    #[test]
+    fn coalesce_lagging_subscriber() {
+        let sender = CanonStateNotificationSender::new(2);
+        let mut fast = sender.subscribe();
+        let mut slow = sender.subscribe();
+
+        sender.send(commit(1)).unwrap();
+        assert_eq!(fast.try_recv(), Ok(commit(1)));
+        sender.send(commit(2)).unwrap();
+        assert_eq!(fast.try_recv(), Ok(commit(2)));
+        assert_eq!(slow.lag(), 2);
+
+        // the queue of the slow subscriber is full and gets coalesced
+        sender.send(commit(3)).unwrap();
+        assert_eq!(fast.try_recv(), Ok(commit(3)));
+        assert_eq!(
+            slow.try_recv(),
+            Err(CanonStateTryRecvError::Lagged(SkippedCanonStateNotifications {
+                notifications: 2,
+                committed: vec![
+                    BlockNumHash::new(1, B256::with_last_byte(1)),
+                    BlockNumHash::new(2, B256::with_last_byte(2))
+                ],
+                reverted: vec![],
+            }))
+        );
+        assert_eq!(slow.try_recv(), Ok(commit(3)));
+        assert_eq!(slow.try_recv(), Err(CanonStateTryRecvError::Empty));
+    }
+
+    #[tokio::test]
+    async fn recv_until_closed() {
+        let sender = CanonStateNotificationSender::new(1);
+        let mut notifications = sender.subscribe();
+
+        let handle = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Ok(notification) = notifications.recv().await {
+                received.push(notification);
+            }
+            received
+        });
+
+        tokio::task::yield_now().await;
+        sender.send(commit(1)).unwrap();
+        drop(sender);
+
+        let received = handle.await.unwrap();
+        assert_eq!(received, vec![commit(1)]);
+    }
+
+    #[test]
+    fn send_without_subscribers() {
+        let sender = CanonStateNotificationSender::new(1);
+        drop(sender.subscribe());
+        assert_eq!(sender.send(commit(1)), Err(commit(1)));
+        assert_eq!(sender.receiver_count(), 0);
+    }
+}
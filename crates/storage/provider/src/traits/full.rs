@@ -2,8 +2,8 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, StageCheckpointReader,
-    StateProviderFactory, StaticFileProviderFactory, TransactionsProvider,
+    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ReorgJournalReader,
+    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory, TransactionsProvider,
 };
 use reth_db_api::database::Database;
 
@@ -19,6 +19,7 @@ pub trait FullProvider<DB: Database>:
     + ChangeSetReader
     + CanonStateSubscriptions
     + StageCheckpointReader
+    + ReorgJournalReader
     + Clone
     + Unpin
     + 'static
@@ -36,6 +37,7 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + ChangeSetReader
         + CanonStateSubscriptions
         + StageCheckpointReader
+        + ReorgJournalReader
         + Clone
         + Unpin
         + 'static
@@ -51,6 +53,7 @@ pub trait FullRpcProvider:
     + BlockReaderIdExt
     + HeaderProvider
     + TransactionsProvider
+    + ReorgJournalReader
     + Clone
     + Unpin
     + 'static
@@ -64,6 +67,7 @@ impl<T> FullRpcProvider for T where
         + BlockReaderIdExt
         + HeaderProvider
         + TransactionsProvider
+        + ReorgJournalReader
         + Clone
         + Unpin
         + 'static
@@ -1,5 +1,5 @@
 use auto_impl::auto_impl;
-use reth_db_api::models::BlockNumberAddress;
+use reth_db_api::models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress, ShardedKey};
 use reth_primitives::{Address, BlockNumber, B256};
 use reth_storage_errors::provider::ProviderResult;
 use std::{
@@ -38,6 +38,34 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Merges the sealed account history shards that are not full into full shards, walking at
+    /// most `limit` shards from the given key.
+    ///
+    /// If `split_last` is set, last shards that exceed the size of a last shard are split as well.
+    ///
+    /// Returns the number of merged shards and the key to continue at, or `None` if the end of the
+    /// table was reached.
+    fn merge_account_history_shards(
+        &self,
+        from: ShardedKey<Address>,
+        limit: usize,
+        split_last: bool,
+    ) -> ProviderResult<(usize, Option<ShardedKey<Address>>)>;
+
+    /// Merges the sealed storage history shards that are not full into full shards, walking at
+    /// most `limit` shards from the given key.
+    ///
+    /// If `split_last` is set, last shards that exceed the size of a last shard are split as well.
+    ///
+    /// Returns the number of merged shards and the key to continue at, or `None` if the end of the
+    /// table was reached.
+    fn merge_storage_history_shards(
+        &self,
+        from: StorageShardedKey,
+        limit: usize,
+        split_last: bool,
+    ) -> ProviderResult<(usize, Option<StorageShardedKey>)>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -21,7 +21,8 @@ pub use state::StateWriter;
 mod chain;
 pub use chain::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    CanonStateNotifications, CanonStateRecvError, CanonStateSubscriptions, CanonStateTryRecvError,
+    SkippedCanonStateNotifications,
 };
 
 mod spec;
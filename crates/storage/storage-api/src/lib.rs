@@ -31,6 +31,9 @@ pub use prune_checkpoint::*;
 mod receipts;
 pub use receipts::*;
 
+mod reorg_journal;
+pub use reorg_journal::*;
+
 mod requests;
 pub use requests::*;
 
@@ -1,7 +1,9 @@
 use crate::BlockIdReader;
-use reth_primitives::{BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, TxHash, TxNumber};
+use reth_primitives::{
+    BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag, Bloom, Receipt, TxHash, TxNumber,
+};
 use reth_storage_errors::provider::ProviderResult;
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
 
 /// Client trait for fetching [Receipt] data .
 #[auto_impl::auto_impl(&, Arc)]
@@ -26,6 +28,35 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>>;
+
+    /// Get the receipts of every block in the inclusive range, grouped by block.
+    ///
+    /// Note: returns only the receipts of the blocks up to the first one that is not found.
+    fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        let mut receipts = Vec::new();
+        for number in range {
+            let Some(block_receipts) = self.receipts_by_block(number.into())? else { break };
+            receipts.push(block_receipts);
+        }
+        Ok(receipts)
+    }
+
+    /// Get the aggregated log bloom of a range of blocks that contains the given block, along
+    /// with that range.
+    ///
+    /// The bloom contains the logs of all receipts in the range, so a log filter that doesn't
+    /// match it can skip the whole range.
+    ///
+    /// Returns `None` if no such bloom is available for the block.
+    fn receipts_bloom_by_block(
+        &self,
+        _block: BlockNumber,
+    ) -> ProviderResult<Option<(RangeInclusive<BlockNumber>, Bloom)>> {
+        Ok(None)
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -0,0 +1,30 @@
+use reth_db_api::models::StoredReorg;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The maximum number of reorgs kept in the reorg journal. The oldest reorgs are removed once a
+/// reorg is appended to a full journal.
+pub const MAX_REORG_JOURNAL_LEN: u64 = 1024;
+
+/// The trait for fetching entries of the reorg journal.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgJournalReader: Send + Sync {
+    /// Returns up to `limit` of the most recent reorgs with their sequence number, newest first.
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>>;
+
+    /// Returns the reorgs with a sequence number of at least `first_id`, oldest first.
+    fn reorgs_since(&self, first_id: u64) -> ProviderResult<Vec<(u64, StoredReorg)>>;
+
+    /// Returns the most recent reorg with its sequence number.
+    fn latest_reorg(&self) -> ProviderResult<Option<(u64, StoredReorg)>> {
+        Ok(self.reorg_history(1)?.pop())
+    }
+}
+
+/// The trait for appending to the reorg journal.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgJournalWriter: Send + Sync {
+    /// Appends the reorg to the journal and returns its sequence number.
+    ///
+    /// Removes the oldest reorgs if the journal holds more than [`MAX_REORG_JOURNAL_LEN`] reorgs.
+    fn append_reorg(&self, reorg: StoredReorg) -> ProviderResult<u64>;
+}
@@ -78,6 +78,15 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
     ///
     /// Returns None if the transaction is not found.
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>>;
+
+    /// Returns the highest block whose transactions were pruned from the transaction hash index,
+    /// if the transaction with the given hash is in one of the pruned blocks.
+    ///
+    /// Used for a transaction that is not found by hash, to tell a pruned transaction from an
+    /// unknown one.
+    fn transaction_index_pruned_to(&self, _hash: TxHash) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
 }
 
 ///  Client trait for fetching additional [TransactionSigned] related data.
@@ -107,6 +107,19 @@ impl Layers {
         self.inner.push(layer);
         Ok(guard)
     }
+
+    /// Adds a layer that exports spans to an OpenTelemetry collector.
+    ///
+    /// # Arguments
+    /// * `config` - The endpoint, sampling and filter of the export.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    #[cfg(feature = "otlp")]
+    pub(crate) fn otlp(&mut self, config: &crate::OtlpConfig) -> eyre::Result<()> {
+        self.inner.push(crate::otlp::layer(config)?);
+        Ok(())
+    }
 }
 
 /// Holds configuration information for file logging.
@@ -166,7 +179,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
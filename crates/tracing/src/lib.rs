@@ -50,11 +50,15 @@ pub use tracing_subscriber;
 // Re-export LogFormat
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard};
+#[cfg(feature = "otlp")]
+pub use otlp::{shutdown_otlp, OtlpConfig};
 
 pub use test_tracer::TestTracer;
 
 mod formatter;
 mod layers;
+#[cfg(feature = "otlp")]
+mod otlp;
 mod test_tracer;
 
 use crate::layers::Layers;
@@ -71,6 +75,8 @@ pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    #[cfg(feature = "otlp")]
+    otlp: Option<OtlpConfig>,
 }
 
 impl RethTracer {
@@ -79,7 +85,13 @@ impl RethTracer {
     ///  Initializes with default stdout layer configuration.
     ///  Journald and file layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self {
+            stdout: LayerInfo::default(),
+            journald: None,
+            file: None,
+            #[cfg(feature = "otlp")]
+            otlp: None,
+        }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -109,6 +121,16 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets the configuration of the export of spans to an OpenTelemetry collector.
+    ///
+    ///  # Arguments
+    ///  * `config` - The `OtlpConfig` to use for the export.
+    #[cfg(feature = "otlp")]
+    pub fn with_otlp(mut self, config: OtlpConfig) -> Self {
+        self.otlp = Some(config);
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -210,6 +232,11 @@ impl Tracer for RethTracer {
             None
         };
 
+        #[cfg(feature = "otlp")]
+        if let Some(config) = &self.otlp {
+            layers.otlp(config)?;
+        }
+
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
         let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
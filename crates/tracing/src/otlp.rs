@@ -0,0 +1,89 @@
+//! Export of spans to an OpenTelemetry collector over OTLP.
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    runtime,
+    trace::{self, Sampler},
+    Resource,
+};
+use std::sync::OnceLock;
+use tracing_subscriber::{Layer, Registry};
+
+use crate::layers::{build_env_filter, BoxedLayer};
+
+/// The runtime the batch exporter runs on.
+///
+/// Tracing is initialized before the runtime of the node is created, so the exporter gets a
+/// runtime of its own.
+static EXPORTER_RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+/// Configuration of the export of spans over OTLP.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub endpoint: String,
+    /// The ratio of traces that are exported, between 0 and 1.
+    pub sample_ratio: f64,
+    /// The filter directives for the exported spans.
+    pub filter: String,
+}
+
+impl OtlpConfig {
+    /// Creates a new configuration that exports all traces to the endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self { endpoint: endpoint.into(), sample_ratio: 1.0, filter: "debug".to_string() }
+    }
+
+    /// Sets the ratio of traces that are exported.
+    pub const fn with_sample_ratio(mut self, sample_ratio: f64) -> Self {
+        self.sample_ratio = sample_ratio;
+        self
+    }
+
+    /// Sets the filter directives for the exported spans.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+}
+
+/// Builds the layer that exports the spans to the collector.
+pub(crate) fn layer(config: &OtlpConfig) -> eyre::Result<BoxedLayer<Registry>> {
+    let runtime = match EXPORTER_RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("otlp-exporter")
+                .enable_all()
+                .build()?;
+            EXPORTER_RUNTIME.get_or_init(|| runtime)
+        }
+    };
+    let _guard = runtime.enter();
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&config.endpoint))
+        .with_trace_config(
+            trace::config()
+                .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                    config.sample_ratio,
+                ))))
+                .with_resource(Resource::new([KeyValue::new("service.name", "reth")])),
+        )
+        .install_batch(runtime::Tokio)?;
+
+    let filter = build_env_filter(None, &config.filter)?;
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer).with_filter(filter).boxed())
+}
+
+/// Exports the spans that are still buffered.
+///
+/// This should be called before the process exits.
+pub fn shutdown_otlp() {
+    if EXPORTER_RUNTIME.get().is_some() {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
@@ -0,0 +1,194 @@
+//! Computation of the KZG proofs of blob sidecars that are submitted without them.
+
+use crate::error::Eip4844PoolTransactionError;
+use reth_primitives::{
+    kzg::{self, KzgProof, KzgSettings},
+    BlobTransactionSidecar, BlobTransactionValidationError,
+};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The default maximum number of sidecars whose proofs are computed concurrently.
+pub const DEFAULT_MAX_BLOB_PROOF_COMPUTATIONS: usize = 4;
+
+/// Computes the missing KZG proofs of the blob sidecars of local transactions.
+///
+/// Some toolchains produce sidecars with blobs and commitments but without proofs, which fail the
+/// blob validation. If enabled, the validator computes the proofs of such sidecars before the
+/// blobs are validated. Computing proofs is expensive, so the number of sidecars whose proofs are
+/// computed concurrently is bounded and sidecars above the limit are rejected.
+///
+/// This is cheap to clone, all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct BlobProofComputation {
+    inner: Arc<BlobProofComputationInner>,
+}
+
+#[derive(Debug)]
+struct BlobProofComputationInner {
+    /// Whether missing proofs are computed.
+    enabled: AtomicBool,
+    /// The number of sidecars whose proofs are currently computed.
+    active: AtomicUsize,
+    /// The maximum number of sidecars whose proofs are computed concurrently.
+    max_concurrent: usize,
+}
+
+impl BlobProofComputation {
+    /// Creates a new instance that computes the proofs of at most `max_concurrent` sidecars at
+    /// the same time.
+    pub fn new(enabled: bool, max_concurrent: usize) -> Self {
+        Self {
+            inner: Arc::new(BlobProofComputationInner {
+                enabled: AtomicBool::new(enabled),
+                active: AtomicUsize::new(0),
+                max_concurrent,
+            }),
+        }
+    }
+
+    /// Returns `true` if missing proofs are computed.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the computation of missing proofs.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.inner.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of sidecars whose proofs are computed concurrently.
+    pub fn max_concurrent(&self) -> usize {
+        self.inner.max_concurrent
+    }
+
+    /// Computes the proofs of a sidecar that has blobs but no proofs.
+    ///
+    /// Returns `Ok(false)` if the computation is disabled or the sidecar doesn't lack proofs, in
+    /// which case the sidecar is left as is.
+    pub fn compute_missing_proofs(
+        &self,
+        sidecar: &mut BlobTransactionSidecar,
+        settings: &KzgSettings,
+    ) -> Result<bool, Eip4844PoolTransactionError> {
+        if !self.is_enabled() ||
+            sidecar.blobs.is_empty() ||
+            !sidecar.proofs.is_empty() ||
+            sidecar.blobs.len() != sidecar.commitments.len()
+        {
+            return Ok(false)
+        }
+
+        let _permit = self.try_acquire().ok_or(
+            Eip4844PoolTransactionError::TooManyBlobProofComputations {
+                permitted: self.max_concurrent(),
+            },
+        )?;
+        let proofs = sidecar
+            .blobs
+            .iter()
+            .zip(&sidecar.commitments)
+            .map(|(blob, commitment)| {
+                let blob = kzg::Blob::from_bytes(blob.as_slice())?;
+                let commitment = kzg::Bytes48::from_bytes(commitment.as_slice())?;
+                let proof = KzgProof::compute_blob_kzg_proof(&blob, &commitment, settings)?;
+                Ok((*proof.to_bytes()).into())
+            })
+            .collect::<Result<_, kzg::Error>>()
+            .map_err(|err| {
+                Eip4844PoolTransactionError::InvalidEip4844Blob(
+                    BlobTransactionValidationError::KZGError(err),
+                )
+            })?;
+        sidecar.proofs = proofs;
+        Ok(true)
+    }
+
+    /// Reserves a slot for a computation, returns `None` if all slots are taken.
+    fn try_acquire(&self) -> Option<BlobProofComputationPermit<'_>> {
+        self.inner
+            .active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < self.inner.max_concurrent).then_some(active + 1)
+            })
+            .ok()
+            .map(|_| BlobProofComputationPermit(&self.inner.active))
+    }
+}
+
+impl Default for BlobProofComputation {
+    fn default() -> Self {
+        Self::new(false, DEFAULT_MAX_BLOB_PROOF_COMPUTATIONS)
+    }
+}
+
+/// Releases the reserved slot of a computation when dropped.
+struct BlobProofComputationPermit<'a>(&'a AtomicUsize);
+
+impl Drop for BlobProofComputationPermit<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthPoolTransaction, EthPooledTransaction, PoolTransaction};
+    use reth_primitives::{
+        kzg::Blob, transaction::generate_blob_sidecar, Address,
+        PooledTransactionsElementEcRecovered, Signature, Transaction, TransactionSigned, TxEip4844,
+    };
+    use revm::primitives::EnvKzgSettings;
+
+    #[test]
+    fn computes_missing_proofs() {
+        let sidecar = generate_blob_sidecar(vec![Blob::new([1; 131072])]);
+        let mut without_proofs = BlobTransactionSidecar { proofs: Vec::new(), ..sidecar.clone() };
+        let settings = EnvKzgSettings::Default;
+
+        let computation = BlobProofComputation::new(false, 1);
+        assert!(!computation.compute_missing_proofs(&mut without_proofs, settings.get()).unwrap());
+        assert!(without_proofs.proofs.is_empty());
+
+        computation.set_enabled(true);
+        let permit = computation.try_acquire();
+        assert!(matches!(
+            computation.compute_missing_proofs(&mut without_proofs, settings.get()),
+            Err(Eip4844PoolTransactionError::TooManyBlobProofComputations { permitted: 1 })
+        ));
+        drop(permit);
+
+        assert!(computation.compute_missing_proofs(&mut without_proofs, settings.get()).unwrap());
+        assert_eq!(without_proofs, sidecar);
+        assert!(computation.try_acquire().is_some());
+    }
+
+    #[test]
+    fn updates_encoded_length_with_proofs() {
+        let sidecar = generate_blob_sidecar(vec![Blob::new([1; 131072])]);
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip4844(TxEip4844::default()),
+            Signature::default(),
+        )
+        .with_signer(Address::ZERO);
+        let pooled = |sidecar| {
+            EthPooledTransaction::from(
+                PooledTransactionsElementEcRecovered::try_from_blob_transaction(
+                    tx.clone(),
+                    sidecar,
+                )
+                .unwrap(),
+            )
+        };
+
+        let mut transaction =
+            pooled(BlobTransactionSidecar { proofs: Vec::new(), ..sidecar.clone() });
+        let encoded_length = transaction.encoded_length();
+        transaction.update_encoded_length(&sidecar);
+        assert!(transaction.encoded_length() > encoded_length);
+        assert_eq!(transaction.encoded_length(), pooled(sidecar).encoded_length());
+    }
+}
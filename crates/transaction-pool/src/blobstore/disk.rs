@@ -1,6 +1,9 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::{
+    blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize},
+    metrics::BlobStoreCacheMetrics,
+};
 use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
@@ -139,6 +142,7 @@ struct DiskFileBlobStoreInner {
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
+    metrics: BlobStoreCacheMetrics,
 }
 
 impl DiskFileBlobStoreInner {
@@ -150,6 +154,7 @@ impl DiskFileBlobStoreInner {
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
+            metrics: Default::default(),
         }
     }
 
@@ -234,8 +239,10 @@ impl DiskFileBlobStoreInner {
     /// Retrieves the blob for the given transaction hash from the blob cache or disk.
     fn get_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         if let Some(blob) = self.blob_cache.lock().get(&tx) {
+            self.metrics.blobstore_cache_hits.increment(1);
             return Ok(Some(blob.clone()))
         }
+        self.metrics.blobstore_cache_misses.increment(1);
         let blob = self.read_one(tx)?;
         if let Some(blob) = &blob {
             self.blob_cache.lock().insert(tx, blob.clone());
@@ -341,6 +348,8 @@ impl DiskFileBlobStoreInner {
                 }
             }
         }
+        self.metrics.blobstore_cache_hits.increment(res.len() as u64);
+        self.metrics.blobstore_cache_misses.increment(cache_miss.len() as u64);
         if cache_miss.is_empty() {
             return Ok(res)
         }
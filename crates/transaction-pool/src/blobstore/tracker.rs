@@ -46,6 +46,14 @@ impl BlobStoreCanonTracker {
         self.add_blocks(blob_txs);
     }
 
+    /// Stops tracking the blocks of a reorged chain, starting at its first block.
+    ///
+    /// The blob transactions of reorged blocks are not included anymore, so their blobs must not
+    /// be deleted once the block numbers are finalized, even if the new chain is shorter.
+    pub fn remove_reorged_blocks(&mut self, first_reorged_block: BlockNumber) {
+        self.blob_txs_in_blocks.split_off(&first_reorged_block);
+    }
+
     /// Invoked when a block is finalized.
     ///
     /// This returns all blob transactions that were included in blocks that are now finalized.
@@ -98,4 +106,20 @@ mod tests {
             BlobStoreUpdates::Finalized(block2.into_iter().chain(block3).collect::<Vec<_>>())
         );
     }
+
+    #[test]
+    fn test_reorged_blocks_are_not_finalized() {
+        let mut tracker = BlobStoreCanonTracker::default();
+
+        let block1 = vec![B256::random()];
+        tracker.add_block(1, block1.clone());
+        tracker.add_block(2, vec![B256::random()]);
+        tracker.add_block(3, vec![B256::random()]);
+
+        // reorg to a shorter chain that has no blob transactions in block 2
+        tracker.remove_reorged_blocks(2);
+        tracker.add_block(2, Vec::new());
+
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block1));
+    }
 }
@@ -1,6 +1,9 @@
-use crate::{PoolSize, TransactionOrigin};
+use crate::{
+    blob_proofs::BlobProofComputation, policy::AddressListPolicy, tip_floor::TipFloor, PoolSize,
+    TransactionOrigin,
+};
 use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
@@ -10,6 +13,13 @@ pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 /// The default maximum allowed size of the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 
+/// The default time after which the queued transactions of an inactive sender expire, compatible
+/// with geth.
+pub const DEFAULT_QUEUED_TRANSACTION_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// The default time after which the basefee transactions of an inactive sender expire.
+pub const DEFAULT_BASEFEE_TRANSACTION_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -36,6 +46,31 @@ pub struct PoolConfig {
     /// How to handle locally received transactions:
     /// [`TransactionOrigin::Local`](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// The sender and recipient lists that transactions are checked against before validation.
+    ///
+    /// The lists only apply if the policy is passed to the validator, see
+    /// [`EthTransactionValidatorBuilder::with_transaction_policy`](crate::validate::EthTransactionValidatorBuilder::with_transaction_policy).
+    pub transaction_policy: AddressListPolicy,
+    /// Whether the missing KZG proofs of the blob sidecars of local transactions are computed.
+    ///
+    /// This only applies if it's passed to the validator, see
+    /// [`EthTransactionValidatorBuilder::with_blob_proof_computation`](crate::validate::EthTransactionValidatorBuilder::with_blob_proof_computation).
+    pub blob_proof_computation: BlobProofComputation,
+    /// The minimum tip that non-local transactions must pay to be accepted into the pool.
+    ///
+    /// This only applies if it's passed to the validator, see
+    /// [`EthTransactionValidatorBuilder::with_tip_floor`](crate::validate::EthTransactionValidatorBuilder::with_tip_floor).
+    pub tip_floor: TipFloor,
+    /// How long the transactions of a sender are kept in the queued sub-pool after the last
+    /// transaction of the sender was added to the pool.
+    ///
+    /// Local transactions don't expire, unless local exemptions are disabled.
+    pub queued_lifetime: Duration,
+    /// How long the transactions of a sender are kept in the basefee sub-pool after the last
+    /// transaction of the sender was added to the pool.
+    ///
+    /// Local transactions don't expire, unless local exemptions are disabled.
+    pub basefee_lifetime: Duration,
 }
 
 impl PoolConfig {
@@ -59,6 +94,11 @@ impl Default for PoolConfig {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
+            transaction_policy: Default::default(),
+            blob_proof_computation: Default::default(),
+            tip_floor: Default::default(),
+            queued_lifetime: DEFAULT_QUEUED_TRANSACTION_LIFETIME,
+            basefee_lifetime: DEFAULT_BASEFEE_TRANSACTION_LIFETIME,
         }
     }
 }
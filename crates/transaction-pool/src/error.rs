@@ -166,6 +166,13 @@ pub enum Eip4844PoolTransactionError {
     /// would introduce gap in the nonce sequence.
     #[error("nonce too high")]
     Eip4844NonceGap,
+    /// Thrown if the proofs of a sidecar that was submitted without proofs can't be computed,
+    /// because too many proofs are computed concurrently.
+    #[error("too many concurrent blob proof computations, permitted {permitted}")]
+    TooManyBlobProofComputations {
+        /// Number of sidecars whose proofs can be computed concurrently
+        permitted: usize,
+    },
 }
 
 /// Represents errors that can happen when validating transactions for the pool
@@ -205,6 +212,19 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the transaction is rejected by the
+    /// [`TransactionPolicy`](crate::policy::TransactionPolicy) of the validator.
+    #[error("transaction rejected by policy")]
+    RejectedByPolicy,
+    /// Thrown if the tip of the transaction is below the minimum tip of the
+    /// [`TipFloor`](crate::tip_floor::TipFloor).
+    #[error("transaction tip {tip} below minimum tip {floor}")]
+    TipBelowFloor {
+        /// The effective tip of the transaction at the current base fee.
+        tip: u128,
+        /// The minimum tip.
+        floor: u128,
+    },
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -261,6 +281,14 @@ impl InvalidPoolTransactionError {
             }
             Self::IntrinsicGasTooLow => true,
             Self::Overdraft => false,
+            Self::RejectedByPolicy => {
+                // local setting
+                false
+            }
+            Self::TipBelowFloor { .. } => {
+                // local setting that depends on recent blocks
+                false
+            }
             Self::Other(err) => err.is_bad_transaction(),
             Self::Eip4844(eip4844_err) => {
                 match eip4844_err {
@@ -286,6 +314,10 @@ impl InvalidPoolTransactionError {
                         // this is a malformed transaction and should not be sent over the network
                         true
                     }
+                    Eip4844PoolTransactionError::TooManyBlobProofComputations { .. } => {
+                        // this is only reachable for local transactions and depends on the load
+                        false
+                    }
                 }
             }
         }
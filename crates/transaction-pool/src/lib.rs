@@ -155,16 +155,18 @@ use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
 use reth_provider::StateProviderFactory;
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, sync::Arc, time::Instant};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_BASEFEE_TRANSACTION_LIFETIME, DEFAULT_PRICE_BUMP,
+        DEFAULT_QUEUED_TRANSACTION_LIFETIME, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
@@ -179,11 +181,14 @@ pub use crate::{
     },
 };
 
+pub mod blob_proofs;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
+pub mod policy;
 pub mod pool;
+pub mod tip_floor;
 pub mod validate;
 
 pub mod blobstore;
@@ -253,7 +258,9 @@ where
     ) -> (TxHash, TransactionValidationOutcome<V::Transaction>) {
         let hash = *transaction.hash();
 
+        let start = Instant::now();
         let outcome = self.pool.validator().validate_transaction(origin, transaction).await;
+        self.pool.validation_metrics().validation_duration_seconds.record(start.elapsed());
 
         (hash, outcome)
     }
@@ -555,6 +562,10 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.remove_expired_transactions()
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
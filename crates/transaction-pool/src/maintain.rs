@@ -7,6 +7,7 @@ use crate::{
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
     BlockInfo,
 };
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -16,7 +17,7 @@ use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
     IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    TryFromRecoveredTransaction, TxHash,
 };
 use reth_provider::{
     BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ProviderError,
@@ -25,11 +26,15 @@ use reth_provider::{
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::oneshot,
+    time::{self, MissedTickBehavior},
 };
-use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
@@ -44,25 +49,87 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// How often the transactions of inactive senders are checked for expiry, see
+    /// [`PoolConfig::queued_lifetime`](crate::PoolConfig::queued_lifetime).
+    ///
+    /// Must be non-zero. Default: 1 minute
+    pub expiry_interval: Duration,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self {
+            max_update_depth: 64,
+            max_reload_accounts: 100,
+            expiry_interval: Duration::from_secs(60),
+        }
     }
 }
 
+/// The default maximum number of backed up local transactions per account.
+pub const DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT: usize = 64;
+
+/// The default duration that local transactions are backed up for.
+pub const DEFAULT_LOCAL_TXS_BACKUP_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// The default interval at which the local transactions are backed up while the node is running.
+pub const DEFAULT_LOCAL_TXS_BACKUP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 /// Settings for local transaction backup task
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// The maximum number of backed up transactions per account, the transactions with the
+    /// lowest nonces are kept.
+    pub account_limit: usize,
+    /// The duration after which a backed up transaction expires, measured from the first time it
+    /// was backed up.
+    pub lifetime: Duration,
+    /// The interval at which the backup is written while the node is running, so that the local
+    /// transactions survive a crash. The backup is always written on shutdown.
+    pub backup_interval: Option<Duration>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self {
+            transactions_path: Some(transactions_path),
+            account_limit: DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT,
+            lifetime: DEFAULT_LOCAL_TXS_BACKUP_LIFETIME,
+            backup_interval: Some(DEFAULT_LOCAL_TXS_BACKUP_INTERVAL),
+        }
+    }
+
+    /// Sets the maximum number of backed up transactions per account.
+    pub const fn with_account_limit(mut self, account_limit: usize) -> Self {
+        self.account_limit = account_limit;
+        self
+    }
+
+    /// Sets the duration after which a backed up transaction expires.
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets the interval at which the backup is written while the node is running, `None` only
+    /// writes it on shutdown.
+    pub const fn with_backup_interval(mut self, backup_interval: Option<Duration>) -> Self {
+        self.backup_interval = backup_interval;
+        self
+    }
+}
+
+impl Default for LocalTransactionBackupConfig {
+    fn default() -> Self {
+        Self {
+            transactions_path: None,
+            account_limit: DEFAULT_LOCAL_TXS_BACKUP_ACCOUNT_LIMIT,
+            lifetime: DEFAULT_LOCAL_TXS_BACKUP_LIFETIME,
+            backup_interval: Some(DEFAULT_LOCAL_TXS_BACKUP_INTERVAL),
+        }
     }
 }
 
@@ -102,7 +169,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, expiry_interval } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -113,7 +180,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             pending_basefee: latest
                 .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(latest.timestamp + 12))
                 .unwrap_or_default(),
-            pending_blob_fee: latest.next_block_blob_fee(),
+            pending_blob_fee: latest.next_block_blob_fee_with_params(
+                chain_spec.blob_params_at_timestamp(latest.timestamp + 12),
+            ),
         };
         pool.set_block_info(info);
     }
@@ -134,6 +203,10 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
+    // the interval at which transactions of inactive senders are expired
+    let mut expiry_interval = time::interval(expiry_interval);
+    expiry_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
     // The update loop that waits for new blocks and reorgs and performs pool updated
     // Listen for new chain events and derive the update action for the pool
     loop {
@@ -207,13 +280,17 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         // outcomes of the futures we are waiting on
         let mut event = None;
         let mut reloaded = None;
+        let mut expire = false;
 
-        // select of account reloads and new canonical state updates which should arrive at the rate
-        // of the block time (12s)
+        // select of account reloads, expiry ticks and new canonical state updates which should
+        // arrive at the rate of the block time (12s)
         tokio::select! {
             res = &mut reload_accounts_fut =>  {
                 reloaded = Some(res);
             }
+            _ = expiry_interval.tick() => {
+                expire = true;
+            }
             ev = events.next() =>  {
                  if ev.is_none() {
                     // the stream ended, we are done
@@ -245,6 +322,14 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             None => {}
         }
 
+        // remove the parked transactions of inactive senders
+        if expire {
+            let expired = pool.remove_expired_transactions();
+            if !expired.is_empty() {
+                debug!(target: "txpool", expired = expired.len(), "removed expired transactions");
+            }
+        }
+
         // handle the new block or reorg
         let Some(event) = event else { continue };
         match event {
@@ -271,7 +356,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         chain_spec.base_fee_params_at_timestamp(new_tip.timestamp + 12),
                     )
                     .unwrap_or_default();
-                let pending_block_blob_fee = new_tip.next_block_blob_fee();
+                let pending_block_blob_fee = new_tip.next_block_blob_fee_with_params(
+                    chain_spec.blob_params_at_timestamp(new_tip.timestamp + 12),
+                );
 
                 // we know all changed account in the new chain
                 let new_changed_accounts: HashSet<_> =
@@ -314,9 +401,13 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
 
                 // update the pool then re-inject the pruned transactions
                 // find all transactions that were mined in the old chain but not in the new chain
-                let pruned_old_transactions = old_blocks
+                let reorged_transactions = old_blocks
                     .transactions_ecrecovered()
                     .filter(|tx| !new_mined_transactions.contains(&tx.hash))
+                    .collect::<Vec<_>>();
+                let reorged_transactions_len = reorged_transactions.len();
+                let pruned_old_transactions = reorged_transactions
+                    .into_iter()
                     .filter_map(|tx| {
                         if tx.is_eip4844() {
                             // reorged blobs no longer include the blob, which is necessary for
@@ -343,6 +434,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         }
                     })
                     .collect::<Vec<_>>();
+                metrics.inc_dropped_reorged_transactions(
+                    reorged_transactions_len - pruned_old_transactions.len(),
+                );
 
                 // update the pool first
                 let update = CanonicalStateUpdate {
@@ -361,10 +455,31 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // Note: we no longer know if the tx was local or external
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
+                //
+                // The transactions are validated again against the state of the new tip, so
+                // transactions whose nonce was used by the new chain are rejected
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let mut resurrected = 0;
+                for result in pool.add_external_transactions(pruned_old_transactions).await {
+                    match result {
+                        Ok(_) => resurrected += 1,
+                        Err(err) => {
+                            metrics.inc_failed_resurrected_transactions();
+                            debug!(target: "txpool", %err, "failed to resurrect reorged transaction");
+                        }
+                    }
+                }
+                metrics.inc_resurrected_transactions(resurrected);
+                trace!(
+                    target: "txpool",
+                    reorged = reorged_transactions_len,
+                    resurrected,
+                    "resurrected reorged transactions"
+                );
 
-                // keep track of new mined blob transactions
+                // keep track of new mined blob transactions, the resurrected blob transactions of
+                // the old chain must keep their blobs
+                blob_store_tracker.remove_reorged_blocks(old_first.number);
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
             }
             CanonStateNotification::Commit { new } => {
@@ -378,7 +493,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         chain_spec.base_fee_params_at_timestamp(tip.timestamp + 12),
                     )
                     .unwrap_or_default();
-                let pending_block_blob_fee = tip.next_block_blob_fee();
+                let pending_block_blob_fee = tip.next_block_blob_fee_with_params(
+                    chain_spec.blob_params_at_timestamp(tip.timestamp + 12),
+                );
 
                 let first_block = blocks.first();
                 trace!(
@@ -565,12 +682,40 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// A backed up local transaction.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct LocalTransactionBackupEntry {
+    /// The unix timestamp at which the transaction was first backed up.
+    timestamp: u64,
+    /// The backed up transaction.
+    transaction: TransactionSigned,
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Returns `true` if a transaction first backed up at the timestamp expired.
+const fn is_backup_expired(
+    config: &LocalTransactionBackupConfig,
+    timestamp: u64,
+    now: u64,
+) -> bool {
+    now.saturating_sub(timestamp) > config.lifetime.as_secs()
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
-/// The file is removed after the transactions have been successfully processed.
+///
+/// Transactions that expired are dropped. The timestamps at which the reinserted transactions
+/// were first backed up are recorded in `timestamps`, so that they keep expiring after the same
+/// lifetime.
 async fn load_and_reinsert_transactions<P>(
     pool: P,
     file_path: &Path,
+    config: &LocalTransactionBackupConfig,
+    timestamps: &mut HashMap<TxHash, u64>,
 ) -> Result<(), TransactionsBackupError>
 where
     P: TransactionPool,
@@ -586,11 +731,24 @@ where
         return Ok(())
     }
 
-    let txs_signed: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let now = unix_timestamp();
+    let entries = match Vec::<LocalTransactionBackupEntry>::decode(&mut data.as_slice()) {
+        Ok(entries) => entries,
+        // backups of older versions only contain the transactions
+        Err(_) => Vec::<TransactionSigned>::decode(&mut data.as_slice())?
+            .into_iter()
+            .map(|transaction| LocalTransactionBackupEntry { timestamp: now, transaction })
+            .collect(),
+    };
 
-    let pool_transactions = txs_signed
+    let mut first_backed_up = HashMap::with_capacity(entries.len());
+    let pool_transactions = entries
         .into_iter()
-        .filter_map(|tx| tx.try_ecrecovered())
+        .filter(|entry| !is_backup_expired(config, entry.timestamp, now))
+        .filter_map(|LocalTransactionBackupEntry { timestamp, transaction }| {
+            first_backed_up.insert(transaction.hash(), timestamp);
+            transaction.try_ecrecovered()
+        })
         .filter_map(|tx| {
             // Filter out errors
             <P as TransactionPool>::Transaction::try_from_recovered_transaction(tx).ok()
@@ -598,34 +756,79 @@ where
         .collect::<Vec<_>>();
 
     let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    for hash in outcome.iter().filter_map(|res| res.as_ref().ok()) {
+        if let Some(timestamp) = first_backed_up.get(hash) {
+            timestamps.insert(*hash, *timestamp);
+        }
+    }
 
-    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
-    reth_fs_util::remove_file(file_path)?;
+    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), num_reinserted=%timestamps.len(), "Successfully reinserted local transactions from file");
     Ok(())
 }
 
-fn save_local_txs_backup<P>(pool: P, file_path: &Path)
-where
+/// Writes the local transactions of the pool to the backup file.
+///
+/// Blob transactions are not backed up, because the backup doesn't contain their sidecars. Only
+/// the transactions with the lowest nonces up to the account limit are backed up, and the
+/// transactions that expired are dropped. `timestamps` tracks when the transactions were first
+/// backed up.
+fn save_local_txs_backup<P>(
+    pool: &P,
+    file_path: &Path,
+    config: &LocalTransactionBackupConfig,
+    timestamps: &mut HashMap<TxHash, u64>,
+) where
     P: TransactionPool,
 {
-    let local_transactions = pool.get_local_transactions();
-    if local_transactions.is_empty() {
+    let mut local_transactions =
+        pool.get_local_transactions().into_iter().filter(|tx| !tx.is_eip4844()).collect::<Vec<_>>();
+    // the account limit keeps the transactions with the lowest nonces
+    local_transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let now = unix_timestamp();
+    let previous = std::mem::take(timestamps);
+    let mut backed_up = Vec::with_capacity(local_transactions.len());
+    let mut sender_count = (Address::ZERO, 0);
+    for tx in local_transactions {
+        if sender_count.0 != tx.sender() {
+            sender_count = (tx.sender(), 0);
+        }
+        let timestamp = previous.get(tx.hash()).copied().unwrap_or(now);
+        if sender_count.1 >= config.account_limit || is_backup_expired(config, timestamp, now) {
+            continue
+        }
+        sender_count.1 += 1;
+        timestamps.insert(*tx.hash(), timestamp);
+        backed_up.push(LocalTransactionBackupEntry {
+            timestamp,
+            transaction: tx.to_recovered_transaction().into_signed(),
+        });
+    }
+
+    if backed_up.is_empty() {
         trace!(target: "txpool", "no local transactions to save");
+        if file_path.exists() {
+            if let Err(err) = reth_fs_util::remove_file(file_path) {
+                warn!(target: "txpool", %err, txs_file=?file_path, "Failed to remove local transactions file");
+            }
+        }
         return
     }
 
-    let local_transactions = local_transactions
-        .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
-        .collect::<Vec<_>>();
-
-    let num_txs = local_transactions.len();
+    let num_txs = backed_up.len();
     let mut buf = Vec::new();
-    alloy_rlp::encode_list(&local_transactions, &mut buf);
+    alloy_rlp::encode_list(&backed_up, &mut buf);
     info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
-    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
 
-    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+    // the backup is written to a temporary file first, so that it's never lost
+    let tmp_path = file_path.with_extension("tmp");
+    let result = file_path
+        .parent()
+        .map(reth_fs_util::create_dir_all)
+        .transpose()
+        .and_then(|_| reth_fs_util::write(&tmp_path, buf))
+        .and_then(|_| reth_fs_util::rename(&tmp_path, file_path));
+    match result {
         Ok(_) => {
             info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
         }
@@ -650,7 +853,11 @@ pub enum TransactionsBackupError {
 }
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
-/// Reloads the transactions from the file on the boot up and inserts them into the pool.
+/// Reloads the transactions from the file on the boot up and inserts them into the pool, which
+/// propagates them to the network again.
+///
+/// The backup is also written periodically, see
+/// [`LocalTransactionBackupConfig::backup_interval`].
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -658,19 +865,40 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let Some(transactions_path) = config.transactions_path.clone() else {
         // nothing to do
         return
     };
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
+    let mut timestamps = HashMap::new();
+    if let Err(err) =
+        load_and_reinsert_transactions(pool.clone(), &transactions_path, &config, &mut timestamps)
+            .await
+    {
         error!(target: "txpool", "{}", err)
     }
-
-    let graceful_guard = shutdown.await;
+    // drops the backed up transactions that weren't reinserted
+    save_local_txs_backup(&pool, &transactions_path, &config, &mut timestamps);
+
+    let graceful_guard = match config.backup_interval {
+        Some(backup_interval) => {
+            let mut interval =
+                time::interval_at(time::Instant::now() + backup_interval, backup_interval);
+            let mut shutdown = std::pin::pin!(shutdown);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        save_local_txs_backup(&pool, &transactions_path, &config, &mut timestamps)
+                    }
+                    guard = &mut shutdown => break guard,
+                }
+            }
+        }
+        None => shutdown.await,
+    };
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    save_local_txs_backup(&pool, &transactions_path, &config, &mut timestamps);
 
     drop(graceful_guard)
 }
@@ -740,11 +968,20 @@ mod tests {
         // shutdown the executor
         manager.graceful_shutdown();
 
-        let data = fs::read(transactions_path).unwrap();
+        let data = fs::read(&transactions_path).unwrap();
 
-        let txs: Vec<TransactionSigned> =
+        let txs: Vec<LocalTransactionBackupEntry> =
             alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
         assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].transaction.hash(), *tx_to_cmp.hash());
+
+        // the transaction expires after the lifetime, measured from the first backup
+        let config = LocalTransactionBackupConfig::with_local_txs_backup(transactions_path.clone())
+            .with_lifetime(Duration::ZERO);
+        let mut timestamps = HashMap::from([(*tx_to_cmp.hash(), txs[0].timestamp - 1)]);
+        save_local_txs_backup(&txpool, &transactions_path, &config, &mut timestamps);
+        assert!(!transactions_path.exists());
+        assert!(timestamps.is_empty());
 
         temp_dir.close().unwrap();
     }
@@ -1,7 +1,7 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -15,6 +15,16 @@ pub struct TxPoolMetrics {
     pub(crate) invalid_transactions: Counter,
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
+    /// Number of transactions that expired in the queued sub-pool, including their descendants
+    pub(crate) expired_queued_transactions: Counter,
+    /// Number of transactions that expired in the basefee sub-pool, including their descendants
+    pub(crate) expired_basefee_transactions: Counter,
+    /// Number of transactions that were replaced by a transaction of the same sender and nonce
+    pub(crate) replaced_transactions: Counter,
+    /// Number of transactions that were moved to a better sub-pool, e.g. from queued to pending
+    pub(crate) promoted_transactions: Counter,
+    /// Number of transactions that were moved to a worse sub-pool, e.g. from pending to basefee
+    pub(crate) demoted_transactions: Counter,
 
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
@@ -57,6 +67,33 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_entries: Gauge,
 }
 
+/// Transaction pool disk blobstore cache metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct BlobStoreCacheMetrics {
+    /// Number of blob lookups that were served from the in-memory cache
+    pub(crate) blobstore_cache_hits: Counter,
+    /// Number of blob lookups that had to read from disk
+    pub(crate) blobstore_cache_misses: Counter,
+}
+
+/// Transaction validation metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct TxValidationMetrics {
+    /// How long it took to validate a transaction before inserting it into the pool
+    pub(crate) validation_duration_seconds: Histogram,
+}
+
+/// Metrics of the iterators over the best transactions, one of which is created for every built
+/// payload
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct BestTransactionsMetrics {
+    /// Number of transactions an iterator over the best transactions yielded before it was dropped
+    pub(crate) best_transactions_yielded: Histogram,
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -68,6 +105,14 @@ pub struct MaintainPoolMetrics {
     pub(crate) drift_count: Counter,
     /// Number of transaction reinserted into the pool after reorg.
     pub(crate) reinserted_transactions: Counter,
+    /// Number of reorged transactions that passed validation against the new chain and were
+    /// added back to the pool.
+    pub(crate) resurrected_transactions: Counter,
+    /// Number of reorged transactions that failed validation against the new chain.
+    pub(crate) failed_resurrected_transactions: Counter,
+    /// Number of reorged transactions that could not be turned back into pool transactions, e.g.
+    /// blob transactions whose sidecar is no longer available.
+    pub(crate) dropped_reorged_transactions: Counter,
     /// Number of transactions finalized blob transactions we were tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
 }
@@ -83,6 +128,21 @@ impl MaintainPoolMetrics {
         self.reinserted_transactions.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_resurrected_transactions(&self, count: usize) {
+        self.resurrected_transactions.increment(count as u64);
+    }
+
+    #[inline]
+    pub(crate) fn inc_failed_resurrected_transactions(&self) {
+        self.failed_resurrected_transactions.increment(1);
+    }
+
+    #[inline]
+    pub(crate) fn inc_dropped_reorged_transactions(&self, count: usize) {
+        self.dropped_reorged_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_deleted_tracked_blobs(&self, count: usize) {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
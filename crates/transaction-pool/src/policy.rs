@@ -0,0 +1,135 @@
+//! Policies that reject or always accept transactions based on their sender and recipient.
+
+use parking_lot::RwLock;
+use reth_primitives::Address;
+use std::{collections::HashSet, fmt, sync::Arc};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The decision of a [`TransactionPolicy`] for a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// The transaction is validated as usual.
+    Continue,
+    /// The transaction is always accepted.
+    ///
+    /// It is exempt from the minimum priority fee like a local transaction, but it's still
+    /// validated against the chain and the state.
+    Accept,
+    /// The transaction is rejected without further validation.
+    Reject,
+}
+
+/// A policy that is evaluated for every transaction before the transaction is validated.
+///
+/// Policies should be cheap to evaluate, they run before the stateless and stateful checks of the
+/// validator.
+pub trait TransactionPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns the decision for a transaction of the sender to the recipient, which is `None` for
+    /// contract creations.
+    fn evaluate(&self, sender: Address, recipient: Option<Address>) -> PolicyDecision;
+}
+
+/// The sender and recipient lists of an [`AddressListPolicy`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+pub struct AddressLists {
+    /// Senders whose transactions are always accepted, this takes precedence over all other
+    /// lists.
+    pub allowed_senders: HashSet<Address>,
+    /// Senders whose transactions are rejected.
+    pub denied_senders: HashSet<Address>,
+    /// Recipients that transactions are rejected for.
+    pub denied_recipients: HashSet<Address>,
+    /// Whether transactions of senders that are not in `allowed_senders` are rejected.
+    pub reject_unlisted: bool,
+}
+
+impl AddressLists {
+    /// Returns the decision for a transaction of the sender to the recipient.
+    pub fn evaluate(&self, sender: Address, recipient: Option<Address>) -> PolicyDecision {
+        if self.allowed_senders.contains(&sender) {
+            return PolicyDecision::Accept
+        }
+        if self.reject_unlisted ||
+            self.denied_senders.contains(&sender) ||
+            recipient.is_some_and(|recipient| self.denied_recipients.contains(&recipient))
+        {
+            return PolicyDecision::Reject
+        }
+        PolicyDecision::Continue
+    }
+}
+
+/// A [`TransactionPolicy`] backed by [`AddressLists`] that can be replaced at runtime.
+///
+/// This is cheap to clone, all clones share the same lists.
+#[derive(Debug, Clone, Default)]
+pub struct AddressListPolicy(Arc<RwLock<AddressLists>>);
+
+impl AddressListPolicy {
+    /// Creates a new policy with the given lists.
+    pub fn new(lists: AddressLists) -> Self {
+        Self(Arc::new(RwLock::new(lists)))
+    }
+
+    /// Returns the current lists.
+    pub fn lists(&self) -> AddressLists {
+        self.0.read().clone()
+    }
+
+    /// Replaces the lists, which applies to all transactions that are validated afterwards.
+    pub fn set_lists(&self, lists: AddressLists) {
+        *self.0.write() = lists;
+    }
+}
+
+impl TransactionPolicy for AddressListPolicy {
+    fn evaluate(&self, sender: Address, recipient: Option<Address>) -> PolicyDecision {
+        self.0.read().evaluate(sender, recipient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_address_lists() {
+        let allowed = Address::with_last_byte(1);
+        let denied = Address::with_last_byte(2);
+        let other = Address::with_last_byte(3);
+        let policy = AddressListPolicy::default();
+        assert_eq!(policy.evaluate(denied, Some(other)), PolicyDecision::Continue);
+
+        // the lists are shared between clones
+        policy.clone().set_lists(AddressLists {
+            allowed_senders: HashSet::from([allowed]),
+            denied_senders: HashSet::from([denied]),
+            denied_recipients: HashSet::from([denied]),
+            reject_unlisted: false,
+        });
+        assert_eq!(policy.evaluate(allowed, Some(denied)), PolicyDecision::Accept);
+        assert_eq!(policy.evaluate(denied, None), PolicyDecision::Reject);
+        assert_eq!(policy.evaluate(other, Some(denied)), PolicyDecision::Reject);
+        assert_eq!(policy.evaluate(other, None), PolicyDecision::Continue);
+
+        policy.set_lists(AddressLists { reject_unlisted: true, ..policy.lists() });
+        assert_eq!(policy.evaluate(other, None), PolicyDecision::Reject);
+        assert_eq!(policy.evaluate(allowed, None), PolicyDecision::Accept);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_address_lists() {
+        let lists: AddressLists = serde_json::from_str(
+            r#"{"deniedSenders":["0x0000000000000000000000000000000000000002"],"rejectUnlisted":true}"#,
+        )
+        .unwrap();
+        assert_eq!(lists.denied_senders, HashSet::from([Address::with_last_byte(2)]));
+        assert!(lists.allowed_senders.is_empty());
+        assert!(lists.reject_unlisted);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    identifier::TransactionId, pool::pending::PendingTransaction, PoolTransaction,
-    TransactionOrdering, ValidPoolTransaction,
+    identifier::TransactionId, metrics::BestTransactionsMetrics, pool::pending::PendingTransaction,
+    PoolTransaction, TransactionOrdering, ValidPoolTransaction,
 };
 use core::fmt;
 use reth_primitives::B256 as TxHash;
@@ -90,6 +90,10 @@ pub(crate) struct BestTransactions<T: TransactionOrdering> {
     pub(crate) new_transaction_receiver: Option<Receiver<PendingTransaction<T>>>,
     /// Flag to control whether to skip blob transactions (EIP4844).
     pub(crate) skip_blobs: bool,
+    /// The number of transactions this iterator yielded, recorded when it is dropped.
+    pub(crate) yielded: usize,
+    /// Metrics of the best transactions iterators.
+    pub(crate) metrics: BestTransactionsMetrics,
 }
 
 impl<T: TransactionOrdering> BestTransactions<T> {
@@ -192,12 +196,19 @@ impl<T: TransactionOrdering> Iterator for BestTransactions<T> {
                 // transactions are returned
                 self.mark_invalid(&best.transaction)
             } else {
+                self.yielded += 1;
                 return Some(best.transaction)
             }
         }
     }
 }
 
+impl<T: TransactionOrdering> Drop for BestTransactions<T> {
+    fn drop(&mut self) {
+        self.metrics.best_transactions_yielded.record(self.yielded as f64);
+    }
+}
+
 /// A[`BestTransactions`](crate::traits::BestTransactions) implementation that filters the
 /// transactions of iter with predicate.
 ///
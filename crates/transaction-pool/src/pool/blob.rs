@@ -85,7 +85,7 @@ impl<T: PoolTransaction> BlobTransactions<T> {
     /// Note: This does not remove any the transactions from the pool.
     pub(crate) fn satisfy_attributes(
         &self,
-        best_transactions_attributes: BestTransactionsAttributes,
+        best_transactions_attributes: &BestTransactionsAttributes,
     ) -> Vec<Arc<ValidPoolTransaction<T>>> {
         let mut transactions = Vec::new();
         {
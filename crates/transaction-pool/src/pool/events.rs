@@ -30,6 +30,9 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
     },
     /// Transaction was dropped due to configured limits.
     Discarded(TxHash),
+    /// Transaction was dropped because its sender was inactive for longer than the configured
+    /// lifetime.
+    Expired(TxHash),
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -48,6 +51,7 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
             Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Expired(hash) => Self::Expired(*hash),
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
@@ -70,6 +74,9 @@ pub enum TransactionEvent {
     Replaced(TxHash),
     /// Transaction was dropped due to configured limits.
     Discarded,
+    /// Transaction was dropped because its sender was inactive for longer than the configured
+    /// lifetime.
+    Expired,
     /// Transaction became invalid indefinitely.
     Invalid,
     /// Transaction was propagated to peers.
@@ -80,6 +87,6 @@ impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
     pub const fn is_final(&self) -> bool {
-        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
+        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded | Self::Expired)
     }
 }
@@ -98,7 +98,7 @@ use tracing::{debug, trace, warn};
 mod events;
 use crate::{
     blobstore::BlobStore,
-    metrics::BlobStoreMetrics,
+    metrics::{BlobStoreMetrics, TxValidationMetrics},
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
     validate::ValidTransaction,
@@ -149,6 +149,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Metrics for the validation of transactions
+    validation_metrics: TxValidationMetrics,
 }
 
 // === impl PoolInner ===
@@ -172,6 +174,7 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            validation_metrics: Default::default(),
         }
     }
 
@@ -230,6 +233,11 @@ where
         &self.validator
     }
 
+    /// Get the metrics for the validation of transactions.
+    pub(crate) const fn validation_metrics(&self) -> &TxValidationMetrics {
+        &self.validation_metrics
+    }
+
     /// Adds a new transaction listener to the pool that gets notified about every new _pending_
     /// transaction inserted into the pool
     pub fn add_pending_listener(&self, kind: TransactionListenerKind) -> mpsc::Receiver<TxHash> {
@@ -785,6 +793,27 @@ where
         discarded.into_iter().map(|tx| *tx.hash()).collect()
     }
 
+    /// Removes the parked transactions of inactive senders whose lifetime expired and returns them.
+    ///
+    /// If some of the transactions are blob transactions, they are also removed from the blob
+    /// store.
+    pub(crate) fn remove_expired_transactions(
+        &self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let expired = self.pool.write().remove_expired(Instant::now());
+        if expired.is_empty() {
+            return expired
+        }
+
+        let mut listener = self.event_listener.write();
+        expired.iter().for_each(|tx| listener.expired(tx.hash()));
+        drop(listener);
+
+        self.delete_discarded_blobs(expired.iter());
+
+        expired
+    }
+
     /// Inserts a blob transaction into the blob store
     fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
         if let Err(err) = self.blob_store.insert(hash, blob) {
@@ -112,6 +112,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
             invalid: Default::default(),
             new_transaction_receiver: Some(self.new_transaction_notifier.subscribe()),
             skip_blobs: false,
+            yielded: 0,
+            metrics: Default::default(),
         }
     }
 
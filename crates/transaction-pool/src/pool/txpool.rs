@@ -6,7 +6,7 @@ use crate::{
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
     pool::{
-        best::BestTransactions,
+        best::{BestTransactionFilter, BestTransactions},
         blob::BlobTransactions,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
@@ -32,6 +32,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Instant,
 };
 use tracing::trace;
 
@@ -163,6 +164,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.record_subpool_move(SubPool::Pending, to);
                     self.add_transaction_to_subpool(to, tx);
                 }
             }
@@ -179,6 +181,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.record_subpool_move(SubPool::Blob, to);
                     self.add_transaction_to_subpool(to, tx);
                 }
             }
@@ -208,6 +211,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.record_subpool_move(SubPool::Pending, to);
                     self.add_transaction_to_subpool(to, tx);
                 }
 
@@ -225,6 +229,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
+                    self.record_subpool_move(SubPool::BaseFee, to);
                     self.add_transaction_to_subpool(to, tx);
                 }
 
@@ -264,10 +269,29 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// If the provided attributes differ from the currently tracked fees, this will also include
     /// transactions that are unlocked by the new fees, or exclude transactions that are no longer
     /// valid with the new fees.
+    ///
+    /// Transactions excluded by the attributes, and their descendants, are skipped.
     pub(crate) fn best_transactions_with_attributes(
         &self,
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
+    {
+        let best = self.best_transactions_with_fees(&best_transactions_attributes);
+
+        if !best_transactions_attributes.has_exclusions() {
+            return best
+        }
+
+        Box::new(BestTransactionFilter::new(best, move |tx: &Arc<ValidPoolTransaction<_>>| {
+            !best_transactions_attributes.is_excluded(&tx.transaction)
+        }))
+    }
+
+    /// Returns an iterator that yields transactions that satisfy the fees of the given attributes.
+    fn best_transactions_with_fees(
+        &self,
+        best_transactions_attributes: &BestTransactionsAttributes,
+    ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
     {
         // First we need to check if the given base fee is different than what's currently being
         // tracked
@@ -487,6 +511,9 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
+                if replaced_tx.is_some() {
+                    self.metrics.replaced_transactions.increment(1);
+                }
                 let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
@@ -579,6 +606,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 }
                 Destination::Pool(move_to) => {
                     debug_assert_ne!(&move_to, &current, "destination must be different");
+                    self.record_subpool_move(current, move_to);
                     let moved = self.move_transaction(current, move_to, &id);
                     if matches!(move_to, SubPool::Pending) {
                         if let Some(tx) = moved {
@@ -591,6 +619,15 @@ impl<T: TransactionOrdering> TxPool<T> {
         outcome
     }
 
+    /// Records a move of a transaction from one sub pool to another in the metrics.
+    fn record_subpool_move(&self, from: SubPool, to: SubPool) {
+        if to.is_promoted(from) {
+            self.metrics.promoted_transactions.increment(1);
+        } else if from.is_promoted(to) {
+            self.metrics.demoted_transactions.increment(1);
+        }
+    }
+
     /// Moves a transaction from one sub pool to another.
     ///
     /// This will remove the given transaction from one sub-pool and insert it into the other
@@ -816,6 +853,72 @@ impl<T: TransactionOrdering> TxPool<T> {
         removed
     }
 
+    /// Removes the parked transactions of senders that have been inactive for longer than the
+    /// configured lifetimes.
+    ///
+    /// A sender is inactive if none of its transactions in the pool was added within the lifetime.
+    /// Transactions expire in the queued sub-pool after [`PoolConfig::queued_lifetime`] and in the
+    /// basefee sub-pool after [`PoolConfig::basefee_lifetime`], together with all their
+    /// descendants. Senders of local transactions are exempt.
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    pub(crate) fn remove_expired(
+        &mut self,
+        now: Instant,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let mut removed = Vec::new();
+
+        let senders = self.all_transactions.tx_counter.keys().copied().collect::<Vec<_>>();
+        for sender in senders {
+            let mut last_added = None;
+            let mut is_local = false;
+            for (_, tx) in self.all_transactions.txs_iter(sender) {
+                last_added = last_added.max(Some(tx.transaction.timestamp));
+                is_local |= self
+                    .config
+                    .local_transactions_config
+                    .is_local(tx.transaction.origin, tx.transaction.sender());
+            }
+            let Some(last_added) = last_added else { continue };
+            if is_local {
+                continue
+            }
+
+            // all transactions after the first expired transaction are removed with it
+            let inactive = now.saturating_duration_since(last_added);
+            let Some((id, subpool)) = self
+                .all_transactions
+                .txs_iter(sender)
+                .find(|(_, tx)| match tx.subpool {
+                    SubPool::Queued => inactive > self.config.queued_lifetime,
+                    SubPool::BaseFee => inactive > self.config.basefee_lifetime,
+                    SubPool::Pending | SubPool::Blob => false,
+                })
+                .map(|(id, tx)| (*id, tx.subpool))
+            else {
+                continue
+            };
+
+            let removed_len = removed.len();
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+            }
+            self.remove_descendants(&id, &mut removed);
+
+            let expired = removed.len() - removed_len;
+            trace!(target: "txpool", ?sender, ?subpool, ?inactive, expired, "removed expired transactions");
+            let expired = expired as u64;
+            if subpool == SubPool::Queued {
+                self.metrics.expired_queued_transactions.increment(expired);
+            } else {
+                self.metrics.expired_basefee_transactions.increment(expired);
+            }
+        }
+
+        self.update_size_metrics();
+        removed
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -1829,10 +1932,12 @@ mod tests {
 
     use super::*;
     use crate::{
+        config::{DEFAULT_BASEFEE_TRANSACTION_LIFETIME, DEFAULT_QUEUED_TRANSACTION_LIFETIME},
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
         SubPoolLimit,
     };
+    use std::time::Duration;
 
     #[test]
     fn test_insert_blob() {
@@ -2626,6 +2731,52 @@ mod tests {
         assert_eq!(pool.all_transactions.txs.get(&id).unwrap().subpool, SubPool::BaseFee)
     }
 
+    #[test]
+    fn remove_expired_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let balance = U256::from(1_000);
+
+        let pending = MockTransaction::eip1559().inc_price_by(10);
+        pool.add_transaction(f.validated(pending), balance, 0).unwrap();
+
+        // senders with a nonce gap
+        let queued = MockTransaction::eip1559().next();
+        pool.add_transaction(f.validated(queued.clone()), balance, 0).unwrap();
+        let local = MockTransaction::eip1559().next();
+        pool.add_transaction(f.validated_with_origin(TransactionOrigin::Local, local), balance, 0)
+            .unwrap();
+
+        // a sender with underpriced transactions
+        let underpriced = MockTransaction::eip1559();
+        pool.add_transaction(f.validated(underpriced.clone()), balance, 0).unwrap();
+        pool.add_transaction(f.validated(underpriced.next()), balance, 0).unwrap();
+        pool.update_basefee((underpriced.max_fee_per_gas() + 1) as u64);
+
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 2);
+        assert_eq!(pool.basefee_pool.len(), 2);
+
+        let now = Instant::now();
+        assert!(pool.remove_expired(now).is_empty());
+
+        let expired = pool
+            .remove_expired(now + DEFAULT_BASEFEE_TRANSACTION_LIFETIME + Duration::from_secs(1));
+        assert_eq!(expired.len(), 2);
+        assert!(pool.basefee_pool.is_empty());
+        assert_eq!(pool.queued_pool.len(), 2);
+
+        let expired =
+            pool.remove_expired(now + DEFAULT_QUEUED_TRANSACTION_LIFETIME + Duration::from_secs(1));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].transaction, queued);
+
+        // pending and local transactions don't expire
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 1);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn get_highest_transaction_by_sender_and_nonce() {
         // Set up a mock transaction factory and a new transaction pool.
@@ -2958,4 +3109,49 @@ mod tests {
             vec![1, 2, 3]
         );
     }
+
+    #[test]
+    fn best_transactions_with_exclusions() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let a_0 = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let a_1 = a_0.next();
+        let b_0 = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let b_1 = b_0.next();
+
+        for tx in [&a_0, &a_1, &b_0, &b_1] {
+            pool.add_transaction(f.validated(tx.clone()), on_chain_balance, on_chain_nonce)
+                .unwrap();
+        }
+        assert_eq!(4, pool.pending_transactions().len());
+
+        let base_fee = pool.all_transactions.pending_fees.base_fee;
+
+        // excluding a sender skips all of its transactions
+        let attributes = BestTransactionsAttributes::base_fee(base_fee)
+            .with_excluded_senders(HashSet::from([a_0.get_sender()]));
+        let best = pool.best_transactions_with_attributes(attributes).collect::<Vec<_>>();
+        assert_eq!(
+            best.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>(),
+            HashSet::from([b_0.get_hash(), b_1.get_hash()])
+        );
+
+        // excluding a transaction also skips its descendants
+        let attributes = BestTransactionsAttributes::base_fee(base_fee)
+            .with_excluded_hashes(HashSet::from([a_0.get_hash()]));
+        let best = pool.best_transactions_with_attributes(attributes).collect::<Vec<_>>();
+        assert_eq!(
+            best.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>(),
+            HashSet::from([b_0.get_hash(), b_1.get_hash()])
+        );
+
+        // without exclusions all transactions are yielded
+        let best = pool
+            .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee))
+            .count();
+        assert_eq!(best, 4);
+    }
 }
@@ -749,6 +749,8 @@ impl EthPoolTransaction for MockTransaction {
             _ => Err(BlobTransactionValidationError::NotBlobTransaction(self.tx_type())),
         }
     }
+
+    fn update_encoded_length(&mut self, _sidecar: &BlobTransactionSidecar) {}
 }
 
 impl TryFromRecoveredTransaction for MockTransaction {
@@ -0,0 +1,172 @@
+//! A minimum priority fee for transactions to be accepted into the pool.
+
+use parking_lot::Mutex;
+use reth_primitives::SealedBlock;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// The default percentile of the effective tips of recent blocks used as the dynamic floor.
+pub const DEFAULT_TIP_FLOOR_PERCENTILE: u8 = 25;
+
+/// The default number of recent blocks whose effective tips determine the dynamic floor.
+pub const DEFAULT_TIP_FLOOR_BLOCKS: usize = 20;
+
+/// How the minimum tip of transactions is determined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TipFloorMode {
+    /// Transactions are accepted regardless of their tip.
+    #[default]
+    Disabled,
+    /// Transactions must pay at least the given tip in wei.
+    Static(u128),
+    /// Transactions must pay at least the given percentile of the effective tips of the
+    /// transactions included in the recent blocks.
+    Dynamic {
+        /// The percentile of the effective tips, in `0..=100`.
+        percentile: u8,
+        /// The number of recent blocks whose transactions are considered.
+        blocks: usize,
+    },
+}
+
+/// Tracks the minimum priority fee that transactions must pay to be accepted into the pool.
+///
+/// This protects the pool against being flooded with transactions that don't pay a tip and are
+/// unlikely to be included. The floor is either static or derived from the effective tips of the
+/// transactions of recent blocks, see [`TipFloorMode`]. The floor applies to the effective tip of
+/// all transaction types at the base fee of the head block, and local transactions are exempt.
+///
+/// This is cheap to clone, all clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct TipFloor {
+    inner: Arc<TipFloorInner>,
+}
+
+#[derive(Debug, Default)]
+struct TipFloorInner {
+    mode: TipFloorMode,
+    /// The current floor, saturated to `u64::MAX`.
+    floor: AtomicU64,
+    /// The base fee of the head block, which the effective tips are computed at.
+    base_fee: AtomicU64,
+    /// The effective tips of the transactions of the recent blocks, newest last.
+    recent_tips: Mutex<VecDeque<Vec<u128>>>,
+}
+
+impl TipFloor {
+    /// Creates a new instance with the given mode.
+    pub fn new(mode: TipFloorMode) -> Self {
+        let floor = match mode {
+            TipFloorMode::Static(floor) => floor.min(u64::MAX as u128) as u64,
+            TipFloorMode::Disabled | TipFloorMode::Dynamic { .. } => 0,
+        };
+        Self {
+            inner: Arc::new(TipFloorInner {
+                mode,
+                floor: AtomicU64::new(floor),
+                base_fee: AtomicU64::new(0),
+                recent_tips: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the configured mode.
+    pub fn mode(&self) -> TipFloorMode {
+        self.inner.mode
+    }
+
+    /// Returns the current minimum tip in wei.
+    ///
+    /// The dynamic floor is zero until a block with transactions was observed.
+    pub fn floor(&self) -> u128 {
+        self.inner.floor.load(Ordering::Relaxed) as u128
+    }
+
+    /// Returns the base fee of the head block, which the tips of transactions are compared at.
+    pub fn base_fee(&self) -> u64 {
+        self.inner.base_fee.load(Ordering::Relaxed)
+    }
+
+    /// Updates the base fee and the dynamic floor with the effective tips of the transactions of
+    /// the new head block.
+    pub fn on_new_head_block(&self, block: &SealedBlock) {
+        self.inner.base_fee.store(block.base_fee_per_gas.unwrap_or_default(), Ordering::Relaxed);
+        let TipFloorMode::Dynamic { percentile, blocks } = self.inner.mode else { return };
+
+        let tips = block
+            .body
+            .iter()
+            .filter_map(|tx| tx.effective_tip_per_gas(block.base_fee_per_gas))
+            .collect::<Vec<_>>();
+
+        let mut recent_tips = self.inner.recent_tips.lock();
+        recent_tips.push_back(tips);
+        while recent_tips.len() > blocks.max(1) {
+            recent_tips.pop_front();
+        }
+
+        let mut tips = recent_tips.iter().flatten().copied().collect::<Vec<_>>();
+        drop(recent_tips);
+
+        let floor = if tips.is_empty() {
+            0
+        } else {
+            let index = (tips.len() - 1) * percentile.min(100) as usize / 100;
+            *tips.select_nth_unstable(index).1
+        };
+        self.inner.floor.store(floor.min(u64::MAX as u128) as u64, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Signature, Transaction, TransactionSigned, TxEip1559};
+
+    fn block(base_fee: u64, tips: &[u128]) -> SealedBlock {
+        let body = tips
+            .iter()
+            .map(|tip| {
+                TransactionSigned::from_transaction_and_signature(
+                    Transaction::Eip1559(TxEip1559 {
+                        max_fee_per_gas: base_fee as u128 + tip,
+                        max_priority_fee_per_gas: *tip,
+                        ..Default::default()
+                    }),
+                    Signature::default(),
+                )
+            })
+            .collect();
+        SealedBlock {
+            header: Header { base_fee_per_gas: Some(base_fee), ..Default::default() }.seal_slow(),
+            body,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dynamic_floor() {
+        let floor = TipFloor::new(TipFloorMode::Dynamic { percentile: 25, blocks: 2 });
+        assert_eq!(floor.floor(), 0);
+
+        floor.on_new_head_block(&block(10, &[1, 2, 3, 4, 5]));
+        assert_eq!(floor.floor(), 2);
+        assert_eq!(floor.base_fee(), 10);
+
+        floor.on_new_head_block(&block(10, &[10, 20, 30, 40, 50, 60, 70]));
+        assert_eq!(floor.floor(), 3);
+
+        // the first block is no longer considered
+        floor.on_new_head_block(&block(10, &[]));
+        assert_eq!(floor.floor(), 20);
+
+        let floor = TipFloor::new(TipFloorMode::Static(7));
+        floor.on_new_head_block(&block(10, &[1]));
+        assert_eq!(floor.floor(), 7);
+    }
+}
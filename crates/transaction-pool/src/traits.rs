@@ -11,10 +11,11 @@ use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{
     kzg::KzgSettings, transaction::TryFromRecoveredTransactionError, AccessList, Address,
-    BlobTransactionSidecar, BlobTransactionValidationError, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
-    SealedBlock, Transaction, TransactionSignedEcRecovered, TryFromRecoveredTransaction, TxHash,
-    TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, U256,
+    BlobTransaction, BlobTransactionSidecar, BlobTransactionValidationError,
+    FromRecoveredPooledTransaction, IntoRecoveredTransaction, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, SealedBlock, Transaction, TransactionSignedEcRecovered,
+    TryFromRecoveredTransaction, TxHash, TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    U256,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -421,6 +422,12 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Maintenance function to remove the parked transactions of senders that have been inactive
+    /// for longer than the configured lifetimes.
+    ///
+    /// Returns the removed transactions.
+    fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -713,6 +720,27 @@ pub trait BestTransactions: Iterator + Send {
     }
 }
 
+impl<T> BestTransactions for Box<T>
+where
+    T: BestTransactions + ?Sized,
+{
+    fn mark_invalid(&mut self, transaction: &Self::Item) {
+        (**self).mark_invalid(transaction)
+    }
+
+    fn no_updates(&mut self) {
+        (**self).no_updates()
+    }
+
+    fn skip_blobs(&mut self) {
+        (**self).skip_blobs()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        (**self).set_skip_blobs(skip_blobs)
+    }
+}
+
 /// A no-op implementation that yields no transactions.
 impl<T> BestTransactions for std::iter::Empty<T> {
     fn mark_invalid(&mut self, _tx: &T) {}
@@ -725,12 +753,18 @@ impl<T> BestTransactions for std::iter::Empty<T> {
 }
 
 /// A Helper type that bundles best transactions attributes together.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BestTransactionsAttributes {
     /// The base fee attribute for best transactions.
     pub basefee: u64,
     /// The blob fee attribute for best transactions.
     pub blob_fee: Option<u64>,
+    /// Senders whose transactions should not be yielded.
+    pub excluded_senders: Option<Arc<HashSet<Address>>>,
+    /// Hashes of transactions that should not be yielded.
+    ///
+    /// Note: transactions that depend on an excluded transaction are not yielded either.
+    pub excluded_hashes: Option<Arc<HashSet<TxHash>>>,
 }
 
 // === impl BestTransactionsAttributes ===
@@ -738,7 +772,7 @@ pub struct BestTransactionsAttributes {
 impl BestTransactionsAttributes {
     /// Creates a new `BestTransactionsAttributes` with the given basefee and blob fee.
     pub const fn new(basefee: u64, blob_fee: Option<u64>) -> Self {
-        Self { basefee, blob_fee }
+        Self { basefee, blob_fee, excluded_senders: None, excluded_hashes: None }
     }
 
     /// Creates a new `BestTransactionsAttributes` with the given basefee.
@@ -751,6 +785,34 @@ impl BestTransactionsAttributes {
         self.blob_fee = Some(blob_fee);
         self
     }
+
+    /// Sets the senders whose transactions should not be yielded.
+    pub fn with_excluded_senders(mut self, senders: impl Into<Arc<HashSet<Address>>>) -> Self {
+        self.excluded_senders = Some(senders.into());
+        self
+    }
+
+    /// Sets the hashes of transactions that should not be yielded.
+    pub fn with_excluded_hashes(mut self, hashes: impl Into<Arc<HashSet<TxHash>>>) -> Self {
+        self.excluded_hashes = Some(hashes.into());
+        self
+    }
+
+    /// Returns `true` if any senders or transaction hashes are excluded.
+    pub fn has_exclusions(&self) -> bool {
+        self.excluded_senders.as_ref().is_some_and(|senders| !senders.is_empty()) ||
+            self.excluded_hashes.as_ref().is_some_and(|hashes| !hashes.is_empty())
+    }
+
+    /// Returns `true` if the given transaction is excluded by these attributes.
+    pub fn is_excluded<T: PoolTransaction>(&self, transaction: &T) -> bool {
+        self.excluded_senders
+            .as_ref()
+            .is_some_and(|senders| senders.contains(&transaction.sender())) ||
+            self.excluded_hashes
+                .as_ref()
+                .is_some_and(|hashes| hashes.contains(transaction.hash()))
+    }
 }
 
 /// Trait for transaction types used inside the pool
@@ -866,6 +928,11 @@ pub trait EthPoolTransaction: PoolTransaction {
         blob: &BlobTransactionSidecar,
         settings: &KzgSettings,
     ) -> Result<(), BlobTransactionValidationError>;
+
+    /// Updates the cached [`PoolTransaction::encoded_length`] for the given blob sidecar, which
+    /// replaces the sidecar the transaction was submitted with, e.g. after its missing proofs were
+    /// computed.
+    fn update_encoded_length(&mut self, sidecar: &BlobTransactionSidecar);
 }
 
 /// The default [`PoolTransaction`] for the [Pool](crate::Pool) for Ethereum.
@@ -1120,6 +1187,19 @@ impl EthPoolTransaction for EthPooledTransaction {
             _ => Err(BlobTransactionValidationError::NotBlobTransaction(self.tx_type())),
         }
     }
+
+    fn update_encoded_length(&mut self, sidecar: &BlobTransactionSidecar) {
+        if let Transaction::Eip4844(tx) = &self.transaction.transaction {
+            let tx = BlobTransaction {
+                hash: self.transaction.hash,
+                transaction: tx.clone(),
+                signature: self.transaction.signature,
+                sidecar: sidecar.clone(),
+            };
+            self.encoded_length =
+                PooledTransactionsElement::BlobTransaction(tx).length_without_header();
+        }
+    }
 }
 
 impl TryFromRecoveredTransaction for EthPooledTransaction {
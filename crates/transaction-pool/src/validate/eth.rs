@@ -2,18 +2,21 @@
 
 use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
 use crate::{
+    blob_proofs::BlobProofComputation,
     blobstore::BlobStore,
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
+    policy::{PolicyDecision, TransactionPolicy},
+    tip_floor::TipFloor,
     traits::TransactionOrigin,
     validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::{BlobParams, ChainSpec, EthereumHardforks};
 use reth_primitives::{
-    constants::{eip4844::MAX_BLOBS_PER_BLOCK, ETHEREUM_BLOCK_GAS_LIMIT},
-    Address, GotExpected, InvalidTransactionError, SealedBlock, TxKind, EIP1559_TX_TYPE_ID,
-    EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
+    constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, GotExpected, InvalidTransactionError,
+    SealedBlock, TxKind, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    LEGACY_TX_TYPE_ID, U256,
 };
 use reth_provider::{AccountReader, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -23,7 +26,10 @@ use revm::{
 };
 use std::{
     marker::PhantomData,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64},
+        Arc,
+    },
 };
 use tokio::sync::Mutex;
 
@@ -129,6 +135,12 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// The policy that is evaluated before the transaction is validated.
+    transaction_policy: Option<Arc<dyn TransactionPolicy>>,
+    /// Computes the missing proofs of the blob sidecars of local transactions.
+    blob_proof_computation: BlobProofComputation,
+    /// The minimum tip of non-local dynamic fee transactions.
+    tip_floor: TipFloor,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -153,6 +165,18 @@ where
         origin: TransactionOrigin,
         mut transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
+        // Evaluate the policy before any other checks
+        let policy_decision =
+            self.transaction_policy.as_ref().map_or(PolicyDecision::Continue, |policy| {
+                policy.evaluate(transaction.sender(), transaction.to())
+            });
+        if policy_decision == PolicyDecision::Reject {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::RejectedByPolicy,
+            )
+        }
+
         // Checks for tx_type
         match transaction.tx_type() {
             LEGACY_TX_TYPE_ID => {
@@ -234,8 +258,9 @@ where
         }
 
         // Drop non-local transactions with a fee lower than the configured fee for acceptance into
-        // the pool.
-        if !self.local_transactions_config.is_local(origin, transaction.sender()) &&
+        // the pool, unless the policy always accepts them.
+        if policy_decision != PolicyDecision::Accept &&
+            !self.local_transactions_config.is_local(origin, transaction.sender()) &&
             transaction.is_eip1559() &&
             transaction.max_priority_fee_per_gas() < self.minimum_priority_fee
         {
@@ -245,6 +270,23 @@ where
             )
         }
 
+        // Drop non-local transactions whose effective tip at the current base fee is below the
+        // floor, unless the policy always accepts them. A fee cap below the base fee pays no tip.
+        let floor = self.tip_floor.floor();
+        if floor > 0 &&
+            policy_decision != PolicyDecision::Accept &&
+            !self.local_transactions_config.is_local(origin, transaction.sender())
+        {
+            let tip =
+                transaction.effective_tip_per_gas(self.tip_floor.base_fee()).unwrap_or_default();
+            if tip < floor {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::TipBelowFloor { tip, floor },
+                )
+            }
+        }
+
         // Checks for chainid
         if let Some(chain_id) = transaction.chain_id() {
             if chain_id != self.chain_id() {
@@ -282,14 +324,15 @@ where
                 )
             }
 
-            if blob_count > MAX_BLOBS_PER_BLOCK {
+            let max_blobs_per_tx = self.fork_tracker.max_blobs_per_tx() as usize;
+            if blob_count > max_blobs_per_tx {
                 // too many blobs
                 return TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
                             have: blob_count,
-                            permitted: MAX_BLOBS_PER_BLOCK,
+                            permitted: max_blobs_per_tx,
                         },
                     ),
                 )
@@ -366,7 +409,25 @@ where
                         )
                     }
                 }
-                EthBlobTransactionSidecar::Present(blob) => {
+                EthBlobTransactionSidecar::Present(mut blob) => {
+                    // compute the proofs of local sidecars that were submitted without them
+                    if origin.is_local() {
+                        match self
+                            .blob_proof_computation
+                            .compute_missing_proofs(&mut blob, self.kzg_settings.get())
+                        {
+                            // the proofs are part of the encoded transaction
+                            Ok(true) => transaction.update_encoded_length(&blob),
+                            Ok(false) => {}
+                            Err(err) => {
+                                return TransactionValidationOutcome::Invalid(
+                                    transaction,
+                                    InvalidPoolTransactionError::Eip4844(err),
+                                )
+                            }
+                        }
+                    }
+
                     // validate the blob
                     if let Err(err) = transaction.validate_blob(&blob, self.kzg_settings.get()) {
                         return TransactionValidationOutcome::Invalid(
@@ -407,6 +468,13 @@ where
         if self.chain_spec.is_shanghai_active_at_timestamp(new_tip_block.timestamp) {
             self.fork_tracker.shanghai.store(true, std::sync::atomic::Ordering::Relaxed);
         }
+
+        let blob_params = self.chain_spec.blob_params_at_timestamp(new_tip_block.timestamp);
+        self.fork_tracker
+            .max_blobs_per_tx
+            .store(blob_params.max_blobs_per_tx(), std::sync::atomic::Ordering::Relaxed);
+
+        self.tip_floor.on_new_head_block(new_tip_block);
     }
 }
 
@@ -418,6 +486,8 @@ pub struct EthTransactionValidatorBuilder {
     shanghai: bool,
     /// Fork indicator whether we are in the Cancun hardfork.
     cancun: bool,
+    /// The maximum number of blobs per transaction of the active fork.
+    max_blobs_per_tx: u64,
     /// Whether using EIP-2718 type transactions is allowed
     eip2718: bool,
     /// Whether using EIP-1559 type transactions is allowed
@@ -439,6 +509,12 @@ pub struct EthTransactionValidatorBuilder {
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
+    /// The policy that is evaluated before the transaction is validated.
+    transaction_policy: Option<Arc<dyn TransactionPolicy>>,
+    /// Computes the missing proofs of the blob sidecars of local transactions.
+    blob_proof_computation: BlobProofComputation,
+    /// The minimum tip of non-local dynamic fee transactions.
+    tip_floor: TipFloor,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -459,6 +535,9 @@ impl EthTransactionValidatorBuilder {
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
+            transaction_policy: None,
+            blob_proof_computation: Default::default(),
+            tip_floor: Default::default(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -470,6 +549,7 @@ impl EthTransactionValidatorBuilder {
 
             // cancun is activated by default
             cancun: true,
+            max_blobs_per_tx: BlobParams::cancun().max_blobs_per_tx(),
         }
     }
 
@@ -487,6 +567,32 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the [`TransactionPolicy`] that rejects or always accepts transactions before they are
+    /// validated.
+    pub fn with_transaction_policy(mut self, transaction_policy: impl TransactionPolicy) -> Self {
+        self.transaction_policy = Some(Arc::new(transaction_policy));
+        self
+    }
+
+    /// Sets the [`BlobProofComputation`] that computes the missing KZG proofs of the blob sidecars
+    /// of local transactions.
+    pub fn with_blob_proof_computation(
+        mut self,
+        blob_proof_computation: BlobProofComputation,
+    ) -> Self {
+        self.blob_proof_computation = blob_proof_computation;
+        self
+    }
+
+    /// Sets the [`TipFloor`] that determines the minimum tip of non-local dynamic fee
+    /// transactions.
+    ///
+    /// The validator updates a dynamic floor with the transactions of new head blocks.
+    pub fn with_tip_floor(mut self, tip_floor: TipFloor) -> Self {
+        self.tip_floor = tip_floor;
+        self
+    }
+
     /// Set the Cancun fork.
     pub const fn set_cancun(mut self, cancun: bool) -> Self {
         self.cancun = cancun;
@@ -561,6 +667,8 @@ impl EthTransactionValidatorBuilder {
     pub fn with_head_timestamp(mut self, timestamp: u64) -> Self {
         self.cancun = self.chain_spec.is_cancun_active_at_timestamp(timestamp);
         self.shanghai = self.chain_spec.is_shanghai_active_at_timestamp(timestamp);
+        self.max_blobs_per_tx =
+            self.chain_spec.blob_params_at_timestamp(timestamp).max_blobs_per_tx();
         self
     }
 
@@ -591,6 +699,7 @@ impl EthTransactionValidatorBuilder {
             chain_spec,
             shanghai,
             cancun,
+            max_blobs_per_tx,
             eip2718,
             eip1559,
             eip4844,
@@ -599,11 +708,17 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            transaction_policy,
+            blob_proof_computation,
+            tip_floor,
             ..
         } = self;
 
-        let fork_tracker =
-            ForkTracker { shanghai: AtomicBool::new(shanghai), cancun: AtomicBool::new(cancun) };
+        let fork_tracker = ForkTracker {
+            shanghai: AtomicBool::new(shanghai),
+            cancun: AtomicBool::new(cancun),
+            max_blobs_per_tx: AtomicU64::new(max_blobs_per_tx),
+        };
 
         let inner = EthTransactionValidatorInner {
             chain_spec,
@@ -618,6 +733,9 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            transaction_policy,
+            blob_proof_computation,
+            tip_floor,
             _marker: Default::default(),
         };
 
@@ -675,6 +793,8 @@ pub(crate) struct ForkTracker {
     pub(crate) shanghai: AtomicBool,
     /// Tracks if cancun is activated at the block's timestamp.
     pub(crate) cancun: AtomicBool,
+    /// Tracks the maximum number of blobs per transaction at the block's timestamp.
+    pub(crate) max_blobs_per_tx: AtomicU64,
 }
 
 impl ForkTracker {
@@ -687,6 +807,11 @@ impl ForkTracker {
     pub(crate) fn is_cancun_activated(&self) -> bool {
         self.cancun.load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Returns the maximum number of blobs per transaction.
+    pub(crate) fn max_blobs_per_tx(&self) -> u64 {
+        self.max_blobs_per_tx.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 /// Ensure that the code size is not greater than `max_init_code_size`.
@@ -745,8 +870,11 @@ pub fn calculate_intrinsic_gas_after_merge(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, CoinbaseTipOrdering,
-        EthPooledTransaction, Pool, TransactionPool,
+        blobstore::InMemoryBlobStore,
+        error::PoolErrorKind,
+        policy::{AddressListPolicy, AddressLists},
+        tip_floor::TipFloorMode,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use reth_chainspec::MAINNET;
     use reth_primitives::{hex, FromRecoveredPooledTransaction, PooledTransactionsElement, U256};
@@ -827,4 +955,72 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn evaluates_transaction_policy() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+        let policy = AddressListPolicy::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_minimum_priority_fee(u128::MAX)
+            .with_transaction_policy(policy.clone())
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Underpriced)
+        ));
+
+        // allowed senders are exempt from the minimum priority fee
+        policy.set_lists(AddressLists {
+            allowed_senders: [transaction.sender()].into(),
+            ..Default::default()
+        });
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        policy.set_lists(AddressLists {
+            denied_senders: [transaction.sender()].into(),
+            ..Default::default()
+        });
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::RejectedByPolicy)
+        ));
+    }
+
+    #[test]
+    fn enforces_tip_floor() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+        let tip = transaction.max_priority_fee_per_gas().unwrap();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_tip_floor(TipFloor::new(TipFloorMode::Static(tip + 1)))
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::TipBelowFloor { floor, .. }
+            ) if floor == tip + 1
+        ));
+
+        // local transactions are exempt from the floor
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction);
+        assert!(outcome.is_valid());
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{TrieElement, TrieNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::TriePrefixSetsMut,
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     walker::TrieWalker,
     HashBuilder, Nibbles,
@@ -10,8 +10,9 @@ use alloy_rlp::{BufMut, Encodable};
 use reth_db::tables;
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
-use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, B256};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, Bytes, B256, U256};
 use reth_trie_common::{proof::ProofRetainer, AccountProof, StorageProof, TrieAccount};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// A struct for generating merkle proofs.
 ///
@@ -24,23 +25,32 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// The prefix sets of the hashed state that differs from the tries in the database.
+    prefix_sets: TriePrefixSetsMut,
 }
 
 impl<'a, TX, H> Proof<'a, TX, H> {
     /// Creates a new proof generator.
-    pub const fn new(tx: &'a TX, hashed_cursor_factory: H) -> Self {
-        Self { tx, hashed_cursor_factory }
+    pub fn new(tx: &'a TX, hashed_cursor_factory: H) -> Self {
+        Self { tx, hashed_cursor_factory, prefix_sets: TriePrefixSetsMut::default() }
     }
 
     /// Set the hashed cursor factory.
     pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
-        Proof { tx: self.tx, hashed_cursor_factory }
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets of the keys that are changed by the hashed cursor factory, so that the
+    /// proofs are not built from the stale intermediate nodes of the database.
+    pub fn with_prefix_sets_mut(mut self, prefix_sets: TriePrefixSetsMut) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance from database transaction.
-    pub const fn from_tx(tx: &'a TX) -> Self {
+    pub fn from_tx(tx: &'a TX) -> Self {
         Self::new(tx, tx)
     }
 }
@@ -65,7 +75,7 @@ where
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
+        let mut prefix_set = self.prefix_sets.account_prefix_set.clone();
         prefix_set.insert(target_nibbles.clone());
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
@@ -107,6 +117,68 @@ where
         Ok(account_proof)
     }
 
+    /// Generate the proof nodes of the given hashed accounts and, for each of them, of the given
+    /// hashed storage slots, in a single pass over the account trie.
+    ///
+    /// Unlike [`Self::account_proof`], the targets are hashed keys, so the nodes along the paths of
+    /// keys whose preimages are unknown can be retained as well.
+    pub fn multiproof_nodes(
+        &self,
+        targets: &BTreeMap<B256, BTreeSet<B256>>,
+    ) -> Result<Vec<Bytes>, StateRootError> {
+        let target_nibbles = targets.keys().map(Nibbles::unpack).collect::<Vec<_>>();
+
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor =
+            DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+
+        let mut prefix_set = self.prefix_sets.account_prefix_set.clone();
+        for nibbles in &target_nibbles {
+            prefix_set.insert(nibbles.clone());
+        }
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        let retainer = ProofRetainer::from_iter(target_nibbles);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+
+        let mut nodes = Vec::new();
+        let mut account_rlp = Vec::with_capacity(128);
+        let mut account_node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                TrieElement::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                TrieElement::Leaf(hashed_address, account) => {
+                    let storage_root = match targets.get(&hashed_address) {
+                        Some(slots) => {
+                            let (storage_root, storage_nodes) = self
+                                .storage_root_with_proof_nodes(
+                                    hashed_address,
+                                    slots.iter().map(Nibbles::unpack).collect(),
+                                    |_, _| {},
+                                )?;
+                            nodes.extend(storage_nodes.into_values());
+                            storage_root
+                        }
+                        None => self.storage_root(hashed_address)?,
+                    };
+
+                    account_rlp.clear();
+                    let account = TrieAccount::from((account, storage_root));
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let _ = hash_builder.root();
+
+        nodes.extend(hash_builder.take_proofs().into_values());
+        Ok(nodes)
+    }
+
     /// Compute storage root.
     pub fn storage_root(&self, hashed_address: B256) -> Result<B256, StorageRootError> {
         let (storage_root, _) = self.storage_root_with_proofs(hashed_address, &[])?;
@@ -119,18 +191,54 @@ where
         hashed_address: B256,
         slots: &[B256],
     ) -> Result<(B256, Vec<StorageProof>), StorageRootError> {
+        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+
+        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
+        let (root, all_proof_nodes) = self.storage_root_with_proof_nodes(
+            hashed_address,
+            target_nibbles,
+            |nibbles, value| {
+                if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == *nibbles) {
+                    proof.set_value(value);
+                }
+            },
+        )?;
+
+        for proof in &mut proofs {
+            // Iterate over all proof nodes and find the matching ones.
+            // The filtered results are guaranteed to be in order.
+            let matching_proof_nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| proof.nibbles.starts_with(path))
+                .map(|(_, node)| node.clone());
+            proof.set_proof(matching_proof_nodes.collect());
+        }
+
+        Ok((root, proofs))
+    }
+
+    /// Compute the storage root and retain the nodes along the paths of the target nibbles, by
+    /// path. `on_leaf` is called with the path and the value of every leaf.
+    fn storage_root_with_proof_nodes(
+        &self,
+        hashed_address: B256,
+        target_nibbles: Vec<Nibbles>,
+        mut on_leaf: impl FnMut(&Nibbles, U256),
+    ) -> Result<(B256, BTreeMap<Nibbles, Bytes>), StorageRootError> {
         let mut hashed_storage_cursor =
             self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
 
-        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
-
         // short circuit on empty storage
         if hashed_storage_cursor.is_storage_empty()? {
-            return Ok((EMPTY_ROOT_HASH, proofs))
+            return Ok((EMPTY_ROOT_HASH, BTreeMap::new()))
         }
 
-        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let mut prefix_set =
+            self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default();
+        for nibbles in &target_nibbles {
+            prefix_set.insert(nibbles.clone());
+        }
+        let prefix_set = prefix_set.freeze();
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -147,9 +255,7 @@ where
                 }
                 TrieElement::Leaf(hashed_slot, value) => {
                     let nibbles = Nibbles::unpack(hashed_slot);
-                    if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == nibbles) {
-                        proof.set_value(value);
-                    }
+                    on_leaf(&nibbles, value);
                     hash_builder.add_leaf(nibbles, alloy_rlp::encode_fixed_size(&value).as_ref());
                 }
             }
@@ -157,25 +263,14 @@ where
 
         let root = hash_builder.root();
 
-        let all_proof_nodes = hash_builder.take_proofs();
-        for proof in &mut proofs {
-            // Iterate over all proof nodes and find the matching ones.
-            // The filtered results are guaranteed to be in order.
-            let matching_proof_nodes = all_proof_nodes
-                .iter()
-                .filter(|(path, _)| proof.nibbles.starts_with(path))
-                .map(|(_, node)| node.clone());
-            proof.set_proof(matching_proof_nodes.collect());
-        }
-
-        Ok((root, proofs))
+        Ok((root, hash_builder.take_proofs()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, StateRoot};
     use once_cell::sync::Lazy;
     use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET};
     use reth_db_api::database::Database;
@@ -462,4 +557,27 @@ mod tests {
         similar_asserts::assert_eq!(account_proof, expected);
         assert_eq!(account_proof.verify(root), Ok(()));
     }
+
+    #[test]
+    fn proof_with_hashed_state_overlay() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let changed = Address::from_str("0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2").unwrap();
+        let unchanged = Address::from_str("0x62b0dd4aab2b1a0a04e279e2b828791a10755528").unwrap();
+        let hashed_state = HashedPostState::default().with_accounts([(
+            keccak256(changed),
+            Some(Account { balance: U256::from(7), ..Default::default() }),
+        )]);
+
+        let provider = factory.provider().unwrap();
+        let root = hashed_state.state_root(provider.tx_ref()).unwrap();
+        for target in [changed, unchanged] {
+            let account_proof = hashed_state.account_proof(provider.tx_ref(), target, &[]).unwrap();
+            assert_eq!(account_proof.verify(root), Ok(()));
+        }
+        let account_proof = hashed_state.account_proof(provider.tx_ref(), changed, &[]).unwrap();
+        assert_eq!(account_proof.info.map(|account| account.balance), Some(U256::from(7)));
+    }
 }
@@ -0,0 +1,297 @@
+use super::{TrieCursor, TrieCursorFactory};
+use crate::{updates::TrieUpdates, BranchNodeCompact, Nibbles};
+use parking_lot::Mutex;
+use reth_db::DatabaseError;
+use reth_primitives::B256;
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// The configuration of the [`TrieNodeCache`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrieNodeCacheConfig {
+    /// The maximum number of cached account trie nodes.
+    pub max_account_nodes: u32,
+    /// The maximum number of cached storage trie nodes, across all storage tries.
+    pub max_storage_nodes: u32,
+    /// The maximum path length of cached nodes.
+    ///
+    /// Only the upper levels of the tries are shared between the state root computations of
+    /// consecutive blocks, deeper nodes are rarely accessed twice.
+    pub max_depth: usize,
+}
+
+impl Default for TrieNodeCacheConfig {
+    fn default() -> Self {
+        Self { max_account_nodes: 100_000, max_storage_nodes: 100_000, max_depth: 4 }
+    }
+}
+
+/// An in-memory cache of the branch nodes of the account and storage tries in the database,
+/// keyed by their path.
+///
+/// The cache is cheap to clone and can be shared between the state root computations of
+/// consecutive blocks. It must only be used with cursors over committed database state, and the
+/// cached nodes must be invalidated whenever the tries in the database change, either with
+/// [`TrieNodeCache::apply_updates`] for the trie updates that are written, or with
+/// [`TrieNodeCache::clear`].
+#[derive(Clone, Debug)]
+pub struct TrieNodeCache {
+    inner: Arc<Mutex<TrieNodeCacheInner>>,
+    max_depth: usize,
+}
+
+#[derive(Debug)]
+struct TrieNodeCacheInner {
+    account_nodes: LruMap<Nibbles, BranchNodeCompact>,
+    storage_nodes: LruMap<(B256, Nibbles), BranchNodeCompact>,
+}
+
+impl TrieNodeCache {
+    /// Creates an empty cache with the given configuration.
+    pub fn new(config: TrieNodeCacheConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(TrieNodeCacheInner {
+                account_nodes: LruMap::new(ByLength::new(config.max_account_nodes)),
+                storage_nodes: LruMap::new(ByLength::new(config.max_storage_nodes)),
+            })),
+            max_depth: config.max_depth,
+        }
+    }
+
+    /// Returns the total number of cached nodes.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock();
+        inner.account_nodes.len() + inner.storage_nodes.len()
+    }
+
+    /// Returns `true` if no nodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached nodes.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.account_nodes.clear();
+        inner.storage_nodes.clear();
+    }
+
+    /// Invalidates the cached nodes that are changed by the trie updates.
+    ///
+    /// The nodes are only removed from the cache, so it's safe to call this before the updates
+    /// are committed.
+    pub fn apply_updates(&self, updates: &TrieUpdates) {
+        let mut inner = self.inner.lock();
+        for path in updates.account_nodes.keys().chain(&updates.removed_nodes) {
+            inner.account_nodes.remove(path);
+        }
+
+        for (hashed_address, storage_updates) in &updates.storage_tries {
+            if storage_updates.is_deleted {
+                let deleted = inner
+                    .storage_nodes
+                    .iter()
+                    .filter(|((address, _), _)| address == hashed_address)
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>();
+                for key in deleted {
+                    inner.storage_nodes.remove(&key);
+                }
+            }
+            for path in storage_updates.storage_nodes.keys().chain(&storage_updates.removed_nodes) {
+                inner.storage_nodes.remove(&(*hashed_address, path.clone()));
+            }
+        }
+    }
+
+    fn get(&self, hashed_address: Option<B256>, path: &Nibbles) -> Option<BranchNodeCompact> {
+        if path.len() > self.max_depth {
+            return None
+        }
+        let mut inner = self.inner.lock();
+        match hashed_address {
+            Some(hashed_address) => {
+                inner.storage_nodes.get(&(hashed_address, path.clone())).cloned()
+            }
+            None => inner.account_nodes.get(path).cloned(),
+        }
+    }
+
+    fn insert(&self, hashed_address: Option<B256>, path: Nibbles, node: BranchNodeCompact) {
+        if path.len() > self.max_depth {
+            return
+        }
+        let mut inner = self.inner.lock();
+        match hashed_address {
+            Some(hashed_address) => {
+                inner.storage_nodes.insert((hashed_address, path), node);
+            }
+            None => {
+                inner.account_nodes.insert(path, node);
+            }
+        }
+    }
+}
+
+/// The trie cursor factory that serves trie nodes from a [`TrieNodeCache`] before falling back to
+/// the underlying cursors.
+///
+/// Without a cache, the cursors only delegate to the underlying cursors.
+#[derive(Clone, Debug)]
+pub struct CachedTrieCursorFactory<CF> {
+    /// Underlying trie cursor factory.
+    cursor_factory: CF,
+    /// The shared trie node cache.
+    cache: Option<TrieNodeCache>,
+}
+
+impl<CF> CachedTrieCursorFactory<CF> {
+    /// Create a new trie cursor factory.
+    pub const fn new(cursor_factory: CF, cache: Option<TrieNodeCache>) -> Self {
+        Self { cursor_factory, cache }
+    }
+}
+
+impl<CF: TrieCursorFactory> TrieCursorFactory for CachedTrieCursorFactory<CF> {
+    type AccountTrieCursor = CachedTrieCursor<CF::AccountTrieCursor>;
+    type StorageTrieCursor = CachedTrieCursor<CF::StorageTrieCursor>;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.account_trie_cursor()?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), None))
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.storage_trie_cursor(hashed_address)?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), Some(hashed_address)))
+    }
+}
+
+/// The cursor that serves trie nodes from a [`TrieNodeCache`] and caches the nodes it reads from
+/// the underlying cursor.
+///
+/// A seek is only served from the cache if a node exists at exactly the sought path, which is
+/// always the first node at or after the path.
+#[derive(Debug)]
+pub struct CachedTrieCursor<C> {
+    /// The underlying cursor.
+    cursor: C,
+    /// The shared trie node cache.
+    cache: Option<TrieNodeCache>,
+    /// The hashed address of the account that the storage trie belongs to, `None` for the account
+    /// trie.
+    hashed_address: Option<B256>,
+    /// The key of the last node served from the cache, which the underlying cursor is not
+    /// positioned at.
+    cached_key: Option<Nibbles>,
+}
+
+impl<C> CachedTrieCursor<C> {
+    const fn new(cursor: C, cache: Option<TrieNodeCache>, hashed_address: Option<B256>) -> Self {
+        Self { cursor, cache, hashed_address, cached_key: None }
+    }
+
+    /// Returns the node at the path from the cache.
+    fn cached(&mut self, key: &Nibbles) -> Option<(Nibbles, BranchNodeCompact)> {
+        let node = self.cache.as_ref()?.get(self.hashed_address, key)?;
+        self.cached_key = Some(key.clone());
+        Some((key.clone(), node))
+    }
+
+    /// Caches the node read from the underlying cursor.
+    fn cache_entry(
+        &mut self,
+        entry: Option<(Nibbles, BranchNodeCompact)>,
+    ) -> Option<(Nibbles, BranchNodeCompact)> {
+        self.cached_key = None;
+        if let Some((cache, (key, node))) = self.cache.as_ref().zip(entry.as_ref()) {
+            cache.insert(self.hashed_address, key.clone(), node.clone());
+        }
+        entry
+    }
+}
+
+impl<C: TrieCursor> TrieCursor for CachedTrieCursor<C> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        if let Some(entry) = self.cached(&key) {
+            return Ok(Some(entry))
+        }
+        let entry = self.cursor.seek_exact(key)?;
+        Ok(self.cache_entry(entry))
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        if let Some(entry) = self.cached(&key) {
+            return Ok(Some(entry))
+        }
+        let entry = self.cursor.seek(key)?;
+        Ok(self.cache_entry(entry))
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        match &self.cached_key {
+            Some(key) => Ok(Some(key.clone())),
+            None => self.cursor.current(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StoredBranchNode;
+    use reth_db::tables;
+    use reth_db_api::{cursor::DbCursorRW, transaction::DbTxMut};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn serves_cached_nodes_until_invalidated() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let key = Nibbles::from_nibbles([0x1, 0x2]);
+        let node = BranchNodeCompact::new(0b11, 0b11, 0, Vec::default(), None);
+        provider
+            .tx_ref()
+            .cursor_write::<tables::AccountsTrie>()
+            .unwrap()
+            .upsert(key.clone().into(), StoredBranchNode(node.clone()))
+            .unwrap();
+
+        let cache = TrieNodeCache::new(TrieNodeCacheConfig::default());
+        let cursor_factory = CachedTrieCursorFactory::new(provider.tx_ref(), Some(cache.clone()));
+        let mut cursor = cursor_factory.account_trie_cursor().unwrap();
+        assert_eq!(cursor.seek(Nibbles::from_nibbles([0x1])).unwrap(), Some((key.clone(), node)));
+        assert_eq!(cache.len(), 1);
+
+        // the node is served from the cache after it's removed from the database
+        provider.tx_ref().delete::<tables::AccountsTrie>(key.clone().into(), None).unwrap();
+        assert!(cursor.seek_exact(key.clone()).unwrap().is_some());
+        assert_eq!(cursor.current().unwrap(), Some(key.clone()));
+
+        let mut updates = TrieUpdates::default();
+        updates.removed_nodes.insert(key.clone());
+        cache.apply_updates(&updates);
+        assert!(cache.is_empty());
+        assert_eq!(cursor.seek_exact(key).unwrap(), None);
+    }
+
+    #[test]
+    fn skips_deep_nodes() {
+        let cache = TrieNodeCache::new(TrieNodeCacheConfig { max_depth: 1, ..Default::default() });
+        let node = BranchNodeCompact::new(0b11, 0b11, 0, Vec::default(), None);
+        cache.insert(None, Nibbles::from_nibbles([0x1, 0x2]), node.clone());
+        cache.insert(Some(B256::ZERO), Nibbles::from_nibbles([0x1]), node);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(Some(B256::ZERO), &Nibbles::from_nibbles([0x1])).is_some());
+        assert!(cache.get(None, &Nibbles::from_nibbles([0x1])).is_none());
+    }
+}
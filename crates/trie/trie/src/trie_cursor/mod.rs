@@ -5,6 +5,9 @@ use reth_primitives::B256;
 /// Database implementations of trie cursors.
 mod database_cursors;
 
+/// Trie cursors backed by a shared cache of trie nodes.
+mod cached;
+
 /// In-memory implementations of trie cursors.
 mod in_memory;
 
@@ -15,6 +18,7 @@ mod subnode;
 pub mod noop;
 
 pub use self::{
+    cached::{CachedTrieCursor, CachedTrieCursorFactory, TrieNodeCache, TrieNodeCacheConfig},
     database_cursors::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     in_memory::*,
     subnode::CursorSubNode,
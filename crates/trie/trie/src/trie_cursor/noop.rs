@@ -4,7 +4,7 @@ use reth_db::DatabaseError;
 use reth_primitives::B256;
 
 /// Noop trie cursor factory.
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 #[non_exhaustive]
 pub struct NoopTrieCursorFactory;
 
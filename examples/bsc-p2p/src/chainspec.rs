@@ -22,7 +22,9 @@ pub(crate) fn bsc_chain_spec() -> Arc<ChainSpec> {
         )]),
         deposit_contract: None,
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+        blob_schedule: Default::default(),
         prune_delete_limit: 0,
+        hardfork_overrides: Default::default(),
     }
     .into()
 }
@@ -100,7 +100,7 @@ impl ConfigureEvmEnv for MyEvmConfig {
         contract: Address,
         data: Bytes,
     ) {
-        EthEvmConfig::fill_tx_env_system_contract_call(env, caller, contract, data)
+        <EthEvmConfig>::fill_tx_env_system_contract_call(env, caller, contract, data)
     }
 }
 
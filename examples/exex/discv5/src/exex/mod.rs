@@ -57,6 +57,9 @@ impl<Node: FullNodeComponents> Future for ExEx<Node> {
                     ExExNotification::ChainReverted { old } => {
                         info!(reverted_chain = ?old.range(), "Received revert");
                     }
+                    ExExNotification::NotificationsSkipped { count } => {
+                        info!(count, "Received skipped notifications");
+                    }
                 }
 
                 if let Some(committed_chain) = notification.committed_chain() {
@@ -0,0 +1,11 @@
+//! Cross-checks the reth block executor against plain revm for a block generated from the seed.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|seed: u64| {
+    if let Err(mismatch) = reth_differential_fuzz::check_seed(seed) {
+        panic!("{mismatch}")
+    }
+});
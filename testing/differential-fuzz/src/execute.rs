@@ -0,0 +1,248 @@
+//! The two execution paths that are cross-checked.
+
+use crate::scenario::Scenario;
+use reth_evm::execute::{BlockExecutionInput, BlockExecutorProvider, Executor};
+use reth_evm_ethereum::execute::EthExecutorProvider;
+use reth_primitives::{
+    keccak256, AccessList, Account, Address, Log, Transaction, B256, KECCAK_EMPTY, U256,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{AccountState, CacheDB, EmptyDB},
+    primitives::{AccountInfo, Bytecode, SpecId},
+    test_utils::StateProviderTest,
+    Evm,
+};
+use std::collections::BTreeMap;
+
+/// The observable result of executing a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// Receipts of the transactions in the block.
+    pub receipts: Vec<ReceiptSummary>,
+    /// Total gas used by the block.
+    pub gas_used: u64,
+    /// All non-empty accounts after the block.
+    pub state: BTreeMap<Address, AccountSummary>,
+}
+
+/// The fields of a receipt that both execution paths produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptSummary {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// Gas used by the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// An account of the post-state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountSummary {
+    /// Account balance.
+    pub balance: U256,
+    /// Account nonce.
+    pub nonce: u64,
+    /// Hash of the account code.
+    pub code_hash: B256,
+    /// Non-zero storage slots.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl AccountSummary {
+    /// Returns `true` if the account is empty as defined by EIP-161.
+    fn is_empty(&self) -> bool {
+        self.balance.is_zero() && self.nonce == 0 && self.code_hash == KECCAK_EMPTY
+    }
+}
+
+/// Returns the accounts of the pre-state of the scenario.
+fn pre_state(scenario: &Scenario) -> BTreeMap<Address, AccountSummary> {
+    scenario
+        .pre
+        .iter()
+        .map(|(address, account)| {
+            let code_hash =
+                if account.code.is_empty() { KECCAK_EMPTY } else { keccak256(&account.code) };
+            let summary = AccountSummary {
+                balance: account.balance,
+                nonce: account.nonce,
+                code_hash,
+                storage: account.storage.clone(),
+            };
+            (*address, summary)
+        })
+        .collect()
+}
+
+/// Executes the block of the scenario with reth's [`EthExecutorProvider`].
+///
+/// Returns the error message if the block is rejected.
+pub fn execute_with_reth(scenario: &Scenario) -> Result<Outcome, String> {
+    let mut db = StateProviderTest::default();
+    for (address, account) in &scenario.pre {
+        db.insert_account(
+            *address,
+            Account { nonce: account.nonce, balance: account.balance, bytecode_hash: None },
+            (!account.code.is_empty()).then(|| account.code.clone()),
+            account.storage.iter().map(|(slot, value)| (B256::from(*slot), *value)).collect(),
+        );
+    }
+
+    let output = EthExecutorProvider::ethereum(scenario.chain_spec.clone())
+        .executor(StateProviderDatabase::new(&db))
+        .execute(BlockExecutionInput::new(&scenario.block, U256::ZERO))
+        .map_err(|err| err.to_string())?;
+
+    let mut state = pre_state(scenario);
+    for (address, account) in output.state.state {
+        let Some(info) = account.info else {
+            state.remove(&address);
+            continue
+        };
+        let mut storage = match state.remove(&address) {
+            Some(pre) if !account.was_destroyed() => pre.storage,
+            _ => BTreeMap::new(),
+        };
+        for (slot, value) in account.storage {
+            if value.present_value.is_zero() {
+                storage.remove(&slot);
+            } else {
+                storage.insert(slot, value.present_value);
+            }
+        }
+        let summary = AccountSummary {
+            balance: info.balance,
+            nonce: info.nonce,
+            code_hash: info.code_hash,
+            storage,
+        };
+        if !summary.is_empty() {
+            state.insert(address, summary);
+        }
+    }
+
+    let receipts = output
+        .receipts
+        .into_iter()
+        .map(|receipt| ReceiptSummary {
+            success: receipt.success,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            logs: receipt.logs,
+        })
+        .collect();
+
+    Ok(Outcome { receipts, gas_used: output.gas_used, state })
+}
+
+/// Executes the transactions of the scenario one by one with a plain [`Evm`] on an in-memory
+/// database, without any of reth's EVM configuration or executor code.
+///
+/// Returns the error message if a transaction is invalid.
+pub fn execute_with_revm(scenario: &Scenario) -> Result<Outcome, String> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, account) in &scenario.pre {
+        let code_hash =
+            if account.code.is_empty() { KECCAK_EMPTY } else { keccak256(&account.code) };
+        db.insert_account_info(
+            *address,
+            AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce,
+                code_hash,
+                code: Some(Bytecode::new_raw(account.code.clone())),
+            },
+        );
+        for (slot, value) in &account.storage {
+            db.insert_account_storage(*address, *slot, *value)
+                .expect("in-memory database does not fail");
+        }
+    }
+
+    let header = &scenario.block.header;
+    let chain_id = scenario.chain_spec.chain.id();
+    let mut receipts = Vec::with_capacity(scenario.block.body.len());
+    let mut cumulative_gas_used = 0;
+    for (sender, transaction) in scenario.block.transactions_with_sender() {
+        let (gas_price, gas_priority_fee, access_list) = match &transaction.transaction {
+            Transaction::Legacy(tx) => (tx.gas_price, None, Vec::new()),
+            Transaction::Eip2930(tx) => (tx.gas_price, None, access_list(&tx.access_list)),
+            Transaction::Eip1559(tx) => (
+                tx.max_fee_per_gas,
+                Some(tx.max_priority_fee_per_gas),
+                access_list(&tx.access_list),
+            ),
+            tx => return Err(format!("unsupported transaction type {:?}", tx.tx_type())),
+        };
+
+        let mut evm = Evm::builder()
+            .with_db(&mut db)
+            .with_spec_id(SpecId::SHANGHAI)
+            .modify_cfg_env(|cfg| cfg.chain_id = chain_id)
+            .modify_block_env(|block| {
+                block.number = U256::from(header.number);
+                block.coinbase = header.beneficiary;
+                block.timestamp = U256::from(header.timestamp);
+                block.gas_limit = U256::from(header.gas_limit);
+                block.basefee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+                block.difficulty = U256::ZERO;
+                block.prevrandao = Some(header.mix_hash);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = *sender;
+                tx.gas_limit = transaction.gas_limit();
+                tx.gas_price = U256::from(gas_price);
+                tx.gas_priority_fee = gas_priority_fee.map(U256::from);
+                tx.transact_to = transaction.kind();
+                tx.value = transaction.value();
+                tx.data = transaction.input().clone();
+                tx.chain_id = transaction.chain_id();
+                tx.nonce = Some(transaction.nonce());
+                tx.access_list = access_list;
+            })
+            .build();
+        let result = evm.transact_commit().map_err(|err| err.to_string())?;
+
+        cumulative_gas_used += result.gas_used();
+        receipts.push(ReceiptSummary {
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs(),
+        });
+    }
+
+    let state = db
+        .accounts
+        .into_iter()
+        .filter(|(_, account)| account.account_state != AccountState::NotExisting)
+        .map(|(address, account)| {
+            let summary = AccountSummary {
+                balance: account.info.balance,
+                nonce: account.info.nonce,
+                code_hash: account.info.code_hash,
+                storage: account
+                    .storage
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .collect(),
+            };
+            (address, summary)
+        })
+        // EIP-161: touched empty accounts are removed from the state
+        .filter(|(_, summary)| !summary.is_empty())
+        .collect();
+
+    Ok(Outcome { receipts, gas_used: cumulative_gas_used, state })
+}
+
+/// Converts an access list into the representation of the revm transaction environment.
+fn access_list(access_list: &AccessList) -> Vec<(Address, Vec<U256>)> {
+    access_list
+        .iter()
+        .map(|item| {
+            let slots = item.storage_keys.iter().map(|key| U256::from_be_bytes(key.0)).collect();
+            (item.address, slots)
+        })
+        .collect()
+}
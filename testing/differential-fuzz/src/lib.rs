@@ -0,0 +1,167 @@
+//! Differential fuzzing of reth's block executor.
+//!
+//! A block of random transactions and its pre-state are generated from a seed, executed with
+//! reth's [`EthExecutorProvider`](reth_evm_ethereum::execute::EthExecutorProvider) and, as a
+//! reference, transaction by transaction with a plain revm [`Evm`](reth_revm::Evm). Receipts, gas
+//! and the post-state of both paths have to match.
+//!
+//! The cargo-fuzz targets live in the `fuzz` directory:
+//!
+//! ```sh
+//! cd testing/differential-fuzz
+//! cargo +nightly fuzz run executor
+//! ```
+//!
+//! A reported mismatch is reproduced by calling [`check_seed`] with the seed of the report.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod execute;
+pub mod scenario;
+
+pub use execute::{execute_with_reth, execute_with_revm, AccountSummary, Outcome, ReceiptSummary};
+pub use scenario::Scenario;
+
+use std::fmt::Debug;
+
+/// The part of the execution output that differs between the two execution paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum MismatchKind {
+    /// Only one of the paths rejected the block.
+    #[display(fmt = "validity")]
+    Validity,
+    /// The receipts differ.
+    #[display(fmt = "receipts")]
+    Receipts,
+    /// The total gas used differs.
+    #[display(fmt = "gas used")]
+    GasUsed,
+    /// The post-state differs.
+    #[display(fmt = "state")]
+    State,
+}
+
+/// A difference between reth's executor and the reference execution.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} mismatch for seed {seed}: {details}")]
+pub struct Mismatch {
+    /// The seed the block was generated from.
+    pub seed: u64,
+    /// What differs.
+    pub kind: MismatchKind,
+    /// Both sides of the first difference.
+    pub details: String,
+}
+
+/// Generates the scenario of the seed, executes it on both paths and compares the outcomes.
+pub fn check_seed(seed: u64) -> Result<(), Mismatch> {
+    let scenario = Scenario::generate(seed);
+    compare(seed, execute_with_reth(&scenario), execute_with_revm(&scenario))
+}
+
+/// Compares the outcomes of reth's executor and the reference execution.
+///
+/// Both paths rejecting the block is not a mismatch.
+pub fn compare(
+    seed: u64,
+    reth: Result<Outcome, String>,
+    reference: Result<Outcome, String>,
+) -> Result<(), Mismatch> {
+    let mismatch = |kind, reth: &dyn Debug, reference: &dyn Debug| Mismatch {
+        seed,
+        kind,
+        details: format!("reth {reth:?}, reference {reference:?}"),
+    };
+
+    let (reth, reference) = match (reth, reference) {
+        (Ok(reth), Ok(reference)) => (reth, reference),
+        (Err(_), Err(_)) => return Ok(()),
+        (reth, reference) => {
+            return Err(mismatch(MismatchKind::Validity, &reth.err(), &reference.err()))
+        }
+    };
+
+    if reth.receipts.len() != reference.receipts.len() {
+        return Err(mismatch(
+            MismatchKind::Receipts,
+            &reth.receipts.len(),
+            &reference.receipts.len(),
+        ))
+    }
+    if let Some((index, (a, b))) =
+        reth.receipts.iter().zip(&reference.receipts).enumerate().find(|(_, (a, b))| a != b)
+    {
+        return Err(mismatch(MismatchKind::Receipts, &(index, a), &(index, b)))
+    }
+
+    if reth.gas_used != reference.gas_used {
+        return Err(mismatch(MismatchKind::GasUsed, &reth.gas_used, &reference.gas_used))
+    }
+
+    if let Some(address) = reth
+        .state
+        .keys()
+        .chain(reference.state.keys())
+        .find(|address| reth.state.get(address) != reference.state.get(address))
+    {
+        return Err(mismatch(
+            MismatchKind::State,
+            &(address, reth.state.get(address)),
+            &(address, reference.state.get(address)),
+        ))
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Address;
+
+    #[test]
+    fn executor_matches_reference() {
+        for seed in 0..64 {
+            check_seed(seed).unwrap();
+        }
+    }
+
+    #[test]
+    fn scenario_is_deterministic() {
+        let a = Scenario::generate(7);
+        let b = Scenario::generate(7);
+        assert_eq!(a.pre, b.pre);
+        assert_eq!(a.block, b.block);
+    }
+
+    #[test]
+    fn reports_seed_of_mismatch() {
+        let reth = Outcome::default();
+        let mut reference = Outcome::default();
+        reference.state.insert(
+            Address::ZERO,
+            AccountSummary {
+                balance: Default::default(),
+                nonce: 1,
+                code_hash: Default::default(),
+                storage: Default::default(),
+            },
+        );
+
+        let mismatch = compare(42, Ok(reth.clone()), Ok(reference)).unwrap_err();
+        assert_eq!(mismatch.kind, MismatchKind::State);
+        assert!(mismatch.to_string().contains("seed 42"));
+
+        assert_eq!(
+            compare(42, Ok(reth), Err("invalid".to_string())).unwrap_err().kind,
+            MismatchKind::Validity
+        );
+        assert_eq!(compare(42, Err("invalid".to_string()), Err("invalid".to_string())), Ok(()));
+    }
+}
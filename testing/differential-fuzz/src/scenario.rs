@@ -0,0 +1,236 @@
+//! Generation of random blocks and their pre-state from a seed.
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, public_key_to_address, AccessList, AccessListItem, Address, Block,
+    BlockWithSenders, Bytes, Header, Transaction, TxEip1559, TxEip2930, TxKind, TxLegacy,
+    Withdrawals, B256, U256,
+};
+use reth_testing_utils::generators::{generate_keys, sign_tx_with_key_pair};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The base fee of generated blocks.
+pub const BASE_FEE: u64 = 7;
+
+/// The number of funded externally owned accounts.
+const SENDERS: usize = 4;
+
+/// The maximum number of transactions in a generated block.
+const MAX_TRANSACTIONS: usize = 16;
+
+/// Stores the first calldata word at the slot given by the second calldata word.
+const STORAGE_WRITER: &[u8] = &[0x60, 0x00, 0x35, 0x60, 0x20, 0x35, 0x55, 0x00];
+
+/// Emits the calldata as a log with a single topic.
+const LOGGER: &[u8] =
+    &[0x36, 0x60, 0x00, 0x60, 0x00, 0x37, 0x60, 0x2a, 0x36, 0x60, 0x00, 0xa1, 0x00];
+
+/// Writes a storage slot and reverts.
+const REVERTER: &[u8] = &[0x60, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x60, 0x00, 0xfd];
+
+/// Forwards the call value to the address in the first calldata word.
+const FORWARDER: &[u8] =
+    &[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x34, 0x60, 0x00, 0x35, 0x5a, 0xf1, 0x00];
+
+/// Self-destructs to the address in the first calldata word.
+const SELFDESTRUCT: &[u8] = &[0x60, 0x00, 0x35, 0xff];
+
+/// The runtime code of the contracts in the pre-state.
+const CONTRACTS: &[&[u8]] = &[STORAGE_WRITER, LOGGER, REVERTER, FORWARDER, SELFDESTRUCT];
+
+/// An account of the pre-state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreAccount {
+    /// Account balance.
+    pub balance: U256,
+    /// Account nonce.
+    pub nonce: u64,
+    /// Runtime code, empty for externally owned accounts.
+    pub code: Bytes,
+    /// Non-zero storage slots.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// A randomly generated block together with the state it is executed on.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    /// The seed the scenario was generated from.
+    pub seed: u64,
+    /// The chain specification the block is executed with.
+    pub chain_spec: Arc<ChainSpec>,
+    /// The state before the block.
+    pub pre: BTreeMap<Address, PreAccount>,
+    /// The block with recovered senders.
+    pub block: BlockWithSenders,
+}
+
+impl Scenario {
+    /// Deterministically generates a scenario from the given seed.
+    ///
+    /// The block contains legacy, EIP-2930 and EIP-1559 transactions of funded accounts that
+    /// transfer value, create contracts and call contracts which write storage, emit logs,
+    /// revert, forward value and self-destruct.
+    pub fn generate(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build());
+
+        let mut pre = BTreeMap::new();
+        let keys = generate_keys(&mut rng, SENDERS);
+        let senders =
+            keys.iter().map(|key| public_key_to_address(key.public_key())).collect::<Vec<_>>();
+        for sender in &senders {
+            let balance = U256::from(rng.gen_range(10u64.pow(17)..=10u64.pow(18)));
+            pre.insert(*sender, PreAccount { balance, ..Default::default() });
+        }
+
+        let mut contracts = Vec::with_capacity(CONTRACTS.len());
+        for code in CONTRACTS {
+            let address = rng.gen();
+            let storage = (0..rng.gen_range(0..3))
+                .map(|_| (U256::from(rng.gen_range(0u8..4)), U256::from(rng.gen_range(1u8..=255))))
+                .collect();
+            pre.insert(
+                address,
+                PreAccount {
+                    balance: U256::from(rng.gen_range(0u64..1_000_000)),
+                    nonce: 1,
+                    code: Bytes::from_static(code),
+                    storage,
+                },
+            );
+            contracts.push(address);
+        }
+
+        // recipients also include precompiles and accounts that don't exist yet
+        let mut recipients = senders.clone();
+        recipients.extend(&contracts);
+        recipients.push(Address::with_last_byte(rng.gen_range(1..=9)));
+        recipients.push(rng.gen());
+
+        let mut nonces = vec![0u64; SENDERS];
+        let mut body = Vec::new();
+        let mut block_senders = Vec::new();
+        for _ in 0..rng.gen_range(1..=MAX_TRANSACTIONS) {
+            let index = rng.gen_range(0..SENDERS);
+            let nonce = nonces[index];
+            nonces[index] += 1;
+
+            let (to, input) = match rng.gen_range(0..10) {
+                0..=1 => (TxKind::Call(*recipients.choose(&mut rng).unwrap()), Bytes::new()),
+                2 => (TxKind::Create, init_code(CONTRACTS.choose(&mut rng).unwrap())),
+                _ => {
+                    let mut input = [0u8; 64];
+                    if rng.gen_bool(0.5) {
+                        let recipient = recipients.choose(&mut rng).unwrap();
+                        input[12..32].copy_from_slice(recipient.as_slice());
+                    } else {
+                        input[31] = rng.gen();
+                    }
+                    input[63] = rng.gen_range(0..4);
+                    let calldata = &input[..rng.gen_range(0..=64)];
+                    (
+                        TxKind::Call(*contracts.choose(&mut rng).unwrap()),
+                        Bytes::copy_from_slice(calldata),
+                    )
+                }
+            };
+            let transaction = random_transaction(&mut rng, nonce, to, input, &contracts);
+
+            body.push(sign_tx_with_key_pair(keys[index], transaction));
+            block_senders.push(senders[index]);
+        }
+
+        let header = Header {
+            number: 1,
+            timestamp: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(BASE_FEE),
+            beneficiary: rng.gen(),
+            mix_hash: rng.gen(),
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            ..Default::default()
+        };
+        let block = Block {
+            header,
+            body,
+            ommers: Vec::new(),
+            withdrawals: Some(Withdrawals::default()),
+            requests: None,
+        };
+
+        Self { seed, chain_spec, pre, block: BlockWithSenders { block, senders: block_senders } }
+    }
+}
+
+/// Returns a random legacy, EIP-2930 or EIP-1559 transaction that pays at least the base fee.
+fn random_transaction<R: Rng>(
+    rng: &mut R,
+    nonce: u64,
+    to: TxKind,
+    input: Bytes,
+    contracts: &[Address],
+) -> Transaction {
+    let gas_limit = rng.gen_range(60_000..300_000);
+    let value = U256::from(rng.gen_range(0u64..1_000_000));
+    let chain_id = 1;
+
+    match rng.gen_range(0..3) {
+        0 => Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: rng.gen_range(BASE_FEE as u128..30),
+            gas_limit,
+            to,
+            value,
+            input,
+        }),
+        1 => Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price: rng.gen_range(BASE_FEE as u128..30),
+            gas_limit,
+            to,
+            value,
+            access_list: random_access_list(rng, contracts),
+            input,
+        }),
+        _ => {
+            let max_fee_per_gas = rng.gen_range(BASE_FEE as u128..30);
+            Transaction::Eip1559(TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas: rng.gen_range(0..=max_fee_per_gas),
+                to,
+                value,
+                access_list: random_access_list(rng, contracts),
+                input,
+            })
+        }
+    }
+}
+
+/// Returns an access list of random contracts and slots.
+fn random_access_list<R: Rng>(rng: &mut R, contracts: &[Address]) -> AccessList {
+    AccessList(
+        (0..rng.gen_range(0..3))
+            .map(|_| AccessListItem {
+                address: *contracts.choose(rng).unwrap(),
+                storage_keys: (0..rng.gen_range(0..3))
+                    .map(|_| B256::with_last_byte(rng.gen_range(0..4)))
+                    .collect(),
+            })
+            .collect(),
+    )
+}
+
+/// Returns init code that deploys the given runtime code.
+fn init_code(runtime: &[u8]) -> Bytes {
+    let len = runtime.len() as u8;
+    // CODECOPY the runtime code that follows the 12 bytes of init code and RETURN it
+    let mut code = vec![0x60, len, 0x60, 0x0c, 0x60, 0x00, 0x39, 0x60, len, 0x60, 0x00, 0xf3];
+    code.extend_from_slice(runtime);
+    code.into()
+}
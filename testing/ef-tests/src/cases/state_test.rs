@@ -0,0 +1,203 @@
+//! Test runners for `GeneralStateTests` in <https://github.com/ethereum/tests>
+
+use crate::{
+    assert::assert_equal,
+    cases::blockchain_test::should_skip,
+    models::{ForkSpec, State, StateTest, StateTestPost},
+    Case, Error, Suite,
+};
+use reth_chainspec::ChainSpec;
+use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_primitives::{keccak256, Header, TransactionSigned, B256, U256};
+use reth_revm::{
+    db::{AccountState, CacheDB, EmptyDB},
+    primitives::{
+        AccountInfo, BlockEnv, Bytecode, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId,
+        TxEnv, KECCAK_EMPTY,
+    },
+};
+use reth_trie_common::root::{state_root_unhashed, storage_root_unhashed};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// A handler for the state test suite.
+#[derive(Debug)]
+pub struct StateTests {
+    suite: String,
+}
+
+impl StateTests {
+    /// Create a new handler for a subset of the state test suite.
+    pub const fn new(suite: String) -> Self {
+        Self { suite }
+    }
+}
+
+impl Suite for StateTests {
+    type Case = StateTestCase;
+
+    fn suite_name(&self) -> String {
+        format!("GeneralStateTests/{}", self.suite)
+    }
+}
+
+/// An Ethereum state test.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateTestCase {
+    tests: BTreeMap<String, StateTest>,
+    skip: bool,
+}
+
+impl Case for StateTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            skip: should_skip(path),
+        })
+    }
+
+    /// Executes the transaction of every expected result on top of the pre-state and compares
+    /// the resulting state root and logs hash.
+    ///
+    /// # Errors
+    /// Returns an error if the test is flagged for skipping or a result doesn't match.
+    fn run(&self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped)
+        }
+
+        for (name, test) in &self.tests {
+            for (fork, posts) in &test.post {
+                if matches!(
+                    fork,
+                    ForkSpec::ByzantiumToConstantinopleAt5 |
+                        ForkSpec::Constantinople |
+                        ForkSpec::MergeEOF |
+                        ForkSpec::MergeMeterInitCode |
+                        ForkSpec::MergePush0 |
+                        ForkSpec::Unknown
+                ) {
+                    continue
+                }
+
+                let chain_spec: ChainSpec = fork.clone().into();
+                let header: Header = test.env.clone().into();
+                for post in posts {
+                    run_post(&chain_spec, &header, &test.pre, post).map_err(|err| match err {
+                        Error::Skipped => err,
+                        err => Error::Assertion(format!(
+                            "{name} ({fork:?}, data {}, gas {}, value {}): {err}",
+                            post.indexes.data, post.indexes.gas, post.indexes.value
+                        )),
+                    })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the transaction of the expected result with reth's EVM configuration and checks the
+/// resulting state root and logs hash.
+fn run_post(
+    chain_spec: &ChainSpec,
+    header: &Header,
+    pre: &State,
+    post: &StateTestPost,
+) -> Result<(), Error> {
+    let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+    let mut block_env = BlockEnv::default();
+    <EthEvmConfig>::fill_cfg_and_block_env(
+        &mut cfg,
+        &mut block_env,
+        chain_spec,
+        header,
+        U256::ZERO,
+    );
+    let spec_id = cfg.handler_cfg.spec_id;
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (&address, account) in pre.iter() {
+        let code_hash =
+            if account.code.is_empty() { KECCAK_EMPTY } else { keccak256(&account.code) };
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce.to(),
+                code_hash,
+                code: Some(Bytecode::new_raw(account.code.clone())),
+            },
+        );
+        for (slot, value) in &account.storage {
+            db.insert_account_storage(address, *slot, *value)
+                .expect("in-memory database does not fail");
+        }
+    }
+
+    let Some(txbytes) = &post.txbytes else { return Err(Error::Skipped) };
+
+    // A transaction that can't be decoded or recovered is only valid if an exception is expected
+    let transaction = match TransactionSigned::decode_enveloped(&mut txbytes.as_ref()) {
+        Ok(transaction) => transaction,
+        Err(_) if post.expect_exception.is_some() => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let Some(sender) = transaction.recover_signer() else {
+        if post.expect_exception.is_some() {
+            return Ok(())
+        }
+        return Err(Error::Assertion("invalid transaction signature".to_string()))
+    };
+
+    let evm_config = EthEvmConfig::default();
+    let mut tx_env = TxEnv::default();
+    evm_config.fill_tx_env(&mut tx_env, &transaction, sender);
+
+    let mut evm = evm_config
+        .evm_with_env(&mut db, EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env));
+    let logs = match (evm.transact_commit(), &post.expect_exception) {
+        (Ok(result), None) => result.into_logs(),
+        (Ok(_), Some(exception)) => {
+            return Err(Error::Assertion(format!(
+                "expected exception {exception}, but the transaction is valid"
+            )))
+        }
+        (Err(_), Some(_)) => Vec::new(),
+        (Err(err), None) => return Err(Error::Assertion(format!("transaction is invalid: {err}"))),
+    };
+    drop(evm);
+
+    let state_root =
+        state_root_unhashed(db.accounts.into_iter().filter_map(|(address, account)| {
+            if account.account_state == AccountState::NotExisting {
+                return None
+            }
+            // EIP-161: touched empty accounts are removed from the state
+            if spec_id >= SpecId::SPURIOUS_DRAGON &&
+                account.account_state != AccountState::None &&
+                account.info.is_empty()
+            {
+                return None
+            }
+            let storage_root = storage_root_unhashed(
+                account
+                    .storage
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (B256::from(slot), value)),
+            );
+            Some((address, (account.info, storage_root)))
+        }));
+
+    assert_equal(post.hash, state_root, "State root does not match")?;
+    assert_equal(post.logs, keccak256(alloy_rlp::encode(logs)), "Logs hash does not match")?;
+
+    Ok(())
+}
@@ -17,4 +17,4 @@ pub mod models;
 
 pub use case::{Case, Cases};
 pub use result::{CaseResult, Error};
-pub use suite::Suite;
+pub use suite::{run_tests_in, Suite, TestKind};
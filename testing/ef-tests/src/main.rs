@@ -0,0 +1,35 @@
+//! Runs the state and blockchain tests of the execution spec tests found in the given directory
+//! through reth's executor and reports the tests that don't match.
+//!
+//! Usage: `ef-tests <PATH>`
+//!
+//! This is a separate binary instead of a `reth` subcommand, since the tests run on a database
+//! with the test utilities and without the storage lock, which must not end up in the node.
+
+use ef_tests::{
+    result::{categorize_results, print_results},
+    run_tests_in,
+};
+use std::{path::PathBuf, process::ExitCode};
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("Usage: ef-tests <PATH>");
+        return ExitCode::FAILURE
+    };
+    if !path.exists() {
+        eprintln!("Test fixtures path does not exist: {}", path.display());
+        return ExitCode::FAILURE
+    }
+
+    let results = run_tests_in(&path);
+    let (passed, failed, skipped) = categorize_results(&results);
+    print_results("ethereum-tests", &path, &passed, &failed, &skipped);
+
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{} of {} tests failed", failed.len(), results.len());
+        ExitCode::FAILURE
+    }
+}
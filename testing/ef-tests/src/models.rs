@@ -119,6 +119,83 @@ impl From<Header> for SealedHeader {
     }
 }
 
+/// The definition of a state test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTest {
+    /// Environment of the block the transaction is executed in.
+    pub env: StateTestEnv,
+    /// The test pre-state.
+    pub pre: State,
+    /// The expected results for each fork.
+    pub post: BTreeMap<ForkSpec, Vec<StateTestPost>>,
+}
+
+/// The block environment of a state test.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestEnv {
+    /// Coinbase.
+    pub current_coinbase: Address,
+    /// Difficulty.
+    pub current_difficulty: U256,
+    /// Gas limit.
+    pub current_gas_limit: U256,
+    /// Block number.
+    pub current_number: U256,
+    /// Timestamp.
+    pub current_timestamp: U256,
+    /// Base fee per gas.
+    pub current_base_fee: Option<U256>,
+    /// Prevrandao.
+    pub current_random: Option<B256>,
+    /// Excess blob gas.
+    pub current_excess_blob_gas: Option<U256>,
+}
+
+impl From<StateTestEnv> for RethHeader {
+    fn from(env: StateTestEnv) -> Self {
+        Self {
+            beneficiary: env.current_coinbase,
+            difficulty: env.current_difficulty,
+            gas_limit: env.current_gas_limit.to::<u64>(),
+            number: env.current_number.to::<u64>(),
+            timestamp: env.current_timestamp.to::<u64>(),
+            base_fee_per_gas: env.current_base_fee.map(|v| v.to::<u64>()),
+            mix_hash: env.current_random.unwrap_or_default(),
+            excess_blob_gas: env.current_excess_blob_gas.map(|v| v.to::<u64>()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The expected result of one transaction of a state test.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestPost {
+    /// The expected post state root.
+    pub hash: B256,
+    /// The expected hash of the RLP encoded logs.
+    pub logs: B256,
+    /// The indexes of the data, gas limit and value of the transaction.
+    pub indexes: StateTestIndexes,
+    /// The encoded transaction.
+    pub txbytes: Option<Bytes>,
+    /// The exception the transaction is expected to fail with.
+    pub expect_exception: Option<String>,
+}
+
+/// The indexes into the transaction fields of a state test.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+pub struct StateTestIndexes {
+    /// Index of the data.
+    pub data: usize,
+    /// Index of the gas limit.
+    pub gas: usize,
+    /// Index of the value.
+    pub value: usize,
+}
+
 /// A block in an Ethereum blockchain test.
 #[derive(Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -294,6 +371,7 @@ pub enum ForkSpec {
     /// London
     London,
     /// Paris aka The Merge
+    #[serde(alias = "Paris")]
     Merge,
     /// Shanghai
     Shanghai,
@@ -435,6 +513,45 @@ mod tests {
         assert!(res.is_ok(), "Failed to deserialize Header with error: {res:?}");
     }
 
+    #[test]
+    fn state_test_deserialize() {
+        let test = r#"{
+            "env" : {
+                "currentBaseFee" : "0x0a",
+                "currentCoinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+                "currentDifficulty" : "0x020000",
+                "currentExcessBlobGas" : "0x00",
+                "currentGasLimit" : "0x05f5e100",
+                "currentNumber" : "0x01",
+                "currentRandom" : "0x0000000000000000000000000000000000000000000000000000000000020000",
+                "currentTimestamp" : "0x03e8"
+            },
+            "pre" : {
+                "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b" : {
+                    "balance" : "0x0de0b6b3a7640000",
+                    "code" : "0x",
+                    "nonce" : "0x00",
+                    "storage" : {}
+                }
+            },
+            "post" : {
+                "Cancun" : [
+                    {
+                        "hash" : "0x1a7bc1b9f6d3b8a8b4fa1b1ee29b2e1a04fd17ba5d4fbfbd2d0bdc1e9d2e1f8b",
+                        "logs" : "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                        "indexes" : { "data" : 0, "gas" : 0, "value" : 0 },
+                        "txbytes" : "0x"
+                    }
+                ],
+                "Prague" : []
+            },
+            "transaction" : {}
+        }"#;
+        let test = serde_json::from_str::<StateTest>(test).unwrap();
+        assert_eq!(test.post[&ForkSpec::Cancun].len(), 1);
+        assert!(test.post.contains_key(&ForkSpec::Unknown));
+    }
+
     #[test]
     fn transaction_deserialize() {
         let test = r#"[
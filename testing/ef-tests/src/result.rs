@@ -80,7 +80,7 @@ pub(crate) fn assert_tests_pass(suite_name: &str, path: &Path, results: &[CaseRe
 }
 
 /// Categorize test results into `(passed, failed, skipped)`.
-pub(crate) fn categorize_results(
+pub fn categorize_results(
     results: &[CaseResult],
 ) -> (Vec<&CaseResult>, Vec<&CaseResult>, Vec<&CaseResult>) {
     let mut passed = Vec::new();
@@ -99,7 +99,7 @@ pub(crate) fn categorize_results(
 }
 
 /// Display the given test results to stdout.
-pub(crate) fn print_results(
+pub fn print_results(
     suite_name: &str,
     path: &Path,
     passed: &[&CaseResult],
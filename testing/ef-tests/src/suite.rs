@@ -2,9 +2,14 @@
 
 use crate::{
     case::{Case, Cases},
-    result::assert_tests_pass,
+    cases::{blockchain_test::BlockchainTestCase, state_test::StateTestCase},
+    result::{assert_tests_pass, CaseResult},
+    Error,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
 };
-use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
 /// A collection of tests.
@@ -52,6 +57,69 @@ pub trait Suite {
     }
 }
 
+/// Loads and runs every blockchain and state test found recursively in the given path.
+///
+/// The kind of each test is detected from the contents of its file, JSON files that are neither
+/// blockchain nor state tests are ignored.
+pub fn run_tests_in(path: &Path) -> Vec<CaseResult> {
+    let mut results = Vec::new();
+    for test_case_path in find_all_files_with_extension(path, ".json") {
+        let result = match TestKind::detect(&test_case_path) {
+            Ok(Some(TestKind::Blockchain)) => run_case::<BlockchainTestCase>(&test_case_path),
+            Ok(Some(TestKind::State)) => run_case::<StateTestCase>(&test_case_path),
+            Ok(None) => continue,
+            Err(error) => CaseResult {
+                desc: "no description".to_string(),
+                path: test_case_path,
+                result: Err(error),
+            },
+        };
+        results.push(result);
+    }
+    results
+}
+
+/// Loads and runs a single test case.
+fn run_case<C: Case>(path: &Path) -> CaseResult {
+    match C::load(path) {
+        Ok(case) => CaseResult::new(path, &case, case.run()),
+        Err(error) => {
+            CaseResult { desc: "no description".to_string(), path: path.into(), result: Err(error) }
+        }
+    }
+}
+
+/// The kinds of tests that can be run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestKind {
+    /// Blockchain tests, see [`BlockchainTestCase`].
+    Blockchain,
+    /// State tests, see [`StateTestCase`].
+    State,
+}
+
+impl TestKind {
+    /// Detects the kind of the tests in the given file from the fields of its first test.
+    ///
+    /// Returns `None` if the file doesn't contain blockchain or state tests.
+    pub fn detect(path: &Path) -> Result<Option<Self>, Error> {
+        let s = fs::read_to_string(path).map_err(|error| Error::Io { path: path.into(), error })?;
+        let tests: serde_json::Value = serde_json::from_str(&s)
+            .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?;
+
+        let Some(test) = tests.as_object().and_then(|tests| tests.values().next()) else {
+            return Ok(None)
+        };
+        if test.get("blocks").is_some() {
+            Ok(Some(Self::Blockchain))
+        } else if test.get("transaction").is_some() && test.get("post").is_some() {
+            Ok(Some(Self::State))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// Recursively find all files with a given extension.
 fn find_all_files_with_extension(path: &Path, extension: &str) -> Vec<PathBuf> {
     WalkDir::new(path)